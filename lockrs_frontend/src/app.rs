@@ -19,6 +19,9 @@ pub fn App(cx: Scope) -> impl IntoView {
                     <Route path="" view=  move |cx| view! { cx, <HomePage /> }/>
                     <Route path="/login" view= move |cx| view! { cx, <LoginPage /> }/>
                     <Route path="/register" view= move |cx| view! { cx, <RegisterPage /> }/>
+                    <Route path="/consent" view= move |cx| view! { cx, <ConsentPage /> }/>
+                    <Route path="/password-reset" view= move |cx| view! { cx, <PasswordResetPage /> }/>
+                    <Route path="/password-reset/confirm" view= move |cx| view! { cx, <PasswordResetConfirmPage /> }/>
                     // <Route path="/logout" view= move |cx| view! { cx, <LogoutLayout /> }>
                         // <Route path="/success" view= move |cx| view! { cx, <LogoutSuccessPage /> }/>
                         // <Route path="" view=move |cx| view! { cx, <LogoutConfirmationPage /> }/>
@@ -1,2 +1,5 @@
 pub mod login_form;
+pub mod passkey_login_button;
+pub mod password_reset_form;
+pub mod password_reset_request_form;
 pub mod register_form;
@@ -0,0 +1,255 @@
+use base64::{engine::general_purpose, Engine as _};
+use js_sys::{Array, ArrayBuffer, Uint8Array};
+use leptos::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    AuthenticatorAssertionResponse, CredentialRequestOptions, PublicKeyCredential,
+    PublicKeyCredentialDescriptor, PublicKeyCredentialRequestOptions, PublicKeyCredentialType,
+    Request, RequestCredentials, RequestInit, Response, UserVerificationRequirement,
+};
+
+use crate::components::ui::button::*;
+
+const LOGIN_OPTIONS_URL: &str = "/api/v1/auth/webauthn/login/options";
+const LOGIN_URL: &str = "/api/v1/auth/webauthn/login";
+const SESSIONS_URL: &str = "/api/v1/sessions";
+
+#[derive(Debug, Error)]
+enum PasskeyLoginError {
+    #[error("Passkeys are not supported by this browser")]
+    Unsupported,
+    #[error("Sign in with a passkey was cancelled")]
+    Cancelled,
+    #[error("The passkey was not recognised")]
+    Rejected,
+    #[error("Something went wrong, please try again")]
+    Unexpected,
+}
+
+impl From<JsValue> for PasskeyLoginError {
+    fn from(err: JsValue) -> Self {
+        log::error!("{:?}", err);
+        Self::Unexpected
+    }
+}
+
+#[derive(Deserialize)]
+struct LoginOptions {
+    challenge_id: String,
+    public_key: RequestOptions,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RequestOptions {
+    challenge: String,
+    timeout: u32,
+    rp_id: String,
+    allow_credentials: Vec<CredentialDescriptor>,
+    user_verification: String,
+}
+
+#[derive(Deserialize)]
+struct CredentialDescriptor {
+    id: String,
+}
+
+#[derive(Serialize)]
+struct LoginRequest {
+    challenge_id: String,
+    credential_id: String,
+    client_data_json: String,
+    authenticator_data: String,
+    signature: String,
+}
+
+#[derive(Deserialize)]
+struct SessionToken {
+    session_token: String,
+    mfa_required: bool,
+}
+
+/// Signs the user in without a password, running the authentication ceremony against
+/// `/api/v1/auth/webauthn` and starting a session with the token it returns.
+#[component]
+pub fn PasskeyLoginButton(cx: Scope, #[prop(optional)] class: Option<String>) -> impl IntoView {
+    let (pending, set_pending) = create_signal(cx, false);
+    let (error, set_error) = create_signal(cx, None::<String>);
+
+    let sign_in = move |ev: ev::MouseEvent| {
+        ev.prevent_default();
+
+        if pending.get_untracked() {
+            return;
+        }
+
+        set_pending(true);
+        set_error(None);
+
+        spawn_local(async move {
+            match sign_in_with_passkey().await {
+                Ok(()) => {
+                    let _ = window().location().set_href("/");
+                }
+                Err(err) => set_error(Some(err.to_string())),
+            }
+
+            set_pending(false);
+        });
+    };
+
+    view! { cx,
+        <>
+            <Button
+                class=class.unwrap_or_default()
+                variant=ButtonVariant::Outline
+                on:click=sign_in
+            >
+                {move || if pending() { "Waiting for your passkey..." } else { "Sign in with a passkey" }}
+            </Button>
+            <Show
+                when=move || error().is_some()
+                fallback=|_| ()
+            >
+                <p class="text-sm font-medium text-destructive mt-2">{move || error().unwrap_or_default()}</p>
+            </Show>
+        </>
+    }
+}
+
+async fn sign_in_with_passkey() -> Result<(), PasskeyLoginError> {
+    let options = post_json::<LoginOptions>(LOGIN_OPTIONS_URL, None, None)
+        .await?
+        .ok_or(PasskeyLoginError::Unexpected)?;
+
+    let credential = get_credential(&options.public_key).await?;
+    let assertion = credential
+        .response()
+        .dyn_into::<AuthenticatorAssertionResponse>()
+        .map_err(|_| PasskeyLoginError::Unexpected)?;
+
+    let login_request = LoginRequest {
+        challenge_id: options.challenge_id,
+        credential_id: encode(&credential.raw_id()),
+        client_data_json: encode(&assertion.client_data_json()),
+        authenticator_data: encode(&assertion.authenticator_data()),
+        signature: encode(&assertion.signature()),
+    };
+    let body = serde_json::to_string(&login_request).map_err(|_| PasskeyLoginError::Unexpected)?;
+
+    let session_token = post_json::<SessionToken>(LOGIN_URL, Some(body), None)
+        .await?
+        .ok_or(PasskeyLoginError::Rejected)?;
+
+    // a passkey verifies the user, so a passwordless login is never left waiting on a second factor
+    if session_token.mfa_required {
+        return Err(PasskeyLoginError::Unexpected);
+    }
+
+    // the server answers with the session cookies
+    post_json::<serde_json::Value>(
+        SESSIONS_URL,
+        None,
+        Some(session_token.session_token.as_str()),
+    )
+    .await?
+    .ok_or(PasskeyLoginError::Unexpected)?;
+
+    Ok(())
+}
+
+/// Asks the browser for an assertion over the challenge, prompting the user for their passkey.
+async fn get_credential(
+    options: &RequestOptions,
+) -> Result<PublicKeyCredential, PasskeyLoginError> {
+    let allow_credentials = Array::new();
+    for credential in options.allow_credentials.iter() {
+        let id = decode(credential.id.as_str())?;
+        allow_credentials.push(&PublicKeyCredentialDescriptor::new(
+            &id,
+            PublicKeyCredentialType::PublicKey,
+        ));
+    }
+
+    let user_verification = match options.user_verification.as_str() {
+        "required" => UserVerificationRequirement::Required,
+        "discouraged" => UserVerificationRequirement::Discouraged,
+        _ => UserVerificationRequirement::Preferred,
+    };
+
+    let mut public_key = PublicKeyCredentialRequestOptions::new(&decode(&options.challenge)?);
+    public_key
+        .rp_id(options.rp_id.as_str())
+        .timeout(options.timeout)
+        .user_verification(user_verification)
+        .allow_credentials(&allow_credentials);
+
+    let mut request_options = CredentialRequestOptions::new();
+    request_options.public_key(&public_key);
+
+    let credentials = window().navigator().credentials();
+    let promise = credentials
+        .get_with_options(&request_options)
+        .map_err(|_| PasskeyLoginError::Unsupported)?;
+
+    // the browser rejects the promise when the user dismisses the prompt or it times out
+    let credential = JsFuture::from(promise)
+        .await
+        .map_err(|_| PasskeyLoginError::Cancelled)?;
+
+    credential
+        .dyn_into::<PublicKeyCredential>()
+        .map_err(|_| PasskeyLoginError::Cancelled)
+}
+
+/// Posts `body` to the api, returning the parsed body of a successful response and `None` for any
+/// other.
+async fn post_json<T: DeserializeOwned>(
+    url: &str,
+    body: Option<String>,
+    bearer_token: Option<&str>,
+) -> Result<Option<T>, PasskeyLoginError> {
+    let mut init = RequestInit::new();
+    init.method("POST")
+        .credentials(RequestCredentials::SameOrigin)
+        .body(body.map(|body| JsValue::from_str(body.as_str())).as_ref());
+
+    let request = Request::new_with_str_and_init(url, &init)?;
+    request.headers().set("Content-Type", "application/json")?;
+    if let Some(bearer_token) = bearer_token {
+        request
+            .headers()
+            .set("Authorization", format!("Bearer {}", bearer_token).as_str())?;
+    }
+
+    let response = JsFuture::from(window().fetch_with_request(&request))
+        .await?
+        .dyn_into::<Response>()?;
+
+    if !response.ok() {
+        return Ok(None);
+    }
+
+    let text = JsFuture::from(response.text()?)
+        .await?
+        .as_string()
+        .unwrap_or_default();
+
+    serde_json::from_str(text.as_str())
+        .map(Some)
+        .map_err(|_| PasskeyLoginError::Unexpected)
+}
+
+fn encode(buffer: &ArrayBuffer) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(Uint8Array::new(buffer).to_vec())
+}
+
+fn decode(value: &str) -> Result<Uint8Array, PasskeyLoginError> {
+    general_purpose::URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map(|bytes| Uint8Array::from(bytes.as_slice()))
+        .map_err(|_| PasskeyLoginError::Unexpected)
+}
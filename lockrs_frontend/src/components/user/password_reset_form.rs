@@ -0,0 +1,91 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use validify::Validify;
+
+use crate::components::ui::button::*;
+use crate::components::ui::form::*;
+use crate::components::ui::input::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validify)]
+pub struct PasswordResetFormSchema {
+    pub token: String,
+    #[modify(trim)]
+    #[validate(length(
+        min = 1,
+        message = "Please enter a password",
+        code = "INVALID_PASSWORD"
+    ))]
+    #[validate(length(
+        min = 8,
+        message = "Password must be at least 8 characters long",
+        code = "INVALID_PASSWORD"
+    ))]
+    pub password: String,
+}
+
+impl PasswordResetFormSchema {
+    pub fn new(token: String, password: String) -> Self {
+        Self { token, password }
+    }
+}
+
+/// Sets a new password with the token from a reset link.
+#[component]
+pub fn PasswordResetForm(
+    cx: Scope,
+    #[prop(into)] token: Signal<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let class = format!(
+        "grid gap-6 w-full {}",
+        if let Some(c) = class { c } else { "" }
+    );
+
+    let (password, set_password) = create_signal(cx, String::new());
+
+    let schema = Signal::derive(cx, move || {
+        PasswordResetFormSchema::new(token(), password())
+    });
+
+    view! { cx,
+        <div id="password-reset-form" class=class.clone()>
+            <Form
+                validator=schema
+            >
+                <form class="space-y-4">
+                    <FormField
+                        name="password"
+                    >
+                        <FormItem>
+                            <FormLabel>New Password</FormLabel>
+                            <FormControl>
+                                <Input
+                                    id="password"
+                                    placeholder="●●●●●●●●"
+                                    input_type="password"
+                                    autocomplete="new-password"
+                                    autocorrect="off"
+                                    value=password
+                                    on:input=move |ev| {
+                                        set_password(event_target_value(&ev));
+                                    }
+                                />
+                            </FormControl>
+                            <FormDescription>
+                                This will replace your current password, and log you out everywhere
+                            </FormDescription>
+                            <FormMessage />
+                        </FormItem>
+                    </FormField>
+                    <Button
+                        on:click=move |ev| {
+                            ev.prevent_default();
+                        }
+                    >
+                        Reset Password
+                    </Button>
+                </form>
+            </Form>
+        </div>
+    }
+}
@@ -0,0 +1,79 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use validify::Validify;
+
+use crate::components::ui::button::*;
+use crate::components::ui::form::*;
+use crate::components::ui::input::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validify)]
+pub struct PasswordResetRequestFormSchema {
+    #[modify(trim)]
+    #[validate(length(min = 1, message = "Please enter an email", code = "INVALID_EMAIL"))]
+    #[validate(email(message = "Invalid email", code = "INVALID_EMAIL"))]
+    pub email: String,
+}
+
+impl PasswordResetRequestFormSchema {
+    pub fn new(email: String) -> Self {
+        Self { email }
+    }
+}
+
+#[component]
+pub fn PasswordResetRequestForm(
+    cx: Scope,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let class = format!(
+        "grid gap-6 w-full {}",
+        if let Some(c) = class { c } else { "" }
+    );
+
+    let (email, set_email) = create_signal(cx, String::new());
+
+    let schema = Signal::derive(cx, move || PasswordResetRequestFormSchema::new(email()));
+
+    view! { cx,
+        <div id="password-reset-request-form" class=class.clone()>
+            <Form
+                validator=schema
+            >
+                <form class="space-y-4">
+                    <FormField
+                        name="email"
+                    >
+                        <FormItem>
+                            <FormLabel>Email</FormLabel>
+                            <FormControl>
+                                <Input
+                                    id="email"
+                                    placeholder="name@example.com"
+                                    input_type="email"
+                                    autocapitalize="none"
+                                    autocomplete="email"
+                                    autocorrect="off"
+                                    value=email
+                                    on:input=move |ev| {
+                                        set_email(event_target_value(&ev));
+                                    }
+                                />
+                            </FormControl>
+                            <FormDescription>
+                                Enter your account email
+                            </FormDescription>
+                            <FormMessage />
+                        </FormItem>
+                    </FormField>
+                    <Button
+                        on:click=move |ev| {
+                            ev.prevent_default();
+                        }
+                    >
+                        Send Reset Link
+                    </Button>
+                </form>
+            </Form>
+        </div>
+    }
+}
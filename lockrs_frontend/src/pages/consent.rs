@@ -0,0 +1,150 @@
+use leptos::*;
+use leptos_router::use_query_map;
+use serde_json::{Map, Value};
+
+use crate::components::ui::button::*;
+use crate::components::ui::card::*;
+use crate::components::ui::separator::*;
+
+/// One authorization detail the client asked for, split into its type and the fields describing
+/// the transaction.
+#[derive(Clone, Debug, PartialEq)]
+struct AuthorizationDetail {
+    detail_type: String,
+    fields: Vec<(String, String)>,
+}
+
+impl AuthorizationDetail {
+    /// Reads the `authorization_details` the server returned with its consent prompt, skipping
+    /// anything that is not an object carrying a type.
+    fn from_json(authorization_details: &str) -> Vec<Self> {
+        let Ok(Value::Array(details)) = serde_json::from_str::<Value>(authorization_details)
+        else {
+            return Vec::new();
+        };
+
+        details
+            .into_iter()
+            .filter_map(|detail| match detail {
+                Value::Object(fields) => Self::from_fields(fields),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn from_fields(mut fields: Map<String, Value>) -> Option<Self> {
+        let Some(Value::String(detail_type)) = fields.remove("type")
+        else {
+            return None;
+        };
+
+        let fields = fields
+            .into_iter()
+            .map(|(name, value)| match value {
+                Value::String(value) => (name, value),
+                value => (name, value.to_string()),
+            })
+            .collect();
+
+        Some(Self {
+            detail_type,
+            fields,
+        })
+    }
+}
+
+/// Where the user lands when `/authorize` answers with a consent prompt, carrying the client, the
+/// scopes and the authorization details it asked for in its query.
+#[component]
+pub fn ConsentPage(cx: Scope) -> impl IntoView {
+    let query = use_query_map(cx);
+    let client_id = Signal::derive(cx, move || {
+        query.with(|query| query.get("client_id").cloned().unwrap_or_default())
+    });
+    let scopes = Signal::derive(cx, move || {
+        query.with(|query| {
+            query
+                .get("scope")
+                .map(|scope| scope.split_whitespace().map(String::from).collect())
+                .unwrap_or_else(Vec::<String>::new)
+        })
+    });
+    let authorization_details = Signal::derive(cx, move || {
+        query.with(|query| {
+            query
+                .get("authorization_details")
+                .map(|details| AuthorizationDetail::from_json(details))
+                .unwrap_or_default()
+        })
+    });
+
+    view! { cx,
+        <div id="consent-page" class="relative h-full flex-col items-center justify-center">
+            <div class="flex flex-col justify-center items-center h-full">
+                <Card>
+                    <CardHeader>
+                        <CardTitle>Authorize Application</CardTitle>
+                        <CardDescription>
+                            {move || format!("{} is requesting access to your account", client_id())}
+                        </CardDescription>
+                    </CardHeader>
+                    <CardContent>
+                        <h4 class="text-sm font-medium leading-none mb-2">Permissions</h4>
+                        <ul class="list-disc pl-6 text-sm text-muted-foreground">
+                            <For
+                                each=scopes
+                                key=|scope| scope.clone()
+                                view=move |cx, scope| view! { cx, <li>{scope}</li> }
+                            />
+                        </ul>
+                        <Show
+                            when=move || !authorization_details().is_empty()
+                            fallback=|_| ()
+                        >
+                            <Separator class="my-4" />
+                            <h4 class="text-sm font-medium leading-none mb-2">Transactions</h4>
+                            <For
+                                each=authorization_details
+                                key=|detail| format!("{:?}", detail)
+                                view=move |cx, detail| view! { cx,
+                                    <div class="rounded-md border p-4 mb-2">
+                                        <p class="text-sm font-medium">{detail.detail_type}</p>
+                                        <dl class="grid grid-cols-2 gap-1 text-sm text-muted-foreground">
+                                            {detail
+                                                .fields
+                                                .into_iter()
+                                                .map(|(name, value)| view! { cx,
+                                                    <dt>{name}</dt>
+                                                    <dd>{value}</dd>
+                                                })
+                                                .collect::<Vec<_>>()}
+                                        </dl>
+                                    </div>
+                                }
+                            />
+                        </Show>
+                    </CardContent>
+                    <CardFooter class="gap-2">
+                        <Button
+                            class="w-full".to_string()
+                            variant=ButtonVariant::Outline
+                            on:click=move |ev| {
+                                ev.prevent_default();
+                            }
+                        >
+                            Deny
+                        </Button>
+                        <Button
+                            class="w-full".to_string()
+                            on:click=move |ev| {
+                                ev.prevent_default();
+                            }
+                        >
+                            Allow
+                        </Button>
+                    </CardFooter>
+                </Card>
+            </div>
+        </div>
+    }
+}
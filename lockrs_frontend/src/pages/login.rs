@@ -3,7 +3,9 @@ use leptos::*;
 use crate::components::ui::button::*;
 use crate::components::ui::card::*;
 use crate::components::ui::link::*;
+use crate::components::ui::separator::*;
 use crate::components::user::login_form::*;
+use crate::components::user::passkey_login_button::*;
 
 #[component]
 pub fn LoginPage(cx: Scope) -> impl IntoView {
@@ -25,6 +27,8 @@ pub fn LoginPage(cx: Scope) -> impl IntoView {
                     </CardHeader>
                     <CardContent>
                         <UserLoginForm />
+                        <Separator class="my-4" />
+                        <PasskeyLoginButton class="w-full".to_string() />
                     </CardContent>
                     <CardFooter>
                         <Link
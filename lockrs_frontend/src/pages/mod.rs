@@ -1,13 +1,17 @@
 mod client;
+mod consent;
 mod home;
 mod login;
 // mod logout;
+mod password_reset;
 mod register;
 
 pub use self::{
     client::*,
+    consent::*,
     home::*,
     login::*,
     // logout::*,
+    password_reset::*,
     register::*,
 };
@@ -0,0 +1,76 @@
+use leptos::*;
+use leptos_router::use_query_map;
+
+use crate::components::ui::button::*;
+use crate::components::ui::card::*;
+use crate::components::ui::link::*;
+use crate::components::user::password_reset_form::*;
+use crate::components::user::password_reset_request_form::*;
+
+#[component]
+pub fn PasswordResetPage(cx: Scope) -> impl IntoView {
+    view! { cx,
+        <div id="password-reset-page" class="relative h-full flex-col items-center justify-center">
+            <Button
+                class="absolute right-4 top-4 md:right-8 md:top-8".to_string()
+                variant=ButtonVariant::Ghost
+            >
+                <Link href="/login">Login</Link>
+            </Button>
+            <div class="flex flex-col justify-center items-center h-full">
+                <Card>
+                    <CardHeader>
+                        <CardTitle>Forgot Password</CardTitle>
+                        <CardDescription>
+                            Enter your email and we will send you a link to reset your password
+                        </CardDescription>
+                    </CardHeader>
+                    <CardContent>
+                        <PasswordResetRequestForm />
+                    </CardContent>
+                </Card>
+            </div>
+        </div>
+    }
+}
+
+/// Where the link in a password reset mail leads, carrying the reset token in its query.
+#[component]
+pub fn PasswordResetConfirmPage(cx: Scope) -> impl IntoView {
+    let query = use_query_map(cx);
+    let token = Signal::derive(cx, move || {
+        query.with(|query| query.get("token").cloned().unwrap_or_default())
+    });
+
+    view! { cx,
+        <div id="password-reset-confirm-page" class="relative h-full flex-col items-center justify-center">
+            <Button
+                class="absolute right-4 top-4 md:right-8 md:top-8".to_string()
+                variant=ButtonVariant::Ghost
+            >
+                <Link href="/login">Login</Link>
+            </Button>
+            <div class="flex flex-col justify-center items-center h-full">
+                <Card>
+                    <CardHeader>
+                        <CardTitle>Reset Password</CardTitle>
+                        <CardDescription>
+                            Choose a new password for your account
+                        </CardDescription>
+                    </CardHeader>
+                    <CardContent>
+                        <PasswordResetForm token=token />
+                    </CardContent>
+                    <CardFooter>
+                        <Link
+                            class="w-full text-center".to_string()
+                            href="/password-reset"
+                        >
+                            Link expired? Request a new one
+                        </Link>
+                    </CardFooter>
+                </Card>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,8 @@
+user_id="$1"
+admin_api_key="$2"
+
+curl "http://127.0.0.1:9000/api/v1/admin/users/$user_id/lockout" \
+  -X DELETE \
+  --silent \
+  --write-out "%{http_code}\n" \
+  --header "Authorization: Bearer $admin_api_key"
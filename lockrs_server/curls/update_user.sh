@@ -1,11 +1,12 @@
 user_id="$1"
-password="$2"
+current_password="$2"
+new_password="$3"
 
-response=$(curl "http://127.0.0.1:9000/api/v1/users/$user_id" \
+response=$(curl "http://127.0.0.1:9000/api/v1/users/$user_id/password" \
   -X PUT \
   --silent \
   --cookie ./cookies --cookie-jar ./cookies \
   --location \
-  --json '{"password": "'"$password"'"}')
+  --json '{"current_password": "'"$current_password"'", "new_password": "'"$new_password"'"}')
 
 echo "$response" | jq --color-output .
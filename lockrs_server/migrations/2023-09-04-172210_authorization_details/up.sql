@@ -0,0 +1,22 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS authorization_detail_types (
+  name VARCHAR(64) PRIMARY KEY,
+  client_id VARCHAR(32) NOT NULL,
+  description TEXT NOT NULL,
+  schema JSONB NOT NULL,
+  CONSTRAINT authorization_detail_types_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE
+);
+
+CREATE INDEX authorization_detail_types_client_id_idx ON authorization_detail_types (client_id);
+
+ALTER TABLE authorization_codes
+  ADD COLUMN authorization_details JSONB NOT NULL DEFAULT '[]';
+
+ALTER TABLE access_tokens
+  ADD COLUMN authorization_details JSONB NOT NULL DEFAULT '[]';
+
+ALTER TABLE refresh_tokens
+  ADD COLUMN authorization_details JSONB NOT NULL DEFAULT '[]';
@@ -0,0 +1,41 @@
+-- Your SQL goes here
+CREATE TABLE backchannel_authorizations (
+  id SERIAL PRIMARY KEY,
+  auth_req_id VARCHAR(44) UNIQUE NOT NULL,
+  client_id VARCHAR(32) NOT NULL,
+  user_id UUID NOT NULL,
+  binding_message VARCHAR(64),
+  delivery_mode VARCHAR(4) NOT NULL,
+  client_notification_token TEXT,
+  client_notification_endpoint TEXT,
+  status VARCHAR(8) NOT NULL DEFAULT 'pending',
+  poll_interval INT NOT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT NOW(),
+  expires_at TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+  last_polled_at TIMESTAMP WITHOUT TIME ZONE,
+  scopes TEXT[] NOT NULL,
+  authorization_details JSONB NOT NULL DEFAULT '[]',
+  CONSTRAINT backchannel_authorizations_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT backchannel_authorizations_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE,
+  CONSTRAINT backchannel_authorizations_delivery_mode_valid CHECK (
+    delivery_mode IN ('poll', 'ping', 'push')
+  ),
+  CONSTRAINT backchannel_authorizations_status_valid CHECK (
+    status IN ('pending', 'approved', 'denied')
+  ),
+  CONSTRAINT backchannel_authorizations_notification_present CHECK (
+    delivery_mode = 'poll'
+    OR (client_notification_token IS NOT NULL AND client_notification_endpoint IS NOT NULL)
+  ),
+  CONSTRAINT backchannel_authorizations_scope_present CHECK (
+    CARDINALITY(scopes) > 0
+  )
+);
+
+CREATE INDEX backchannel_authorizations_auth_req_id_idx ON backchannel_authorizations (auth_req_id);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE redirect_uris
+  DROP CONSTRAINT IF EXISTS redirect_uris_match_mode_valid,
+  DROP COLUMN IF EXISTS match_mode;
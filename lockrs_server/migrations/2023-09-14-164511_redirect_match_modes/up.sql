@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE redirect_uris
+  ADD COLUMN match_mode VARCHAR(16) NOT NULL DEFAULT 'exact',
+  ADD CONSTRAINT redirect_uris_match_mode_valid CHECK (
+    match_mode IN ('exact', 'loopback', 'private_use')
+  );
@@ -0,0 +1,23 @@
+-- Your SQL goes here
+CREATE TABLE consents (
+  id SERIAL PRIMARY KEY,
+  user_id UUID NOT NULL,
+  client_id VARCHAR(32) NOT NULL,
+  scopes TEXT[] NOT NULL,
+  granted_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT NOW(),
+  expires_at TIMESTAMP WITHOUT TIME ZONE,
+  CONSTRAINT consents_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE,
+  CONSTRAINT consents_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT consents_user_id_client_id_unique UNIQUE (user_id, client_id),
+  CONSTRAINT consents_scope_present CHECK (
+    CARDINALITY(scopes) > 0
+  )
+);
+
+CREATE INDEX consents_user_id_idx ON consents (user_id);
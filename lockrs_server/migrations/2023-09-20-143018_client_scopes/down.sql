@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS scopes_client_id_idx;
+
+ALTER TABLE scopes
+  DROP CONSTRAINT IF EXISTS scopes_name_namespaced;
+
+ALTER TABLE scopes
+  DROP COLUMN IF EXISTS consent_text;
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+ALTER TABLE scopes
+  ADD COLUMN consent_text TEXT;
+
+-- client scopes are namespaced by their owning client, so they can never shadow a global scope or
+-- a scope owned by another client
+ALTER TABLE scopes
+  ADD CONSTRAINT scopes_name_namespaced CHECK (
+    (client_id IS NULL AND POSITION(':' IN name) = 0)
+    OR (client_id IS NOT NULL AND LEFT(name, LENGTH(client_id) + 1) = client_id || ':')
+  );
+
+CREATE INDEX scopes_client_id_idx ON scopes (client_id);
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS allowed_scopes (
+  client_id VARCHAR(32) NOT NULL,
+  scope VARCHAR NOT NULL,
+  is_default BOOLEAN NOT NULL DEFAULT FALSE,
+  PRIMARY KEY (client_id, scope),
+  CONSTRAINT allowed_scopes_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT allowed_scopes_scope_fkey
+    FOREIGN KEY (scope)
+    REFERENCES scopes (name)
+    ON DELETE CASCADE
+);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE refresh_tokens
+  DROP COLUMN IF EXISTS grant_created_at;
+
+DROP TABLE IF EXISTS client_policies CASCADE;
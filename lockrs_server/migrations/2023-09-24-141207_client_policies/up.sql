@@ -0,0 +1,27 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS client_policies (
+  client_id VARCHAR(32) PRIMARY KEY,
+  grant_types TEXT[] NOT NULL,
+  access_token_lifetime INTEGER NOT NULL DEFAULT 600,
+  refresh_token_lifetime INTEGER NOT NULL DEFAULT 86400,
+  id_token_lifetime INTEGER NOT NULL DEFAULT 3600,
+  absolute_refresh_lifetime INTEGER,
+  sliding_refresh_window INTEGER,
+  CONSTRAINT client_policies_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT client_policies_lifetimes_positive CHECK (
+    access_token_lifetime > 0
+    AND refresh_token_lifetime > 0
+    AND id_token_lifetime > 0
+    AND (absolute_refresh_lifetime IS NULL OR absolute_refresh_lifetime > 0)
+    AND (sliding_refresh_window IS NULL OR sliding_refresh_window > 0)
+  )
+);
+
+-- rotated refresh tokens carry the time of the original grant forward
+ALTER TABLE refresh_tokens
+  ADD COLUMN grant_created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT NOW();
+
+UPDATE refresh_tokens SET grant_created_at = created_at;
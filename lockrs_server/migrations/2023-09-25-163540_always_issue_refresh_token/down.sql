@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE client_policies
+  DROP COLUMN IF EXISTS always_issue_refresh_token;
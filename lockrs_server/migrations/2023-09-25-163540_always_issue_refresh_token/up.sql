@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE client_policies
+  ADD COLUMN always_issue_refresh_token BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+-- hashed secrets cannot be restored, so confidential clients will need new secrets
+ALTER TABLE clients
+  ADD COLUMN secret VARCHAR(32);
+
+DROP TABLE IF EXISTS client_secrets CASCADE;
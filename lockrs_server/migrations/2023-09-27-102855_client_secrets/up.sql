@@ -0,0 +1,31 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS client_secrets (
+  id SERIAL PRIMARY KEY,
+  client_id VARCHAR(32) NOT NULL,
+  salt VARCHAR(32) NOT NULL,
+  secret_hash VARCHAR(43) NOT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT NOW(),
+  expires_at TIMESTAMP WITHOUT TIME ZONE,
+  CONSTRAINT client_secrets_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE
+);
+
+CREATE INDEX client_secrets_client_id_idx ON client_secrets (client_id);
+
+-- hash the existing plaintext secrets the same way the server does:
+-- base64url(sha256(salt || secret)) without padding
+INSERT INTO client_secrets (client_id, salt, secret_hash)
+SELECT
+  id,
+  salt,
+  RTRIM(TRANSLATE(ENCODE(SHA256(CONVERT_TO(salt || secret, 'UTF8')), 'base64'), '+/', '-_'), '=')
+FROM (
+  SELECT id, secret, MD5(RANDOM()::TEXT || CLOCK_TIMESTAMP()::TEXT || id) AS salt
+  FROM clients
+  WHERE secret IS NOT NULL
+) AS confidential_clients;
+
+ALTER TABLE clients
+  DROP COLUMN secret;
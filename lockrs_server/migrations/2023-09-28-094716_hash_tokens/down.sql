@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+-- digests cannot be reversed, so any token or code issued before the rollback is invalidated
+DELETE FROM refresh_tokens;
+DELETE FROM access_tokens;
+DELETE FROM authorization_codes;
+DELETE FROM device_authorizations;
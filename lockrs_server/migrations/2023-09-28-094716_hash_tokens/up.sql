@@ -0,0 +1,17 @@
+-- Your SQL goes here
+-- replace the stored bearer credentials with their digests, the same way the server does:
+-- base64url(sha256(value)) without padding
+UPDATE access_tokens
+  SET token = RTRIM(TRANSLATE(ENCODE(SHA256(CONVERT_TO(token, 'UTF8')), 'base64'), '+/', '-_'), '=');
+
+UPDATE refresh_tokens
+  SET token = RTRIM(TRANSLATE(ENCODE(SHA256(CONVERT_TO(token, 'UTF8')), 'base64'), '+/', '-_'), '=');
+
+-- the server did not issue authorization codes from pg yet (its repository was a stub), so any
+-- row here was written by hand or by another tool; hash them anyway so that they stay
+-- redeemable once the repository looks codes up by their digest
+UPDATE authorization_codes
+  SET code = RTRIM(TRANSLATE(ENCODE(SHA256(CONVERT_TO(code, 'UTF8')), 'base64'), '+/', '-_'), '=');
+
+UPDATE device_authorizations
+  SET device_code = RTRIM(TRANSLATE(ENCODE(SHA256(CONVERT_TO(device_code, 'UTF8')), 'base64'), '+/', '-_'), '=');
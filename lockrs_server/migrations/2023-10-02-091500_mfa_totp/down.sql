@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS recovery_codes CASCADE;
+DROP TABLE IF EXISTS totp_secrets CASCADE;
@@ -0,0 +1,28 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS totp_secrets (
+  user_id UUID PRIMARY KEY,
+  secret VARCHAR(32) NOT NULL,
+  -- an enrollment only takes effect once the user has proven their authenticator works
+  confirmed_at TIMESTAMP WITHOUT TIME ZONE,
+  -- the time step of the last accepted code, so a code can never be replayed
+  last_used_step BIGINT,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT NOW(),
+  CONSTRAINT totp_secrets_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS recovery_codes (
+  id SERIAL PRIMARY KEY,
+  user_id UUID NOT NULL,
+  code_hash VARCHAR(43) NOT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT NOW(),
+  used_at TIMESTAMP WITHOUT TIME ZONE,
+  CONSTRAINT recovery_codes_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE
+);
+
+CREATE INDEX recovery_codes_user_id_idx ON recovery_codes (user_id);
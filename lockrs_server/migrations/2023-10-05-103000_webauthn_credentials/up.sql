@@ -0,0 +1,19 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS webauthn_credentials (
+  id SERIAL PRIMARY KEY,
+  user_id UUID NOT NULL,
+  -- the id the authenticator gave the credential, base64url encoded
+  credential_id VARCHAR(1366) NOT NULL UNIQUE,
+  -- the COSE encoded public key, as registered
+  public_key BYTEA NOT NULL,
+  -- the last signature counter reported by the authenticator, used to spot a cloned one
+  sign_count BIGINT NOT NULL DEFAULT 0,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT NOW(),
+  last_used_at TIMESTAMP WITHOUT TIME ZONE,
+  CONSTRAINT webauthn_credentials_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE
+);
+
+CREATE INDEX webauthn_credentials_user_id_idx ON webauthn_credentials (user_id);
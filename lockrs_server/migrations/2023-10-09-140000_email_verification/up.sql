@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE users
+  ADD COLUMN email_verified BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS federated_identities (
+  id SERIAL PRIMARY KEY,
+  user_id UUID NOT NULL,
+  -- the name the upstream identity provider is configured under
+  provider VARCHAR(255) NOT NULL,
+  -- the identifier of the user at the provider
+  subject VARCHAR(255) NOT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT NOW(),
+  CONSTRAINT federated_identities_provider_subject_key UNIQUE (provider, subject),
+  CONSTRAINT federated_identities_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE
+);
+
+CREATE INDEX federated_identities_user_id_idx ON federated_identities (user_id);
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM authorization_codes
+  WHERE (client_id, redirect_uri) NOT IN (SELECT client_id, uri FROM redirect_uris);
+
+ALTER TABLE authorization_codes
+  ADD CONSTRAINT authorization_codes_redirect_uri_fkey
+    FOREIGN KEY (client_id, redirect_uri)
+    REFERENCES redirect_uris (client_id, uri)
+    ON DELETE CASCADE;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- a loopback redirect is matched on any port, so a code can be issued for a uri that is not
+-- registered verbatim. The redirect a code was issued for is checked again on exchange instead
+ALTER TABLE authorization_codes DROP CONSTRAINT IF EXISTS authorization_codes_redirect_uri_fkey;
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM authorization_detail_types a
+  USING authorization_detail_types b
+  WHERE a.name = b.name AND a.client_id > b.client_id;
+
+ALTER TABLE authorization_detail_types DROP CONSTRAINT authorization_detail_types_pkey;
+ALTER TABLE authorization_detail_types ADD PRIMARY KEY (name);
+
+CREATE INDEX authorization_detail_types_client_id_idx ON authorization_detail_types (client_id);
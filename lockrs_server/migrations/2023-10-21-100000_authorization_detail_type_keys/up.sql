@@ -0,0 +1,8 @@
+-- Your SQL goes here
+-- a type name only has to be unique for the client that registered it, so that one client can not
+-- claim a name, or read the schema, of a type another client uses
+ALTER TABLE authorization_detail_types DROP CONSTRAINT authorization_detail_types_pkey;
+ALTER TABLE authorization_detail_types ADD PRIMARY KEY (client_id, name);
+
+-- the primary key leads with client_id, so it covers lookups by client on its own
+DROP INDEX IF EXISTS authorization_detail_types_client_id_idx;
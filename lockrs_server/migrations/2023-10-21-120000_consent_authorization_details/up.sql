@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- authorization details describe a single transaction, such as a payment of a set amount, so a
+-- consent remembers the ones the user approved and any others prompt the user again
+ALTER TABLE consents ADD COLUMN authorization_details JSONB NOT NULL DEFAULT '[]'::jsonb;
@@ -0,0 +1,4 @@
+-- bumped whenever the password changes, but not when the same password is rehashed, so a reset
+-- link can tell whether the password it was mailed for is still the current one
+ALTER TABLE users
+  ADD COLUMN password_version INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+-- ping and push mode notifications go to the endpoint registered here, instead of one taken from
+-- the request and matched against the redirect uris, which allow loopback addresses
+ALTER TABLE client_policies
+  ADD COLUMN backchannel_client_notification_endpoint TEXT;
@@ -0,0 +1,17 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS session_tokens;
+DROP TABLE IF EXISTS sessions;
+DROP TABLE IF EXISTS client_secrets;
+DROP TABLE IF EXISTS client_policies;
+DROP TABLE IF EXISTS allowed_scopes;
+DROP TABLE IF EXISTS consents;
+DROP TABLE IF EXISTS backchannel_authorizations;
+DROP TABLE IF EXISTS authorization_detail_types;
+DROP TABLE IF EXISTS authorization_codes;
+DROP TABLE IF EXISTS device_authorizations;
+DROP TABLE IF EXISTS redirect_uris;
+DROP TABLE IF EXISTS refresh_tokens;
+DROP TABLE IF EXISTS access_tokens;
+DROP TABLE IF EXISTS scopes;
+DROP TABLE IF EXISTS clients;
+DROP TABLE IF EXISTS users;
@@ -0,0 +1,317 @@
+-- Your SQL goes here
+-- the schema of every postgres migration up to hash_tokens, plus the tables standing in for the
+-- redis session keys. uuids are stored as hyphenated text, TEXT[] and JSONB columns as json text
+CREATE TABLE IF NOT EXISTS users (
+  id TEXT PRIMARY KEY,
+  email VARCHAR(256) UNIQUE NOT NULL,
+  password_hash VARCHAR(256) NOT NULL,
+  CONSTRAINT min_password_length CHECK (
+    (LENGTH(password_hash) >= 8)
+  )
+);
+
+CREATE TABLE IF NOT EXISTS clients (
+  id VARCHAR(32) PRIMARY KEY,
+  user_id TEXT NOT NULL,
+  is_public BOOLEAN NOT NULL,
+  name TEXT NOT NULL,
+  description VARCHAR(300) NOT NULL,
+  homepage_url TEXT NOT NULL,
+  CONSTRAINT clients_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS scopes (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  name VARCHAR NOT NULL UNIQUE,
+  description TEXT NOT NULL,
+  client_id VARCHAR(32),
+  consent_text TEXT,
+  CONSTRAINT scopes_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT scopes_name_namespaced CHECK (
+    (client_id IS NULL AND INSTR(name, ':') = 0)
+    OR (client_id IS NOT NULL AND SUBSTR(name, 1, LENGTH(client_id) + 1) = client_id || ':')
+  )
+);
+
+CREATE INDEX scopes_client_id_idx ON scopes (client_id);
+
+INSERT INTO scopes (name, description)
+VALUES
+  ('read', 'Allows the client application to read user data, such as their profile information or email address.'),
+  ('write', 'Allows the client application to create or modify user data, such as adding or editing user comments.'),
+  ('delete', 'Allows the client application to delete user data, such as removing user comments.'),
+  ('offline_access', 'Allows the client application to access the user''s resources even when the user is not actively logged in.'),
+  ('openid', 'Allows the client application to obtain the user''s OpenID identifier, which can be used to authenticate the user on other systems.'),
+  ('profile', 'Allows the client application to obtain the user''s profile information, such as their name and profile picture.');
+
+CREATE TABLE IF NOT EXISTS access_tokens (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  token VARCHAR(128) NOT NULL,
+  client_id VARCHAR(32) NOT NULL,
+  user_id TEXT,
+  created_at TIMESTAMP NOT NULL,
+  expires_at TIMESTAMP NOT NULL,
+  scopes TEXT NOT NULL,
+  authorization_details TEXT NOT NULL DEFAULT '[]',
+  CONSTRAINT access_tokens_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT access_tokens_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE,
+  CONSTRAINT access_tokens_min_token_length CHECK (
+    LENGTH(token) >= 43
+  ),
+  CONSTRAINT access_tokens_scope_present CHECK (
+    JSON_ARRAY_LENGTH(scopes) > 0
+  )
+);
+
+CREATE INDEX access_tokens_token_idx ON access_tokens (token);
+
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  access_token_id INTEGER NOT NULL UNIQUE,
+  token VARCHAR(44) NOT NULL UNIQUE,
+  client_id VARCHAR(32) NOT NULL,
+  user_id TEXT,
+  created_at TIMESTAMP NOT NULL,
+  expires_at TIMESTAMP NOT NULL,
+  used BOOLEAN NOT NULL DEFAULT FALSE,
+  scopes TEXT NOT NULL,
+  authorization_details TEXT NOT NULL DEFAULT '[]',
+  grant_created_at TIMESTAMP NOT NULL,
+  CONSTRAINT refresh_tokens_access_token_id_fkey
+    FOREIGN KEY (access_token_id)
+    REFERENCES access_tokens (id)
+    ON DELETE CASCADE,
+  CONSTRAINT refresh_tokens_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT refresh_tokens_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE,
+  CONSTRAINT refresh_tokens_min_token_length CHECK (
+    LENGTH(token) >= 43
+  ),
+  CONSTRAINT refresh_tokens_scope_present CHECK (
+    JSON_ARRAY_LENGTH(scopes) > 0
+  )
+);
+
+CREATE TABLE IF NOT EXISTS redirect_uris (
+  id TEXT PRIMARY KEY,
+  client_id VARCHAR(32) NOT NULL,
+  uri TEXT NOT NULL,
+  created_at TIMESTAMP NOT NULL,
+  updated_at TIMESTAMP NOT NULL,
+  match_mode VARCHAR(16) NOT NULL DEFAULT 'exact',
+  CONSTRAINT redirect_uris_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT redirect_uri_unique
+    UNIQUE (client_id, uri),
+  CONSTRAINT redirect_uris_match_mode_valid CHECK (
+    match_mode IN ('exact', 'loopback', 'private_use')
+  )
+);
+
+CREATE TABLE IF NOT EXISTS device_authorizations (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  client_id VARCHAR(32) NOT NULL,
+  user_code VARCHAR(8) UNIQUE NOT NULL,
+  device_code VARCHAR(44) UNIQUE NOT NULL,
+  created_at TIMESTAMP NOT NULL,
+  expires_at TIMESTAMP NOT NULL,
+  scopes TEXT NOT NULL,
+  CONSTRAINT device_authorizations_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT device_authorizations_scope_present CHECK (
+    JSON_ARRAY_LENGTH(scopes) > 0
+  )
+);
+
+CREATE TABLE IF NOT EXISTS authorization_codes (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  code VARCHAR(100) NOT NULL,
+  challenge VARCHAR(128) NOT NULL,
+  is_challenge_plain BOOLEAN NOT NULL,
+  client_id VARCHAR(32) NOT NULL,
+  user_id TEXT NOT NULL,
+  redirect_uri TEXT NOT NULL,
+  created_at TIMESTAMP NOT NULL,
+  expires_at TIMESTAMP NOT NULL,
+  used BOOLEAN NOT NULL DEFAULT FALSE,
+  scopes TEXT NOT NULL,
+  authorization_details TEXT NOT NULL DEFAULT '[]',
+  CONSTRAINT authorization_codes_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT authorization_codes_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE,
+  CONSTRAINT authorization_codes_redirect_uri_fkey
+    FOREIGN KEY (client_id, redirect_uri)
+    REFERENCES redirect_uris (client_id, uri)
+    ON DELETE CASCADE,
+  CONSTRAINT min_challenge_length CHECK (
+    (LENGTH(challenge) >= 43)
+  ),
+  CONSTRAINT authorization_codes_scope_present CHECK (
+    JSON_ARRAY_LENGTH(scopes) > 0
+  )
+);
+
+CREATE TABLE IF NOT EXISTS authorization_detail_types (
+  name VARCHAR(64) PRIMARY KEY,
+  client_id VARCHAR(32) NOT NULL,
+  description TEXT NOT NULL,
+  schema TEXT NOT NULL,
+  CONSTRAINT authorization_detail_types_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE
+);
+
+CREATE INDEX authorization_detail_types_client_id_idx ON authorization_detail_types (client_id);
+
+CREATE TABLE IF NOT EXISTS backchannel_authorizations (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  auth_req_id VARCHAR(44) UNIQUE NOT NULL,
+  client_id VARCHAR(32) NOT NULL,
+  user_id TEXT NOT NULL,
+  binding_message VARCHAR(64),
+  delivery_mode VARCHAR(4) NOT NULL,
+  client_notification_token TEXT,
+  client_notification_endpoint TEXT,
+  status VARCHAR(8) NOT NULL DEFAULT 'pending',
+  poll_interval INTEGER NOT NULL,
+  created_at TIMESTAMP NOT NULL,
+  expires_at TIMESTAMP NOT NULL,
+  last_polled_at TIMESTAMP,
+  scopes TEXT NOT NULL,
+  authorization_details TEXT NOT NULL DEFAULT '[]',
+  CONSTRAINT backchannel_authorizations_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT backchannel_authorizations_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE,
+  CONSTRAINT backchannel_authorizations_delivery_mode_valid CHECK (
+    delivery_mode IN ('poll', 'ping', 'push')
+  ),
+  CONSTRAINT backchannel_authorizations_status_valid CHECK (
+    status IN ('pending', 'approved', 'denied')
+  ),
+  CONSTRAINT backchannel_authorizations_notification_present CHECK (
+    delivery_mode = 'poll'
+    OR (client_notification_token IS NOT NULL AND client_notification_endpoint IS NOT NULL)
+  ),
+  CONSTRAINT backchannel_authorizations_scope_present CHECK (
+    JSON_ARRAY_LENGTH(scopes) > 0
+  )
+);
+
+CREATE TABLE IF NOT EXISTS consents (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  user_id TEXT NOT NULL,
+  client_id VARCHAR(32) NOT NULL,
+  scopes TEXT NOT NULL,
+  granted_at TIMESTAMP NOT NULL,
+  expires_at TIMESTAMP,
+  CONSTRAINT consents_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE,
+  CONSTRAINT consents_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT consents_user_id_client_id_unique UNIQUE (user_id, client_id),
+  CONSTRAINT consents_scope_present CHECK (
+    JSON_ARRAY_LENGTH(scopes) > 0
+  )
+);
+
+CREATE TABLE IF NOT EXISTS allowed_scopes (
+  client_id VARCHAR(32) NOT NULL,
+  scope VARCHAR NOT NULL,
+  is_default BOOLEAN NOT NULL DEFAULT FALSE,
+  PRIMARY KEY (client_id, scope),
+  CONSTRAINT allowed_scopes_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT allowed_scopes_scope_fkey
+    FOREIGN KEY (scope)
+    REFERENCES scopes (name)
+    ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS client_policies (
+  client_id VARCHAR(32) PRIMARY KEY,
+  grant_types TEXT NOT NULL,
+  access_token_lifetime INTEGER NOT NULL DEFAULT 600,
+  refresh_token_lifetime INTEGER NOT NULL DEFAULT 86400,
+  id_token_lifetime INTEGER NOT NULL DEFAULT 3600,
+  absolute_refresh_lifetime INTEGER,
+  sliding_refresh_window INTEGER,
+  always_issue_refresh_token BOOLEAN NOT NULL DEFAULT FALSE,
+  CONSTRAINT client_policies_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT client_policies_lifetimes_positive CHECK (
+    access_token_lifetime > 0
+    AND refresh_token_lifetime > 0
+    AND id_token_lifetime > 0
+    AND (absolute_refresh_lifetime IS NULL OR absolute_refresh_lifetime > 0)
+    AND (sliding_refresh_window IS NULL OR sliding_refresh_window > 0)
+  )
+);
+
+CREATE TABLE IF NOT EXISTS client_secrets (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  client_id VARCHAR(32) NOT NULL,
+  salt VARCHAR(32) NOT NULL,
+  secret_hash VARCHAR(43) NOT NULL,
+  created_at TIMESTAMP NOT NULL,
+  expires_at TIMESTAMP,
+  CONSTRAINT client_secrets_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE
+);
+
+CREATE INDEX client_secrets_client_id_idx ON client_secrets (client_id);
+
+-- the sessions of a user share one expiry, like the fields of the user's redis hash
+CREATE TABLE IF NOT EXISTS sessions (
+  id TEXT NOT NULL,
+  user_id TEXT NOT NULL,
+  expires_at BIGINT NOT NULL,
+  PRIMARY KEY (user_id, id)
+);
+
+CREATE TABLE IF NOT EXISTS session_tokens (
+  token TEXT PRIMARY KEY,
+  user_id TEXT NOT NULL,
+  expires_at BIGINT NOT NULL
+);
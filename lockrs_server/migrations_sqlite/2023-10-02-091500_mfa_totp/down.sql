@@ -0,0 +1,5 @@
+ALTER TABLE session_tokens
+  DROP COLUMN mfa_pending;
+
+DROP TABLE IF EXISTS recovery_codes;
+DROP TABLE IF EXISTS totp_secrets;
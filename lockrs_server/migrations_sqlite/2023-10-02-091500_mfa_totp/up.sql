@@ -0,0 +1,29 @@
+CREATE TABLE IF NOT EXISTS totp_secrets (
+  user_id TEXT PRIMARY KEY,
+  secret VARCHAR(32) NOT NULL,
+  confirmed_at TIMESTAMP,
+  last_used_step BIGINT,
+  created_at TIMESTAMP NOT NULL,
+  CONSTRAINT totp_secrets_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS recovery_codes (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  user_id TEXT NOT NULL,
+  code_hash VARCHAR(43) NOT NULL,
+  created_at TIMESTAMP NOT NULL,
+  used_at TIMESTAMP,
+  CONSTRAINT recovery_codes_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE
+);
+
+CREATE INDEX recovery_codes_user_id_idx ON recovery_codes (user_id);
+
+-- a session token issued to a user with a second factor, waiting on that factor
+ALTER TABLE session_tokens
+  ADD COLUMN mfa_pending BOOLEAN NOT NULL DEFAULT FALSE;
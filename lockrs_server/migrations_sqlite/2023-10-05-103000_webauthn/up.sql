@@ -0,0 +1,24 @@
+CREATE TABLE IF NOT EXISTS webauthn_credentials (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  user_id TEXT NOT NULL,
+  credential_id VARCHAR(1366) NOT NULL UNIQUE,
+  public_key BLOB NOT NULL,
+  sign_count BIGINT NOT NULL DEFAULT 0,
+  created_at TIMESTAMP NOT NULL,
+  last_used_at TIMESTAMP,
+  CONSTRAINT webauthn_credentials_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE
+);
+
+CREATE INDEX webauthn_credentials_user_id_idx ON webauthn_credentials (user_id);
+
+-- the challenges of ceremonies in progress, kept in redis alongside the session tokens otherwise
+CREATE TABLE IF NOT EXISTS webauthn_challenges (
+  id TEXT PRIMARY KEY,
+  challenge TEXT NOT NULL,
+  user_id TEXT,
+  ceremony TEXT NOT NULL,
+  expires_at BIGINT NOT NULL
+);
@@ -0,0 +1,8 @@
+-- outstanding password reset tokens, kept in redis alongside the session tokens otherwise
+CREATE TABLE IF NOT EXISTS password_reset_tokens (
+  token TEXT PRIMARY KEY,
+  user_id TEXT NOT NULL,
+  email TEXT NOT NULL,
+  password_fingerprint TEXT NOT NULL,
+  expires_at BIGINT NOT NULL
+);
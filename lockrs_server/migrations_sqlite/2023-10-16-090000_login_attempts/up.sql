@@ -0,0 +1,7 @@
+-- failed login counters, kept in redis alongside the session tokens otherwise
+CREATE TABLE IF NOT EXISTS login_attempts (
+  key TEXT PRIMARY KEY,
+  failures BIGINT NOT NULL,
+  locked_until BIGINT,
+  expires_at BIGINT NOT NULL
+);
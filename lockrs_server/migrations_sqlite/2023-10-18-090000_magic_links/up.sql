@@ -0,0 +1,10 @@
+-- outstanding sign-in links, kept in redis alongside the session tokens otherwise
+CREATE TABLE IF NOT EXISTS magic_links (
+  email TEXT PRIMARY KEY,
+  user_id TEXT NOT NULL,
+  nonce_digest TEXT NOT NULL,
+  code_digest TEXT NOT NULL,
+  binding_digest TEXT NOT NULL,
+  failed_attempts BIGINT NOT NULL DEFAULT 0,
+  expires_at BIGINT NOT NULL
+);
@@ -0,0 +1,25 @@
+CREATE TABLE IF NOT EXISTS federated_identities (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  user_id TEXT NOT NULL,
+  provider VARCHAR(255) NOT NULL,
+  subject VARCHAR(255) NOT NULL,
+  created_at TIMESTAMP NOT NULL,
+  CONSTRAINT federated_identities_provider_subject_key UNIQUE (provider, subject),
+  CONSTRAINT federated_identities_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE
+);
+
+CREATE INDEX federated_identities_user_id_idx ON federated_identities (user_id);
+
+-- sign-ins with upstream identity providers in progress, kept in redis alongside the session
+-- tokens otherwise
+CREATE TABLE IF NOT EXISTS federation_states (
+  state TEXT PRIMARY KEY,
+  provider TEXT NOT NULL,
+  nonce TEXT NOT NULL,
+  code_verifier TEXT NOT NULL,
+  binding_digest TEXT NOT NULL,
+  expires_at BIGINT NOT NULL
+);
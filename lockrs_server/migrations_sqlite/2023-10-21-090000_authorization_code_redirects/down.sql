@@ -0,0 +1,38 @@
+-- This file should undo anything in `up.sql`
+CREATE TABLE authorization_codes_rebuilt (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  code VARCHAR(100) NOT NULL,
+  challenge VARCHAR(128) NOT NULL,
+  is_challenge_plain BOOLEAN NOT NULL,
+  client_id VARCHAR(32) NOT NULL,
+  user_id TEXT NOT NULL,
+  redirect_uri TEXT NOT NULL,
+  created_at TIMESTAMP NOT NULL,
+  expires_at TIMESTAMP NOT NULL,
+  used BOOLEAN NOT NULL DEFAULT FALSE,
+  scopes TEXT NOT NULL,
+  authorization_details TEXT NOT NULL DEFAULT '[]',
+  CONSTRAINT authorization_codes_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE,
+  CONSTRAINT authorization_codes_user_id_fkey
+    FOREIGN KEY (user_id)
+    REFERENCES users (id)
+    ON DELETE CASCADE,
+  CONSTRAINT authorization_codes_redirect_uri_fkey
+    FOREIGN KEY (client_id, redirect_uri)
+    REFERENCES redirect_uris (client_id, uri)
+    ON DELETE CASCADE,
+  CONSTRAINT min_challenge_length CHECK (
+    (LENGTH(challenge) >= 43)
+  ),
+  CONSTRAINT authorization_codes_scope_present CHECK (
+    JSON_ARRAY_LENGTH(scopes) > 0
+  )
+);
+
+INSERT INTO authorization_codes_rebuilt SELECT * FROM authorization_codes
+  WHERE (client_id, redirect_uri) IN (SELECT client_id, uri FROM redirect_uris);
+DROP TABLE authorization_codes;
+ALTER TABLE authorization_codes_rebuilt RENAME TO authorization_codes;
@@ -0,0 +1,18 @@
+-- This file should undo anything in `up.sql`
+CREATE TABLE authorization_detail_types_rebuilt (
+  name VARCHAR(64) PRIMARY KEY,
+  client_id VARCHAR(32) NOT NULL,
+  description TEXT NOT NULL,
+  schema TEXT NOT NULL,
+  CONSTRAINT authorization_detail_types_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE
+);
+
+INSERT OR IGNORE INTO authorization_detail_types_rebuilt
+  SELECT * FROM authorization_detail_types ORDER BY client_id;
+DROP TABLE authorization_detail_types;
+ALTER TABLE authorization_detail_types_rebuilt RENAME TO authorization_detail_types;
+
+CREATE INDEX authorization_detail_types_client_id_idx ON authorization_detail_types (client_id);
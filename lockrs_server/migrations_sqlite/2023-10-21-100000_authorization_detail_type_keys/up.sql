@@ -0,0 +1,19 @@
+-- Your SQL goes here
+-- a type name only has to be unique for the client that registered it, so that one client can not
+-- claim a name, or read the schema, of a type another client uses.
+-- sqlite cannot change a primary key, so the table is rebuilt with the new one
+CREATE TABLE authorization_detail_types_rebuilt (
+  name VARCHAR(64) NOT NULL,
+  client_id VARCHAR(32) NOT NULL,
+  description TEXT NOT NULL,
+  schema TEXT NOT NULL,
+  PRIMARY KEY (client_id, name),
+  CONSTRAINT authorization_detail_types_client_id_fkey
+    FOREIGN KEY (client_id)
+    REFERENCES clients (id)
+    ON DELETE CASCADE
+);
+
+INSERT INTO authorization_detail_types_rebuilt SELECT * FROM authorization_detail_types;
+DROP TABLE authorization_detail_types;
+ALTER TABLE authorization_detail_types_rebuilt RENAME TO authorization_detail_types;
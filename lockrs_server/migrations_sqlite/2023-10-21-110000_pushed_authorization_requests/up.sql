@@ -0,0 +1,8 @@
+-- Your SQL goes here
+-- authorization requests pushed ahead of the redirect to /authorize, kept in redis alongside the
+-- session tokens otherwise. The request is only ever read back whole, so it is kept as json
+CREATE TABLE IF NOT EXISTS pushed_authorization_requests (
+  request_uri TEXT PRIMARY KEY,
+  request TEXT NOT NULL,
+  expires_at BIGINT NOT NULL
+);
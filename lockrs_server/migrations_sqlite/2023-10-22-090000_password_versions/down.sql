@@ -0,0 +1,11 @@
+DROP TABLE IF EXISTS password_reset_tokens;
+CREATE TABLE IF NOT EXISTS password_reset_tokens (
+  token TEXT PRIMARY KEY,
+  user_id TEXT NOT NULL,
+  email TEXT NOT NULL,
+  password_fingerprint TEXT NOT NULL,
+  expires_at BIGINT NOT NULL
+);
+
+ALTER TABLE users
+  DROP COLUMN password_version;
@@ -0,0 +1,13 @@
+ALTER TABLE users
+  ADD COLUMN password_version INTEGER NOT NULL DEFAULT 0;
+
+-- outstanding reset tokens were bound to a digest of the password hash, and are only good for 30
+-- minutes anyway
+DROP TABLE IF EXISTS password_reset_tokens;
+CREATE TABLE IF NOT EXISTS password_reset_tokens (
+  token TEXT PRIMARY KEY,
+  user_id TEXT NOT NULL,
+  email TEXT NOT NULL,
+  password_version INTEGER NOT NULL,
+  expires_at BIGINT NOT NULL
+);
@@ -0,0 +1,2 @@
+ALTER TABLE client_policies
+  DROP COLUMN backchannel_client_notification_endpoint;
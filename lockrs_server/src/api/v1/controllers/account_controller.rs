@@ -0,0 +1,352 @@
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::{header::RETRY_AFTER, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    api::v1::{
+        models::{EmailChange, PasswordChange, PasswordPolicyViolation},
+        responses::{PasswordPolicyResponse, UserResponse},
+        services::{
+            AccountService, AccountServiceError, LoginThrottleService, LoginThrottleServiceError,
+        },
+    },
+    services::{UserService, UserServiceError},
+    utils::extractors::SessionJwt,
+    AppState,
+};
+
+#[derive(Deserialize)]
+pub struct PasswordChangeRequest {
+    pub current_password: String,
+    pub new_password: String,
+    pub end_other_sessions: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct EmailChangeRequest {
+    pub email: String,
+    pub current_password: String,
+}
+
+#[derive(Deserialize)]
+pub struct EmailChangeConfirmRequest {
+    pub token: String,
+    pub end_other_sessions: Option<bool>,
+}
+
+pub struct AccountController;
+
+impl AccountController {
+    /// Sets a new password, unless the account or the address the request comes from has been
+    /// locked out by too many failed logins. A wrong current password counts as a failed login.
+    pub async fn change_password(
+        State(state): State<AppState>,
+        Path(user_id): Path<Uuid>,
+        connect_info: Option<ConnectInfo<SocketAddr>>,
+        SessionJwt(session): SessionJwt,
+        Json(change_request): Json<PasswordChangeRequest>,
+    ) -> Result<StatusCode, AccountControllerError> {
+        tracing::trace!(method = "change_password", user_id = user_id.to_string());
+
+        let password_change = PasswordChange::new(
+            change_request.current_password.as_str(),
+            change_request.new_password.as_str(),
+        );
+
+        password_change
+            .validate()
+            .map_err(|_| AccountControllerError::BadRequest)?;
+
+        let db_context = &state.db_context;
+        let user_auth_repository = &*state.repository_container.as_ref().user_auth_repository;
+        let session_repository = &*state.repository_container.as_ref().session_repository;
+        let refresh_token_repository =
+            &*state.repository_container.as_ref().refresh_token_repository;
+
+        let address = connect_info.map(|ConnectInfo(address)| address.ip());
+        let email = Self::check_throttle(&state, &user_id, address.as_ref()).await?;
+
+        let change_result = AccountService::change_password(
+            db_context,
+            user_auth_repository,
+            session_repository,
+            refresh_token_repository,
+            &state.config.password_policy,
+            &state.config.password_hashing,
+            &user_id,
+            session.id.as_str(),
+            &password_change,
+            change_request.end_other_sessions.unwrap_or(true),
+        )
+        .await;
+
+        Self::record_attempt(&state, email.as_str(), address.as_ref(), &change_result).await;
+        change_result.map_err(AccountControllerError::from)?;
+
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Mails a confirmation link to the new address, throttled just like `change_password` as it
+    /// asks for the current password too.
+    pub async fn request_email_change(
+        State(state): State<AppState>,
+        Path(user_id): Path<Uuid>,
+        connect_info: Option<ConnectInfo<SocketAddr>>,
+        Json(change_request): Json<EmailChangeRequest>,
+    ) -> Result<StatusCode, AccountControllerError> {
+        tracing::trace!(
+            method = "request_email_change",
+            user_id = user_id.to_string(),
+            email = change_request.email
+        );
+
+        let email_change = EmailChange::new(
+            change_request.email.as_str(),
+            change_request.current_password.as_str(),
+        );
+
+        email_change
+            .validate()
+            .map_err(|_| AccountControllerError::BadRequest)?;
+
+        let db_context = &state.db_context;
+        let user_auth_repository = &*state.repository_container.as_ref().user_auth_repository;
+
+        let address = connect_info.map(|ConnectInfo(address)| address.ip());
+        let email = Self::check_throttle(&state, &user_id, address.as_ref()).await?;
+
+        let change_result = AccountService::request_email_change(
+            db_context,
+            user_auth_repository,
+            &*state.mailer,
+            state.config.link_signing_key.as_slice(),
+            state.config.frontend_url.as_str(),
+            &user_id,
+            &email_change,
+        )
+        .await;
+
+        Self::record_attempt(&state, email.as_str(), address.as_ref(), &change_result).await;
+        change_result.map_err(AccountControllerError::from)?;
+
+        Ok(StatusCode::ACCEPTED)
+    }
+
+    pub async fn confirm_email_change(
+        State(state): State<AppState>,
+        Path(user_id): Path<Uuid>,
+        SessionJwt(session): SessionJwt,
+        Json(confirm_request): Json<EmailChangeConfirmRequest>,
+    ) -> Result<UserResponse, AccountControllerError> {
+        tracing::trace!(
+            method = "confirm_email_change",
+            user_id = user_id.to_string()
+        );
+
+        let db_context = &state.db_context;
+        let user_repository = &*state.repository_container.as_ref().user_repository;
+        let session_repository = &*state.repository_container.as_ref().session_repository;
+        let refresh_token_repository =
+            &*state.repository_container.as_ref().refresh_token_repository;
+
+        let user = AccountService::confirm_email_change(
+            db_context,
+            user_repository,
+            session_repository,
+            refresh_token_repository,
+            &*state.mailer,
+            state.config.link_signing_key.as_slice(),
+            &user_id,
+            session.id.as_str(),
+            confirm_request.token.as_str(),
+            confirm_request.end_other_sessions.unwrap_or(true),
+        )
+        .await
+        .map_err(AccountControllerError::from)?;
+
+        Ok(UserResponse {
+            id: user.id,
+            email: user.email,
+            email_verified: user.email_verified,
+        })
+    }
+
+    /// Fails while the account or the address is locked out, returning the email the account's
+    /// failures are counted under, so guessing the current password here and guessing it at login
+    /// share one lockout.
+    async fn check_throttle(
+        state: &AppState,
+        user_id: &Uuid,
+        address: Option<&IpAddr>,
+    ) -> Result<String, AccountControllerError> {
+        let db_context = &state.db_context;
+        let user_repository = &*state.repository_container.as_ref().user_repository;
+        let login_attempt_repository =
+            &*state.repository_container.as_ref().login_attempt_repository;
+
+        let user = UserService::get_user_by_id(db_context, user_repository, user_id)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = %err);
+
+                match err {
+                    UserServiceError::NotFound => AccountControllerError::NotFound,
+                    _ => AccountControllerError::InternalError,
+                }
+            })?;
+
+        LoginThrottleService::check(
+            db_context,
+            login_attempt_repository,
+            user.email.as_str(),
+            address,
+        )
+        .await
+        .map_err(AccountControllerError::from)?;
+
+        Ok(user.email)
+    }
+
+    /// Counts a wrong current password as a failed login, and a right one as a successful login.
+    async fn record_attempt<T>(
+        state: &AppState,
+        email: &str,
+        address: Option<&IpAddr>,
+        result: &Result<T, AccountServiceError>,
+    ) {
+        let db_context = &state.db_context;
+        let login_attempt_repository =
+            &*state.repository_container.as_ref().login_attempt_repository;
+
+        let recorded = match result {
+            Ok(_) => {
+                LoginThrottleService::record_success(db_context, login_attempt_repository, email)
+                    .await
+            }
+            Err(AccountServiceError::Credentials) => {
+                LoginThrottleService::record_failure(
+                    db_context,
+                    login_attempt_repository,
+                    &state.config.login_throttle,
+                    email,
+                    address,
+                )
+                .await
+            }
+            Err(_) => Ok(()),
+        };
+
+        if let Err(err) = recorded {
+            tracing::error!(error = %err);
+        }
+    }
+}
+
+pub enum AccountControllerError {
+    NotFound,
+    Credentials,
+    AlreadyExists,
+    InvalidToken,
+    DeliveryFailed,
+    PasswordPolicy(Vec<PasswordPolicyViolation>),
+    /// with the number of seconds until the current password can be tried again
+    LockedOut(i64),
+
+    BadRequest,
+    InternalError,
+}
+
+impl AccountControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::Credentials => StatusCode::FORBIDDEN,
+            Self::AlreadyExists => StatusCode::CONFLICT,
+            Self::InvalidToken => StatusCode::BAD_REQUEST,
+            Self::DeliveryFailed => StatusCode::SERVICE_UNAVAILABLE,
+            Self::PasswordPolicy(_) => StatusCode::BAD_REQUEST,
+            Self::LockedOut(_) => StatusCode::TOO_MANY_REQUESTS,
+
+            Self::BadRequest => StatusCode::BAD_REQUEST,
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::NotFound => "The requested user was not found.",
+            Self::Credentials => "The current password provided was incorrect.",
+            Self::AlreadyExists => {
+                "An account is already associated with that email. Please use a different email."
+            }
+            Self::InvalidToken => {
+                "The confirmation link is invalid or has expired. Please request a new one."
+            }
+            Self::DeliveryFailed => {
+                "The confirmation mail could not be sent. Please try again later."
+            }
+            Self::PasswordPolicy(_) => "The password does not meet the password policy.",
+            Self::LockedOut(_) => "Too many failed attempts. Please try again later.",
+
+            Self::BadRequest => "The data provided in the request was invalid.",
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<AccountServiceError> for AccountControllerError {
+    fn from(err: AccountServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            AccountServiceError::NotFound => Self::NotFound,
+            AccountServiceError::Credentials => Self::Credentials,
+            AccountServiceError::AlreadyExists => Self::AlreadyExists,
+            AccountServiceError::InvalidToken => Self::InvalidToken,
+            AccountServiceError::DeliveryFailed => Self::DeliveryFailed,
+            AccountServiceError::PasswordPolicy(violations) => Self::PasswordPolicy(violations),
+
+            AccountServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl From<LoginThrottleServiceError> for AccountControllerError {
+    fn from(err: LoginThrottleServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            LoginThrottleServiceError::Locked(retry_after) => Self::LockedOut(retry_after),
+            LoginThrottleServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for AccountControllerError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            Self::PasswordPolicy(violations) => (
+                StatusCode::BAD_REQUEST,
+                PasswordPolicyResponse::from(violations),
+            )
+                .into_response(),
+            Self::LockedOut(retry_after) => (
+                self.error_code(),
+                [(RETRY_AFTER, retry_after.to_string())],
+                self.error_message(),
+            )
+                .into_response(),
+            _ => (self.error_code(), self.error_message()).into_response(),
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    api::v1::responses::{AuthorizationDetailTypeListResponse, AuthorizationDetailTypeResponse},
+    oauth2::v1::{
+        models::{AuthorizationDetailSchema, AuthorizationDetailTypeCreateModel},
+        services::{AuthorizationDetailService, AuthorizationDetailServiceError},
+    },
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct AuthorizationDetailTypeCreateRequest {
+    pub name: String,
+    pub description: String,
+    pub schema: AuthorizationDetailSchema,
+}
+
+pub struct AuthorizationDetailTypeController;
+
+impl AuthorizationDetailTypeController {
+    pub async fn read_all(
+        State(state): State<AppState>,
+        Path(client_id): Path<String>,
+    ) -> Result<AuthorizationDetailTypeListResponse, AuthorizationDetailTypeControllerError> {
+        tracing::trace!(method = "read_all", client_id);
+
+        let db_context = &state.db_context;
+        let authorization_detail_type_repository = &*state
+            .repository_container
+            .as_ref()
+            .authorization_detail_type_repository;
+
+        let detail_types = AuthorizationDetailService::get_types_by_client(
+            db_context,
+            authorization_detail_type_repository,
+            client_id.as_str(),
+        )
+        .await
+        .map_err(AuthorizationDetailTypeControllerError::from)?;
+
+        Ok(AuthorizationDetailTypeListResponse {
+            authorization_detail_types: detail_types
+                .into_iter()
+                .map(|t| AuthorizationDetailTypeResponse {
+                    name: t.name,
+                    client_id: t.client_id,
+                    description: t.description,
+                    schema: t.schema,
+                })
+                .collect::<Vec<AuthorizationDetailTypeResponse>>(),
+        })
+    }
+
+    pub async fn create(
+        State(state): State<AppState>,
+        Path(client_id): Path<String>,
+        Json(new_type_request): Json<AuthorizationDetailTypeCreateRequest>,
+    ) -> Result<AuthorizationDetailTypeResponse, AuthorizationDetailTypeControllerError> {
+        tracing::trace!(
+            method = "create",
+            client_id,
+            params = ?new_type_request
+        );
+
+        let type_create = AuthorizationDetailTypeCreateModel::new(
+            new_type_request.name.as_str(),
+            client_id.as_str(),
+            new_type_request.description.as_str(),
+            &new_type_request.schema,
+        );
+
+        let db_context = &state.db_context;
+        let authorization_detail_type_repository = &*state
+            .repository_container
+            .as_ref()
+            .authorization_detail_type_repository;
+
+        let detail_type = AuthorizationDetailService::register_type(
+            db_context,
+            authorization_detail_type_repository,
+            &type_create,
+        )
+        .await
+        .map_err(AuthorizationDetailTypeControllerError::from)?;
+
+        Ok(AuthorizationDetailTypeResponse {
+            name: detail_type.name,
+            client_id: detail_type.client_id,
+            description: detail_type.description,
+            schema: detail_type.schema,
+        })
+    }
+
+    pub async fn delete(
+        State(state): State<AppState>,
+        Path((client_id, name)): Path<(String, String)>,
+    ) -> Result<StatusCode, AuthorizationDetailTypeControllerError> {
+        tracing::trace!(method = "delete", client_id, name);
+
+        let db_context = &state.db_context;
+        let authorization_detail_type_repository = &*state
+            .repository_container
+            .as_ref()
+            .authorization_detail_type_repository;
+
+        AuthorizationDetailService::delete_type(
+            db_context,
+            authorization_detail_type_repository,
+            client_id.as_str(),
+            name.as_str(),
+        )
+        .await
+        .map_err(AuthorizationDetailTypeControllerError::from)?;
+
+        Ok(StatusCode::NO_CONTENT)
+    }
+}
+
+pub enum AuthorizationDetailTypeControllerError {
+    AlreadyExists,
+    InvalidType,
+    NotFound,
+
+    InternalError,
+}
+
+impl AuthorizationDetailTypeControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound => StatusCode::NOT_FOUND,
+
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::AlreadyExists => {
+                "An authorization detail type with the provided name already exists."
+            }
+            Self::InvalidType => "The provided authorization detail type is invalid.",
+            Self::NotFound => {
+                "Unable to find an authorization detail type matching the requested criteria."
+            }
+
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<AuthorizationDetailServiceError> for AuthorizationDetailTypeControllerError {
+    fn from(err: AuthorizationDetailServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            AuthorizationDetailServiceError::AlreadyExists => Self::AlreadyExists,
+            AuthorizationDetailServiceError::NotCreated => Self::InvalidType,
+            AuthorizationDetailServiceError::NotFound
+            | AuthorizationDetailServiceError::NotDeleted => Self::NotFound,
+
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for AuthorizationDetailTypeControllerError {
+    fn into_response(self) -> axum::response::Response {
+        (self.error_code(), self.error_message()).into_response()
+    }
+}
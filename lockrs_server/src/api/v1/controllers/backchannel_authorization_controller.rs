@@ -0,0 +1,355 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use scoped_futures::ScopedFutureExt;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::responses::BackchannelAuthorizationResponse,
+    db::{DbContext, DbContextError},
+    models::GrantType,
+    oauth2::v1::{
+        models::{
+            BackchannelAuthorizationModel, BackchannelAuthorizationStatus, BackchannelDeliveryMode,
+            ScopeModel, TokenModel,
+        },
+        services::{
+            BackchannelAuthorizationService, BackchannelAuthorizationServiceError, TokenService,
+            TokenServiceError,
+        },
+    },
+    services::{ClientPolicyService, ClientPolicyServiceError},
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct BackchannelAuthorizationUpdateRequest {
+    pub approved: bool,
+}
+
+pub struct BackchannelAuthorizationController;
+
+impl BackchannelAuthorizationController {
+    pub async fn read(
+        State(state): State<AppState>,
+        Path((user_id, auth_req_id)): Path<(Uuid, String)>,
+    ) -> Result<BackchannelAuthorizationResponse, BackchannelAuthorizationControllerError> {
+        tracing::trace!(method = "read", user_id = user_id.to_string());
+
+        let db_context = &state.db_context;
+        let backchannel_authorization_repository = &*state
+            .repository_container
+            .as_ref()
+            .backchannel_authorization_repository;
+
+        let backchannel_authorization = BackchannelAuthorizationService::get_for_user(
+            db_context,
+            backchannel_authorization_repository,
+            &user_id,
+            &auth_req_id,
+        )
+        .await
+        .map_err(BackchannelAuthorizationControllerError::from)?;
+
+        Ok(BackchannelAuthorizationResponse {
+            client_id: backchannel_authorization.client_id,
+            binding_message: backchannel_authorization.binding_message,
+            scopes: backchannel_authorization.scopes,
+            authorization_details: backchannel_authorization.authorization_details,
+            expires_at: backchannel_authorization.expires_at.timestamp(),
+        })
+    }
+
+    pub async fn update(
+        State(state): State<AppState>,
+        Path((user_id, auth_req_id)): Path<(Uuid, String)>,
+        Json(update_request): Json<BackchannelAuthorizationUpdateRequest>,
+    ) -> Result<StatusCode, BackchannelAuthorizationControllerError> {
+        tracing::trace!(
+            method = "update",
+            user_id = user_id.to_string(),
+            data = ?update_request
+        );
+
+        let db_context = &state.db_context;
+        // borrowed by the unit of work, as the state is moved into the notification after it
+        let app_state = &state;
+
+        // a push mode request is answered with its tokens, which are issued along with resolving
+        // it, so a failure part way through leaves the request pending for the user to retry
+        let (backchannel_authorization, token) = db_context
+            .transaction::<_, BackchannelAuthorizationControllerError, _>(|db_context| {
+                async move {
+                    let backchannel_authorization_repository = &*app_state
+                        .repository_container
+                        .as_ref()
+                        .backchannel_authorization_repository;
+
+                    let backchannel_authorization = BackchannelAuthorizationService::resolve(
+                        db_context,
+                        backchannel_authorization_repository,
+                        &user_id,
+                        &auth_req_id,
+                        update_request.approved,
+                    )
+                    .await
+                    .map_err(BackchannelAuthorizationControllerError::from)?;
+
+                    if backchannel_authorization.delivery_mode != BackchannelDeliveryMode::Push {
+                        return Ok((backchannel_authorization, None));
+                    }
+
+                    let token =
+                        Self::issue_push_token(app_state, db_context, &backchannel_authorization)
+                            .await?;
+
+                    Ok((backchannel_authorization, token))
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        // the user is not kept waiting on the client, which is notified in the background
+        match backchannel_authorization.delivery_mode {
+            BackchannelDeliveryMode::Poll => {}
+            BackchannelDeliveryMode::Ping => {
+                let allow_private_endpoints = state.config.allow_private_notification_endpoints;
+
+                tokio::spawn(async move {
+                    let body = json!({ "auth_req_id": backchannel_authorization.auth_req_id });
+
+                    // the client is still able to poll if the ping is not delivered
+                    let _ = BackchannelAuthorizationService::notify_client_with_retry(
+                        &backchannel_authorization,
+                        &body,
+                        allow_private_endpoints,
+                    )
+                    .await;
+                });
+            }
+            BackchannelDeliveryMode::Push => {
+                tokio::spawn(async move {
+                    if let Err(err) =
+                        Self::push_result(&state, &backchannel_authorization, token).await
+                    {
+                        tracing::error!(
+                            error = err.error_message(),
+                            client_id = backchannel_authorization.client_id
+                        );
+                    }
+                });
+            }
+        }
+
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Consumes a resolved push mode request, which is never polled, issuing tokens for it if
+    /// it was approved and the client may be issued them.
+    async fn issue_push_token(
+        state: &AppState,
+        db_context: &Arc<DbContext>,
+        backchannel_authorization: &BackchannelAuthorizationModel,
+    ) -> Result<Option<TokenModel>, BackchannelAuthorizationControllerError> {
+        tracing::trace!(method = "issue_push_token", ?backchannel_authorization);
+
+        let backchannel_authorization_repository = &*state
+            .repository_container
+            .as_ref()
+            .backchannel_authorization_repository;
+
+        BackchannelAuthorizationService::consume(
+            db_context,
+            backchannel_authorization_repository,
+            &backchannel_authorization.auth_req_id,
+        )
+        .await
+        .map_err(BackchannelAuthorizationControllerError::from)?;
+
+        let client_policy_repository =
+            &*state.repository_container.as_ref().client_policy_repository;
+
+        let client_policy = ClientPolicyService::get_policy(
+            db_context,
+            client_policy_repository,
+            &backchannel_authorization.client_id,
+        )
+        .await
+        .map_err(BackchannelAuthorizationControllerError::from)?;
+
+        // push mode skips the token endpoint, so the grant type restriction is applied here
+        if backchannel_authorization.status != BackchannelAuthorizationStatus::Approved
+            || !client_policy.allows(GrantType::Ciba)
+        {
+            return Ok(None);
+        }
+
+        let access_token_repository = &*state.repository_container.as_ref().access_token_repository;
+        let refresh_token_repository =
+            &*state.repository_container.as_ref().refresh_token_repository;
+
+        let token = TokenService::create_token(
+            db_context,
+            access_token_repository,
+            refresh_token_repository,
+            &client_policy,
+            GrantType::Ciba,
+            Some(&backchannel_authorization.user_id),
+            ScopeModel::new(&backchannel_authorization.scopes),
+            &backchannel_authorization.authorization_details,
+            None,
+        )
+        .await
+        .map_err(BackchannelAuthorizationControllerError::from)?;
+
+        Ok(Some(token))
+    }
+
+    /// Delivers the result of a push mode request to the client, once the tokens issued for it
+    /// have been committed. They are revoked if the client never receives them.
+    async fn push_result(
+        state: &AppState,
+        backchannel_authorization: &BackchannelAuthorizationModel,
+        token: Option<TokenModel>,
+    ) -> Result<(), BackchannelAuthorizationControllerError> {
+        tracing::trace!(method = "push_result", ?backchannel_authorization);
+
+        let body = match (&backchannel_authorization.status, token.as_ref()) {
+            (BackchannelAuthorizationStatus::Approved, Some(token)) => {
+                let mut body = json!({
+                    "auth_req_id": backchannel_authorization.auth_req_id,
+                    "token_type": token.token_type,
+                    "expires_in": token.expires_in,
+                    "access_token": token.access_token,
+                    "scopes": token.scopes,
+                });
+
+                if let Some(refresh_token) = token.refresh_token.as_deref() {
+                    body["refresh_token"] = json!(refresh_token);
+                }
+
+                body
+            }
+            (BackchannelAuthorizationStatus::Approved, None) => json!({
+                "auth_req_id": backchannel_authorization.auth_req_id,
+                "error": "unauthorized_client",
+                "error_description": "The client is not authorized to use the provided grant_type.",
+            }),
+            _ => json!({
+                "auth_req_id": backchannel_authorization.auth_req_id,
+                "error": "access_denied",
+                "error_description": "The user denied the authorization request.",
+            }),
+        };
+
+        let delivery = BackchannelAuthorizationService::notify_client_with_retry(
+            backchannel_authorization,
+            &body,
+            state.config.allow_private_notification_endpoints,
+        )
+        .await
+        .map_err(BackchannelAuthorizationControllerError::from);
+
+        if let (Err(_), Some(token)) = (&delivery, token.as_ref()) {
+            let access_token_repository =
+                &*state.repository_container.as_ref().access_token_repository;
+            let refresh_token_repository =
+                &*state.repository_container.as_ref().refresh_token_repository;
+
+            TokenService::revoke_token(
+                &state.db_context,
+                access_token_repository,
+                refresh_token_repository,
+                token,
+            )
+            .await
+            .map_err(BackchannelAuthorizationControllerError::from)?;
+        }
+
+        delivery
+    }
+}
+
+pub enum BackchannelAuthorizationControllerError {
+    NotFound,
+    AlreadyResolved,
+    NotificationFailed,
+
+    InternalError,
+}
+
+impl BackchannelAuthorizationControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::AlreadyResolved => StatusCode::BAD_REQUEST,
+            Self::NotificationFailed => StatusCode::BAD_GATEWAY,
+
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::NotFound => "The requested authorization request was not found or has expired.",
+            Self::AlreadyResolved => {
+                "The requested authorization request has already been approved or denied."
+            }
+            Self::NotificationFailed => "The client could not be notified of the result.",
+
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<BackchannelAuthorizationServiceError> for BackchannelAuthorizationControllerError {
+    fn from(err: BackchannelAuthorizationServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            BackchannelAuthorizationServiceError::NotFound => Self::NotFound,
+            BackchannelAuthorizationServiceError::NotUpdated => Self::AlreadyResolved,
+            BackchannelAuthorizationServiceError::NotificationFailed => Self::NotificationFailed,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<TokenServiceError> for BackchannelAuthorizationControllerError {
+    fn from(err: TokenServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<DbContextError> for BackchannelAuthorizationControllerError {
+    fn from(err: DbContextError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<ClientPolicyServiceError> for BackchannelAuthorizationControllerError {
+    fn from(err: ClientPolicyServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl IntoResponse for BackchannelAuthorizationControllerError {
+    fn into_response(self) -> axum::response::Response {
+        (self.error_code(), self.error_message()).into_response()
+    }
+}
@@ -1,9 +1,15 @@
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::Duration;
 use serde::Deserialize;
 use url::Url;
 
 use crate::{
-    api::v1::responses::ClientResponse,
+    api::v1::responses::{ClientRegistrationResponse, ClientSecretResponse},
     models::ClientRegistration,
     services::{ClientAuthService, ClientAuthServiceError},
     utils::extractors::SessionJwt,
@@ -19,6 +25,14 @@ pub struct ClientCreateRequest {
     pub redirect_url: Url,
 }
 
+/// lifetimes are in seconds. the new secret does not expire unless `expires_in` is set, and the
+/// client's other secrets stay valid for `previous_secret_expires_in` (one day by default)
+#[derive(Debug, Default, Deserialize)]
+pub struct ClientSecretCreateRequest {
+    pub expires_in: Option<i64>,
+    pub previous_secret_expires_in: Option<i64>,
+}
+
 pub struct ClientAuthController;
 
 impl ClientAuthController {
@@ -26,7 +40,7 @@ impl ClientAuthController {
         State(state): State<AppState>,
         SessionJwt(auth_info): SessionJwt,
         Json(new_client_request): Json<ClientCreateRequest>,
-    ) -> Result<ClientResponse, ClientAuthControllerError> {
+    ) -> Result<ClientRegistrationResponse, ClientAuthControllerError> {
         tracing::trace!(
             method = "register",
             params = ?new_client_request
@@ -44,20 +58,64 @@ impl ClientAuthController {
         let db_context = &state.db_context;
         let client_auth_repository = &*state.repository_container.as_ref().client_auth_repository;
 
-        let client = ClientAuthService::register(db_context, client_auth_repository, new_client)
-            .await
-            .map_err(ClientAuthControllerError::from)?;
+        let (client, secret) =
+            ClientAuthService::register(db_context, client_auth_repository, new_client)
+                .await
+                .map_err(ClientAuthControllerError::from)?;
 
-        Ok(ClientResponse {
+        Ok(ClientRegistrationResponse {
             id: client.id,
+            secret,
             name: client.name,
             description: client.description,
             homepage_url: client.homepage_url,
         })
     }
+
+    pub async fn rotate_secret(
+        State(state): State<AppState>,
+        Path(client_id): Path<String>,
+        create_request: Option<Json<ClientSecretCreateRequest>>,
+    ) -> Result<ClientSecretResponse, ClientAuthControllerError> {
+        let Json(create_request) = create_request.unwrap_or_default();
+
+        tracing::trace!(
+            method = "rotate_secret",
+            client_id,
+            params = ?create_request
+        );
+
+        let db_context = &state.db_context;
+        let client_auth_repository = &*state.repository_container.as_ref().client_auth_repository;
+
+        let (secret, client_secret) = ClientAuthService::rotate_secret(
+            db_context,
+            client_auth_repository,
+            client_id.as_str(),
+            create_request.expires_in.map(Duration::seconds),
+            create_request
+                .previous_secret_expires_in
+                .map(Duration::seconds)
+                .unwrap_or_else(|| Duration::days(1)),
+        )
+        .await
+        .map_err(ClientAuthControllerError::from)?;
+
+        Ok(ClientSecretResponse {
+            client_id: client_secret.client_id,
+            secret,
+            created_at: client_secret.created_at.timestamp(),
+            expires_at: client_secret
+                .expires_at
+                .map(|expires_at| expires_at.timestamp()),
+        })
+    }
 }
 
 pub enum ClientAuthControllerError {
+    NotFound,
+    PublicClient,
+    InvalidLifetime,
     BadRequest,
     Internal,
 }
@@ -65,6 +123,7 @@ pub enum ClientAuthControllerError {
 impl ClientAuthControllerError {
     pub fn error_code(&self) -> StatusCode {
         match self {
+            Self::NotFound => StatusCode::NOT_FOUND,
             Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
             _ => StatusCode::BAD_REQUEST,
         }
@@ -72,6 +131,9 @@ impl ClientAuthControllerError {
 
     pub fn error_message(&self) -> &'static str {
         match self {
+            Self::NotFound => "Unable to find a client matching the requested criteria.",
+            Self::PublicClient => "Public clients do not use client secrets.",
+            Self::InvalidLifetime => "The provided secret lifetimes are invalid. \"expires_in\" must be positive and \"previous_secret_expires_in\" may not be negative.",
             Self::BadRequest => "Unable to perform the requested operation.",
             Self::Internal => {
                 "An error has occurred while processing your request. Please try again later."
@@ -85,6 +147,9 @@ impl From<ClientAuthServiceError> for ClientAuthControllerError {
         tracing::error!(error = %err);
 
         match err {
+            ClientAuthServiceError::NotFound => Self::NotFound,
+            ClientAuthServiceError::PublicClient => Self::PublicClient,
+            ClientAuthServiceError::InvalidLifetime => Self::InvalidLifetime,
             ClientAuthServiceError::InternalError => Self::Internal,
             _ => Self::BadRequest,
         }
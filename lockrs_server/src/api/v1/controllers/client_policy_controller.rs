@@ -0,0 +1,194 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::Duration;
+use serde::Deserialize;
+use url::Url;
+
+use crate::{
+    api::v1::responses::ClientPolicyResponse,
+    models::{ClientPolicyModel, GrantType},
+    services::{ClientPolicyService, ClientPolicyServiceError},
+    AppState,
+};
+
+/// lifetimes are in seconds
+#[derive(Debug, Deserialize)]
+pub struct ClientPolicyUpdateRequest {
+    pub grant_types: Vec<GrantType>,
+    pub access_token_lifetime: i64,
+    pub refresh_token_lifetime: i64,
+    pub id_token_lifetime: i64,
+    pub absolute_refresh_lifetime: Option<i64>,
+    pub sliding_refresh_window: Option<i64>,
+    #[serde(default)]
+    pub always_issue_refresh_token: bool,
+    /// has to be a public https endpoint
+    pub backchannel_client_notification_endpoint: Option<Url>,
+}
+
+pub struct ClientPolicyController;
+
+impl ClientPolicyController {
+    pub async fn read(
+        State(state): State<AppState>,
+        Path(client_id): Path<String>,
+    ) -> Result<ClientPolicyResponse, ClientPolicyControllerError> {
+        tracing::trace!(method = "read", client_id);
+
+        let db_context = &state.db_context;
+        let client_policy_repository =
+            &*state.repository_container.as_ref().client_policy_repository;
+
+        let client_policy =
+            ClientPolicyService::get_policy(db_context, client_policy_repository, &client_id)
+                .await
+                .map_err(ClientPolicyControllerError::from)?;
+
+        Ok(Self::into_response(client_policy))
+    }
+
+    pub async fn update(
+        State(state): State<AppState>,
+        Path(client_id): Path<String>,
+        Json(update_request): Json<ClientPolicyUpdateRequest>,
+    ) -> Result<ClientPolicyResponse, ClientPolicyControllerError> {
+        tracing::trace!(
+            method = "update",
+            client_id,
+            params = ?update_request
+        );
+
+        let db_context = &state.db_context;
+        let client_policy_repository =
+            &*state.repository_container.as_ref().client_policy_repository;
+
+        let client_policy = ClientPolicyModel::new(
+            &client_id,
+            &update_request.grant_types,
+            &Duration::seconds(update_request.access_token_lifetime),
+            &Duration::seconds(update_request.refresh_token_lifetime),
+            &Duration::seconds(update_request.id_token_lifetime),
+            update_request
+                .absolute_refresh_lifetime
+                .map(Duration::seconds)
+                .as_ref(),
+            update_request
+                .sliding_refresh_window
+                .map(Duration::seconds)
+                .as_ref(),
+            update_request.always_issue_refresh_token,
+            update_request
+                .backchannel_client_notification_endpoint
+                .as_ref()
+                .map(|endpoint| endpoint.as_str()),
+        );
+
+        let client_policy = ClientPolicyService::set_policy(
+            db_context,
+            client_policy_repository,
+            &client_policy,
+            state.config.allow_private_notification_endpoints,
+        )
+        .await
+        .map_err(ClientPolicyControllerError::from)?;
+
+        Ok(Self::into_response(client_policy))
+    }
+
+    pub async fn delete(
+        State(state): State<AppState>,
+        Path(client_id): Path<String>,
+    ) -> Result<StatusCode, ClientPolicyControllerError> {
+        tracing::trace!(method = "delete", client_id);
+
+        let db_context = &state.db_context;
+        let client_policy_repository =
+            &*state.repository_container.as_ref().client_policy_repository;
+
+        ClientPolicyService::reset_policy(db_context, client_policy_repository, &client_id)
+            .await
+            .map_err(ClientPolicyControllerError::from)?;
+
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    fn into_response(client_policy: ClientPolicyModel) -> ClientPolicyResponse {
+        ClientPolicyResponse {
+            client_id: client_policy.client_id,
+            grant_types: client_policy.grant_types,
+            access_token_lifetime: client_policy.access_token_lifetime.num_seconds(),
+            refresh_token_lifetime: client_policy.refresh_token_lifetime.num_seconds(),
+            id_token_lifetime: client_policy.id_token_lifetime.num_seconds(),
+            absolute_refresh_lifetime: client_policy
+                .absolute_refresh_lifetime
+                .map(|lifetime| lifetime.num_seconds()),
+            sliding_refresh_window: client_policy
+                .sliding_refresh_window
+                .map(|window| window.num_seconds()),
+            always_issue_refresh_token: client_policy.always_issue_refresh_token,
+            backchannel_client_notification_endpoint: client_policy
+                .backchannel_client_notification_endpoint,
+        }
+    }
+}
+
+pub enum ClientPolicyControllerError {
+    InvalidPolicy,
+    InvalidNotificationEndpoint,
+    NotFound,
+
+    InternalError,
+}
+
+impl ClientPolicyControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidPolicy | Self::InvalidNotificationEndpoint => StatusCode::BAD_REQUEST,
+            Self::NotFound => StatusCode::NOT_FOUND,
+
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::InvalidPolicy => "The provided policy is invalid. At least one grant type is required, lifetimes must be positive, and no refresh lifetime may exceed the absolute refresh lifetime.",
+            Self::InvalidNotificationEndpoint => "The provided backchannel_client_notification_endpoint is invalid. It must be an https url on a public address.",
+            Self::NotFound => "The client does not have a custom policy.",
+
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<ClientPolicyServiceError> for ClientPolicyControllerError {
+    fn from(err: ClientPolicyServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            ClientPolicyServiceError::InvalidPolicy | ClientPolicyServiceError::NotCreated => {
+                Self::InvalidPolicy
+            }
+            ClientPolicyServiceError::InvalidNotificationEndpoint => {
+                Self::InvalidNotificationEndpoint
+            }
+            ClientPolicyServiceError::NotFound | ClientPolicyServiceError::NotDeleted => {
+                Self::NotFound
+            }
+
+            ClientPolicyServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for ClientPolicyControllerError {
+    fn into_response(self) -> axum::response::Response {
+        (self.error_code(), self.error_message()).into_response()
+    }
+}
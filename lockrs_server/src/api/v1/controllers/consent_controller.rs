@@ -0,0 +1,279 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::Duration;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        responses::{ConsentListResponse, ConsentResponse},
+        services::{EmailVerificationService, EmailVerificationServiceError},
+    },
+    oauth2::v1::{
+        models::ConsentModel,
+        services::{
+            AuthorizationDetailService, AuthorizationDetailServiceError, ConsentService,
+            ConsentServiceError, ScopeService, ScopeServiceError,
+        },
+    },
+    services::{ClientService, ClientServiceError},
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ConsentCreateRequest {
+    pub client_id: String,
+    pub scope: String,
+    /// the authorization details the user approved, checked against the client's registered types
+    pub authorization_details: Option<serde_json::Value>,
+    /// seconds the consent should be remembered for, forever if omitted
+    pub remember_for: Option<i64>,
+}
+
+pub struct ConsentController;
+
+impl ConsentController {
+    pub async fn read_all(
+        State(state): State<AppState>,
+        Path(user_id): Path<Uuid>,
+    ) -> Result<ConsentListResponse, ConsentControllerError> {
+        tracing::trace!(method = "read_all", user_id = user_id.to_string());
+
+        let db_context = &state.db_context;
+        let consent_repository = &*state.repository_container.as_ref().consent_repository;
+
+        let consents =
+            ConsentService::get_consents_for_user(db_context, consent_repository, &user_id)
+                .await
+                .map_err(ConsentControllerError::from)?;
+
+        Ok(ConsentListResponse {
+            consents: consents
+                .into_iter()
+                .map(Self::into_response)
+                .collect::<Vec<ConsentResponse>>(),
+        })
+    }
+
+    pub async fn create(
+        State(state): State<AppState>,
+        Path(user_id): Path<Uuid>,
+        Json(consent_request): Json<ConsentCreateRequest>,
+    ) -> Result<ConsentResponse, ConsentControllerError> {
+        tracing::trace!(
+            method = "create",
+            user_id = user_id.to_string(),
+            data = ?consent_request
+        );
+
+        let remember_for = match consent_request.remember_for {
+            Some(seconds) if seconds <= 0 => {
+                tracing::error!(error = "Consent must be remembered for a positive duration");
+                return Err(ConsentControllerError::InvalidRememberFor);
+            }
+            Some(seconds) => Some(Duration::seconds(seconds)),
+            None => None,
+        };
+
+        let db_context = &state.db_context;
+
+        if state.config.email_verification.is_required_for_consent() {
+            let user_repository = &*state.repository_container.as_ref().user_repository;
+            EmailVerificationService::ensure_verified(db_context, user_repository, &user_id)
+                .await
+                .map_err(ConsentControllerError::from)?;
+        }
+
+        let client_repository = &*state.repository_container.as_ref().client_repository;
+
+        ClientService::get_client_by_id(
+            db_context,
+            client_repository,
+            consent_request.client_id.as_str(),
+        )
+        .await
+        .map_err(ConsentControllerError::from)?;
+
+        let scope_repository = &*state.repository_container.as_ref().scope_repository;
+        let scopes =
+            ScopeService::get_from_list(db_context, scope_repository, &consent_request.scope)
+                .await
+                .map_err(ConsentControllerError::from)?;
+
+        let authorization_details = match consent_request.authorization_details.as_ref() {
+            Some(authorization_details) => {
+                let authorization_detail_type_repository = &*state
+                    .repository_container
+                    .as_ref()
+                    .authorization_detail_type_repository;
+
+                AuthorizationDetailService::get_from_json(
+                    db_context,
+                    authorization_detail_type_repository,
+                    consent_request.client_id.as_str(),
+                    authorization_details.to_string().as_str(),
+                )
+                .await
+                .map_err(ConsentControllerError::from)?
+            }
+            None => Vec::new(),
+        };
+
+        let consent_repository = &*state.repository_container.as_ref().consent_repository;
+        let consent = ConsentService::grant(
+            db_context,
+            consent_repository,
+            &user_id,
+            consent_request.client_id.as_str(),
+            scopes,
+            &authorization_details,
+            remember_for,
+        )
+        .await
+        .map_err(ConsentControllerError::from)?;
+
+        Ok(Self::into_response(consent))
+    }
+
+    pub async fn delete(
+        State(state): State<AppState>,
+        Path((user_id, client_id)): Path<(Uuid, String)>,
+    ) -> Result<StatusCode, ConsentControllerError> {
+        tracing::trace!(method = "delete", user_id = user_id.to_string(), client_id);
+
+        let db_context = &state.db_context;
+        let consent_repository = &*state.repository_container.as_ref().consent_repository;
+        let refresh_token_repository =
+            &*state.repository_container.as_ref().refresh_token_repository;
+
+        ConsentService::revoke(
+            db_context,
+            consent_repository,
+            refresh_token_repository,
+            &user_id,
+            client_id.as_str(),
+        )
+        .await
+        .map_err(ConsentControllerError::from)?;
+
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    fn into_response(consent: ConsentModel) -> ConsentResponse {
+        ConsentResponse {
+            client_id: consent.client_id,
+            scopes: consent.scopes,
+            granted_at: consent.granted_at.timestamp(),
+            expires_at: consent.expires_at.map(|e| e.timestamp()),
+            authorization_details: consent.authorization_details,
+        }
+    }
+}
+
+pub enum ConsentControllerError {
+    NotFound,
+    InvalidClient,
+    InvalidScopes,
+    InvalidAuthorizationDetails,
+    InvalidRememberFor,
+    EmailNotVerified,
+
+    BadRequest,
+    InternalError,
+}
+
+impl ConsentControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::EmailNotVerified => StatusCode::FORBIDDEN,
+
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::NotFound => "No consent was found for the requested client.",
+            Self::InvalidClient => "The provided client id is invalid.",
+            Self::InvalidScopes => "The provided scopes are invalid.",
+            Self::InvalidAuthorizationDetails => "The provided authorization details are invalid.",
+            Self::InvalidRememberFor => "The provided remember_for duration must be positive.",
+            Self::EmailNotVerified => "The email address must be verified before granting consent.",
+
+            Self::BadRequest => "Unable to perform the requested operation.",
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<ConsentServiceError> for ConsentControllerError {
+    fn from(err: ConsentServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            ConsentServiceError::NotFound | ConsentServiceError::NotDeleted => Self::NotFound,
+            ConsentServiceError::NotCreated => Self::BadRequest,
+
+            ConsentServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl From<ClientServiceError> for ConsentControllerError {
+    fn from(err: ClientServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            ClientServiceError::NotFound => Self::InvalidClient,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<ScopeServiceError> for ConsentControllerError {
+    fn from(err: ScopeServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            ScopeServiceError::InvalidScopes => Self::InvalidScopes,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<AuthorizationDetailServiceError> for ConsentControllerError {
+    fn from(err: AuthorizationDetailServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            AuthorizationDetailServiceError::InvalidAuthorizationDetails
+            | AuthorizationDetailServiceError::UnknownType => Self::InvalidAuthorizationDetails,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<EmailVerificationServiceError> for ConsentControllerError {
+    fn from(err: EmailVerificationServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            EmailVerificationServiceError::NotVerified => Self::EmailNotVerified,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for ConsentControllerError {
+    fn into_response(self) -> axum::response::Response {
+        (self.error_code(), self.error_message()).into_response()
+    }
+}
@@ -0,0 +1,134 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        responses::UserResponse,
+        services::{EmailVerificationService, EmailVerificationServiceError},
+    },
+    AppState,
+};
+
+#[derive(Deserialize)]
+pub struct EmailVerificationRequest {
+    pub token: String,
+}
+
+pub struct EmailVerificationController;
+
+impl EmailVerificationController {
+    pub async fn verify(
+        State(state): State<AppState>,
+        Json(verification_request): Json<EmailVerificationRequest>,
+    ) -> Result<UserResponse, EmailVerificationControllerError> {
+        tracing::trace!(method = "verify");
+
+        let db_context = &state.db_context;
+        let user_repository = &*state.repository_container.as_ref().user_repository;
+
+        let user = EmailVerificationService::verify(
+            db_context,
+            user_repository,
+            state.config.link_signing_key.as_slice(),
+            verification_request.token.as_str(),
+        )
+        .await
+        .map_err(EmailVerificationControllerError::from)?;
+
+        Ok(UserResponse {
+            id: user.id,
+            email: user.email,
+            email_verified: user.email_verified,
+        })
+    }
+
+    pub async fn resend(
+        State(state): State<AppState>,
+        Path(user_id): Path<Uuid>,
+    ) -> Result<StatusCode, EmailVerificationControllerError> {
+        tracing::trace!(method = "resend", user_id = user_id.to_string());
+
+        let db_context = &state.db_context;
+        let user_repository = &*state.repository_container.as_ref().user_repository;
+
+        EmailVerificationService::resend_verification(
+            db_context,
+            user_repository,
+            &*state.mailer,
+            state.config.link_signing_key.as_slice(),
+            state.config.frontend_url.as_str(),
+            &user_id,
+        )
+        .await
+        .map_err(EmailVerificationControllerError::from)?;
+
+        Ok(StatusCode::NO_CONTENT)
+    }
+}
+
+pub enum EmailVerificationControllerError {
+    NotFound,
+    AlreadyVerified,
+    InvalidToken,
+    DeliveryFailed,
+
+    InternalError,
+}
+
+impl EmailVerificationControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::AlreadyVerified => StatusCode::CONFLICT,
+            Self::InvalidToken => StatusCode::BAD_REQUEST,
+            Self::DeliveryFailed => StatusCode::SERVICE_UNAVAILABLE,
+
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::NotFound => "The requested user was not found.",
+            Self::AlreadyVerified => "The email address of this account is already verified.",
+            Self::InvalidToken => {
+                "The verification link is invalid or has expired. Please request a new one."
+            }
+            Self::DeliveryFailed => {
+                "The verification mail could not be sent. Please try again later."
+            }
+
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<EmailVerificationServiceError> for EmailVerificationControllerError {
+    fn from(err: EmailVerificationServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            EmailVerificationServiceError::NotFound => Self::NotFound,
+            EmailVerificationServiceError::AlreadyVerified => Self::AlreadyVerified,
+            EmailVerificationServiceError::InvalidToken => Self::InvalidToken,
+            EmailVerificationServiceError::DeliveryFailed => Self::DeliveryFailed,
+
+            EmailVerificationServiceError::NotVerified
+            | EmailVerificationServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for EmailVerificationControllerError {
+    fn into_response(self) -> axum::response::Response {
+        (self.error_code(), self.error_message()).into_response()
+    }
+}
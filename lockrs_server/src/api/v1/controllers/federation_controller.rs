@@ -0,0 +1,235 @@
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+
+use crate::{
+    api::v1::{
+        responses::{
+            FederationAuthorizationResponse, FederationProviderListResponse, SessionTokenResponse,
+        },
+        services::{FederationService, FederationServiceError},
+    },
+    utils::extractors::Cookies,
+    AppState, FederationProvider,
+};
+
+/// What the provider sent the user back to the frontend with.
+#[derive(Debug, Deserialize)]
+pub struct FederationCallbackRequest {
+    pub code: String,
+    pub state: String,
+}
+
+pub struct FederationController;
+
+impl FederationController {
+    pub async fn read_all(State(state): State<AppState>) -> FederationProviderListResponse {
+        tracing::trace!(method = "read_all");
+
+        FederationProviderListResponse {
+            providers: state
+                .config
+                .federation_providers
+                .iter()
+                .map(|provider| provider.name.to_owned())
+                .collect(),
+        }
+    }
+
+    /// Starts a sign-in with the provider, answering with the url to send the user to along with
+    /// a fresh binding cookie.
+    pub async fn begin(
+        State(state): State<AppState>,
+        Path(provider_name): Path<String>,
+    ) -> Result<FederationAuthorizationResponse, FederationControllerError> {
+        tracing::trace!(method = "begin", provider = provider_name);
+
+        let provider = Self::provider(&state, provider_name.as_str())?;
+
+        let db_context = &state.db_context;
+        let federation_state_repository = &*state
+            .repository_container
+            .as_ref()
+            .federation_state_repository;
+
+        let binding = FederationService::generate_binding();
+
+        let authorization_url = FederationService::begin(
+            db_context,
+            federation_state_repository,
+            provider,
+            state.config.frontend_url.as_str(),
+            binding.as_str(),
+        )
+        .await
+        .map_err(FederationControllerError::from)?;
+
+        Ok(FederationAuthorizationResponse {
+            authorization_url,
+            binding,
+        })
+    }
+
+    /// Completes a sign-in with the provider from the browser that started it, signing in the
+    /// local user the upstream identity is linked to.
+    pub async fn callback(
+        State(state): State<AppState>,
+        Path(provider_name): Path<String>,
+        cookies: Option<Cookies>,
+        Json(callback_request): Json<FederationCallbackRequest>,
+    ) -> Result<SessionTokenResponse, FederationControllerError> {
+        tracing::trace!(method = "callback", provider = provider_name);
+
+        let provider = Self::provider(&state, provider_name.as_str())?;
+
+        let db_context = &state.db_context;
+        let federation_state_repository = &*state
+            .repository_container
+            .as_ref()
+            .federation_state_repository;
+        let federated_identity_repository = &*state
+            .repository_container
+            .as_ref()
+            .federated_identity_repository;
+        let user_repository = &*state.repository_container.as_ref().user_repository;
+        let user_auth_repository = &*state.repository_container.as_ref().user_auth_repository;
+        let session_token_repository =
+            &*state.repository_container.as_ref().session_token_repository;
+        let totp_repository = &*state.repository_container.as_ref().totp_repository;
+        let webauthn_credential_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_credential_repository;
+
+        let binding = cookies.as_ref().and_then(|Cookies(cookies)| {
+            cookies.get(FederationAuthorizationResponse::cookie_name())
+        });
+
+        let upstream_identity = FederationService::authenticate(
+            db_context,
+            federation_state_repository,
+            provider,
+            state.config.frontend_url.as_str(),
+            callback_request.code.as_str(),
+            callback_request.state.as_str(),
+            binding,
+        )
+        .await
+        .map_err(FederationControllerError::from)?;
+
+        let session_token = FederationService::sign_in(
+            db_context,
+            user_repository,
+            user_auth_repository,
+            federated_identity_repository,
+            session_token_repository,
+            totp_repository,
+            webauthn_credential_repository,
+            &state.config.password_hashing,
+            provider,
+            &upstream_identity,
+            state.config.email_verification.is_required_for_login(),
+        )
+        .await
+        .map_err(FederationControllerError::from)?;
+
+        let token_response = SessionTokenResponse {
+            session_token: session_token.token,
+            expires_at: session_token.expires_at,
+            mfa_required: session_token.mfa_pending,
+        };
+
+        Ok(token_response)
+    }
+
+    fn provider<'a>(
+        state: &'a AppState,
+        provider_name: &str,
+    ) -> Result<&'a FederationProvider, FederationControllerError> {
+        state
+            .config
+            .federation_provider(provider_name)
+            .ok_or_else(|| {
+                tracing::error!(error = "Unknown federation provider", provider_name);
+                FederationControllerError::UnknownProvider
+            })
+    }
+}
+
+pub enum FederationControllerError {
+    UnknownProvider,
+    InvalidState,
+    InvalidIdentity,
+    MissingEmail,
+    EmailTaken,
+    EmailNotVerified,
+    UpstreamFailed,
+
+    InternalError,
+}
+
+impl FederationControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::UnknownProvider => StatusCode::NOT_FOUND,
+            Self::InvalidState => StatusCode::BAD_REQUEST,
+            Self::InvalidIdentity => StatusCode::UNAUTHORIZED,
+            Self::MissingEmail => StatusCode::BAD_REQUEST,
+            Self::EmailTaken => StatusCode::CONFLICT,
+            Self::EmailNotVerified => StatusCode::FORBIDDEN,
+            Self::UpstreamFailed => StatusCode::BAD_GATEWAY,
+
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::UnknownProvider => "The identity provider was not found.",
+            Self::InvalidState => {
+                "The sign-in is invalid, expired, has already been completed or was started from another browser. Please sign in again."
+            }
+            Self::InvalidIdentity => "The identity provider's answer could not be verified.",
+            Self::MissingEmail => {
+                "The identity provider did not share an email address to create an account with."
+            }
+            Self::EmailTaken => {
+                "An account with this email address already exists. Please log in to it instead."
+            }
+            Self::EmailNotVerified => "The email address of this account has to be verified first.",
+            Self::UpstreamFailed => {
+                "The identity provider could not be reached. Please try again later."
+            }
+
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<FederationServiceError> for FederationControllerError {
+    fn from(err: FederationServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            FederationServiceError::InvalidState => Self::InvalidState,
+            FederationServiceError::InvalidIdentity => Self::InvalidIdentity,
+            FederationServiceError::MissingEmail => Self::MissingEmail,
+            FederationServiceError::EmailTaken => Self::EmailTaken,
+            FederationServiceError::EmailNotVerified => Self::EmailNotVerified,
+            FederationServiceError::UpstreamFailed => Self::UpstreamFailed,
+
+            FederationServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for FederationControllerError {
+    fn into_response(self) -> axum::response::Response {
+        (self.error_code(), self.error_message()).into_response()
+    }
+}
@@ -0,0 +1,116 @@
+use std::net::IpAddr;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::v1::services::{LoginThrottleService, LoginThrottleServiceError},
+    services::{UserService, UserServiceError},
+    AppState,
+};
+
+pub struct LockoutController;
+
+impl LockoutController {
+    /// Lifts the lockout of a user's account, and forgets the failed logins that led to it.
+    pub async fn unlock_user(
+        State(state): State<AppState>,
+        Path(user_id): Path<Uuid>,
+    ) -> Result<StatusCode, LockoutControllerError> {
+        tracing::trace!(method = "unlock_user", ?user_id);
+
+        let db_context = &state.db_context;
+        let user_repository = &*state.repository_container.as_ref().user_repository;
+        let login_attempt_repository =
+            &*state.repository_container.as_ref().login_attempt_repository;
+
+        let user = UserService::get_user_by_id(db_context, user_repository, &user_id)
+            .await
+            .map_err(LockoutControllerError::from)?;
+
+        LoginThrottleService::unlock_account(
+            db_context,
+            login_attempt_repository,
+            user.email.as_str(),
+        )
+        .await
+        .map_err(LockoutControllerError::from)?;
+
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Lifts the lockout of an address logins come from, and forgets the failed logins that led
+    /// to it.
+    pub async fn unlock_address(
+        State(state): State<AppState>,
+        Path(address): Path<IpAddr>,
+    ) -> Result<StatusCode, LockoutControllerError> {
+        tracing::trace!(method = "unlock_address", ?address);
+
+        let db_context = &state.db_context;
+        let login_attempt_repository =
+            &*state.repository_container.as_ref().login_attempt_repository;
+
+        LoginThrottleService::unlock_address(db_context, login_attempt_repository, &address)
+            .await
+            .map_err(LockoutControllerError::from)?;
+
+        Ok(StatusCode::NO_CONTENT)
+    }
+}
+
+pub enum LockoutControllerError {
+    NotFound,
+
+    InternalError,
+}
+
+impl LockoutControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound => StatusCode::NOT_FOUND,
+
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::NotFound => "The requested user was not found.",
+
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<UserServiceError> for LockoutControllerError {
+    fn from(err: UserServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            UserServiceError::NotFound => Self::NotFound,
+
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<LoginThrottleServiceError> for LockoutControllerError {
+    fn from(err: LoginThrottleServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl IntoResponse for LockoutControllerError {
+    fn into_response(self) -> axum::response::Response {
+        (self.error_code(), self.error_message()).into_response()
+    }
+}
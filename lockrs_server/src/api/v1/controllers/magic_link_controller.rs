@@ -0,0 +1,225 @@
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, Json, State},
+    http::{header::RETRY_AFTER, StatusCode},
+    response::IntoResponse,
+};
+use serde::Deserialize;
+
+use crate::{
+    api::v1::{
+        models::MagicLinkRedemption,
+        responses::{MagicLinkResponse, SessionTokenResponse},
+        services::{
+            LoginThrottleService, LoginThrottleServiceError, MagicLinkService,
+            MagicLinkServiceError,
+        },
+    },
+    utils::extractors::Cookies,
+    AppState,
+};
+
+#[derive(Deserialize)]
+pub struct MagicLinkRequest {
+    pub email: String,
+}
+
+pub struct MagicLinkController;
+
+impl MagicLinkController {
+    /// Always answers 202 with a fresh binding cookie, whether or not the address has an account
+    /// or the mail went out, so the endpoint can't be used to find out who is registered. The
+    /// lookup and the mail happen after answering, so neither can how long the answer took.
+    pub async fn request(
+        State(state): State<AppState>,
+        Json(magic_link_request): Json<MagicLinkRequest>,
+    ) -> MagicLinkResponse {
+        tracing::trace!(method = "request", email = magic_link_request.email);
+
+        let binding = MagicLinkService::generate_binding();
+        let link_binding = binding.to_owned();
+
+        tokio::spawn(async move {
+            let db_context = &state.db_context;
+            let user_auth_repository = &*state.repository_container.as_ref().user_auth_repository;
+            let magic_link_repository = &*state.repository_container.as_ref().magic_link_repository;
+
+            if let Err(err) = MagicLinkService::request_link(
+                db_context,
+                user_auth_repository,
+                magic_link_repository,
+                &*state.mailer,
+                state.config.link_signing_key.as_slice(),
+                state.config.frontend_url.as_str(),
+                magic_link_request.email.as_str(),
+                link_binding.as_str(),
+            )
+            .await
+            {
+                tracing::error!(error = %err);
+            }
+        });
+
+        MagicLinkResponse { binding }
+    }
+
+    /// Redeems the token from a sign-in link, or the code from the mail along with the address
+    /// it went to, from the browser that asked for it. Codes are short enough to guess, so wrong
+    /// ones count towards the same lockout as failed password logins.
+    pub async fn redeem(
+        State(state): State<AppState>,
+        connect_info: Option<ConnectInfo<SocketAddr>>,
+        cookies: Option<Cookies>,
+        Json(redemption): Json<MagicLinkRedemption>,
+    ) -> Result<SessionTokenResponse, MagicLinkControllerError> {
+        tracing::trace!(method = "redeem", ?redemption);
+
+        let db_context = &state.db_context;
+        let user_repository = &*state.repository_container.as_ref().user_repository;
+        let magic_link_repository = &*state.repository_container.as_ref().magic_link_repository;
+        let session_token_repository =
+            &*state.repository_container.as_ref().session_token_repository;
+        let totp_repository = &*state.repository_container.as_ref().totp_repository;
+        let webauthn_credential_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_credential_repository;
+        let login_attempt_repository =
+            &*state.repository_container.as_ref().login_attempt_repository;
+
+        let address = connect_info.map(|ConnectInfo(address)| address.ip());
+        let binding = cookies
+            .as_ref()
+            .and_then(|Cookies(cookies)| cookies.get(MagicLinkResponse::cookie_name()));
+
+        let code_email = match &redemption {
+            MagicLinkRedemption::Code { email, .. } => Some(email.as_str()),
+            MagicLinkRedemption::Link { .. } => None,
+        };
+
+        if let Some(email) = code_email {
+            LoginThrottleService::check(
+                db_context,
+                login_attempt_repository,
+                email,
+                address.as_ref(),
+            )
+            .await
+            .map_err(MagicLinkControllerError::from)?;
+        }
+
+        let redeem_result = MagicLinkService::redeem(
+            db_context,
+            user_repository,
+            magic_link_repository,
+            session_token_repository,
+            totp_repository,
+            webauthn_credential_repository,
+            state.config.link_signing_key.as_slice(),
+            &redemption,
+            binding,
+        )
+        .await;
+
+        let session_token = match (redeem_result, code_email) {
+            (Ok(session_token), _) => session_token,
+            (Err(err @ MagicLinkServiceError::InvalidToken), Some(email)) => {
+                // the redemption has failed either way, the lockout is just not enforced as early
+                if let Err(err) = LoginThrottleService::record_failure(
+                    db_context,
+                    login_attempt_repository,
+                    &state.config.login_throttle,
+                    email,
+                    address.as_ref(),
+                )
+                .await
+                {
+                    tracing::error!(error = %err);
+                }
+
+                return Err(MagicLinkControllerError::from(err));
+            }
+            (Err(err), _) => return Err(MagicLinkControllerError::from(err)),
+        };
+
+        let token_response = SessionTokenResponse {
+            session_token: session_token.token,
+            expires_at: session_token.expires_at,
+            mfa_required: session_token.mfa_pending,
+        };
+
+        Ok(token_response)
+    }
+}
+
+pub enum MagicLinkControllerError {
+    InvalidToken,
+    /// with the number of seconds until redemptions are let through again
+    LockedOut(i64),
+
+    InternalError,
+}
+
+impl MagicLinkControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidToken => StatusCode::BAD_REQUEST,
+            Self::LockedOut(_) => StatusCode::TOO_MANY_REQUESTS,
+
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::InvalidToken => {
+                "The sign-in link or code is invalid, expired, has already been used or was requested from another browser. Please request a new one."
+            }
+            Self::LockedOut(_) => "Too many failed logins. Please try again later.",
+
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<MagicLinkServiceError> for MagicLinkControllerError {
+    fn from(err: MagicLinkServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            MagicLinkServiceError::InvalidToken => Self::InvalidToken,
+
+            MagicLinkServiceError::DeliveryFailed | MagicLinkServiceError::InternalError => {
+                Self::InternalError
+            }
+        }
+    }
+}
+
+impl From<LoginThrottleServiceError> for MagicLinkControllerError {
+    fn from(err: LoginThrottleServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            LoginThrottleServiceError::Locked(retry_after) => Self::LockedOut(retry_after),
+            LoginThrottleServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for MagicLinkControllerError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            Self::LockedOut(retry_after) => (
+                self.error_code(),
+                [(RETRY_AFTER, retry_after.to_string())],
+                self.error_message(),
+            )
+                .into_response(),
+            _ => (self.error_code(), self.error_message()).into_response(),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        responses::{RecoveryCodesResponse, SessionTokenResponse, TotpEnrollmentResponse},
+        services::{MfaFactor, MfaService, MfaServiceError},
+    },
+    utils::extractors::BearerAuth,
+    AppState,
+};
+
+#[derive(Deserialize)]
+pub struct TotpConfirmRequest {
+    pub code: String,
+}
+
+#[derive(Deserialize)]
+pub struct MfaVerifyRequest {
+    pub code: Option<String>,
+    pub recovery_code: Option<String>,
+}
+
+pub struct MfaController;
+
+impl MfaController {
+    pub async fn enroll_totp(
+        State(state): State<AppState>,
+        Path(user_id): Path<Uuid>,
+    ) -> Result<TotpEnrollmentResponse, MfaControllerError> {
+        tracing::trace!(method = "enroll_totp", user_id = user_id.to_string());
+
+        let db_context = &state.db_context;
+        let user_repository = &*state.repository_container.as_ref().user_repository;
+        let totp_repository = &*state.repository_container.as_ref().totp_repository;
+
+        let enrollment =
+            MfaService::enroll_totp(db_context, user_repository, totp_repository, &user_id)
+                .await
+                .map_err(MfaControllerError::from)?;
+
+        Ok(TotpEnrollmentResponse {
+            secret: enrollment.secret,
+            provisioning_uri: enrollment.provisioning_uri,
+        })
+    }
+
+    pub async fn confirm_totp(
+        State(state): State<AppState>,
+        Path(user_id): Path<Uuid>,
+        Json(confirm_request): Json<TotpConfirmRequest>,
+    ) -> Result<RecoveryCodesResponse, MfaControllerError> {
+        tracing::trace!(method = "confirm_totp", user_id = user_id.to_string());
+
+        let db_context = &state.db_context;
+        let totp_repository = &*state.repository_container.as_ref().totp_repository;
+        let recovery_code_repository =
+            &*state.repository_container.as_ref().recovery_code_repository;
+
+        let recovery_codes = MfaService::confirm_totp(
+            db_context,
+            totp_repository,
+            recovery_code_repository,
+            &user_id,
+            confirm_request.code.as_str(),
+        )
+        .await
+        .map_err(MfaControllerError::from)?;
+
+        Ok(RecoveryCodesResponse { recovery_codes })
+    }
+
+    pub async fn disable_totp(
+        State(state): State<AppState>,
+        Path(user_id): Path<Uuid>,
+    ) -> Result<StatusCode, MfaControllerError> {
+        tracing::trace!(method = "disable_totp", user_id = user_id.to_string());
+
+        let db_context = &state.db_context;
+        let totp_repository = &*state.repository_container.as_ref().totp_repository;
+        let recovery_code_repository =
+            &*state.repository_container.as_ref().recovery_code_repository;
+
+        MfaService::disable_totp(
+            db_context,
+            totp_repository,
+            recovery_code_repository,
+            &user_id,
+        )
+        .await
+        .map_err(MfaControllerError::from)?;
+
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    pub async fn verify(
+        State(state): State<AppState>,
+        BearerAuth(session_token): BearerAuth,
+        Json(verify_request): Json<MfaVerifyRequest>,
+    ) -> Result<SessionTokenResponse, MfaControllerError> {
+        tracing::trace!(method = "verify");
+
+        let factor = match (
+            verify_request.code.as_deref(),
+            verify_request.recovery_code.as_deref(),
+        ) {
+            (Some(code), None) => MfaFactor::Totp(code),
+            (None, Some(recovery_code)) => MfaFactor::RecoveryCode(recovery_code),
+            _ => {
+                tracing::error!(error = "Expected exactly one of code or recovery_code");
+                return Err(MfaControllerError::BadRequest);
+            }
+        };
+
+        let db_context = &state.db_context;
+        let session_token_repository =
+            &*state.repository_container.as_ref().session_token_repository;
+        let totp_repository = &*state.repository_container.as_ref().totp_repository;
+        let recovery_code_repository =
+            &*state.repository_container.as_ref().recovery_code_repository;
+
+        let session_token = MfaService::verify(
+            db_context,
+            session_token_repository,
+            totp_repository,
+            recovery_code_repository,
+            session_token.as_str(),
+            &factor,
+        )
+        .await
+        .map_err(MfaControllerError::from)?;
+
+        Ok(SessionTokenResponse {
+            session_token: session_token.token,
+            expires_at: session_token.expires_at,
+            mfa_required: session_token.mfa_pending,
+        })
+    }
+}
+
+pub enum MfaControllerError {
+    NotEnrolled,
+    AlreadyEnrolled,
+    InvalidCode,
+    InvalidToken,
+
+    BadRequest,
+    InternalError,
+}
+
+impl MfaControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::NotEnrolled => StatusCode::NOT_FOUND,
+            Self::AlreadyEnrolled => StatusCode::CONFLICT,
+            Self::InvalidCode | Self::InvalidToken => StatusCode::UNAUTHORIZED,
+
+            Self::BadRequest => StatusCode::BAD_REQUEST,
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::NotEnrolled => "No TOTP authenticator is enrolled for the user.",
+            Self::AlreadyEnrolled => {
+                "A TOTP authenticator is already enrolled for the user. Disable it before enrolling another."
+            }
+            Self::InvalidCode => "The provided code is invalid, expired or has already been used.",
+            Self::InvalidToken => "The provided session token is invalid or expired. Please log in again.",
+
+            Self::BadRequest => "The data provided in the request was invalid.",
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<MfaServiceError> for MfaControllerError {
+    fn from(err: MfaServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            MfaServiceError::NotEnrolled => Self::NotEnrolled,
+            MfaServiceError::AlreadyEnrolled => Self::AlreadyEnrolled,
+            MfaServiceError::InvalidCode => Self::InvalidCode,
+            MfaServiceError::Token => Self::InvalidToken,
+
+            MfaServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for MfaControllerError {
+    fn into_response(self) -> axum::response::Response {
+        (self.error_code(), self.error_message()).into_response()
+    }
+}
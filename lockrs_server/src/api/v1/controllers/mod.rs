@@ -1,11 +1,28 @@
+mod account_controller;
+mod authorization_detail_type_controller;
+mod backchannel_authorization_controller;
 mod client_auth_controller;
 mod client_controller;
+mod client_policy_controller;
+mod consent_controller;
+mod email_verification_controller;
+mod federation_controller;
+mod lockout_controller;
+mod magic_link_controller;
+mod mfa_controller;
+mod password_reset_controller;
 mod redirect_controller;
+mod scope_controller;
 mod session_controller;
 mod user_auth_controller;
 mod user_controller;
+mod webauthn_controller;
 
 pub use self::{
-    client_auth_controller::*, client_controller::*, redirect_controller::*, session_controller::*,
-    user_auth_controller::*, user_controller::*,
+    account_controller::*, authorization_detail_type_controller::*,
+    backchannel_authorization_controller::*, client_auth_controller::*, client_controller::*,
+    client_policy_controller::*, consent_controller::*, email_verification_controller::*,
+    federation_controller::*, lockout_controller::*, magic_link_controller::*, mfa_controller::*,
+    password_reset_controller::*, redirect_controller::*, scope_controller::*,
+    session_controller::*, user_auth_controller::*, user_controller::*, webauthn_controller::*,
 };
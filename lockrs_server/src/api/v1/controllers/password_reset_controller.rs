@@ -0,0 +1,165 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    api::v1::{
+        models::{PasswordPolicyViolation, PasswordReset},
+        responses::PasswordPolicyResponse,
+        services::{PasswordResetService, PasswordResetServiceError},
+    },
+    AppState,
+};
+
+#[derive(Deserialize)]
+pub struct PasswordForgotRequest {
+    pub email: String,
+}
+
+#[derive(Deserialize)]
+pub struct PasswordResetRequest {
+    pub token: String,
+    pub password: String,
+}
+
+pub struct PasswordResetController;
+
+impl PasswordResetController {
+    /// Always answers 202, whether or not the address has an account or the mail went out, so
+    /// the endpoint can't be used to find out who is registered. The lookup and the mail happen
+    /// after answering, so neither can how long the answer took.
+    pub async fn forgot(
+        State(state): State<AppState>,
+        Json(forgot_request): Json<PasswordForgotRequest>,
+    ) -> StatusCode {
+        tracing::trace!(method = "forgot", email = forgot_request.email);
+
+        tokio::spawn(async move {
+            let db_context = &state.db_context;
+            let user_auth_repository = &*state.repository_container.as_ref().user_auth_repository;
+            let password_reset_token_repository = &*state
+                .repository_container
+                .as_ref()
+                .password_reset_token_repository;
+
+            if let Err(err) = PasswordResetService::request_reset(
+                db_context,
+                user_auth_repository,
+                password_reset_token_repository,
+                &*state.mailer,
+                state.config.frontend_url.as_str(),
+                forgot_request.email.as_str(),
+            )
+            .await
+            {
+                tracing::error!(error = %err);
+            }
+        });
+
+        StatusCode::ACCEPTED
+    }
+
+    pub async fn reset(
+        State(state): State<AppState>,
+        Json(reset_request): Json<PasswordResetRequest>,
+    ) -> Result<StatusCode, PasswordResetControllerError> {
+        tracing::trace!(method = "reset");
+
+        let password_reset = PasswordReset::new(
+            reset_request.token.as_str(),
+            reset_request.password.as_str(),
+        );
+
+        password_reset
+            .validate()
+            .map_err(|_| PasswordResetControllerError::BadRequest)?;
+
+        let db_context = &state.db_context;
+        let user_auth_repository = &*state.repository_container.as_ref().user_auth_repository;
+        let password_reset_token_repository = &*state
+            .repository_container
+            .as_ref()
+            .password_reset_token_repository;
+        let session_repository = &*state.repository_container.as_ref().session_repository;
+        let refresh_token_repository =
+            &*state.repository_container.as_ref().refresh_token_repository;
+
+        PasswordResetService::reset_password(
+            db_context,
+            user_auth_repository,
+            password_reset_token_repository,
+            session_repository,
+            refresh_token_repository,
+            &state.config.password_policy,
+            &state.config.password_hashing,
+            &password_reset,
+        )
+        .await
+        .map_err(PasswordResetControllerError::from)?;
+
+        Ok(StatusCode::NO_CONTENT)
+    }
+}
+
+pub enum PasswordResetControllerError {
+    InvalidToken,
+    PasswordPolicy(Vec<PasswordPolicyViolation>),
+
+    BadRequest,
+    InternalError,
+}
+
+impl PasswordResetControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidToken => StatusCode::BAD_REQUEST,
+            Self::PasswordPolicy(_) => StatusCode::BAD_REQUEST,
+
+            Self::BadRequest => StatusCode::BAD_REQUEST,
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::InvalidToken => {
+                "The password reset link is invalid, expired or has already been used. Please request a new one."
+            }
+            Self::PasswordPolicy(_) => "The password does not meet the password policy.",
+
+            Self::BadRequest => "The data provided in the request was invalid.",
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<PasswordResetServiceError> for PasswordResetControllerError {
+    fn from(err: PasswordResetServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            PasswordResetServiceError::InvalidToken => Self::InvalidToken,
+            PasswordResetServiceError::PasswordPolicy(violations) => {
+                Self::PasswordPolicy(violations)
+            }
+
+            PasswordResetServiceError::DeliveryFailed
+            | PasswordResetServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for PasswordResetControllerError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            Self::PasswordPolicy(violations) => (
+                StatusCode::BAD_REQUEST,
+                PasswordPolicyResponse::from(violations),
+            )
+                .into_response(),
+            _ => (self.error_code(), self.error_message()).into_response(),
+        }
+    }
+}
@@ -10,7 +10,7 @@ use uuid::Uuid;
 
 use crate::{
     api::v1::responses::{RedirectListResponse, RedirectResponse},
-    models::RedirectCreateModel,
+    models::{RedirectCreateModel, RedirectMatchMode},
     services::{ClientAuthService, ClientAuthServiceError, RedirectService, RedirectServiceError},
     utils::extractors::SessionJwt,
     AppState,
@@ -20,6 +20,8 @@ use crate::{
 pub struct RedirectCreateRequest {
     pub client_id: String,
     pub uri: Url,
+    #[serde(default)]
+    pub match_mode: RedirectMatchMode,
 }
 
 pub struct RedirectController;
@@ -49,6 +51,7 @@ impl RedirectController {
                     id: r.id,
                     client_id: r.client_id,
                     uri: r.uri,
+                    match_mode: r.match_mode,
                 })
                 .collect::<Vec<RedirectResponse>>(),
         })
@@ -67,6 +70,7 @@ impl RedirectController {
         let new_redirect = RedirectCreateModel::new(
             new_redirect_request.client_id.as_str(),
             &new_redirect_request.uri,
+            new_redirect_request.match_mode,
         );
 
         let db_context = &state.db_context;
@@ -93,6 +97,7 @@ impl RedirectController {
             id: redirect.id,
             client_id: redirect.client_id,
             uri: redirect.uri,
+            match_mode: redirect.match_mode,
         })
     }
 
@@ -114,6 +119,7 @@ impl RedirectController {
             id: redirect.id,
             client_id: redirect.client_id,
             uri: redirect.uri,
+            match_mode: redirect.match_mode,
         })
     }
 
@@ -185,7 +191,9 @@ impl From<RedirectServiceError> for RedirectControllerError {
         match err {
             RedirectServiceError::AlreadyExists => Self::AlreadyExists,
             RedirectServiceError::NotFound => Self::NotFound,
-            RedirectServiceError::NotCreated => Self::InvalidRedirect,
+            RedirectServiceError::NotCreated | RedirectServiceError::InvalidRedirect => {
+                Self::InvalidRedirect
+            }
             RedirectServiceError::TooFewRedirects => Self::TooFewRedirects,
             RedirectServiceError::NotDeleted => Self::NotDeleted,
 
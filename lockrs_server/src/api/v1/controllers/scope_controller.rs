@@ -0,0 +1,268 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    api::v1::responses::{AllowedScopesResponse, ScopeListResponse, ScopeResponse},
+    oauth2::v1::{
+        models::{AllowedScopeModel, ScopeDefinitionModel, ScopeUpdateModel},
+        services::{ScopeService, ScopeServiceError},
+    },
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ScopeCreateRequest {
+    /// the scope name without the client namespace, e.g. `photos.read`
+    pub name: String,
+    pub description: String,
+    pub consent_text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScopeUpdateRequest {
+    pub description: Option<String>,
+    pub consent_text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AllowedScopesUpdateRequest {
+    pub allowed_scopes: Vec<String>,
+    #[serde(default)]
+    pub default_scopes: Vec<String>,
+}
+
+pub struct ScopeController;
+
+impl ScopeController {
+    pub async fn read_all(
+        State(state): State<AppState>,
+        Path(client_id): Path<String>,
+    ) -> Result<ScopeListResponse, ScopeControllerError> {
+        tracing::trace!(method = "read_all", client_id);
+
+        let db_context = &state.db_context;
+        let scope_repository = &*state.repository_container.as_ref().scope_repository;
+
+        let scopes =
+            ScopeService::get_scopes_by_client(db_context, scope_repository, client_id.as_str())
+                .await
+                .map_err(ScopeControllerError::from)?;
+
+        Ok(ScopeListResponse {
+            scopes: scopes
+                .into_iter()
+                .map(Self::into_response)
+                .collect::<Vec<ScopeResponse>>(),
+        })
+    }
+
+    pub async fn create(
+        State(state): State<AppState>,
+        Path(client_id): Path<String>,
+        Json(new_scope_request): Json<ScopeCreateRequest>,
+    ) -> Result<ScopeResponse, ScopeControllerError> {
+        tracing::trace!(
+            method = "create",
+            client_id,
+            params = ?new_scope_request
+        );
+
+        let db_context = &state.db_context;
+        let scope_repository = &*state.repository_container.as_ref().scope_repository;
+
+        let scope = ScopeService::create_scope(
+            db_context,
+            scope_repository,
+            client_id.as_str(),
+            new_scope_request.name.as_str(),
+            new_scope_request.description.as_str(),
+            new_scope_request.consent_text.as_deref(),
+        )
+        .await
+        .map_err(ScopeControllerError::from)?;
+
+        Ok(Self::into_response(scope))
+    }
+
+    pub async fn update(
+        State(state): State<AppState>,
+        Path((client_id, name)): Path<(String, String)>,
+        Json(update_scope_request): Json<ScopeUpdateRequest>,
+    ) -> Result<ScopeResponse, ScopeControllerError> {
+        tracing::trace!(
+            method = "update",
+            client_id,
+            name,
+            params = ?update_scope_request
+        );
+
+        let scope_update = ScopeUpdateModel::new(
+            update_scope_request.description.as_deref(),
+            update_scope_request.consent_text.as_deref(),
+        );
+
+        let db_context = &state.db_context;
+        let scope_repository = &*state.repository_container.as_ref().scope_repository;
+
+        let scope = ScopeService::update_scope(
+            db_context,
+            scope_repository,
+            client_id.as_str(),
+            name.as_str(),
+            &scope_update,
+        )
+        .await
+        .map_err(ScopeControllerError::from)?;
+
+        Ok(Self::into_response(scope))
+    }
+
+    pub async fn delete(
+        State(state): State<AppState>,
+        Path((client_id, name)): Path<(String, String)>,
+    ) -> Result<StatusCode, ScopeControllerError> {
+        tracing::trace!(method = "delete", client_id, name);
+
+        let db_context = &state.db_context;
+        let scope_repository = &*state.repository_container.as_ref().scope_repository;
+
+        ScopeService::delete_scope(
+            db_context,
+            scope_repository,
+            client_id.as_str(),
+            name.as_str(),
+        )
+        .await
+        .map_err(ScopeControllerError::from)?;
+
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    pub async fn read_allowed(
+        State(state): State<AppState>,
+        Path(client_id): Path<String>,
+    ) -> Result<AllowedScopesResponse, ScopeControllerError> {
+        tracing::trace!(method = "read_allowed", client_id);
+
+        let db_context = &state.db_context;
+        let scope_repository = &*state.repository_container.as_ref().scope_repository;
+
+        let allowed_scopes =
+            ScopeService::get_allowed_scopes(db_context, scope_repository, client_id.as_str())
+                .await
+                .map_err(ScopeControllerError::from)?;
+
+        Ok(Self::into_allowed_response(allowed_scopes))
+    }
+
+    pub async fn update_allowed(
+        State(state): State<AppState>,
+        Path(client_id): Path<String>,
+        Json(update_request): Json<AllowedScopesUpdateRequest>,
+    ) -> Result<AllowedScopesResponse, ScopeControllerError> {
+        tracing::trace!(
+            method = "update_allowed",
+            client_id,
+            params = ?update_request
+        );
+
+        let db_context = &state.db_context;
+        let scope_repository = &*state.repository_container.as_ref().scope_repository;
+
+        let allowed_scopes = ScopeService::set_allowed_scopes(
+            db_context,
+            scope_repository,
+            client_id.as_str(),
+            &update_request.allowed_scopes,
+            &update_request.default_scopes,
+        )
+        .await
+        .map_err(ScopeControllerError::from)?;
+
+        Ok(Self::into_allowed_response(allowed_scopes))
+    }
+
+    fn into_allowed_response(allowed_scopes: Vec<AllowedScopeModel>) -> AllowedScopesResponse {
+        AllowedScopesResponse {
+            default_scopes: allowed_scopes
+                .iter()
+                .filter(|allowed_scope| allowed_scope.is_default)
+                .map(|allowed_scope| allowed_scope.scope.to_owned())
+                .collect::<Vec<String>>(),
+            allowed_scopes: allowed_scopes
+                .into_iter()
+                .map(|allowed_scope| allowed_scope.scope)
+                .collect::<Vec<String>>(),
+        }
+    }
+
+    fn into_response(scope: ScopeDefinitionModel) -> ScopeResponse {
+        ScopeResponse {
+            name: scope.name,
+            client_id: scope.client_id,
+            description: scope.description,
+            consent_text: scope.consent_text,
+        }
+    }
+}
+
+pub enum ScopeControllerError {
+    AlreadyExists,
+    InvalidName,
+    InvalidScope,
+    NotFound,
+
+    InternalError,
+}
+
+impl ScopeControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound => StatusCode::NOT_FOUND,
+
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::AlreadyExists => "A scope with the provided name already exists.",
+            Self::InvalidName => "The provided scope name is invalid. Scope names may only contain lowercase letters, digits, \".\", \"_\" and \"-\".",
+            Self::InvalidScope => "The provided scope is invalid.",
+            Self::NotFound => "Unable to find a scope matching the requested criteria.",
+
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<ScopeServiceError> for ScopeControllerError {
+    fn from(err: ScopeServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            ScopeServiceError::AlreadyExists => Self::AlreadyExists,
+            ScopeServiceError::InvalidName => Self::InvalidName,
+            ScopeServiceError::InvalidScopes
+            | ScopeServiceError::NotCreated
+            | ScopeServiceError::NotUpdated => Self::InvalidScope,
+            ScopeServiceError::NotFound | ScopeServiceError::NotDeleted => Self::NotFound,
+
+            ScopeServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for ScopeControllerError {
+    fn into_response(self) -> axum::response::Response {
+        (self.error_code(), self.error_message()).into_response()
+    }
+}
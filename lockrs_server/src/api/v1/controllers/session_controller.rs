@@ -61,6 +61,7 @@ impl SessionController {
         let session_repository = &*state.repository_container.as_ref().session_repository;
         let session_token_repository =
             &*state.repository_container.as_ref().session_token_repository;
+        let user_repository = &*state.repository_container.as_ref().user_repository;
 
         let session_create = SessionCreateModel::new(session_token.as_str());
 
@@ -68,8 +69,10 @@ impl SessionController {
             db_context,
             session_repository,
             session_token_repository,
+            user_repository,
             &session_create,
             &state.config.auth_interval,
+            state.config.email_verification.is_required_for_login(),
         )
         .await
         .map_err(SessionServiceError::from)?;
@@ -164,6 +167,7 @@ impl SessionController {
 pub enum SessionControllerError {
     Jwt,
     SessionToken,
+    EmailNotVerified,
     NotFound,
     BadRequest,
     InternalError,
@@ -174,6 +178,7 @@ impl SessionControllerError {
         match self {
             Self::Jwt => StatusCode::UNAUTHORIZED,
             Self::SessionToken => StatusCode::UNAUTHORIZED,
+            Self::EmailNotVerified => StatusCode::FORBIDDEN,
             Self::NotFound => StatusCode::NOT_FOUND,
             Self::BadRequest => StatusCode::BAD_REQUEST,
             Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
@@ -184,6 +189,7 @@ impl SessionControllerError {
         match self {
             Self::Jwt => "You do not have permission to access this resource.",
             Self::SessionToken => "The provided session token is invalid.",
+            Self::EmailNotVerified => "The email address of this account has to be verified first.",
             Self::NotFound => "Session token not found.",
             Self::BadRequest => "Unable to perform the requested operation.",
             Self::InternalError => {
@@ -199,6 +205,7 @@ impl From<SessionServiceError> for SessionControllerError {
 
         match err {
             SessionServiceError::Token => Self::SessionToken,
+            SessionServiceError::EmailNotVerified => Self::EmailNotVerified,
             SessionServiceError::NotFound => Self::NotFound,
 
             SessionServiceError::NotCreated => Self::BadRequest,
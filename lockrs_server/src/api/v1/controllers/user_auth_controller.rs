@@ -1,6 +1,8 @@
+use std::net::SocketAddr;
+
 use axum::{
-    extract::{Json, State},
-    http::StatusCode,
+    extract::{ConnectInfo, Json, State},
+    http::{header::RETRY_AFTER, StatusCode},
     response::IntoResponse,
 };
 use serde::Deserialize;
@@ -8,9 +10,12 @@ use validator::Validate;
 
 use crate::{
     api::v1::{
-        models::{UserLoginCredentials, UserRegistration},
-        responses::{SessionTokenResponse, UserResponse},
-        services::{UserAuthService, UserAuthServiceError},
+        models::{PasswordPolicyViolation, UserLoginCredentials, UserRegistration},
+        responses::{PasswordPolicyResponse, SessionTokenResponse, UserResponse},
+        services::{
+            EmailVerificationService, LoginThrottleService, LoginThrottleServiceError,
+            UserAuthService, UserAuthServiceError,
+        },
     },
     utils::extractors::BasicAuth,
     AppState,
@@ -43,20 +48,42 @@ impl UserAuthController {
         let db_context = &state.db_context;
         let user_auth_repository = &*state.repository_container.as_ref().user_auth_repository;
 
-        let user = UserAuthService::register_user(db_context, user_auth_repository, &registration)
-            .await
-            .map_err(UserAuthControllerError::from)?;
+        let user = UserAuthService::register_user(
+            db_context,
+            user_auth_repository,
+            &state.config.password_policy,
+            &state.config.password_hashing,
+            &registration,
+        )
+        .await
+        .map_err(UserAuthControllerError::from)?;
+
+        // the account exists either way, and the user can ask for the mail again
+        if let Err(err) = EmailVerificationService::send_verification(
+            &*state.mailer,
+            state.config.link_signing_key.as_slice(),
+            state.config.frontend_url.as_str(),
+            &user,
+        )
+        .await
+        {
+            tracing::error!(error = %err);
+        }
 
         let user_response = UserResponse {
             id: user.id,
             email: user.email,
+            email_verified: user.email_verified,
         };
 
         Ok(user_response)
     }
 
+    /// Logs the user in, unless the account or the address the login comes from has been
+    /// locked out by too many failed logins.
     pub async fn authenticate(
         State(state): State<AppState>,
+        connect_info: Option<ConnectInfo<SocketAddr>>,
         BasicAuth(credentials): BasicAuth,
     ) -> Result<SessionTokenResponse, UserAuthControllerError> {
         tracing::trace!(method = "verify_credentials", email = credentials.public,);
@@ -71,19 +98,72 @@ impl UserAuthController {
         let user_auth_repository = &*state.repository_container.as_ref().user_auth_repository;
         let session_token_repository =
             &*state.repository_container.as_ref().session_token_repository;
+        let totp_repository = &*state.repository_container.as_ref().totp_repository;
+        let webauthn_credential_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_credential_repository;
+        let login_attempt_repository =
+            &*state.repository_container.as_ref().login_attempt_repository;
+
+        let address = connect_info.map(|ConnectInfo(address)| address.ip());
+
+        LoginThrottleService::check(
+            db_context,
+            login_attempt_repository,
+            auth.email.as_str(),
+            address.as_ref(),
+        )
+        .await
+        .map_err(UserAuthControllerError::from)?;
 
-        let session_token = UserAuthService::login(
+        let login_result = UserAuthService::login(
             db_context,
             user_auth_repository,
             session_token_repository,
+            totp_repository,
+            webauthn_credential_repository,
+            &state.config.password_hashing,
             &auth,
+            state.config.email_verification.is_required_for_login(),
+        )
+        .await;
+
+        let session_token = match login_result {
+            Ok(session_token) => session_token,
+            Err(err @ UserAuthServiceError::Credentials) => {
+                // the login has failed either way, the lockout is just not enforced as early
+                if let Err(err) = LoginThrottleService::record_failure(
+                    db_context,
+                    login_attempt_repository,
+                    &state.config.login_throttle,
+                    auth.email.as_str(),
+                    address.as_ref(),
+                )
+                .await
+                {
+                    tracing::error!(error = %err);
+                }
+
+                return Err(UserAuthControllerError::from(err));
+            }
+            Err(err) => return Err(UserAuthControllerError::from(err)),
+        };
+
+        if let Err(err) = LoginThrottleService::record_success(
+            db_context,
+            login_attempt_repository,
+            auth.email.as_str(),
         )
         .await
-        .map_err(UserAuthControllerError::from)?;
+        {
+            tracing::error!(error = %err);
+        }
 
         let token_response = SessionTokenResponse {
             session_token: session_token.token,
             expires_at: session_token.expires_at,
+            mfa_required: session_token.mfa_pending,
         };
 
         Ok(token_response)
@@ -92,6 +172,10 @@ impl UserAuthController {
 
 pub enum UserAuthControllerError {
     InvalidCredentials,
+    EmailNotVerified,
+    PasswordPolicy(Vec<PasswordPolicyViolation>),
+    /// with the number of seconds until logins are let through again
+    LockedOut(i64),
     BadRequest,
     Internal,
 }
@@ -100,6 +184,9 @@ impl UserAuthControllerError {
     pub fn error_code(&self) -> StatusCode {
         match self {
             Self::InvalidCredentials => StatusCode::UNAUTHORIZED,
+            Self::EmailNotVerified => StatusCode::FORBIDDEN,
+            Self::PasswordPolicy(_) => StatusCode::BAD_REQUEST,
+            Self::LockedOut(_) => StatusCode::TOO_MANY_REQUESTS,
             Self::BadRequest => StatusCode::BAD_REQUEST,
             Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
         }
@@ -108,6 +195,9 @@ impl UserAuthControllerError {
     pub fn error_message(&self) -> &'static str {
         match self {
             Self::InvalidCredentials => "The provided credentials were invalid or not found.",
+            Self::EmailNotVerified => "The email address of this account has to be verified first.",
+            Self::PasswordPolicy(_) => "The password does not meet the password policy.",
+            Self::LockedOut(_) => "Too many failed logins. Please try again later.",
             Self::BadRequest => "The data provided in the request was invalid.",
             Self::Internal => {
                 "An error has occurred while proccessing your request. Please try again later."
@@ -122,13 +212,39 @@ impl From<UserAuthServiceError> for UserAuthControllerError {
 
         match err {
             UserAuthServiceError::Credentials => Self::InvalidCredentials,
+            UserAuthServiceError::EmailNotVerified => Self::EmailNotVerified,
+            UserAuthServiceError::PasswordPolicy(violations) => Self::PasswordPolicy(violations),
             _ => Self::Internal,
         }
     }
 }
 
+impl From<LoginThrottleServiceError> for UserAuthControllerError {
+    fn from(err: LoginThrottleServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            LoginThrottleServiceError::Locked(retry_after) => Self::LockedOut(retry_after),
+            LoginThrottleServiceError::InternalError => Self::Internal,
+        }
+    }
+}
+
 impl IntoResponse for UserAuthControllerError {
     fn into_response(self) -> axum::response::Response {
-        (self.error_code(), self.error_message()).into_response()
+        match self {
+            Self::PasswordPolicy(violations) => (
+                StatusCode::BAD_REQUEST,
+                PasswordPolicyResponse::from(violations),
+            )
+                .into_response(),
+            Self::LockedOut(retry_after) => (
+                self.error_code(),
+                [(RETRY_AFTER, retry_after.to_string())],
+                self.error_message(),
+            )
+                .into_response(),
+            _ => (self.error_code(), self.error_message()).into_response(),
+        }
     }
 }
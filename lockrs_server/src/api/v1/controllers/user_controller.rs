@@ -1,25 +1,16 @@
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
+    http::{header::LINK, HeaderName, StatusCode},
     response::IntoResponse,
-    Json,
 };
-use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::{
     api::v1::responses::UserResponse,
-    models::UserUpdateModel,
     services::{UserService, UserServiceError},
     AppState,
 };
 
-#[derive(Debug, Deserialize)]
-pub struct UserUpdateRequest {
-    pub email: Option<String>,
-    pub password: Option<String>,
-}
-
 pub struct UserController;
 
 impl UserController {
@@ -45,33 +36,33 @@ impl UserController {
         Ok(UserResponse {
             id: user.id,
             email: user.email,
+            email_verified: user.email_verified,
         })
     }
 
-    pub async fn update(
-        State(state): State<AppState>,
-        Path(user_id): Path<Uuid>,
-        Json(update_user_request): Json<UserUpdateRequest>,
-    ) -> Result<UserResponse, UserControllerError> {
-        tracing::trace!(
-            method = "update",
-            id = user_id.to_string(),
-            data = ?update_user_request,
+    /// Deprecated, as it changed the email address without confirming it and never changed the
+    /// password. Answers 410 and points at `PUT /:user_id/password` and `POST /:user_id/email`,
+    /// which replace it.
+    pub async fn update(Path(user_id): Path<Uuid>) -> impl IntoResponse {
+        tracing::trace!(method = "update", id = user_id.to_string());
+        tracing::warn!(
+            "Deprecated user update called for user with ID: {}",
+            user_id
         );
 
-        let update_user = UserUpdateModel::new(update_user_request.email.as_deref());
-
-        let db_context = &state.db_context;
-        let user_repository = &*state.repository_container.as_ref().user_repository;
-        let user =
-            UserService::update_user_by_id(db_context, user_repository, &user_id, &update_user)
-                .await
-                .map_err(UserControllerError::from)?;
+        let successors = format!(
+            "</api/v1/users/{0}/password>; rel=\"successor-version\", </api/v1/users/{0}/email>; rel=\"successor-version\"",
+            user_id
+        );
 
-        Ok(UserResponse {
-            id: user.id,
-            email: user.email,
-        })
+        (
+            StatusCode::GONE,
+            [
+                (HeaderName::from_static("deprecation"), String::from("true")),
+                (LINK, successors),
+            ],
+            "Updating a user in place is no longer supported. Change the password with PUT /api/v1/users/:user_id/password and the email with POST /api/v1/users/:user_id/email.",
+        )
     }
 
     pub async fn delete(
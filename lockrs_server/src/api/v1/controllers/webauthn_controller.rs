@@ -0,0 +1,441 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use base64::{engine::general_purpose, Engine as _};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        models::{WebauthnAssertionModel, WebauthnOptionsModel, WebauthnRegistrationModel},
+        responses::{
+            SessionTokenResponse, WebauthnAuthenticatorSelection, WebauthnCreationOptions,
+            WebauthnCredentialDescriptor, WebauthnCredentialListResponse,
+            WebauthnCredentialParameters, WebauthnCredentialResponse, WebauthnLoginOptionsResponse,
+            WebauthnRegistrationOptionsResponse, WebauthnRelyingParty, WebauthnRequestOptions,
+            WebauthnUser,
+        },
+        services::{
+            SessionService, WebauthnService, WebauthnServiceError, WEBAUTHN_CHALLENGE_TTL_MINUTES,
+        },
+    },
+    utils::{
+        extractors::{BearerAuth, SessionJwt},
+        webauthn::{SUPPORTED_ALGORITHMS, WEBAUTHN_RP_NAME},
+    },
+    AppState,
+};
+
+const PUBLIC_KEY_CREDENTIAL_TYPE: &str = "public-key";
+
+/// The fields of a `PublicKeyCredential` returned by `navigator.credentials.create()`, base64url
+/// encoded.
+#[derive(Deserialize)]
+pub struct WebauthnRegisterRequest {
+    pub challenge_id: String,
+    pub client_data_json: String,
+    pub attestation_object: String,
+}
+
+/// The fields of a `PublicKeyCredential` returned by `navigator.credentials.get()`, base64url
+/// encoded.
+#[derive(Deserialize)]
+pub struct WebauthnLoginRequest {
+    pub challenge_id: String,
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+}
+
+pub struct WebauthnController;
+
+impl WebauthnController {
+    pub async fn registration_options(
+        State(state): State<AppState>,
+        SessionJwt(jwt): SessionJwt,
+    ) -> Result<WebauthnRegistrationOptionsResponse, WebauthnControllerError> {
+        tracing::trace!(method = "registration_options", session_id = jwt.id);
+
+        Self::validate_session(&state, &jwt.user_id, jwt.id.as_str()).await?;
+
+        let db_context = &state.db_context;
+        let user_repository = &*state.repository_container.as_ref().user_repository;
+        let webauthn_credential_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_credential_repository;
+        let webauthn_challenge_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_challenge_repository;
+
+        let options = WebauthnService::start_registration(
+            db_context,
+            user_repository,
+            webauthn_credential_repository,
+            webauthn_challenge_repository,
+            &jwt.user_id,
+        )
+        .await
+        .map_err(WebauthnControllerError::from)?;
+
+        let Some((user_id, email)) = options.user
+        else {
+            return Err(WebauthnControllerError::InternalError);
+        };
+
+        Ok(WebauthnRegistrationOptionsResponse {
+            challenge_id: options.challenge.id,
+            public_key: WebauthnCreationOptions {
+                rp: WebauthnRelyingParty {
+                    id: state.config.webauthn_rp_id.to_owned(),
+                    name: WEBAUTHN_RP_NAME.to_owned(),
+                },
+                user: WebauthnUser {
+                    id: general_purpose::URL_SAFE_NO_PAD.encode(user_id.as_bytes()),
+                    name: email.to_owned(),
+                    display_name: email,
+                },
+                challenge: options.challenge.challenge,
+                pub_key_cred_params: SUPPORTED_ALGORITHMS
+                    .iter()
+                    .map(|alg| WebauthnCredentialParameters {
+                        credential_type: PUBLIC_KEY_CREDENTIAL_TYPE.to_owned(),
+                        alg: *alg,
+                    })
+                    .collect(),
+                timeout: WEBAUTHN_CHALLENGE_TTL_MINUTES * 60 * 1000,
+                exclude_credentials: Self::into_descriptors(options.credential_ids),
+                authenticator_selection: WebauthnAuthenticatorSelection {
+                    resident_key: String::from("required"),
+                    user_verification: String::from("preferred"),
+                },
+                attestation: String::from("none"),
+            },
+        })
+    }
+
+    pub async fn register(
+        State(state): State<AppState>,
+        SessionJwt(jwt): SessionJwt,
+        Json(register_request): Json<WebauthnRegisterRequest>,
+    ) -> Result<WebauthnCredentialResponse, WebauthnControllerError> {
+        tracing::trace!(method = "register", session_id = jwt.id);
+
+        Self::validate_session(&state, &jwt.user_id, jwt.id.as_str()).await?;
+
+        let registration = WebauthnRegistrationModel::new(
+            register_request.challenge_id.as_str(),
+            Self::decode(register_request.client_data_json.as_str())?.as_slice(),
+            Self::decode(register_request.attestation_object.as_str())?.as_slice(),
+        );
+
+        let db_context = &state.db_context;
+        let webauthn_credential_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_credential_repository;
+        let webauthn_challenge_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_challenge_repository;
+
+        let credential = WebauthnService::finish_registration(
+            db_context,
+            webauthn_credential_repository,
+            webauthn_challenge_repository,
+            state.config.webauthn_rp_id.as_str(),
+            state.config.webauthn_origin.as_str(),
+            &jwt.user_id,
+            &registration,
+        )
+        .await
+        .map_err(WebauthnControllerError::from)?;
+
+        Ok(WebauthnCredentialResponse {
+            credential_id: credential.credential_id,
+            sign_count: credential.sign_count,
+            created_at: credential.created_at.and_utc().timestamp(),
+            last_used_at: credential.last_used_at.map(|l| l.and_utc().timestamp()),
+        })
+    }
+
+    /// Starts a passkey login, as a second factor when given a session token pending one and
+    /// passwordless otherwise.
+    pub async fn login_options(
+        State(state): State<AppState>,
+        pending_token: Option<BearerAuth>,
+    ) -> Result<WebauthnLoginOptionsResponse, WebauthnControllerError> {
+        tracing::trace!(method = "login_options");
+
+        let pending_token = pending_token.map(|BearerAuth(token)| token);
+
+        let db_context = &state.db_context;
+        let session_token_repository =
+            &*state.repository_container.as_ref().session_token_repository;
+        let webauthn_credential_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_credential_repository;
+        let webauthn_challenge_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_challenge_repository;
+
+        let options = WebauthnService::start_authentication(
+            db_context,
+            session_token_repository,
+            webauthn_credential_repository,
+            webauthn_challenge_repository,
+            pending_token.as_deref(),
+        )
+        .await
+        .map_err(WebauthnControllerError::from)?;
+
+        let user_verification = match pending_token {
+            Some(_) => "preferred",
+            None => "required",
+        };
+
+        Ok(Self::into_login_options(&state, options, user_verification))
+    }
+
+    pub async fn login(
+        State(state): State<AppState>,
+        pending_token: Option<BearerAuth>,
+        Json(login_request): Json<WebauthnLoginRequest>,
+    ) -> Result<SessionTokenResponse, WebauthnControllerError> {
+        tracing::trace!(method = "login");
+
+        let pending_token = pending_token.map(|BearerAuth(token)| token);
+
+        let assertion = WebauthnAssertionModel::new(
+            login_request.challenge_id.as_str(),
+            login_request.credential_id.as_str(),
+            Self::decode(login_request.client_data_json.as_str())?.as_slice(),
+            Self::decode(login_request.authenticator_data.as_str())?.as_slice(),
+            Self::decode(login_request.signature.as_str())?.as_slice(),
+        );
+
+        let db_context = &state.db_context;
+        let session_token_repository =
+            &*state.repository_container.as_ref().session_token_repository;
+        let webauthn_credential_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_credential_repository;
+        let webauthn_challenge_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_challenge_repository;
+
+        let session_token = WebauthnService::finish_authentication(
+            db_context,
+            session_token_repository,
+            webauthn_credential_repository,
+            webauthn_challenge_repository,
+            state.config.webauthn_rp_id.as_str(),
+            state.config.webauthn_origin.as_str(),
+            pending_token.as_deref(),
+            &assertion,
+        )
+        .await
+        .map_err(WebauthnControllerError::from)?;
+
+        Ok(SessionTokenResponse {
+            session_token: session_token.token,
+            expires_at: session_token.expires_at,
+            mfa_required: session_token.mfa_pending,
+        })
+    }
+
+    pub async fn read_all(
+        State(state): State<AppState>,
+        Path(user_id): Path<Uuid>,
+    ) -> Result<WebauthnCredentialListResponse, WebauthnControllerError> {
+        tracing::trace!(method = "read_all", user_id = user_id.to_string());
+
+        let db_context = &state.db_context;
+        let webauthn_credential_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_credential_repository;
+
+        let credentials =
+            WebauthnService::get_credentials(db_context, webauthn_credential_repository, &user_id)
+                .await
+                .map_err(WebauthnControllerError::from)?;
+
+        Ok(WebauthnCredentialListResponse {
+            credentials: credentials
+                .into_iter()
+                .map(|credential| WebauthnCredentialResponse {
+                    credential_id: credential.credential_id,
+                    sign_count: credential.sign_count,
+                    created_at: credential.created_at.and_utc().timestamp(),
+                    last_used_at: credential.last_used_at.map(|l| l.and_utc().timestamp()),
+                })
+                .collect(),
+        })
+    }
+
+    pub async fn delete(
+        State(state): State<AppState>,
+        Path((user_id, credential_id)): Path<(Uuid, String)>,
+    ) -> Result<StatusCode, WebauthnControllerError> {
+        tracing::trace!(
+            method = "delete",
+            user_id = user_id.to_string(),
+            credential_id
+        );
+
+        let db_context = &state.db_context;
+        let webauthn_credential_repository = &*state
+            .repository_container
+            .as_ref()
+            .webauthn_credential_repository;
+
+        WebauthnService::delete_credential(
+            db_context,
+            webauthn_credential_repository,
+            &user_id,
+            credential_id.as_str(),
+        )
+        .await
+        .map_err(WebauthnControllerError::from)?;
+
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// registration is only open to a live session, not just a session jwt that has not expired
+    async fn validate_session(
+        state: &AppState,
+        user_id: &Uuid,
+        session_id: &str,
+    ) -> Result<(), WebauthnControllerError> {
+        let db_context = &state.db_context;
+        let session_repository = &*state.repository_container.as_ref().session_repository;
+
+        SessionService::get_session(db_context, session_repository, user_id, session_id)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = %err);
+                WebauthnControllerError::InvalidSession
+            })?;
+
+        Ok(())
+    }
+
+    fn into_login_options(
+        state: &AppState,
+        options: WebauthnOptionsModel,
+        user_verification: &str,
+    ) -> WebauthnLoginOptionsResponse {
+        WebauthnLoginOptionsResponse {
+            challenge_id: options.challenge.id,
+            public_key: WebauthnRequestOptions {
+                challenge: options.challenge.challenge,
+                timeout: WEBAUTHN_CHALLENGE_TTL_MINUTES * 60 * 1000,
+                rp_id: state.config.webauthn_rp_id.to_owned(),
+                allow_credentials: Self::into_descriptors(options.credential_ids),
+                user_verification: user_verification.to_owned(),
+            },
+        }
+    }
+
+    fn into_descriptors(credential_ids: Vec<String>) -> Vec<WebauthnCredentialDescriptor> {
+        credential_ids
+            .into_iter()
+            .map(|id| WebauthnCredentialDescriptor {
+                credential_type: PUBLIC_KEY_CREDENTIAL_TYPE.to_owned(),
+                id,
+            })
+            .collect()
+    }
+
+    fn decode(value: &str) -> Result<Vec<u8>, WebauthnControllerError> {
+        general_purpose::URL_SAFE_NO_PAD
+            .decode(value.trim_end_matches('='))
+            .map_err(|_| {
+                tracing::error!(error = "Expected a base64url encoded value");
+                WebauthnControllerError::BadRequest
+            })
+    }
+}
+
+pub enum WebauthnControllerError {
+    NotFound,
+    AlreadyExists,
+    InvalidChallenge,
+    InvalidResponse,
+    InvalidSession,
+    InvalidToken,
+
+    BadRequest,
+    InternalError,
+}
+
+impl WebauthnControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::AlreadyExists => StatusCode::CONFLICT,
+            Self::InvalidChallenge
+            | Self::InvalidResponse
+            | Self::InvalidSession
+            | Self::InvalidToken => StatusCode::UNAUTHORIZED,
+
+            Self::BadRequest => StatusCode::BAD_REQUEST,
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::NotFound => "The requested passkey could not be found.",
+            Self::AlreadyExists => "The passkey is already registered.",
+            Self::InvalidChallenge => {
+                "The challenge is invalid or has expired. Please start over and try again."
+            }
+            Self::InvalidResponse => "The passkey could not be verified.",
+            Self::InvalidSession => "The session is invalid or expired. Please log in again.",
+            Self::InvalidToken => {
+                "The provided session token is invalid or expired. Please log in again."
+            }
+
+            Self::BadRequest => "The data provided in the request was invalid.",
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<WebauthnServiceError> for WebauthnControllerError {
+    fn from(err: WebauthnServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            WebauthnServiceError::NotFound => Self::NotFound,
+            WebauthnServiceError::AlreadyExists => Self::AlreadyExists,
+            WebauthnServiceError::InvalidChallenge => Self::InvalidChallenge,
+            // a cloned authenticator is not told apart from any other bad response
+            WebauthnServiceError::InvalidResponse | WebauthnServiceError::ClonedAuthenticator => {
+                Self::InvalidResponse
+            }
+            WebauthnServiceError::Token => Self::InvalidToken,
+
+            WebauthnServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for WebauthnControllerError {
+    fn into_response(self) -> axum::response::Response {
+        (self.error_code(), self.error_message()).into_response()
+    }
+}
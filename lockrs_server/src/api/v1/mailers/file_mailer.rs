@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{Mail, Mailer, MailerError};
+
+/// Drops every mail into a directory as an .eml file instead of delivering it, for local
+/// development without a mail server.
+#[derive(Debug)]
+pub struct FileMailer {
+    dir: PathBuf,
+    from: String,
+}
+
+impl FileMailer {
+    pub fn new(dir: &str, from: &str) -> Self {
+        Self {
+            dir: PathBuf::from(dir),
+            from: from.to_owned(),
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, mail: &Mail) -> Result<(), MailerError> {
+        tracing::trace!(method = "send", to = mail.to, subject = mail.subject);
+
+        let message = mail.to_message(self.from.as_str())?;
+
+        // prefixed with the time sent, so a directory listing shows the newest mail last
+        let path = self.dir.join(format!(
+            "{}-{}.eml",
+            Utc::now().timestamp_millis(),
+            Uuid::new_v4()
+        ));
+
+        tokio::fs::create_dir_all(&self.dir).await.map_err(|err| {
+            tracing::error!(error = %err);
+            MailerError::DeliveryFailed
+        })?;
+
+        tokio::fs::write(&path, message).await.map_err(|err| {
+            tracing::error!(error = %err);
+            MailerError::DeliveryFailed
+        })?;
+
+        tracing::info!("Mail dropped: {{ to: {}, path: {:?} }}", mail.to, path);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use super::{Mail, Mailer, MailerError};
+
+/// Keeps sent mail in memory instead of delivering it. Useful for integration tests.
+#[derive(Debug, Default)]
+pub struct InMemoryMailer {
+    mails: Mutex<Vec<Mail>>,
+}
+
+impl InMemoryMailer {
+    pub fn get_mails(&self) -> Vec<Mail> {
+        self.mails
+            .lock()
+            .map(|mails| mails.to_vec())
+            .unwrap_or_default()
+    }
+
+    pub fn get_latest_for(&self, to: &str) -> Option<Mail> {
+        self.get_mails()
+            .into_iter()
+            .rev()
+            .find(|mail| mail.to == to)
+    }
+}
+
+#[async_trait]
+impl Mailer for InMemoryMailer {
+    async fn send(&self, mail: &Mail) -> Result<(), MailerError> {
+        tracing::trace!(method = "send", to = mail.to, subject = mail.subject);
+
+        self.mails
+            .lock()
+            .map_err(|_| {
+                tracing::error!(error = "In-memory mailer lock poisoned");
+                MailerError::DeliveryFailed
+            })?
+            .push(mail.clone());
+
+        Ok(())
+    }
+}
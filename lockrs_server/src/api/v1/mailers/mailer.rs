@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// A plain text mail to a single recipient.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl Mail {
+    pub fn new(to: &str, subject: &str, body: &str) -> Self {
+        Self {
+            to: to.to_owned(),
+            subject: subject.to_owned(),
+            body: body.to_owned(),
+        }
+    }
+
+    /// Renders the mail as an RFC 5322 message sent by `from`, refusing any header value that
+    /// could smuggle in headers of its own.
+    pub fn to_message(&self, from: &str) -> Result<String, MailerError> {
+        if [from, self.to.as_str(), self.subject.as_str()]
+            .iter()
+            .any(|value| value.contains(['\r', '\n']))
+        {
+            tracing::error!(error = "Mail header contains a line break");
+            return Err(MailerError::InvalidMail);
+        }
+
+        let domain = from
+            .rsplit_once('@')
+            .map_or("localhost", |(_, domain)| domain);
+
+        Ok(format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMessage-ID: <{}@{}>\r\nMIME-Version: 1.0\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n{}\r\n",
+            from,
+            self.to,
+            self.subject,
+            Utc::now().to_rfc2822(),
+            Uuid::new_v4(),
+            domain,
+            self.body.replace('\n', "\r\n"),
+        ))
+    }
+}
+
+/// Delivers mail to users, e.g. the links that verify their email address.
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, mail: &Mail) -> Result<(), MailerError>;
+}
+
+#[derive(Debug, Error)]
+pub enum MailerError {
+    #[error("MAILER ERROR :: Invalid mail")]
+    InvalidMail,
+    #[error("MAILER ERROR :: Delivery failed")]
+    DeliveryFailed,
+}
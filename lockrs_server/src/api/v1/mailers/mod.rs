@@ -0,0 +1,6 @@
+mod file_mailer;
+mod in_memory_mailer;
+mod mailer;
+mod smtp_mailer;
+
+pub use self::{file_mailer::*, in_memory_mailer::*, mailer::*, smtp_mailer::*};
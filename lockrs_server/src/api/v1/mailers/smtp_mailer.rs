@@ -0,0 +1,84 @@
+use async_native_tls::TlsConnector;
+use async_smtp::{
+    smtp::authentication::Credentials, ClientSecurity, ClientTlsParameters, EmailAddress, Envelope,
+    SendableEmail, ServerAddress, SmtpClient,
+};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::SmtpConfig;
+
+use super::{Mail, Mailer, MailerError};
+
+/// Delivers mail through an SMTP relay, connecting anew for every mail as they are only sent
+/// every so often.
+pub struct SmtpMailer {
+    config: SmtpConfig,
+    from: String,
+}
+
+impl SmtpMailer {
+    pub fn new(config: &SmtpConfig, from: &str) -> Self {
+        Self {
+            config: config.to_owned(),
+            from: from.to_owned(),
+        }
+    }
+
+    fn client(&self) -> SmtpClient {
+        let tls_parameters =
+            ClientTlsParameters::new(self.config.host.to_owned(), TlsConnector::new());
+        let security = match self.config.starttls {
+            true => ClientSecurity::Required(tls_parameters),
+            false => ClientSecurity::Wrapper(tls_parameters),
+        };
+
+        let client = SmtpClient::with_security(
+            ServerAddress::new(self.config.host.to_owned(), self.config.port),
+            security,
+        );
+
+        match (&self.config.username, &self.config.password) {
+            (Some(username), Some(password)) => {
+                client.credentials(Credentials::new(username.to_owned(), password.to_owned()))
+            }
+            _ => client,
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, mail: &Mail) -> Result<(), MailerError> {
+        tracing::trace!(method = "send", to = mail.to, subject = mail.subject);
+
+        let message = mail.to_message(self.from.as_str())?;
+
+        let envelope = Envelope::new(
+            Some(EmailAddress::new(self.from.to_owned()).map_err(|_| MailerError::InvalidMail)?),
+            vec![EmailAddress::new(mail.to.to_owned()).map_err(|_| MailerError::InvalidMail)?],
+        )
+        .map_err(|_| MailerError::InvalidMail)?;
+
+        let email = SendableEmail::new(envelope, Uuid::new_v4().to_string(), message);
+
+        let mut transport = self.client().into_transport();
+        let sent = transport.connect_and_send(email).await;
+
+        // the mail is out either way, so failing to say goodbye is not worth reporting
+        let _ = transport.close().await;
+
+        sent.map_err(|err| {
+            tracing::error!(error = %err);
+            MailerError::DeliveryFailed
+        })?;
+
+        tracing::info!(
+            "Mail sent: {{ to: {}, subject: {} }}",
+            mail.to,
+            mail.subject
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{api::v1::models::FederatedIdentityModel, db::pg::models::PgFederatedIdentity};
+
+pub struct FederatedIdentityMapper;
+
+impl FederatedIdentityMapper {
+    pub fn from_pg(pg_identity: PgFederatedIdentity) -> FederatedIdentityModel {
+        FederatedIdentityModel::new(
+            pg_identity.id,
+            &pg_identity.user_id,
+            pg_identity.provider.as_str(),
+            pg_identity.subject.as_str(),
+            &pg_identity.created_at,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn it_should_map_pg() {
+        let id = 1;
+        let user_id = Uuid::new_v4();
+        let provider = String::from("google");
+        let subject = String::from("110169484474386276334");
+        let created_at = Utc::now().naive_utc();
+
+        let pg_identity = PgFederatedIdentity {
+            id,
+            user_id,
+            provider: provider.clone(),
+            subject: subject.clone(),
+            created_at,
+        };
+
+        let actual_identity = FederatedIdentityMapper::from_pg(pg_identity);
+
+        let expected_identity = FederatedIdentityModel::new(
+            id,
+            &user_id,
+            provider.as_str(),
+            subject.as_str(),
+            &created_at,
+        );
+
+        assert_eq!(actual_identity, expected_identity);
+    }
+}
@@ -1,3 +1,9 @@
+mod federated_identity_mapper;
+mod totp_mapper;
 mod user_auth_mapper;
+mod webauthn_credential_mapper;
 
-pub use self::user_auth_mapper::*;
+pub use self::{
+    federated_identity_mapper::*, totp_mapper::*, user_auth_mapper::*,
+    webauthn_credential_mapper::*,
+};
@@ -0,0 +1,52 @@
+use crate::{api::v1::models::TotpModel, db::pg::models::PgTotpSecret};
+
+pub struct TotpMapper;
+
+impl TotpMapper {
+    pub fn from_pg(pg_totp_secret: PgTotpSecret) -> TotpModel {
+        TotpModel::new(
+            &pg_totp_secret.user_id,
+            pg_totp_secret.secret.as_str(),
+            pg_totp_secret.confirmed_at.as_ref(),
+            pg_totp_secret.last_used_step,
+            &pg_totp_secret.created_at,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn it_should_map_pg() {
+        let user_id = Uuid::new_v4();
+        let secret = String::from("JBSWY3DPEHPK3PXP");
+        let created_at = Utc::now().naive_utc();
+        let confirmed_at = Some(created_at);
+        let last_used_step = Some(56_000_000);
+
+        let pg_totp_secret = PgTotpSecret {
+            user_id,
+            secret: secret.clone(),
+            confirmed_at,
+            last_used_step,
+            created_at,
+        };
+
+        let actual_totp = TotpMapper::from_pg(pg_totp_secret);
+
+        let expected_totp = TotpModel::new(
+            &user_id,
+            secret.as_str(),
+            confirmed_at.as_ref(),
+            last_used_step,
+            &created_at,
+        );
+
+        assert_eq!(actual_totp, expected_totp);
+    }
+}
@@ -8,10 +8,16 @@ impl UserAuthMapper {
             &pg_user.id,
             pg_user.email.as_str(),
             pg_user.password_hash.as_str(),
+            pg_user.email_verified,
+            pg_user.password_version,
         )
     }
 
     pub fn into_user(user_auth: UserAuthModel) -> UserModel {
-        UserModel::new(user_auth.id, user_auth.email.as_str())
+        UserModel::new(
+            user_auth.id,
+            user_auth.email.as_str(),
+            user_auth.email_verified,
+        )
     }
 }
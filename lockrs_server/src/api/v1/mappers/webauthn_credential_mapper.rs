@@ -0,0 +1,60 @@
+use crate::{api::v1::models::WebauthnCredentialModel, db::pg::models::PgWebauthnCredential};
+
+pub struct WebauthnCredentialMapper;
+
+impl WebauthnCredentialMapper {
+    pub fn from_pg(pg_credential: PgWebauthnCredential) -> WebauthnCredentialModel {
+        WebauthnCredentialModel::new(
+            pg_credential.id,
+            &pg_credential.user_id,
+            pg_credential.credential_id.as_str(),
+            pg_credential.public_key.as_slice(),
+            pg_credential.sign_count,
+            &pg_credential.created_at,
+            pg_credential.last_used_at.as_ref(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn it_should_map_pg() {
+        let id = 1;
+        let user_id = Uuid::new_v4();
+        let credential_id = String::from("Y3JlZGVudGlhbA");
+        let public_key = vec![0xa5, 0x01, 0x02, 0x03, 0x26];
+        let sign_count = 12;
+        let created_at = Utc::now().naive_utc();
+        let last_used_at = Some(created_at);
+
+        let pg_credential = PgWebauthnCredential {
+            id,
+            user_id,
+            credential_id: credential_id.clone(),
+            public_key: public_key.clone(),
+            sign_count,
+            created_at,
+            last_used_at,
+        };
+
+        let actual_credential = WebauthnCredentialMapper::from_pg(pg_credential);
+
+        let expected_credential = WebauthnCredentialModel::new(
+            id,
+            &user_id,
+            credential_id.as_str(),
+            public_key.as_slice(),
+            sign_count,
+            &created_at,
+            last_used_at.as_ref(),
+        );
+
+        assert_eq!(actual_credential, expected_credential);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod controllers;
+pub mod mailers;
 pub mod mappers;
 pub mod models;
 pub mod responses;
@@ -0,0 +1,54 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+pub struct PasswordChange {
+    pub current_password: String,
+
+    pub new_password: String,
+}
+
+impl PasswordChange {
+    pub fn new(current_password: &str, new_password: &str) -> Self {
+        Self {
+            current_password: current_password.to_owned(),
+            new_password: new_password.to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Debug for PasswordChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PasswordChange: {{ current_password: ********, new_password: ******** }}"
+        )
+    }
+}
+
+#[derive(Deserialize, Validate)]
+pub struct EmailChange {
+    #[validate(email)]
+    pub email: String,
+
+    pub current_password: String,
+}
+
+impl EmailChange {
+    pub fn new(email: &str, current_password: &str) -> Self {
+        Self {
+            email: email.to_owned(),
+            current_password: current_password.to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Debug for EmailChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "EmailChange: {{ {:?}, current_password: ******** }}",
+            self.email
+        )
+    }
+}
@@ -0,0 +1,112 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A link between a local user and their account at an upstream identity provider.
+#[derive(Debug, PartialEq)]
+pub struct FederatedIdentityModel {
+    pub id: i32,
+    pub user_id: Uuid,
+    /// the name the provider is configured under
+    pub provider: String,
+    /// the identifier of the user at the provider, which never changes unlike their email
+    pub subject: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl FederatedIdentityModel {
+    pub fn new(
+        id: i32,
+        user_id: &Uuid,
+        provider: &str,
+        subject: &str,
+        created_at: &NaiveDateTime,
+    ) -> Self {
+        Self {
+            id,
+            user_id: user_id.to_owned(),
+            provider: provider.to_owned(),
+            subject: subject.to_owned(),
+            created_at: created_at.to_owned(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FederatedIdentityCreateModel {
+    pub user_id: Uuid,
+    pub provider: String,
+    pub subject: String,
+}
+
+impl FederatedIdentityCreateModel {
+    pub fn new(user_id: &Uuid, provider: &str, subject: &str) -> Self {
+        Self {
+            user_id: user_id.to_owned(),
+            provider: provider.to_owned(),
+            subject: subject.to_owned(),
+        }
+    }
+}
+
+/// What a sign-in with an upstream identity provider has to be completed with, kept under its
+/// `state` until the user comes back from the provider.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct FederationStateModel {
+    pub provider: String,
+    /// the nonce an OIDC provider has to sign into the id token
+    pub nonce: String,
+    /// the PKCE verifier the authorization code is redeemed with
+    pub code_verifier: String,
+    /// digest of the value of the cookie set on the browser that started the sign-in, which has
+    /// to be the one completing it
+    pub binding_digest: String,
+    pub expires_at: i64,
+}
+
+impl FederationStateModel {
+    pub fn new(
+        provider: &str,
+        nonce: &str,
+        code_verifier: &str,
+        binding_digest: &str,
+        expires_at: i64,
+    ) -> Self {
+        Self {
+            provider: provider.to_owned(),
+            nonce: nonce.to_owned(),
+            code_verifier: code_verifier.to_owned(),
+            binding_digest: binding_digest.to_owned(),
+            expires_at,
+        }
+    }
+}
+
+impl std::fmt::Debug for FederationStateModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "FederationStateModel: {{ {:?}, nonce: ********, code_verifier: ********, binding_digest: ********, {:?} }}",
+            self.provider, self.expires_at
+        )
+    }
+}
+
+/// Who an upstream identity provider says the user is.
+#[derive(Debug, PartialEq)]
+pub struct UpstreamIdentityModel {
+    pub subject: String,
+    pub email: Option<String>,
+    /// whether the provider vouches for the user owning `email`
+    pub email_verified: bool,
+}
+
+impl UpstreamIdentityModel {
+    pub fn new(subject: &str, email: Option<&str>, email_verified: bool) -> Self {
+        Self {
+            subject: subject.to_owned(),
+            email: email.map(String::from),
+            email_verified,
+        }
+    }
+}
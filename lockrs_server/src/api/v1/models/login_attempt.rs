@@ -0,0 +1,20 @@
+/// The failed logins counted against an account or an address, and how long it is locked out
+/// for because of them.
+#[derive(Clone, Debug)]
+pub struct LoginAttemptModel {
+    pub failures: i64,
+    /// millisecond timestamp logins are turned down until
+    pub locked_until: Option<i64>,
+    /// millisecond timestamp the failures are forgotten at, unless more come in before then
+    pub expires_at: i64,
+}
+
+impl LoginAttemptModel {
+    pub fn new(failures: i64, locked_until: Option<i64>, expires_at: i64) -> Self {
+        Self {
+            failures,
+            locked_until,
+            expires_at,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The outstanding sign-in link and code of a user, of which there is at most one at a time. The
+/// secrets in it are only ever stored as their digests.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct MagicLinkModel {
+    pub user_id: Uuid,
+    pub email: String,
+    /// digest of the nonce signed into the link
+    pub nonce_digest: String,
+    /// digest of the one-time code
+    pub code_digest: String,
+    /// digest of the value of the cookie set on the browser that asked for the link, which has
+    /// to be the one redeeming it
+    pub binding_digest: String,
+    /// how many times redeeming it has failed so far
+    pub failed_attempts: i64,
+    pub expires_at: i64,
+}
+
+impl MagicLinkModel {
+    pub fn new(
+        user_id: &Uuid,
+        email: &str,
+        nonce_digest: &str,
+        code_digest: &str,
+        binding_digest: &str,
+        failed_attempts: i64,
+        expires_at: i64,
+    ) -> Self {
+        Self {
+            user_id: user_id.to_owned(),
+            email: email.to_owned(),
+            nonce_digest: nonce_digest.to_owned(),
+            code_digest: code_digest.to_owned(),
+            binding_digest: binding_digest.to_owned(),
+            failed_attempts,
+            expires_at,
+        }
+    }
+}
+
+impl std::fmt::Debug for MagicLinkModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "MagicLinkModel: {{ {:?}, {:?}, nonce_digest: ********, code_digest: ********, binding_digest: ********, {:?}, {:?} }}",
+            self.user_id, self.email, self.failed_attempts, self.expires_at
+        )
+    }
+}
+
+/// Either the token from the link in a sign-in mail, or the code from it along with the email it
+/// was sent to.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum MagicLinkRedemption {
+    Link { token: String },
+    Code { email: String, code: String },
+}
+
+impl std::fmt::Debug for MagicLinkRedemption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Link { .. } => write!(f, "MagicLinkRedemption::Link {{ token: ******** }}"),
+            Self::Code { email, .. } => write!(
+                f,
+                "MagicLinkRedemption::Code {{ {:?}, code: ******** }}",
+                email
+            ),
+        }
+    }
+}
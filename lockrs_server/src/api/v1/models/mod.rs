@@ -1,5 +1,16 @@
+mod account;
+mod federation;
+mod login_attempt;
+mod magic_link;
+mod password_policy;
+mod password_reset;
 mod session;
 mod session_token;
+mod totp;
 mod user_auth;
+mod webauthn;
 
-pub use self::{session::*, session_token::*, user_auth::*};
+pub use self::{
+    account::*, federation::*, login_attempt::*, magic_link::*, password_policy::*,
+    password_reset::*, session::*, session_token::*, totp::*, user_auth::*, webauthn::*,
+};
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// A rule of the password policy a password fails, tagged by `rule` when serialized.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum PasswordPolicyViolation {
+    TooShort { min_length: usize },
+    TooLong { max_length: usize },
+    MissingLowercase,
+    MissingUppercase,
+    MissingDigit,
+    MissingSymbol,
+    ContainsEmail,
+    Breached,
+}
+
+impl PasswordPolicyViolation {
+    pub fn message(&self) -> String {
+        match self {
+            Self::TooShort { min_length } => {
+                format!("Password must be at least {} characters long.", min_length)
+            }
+            Self::TooLong { max_length } => {
+                format!("Password must be at most {} characters long.", max_length)
+            }
+            Self::MissingLowercase => {
+                String::from("Password must contain a lowercase letter.")
+            }
+            Self::MissingUppercase => {
+                String::from("Password must contain an uppercase letter.")
+            }
+            Self::MissingDigit => String::from("Password must contain a digit."),
+            Self::MissingSymbol => {
+                String::from("Password must contain a character that is not a letter or digit.")
+            }
+            Self::ContainsEmail => String::from("Password must not contain your email address."),
+            Self::Breached => String::from(
+                "Password has appeared in a data breach, and can not be used. Please choose another.",
+            ),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// What an outstanding password reset token was issued for. The token itself is only ever stored
+/// as its digest.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PasswordResetTokenModel {
+    pub user_id: Uuid,
+    pub email: String,
+    /// the version of the password at the time the token was issued, so the token stops working
+    /// once the password has been changed by any means
+    pub password_version: i32,
+    pub expires_at: i64,
+}
+
+impl PasswordResetTokenModel {
+    pub fn new(user_id: &Uuid, email: &str, password_version: i32, expires_at: i64) -> Self {
+        Self {
+            user_id: user_id.to_owned(),
+            email: email.to_owned(),
+            password_version,
+            expires_at,
+        }
+    }
+}
+
+impl std::fmt::Debug for PasswordResetTokenModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PasswordResetTokenModel: {{ {:?}, {:?}, {:?}, {:?} }}",
+            self.user_id, self.email, self.password_version, self.expires_at
+        )
+    }
+}
+
+#[derive(Deserialize, Validate)]
+pub struct PasswordReset {
+    pub token: String,
+
+    pub password: String,
+}
+
+impl PasswordReset {
+    pub fn new(token: &str, password: &str) -> Self {
+        Self {
+            token: token.to_owned(),
+            password: password.to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Debug for PasswordReset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PasswordReset: {{ token: ********, password: ******** }}"
+        )
+    }
+}
@@ -6,14 +6,18 @@ pub struct SessionTokenModel {
     pub token: String,
     pub user_id: Uuid,
     pub expires_at: i64,
+    /// issued after the user's password was verified, but before their second factor was
+    #[serde(default)]
+    pub mfa_pending: bool,
 }
 
 impl SessionTokenModel {
-    pub fn new(token: &str, user_id: &Uuid, expires_at: i64) -> Self {
+    pub fn new(token: &str, user_id: &Uuid, expires_at: i64, mfa_pending: bool) -> Self {
         Self {
             token: token.to_owned(),
             user_id: user_id.to_owned(),
             expires_at,
+            mfa_pending,
         }
     }
 }
@@ -22,8 +26,8 @@ impl std::fmt::Debug for SessionTokenModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "SessionTokenModel: {{ token: ********, {:?}, {:?} }}",
-            self.user_id, self.expires_at
+            "SessionTokenModel: {{ token: ********, {:?}, {:?}, {:?} }}",
+            self.user_id, self.expires_at, self.mfa_pending
         )
     }
 }
@@ -0,0 +1,92 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+#[derive(PartialEq)]
+pub struct TotpModel {
+    pub user_id: Uuid,
+    pub secret: String,
+    pub confirmed_at: Option<NaiveDateTime>,
+    pub last_used_step: Option<i64>,
+    pub created_at: NaiveDateTime,
+}
+
+impl TotpModel {
+    pub fn new(
+        user_id: &Uuid,
+        secret: &str,
+        confirmed_at: Option<&NaiveDateTime>,
+        last_used_step: Option<i64>,
+        created_at: &NaiveDateTime,
+    ) -> Self {
+        Self {
+            user_id: user_id.to_owned(),
+            secret: secret.to_owned(),
+            confirmed_at: confirmed_at.map(|c| c.to_owned()),
+            last_used_step,
+            created_at: created_at.to_owned(),
+        }
+    }
+
+    /// whether the enrollment has been confirmed, and so is required at login
+    pub fn is_confirmed(&self) -> bool {
+        self.confirmed_at.is_some()
+    }
+}
+
+impl std::fmt::Debug for TotpModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "TotpModel: {{ {:?}, secret: ********, {:?}, {:?}, {:?} }}",
+            self.user_id, self.confirmed_at, self.last_used_step, self.created_at,
+        )
+    }
+}
+
+pub struct TotpCreateModel {
+    pub user_id: Uuid,
+    pub secret: String,
+}
+
+impl TotpCreateModel {
+    pub fn new(user_id: &Uuid, secret: &str) -> Self {
+        Self {
+            user_id: user_id.to_owned(),
+            secret: secret.to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Debug for TotpCreateModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "TotpCreateModel: {{ {:?}, secret: ******** }}",
+            self.user_id
+        )
+    }
+}
+
+/// What an authenticator app needs to enroll, returned once when an enrollment is started.
+pub struct TotpEnrollmentModel {
+    pub secret: String,
+    pub provisioning_uri: String,
+}
+
+impl TotpEnrollmentModel {
+    pub fn new(secret: &str, provisioning_uri: &str) -> Self {
+        Self {
+            secret: secret.to_owned(),
+            provisioning_uri: provisioning_uri.to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Debug for TotpEnrollmentModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "TotpEnrollmentModel: {{ secret: ********, provisioning_uri: ******** }}"
+        )
+    }
+}
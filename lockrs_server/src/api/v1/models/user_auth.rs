@@ -11,14 +11,25 @@ pub struct UserAuthModel {
     pub id: Uuid,
     pub email: String,
     pub password_hash: String,
+    pub email_verified: bool,
+    /// counts the changes to the password, leaving out rehashes of the same password
+    pub password_version: i32,
 }
 
 impl UserAuthModel {
-    pub fn new(id: &Uuid, email: &str, password_hash: &str) -> Self {
+    pub fn new(
+        id: &Uuid,
+        email: &str,
+        password_hash: &str,
+        email_verified: bool,
+        password_version: i32,
+    ) -> Self {
         Self {
             id: id.to_owned(),
             email: email.to_owned(),
             password_hash: password_hash.to_owned(),
+            email_verified,
+            password_version,
         }
     }
 }
@@ -27,8 +38,8 @@ impl std::fmt::Debug for UserAuthModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "UserAuthModel {{ {:?}, {:?}, password_hash: ######## }}",
-            self.id, self.email
+            "UserAuthModel {{ {:?}, {:?}, password_hash: ########, {:?}, {:?} }}",
+            self.id, self.email, self.email_verified, self.password_version
         )
     }
 }
@@ -38,7 +49,7 @@ pub struct UserLoginCredentials {
     #[validate(email)]
     pub email: String,
 
-    #[validate(length(min = 8))]
+    #[validate(length(min = 1))]
     pub password: String,
 }
 
@@ -66,7 +77,6 @@ pub struct UserRegistration {
     #[validate(email)]
     pub email: String,
 
-    #[validate(length(min = 8))]
     pub password: String,
 }
 
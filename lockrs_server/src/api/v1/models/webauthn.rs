@@ -0,0 +1,170 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// the `type` of the client data signed during a registration ceremony
+pub const WEBAUTHN_CEREMONY_CREATE: &str = "webauthn.create";
+/// the `type` of the client data signed during an authentication ceremony
+pub const WEBAUTHN_CEREMONY_GET: &str = "webauthn.get";
+
+#[derive(Debug, PartialEq)]
+pub struct WebauthnCredentialModel {
+    pub id: i32,
+    pub user_id: Uuid,
+    pub credential_id: String,
+    pub public_key: Vec<u8>,
+    pub sign_count: i64,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+}
+
+impl WebauthnCredentialModel {
+    pub fn new(
+        id: i32,
+        user_id: &Uuid,
+        credential_id: &str,
+        public_key: &[u8],
+        sign_count: i64,
+        created_at: &NaiveDateTime,
+        last_used_at: Option<&NaiveDateTime>,
+    ) -> Self {
+        Self {
+            id,
+            user_id: user_id.to_owned(),
+            credential_id: credential_id.to_owned(),
+            public_key: public_key.to_vec(),
+            sign_count,
+            created_at: created_at.to_owned(),
+            last_used_at: last_used_at.map(|l| l.to_owned()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct WebauthnCredentialCreateModel {
+    pub user_id: Uuid,
+    pub credential_id: String,
+    pub public_key: Vec<u8>,
+    pub sign_count: i64,
+}
+
+impl WebauthnCredentialCreateModel {
+    pub fn new(user_id: &Uuid, credential_id: &str, public_key: &[u8], sign_count: i64) -> Self {
+        Self {
+            user_id: user_id.to_owned(),
+            credential_id: credential_id.to_owned(),
+            public_key: public_key.to_vec(),
+            sign_count,
+        }
+    }
+}
+
+/// The challenge of a ceremony in progress, which can be answered once before it expires.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct WebauthnChallengeModel {
+    pub id: String,
+    pub challenge: String,
+    /// the user the ceremony is for, if known when it was started
+    pub user_id: Option<Uuid>,
+    pub ceremony: String,
+    pub expires_at: i64,
+}
+
+impl WebauthnChallengeModel {
+    pub fn new(
+        id: &str,
+        challenge: &str,
+        user_id: Option<&Uuid>,
+        ceremony: &str,
+        expires_at: i64,
+    ) -> Self {
+        Self {
+            id: id.to_owned(),
+            challenge: challenge.to_owned(),
+            user_id: user_id.map(|u| u.to_owned()),
+            ceremony: ceremony.to_owned(),
+            expires_at,
+        }
+    }
+}
+
+impl std::fmt::Debug for WebauthnChallengeModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "WebauthnChallengeModel: {{ {:?}, challenge: ********, {:?}, {:?}, {:?} }}",
+            self.id, self.user_id, self.ceremony, self.expires_at
+        )
+    }
+}
+
+/// What the browser needs to start a ceremony, returned when one is started.
+#[derive(Debug)]
+pub struct WebauthnOptionsModel {
+    pub challenge: WebauthnChallengeModel,
+    /// the user to register a credential for, left out of authentication ceremonies
+    pub user: Option<(Uuid, String)>,
+    /// the credentials already registered to the user, to be excluded from a registration or
+    /// allowed in an authentication
+    pub credential_ids: Vec<String>,
+}
+
+impl WebauthnOptionsModel {
+    pub fn new(
+        challenge: WebauthnChallengeModel,
+        user: Option<(Uuid, String)>,
+        credential_ids: Vec<String>,
+    ) -> Self {
+        Self {
+            challenge,
+            user,
+            credential_ids,
+        }
+    }
+}
+
+/// The response of an authenticator to a registration ceremony.
+#[derive(Debug)]
+pub struct WebauthnRegistrationModel {
+    pub challenge_id: String,
+    pub client_data_json: Vec<u8>,
+    pub attestation_object: Vec<u8>,
+}
+
+impl WebauthnRegistrationModel {
+    pub fn new(challenge_id: &str, client_data_json: &[u8], attestation_object: &[u8]) -> Self {
+        Self {
+            challenge_id: challenge_id.to_owned(),
+            client_data_json: client_data_json.to_vec(),
+            attestation_object: attestation_object.to_vec(),
+        }
+    }
+}
+
+/// The response of an authenticator to an authentication ceremony.
+#[derive(Debug)]
+pub struct WebauthnAssertionModel {
+    pub challenge_id: String,
+    pub credential_id: String,
+    pub client_data_json: Vec<u8>,
+    pub authenticator_data: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+impl WebauthnAssertionModel {
+    pub fn new(
+        challenge_id: &str,
+        credential_id: &str,
+        client_data_json: &[u8],
+        authenticator_data: &[u8],
+        signature: &[u8],
+    ) -> Self {
+        Self {
+            challenge_id: challenge_id.to_owned(),
+            credential_id: credential_id.to_owned(),
+            client_data_json: client_data_json.to_vec(),
+            authenticator_data: authenticator_data.to_vec(),
+            signature: signature.to_vec(),
+        }
+    }
+}
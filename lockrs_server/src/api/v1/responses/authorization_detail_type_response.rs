@@ -0,0 +1,29 @@
+use axum::{response::IntoResponse, Json};
+use serde::Serialize;
+
+use crate::oauth2::v1::models::AuthorizationDetailSchema;
+
+#[derive(Serialize)]
+pub struct AuthorizationDetailTypeResponse {
+    pub name: String,
+    pub client_id: String,
+    pub description: String,
+    pub schema: AuthorizationDetailSchema,
+}
+
+impl IntoResponse for AuthorizationDetailTypeResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Serialize)]
+pub struct AuthorizationDetailTypeListResponse {
+    pub authorization_detail_types: Vec<AuthorizationDetailTypeResponse>,
+}
+
+impl IntoResponse for AuthorizationDetailTypeListResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
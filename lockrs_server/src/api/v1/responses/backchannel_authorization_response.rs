@@ -0,0 +1,19 @@
+use axum::{response::IntoResponse, Json};
+use serde::Serialize;
+
+use crate::oauth2::v1::models::AuthorizationDetailModel;
+
+#[derive(Serialize)]
+pub struct BackchannelAuthorizationResponse {
+    pub client_id: String,
+    pub binding_message: Option<String>,
+    pub scopes: Vec<String>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
+    pub expires_at: i64,
+}
+
+impl IntoResponse for BackchannelAuthorizationResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
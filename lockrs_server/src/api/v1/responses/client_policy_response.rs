@@ -0,0 +1,24 @@
+use axum::{response::IntoResponse, Json};
+use serde::Serialize;
+
+use crate::models::GrantType;
+
+/// lifetimes are in seconds
+#[derive(Serialize)]
+pub struct ClientPolicyResponse {
+    pub client_id: String,
+    pub grant_types: Vec<GrantType>,
+    pub access_token_lifetime: i64,
+    pub refresh_token_lifetime: i64,
+    pub id_token_lifetime: i64,
+    pub absolute_refresh_lifetime: Option<i64>,
+    pub sliding_refresh_window: Option<i64>,
+    pub always_issue_refresh_token: bool,
+    pub backchannel_client_notification_endpoint: Option<String>,
+}
+
+impl IntoResponse for ClientPolicyResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
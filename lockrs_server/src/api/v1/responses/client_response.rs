@@ -15,6 +15,38 @@ impl IntoResponse for ClientResponse {
     }
 }
 
+/// the secret is only ever returned here, it cannot be retrieved afterwards
+#[derive(Serialize)]
+pub struct ClientRegistrationResponse {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub name: String,
+    pub description: String,
+    pub homepage_url: String,
+}
+
+impl IntoResponse for ClientRegistrationResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
+
+/// the secret is only ever returned here, it cannot be retrieved afterwards
+#[derive(Serialize)]
+pub struct ClientSecretResponse {
+    pub client_id: String,
+    pub secret: String,
+    pub created_at: i64,
+    pub expires_at: Option<i64>,
+}
+
+impl IntoResponse for ClientSecretResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
+
 #[derive(Serialize)]
 pub struct ClientListResponse {
     pub clients: Vec<ClientResponse>,
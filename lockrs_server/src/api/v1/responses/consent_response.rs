@@ -0,0 +1,30 @@
+use axum::{response::IntoResponse, Json};
+use serde::Serialize;
+
+use crate::oauth2::v1::models::AuthorizationDetailModel;
+
+#[derive(Serialize)]
+pub struct ConsentResponse {
+    pub client_id: String,
+    pub scopes: Vec<String>,
+    pub granted_at: i64,
+    pub expires_at: Option<i64>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
+}
+
+impl IntoResponse for ConsentResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Serialize)]
+pub struct ConsentListResponse {
+    pub consents: Vec<ConsentResponse>,
+}
+
+impl IntoResponse for ConsentListResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
@@ -0,0 +1,60 @@
+use axum::{
+    http::header::SET_COOKIE,
+    response::{AppendHeaders, IntoResponse},
+    Json,
+};
+use cookie::{time::Duration, Cookie, SameSite};
+use serde::{Deserialize, Serialize};
+
+use crate::api::v1::services::FEDERATION_STATE_TTL_MINUTES;
+
+/// The names of the upstream identity providers users can sign in with.
+#[derive(Deserialize, Serialize)]
+pub struct FederationProviderListResponse {
+    pub providers: Vec<String>,
+}
+
+impl IntoResponse for FederationProviderListResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
+
+/// Answers the start of a sign-in with an upstream identity provider, handing the browser the
+/// binding the sign-in has to be completed with. The cookie is only ever sent back to the
+/// federation endpoints.
+#[derive(Deserialize, Serialize)]
+pub struct FederationAuthorizationResponse {
+    /// where to send the user to sign in at the provider
+    pub authorization_url: String,
+    #[serde(skip)]
+    pub binding: String,
+}
+
+impl FederationAuthorizationResponse {
+    pub fn cookie_name() -> &'static str {
+        "federation_binding"
+    }
+
+    pub fn create_binding_cookie(binding: &str) -> String {
+        Cookie::build(Self::cookie_name(), binding)
+            .path("/api/v1/auth/federation")
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .max_age(Duration::minutes(FEDERATION_STATE_TTL_MINUTES))
+            .finish()
+            .to_string()
+    }
+}
+
+impl IntoResponse for FederationAuthorizationResponse {
+    fn into_response(self) -> axum::response::Response {
+        let binding_cookie = Self::create_binding_cookie(self.binding.as_str());
+
+        (
+            AppendHeaders([(SET_COOKIE, binding_cookie.as_str())]),
+            Json(self),
+        )
+            .into_response()
+    }
+}
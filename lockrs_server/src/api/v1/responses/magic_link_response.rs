@@ -0,0 +1,42 @@
+use axum::{
+    http::{header::SET_COOKIE, StatusCode},
+    response::{AppendHeaders, IntoResponse},
+};
+use cookie::{time::Duration, Cookie, SameSite};
+
+use crate::api::v1::services::MAGIC_LINK_TTL_MINUTES;
+
+/// Answers a sign-in link request, handing the browser the binding the link has to be redeemed
+/// with. The cookie is only ever sent back to the magic link endpoints.
+pub struct MagicLinkResponse {
+    pub binding: String,
+}
+
+impl MagicLinkResponse {
+    pub fn cookie_name() -> &'static str {
+        "magic_link_binding"
+    }
+
+    pub fn create_binding_cookie(binding: &str) -> String {
+        Cookie::build(Self::cookie_name(), binding)
+            .path("/api/v1/auth/magic-link")
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .max_age(Duration::minutes(MAGIC_LINK_TTL_MINUTES))
+            .finish()
+            .to_string()
+    }
+}
+
+impl IntoResponse for MagicLinkResponse {
+    fn into_response(self) -> axum::response::Response {
+        (
+            AppendHeaders([(
+                SET_COOKIE,
+                Self::create_binding_cookie(self.binding.as_str()).as_str(),
+            )]),
+            StatusCode::ACCEPTED,
+        )
+            .into_response()
+    }
+}
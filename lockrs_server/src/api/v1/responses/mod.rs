@@ -1,12 +1,25 @@
+mod authorization_detail_type_response;
+mod backchannel_authorization_response;
+mod client_policy_response;
 mod client_response;
+mod consent_response;
 mod end_session_response;
+mod federation_response;
+mod magic_link_response;
 mod new_session_response;
+mod password_policy_response;
 mod redirect_response;
+mod scope_response;
 mod session_response;
 mod session_token_response;
+mod totp_response;
 mod user_response;
+mod webauthn_response;
 
 pub use self::{
-    client_response::*, end_session_response::*, new_session_response::*, redirect_response::*,
-    session_response::*, session_token_response::*, user_response::*,
+    authorization_detail_type_response::*, backchannel_authorization_response::*,
+    client_policy_response::*, client_response::*, consent_response::*, end_session_response::*,
+    federation_response::*, magic_link_response::*, new_session_response::*,
+    password_policy_response::*, redirect_response::*, scope_response::*, session_response::*,
+    session_token_response::*, totp_response::*, user_response::*, webauthn_response::*,
 };
@@ -0,0 +1,38 @@
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use crate::api::v1::models::PasswordPolicyViolation;
+
+#[derive(Deserialize, Serialize)]
+pub struct PasswordPolicyViolationResponse {
+    #[serde(flatten)]
+    pub violation: PasswordPolicyViolation,
+    pub message: String,
+}
+
+/// Every rule of the password policy a password fails, each with a message that can be shown
+/// next to the password field as is.
+#[derive(Deserialize, Serialize)]
+pub struct PasswordPolicyResponse {
+    pub violations: Vec<PasswordPolicyViolationResponse>,
+}
+
+impl From<Vec<PasswordPolicyViolation>> for PasswordPolicyResponse {
+    fn from(violations: Vec<PasswordPolicyViolation>) -> Self {
+        Self {
+            violations: violations
+                .into_iter()
+                .map(|violation| PasswordPolicyViolationResponse {
+                    message: violation.message(),
+                    violation,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl IntoResponse for PasswordPolicyResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
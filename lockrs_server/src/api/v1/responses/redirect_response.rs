@@ -3,11 +3,14 @@ use serde::Serialize;
 use url::Url;
 use uuid::Uuid;
 
+use crate::models::RedirectMatchMode;
+
 #[derive(Serialize)]
 pub struct RedirectResponse {
     pub id: Uuid,
     pub client_id: String,
     pub uri: Url,
+    pub match_mode: RedirectMatchMode,
 }
 
 impl IntoResponse for RedirectResponse {
@@ -0,0 +1,39 @@
+use axum::{response::IntoResponse, Json};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct ScopeResponse {
+    pub name: String,
+    pub client_id: Option<String>,
+    pub description: String,
+    pub consent_text: Option<String>,
+}
+
+impl IntoResponse for ScopeResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Serialize)]
+pub struct ScopeListResponse {
+    pub scopes: Vec<ScopeResponse>,
+}
+
+impl IntoResponse for ScopeListResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Serialize)]
+pub struct AllowedScopesResponse {
+    pub allowed_scopes: Vec<String>,
+    pub default_scopes: Vec<String>,
+}
+
+impl IntoResponse for AllowedScopesResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 pub struct SessionTokenResponse {
     pub session_token: String,
     pub expires_at: i64,
+    /// the token has to be exchanged at `/api/v1/auth/mfa` before it can start a session
+    pub mfa_required: bool,
 }
 
 impl IntoResponse for SessionTokenResponse {
@@ -0,0 +1,25 @@
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct TotpEnrollmentResponse {
+    pub secret: String,
+    pub provisioning_uri: String,
+}
+
+impl IntoResponse for TotpEnrollmentResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+impl IntoResponse for RecoveryCodesResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
@@ -6,6 +6,7 @@ use uuid::Uuid;
 pub struct UserResponse {
     pub id: Uuid,
     pub email: String,
+    pub email_verified: bool,
 }
 
 impl IntoResponse for UserResponse {
@@ -0,0 +1,115 @@
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+/// The options to pass to `navigator.credentials.create()`, with the challenge and ids base64url
+/// encoded.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebauthnRegistrationOptionsResponse {
+    /// sent back with the authenticator's response, to look the challenge up again
+    pub challenge_id: String,
+    pub public_key: WebauthnCreationOptions,
+}
+
+impl IntoResponse for WebauthnRegistrationOptionsResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
+
+/// The options to pass to `navigator.credentials.get()`, with the challenge and ids base64url
+/// encoded.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebauthnLoginOptionsResponse {
+    /// sent back with the authenticator's response, to look the challenge up again
+    pub challenge_id: String,
+    pub public_key: WebauthnRequestOptions,
+}
+
+impl IntoResponse for WebauthnLoginOptionsResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebauthnCreationOptions {
+    pub rp: WebauthnRelyingParty,
+    pub user: WebauthnUser,
+    pub challenge: String,
+    pub pub_key_cred_params: Vec<WebauthnCredentialParameters>,
+    pub timeout: i64,
+    pub exclude_credentials: Vec<WebauthnCredentialDescriptor>,
+    pub authenticator_selection: WebauthnAuthenticatorSelection,
+    pub attestation: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebauthnRequestOptions {
+    pub challenge: String,
+    pub timeout: i64,
+    pub rp_id: String,
+    pub allow_credentials: Vec<WebauthnCredentialDescriptor>,
+    pub user_verification: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebauthnRelyingParty {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebauthnUser {
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebauthnCredentialParameters {
+    #[serde(rename = "type")]
+    pub credential_type: String,
+    pub alg: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebauthnCredentialDescriptor {
+    #[serde(rename = "type")]
+    pub credential_type: String,
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebauthnAuthenticatorSelection {
+    pub resident_key: String,
+    pub user_verification: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebauthnCredentialResponse {
+    pub credential_id: String,
+    pub sign_count: i64,
+    pub created_at: i64,
+    pub last_used_at: Option<i64>,
+}
+
+impl IntoResponse for WebauthnCredentialResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebauthnCredentialListResponse {
+    pub credentials: Vec<WebauthnCredentialResponse>,
+}
+
+impl IntoResponse for WebauthnCredentialListResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
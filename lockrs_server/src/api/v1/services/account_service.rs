@@ -0,0 +1,369 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        mailers::{Mail, Mailer, MailerError},
+        models::{EmailChange, PasswordChange, PasswordPolicyViolation},
+        services::{
+            PasswordPolicyService, PasswordPolicyServiceError, UserAuthService,
+            UserAuthServiceError,
+        },
+    },
+    db::{
+        repositories::{
+            QueryFailure, RefreshTokenRepository, RepositoryError, SessionRepository,
+            UserAuthRepository, UserRepository,
+        },
+        DbContext,
+    },
+    models::{UserModel, UserUpdateModel},
+    utils::signed_token::{SignedTokenError, SignedTokenUtil},
+    PasswordHashingConfig, PasswordPolicy,
+};
+
+/// how long the link confirming a new email address can be followed for
+pub const EMAIL_CHANGE_TTL_HOURS: i64 = 24;
+
+const EMAIL_CHANGE_PURPOSE: &str = "email_change";
+
+/// The current address is signed along with the new one, so that once the address has changed
+/// by any means, no link mailed out before can change it again.
+#[derive(Serialize, Deserialize)]
+struct EmailChangeClaims {
+    sub: Uuid,
+    email: String,
+    new_email: String,
+}
+
+pub struct AccountService;
+
+impl AccountService {
+    /// Replaces the user's password, as long as they know the current one. With
+    /// `end_other_sessions`, every session of the user but `session_id` is ended and their
+    /// refresh tokens are revoked.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn change_password(
+        db_context: &Arc<DbContext>,
+        user_auth_repository: &dyn UserAuthRepository,
+        session_repository: &dyn SessionRepository,
+        refresh_token_repository: &dyn RefreshTokenRepository,
+        password_policy: &PasswordPolicy,
+        password_hashing: &PasswordHashingConfig,
+        user_id: &Uuid,
+        session_id: &str,
+        password_change: &PasswordChange,
+        end_other_sessions: bool,
+    ) -> Result<(), AccountServiceError> {
+        tracing::trace!(method = "change_password", ?user_id, end_other_sessions);
+
+        let user = user_auth_repository
+            .get_by_id(db_context, user_id)
+            .await
+            .map_err(AccountServiceError::from)?;
+
+        UserAuthService::verify_password(
+            password_change.current_password.as_str(),
+            user.password_hash.as_str(),
+        )
+        .map_err(AccountServiceError::from)?;
+
+        PasswordPolicyService::check(
+            password_policy,
+            password_change.new_password.as_str(),
+            user.email.as_str(),
+        )
+        .map_err(AccountServiceError::from)?;
+
+        let password_hash =
+            UserAuthService::hash_password(password_change.new_password.as_str(), password_hashing)
+                .map_err(AccountServiceError::from)?;
+
+        user_auth_repository
+            .change_password_hash_by_id(db_context, user_id, password_hash.as_str())
+            .await
+            .map_err(AccountServiceError::from)?;
+
+        if end_other_sessions {
+            Self::end_other_sessions(
+                db_context,
+                session_repository,
+                refresh_token_repository,
+                user_id,
+                session_id,
+            )
+            .await?;
+        }
+
+        tracing::info!("Password changed for user with ID: {}", user_id);
+
+        Ok(())
+    }
+
+    /// Mails a link to the new address, which has to be followed before the address of the
+    /// account is changed over to it.
+    pub async fn request_email_change(
+        db_context: &Arc<DbContext>,
+        user_auth_repository: &dyn UserAuthRepository,
+        mailer: &dyn Mailer,
+        link_signing_key: &[u8],
+        frontend_url: &str,
+        user_id: &Uuid,
+        email_change: &EmailChange,
+    ) -> Result<(), AccountServiceError> {
+        tracing::trace!(method = "request_email_change", ?user_id, ?email_change);
+
+        let user = user_auth_repository
+            .get_by_id(db_context, user_id)
+            .await
+            .map_err(AccountServiceError::from)?;
+
+        UserAuthService::verify_password(
+            email_change.current_password.as_str(),
+            user.password_hash.as_str(),
+        )
+        .map_err(AccountServiceError::from)?;
+
+        if user.email == email_change.email {
+            tracing::error!(error = "New email address is the current one");
+            return Err(AccountServiceError::AlreadyExists);
+        }
+
+        match user_auth_repository
+            .get_by_email(db_context, email_change.email.as_str())
+            .await
+        {
+            Ok(_) => {
+                tracing::error!(error = "New email address belongs to another account");
+                return Err(AccountServiceError::AlreadyExists);
+            }
+            Err(RepositoryError::QueryFailed(QueryFailure::NotFound)) => {}
+            Err(err) => return Err(AccountServiceError::from(err)),
+        }
+
+        let claims = EmailChangeClaims {
+            sub: user.id,
+            email: user.email,
+            new_email: email_change.email.to_owned(),
+        };
+        let expires_at = (Utc::now() + Duration::hours(EMAIL_CHANGE_TTL_HOURS)).timestamp();
+
+        let token =
+            SignedTokenUtil::sign(link_signing_key, EMAIL_CHANGE_PURPOSE, &claims, expires_at)
+                .map_err(AccountServiceError::from)?;
+
+        let mut link = Url::parse(frontend_url)
+            .and_then(|url| url.join("confirm-email"))
+            .map_err(|err| {
+                tracing::error!(error = %err);
+                AccountServiceError::InternalError
+            })?;
+        link.query_pairs_mut().append_pair("token", token.as_str());
+
+        let mail = Mail::new(
+            email_change.email.as_str(),
+            "Confirm your new email address",
+            format!(
+                "Follow the link below to start using this address for your account. It expires in {} hours.\n\n{}\n\nIf you did not ask to change your email address, you can ignore this mail.",
+                EMAIL_CHANGE_TTL_HOURS, link
+            )
+            .as_str(),
+        );
+
+        mailer
+            .send(&mail)
+            .await
+            .map_err(AccountServiceError::from)?;
+
+        tracing::info!("Email change requested for user with ID: {}", user_id);
+
+        Ok(())
+    }
+
+    /// Changes the address of the account over to the one a confirmation token was mailed to,
+    /// which counts as verified from then on, and lets the previous address know. With
+    /// `end_other_sessions`, every session of the user but `session_id` is ended and their
+    /// refresh tokens are revoked.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn confirm_email_change(
+        db_context: &Arc<DbContext>,
+        user_repository: &dyn UserRepository,
+        session_repository: &dyn SessionRepository,
+        refresh_token_repository: &dyn RefreshTokenRepository,
+        mailer: &dyn Mailer,
+        link_signing_key: &[u8],
+        user_id: &Uuid,
+        session_id: &str,
+        token: &str,
+        end_other_sessions: bool,
+    ) -> Result<UserModel, AccountServiceError> {
+        tracing::trace!(
+            method = "confirm_email_change",
+            ?user_id,
+            end_other_sessions
+        );
+
+        let claims = SignedTokenUtil::verify::<EmailChangeClaims>(
+            link_signing_key,
+            EMAIL_CHANGE_PURPOSE,
+            token,
+        )
+        .map_err(AccountServiceError::from)?;
+
+        if &claims.sub != user_id {
+            tracing::error!(error = "Email change token was issued to another user");
+            return Err(AccountServiceError::InvalidToken);
+        }
+
+        let user = user_repository
+            .get_by_id(db_context, user_id)
+            .await
+            .map_err(AccountServiceError::from)?;
+
+        if user.email != claims.email {
+            tracing::error!(error = "Email address has changed since the token was issued");
+            return Err(AccountServiceError::InvalidToken);
+        }
+
+        // following the link proves the new address is the user's
+        let user_update = UserUpdateModel::new(Some(claims.new_email.as_str()), Some(true));
+        let user = user_repository
+            .update_by_id(db_context, user_id, &user_update)
+            .await
+            .map_err(AccountServiceError::from)?;
+
+        if end_other_sessions {
+            Self::end_other_sessions(
+                db_context,
+                session_repository,
+                refresh_token_repository,
+                user_id,
+                session_id,
+            )
+            .await?;
+        }
+
+        let mail = Mail::new(
+            claims.email.as_str(),
+            "Your email address was changed",
+            format!(
+                "The email address of your account was changed to {}. You will no longer receive mail about your account at this address.\n\nIf you did not make this change, please contact support right away.",
+                user.email
+            )
+            .as_str(),
+        );
+
+        // the address has changed either way, so there is nothing to gain from failing now
+        if let Err(err) = mailer.send(&mail).await {
+            tracing::error!(error = %err);
+        }
+
+        tracing::info!("Email address changed for user with ID: {}", user_id);
+
+        Ok(user)
+    }
+
+    async fn end_other_sessions(
+        db_context: &Arc<DbContext>,
+        session_repository: &dyn SessionRepository,
+        refresh_token_repository: &dyn RefreshTokenRepository,
+        user_id: &Uuid,
+        session_id: &str,
+    ) -> Result<(), AccountServiceError> {
+        let revoked_tokens = refresh_token_repository
+            .revoke_all_by_user_id(db_context, user_id)
+            .await
+            .map_err(AccountServiceError::from)?;
+
+        session_repository
+            .delete_others_by_user_id(db_context, user_id, session_id)
+            .await
+            .map_err(AccountServiceError::from)?;
+
+        tracing::info!(
+            "Other sessions ended: {{ user_id: {}, revoked_tokens: {} }}",
+            user_id,
+            revoked_tokens
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AccountServiceError {
+    #[error("ACCOUNT SERVICE ERROR :: Not Found")]
+    NotFound,
+    #[error("ACCOUNT SERVICE ERROR :: Invalid Credentials")]
+    Credentials,
+    #[error("ACCOUNT SERVICE ERROR :: Already Exists")]
+    AlreadyExists,
+    #[error("ACCOUNT SERVICE ERROR :: Invalid Token")]
+    InvalidToken,
+    #[error("ACCOUNT SERVICE ERROR :: Delivery Failed")]
+    DeliveryFailed,
+    #[error("ACCOUNT SERVICE ERROR :: Password Policy Violated")]
+    PasswordPolicy(Vec<PasswordPolicyViolation>),
+
+    #[error("ACCOUNT SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for AccountServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            RepositoryError::QueryFailed(QueryFailure::NotFound) => Self::NotFound,
+            RepositoryError::QueryFailed(QueryFailure::AlreadyExists) => Self::AlreadyExists,
+
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<UserAuthServiceError> for AccountServiceError {
+    fn from(err: UserAuthServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            UserAuthServiceError::Credentials => Self::Credentials,
+
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<PasswordPolicyServiceError> for AccountServiceError {
+    fn from(err: PasswordPolicyServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            PasswordPolicyServiceError::Violated(violations) => Self::PasswordPolicy(violations),
+        }
+    }
+}
+
+impl From<SignedTokenError> for AccountServiceError {
+    fn from(err: SignedTokenError) -> Self {
+        tracing::error!(error = ?err);
+
+        match err {
+            SignedTokenError::InvalidToken | SignedTokenError::Expired => Self::InvalidToken,
+            SignedTokenError::Encode => Self::InternalError,
+        }
+    }
+}
+
+impl From<MailerError> for AccountServiceError {
+    fn from(err: MailerError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::DeliveryFailed
+    }
+}
@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::mailers::{Mail, Mailer, MailerError},
+    db::{
+        repositories::{QueryFailure, RepositoryError, UserRepository},
+        DbContext,
+    },
+    models::{UserModel, UserUpdateModel},
+    utils::signed_token::{SignedTokenError, SignedTokenUtil},
+};
+
+/// how long the link in a verification mail can be followed for
+pub const EMAIL_VERIFICATION_TTL_HOURS: i64 = 24;
+
+const EMAIL_VERIFICATION_PURPOSE: &str = "email_verification";
+
+/// The address is signed along with the user, so that a link mailed to an address the user has
+/// since changed away from can not verify the new one.
+#[derive(Serialize, Deserialize)]
+struct EmailVerificationClaims {
+    sub: Uuid,
+    email: String,
+}
+
+pub struct EmailVerificationService;
+
+impl EmailVerificationService {
+    /// Mails the user a link to verify their email address with.
+    pub async fn send_verification(
+        mailer: &dyn Mailer,
+        link_signing_key: &[u8],
+        frontend_url: &str,
+        user: &UserModel,
+    ) -> Result<(), EmailVerificationServiceError> {
+        tracing::trace!(method = "send_verification", user_id = ?user.id);
+
+        let claims = EmailVerificationClaims {
+            sub: user.id,
+            email: user.email.to_owned(),
+        };
+        let expires_at = (Utc::now() + Duration::hours(EMAIL_VERIFICATION_TTL_HOURS)).timestamp();
+
+        let token = SignedTokenUtil::sign(
+            link_signing_key,
+            EMAIL_VERIFICATION_PURPOSE,
+            &claims,
+            expires_at,
+        )
+        .map_err(EmailVerificationServiceError::from)?;
+
+        let mut link = Url::parse(frontend_url)
+            .and_then(|url| url.join("verify-email"))
+            .map_err(|err| {
+                tracing::error!(error = %err);
+                EmailVerificationServiceError::InternalError
+            })?;
+        link.query_pairs_mut().append_pair("token", token.as_str());
+
+        let mail = Mail::new(
+            user.email.as_str(),
+            "Verify your email address",
+            format!(
+                "Follow the link below to verify your email address. It expires in {} hours.\n\n{}\n\nIf you did not create an account, you can ignore this mail.",
+                EMAIL_VERIFICATION_TTL_HOURS, link
+            )
+            .as_str(),
+        );
+
+        mailer
+            .send(&mail)
+            .await
+            .map_err(EmailVerificationServiceError::from)?;
+
+        tracing::info!("Verification mail sent to user with ID: {}", user.id);
+
+        Ok(())
+    }
+
+    /// Sends the user a new verification mail, unless their address is verified already.
+    pub async fn resend_verification(
+        db_context: &Arc<DbContext>,
+        user_repository: &dyn UserRepository,
+        mailer: &dyn Mailer,
+        link_signing_key: &[u8],
+        frontend_url: &str,
+        user_id: &Uuid,
+    ) -> Result<(), EmailVerificationServiceError> {
+        tracing::trace!(method = "resend_verification", ?user_id);
+
+        let user = user_repository
+            .get_by_id(db_context, user_id)
+            .await
+            .map_err(EmailVerificationServiceError::from)?;
+
+        if user.email_verified {
+            tracing::error!(error = "Email address is already verified");
+            return Err(EmailVerificationServiceError::AlreadyVerified);
+        }
+
+        Self::send_verification(mailer, link_signing_key, frontend_url, &user).await
+    }
+
+    /// Marks the address a verification token was mailed to as verified, as long as it is still
+    /// the user's.
+    pub async fn verify(
+        db_context: &Arc<DbContext>,
+        user_repository: &dyn UserRepository,
+        link_signing_key: &[u8],
+        token: &str,
+    ) -> Result<UserModel, EmailVerificationServiceError> {
+        tracing::trace!(method = "verify");
+
+        let claims = SignedTokenUtil::verify::<EmailVerificationClaims>(
+            link_signing_key,
+            EMAIL_VERIFICATION_PURPOSE,
+            token,
+        )
+        .map_err(EmailVerificationServiceError::from)?;
+
+        let user = user_repository
+            .get_by_id(db_context, &claims.sub)
+            .await
+            .map_err(|err| match err {
+                RepositoryError::QueryFailed(QueryFailure::NotFound) => {
+                    EmailVerificationServiceError::InvalidToken
+                }
+                err => EmailVerificationServiceError::from(err),
+            })?;
+
+        if user.email != claims.email {
+            tracing::error!(error = "Verification token was issued for another address");
+            return Err(EmailVerificationServiceError::InvalidToken);
+        }
+
+        // following the link twice is harmless
+        if user.email_verified {
+            return Ok(user);
+        }
+
+        let user_update = UserUpdateModel::new(None, Some(true));
+        let user = user_repository
+            .update_by_id(db_context, &user.id, &user_update)
+            .await
+            .map_err(EmailVerificationServiceError::from)?;
+
+        tracing::info!("Email address verified for user with ID: {}", user.id);
+
+        Ok(user)
+    }
+
+    /// Fails unless the user has verified their email address.
+    pub async fn ensure_verified(
+        db_context: &Arc<DbContext>,
+        user_repository: &dyn UserRepository,
+        user_id: &Uuid,
+    ) -> Result<(), EmailVerificationServiceError> {
+        tracing::trace!(method = "ensure_verified", ?user_id);
+
+        let user = user_repository
+            .get_by_id(db_context, user_id)
+            .await
+            .map_err(EmailVerificationServiceError::from)?;
+
+        if !user.email_verified {
+            tracing::error!(error = "Email address is not verified");
+            return Err(EmailVerificationServiceError::NotVerified);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum EmailVerificationServiceError {
+    #[error("EMAIL VERIFICATION SERVICE ERROR :: Not Found")]
+    NotFound,
+    #[error("EMAIL VERIFICATION SERVICE ERROR :: Already Verified")]
+    AlreadyVerified,
+    #[error("EMAIL VERIFICATION SERVICE ERROR :: Not Verified")]
+    NotVerified,
+    #[error("EMAIL VERIFICATION SERVICE ERROR :: Invalid Token")]
+    InvalidToken,
+    #[error("EMAIL VERIFICATION SERVICE ERROR :: Delivery Failed")]
+    DeliveryFailed,
+
+    #[error("EMAIL VERIFICATION SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for EmailVerificationServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            RepositoryError::QueryFailed(QueryFailure::NotFound) => Self::NotFound,
+
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<SignedTokenError> for EmailVerificationServiceError {
+    fn from(err: SignedTokenError) -> Self {
+        tracing::error!(error = ?err);
+
+        match err {
+            SignedTokenError::InvalidToken | SignedTokenError::Expired => Self::InvalidToken,
+            SignedTokenError::Encode => Self::InternalError,
+        }
+    }
+}
+
+impl From<MailerError> for EmailVerificationServiceError {
+    fn from(err: MailerError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::DeliveryFailed
+    }
+}
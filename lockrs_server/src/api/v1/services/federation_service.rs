@@ -0,0 +1,626 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::http::header::{ACCEPT, USER_AGENT};
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{
+    jwk::{AlgorithmParameters, JwkSet},
+    Algorithm, DecodingKey, Validation,
+};
+use rand::Rng;
+use ring::constant_time;
+use serde::Deserialize;
+use serde_json::Value;
+use thiserror::Error;
+use url::Url;
+
+use crate::{
+    api::v1::{
+        models::{
+            FederatedIdentityCreateModel, FederationStateModel, SessionTokenModel,
+            UpstreamIdentityModel, UserRegisterModel,
+        },
+        services::{
+            MfaService, MfaServiceError, SessionTokenService, SessionTokenServiceError,
+            UserAuthService, UserAuthServiceError,
+        },
+    },
+    db::{
+        digest_token,
+        repositories::{
+            FederatedIdentityRepository, FederationStateRepository, QueryFailure, RepositoryError,
+            SessionTokenRepository, TotpRepository, UserAuthRepository, UserRepository,
+            WebauthnCredentialRepository,
+        },
+        DbContext,
+    },
+    models::{UserModel, UserUpdateModel},
+    FederationProtocol, FederationProvider, PasswordHashingConfig,
+};
+
+/// how long a user has to sign in at the provider and come back
+pub const FEDERATION_STATE_TTL_MINUTES: i64 = 10;
+
+/// The part of a token response that is used, whatever else the provider sends along.
+#[derive(Deserialize)]
+struct UpstreamTokenResponse {
+    access_token: String,
+    id_token: Option<String>,
+}
+
+pub struct FederationService;
+
+impl FederationService {
+    /// Starts a sign-in with `provider`, returning the url to send the user to. The sign-in can
+    /// only be completed once, within `FEDERATION_STATE_TTL_MINUTES`, and only along with
+    /// `binding`, which is kept by the browser that started it.
+    pub async fn begin(
+        db_context: &Arc<DbContext>,
+        federation_state_repository: &dyn FederationStateRepository,
+        provider: &FederationProvider,
+        frontend_url: &str,
+        binding: &str,
+    ) -> Result<String, FederationServiceError> {
+        tracing::trace!(method = "begin", provider = provider.name);
+
+        let state = Self::generate_secret();
+        let nonce = Self::generate_secret();
+        let code_verifier = Self::generate_secret();
+        let expires_at = Utc::now() + Duration::minutes(FEDERATION_STATE_TTL_MINUTES);
+
+        let federation_state = FederationStateModel::new(
+            provider.name.as_str(),
+            nonce.as_str(),
+            code_verifier.as_str(),
+            digest_token(binding).as_str(),
+            expires_at.timestamp_millis(),
+        );
+
+        federation_state_repository
+            .create(db_context, state.as_str(), &federation_state)
+            .await
+            .map_err(FederationServiceError::from)?;
+
+        let redirect_uri = Self::redirect_uri(provider, frontend_url)?;
+
+        let mut authorization_url =
+            Url::parse(provider.authorization_endpoint.as_str()).map_err(|err| {
+                tracing::error!(error = %err);
+                FederationServiceError::InternalError
+            })?;
+
+        {
+            let mut query = authorization_url.query_pairs_mut();
+            query
+                .append_pair("response_type", "code")
+                .append_pair("client_id", provider.client_id.as_str())
+                .append_pair("redirect_uri", redirect_uri.as_str())
+                .append_pair("state", state.as_str())
+                // the S256 challenge is the same base64url encoded sha256 tokens are stored as
+                .append_pair(
+                    "code_challenge",
+                    digest_token(code_verifier.as_str()).as_str(),
+                )
+                .append_pair("code_challenge_method", "S256");
+
+            if !provider.scopes.is_empty() {
+                query.append_pair("scope", provider.scopes.join(" ").as_str());
+            }
+
+            if provider.protocol == FederationProtocol::Oidc {
+                query.append_pair("nonce", nonce.as_str());
+            }
+        }
+
+        Ok(authorization_url.to_string())
+    }
+
+    /// Completes a sign-in the user has come back from `provider` with, redeeming `code` and
+    /// finding out who the provider says the user is: from the id token for an oidc provider,
+    /// or from its userinfo endpoint otherwise.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn authenticate(
+        db_context: &Arc<DbContext>,
+        federation_state_repository: &dyn FederationStateRepository,
+        provider: &FederationProvider,
+        frontend_url: &str,
+        code: &str,
+        state: &str,
+        binding: Option<&str>,
+    ) -> Result<UpstreamIdentityModel, FederationServiceError> {
+        tracing::trace!(method = "authenticate", provider = provider.name);
+
+        let federation_state = federation_state_repository
+            .take_by_state(db_context, state)
+            .await
+            .map_err(|err| match err {
+                RepositoryError::QueryFailed(QueryFailure::NotFound) => {
+                    tracing::error!(error = %err);
+                    FederationServiceError::InvalidState
+                }
+                err => FederationServiceError::from(err),
+            })?;
+
+        let binding_matches = binding
+            .map(|binding| Self::digest_matches(binding, federation_state.binding_digest.as_str()))
+            .unwrap_or(false);
+
+        if federation_state.provider != provider.name || !binding_matches {
+            tracing::error!(
+                error = "Federated sign-in completed with another provider or from another browser",
+                binding_matches,
+            );
+            return Err(FederationServiceError::InvalidState);
+        }
+
+        let redirect_uri = Self::redirect_uri(provider, frontend_url)?;
+
+        let token_response = Self::http_client()?
+            .post(provider.token_endpoint.as_str())
+            .header(ACCEPT, "application/json")
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri.as_str()),
+                ("client_id", provider.client_id.as_str()),
+                ("client_secret", provider.client_secret.as_str()),
+                ("code_verifier", federation_state.code_verifier.as_str()),
+            ])
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(FederationServiceError::from)?
+            .json::<UpstreamTokenResponse>()
+            .await
+            .map_err(FederationServiceError::from)?;
+
+        let claims = match provider.protocol {
+            FederationProtocol::Oidc => {
+                let Some(id_token) = token_response.id_token
+                else {
+                    tracing::error!(error = "Token response is missing an id token");
+                    return Err(FederationServiceError::InvalidIdentity);
+                };
+
+                Self::verify_id_token(provider, id_token.as_str(), &federation_state).await?
+            }
+            FederationProtocol::OAuth2 => {
+                Self::read_userinfo(provider, token_response.access_token.as_str()).await?
+            }
+        };
+
+        let Some(subject) = Self::string_claim(&claims, provider.subject_claim.as_str())
+        else {
+            tracing::error!(
+                error = "Upstream identity is missing its subject",
+                claim = provider.subject_claim,
+            );
+            return Err(FederationServiceError::InvalidIdentity);
+        };
+
+        let email = Self::string_claim(&claims, "email");
+        let email_verified = match claims.get("email_verified") {
+            Some(Value::Bool(email_verified)) => *email_verified,
+            // some providers send their booleans as strings
+            Some(Value::String(email_verified)) => email_verified == "true",
+            _ => false,
+        };
+
+        Ok(UpstreamIdentityModel::new(
+            subject.as_str(),
+            email.as_deref(),
+            email_verified,
+        ))
+    }
+
+    /// Signs in the local user linked to the upstream identity, the same as a password login
+    /// would. The first sign-in of an identity links it to the user with the same email if the
+    /// provider is trusted to, and has verified the address, or provisions a new user for it
+    /// otherwise. A provisioned user has no usable password until they reset it.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn sign_in(
+        db_context: &Arc<DbContext>,
+        user_repository: &dyn UserRepository,
+        user_auth_repository: &dyn UserAuthRepository,
+        federated_identity_repository: &dyn FederatedIdentityRepository,
+        session_token_repository: &dyn SessionTokenRepository,
+        totp_repository: &dyn TotpRepository,
+        webauthn_credential_repository: &dyn WebauthnCredentialRepository,
+        password_hashing: &PasswordHashingConfig,
+        provider: &FederationProvider,
+        upstream_identity: &UpstreamIdentityModel,
+        require_verified_email: bool,
+    ) -> Result<SessionTokenModel, FederationServiceError> {
+        tracing::trace!(
+            method = "sign_in",
+            provider = provider.name,
+            ?upstream_identity
+        );
+
+        let user = match federated_identity_repository
+            .get_by_provider_and_subject(
+                db_context,
+                provider.name.as_str(),
+                upstream_identity.subject.as_str(),
+            )
+            .await
+        {
+            Ok(identity) => user_repository
+                .get_by_id(db_context, &identity.user_id)
+                .await
+                .map_err(FederationServiceError::from)?,
+            Err(RepositoryError::QueryFailed(QueryFailure::NotFound)) => {
+                Self::link_or_provision(
+                    db_context,
+                    user_repository,
+                    user_auth_repository,
+                    federated_identity_repository,
+                    password_hashing,
+                    provider,
+                    upstream_identity,
+                )
+                .await?
+            }
+            Err(err) => return Err(FederationServiceError::from(err)),
+        };
+
+        if require_verified_email && !user.email_verified {
+            tracing::error!(error = "Email address is not verified");
+            return Err(FederationServiceError::EmailNotVerified);
+        }
+
+        let mfa_pending = MfaService::is_required(
+            db_context,
+            totp_repository,
+            webauthn_credential_repository,
+            &user.id,
+        )
+        .await
+        .map_err(FederationServiceError::from)?;
+
+        let session_token = SessionTokenService::create_session_token(
+            db_context,
+            session_token_repository,
+            &user.id,
+            mfa_pending,
+        )
+        .await
+        .map_err(FederationServiceError::from)?;
+
+        tracing::info!(
+            "User authenticated by {} with ID: {}, second factor pending: {}",
+            provider.name,
+            user.id,
+            mfa_pending,
+        );
+
+        Ok(session_token)
+    }
+
+    /// A random value for the browser starting a sign-in to keep, and present again to complete
+    /// it.
+    pub fn generate_binding() -> String {
+        Self::generate_secret()
+    }
+
+    async fn link_or_provision(
+        db_context: &Arc<DbContext>,
+        user_repository: &dyn UserRepository,
+        user_auth_repository: &dyn UserAuthRepository,
+        federated_identity_repository: &dyn FederatedIdentityRepository,
+        password_hashing: &PasswordHashingConfig,
+        provider: &FederationProvider,
+        upstream_identity: &UpstreamIdentityModel,
+    ) -> Result<UserModel, FederationServiceError> {
+        let Some(email) = upstream_identity.email.as_deref()
+        else {
+            tracing::error!(error = "Upstream identity has no email to provision a user with");
+            return Err(FederationServiceError::MissingEmail);
+        };
+
+        let identity_create = |user_id| {
+            FederatedIdentityCreateModel::new(
+                user_id,
+                provider.name.as_str(),
+                upstream_identity.subject.as_str(),
+            )
+        };
+
+        match user_repository.get_by_email(db_context, email).await {
+            Ok(user) => {
+                // otherwise anyone able to register the address at the provider would be able to
+                // take over the account
+                if !provider.link_by_email || !upstream_identity.email_verified {
+                    tracing::error!(
+                        error = "Upstream identity has the email of an existing user",
+                        link_by_email = provider.link_by_email,
+                        email_verified = upstream_identity.email_verified,
+                    );
+                    return Err(FederationServiceError::EmailTaken);
+                }
+
+                federated_identity_repository
+                    .create(db_context, &identity_create(&user.id))
+                    .await
+                    .map_err(FederationServiceError::from)?;
+
+                tracing::info!(
+                    "Linked {} identity to existing user with ID: {}",
+                    provider.name,
+                    user.id
+                );
+
+                Self::mark_email_verified(db_context, user_repository, user, upstream_identity)
+                    .await
+            }
+            Err(RepositoryError::QueryFailed(QueryFailure::NotFound)) => {
+                let password = Self::generate_secret();
+                let password_hash =
+                    UserAuthService::hash_password(password.as_str(), password_hashing)
+                        .map_err(FederationServiceError::from)?;
+
+                let user = user_auth_repository
+                    .create(
+                        db_context,
+                        &UserRegisterModel::new(email, password_hash.as_str()),
+                    )
+                    .await
+                    .map_err(FederationServiceError::from)?;
+
+                if let Err(err) = federated_identity_repository
+                    .create(db_context, &identity_create(&user.id))
+                    .await
+                {
+                    // the user is no use without the identity it was made for
+                    if let Err(err) = user_repository.delete_by_id(db_context, &user.id).await {
+                        tracing::error!(error = %err);
+                    }
+
+                    return Err(FederationServiceError::from(err));
+                }
+
+                tracing::info!(
+                    "Provisioned user for {} identity with ID: {}",
+                    provider.name,
+                    user.id
+                );
+
+                let user = UserModel::new(user.id, user.email.as_str(), user.email_verified);
+                Self::mark_email_verified(db_context, user_repository, user, upstream_identity)
+                    .await
+            }
+            Err(err) => Err(FederationServiceError::from(err)),
+        }
+    }
+
+    async fn mark_email_verified(
+        db_context: &Arc<DbContext>,
+        user_repository: &dyn UserRepository,
+        user: UserModel,
+        upstream_identity: &UpstreamIdentityModel,
+    ) -> Result<UserModel, FederationServiceError> {
+        if user.email_verified || !upstream_identity.email_verified {
+            return Ok(user);
+        }
+
+        let user_update = UserUpdateModel::new(None, Some(true));
+        user_repository
+            .update_by_id(db_context, &user.id, &user_update)
+            .await
+            .map_err(FederationServiceError::from)
+    }
+
+    /// Checks the id token was signed with one of the provider's published keys, for this
+    /// client, and for the sign-in it is completing.
+    async fn verify_id_token(
+        provider: &FederationProvider,
+        id_token: &str,
+        federation_state: &FederationStateModel,
+    ) -> Result<HashMap<String, Value>, FederationServiceError> {
+        let header = jsonwebtoken::decode_header(id_token).map_err(FederationServiceError::from)?;
+
+        // the client secret is known to more than the provider, so it can't vouch for a token
+        if matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            tracing::error!(error = "Id token is signed with a shared secret", alg = ?header.alg);
+            return Err(FederationServiceError::InvalidIdentity);
+        }
+
+        let (Some(jwks_uri), Some(issuer)) = (&provider.jwks_uri, &provider.issuer)
+        else {
+            tracing::error!(error = "Oidc provider is missing its jwks uri or issuer");
+            return Err(FederationServiceError::InternalError);
+        };
+
+        let jwks = Self::http_client()?
+            .get(jwks_uri.as_str())
+            .header(ACCEPT, "application/json")
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(FederationServiceError::from)?
+            .json::<JwkSet>()
+            .await
+            .map_err(FederationServiceError::from)?;
+
+        let jwk = match (&header.kid, jwks.keys.as_slice()) {
+            (Some(kid), _) => jwks.find(kid.as_str()),
+            (None, [jwk]) => Some(jwk),
+            (None, _) => None,
+        };
+
+        let Some(jwk) =
+            jwk.filter(|jwk| !matches!(jwk.algorithm, AlgorithmParameters::OctetKey(_)))
+        else {
+            tracing::error!(error = "Id token is not signed with a published key", kid = ?header.kid);
+            return Err(FederationServiceError::InvalidIdentity);
+        };
+
+        let decoding_key = DecodingKey::from_jwk(jwk).map_err(FederationServiceError::from)?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_audience(&[provider.client_id.as_str()]);
+        validation.set_issuer(&[issuer.as_str()]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+
+        let claims =
+            jsonwebtoken::decode::<HashMap<String, Value>>(id_token, &decoding_key, &validation)
+                .map_err(FederationServiceError::from)?
+                .claims;
+
+        let nonce_matches = Self::string_claim(&claims, "nonce")
+            .map(|nonce| {
+                constant_time::verify_slices_are_equal(
+                    nonce.as_bytes(),
+                    federation_state.nonce.as_bytes(),
+                )
+                .is_ok()
+            })
+            .unwrap_or(false);
+
+        if !nonce_matches {
+            tracing::error!(error = "Id token was issued for another sign-in");
+            return Err(FederationServiceError::InvalidIdentity);
+        }
+
+        Ok(claims)
+    }
+
+    async fn read_userinfo(
+        provider: &FederationProvider,
+        access_token: &str,
+    ) -> Result<HashMap<String, Value>, FederationServiceError> {
+        let Some(userinfo_endpoint) = &provider.userinfo_endpoint
+        else {
+            tracing::error!(error = "Oauth2 provider is missing its userinfo endpoint");
+            return Err(FederationServiceError::InternalError);
+        };
+
+        Self::http_client()?
+            .get(userinfo_endpoint.as_str())
+            .bearer_auth(access_token)
+            .header(ACCEPT, "application/json")
+            // GitHub turns away requests without one
+            .header(USER_AGENT, "lockrs")
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(FederationServiceError::from)?
+            .json::<HashMap<String, Value>>()
+            .await
+            .map_err(FederationServiceError::from)
+    }
+
+    /// Reads a claim as a string, which for a numeric one, e.g. GitHub's `id`, is its digits.
+    fn string_claim(claims: &HashMap<String, Value>, name: &str) -> Option<String> {
+        match claims.get(name)? {
+            Value::String(value) if !value.is_empty() => Some(value.to_owned()),
+            Value::Number(value) => Some(value.to_string()),
+            _ => None,
+        }
+    }
+
+    fn redirect_uri(
+        provider: &FederationProvider,
+        frontend_url: &str,
+    ) -> Result<String, FederationServiceError> {
+        Url::parse(frontend_url)
+            .and_then(|url| url.join(format!("federation/{}/callback", provider.name).as_str()))
+            .map(|url| url.to_string())
+            .map_err(|err| {
+                tracing::error!(error = %err);
+                FederationServiceError::InternalError
+            })
+    }
+
+    fn http_client() -> Result<reqwest::Client, FederationServiceError> {
+        reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(5))
+            .build()
+            .map_err(|err| {
+                tracing::error!(error = %err);
+                FederationServiceError::InternalError
+            })
+    }
+
+    fn digest_matches(value: &str, digest: &str) -> bool {
+        constant_time::verify_slices_are_equal(digest_token(value).as_bytes(), digest.as_bytes())
+            .is_ok()
+    }
+
+    fn generate_secret() -> String {
+        let mut rng = rand::thread_rng();
+        let bytes = (0..32).map(|_| rng.gen::<u8>()).collect::<Vec<u8>>();
+
+        general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FederationServiceError {
+    #[error("FEDERATION SERVICE ERROR :: Invalid State")]
+    InvalidState,
+    #[error("FEDERATION SERVICE ERROR :: Invalid Identity")]
+    InvalidIdentity,
+    #[error("FEDERATION SERVICE ERROR :: Missing Email")]
+    MissingEmail,
+    #[error("FEDERATION SERVICE ERROR :: Email Taken")]
+    EmailTaken,
+    #[error("FEDERATION SERVICE ERROR :: Email Not Verified")]
+    EmailNotVerified,
+    #[error("FEDERATION SERVICE ERROR :: Upstream Failed")]
+    UpstreamFailed,
+
+    #[error("FEDERATION SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for FederationServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<reqwest::Error> for FederationServiceError {
+    fn from(err: reqwest::Error) -> Self {
+        tracing::error!(error = %err);
+
+        Self::UpstreamFailed
+    }
+}
+
+impl From<jsonwebtoken::errors::Error> for FederationServiceError {
+    fn from(err: jsonwebtoken::errors::Error) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InvalidIdentity
+    }
+}
+
+impl From<UserAuthServiceError> for FederationServiceError {
+    fn from(err: UserAuthServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<MfaServiceError> for FederationServiceError {
+    fn from(err: MfaServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<SessionTokenServiceError> for FederationServiceError {
+    fn from(err: SessionTokenServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
@@ -0,0 +1,209 @@
+use std::{net::IpAddr, sync::Arc};
+
+use chrono::Utc;
+use thiserror::Error;
+
+use crate::{
+    db::{
+        repositories::{LoginAttemptRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    LoginThrottleConfig,
+};
+
+/// the most times a lockout is doubled, which keeps the arithmetic from overflowing long before
+/// `max_lockout` is reached by any sane configuration
+const MAX_BACKOFF_EXPONENT: u32 = 20;
+
+pub struct LoginThrottleService;
+
+impl LoginThrottleService {
+    /// Fails while the account, or the address the login comes from, is locked out. Accounts
+    /// are counted by the email a login is tried with whether or not it belongs to anyone, so a
+    /// lockout says nothing about which addresses are registered.
+    pub async fn check(
+        db_context: &Arc<DbContext>,
+        login_attempt_repository: &dyn LoginAttemptRepository,
+        email: &str,
+        address: Option<&IpAddr>,
+    ) -> Result<(), LoginThrottleServiceError> {
+        tracing::trace!(method = "check", email, ?address);
+
+        let now = Utc::now().timestamp_millis();
+        let mut locked_until = None;
+
+        for key in Self::keys(email, address) {
+            let login_attempt = match login_attempt_repository
+                .get_by_key(db_context, key.as_str())
+                .await
+            {
+                Ok(login_attempt) => login_attempt,
+                Err(RepositoryError::QueryFailed(QueryFailure::NotFound)) => continue,
+                Err(err) => return Err(LoginThrottleServiceError::from(err)),
+            };
+
+            locked_until = locked_until.max(login_attempt.locked_until);
+        }
+
+        match locked_until {
+            Some(locked_until) if locked_until > now => {
+                tracing::error!(error = "Login is locked out", email, ?address, locked_until);
+
+                // rounded up, so a client waiting exactly that long is let through
+                let retry_after = (locked_until - now + 999) / 1000;
+                Err(LoginThrottleServiceError::Locked(retry_after))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Counts a failed login against both the account and the address, locking out whichever
+    /// has reached its limit.
+    pub async fn record_failure(
+        db_context: &Arc<DbContext>,
+        login_attempt_repository: &dyn LoginAttemptRepository,
+        login_throttle: &LoginThrottleConfig,
+        email: &str,
+        address: Option<&IpAddr>,
+    ) -> Result<(), LoginThrottleServiceError> {
+        tracing::trace!(method = "record_failure", email, ?address);
+
+        let mut limits = vec![(
+            Self::account_key(email),
+            login_throttle.max_failures_per_account,
+        )];
+        if let Some(address) = address {
+            limits.push((
+                Self::address_key(address),
+                login_throttle.max_failures_per_address,
+            ));
+        }
+
+        for (key, max_failures) in limits {
+            let now = Utc::now();
+            let expires_at = (now + login_throttle.failure_window).timestamp_millis();
+
+            let login_attempt = login_attempt_repository
+                .record_failure_by_key(db_context, key.as_str(), expires_at)
+                .await
+                .map_err(LoginThrottleServiceError::from)?;
+
+            if login_attempt.failures < max_failures {
+                continue;
+            }
+
+            let lockout = Self::lockout_for(login_throttle, login_attempt.failures - max_failures);
+            let locked_until = (now + lockout).timestamp_millis();
+            // kept past the lockout, so the next failure after it doubles the lockout again
+            let expires_at = (now + lockout + login_throttle.failure_window).timestamp_millis();
+
+            login_attempt_repository
+                .lock_by_key(db_context, key.as_str(), locked_until, expires_at)
+                .await
+                .map_err(LoginThrottleServiceError::from)?;
+
+            tracing::warn!(
+                "Logins locked out: {{ key: {}, failures: {}, locked_until: {} }}",
+                key,
+                login_attempt.failures,
+                locked_until
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Forgets the failures counted against the account. Those counted against the address are
+    /// kept, so one account an attacker knows the password to can't be used to reset them.
+    pub async fn record_success(
+        db_context: &Arc<DbContext>,
+        login_attempt_repository: &dyn LoginAttemptRepository,
+        email: &str,
+    ) -> Result<(), LoginThrottleServiceError> {
+        tracing::trace!(method = "record_success", email);
+
+        login_attempt_repository
+            .delete_by_key(db_context, Self::account_key(email).as_str())
+            .await
+            .map_err(LoginThrottleServiceError::from)
+    }
+
+    pub async fn unlock_account(
+        db_context: &Arc<DbContext>,
+        login_attempt_repository: &dyn LoginAttemptRepository,
+        email: &str,
+    ) -> Result<(), LoginThrottleServiceError> {
+        tracing::trace!(method = "unlock_account", email);
+
+        login_attempt_repository
+            .delete_by_key(db_context, Self::account_key(email).as_str())
+            .await
+            .map_err(LoginThrottleServiceError::from)?;
+
+        tracing::info!("Account unlocked: {}", email);
+
+        Ok(())
+    }
+
+    pub async fn unlock_address(
+        db_context: &Arc<DbContext>,
+        login_attempt_repository: &dyn LoginAttemptRepository,
+        address: &IpAddr,
+    ) -> Result<(), LoginThrottleServiceError> {
+        tracing::trace!(method = "unlock_address", ?address);
+
+        login_attempt_repository
+            .delete_by_key(db_context, Self::address_key(address).as_str())
+            .await
+            .map_err(LoginThrottleServiceError::from)?;
+
+        tracing::info!("Address unlocked: {}", address);
+
+        Ok(())
+    }
+
+    /// `lockout`, doubled for every failure past the limit, up to `max_lockout`
+    fn lockout_for(login_throttle: &LoginThrottleConfig, failures_over: i64) -> chrono::Duration {
+        let exponent = failures_over.clamp(0, MAX_BACKOFF_EXPONENT as i64) as u32;
+
+        let lockout = login_throttle
+            .lockout
+            .checked_mul(2i32.pow(exponent))
+            .unwrap_or(login_throttle.max_lockout);
+
+        lockout.min(login_throttle.max_lockout)
+    }
+
+    fn keys(email: &str, address: Option<&IpAddr>) -> Vec<String> {
+        let mut keys = vec![Self::account_key(email)];
+        keys.extend(address.map(Self::address_key));
+
+        keys
+    }
+
+    fn account_key(email: &str) -> String {
+        format!("account:{}", email.to_lowercase())
+    }
+
+    fn address_key(address: &IpAddr) -> String {
+        format!("address:{}", address)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LoginThrottleServiceError {
+    /// with the number of seconds until logins are let through again
+    #[error("LOGIN THROTTLE SERVICE ERROR :: Locked out for {0} seconds")]
+    Locked(i64),
+
+    #[error("LOGIN THROTTLE SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for LoginThrottleServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
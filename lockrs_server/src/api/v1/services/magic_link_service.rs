@@ -0,0 +1,314 @@
+use std::sync::Arc;
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{Duration, Utc};
+use rand::Rng;
+use ring::constant_time;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use url::Url;
+
+use crate::{
+    api::v1::{
+        mailers::{Mail, Mailer, MailerError},
+        models::{MagicLinkModel, MagicLinkRedemption, SessionTokenModel},
+        services::{MfaService, MfaServiceError, SessionTokenService, SessionTokenServiceError},
+    },
+    db::{
+        digest_token,
+        repositories::{
+            MagicLinkRepository, QueryFailure, RepositoryError, SessionTokenRepository,
+            TotpRepository, UserAuthRepository, UserRepository, WebauthnCredentialRepository,
+        },
+        DbContext,
+    },
+    models::UserUpdateModel,
+    utils::signed_token::{SignedTokenError, SignedTokenUtil},
+};
+
+/// how long the link and code in a sign-in mail can be used for
+pub const MAGIC_LINK_TTL_MINUTES: i64 = 15;
+
+/// how many wrong codes, or redemptions from another browser, a sign-in link survives
+const MAX_REDEEM_ATTEMPTS: i64 = 5;
+
+const MAGIC_LINK_PURPOSE: &str = "magic_link";
+
+/// The nonce ties the link to the one sign-in it was mailed for, so that only the latest link
+/// sent to an address can be used, and only once.
+#[derive(Serialize, Deserialize)]
+struct MagicLinkClaims {
+    email: String,
+    nonce: String,
+}
+
+pub struct MagicLinkService;
+
+impl MagicLinkService {
+    /// Mails a single use sign-in link and one-time code to the owner of `email`, in place of
+    /// any they had outstanding. Either can only be redeemed along with `binding`, which is kept
+    /// by the browser that asked for them. Nothing is sent for an address without an account.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn request_link(
+        db_context: &Arc<DbContext>,
+        user_auth_repository: &dyn UserAuthRepository,
+        magic_link_repository: &dyn MagicLinkRepository,
+        mailer: &dyn Mailer,
+        link_signing_key: &[u8],
+        frontend_url: &str,
+        email: &str,
+        binding: &str,
+    ) -> Result<(), MagicLinkServiceError> {
+        tracing::trace!(method = "request_link", email);
+
+        let user = match user_auth_repository.get_by_email(db_context, email).await {
+            Ok(user) => user,
+            Err(RepositoryError::QueryFailed(QueryFailure::NotFound)) => return Ok(()),
+            Err(err) => return Err(MagicLinkServiceError::from(err)),
+        };
+
+        let nonce = Self::generate_secret();
+        let code = format!("{:06}", rand::thread_rng().gen_range(0..1_000_000));
+        let expires_at = Utc::now() + Duration::minutes(MAGIC_LINK_TTL_MINUTES);
+
+        let claims = MagicLinkClaims {
+            email: user.email.to_owned(),
+            nonce: nonce.to_owned(),
+        };
+
+        let token = SignedTokenUtil::sign(
+            link_signing_key,
+            MAGIC_LINK_PURPOSE,
+            &claims,
+            expires_at.timestamp(),
+        )
+        .map_err(MagicLinkServiceError::from)?;
+
+        let magic_link = MagicLinkModel::new(
+            &user.id,
+            user.email.as_str(),
+            digest_token(nonce.as_str()).as_str(),
+            digest_token(code.as_str()).as_str(),
+            digest_token(binding).as_str(),
+            0,
+            expires_at.timestamp_millis(),
+        );
+
+        magic_link_repository
+            .create(db_context, &magic_link)
+            .await
+            .map_err(MagicLinkServiceError::from)?;
+
+        let mut link = Url::parse(frontend_url)
+            .and_then(|url| url.join("magic-link"))
+            .map_err(|err| {
+                tracing::error!(error = %err);
+                MagicLinkServiceError::InternalError
+            })?;
+        link.query_pairs_mut().append_pair("token", token.as_str());
+
+        let mail = Mail::new(
+            user.email.as_str(),
+            "Your sign-in link",
+            format!(
+                "Follow the link below to sign in, or enter the code {} on the sign-in page. Either can be used once, from the browser you asked for them in, and expires in {} minutes.\n\n{}\n\nIf you did not ask to sign in, you can ignore this mail.",
+                code, MAGIC_LINK_TTL_MINUTES, link
+            )
+            .as_str(),
+        );
+
+        mailer
+            .send(&mail)
+            .await
+            .map_err(MagicLinkServiceError::from)?;
+
+        tracing::info!("Sign-in link mailed to user with ID: {}", user.id);
+
+        Ok(())
+    }
+
+    /// Redeems a sign-in link or code for a session token, the same as a password login would
+    /// give. Following the link proves the user owns the address, so it is marked as verified
+    /// along the way. A failed redemption leaves the link usable, up to `MAX_REDEEM_ATTEMPTS`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn redeem(
+        db_context: &Arc<DbContext>,
+        user_repository: &dyn UserRepository,
+        magic_link_repository: &dyn MagicLinkRepository,
+        session_token_repository: &dyn SessionTokenRepository,
+        totp_repository: &dyn TotpRepository,
+        webauthn_credential_repository: &dyn WebauthnCredentialRepository,
+        link_signing_key: &[u8],
+        redemption: &MagicLinkRedemption,
+        binding: Option<&str>,
+    ) -> Result<SessionTokenModel, MagicLinkServiceError> {
+        tracing::trace!(method = "redeem", ?redemption);
+
+        let (email, secret) = match redemption {
+            MagicLinkRedemption::Link { token } => {
+                let claims = SignedTokenUtil::verify::<MagicLinkClaims>(
+                    link_signing_key,
+                    MAGIC_LINK_PURPOSE,
+                    token.as_str(),
+                )
+                .map_err(MagicLinkServiceError::from)?;
+
+                (claims.email, claims.nonce)
+            }
+            MagicLinkRedemption::Code { email, code } => (email.to_owned(), code.to_owned()),
+        };
+
+        let mut magic_link = magic_link_repository
+            .take_by_email(db_context, email.as_str())
+            .await
+            .map_err(MagicLinkServiceError::from)?;
+
+        let expected_secret = match redemption {
+            MagicLinkRedemption::Link { .. } => magic_link.nonce_digest.as_str(),
+            MagicLinkRedemption::Code { .. } => magic_link.code_digest.as_str(),
+        };
+
+        let secret_matches = Self::digest_matches(secret.as_str(), expected_secret);
+        let binding_matches = binding
+            .map(|binding| Self::digest_matches(binding, magic_link.binding_digest.as_str()))
+            .unwrap_or(false);
+
+        if !secret_matches || !binding_matches {
+            tracing::error!(
+                error = "Sign-in link redeemed with a wrong secret or from another browser",
+                secret_matches,
+                binding_matches,
+            );
+
+            magic_link.failed_attempts += 1;
+            if magic_link.failed_attempts < MAX_REDEEM_ATTEMPTS {
+                magic_link_repository
+                    .create(db_context, &magic_link)
+                    .await
+                    .map_err(MagicLinkServiceError::from)?;
+            }
+
+            return Err(MagicLinkServiceError::InvalidToken);
+        }
+
+        let user = user_repository
+            .get_by_id(db_context, &magic_link.user_id)
+            .await
+            .map_err(MagicLinkServiceError::from)?;
+
+        // the address moved to another account since the link was sent
+        if user.email != magic_link.email {
+            tracing::error!(error = "Sign-in link was issued for another address");
+            return Err(MagicLinkServiceError::InvalidToken);
+        }
+
+        if !user.email_verified {
+            let user_update = UserUpdateModel::new(None, Some(true));
+            user_repository
+                .update_by_id(db_context, &user.id, &user_update)
+                .await
+                .map_err(MagicLinkServiceError::from)?;
+        }
+
+        let mfa_pending = MfaService::is_required(
+            db_context,
+            totp_repository,
+            webauthn_credential_repository,
+            &user.id,
+        )
+        .await
+        .map_err(MagicLinkServiceError::from)?;
+
+        let session_token = SessionTokenService::create_session_token(
+            db_context,
+            session_token_repository,
+            &user.id,
+            mfa_pending,
+        )
+        .await
+        .map_err(MagicLinkServiceError::from)?;
+
+        tracing::info!(
+            "User authenticated by sign-in link with ID: {}, second factor pending: {}",
+            user.id,
+            mfa_pending,
+        );
+
+        Ok(session_token)
+    }
+
+    /// A random value for the requesting browser to keep, and present again to redeem the link.
+    pub fn generate_binding() -> String {
+        Self::generate_secret()
+    }
+
+    fn digest_matches(value: &str, digest: &str) -> bool {
+        constant_time::verify_slices_are_equal(digest_token(value).as_bytes(), digest.as_bytes())
+            .is_ok()
+    }
+
+    fn generate_secret() -> String {
+        let mut rng = rand::thread_rng();
+        let bytes = (0..32).map(|_| rng.gen::<u8>()).collect::<Vec<u8>>();
+
+        general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum MagicLinkServiceError {
+    #[error("MAGIC LINK SERVICE ERROR :: Invalid Token")]
+    InvalidToken,
+    #[error("MAGIC LINK SERVICE ERROR :: Delivery Failed")]
+    DeliveryFailed,
+
+    #[error("MAGIC LINK SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for MagicLinkServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            RepositoryError::QueryFailed(QueryFailure::NotFound) => Self::InvalidToken,
+
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<SignedTokenError> for MagicLinkServiceError {
+    fn from(err: SignedTokenError) -> Self {
+        tracing::error!(error = ?err);
+
+        match err {
+            SignedTokenError::InvalidToken | SignedTokenError::Expired => Self::InvalidToken,
+            SignedTokenError::Encode => Self::InternalError,
+        }
+    }
+}
+
+impl From<MfaServiceError> for MagicLinkServiceError {
+    fn from(err: MfaServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<SessionTokenServiceError> for MagicLinkServiceError {
+    fn from(err: SessionTokenServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<MailerError> for MagicLinkServiceError {
+    fn from(err: MailerError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::DeliveryFailed
+    }
+}
@@ -0,0 +1,356 @@
+use std::sync::Arc;
+
+use ring::rand::{SecureRandom, SystemRandom};
+use scoped_futures::ScopedFutureExt;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        models::{SessionTokenModel, TotpCreateModel, TotpEnrollmentModel},
+        services::{SessionTokenService, SessionTokenServiceError},
+    },
+    db::{
+        digest_token,
+        repositories::{
+            QueryFailure, RecoveryCodeRepository, RepositoryError, SessionTokenRepository,
+            TotpRepository, UserRepository, WebauthnCredentialRepository,
+        },
+        DbContext, DbContextError,
+    },
+    utils::totp::{TotpError, TotpUtil},
+};
+
+/// the number of recovery codes issued when an enrollment is confirmed
+pub const RECOVERY_CODE_COUNT: usize = 10;
+
+pub struct MfaService;
+
+impl MfaService {
+    /// Starts a new TOTP enrollment for the user, replacing any that was never confirmed. The
+    /// enrollment is only required at login once confirmed with a code from the authenticator.
+    pub async fn enroll_totp(
+        db_context: &Arc<DbContext>,
+        user_repository: &dyn UserRepository,
+        totp_repository: &dyn TotpRepository,
+        user_id: &Uuid,
+    ) -> Result<TotpEnrollmentModel, MfaServiceError> {
+        tracing::trace!(method = "enroll_totp", ?user_id);
+
+        let user = user_repository
+            .get_by_id(db_context, user_id)
+            .await
+            .map_err(MfaServiceError::from)?;
+
+        let secret = TotpUtil::generate_secret().map_err(MfaServiceError::from)?;
+        let totp_create = TotpCreateModel::new(user_id, secret.as_str());
+
+        let totp = totp_repository
+            .create(db_context, &totp_create)
+            .await
+            .map_err(MfaServiceError::from)?;
+
+        tracing::info!("TOTP enrollment started for user with ID: {}", user_id);
+
+        Ok(TotpEnrollmentModel::new(
+            totp.secret.as_str(),
+            TotpUtil::provisioning_uri(totp.secret.as_str(), user.email.as_str()).as_str(),
+        ))
+    }
+
+    /// Confirms the user's TOTP enrollment with a code from their authenticator, returning the
+    /// recovery codes issued alongside it. Only the hashes of the codes are kept, so this is the
+    /// one time they can be shown to the user.
+    pub async fn confirm_totp(
+        db_context: &Arc<DbContext>,
+        totp_repository: &dyn TotpRepository,
+        recovery_code_repository: &dyn RecoveryCodeRepository,
+        user_id: &Uuid,
+        code: &str,
+    ) -> Result<Vec<String>, MfaServiceError> {
+        tracing::trace!(method = "confirm_totp", ?user_id);
+
+        let totp = totp_repository
+            .get_by_user_id(db_context, user_id)
+            .await
+            .map_err(MfaServiceError::from)?;
+
+        if totp.is_confirmed() {
+            tracing::error!(error = "TOTP enrollment is already confirmed");
+            return Err(MfaServiceError::AlreadyEnrolled);
+        }
+
+        let step = Self::verify_code(totp.secret.as_str(), code)?;
+
+        let recovery_codes = (0..RECOVERY_CODE_COUNT)
+            .map(|_| Self::generate_recovery_code())
+            .collect::<Result<Vec<String>, MfaServiceError>>()?;
+
+        let code_hashes = recovery_codes
+            .iter()
+            .map(|recovery_code| Self::digest_recovery_code(recovery_code))
+            .collect::<Vec<String>>();
+
+        db_context
+            .transaction::<_, MfaServiceError, _>(|db_context| {
+                async move {
+                    totp_repository
+                        .confirm_by_user_id(db_context, user_id, step)
+                        .await
+                        .map_err(MfaServiceError::from)?;
+
+                    recovery_code_repository
+                        .replace_by_user_id(db_context, user_id, &code_hashes)
+                        .await
+                        .map_err(MfaServiceError::from)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        tracing::info!("TOTP enrollment confirmed for user with ID: {}", user_id);
+
+        Ok(recovery_codes)
+    }
+
+    pub async fn disable_totp(
+        db_context: &Arc<DbContext>,
+        totp_repository: &dyn TotpRepository,
+        recovery_code_repository: &dyn RecoveryCodeRepository,
+        user_id: &Uuid,
+    ) -> Result<(), MfaServiceError> {
+        tracing::trace!(method = "disable_totp", ?user_id);
+
+        db_context
+            .transaction::<_, MfaServiceError, _>(|db_context| {
+                async move {
+                    totp_repository
+                        .delete_by_user_id(db_context, user_id)
+                        .await
+                        .map_err(MfaServiceError::from)?;
+
+                    recovery_code_repository
+                        .delete_by_user_id(db_context, user_id)
+                        .await
+                        .map_err(MfaServiceError::from)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        tracing::info!("TOTP disabled for user with ID: {}", user_id);
+
+        Ok(())
+    }
+
+    /// Whether the user has to present a second factor to log in, which is the case once they
+    /// have confirmed a TOTP enrollment or registered a passkey.
+    pub async fn is_required(
+        db_context: &Arc<DbContext>,
+        totp_repository: &dyn TotpRepository,
+        webauthn_credential_repository: &dyn WebauthnCredentialRepository,
+        user_id: &Uuid,
+    ) -> Result<bool, MfaServiceError> {
+        tracing::trace!(method = "is_required", ?user_id);
+
+        match totp_repository.get_by_user_id(db_context, user_id).await {
+            Ok(totp) if totp.is_confirmed() => return Ok(true),
+            Ok(_) | Err(RepositoryError::QueryFailed(QueryFailure::NotFound)) => (),
+            Err(err) => return Err(MfaServiceError::from(err)),
+        }
+
+        let credentials = webauthn_credential_repository
+            .get_all_by_user_id(db_context, user_id)
+            .await
+            .map_err(MfaServiceError::from)?;
+
+        Ok(!credentials.is_empty())
+    }
+
+    /// Exchanges a session token issued pending a second factor for a full session token, given
+    /// either a code from the user's authenticator or one of their unused recovery codes. The
+    /// pending token is spent whether or not the factor is valid, so a failed attempt has to
+    /// start over from the password.
+    pub async fn verify(
+        db_context: &Arc<DbContext>,
+        session_token_repository: &dyn SessionTokenRepository,
+        totp_repository: &dyn TotpRepository,
+        recovery_code_repository: &dyn RecoveryCodeRepository,
+        session_token: &str,
+        factor: &MfaFactor<'_>,
+    ) -> Result<SessionTokenModel, MfaServiceError> {
+        tracing::trace!(method = "verify");
+
+        let pending_token = SessionTokenService::validate_session_token(
+            db_context,
+            session_token_repository,
+            session_token,
+        )
+        .await
+        .map_err(MfaServiceError::from)?;
+
+        if !pending_token.mfa_pending {
+            tracing::error!(error = "Session token is not pending a second factor");
+            return Err(MfaServiceError::Token);
+        }
+
+        let user_id = &pending_token.user_id;
+
+        match factor {
+            MfaFactor::Totp(code) => {
+                let totp = totp_repository
+                    .get_by_user_id(db_context, user_id)
+                    .await
+                    .map_err(MfaServiceError::from)?;
+
+                let step = Self::verify_code(totp.secret.as_str(), code)?;
+
+                totp_repository
+                    .use_step_by_user_id(db_context, user_id, step)
+                    .await
+                    .map_err(MfaServiceError::from)?;
+            }
+            MfaFactor::RecoveryCode(recovery_code) => {
+                recovery_code_repository
+                    .use_by_user_id_and_hash(
+                        db_context,
+                        user_id,
+                        Self::digest_recovery_code(recovery_code).as_str(),
+                    )
+                    .await
+                    .map_err(MfaServiceError::from)?;
+
+                tracing::info!("Recovery code used by user with ID: {}", user_id);
+            }
+        }
+
+        let session_token = SessionTokenService::create_session_token(
+            db_context,
+            session_token_repository,
+            user_id,
+            false,
+        )
+        .await
+        .map_err(MfaServiceError::from)?;
+
+        tracing::info!(
+            "User successfully completed a second factor with ID: {}",
+            user_id
+        );
+
+        Ok(session_token)
+    }
+
+    fn verify_code(secret: &str, code: &str) -> Result<i64, MfaServiceError> {
+        TotpUtil::verify_code(secret, code, TotpUtil::current_step())
+            .map_err(MfaServiceError::from)?
+            .ok_or_else(|| {
+                tracing::error!(error = "Invalid TOTP code supplied");
+                MfaServiceError::InvalidCode
+            })
+    }
+
+    /// 64 random bits, formatted as `xxxx-xxxx-xxxx-xxxx` to be easier to copy down
+    fn generate_recovery_code() -> Result<String, MfaServiceError> {
+        let mut buffer = [0u8; 8];
+        SystemRandom::new().fill(&mut buffer).map_err(|_| {
+            let msg = "ring::SystemRandom::fill failed on generate_recovery_code";
+
+            tracing::error!(error = msg);
+            MfaServiceError::InternalError
+        })?;
+
+        let hex = buffer
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+
+        Ok(format!(
+            "{}-{}-{}-{}",
+            &hex[0..4],
+            &hex[4..8],
+            &hex[8..12],
+            &hex[12..16]
+        ))
+    }
+
+    /// the hash a recovery code is stored as, ignoring case and any separators the user typed
+    fn digest_recovery_code(recovery_code: &str) -> String {
+        let normalized = recovery_code
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+
+        digest_token(normalized.as_str())
+    }
+}
+
+/// The second factor presented to complete a login.
+pub enum MfaFactor<'a> {
+    Totp(&'a str),
+    RecoveryCode(&'a str),
+}
+
+#[derive(Debug, Error)]
+pub enum MfaServiceError {
+    #[error("MFA SERVICE ERROR :: Not Enrolled")]
+    NotEnrolled,
+    #[error("MFA SERVICE ERROR :: Already Enrolled")]
+    AlreadyEnrolled,
+    #[error("MFA SERVICE ERROR :: Invalid Code")]
+    InvalidCode,
+    #[error("MFA SERVICE ERROR :: Bad Token")]
+    Token,
+
+    #[error("MFA SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for MfaServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            RepositoryError::QueryFailed(query_err) => match query_err {
+                QueryFailure::NotFound | QueryFailure::NotDeleted => Self::NotEnrolled,
+                QueryFailure::AlreadyExists => Self::AlreadyEnrolled,
+                QueryFailure::NotUpdated => Self::InvalidCode,
+
+                QueryFailure::NotCreated => Self::InternalError,
+            },
+
+            RepositoryError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl From<SessionTokenServiceError> for MfaServiceError {
+    fn from(err: SessionTokenServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            SessionTokenServiceError::NotFound => Self::Token,
+            SessionTokenServiceError::NotDeleted => Self::Token,
+
+            SessionTokenServiceError::NotCreated => Self::InternalError,
+            SessionTokenServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl From<TotpError> for MfaServiceError {
+    fn from(err: TotpError) -> Self {
+        tracing::error!(error = ?err);
+
+        Self::InternalError
+    }
+}
+
+impl From<DbContextError> for MfaServiceError {
+    fn from(err: DbContextError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
@@ -1,5 +1,19 @@
+mod account_service;
+mod email_verification_service;
+mod federation_service;
+mod login_throttle_service;
+mod magic_link_service;
+mod mfa_service;
+mod password_policy_service;
+mod password_reset_service;
 mod session_service;
 mod session_token_service;
 mod user_auth_service;
+mod webauthn_service;
 
-pub use self::{session_service::*, session_token_service::*, user_auth_service::*};
+pub use self::{
+    account_service::*, email_verification_service::*, federation_service::*,
+    login_throttle_service::*, magic_link_service::*, mfa_service::*, password_policy_service::*,
+    password_reset_service::*, session_service::*, session_token_service::*, user_auth_service::*,
+    webauthn_service::*,
+};
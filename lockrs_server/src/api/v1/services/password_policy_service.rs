@@ -0,0 +1,85 @@
+use thiserror::Error;
+
+use crate::{api::v1::models::PasswordPolicyViolation, PasswordPolicy};
+
+/// the shortest part of an email address a password is checked for, so a one or two letter name
+/// doesn't rule out every password containing those letters
+const MIN_EMAIL_PART_LENGTH: usize = 3;
+
+pub struct PasswordPolicyService;
+
+impl PasswordPolicyService {
+    /// Checks a new password against every rule of the policy, failing with all of the rules it
+    /// breaks rather than just the first. `email` is the address of the account the password is
+    /// for.
+    pub fn check(
+        password_policy: &PasswordPolicy,
+        password: &str,
+        email: &str,
+    ) -> Result<(), PasswordPolicyServiceError> {
+        tracing::trace!(method = "check");
+
+        let mut violations = Vec::new();
+
+        let length = password.chars().count();
+        if length < password_policy.min_length {
+            violations.push(PasswordPolicyViolation::TooShort {
+                min_length: password_policy.min_length,
+            });
+        }
+        if length > password_policy.max_length {
+            violations.push(PasswordPolicyViolation::TooLong {
+                max_length: password_policy.max_length,
+            });
+        }
+
+        if password_policy.require_lowercase && !password.chars().any(char::is_lowercase) {
+            violations.push(PasswordPolicyViolation::MissingLowercase);
+        }
+        if password_policy.require_uppercase && !password.chars().any(char::is_uppercase) {
+            violations.push(PasswordPolicyViolation::MissingUppercase);
+        }
+        if password_policy.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            violations.push(PasswordPolicyViolation::MissingDigit);
+        }
+        if password_policy.require_symbol && password.chars().all(char::is_alphanumeric) {
+            violations.push(PasswordPolicyViolation::MissingSymbol);
+        }
+
+        if password_policy.forbid_email && Self::contains_email(password, email) {
+            violations.push(PasswordPolicyViolation::ContainsEmail);
+        }
+
+        if let Some(breached_passwords) = &password_policy.breached_passwords {
+            if breached_passwords.contains(password) {
+                violations.push(PasswordPolicyViolation::Breached);
+            }
+        }
+
+        if !violations.is_empty() {
+            tracing::error!(error = "Password violates the password policy", ?violations);
+            return Err(PasswordPolicyServiceError::Violated(violations));
+        }
+
+        Ok(())
+    }
+
+    fn contains_email(password: &str, email: &str) -> bool {
+        let password = password.to_lowercase();
+        let email = email.to_lowercase();
+        let local_part = email.split('@').next().unwrap_or_default();
+
+        let contains_email = [email.as_str(), local_part]
+            .into_iter()
+            .filter(|part| part.chars().count() >= MIN_EMAIL_PART_LENGTH)
+            .any(|part| password.contains(part));
+
+        contains_email
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PasswordPolicyServiceError {
+    #[error("PASSWORD POLICY SERVICE ERROR :: Policy Violated")]
+    Violated(Vec<PasswordPolicyViolation>),
+}
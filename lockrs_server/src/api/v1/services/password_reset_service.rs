@@ -0,0 +1,228 @@
+use std::sync::Arc;
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{Duration, Utc};
+use rand::Rng;
+use thiserror::Error;
+use url::Url;
+
+use crate::{
+    api::v1::{
+        mailers::{Mail, Mailer, MailerError},
+        models::{PasswordPolicyViolation, PasswordReset, PasswordResetTokenModel},
+        services::{
+            PasswordPolicyService, PasswordPolicyServiceError, UserAuthService,
+            UserAuthServiceError,
+        },
+    },
+    db::{
+        repositories::{
+            PasswordResetTokenRepository, QueryFailure, RefreshTokenRepository, RepositoryError,
+            SessionRepository, UserAuthRepository,
+        },
+        DbContext,
+    },
+    PasswordHashingConfig, PasswordPolicy,
+};
+
+/// how long the link in a password reset mail can be followed for
+pub const PASSWORD_RESET_TTL_MINUTES: i64 = 30;
+
+pub struct PasswordResetService;
+
+impl PasswordResetService {
+    /// Mails a single use password reset link to the owner of `email`. Nothing is sent for an
+    /// address without an account, and nothing is logged about it either. Only the time this takes
+    /// tells the two apart, so it should be run after answering whoever asked.
+    pub async fn request_reset(
+        db_context: &Arc<DbContext>,
+        user_auth_repository: &dyn UserAuthRepository,
+        password_reset_token_repository: &dyn PasswordResetTokenRepository,
+        mailer: &dyn Mailer,
+        frontend_url: &str,
+        email: &str,
+    ) -> Result<(), PasswordResetServiceError> {
+        tracing::trace!(method = "request_reset", email);
+
+        let user = match user_auth_repository.get_by_email(db_context, email).await {
+            Ok(user) => user,
+            Err(RepositoryError::QueryFailed(QueryFailure::NotFound)) => return Ok(()),
+            Err(err) => return Err(PasswordResetServiceError::from(err)),
+        };
+
+        let token = Self::generate_reset_token();
+        let expires_at =
+            (Utc::now() + Duration::minutes(PASSWORD_RESET_TTL_MINUTES)).timestamp_millis();
+
+        let reset_token = PasswordResetTokenModel::new(
+            &user.id,
+            user.email.as_str(),
+            user.password_version,
+            expires_at,
+        );
+
+        password_reset_token_repository
+            .create(db_context, token.as_str(), &reset_token)
+            .await
+            .map_err(PasswordResetServiceError::from)?;
+
+        let mut link = Url::parse(frontend_url)
+            .and_then(|url| url.join("password-reset/confirm"))
+            .map_err(|err| {
+                tracing::error!(error = %err);
+                PasswordResetServiceError::InternalError
+            })?;
+        link.query_pairs_mut().append_pair("token", token.as_str());
+
+        let mail = Mail::new(
+            user.email.as_str(),
+            "Reset your password",
+            format!(
+                "Follow the link below to choose a new password. It can be used once, and expires in {} minutes.\n\n{}\n\nIf you did not ask to reset your password, you can ignore this mail.",
+                PASSWORD_RESET_TTL_MINUTES, link
+            )
+            .as_str(),
+        );
+
+        mailer
+            .send(&mail)
+            .await
+            .map_err(PasswordResetServiceError::from)?;
+
+        tracing::info!("Password reset mail sent to user with ID: {}", user.id);
+
+        Ok(())
+    }
+
+    /// Sets a new password with a token from a reset mail, then logs the user out everywhere by
+    /// ending their sessions and revoking their refresh tokens. A password the policy rejects
+    /// leaves the token usable, so the user can try again with another one.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn reset_password(
+        db_context: &Arc<DbContext>,
+        user_auth_repository: &dyn UserAuthRepository,
+        password_reset_token_repository: &dyn PasswordResetTokenRepository,
+        session_repository: &dyn SessionRepository,
+        refresh_token_repository: &dyn RefreshTokenRepository,
+        password_policy: &PasswordPolicy,
+        password_hashing: &PasswordHashingConfig,
+        password_reset: &PasswordReset,
+    ) -> Result<(), PasswordResetServiceError> {
+        tracing::trace!(method = "reset_password");
+
+        let reset_token = password_reset_token_repository
+            .take_by_token(db_context, password_reset.token.as_str())
+            .await
+            .map_err(PasswordResetServiceError::from)?;
+
+        let user = user_auth_repository
+            .get_by_email(db_context, reset_token.email.as_str())
+            .await
+            .map_err(PasswordResetServiceError::from)?;
+
+        // the address moved to another account, or the password was changed since the token was
+        // issued
+        if user.id != reset_token.user_id || user.password_version != reset_token.password_version {
+            tracing::error!(error = "Password reset token is stale");
+            return Err(PasswordResetServiceError::InvalidToken);
+        }
+
+        if let Err(err) = PasswordPolicyService::check(
+            password_policy,
+            password_reset.password.as_str(),
+            reset_token.email.as_str(),
+        ) {
+            password_reset_token_repository
+                .create(db_context, password_reset.token.as_str(), &reset_token)
+                .await
+                .map_err(PasswordResetServiceError::from)?;
+
+            return Err(PasswordResetServiceError::from(err));
+        }
+
+        let password_hash =
+            UserAuthService::hash_password(password_reset.password.as_str(), password_hashing)
+                .map_err(PasswordResetServiceError::from)?;
+
+        user_auth_repository
+            .change_password_hash_by_id(db_context, &user.id, password_hash.as_str())
+            .await
+            .map_err(PasswordResetServiceError::from)?;
+
+        let revoked_tokens = refresh_token_repository
+            .revoke_all_by_user_id(db_context, &user.id)
+            .await
+            .map_err(PasswordResetServiceError::from)?;
+
+        session_repository
+            .delete_by_user_id(db_context, &user.id)
+            .await
+            .map_err(PasswordResetServiceError::from)?;
+
+        tracing::info!(
+            "Password reset: {{ user_id: {}, revoked_tokens: {} }}",
+            user.id,
+            revoked_tokens
+        );
+
+        Ok(())
+    }
+
+    fn generate_reset_token() -> String {
+        let mut rng = rand::thread_rng();
+        let bytes = (0..32).map(|_| rng.gen::<u8>()).collect::<Vec<u8>>();
+
+        general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PasswordResetServiceError {
+    #[error("PASSWORD RESET SERVICE ERROR :: Invalid Token")]
+    InvalidToken,
+    #[error("PASSWORD RESET SERVICE ERROR :: Delivery Failed")]
+    DeliveryFailed,
+    #[error("PASSWORD RESET SERVICE ERROR :: Password Policy Violated")]
+    PasswordPolicy(Vec<PasswordPolicyViolation>),
+
+    #[error("PASSWORD RESET SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for PasswordResetServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            RepositoryError::QueryFailed(QueryFailure::NotFound) => Self::InvalidToken,
+
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<UserAuthServiceError> for PasswordResetServiceError {
+    fn from(err: UserAuthServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<PasswordPolicyServiceError> for PasswordResetServiceError {
+    fn from(err: PasswordPolicyServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            PasswordPolicyServiceError::Violated(violations) => Self::PasswordPolicy(violations),
+        }
+    }
+}
+
+impl From<MailerError> for PasswordResetServiceError {
+    fn from(err: MailerError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::DeliveryFailed
+    }
+}
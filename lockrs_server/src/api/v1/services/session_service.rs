@@ -9,10 +9,16 @@ use uuid::Uuid;
 use crate::{
     api::v1::{
         models::{SessionCreateModel, SessionModel, SessionUpdateModel},
-        services::{SessionTokenService, SessionTokenServiceError},
+        services::{
+            EmailVerificationService, EmailVerificationServiceError, SessionTokenService,
+            SessionTokenServiceError,
+        },
     },
     db::{
-        repositories::{QueryFailure, RepositoryError, SessionRepository, SessionTokenRepository},
+        repositories::{
+            QueryFailure, RepositoryError, SessionRepository, SessionTokenRepository,
+            UserRepository,
+        },
         DbContext,
     },
 };
@@ -20,12 +26,16 @@ use crate::{
 pub struct SessionService;
 
 impl SessionService {
+    /// Exchanges a session token for a session. Every way of logging in ends here, so this is
+    /// also where a user who still has to verify their email address is turned away.
     pub async fn create_session(
         db_context: &Arc<DbContext>,
         session_repository: &dyn SessionRepository,
         session_token_repository: &dyn SessionTokenRepository,
+        user_repository: &dyn UserRepository,
         token: &SessionCreateModel,
         session_duration: &Duration,
+        require_verified_email: bool,
     ) -> Result<SessionModel, SessionServiceError> {
         tracing::trace!(
             method = "create_session",
@@ -40,6 +50,17 @@ impl SessionService {
         .await
         .map_err(SessionServiceError::from)?;
 
+        if token.mfa_pending {
+            tracing::error!(error = "Session token is still pending a second factor");
+            return Err(SessionServiceError::Token);
+        }
+
+        if require_verified_email {
+            EmailVerificationService::ensure_verified(db_context, user_repository, &token.user_id)
+                .await
+                .map_err(SessionServiceError::from)?;
+        }
+
         let user_id = token.user_id;
         let session_id = Self::generate_session_id();
         let expires_at = (Utc::now() + *session_duration).timestamp_millis();
@@ -144,6 +165,8 @@ pub enum SessionServiceError {
     NotDeleted,
     #[error("SESSION SERVICE ERROR :: Bad Token")]
     Token,
+    #[error("SESSION SERVICE ERROR :: Email Not Verified")]
+    EmailNotVerified,
 
     #[error("SESSION SERVICE ERROR :: Internal Error")]
     InternalError,
@@ -181,3 +204,16 @@ impl From<SessionTokenServiceError> for SessionServiceError {
         }
     }
 }
+
+impl From<EmailVerificationServiceError> for SessionServiceError {
+    fn from(err: EmailVerificationServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            EmailVerificationServiceError::NotVerified => Self::EmailNotVerified,
+            EmailVerificationServiceError::NotFound => Self::Token,
+
+            _ => Self::InternalError,
+        }
+    }
+}
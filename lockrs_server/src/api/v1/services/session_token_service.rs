@@ -21,14 +21,19 @@ impl SessionTokenService {
         db_context: &Arc<DbContext>,
         session_token_repository: &dyn SessionTokenRepository,
         user_id: &Uuid,
+        mfa_pending: bool,
     ) -> Result<SessionTokenModel, SessionTokenServiceError> {
-        tracing::trace!(method = "create_session_token", ?user_id);
+        tracing::trace!(method = "create_session_token", ?user_id, mfa_pending);
 
         let ttl = Duration::minutes(5);
         let expires_at = (Utc::now() + ttl).timestamp_millis();
 
-        let token_data =
-            SessionTokenModel::new(Self::generate_session_token().as_str(), user_id, expires_at);
+        let token_data = SessionTokenModel::new(
+            Self::generate_session_token().as_str(),
+            user_id,
+            expires_at,
+            mfa_pending,
+        );
 
         let token = session_token_repository
             .create(db_context, &token_data)
@@ -1,19 +1,33 @@
 use std::sync::Arc;
 
-use bcrypt::{hash, verify, BcryptError, DEFAULT_COST};
+use argon2::{
+    password_hash::{self, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Algorithm, Argon2, Params, Version,
+};
+use bcrypt::BcryptError;
+use rand::Rng;
 use thiserror::Error;
+use uuid::Uuid;
 
 use crate::{
     api::v1::{
         mappers::UserAuthMapper,
+        models::PasswordPolicyViolation,
         models::{SessionTokenModel, UserLoginCredentials, UserRegisterModel, UserRegistration},
-        services::SessionTokenServiceError,
+        services::{
+            MfaService, MfaServiceError, PasswordPolicyService, PasswordPolicyServiceError,
+            SessionTokenServiceError,
+        },
     },
     db::{
-        repositories::{QueryFailure, RepositoryError, SessionTokenRepository, UserAuthRepository},
+        repositories::{
+            QueryFailure, RepositoryError, SessionTokenRepository, TotpRepository,
+            UserAuthRepository, WebauthnCredentialRepository,
+        },
         DbContext,
     },
     models::UserModel,
+    PasswordHashingConfig, PasswordPolicy,
 };
 
 use super::SessionTokenService;
@@ -24,11 +38,20 @@ impl UserAuthService {
     pub async fn register_user(
         db_context: &Arc<DbContext>,
         user_auth_repository: &dyn UserAuthRepository,
+        password_policy: &PasswordPolicy,
+        password_hashing: &PasswordHashingConfig,
         register_user: &UserRegistration,
     ) -> Result<UserModel, UserAuthServiceError> {
         tracing::trace!(method = "register_user",);
 
-        let password_hash = Self::hash_password(register_user.password.as_str())?;
+        PasswordPolicyService::check(
+            password_policy,
+            register_user.password.as_str(),
+            register_user.email.as_str(),
+        )
+        .map_err(UserAuthServiceError::from)?;
+
+        let password_hash = Self::hash_password(register_user.password.as_str(), password_hashing)?;
 
         let create_user =
             UserRegisterModel::new(register_user.email.as_str(), password_hash.as_str());
@@ -47,43 +70,127 @@ impl UserAuthService {
         Ok(UserAuthMapper::into_user(user))
     }
 
+    /// Verifies the user's password, returning a session token. For a user enrolled in TOTP or
+    /// with a passkey the token is only pending, and has to be exchanged through
+    /// `MfaService::verify` or `WebauthnService::finish_authentication` with a second factor
+    /// before it can start a session. With `require_verified_email`, a user who has not verified
+    /// their email address is turned away once their password checks out. A password hash not
+    /// made with the current `password_hashing` parameters is replaced along the way.
+    #[allow(clippy::too_many_arguments)]
     pub async fn login(
         db_context: &Arc<DbContext>,
         user_auth_repository: &dyn UserAuthRepository,
         session_token_repository: &dyn SessionTokenRepository,
+        totp_repository: &dyn TotpRepository,
+        webauthn_credential_repository: &dyn WebauthnCredentialRepository,
+        password_hashing: &PasswordHashingConfig,
         user_auth: &UserLoginCredentials,
+        require_verified_email: bool,
     ) -> Result<SessionTokenModel, UserAuthServiceError> {
         tracing::trace!(method = "login",);
 
-        let user = user_auth_repository
+        let user = match user_auth_repository
             .get_by_email(db_context, user_auth.email.as_str())
             .await
-            .map_err(UserAuthServiceError::from)?;
+        {
+            Ok(user) => user,
+            Err(err @ RepositoryError::QueryFailed(QueryFailure::NotFound)) => {
+                // spend about as long as checking a password would, so an unknown email can't be
+                // told apart from a wrong password by how long the answer takes
+                let _ = Self::hash_password(user_auth.password.as_str(), password_hashing);
+
+                return Err(UserAuthServiceError::from(err));
+            }
+            Err(err) => return Err(UserAuthServiceError::from(err)),
+        };
 
         Self::verify_password(user_auth.password.as_str(), user.password_hash.as_str())?;
 
+        if require_verified_email && !user.email_verified {
+            tracing::error!(error = "Email address is not verified");
+            return Err(UserAuthServiceError::EmailNotVerified);
+        }
+
+        if Self::needs_rehash(user.password_hash.as_str(), password_hashing) {
+            // the login goes ahead on the old hash if the new one can't be stored
+            if let Err(err) = Self::rehash_password(
+                db_context,
+                user_auth_repository,
+                password_hashing,
+                &user.id,
+                user_auth.password.as_str(),
+            )
+            .await
+            {
+                tracing::error!(error = %err);
+            }
+        }
+
+        let mfa_pending = MfaService::is_required(
+            db_context,
+            totp_repository,
+            webauthn_credential_repository,
+            &user.id,
+        )
+        .await
+        .map_err(UserAuthServiceError::from)?;
+
         let session_token = SessionTokenService::create_session_token(
             db_context,
             session_token_repository,
             &user.id,
+            mfa_pending,
         )
         .await
         .map_err(UserAuthServiceError::from)?;
 
         tracing::info!(
-            "User successfully authenticated with ID: {}",
-            session_token.user_id.to_string()
+            "User successfully authenticated with ID: {}, second factor pending: {}",
+            session_token.user_id.to_string(),
+            mfa_pending,
         );
 
         Ok(session_token)
     }
 
-    pub fn hash_password(password: &str) -> Result<String, UserAuthServiceError> {
-        hash(password, DEFAULT_COST).map_err(UserAuthServiceError::from)
+    /// Hashes the password with Argon2id, as a PHC string carrying its own salt and parameters.
+    pub fn hash_password(
+        password: &str,
+        password_hashing: &PasswordHashingConfig,
+    ) -> Result<String, UserAuthServiceError> {
+        let params = Params::new(
+            password_hashing.memory_kib,
+            password_hashing.iterations,
+            password_hashing.parallelism,
+            None,
+        )
+        .map_err(UserAuthServiceError::from)?;
+
+        let salt = rand::thread_rng().gen::<[u8; 16]>();
+        let salt = SaltString::encode_b64(&salt).map_err(UserAuthServiceError::from)?;
+
+        let password_hash = Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(UserAuthServiceError::from)?;
+
+        Ok(password_hash.to_string())
     }
 
-    fn verify_password(password: &str, hash: &str) -> Result<(), UserAuthServiceError> {
-        let valid_password = verify(password, hash).map_err(UserAuthServiceError::from)?;
+    /// Checks the password against a hash made by any version of lockrs: a PHC string, checked
+    /// with the algorithm and parameters it names, or a legacy bcrypt hash.
+    pub fn verify_password(password: &str, hash: &str) -> Result<(), UserAuthServiceError> {
+        let valid_password = match Self::is_bcrypt(hash) {
+            true => bcrypt::verify(password, hash).map_err(UserAuthServiceError::from)?,
+            false => {
+                let password_hash = PasswordHash::new(hash).map_err(UserAuthServiceError::from)?;
+
+                match Argon2::default().verify_password(password.as_bytes(), &password_hash) {
+                    Ok(()) => true,
+                    Err(password_hash::Error::Password) => false,
+                    Err(err) => return Err(UserAuthServiceError::from(err)),
+                }
+            }
+        };
 
         if !valid_password {
             let msg = "Invalid password supplied";
@@ -93,6 +200,53 @@ impl UserAuthService {
 
         Ok(())
     }
+
+    /// Whether the hash was made with anything but Argon2id and the configured parameters.
+    pub fn needs_rehash(hash: &str, password_hashing: &PasswordHashingConfig) -> bool {
+        if Self::is_bcrypt(hash) {
+            return true;
+        }
+
+        let Ok(password_hash) = PasswordHash::new(hash)
+        else {
+            return true;
+        };
+
+        let Ok(params) = Params::try_from(&password_hash)
+        else {
+            return true;
+        };
+
+        password_hash.algorithm != Algorithm::Argon2id.ident()
+            || password_hash.version != Some(Version::V0x13.into())
+            || params.m_cost() != password_hashing.memory_kib
+            || params.t_cost() != password_hashing.iterations
+            || params.p_cost() != password_hashing.parallelism
+    }
+
+    async fn rehash_password(
+        db_context: &Arc<DbContext>,
+        user_auth_repository: &dyn UserAuthRepository,
+        password_hashing: &PasswordHashingConfig,
+        user_id: &Uuid,
+        password: &str,
+    ) -> Result<(), UserAuthServiceError> {
+        let password_hash = Self::hash_password(password, password_hashing)?;
+
+        user_auth_repository
+            .update_password_hash_by_id(db_context, user_id, password_hash.as_str())
+            .await
+            .map_err(UserAuthServiceError::from)?;
+
+        tracing::info!("Password hash upgraded for user with ID: {}", user_id);
+
+        Ok(())
+    }
+
+    /// bcrypt hashes predate PHC strings, and start with `$2a$`, `$2b$` or `$2y$` instead
+    fn is_bcrypt(hash: &str) -> bool {
+        hash.starts_with("$2")
+    }
 }
 
 #[derive(Debug, Error)]
@@ -101,10 +255,14 @@ pub enum UserAuthServiceError {
     Token,
     #[error("AUTH SERVICE ERROR :: Invalid credentials")]
     Credentials,
+    #[error("AUTH SERVICE ERROR :: Email not verified")]
+    EmailNotVerified,
     #[error("AUTH SERVICE ERROR :: User already exists")]
     AlreadyExists,
     #[error("AUTH SERVICE ERROR :: User not created")]
     NotCreated,
+    #[error("AUTH SERVICE ERROR :: Password policy violated")]
+    PasswordPolicy(Vec<PasswordPolicyViolation>),
 
     #[error("AUTH SERVICE ERROR :: Internal error")]
     InternalError,
@@ -143,6 +301,24 @@ impl From<SessionTokenServiceError> for UserAuthServiceError {
     }
 }
 
+impl From<PasswordPolicyServiceError> for UserAuthServiceError {
+    fn from(err: PasswordPolicyServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            PasswordPolicyServiceError::Violated(violations) => Self::PasswordPolicy(violations),
+        }
+    }
+}
+
+impl From<MfaServiceError> for UserAuthServiceError {
+    fn from(err: MfaServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
 impl From<BcryptError> for UserAuthServiceError {
     fn from(err: BcryptError) -> Self {
         tracing::error!(error = ?err);
@@ -150,3 +326,19 @@ impl From<BcryptError> for UserAuthServiceError {
         Self::InternalError
     }
 }
+
+impl From<password_hash::Error> for UserAuthServiceError {
+    fn from(err: password_hash::Error) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<argon2::Error> for UserAuthServiceError {
+    fn from(err: argon2::Error) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
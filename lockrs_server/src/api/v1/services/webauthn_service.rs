@@ -0,0 +1,500 @@
+use std::sync::Arc;
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{offset::Utc, Duration};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        models::{
+            SessionTokenModel, WebauthnAssertionModel, WebauthnChallengeModel,
+            WebauthnCredentialCreateModel, WebauthnCredentialModel, WebauthnOptionsModel,
+            WebauthnRegistrationModel, WEBAUTHN_CEREMONY_CREATE, WEBAUTHN_CEREMONY_GET,
+        },
+        services::{SessionTokenService, SessionTokenServiceError},
+    },
+    db::repositories::{
+        QueryFailure, RepositoryError, SessionTokenRepository, UserRepository,
+        WebauthnChallengeRepository, WebauthnCredentialRepository,
+    },
+    db::DbContext,
+    utils::webauthn::{WebauthnError, WebauthnUtil},
+};
+
+/// how long the browser has to answer a challenge
+pub const WEBAUTHN_CHALLENGE_TTL_MINUTES: i64 = 5;
+
+pub struct WebauthnService;
+
+impl WebauthnService {
+    /// Starts registering a new passkey for the user, returning the challenge to sign alongside
+    /// the credentials they already have, so the authenticator does not register a second one.
+    pub async fn start_registration(
+        db_context: &Arc<DbContext>,
+        user_repository: &dyn UserRepository,
+        webauthn_credential_repository: &dyn WebauthnCredentialRepository,
+        webauthn_challenge_repository: &dyn WebauthnChallengeRepository,
+        user_id: &Uuid,
+    ) -> Result<WebauthnOptionsModel, WebauthnServiceError> {
+        tracing::trace!(method = "start_registration", ?user_id);
+
+        let user = user_repository
+            .get_by_id(db_context, user_id)
+            .await
+            .map_err(WebauthnServiceError::from)?;
+
+        let credential_ids = webauthn_credential_repository
+            .get_all_by_user_id(db_context, user_id)
+            .await
+            .map_err(WebauthnServiceError::from)?
+            .into_iter()
+            .map(|credential| credential.credential_id)
+            .collect::<Vec<String>>();
+
+        let challenge = Self::create_challenge(
+            db_context,
+            webauthn_challenge_repository,
+            Some(user_id),
+            WEBAUTHN_CEREMONY_CREATE,
+        )
+        .await?;
+
+        Ok(WebauthnOptionsModel::new(
+            challenge,
+            Some((user.id, user.email)),
+            credential_ids,
+        ))
+    }
+
+    /// Verifies the authenticator's response to a registration challenge, and stores the new
+    /// credential for the user.
+    pub async fn finish_registration(
+        db_context: &Arc<DbContext>,
+        webauthn_credential_repository: &dyn WebauthnCredentialRepository,
+        webauthn_challenge_repository: &dyn WebauthnChallengeRepository,
+        rp_id: &str,
+        origin: &str,
+        user_id: &Uuid,
+        registration: &WebauthnRegistrationModel,
+    ) -> Result<WebauthnCredentialModel, WebauthnServiceError> {
+        tracing::trace!(method = "finish_registration", ?user_id);
+
+        let challenge = Self::take_challenge(
+            db_context,
+            webauthn_challenge_repository,
+            registration.challenge_id.as_str(),
+            WEBAUTHN_CEREMONY_CREATE,
+        )
+        .await?;
+
+        if challenge.user_id.as_ref() != Some(user_id) {
+            tracing::error!(error = "Registration challenge was issued to a different user");
+            return Err(WebauthnServiceError::InvalidChallenge);
+        }
+
+        WebauthnUtil::verify_client_data(
+            registration.client_data_json.as_slice(),
+            WEBAUTHN_CEREMONY_CREATE,
+            challenge.challenge.as_str(),
+            origin,
+        )
+        .map_err(WebauthnServiceError::from)?;
+
+        let authenticator_data =
+            WebauthnUtil::parse_attestation_object(registration.attestation_object.as_slice())
+                .map_err(WebauthnServiceError::from)?;
+
+        WebauthnUtil::verify_rp_id_hash(&authenticator_data, rp_id)
+            .map_err(WebauthnServiceError::from)?;
+
+        if !authenticator_data.is_user_present() {
+            tracing::error!(error = "Authenticator did not report the user as present");
+            return Err(WebauthnServiceError::InvalidResponse);
+        }
+
+        let Some(attested_credential) = authenticator_data.attested_credential
+        else {
+            tracing::error!(error = "Authenticator data is missing the attested credential");
+            return Err(WebauthnServiceError::InvalidResponse);
+        };
+
+        WebauthnUtil::public_key_algorithm(attested_credential.public_key.as_slice())
+            .map_err(WebauthnServiceError::from)?;
+
+        let credential_create = WebauthnCredentialCreateModel::new(
+            user_id,
+            general_purpose::URL_SAFE_NO_PAD
+                .encode(attested_credential.credential_id)
+                .as_str(),
+            attested_credential.public_key.as_slice(),
+            authenticator_data.sign_count as i64,
+        );
+
+        let credential = webauthn_credential_repository
+            .create(db_context, &credential_create)
+            .await
+            .map_err(WebauthnServiceError::from)?;
+
+        tracing::info!("Passkey registered for user with ID: {}", user_id);
+
+        Ok(credential)
+    }
+
+    /// Starts a passkey login. Given a session token pending a second factor, the passkey has to
+    /// be one of that user's, otherwise any passkey the authenticator discovers for the relying
+    /// party may answer.
+    pub async fn start_authentication(
+        db_context: &Arc<DbContext>,
+        session_token_repository: &dyn SessionTokenRepository,
+        webauthn_credential_repository: &dyn WebauthnCredentialRepository,
+        webauthn_challenge_repository: &dyn WebauthnChallengeRepository,
+        pending_token: Option<&str>,
+    ) -> Result<WebauthnOptionsModel, WebauthnServiceError> {
+        tracing::trace!(method = "start_authentication");
+
+        let user_id = match pending_token {
+            Some(pending_token) => {
+                let pending_token = session_token_repository
+                    .get_by_token(db_context, pending_token)
+                    .await
+                    .map_err(|err| {
+                        tracing::error!(error = %err);
+                        WebauthnServiceError::Token
+                    })?;
+
+                if !pending_token.mfa_pending {
+                    tracing::error!(error = "Session token is not pending a second factor");
+                    return Err(WebauthnServiceError::Token);
+                }
+
+                Some(pending_token.user_id)
+            }
+            None => None,
+        };
+
+        let credential_ids = match user_id.as_ref() {
+            Some(user_id) => webauthn_credential_repository
+                .get_all_by_user_id(db_context, user_id)
+                .await
+                .map_err(WebauthnServiceError::from)?
+                .into_iter()
+                .map(|credential| credential.credential_id)
+                .collect::<Vec<String>>(),
+            None => Vec::new(),
+        };
+
+        let challenge = Self::create_challenge(
+            db_context,
+            webauthn_challenge_repository,
+            user_id.as_ref(),
+            WEBAUTHN_CEREMONY_GET,
+        )
+        .await?;
+
+        Ok(WebauthnOptionsModel::new(challenge, None, credential_ids))
+    }
+
+    /// Verifies the authenticator's response to a login challenge, returning a full session
+    /// token. As a second factor the pending token is spent whether or not the response is
+    /// valid, and as the only factor the authenticator has to have verified the user.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn finish_authentication(
+        db_context: &Arc<DbContext>,
+        session_token_repository: &dyn SessionTokenRepository,
+        webauthn_credential_repository: &dyn WebauthnCredentialRepository,
+        webauthn_challenge_repository: &dyn WebauthnChallengeRepository,
+        rp_id: &str,
+        origin: &str,
+        pending_token: Option<&str>,
+        assertion: &WebauthnAssertionModel,
+    ) -> Result<SessionTokenModel, WebauthnServiceError> {
+        tracing::trace!(method = "finish_authentication");
+
+        let pending_user_id = match pending_token {
+            Some(pending_token) => {
+                let pending_token = SessionTokenService::validate_session_token(
+                    db_context,
+                    session_token_repository,
+                    pending_token,
+                )
+                .await
+                .map_err(WebauthnServiceError::from)?;
+
+                if !pending_token.mfa_pending {
+                    tracing::error!(error = "Session token is not pending a second factor");
+                    return Err(WebauthnServiceError::Token);
+                }
+
+                Some(pending_token.user_id)
+            }
+            None => None,
+        };
+
+        let challenge = Self::take_challenge(
+            db_context,
+            webauthn_challenge_repository,
+            assertion.challenge_id.as_str(),
+            WEBAUTHN_CEREMONY_GET,
+        )
+        .await?;
+
+        if challenge.user_id != pending_user_id {
+            tracing::error!(error = "Login challenge was issued for a different login");
+            return Err(WebauthnServiceError::InvalidChallenge);
+        }
+
+        let credential = webauthn_credential_repository
+            .get_by_credential_id(db_context, assertion.credential_id.as_str())
+            .await
+            .map_err(|err| match err {
+                RepositoryError::QueryFailed(QueryFailure::NotFound) => {
+                    tracing::error!(error = "Unknown passkey presented");
+                    WebauthnServiceError::InvalidResponse
+                }
+                err => WebauthnServiceError::from(err),
+            })?;
+
+        if pending_user_id.is_some_and(|user_id| user_id != credential.user_id) {
+            tracing::error!(error = "Passkey belongs to a different user");
+            return Err(WebauthnServiceError::InvalidResponse);
+        }
+
+        WebauthnUtil::verify_client_data(
+            assertion.client_data_json.as_slice(),
+            WEBAUTHN_CEREMONY_GET,
+            challenge.challenge.as_str(),
+            origin,
+        )
+        .map_err(WebauthnServiceError::from)?;
+
+        let authenticator_data =
+            WebauthnUtil::parse_authenticator_data(assertion.authenticator_data.as_slice())
+                .map_err(WebauthnServiceError::from)?;
+
+        WebauthnUtil::verify_rp_id_hash(&authenticator_data, rp_id)
+            .map_err(WebauthnServiceError::from)?;
+
+        if !authenticator_data.is_user_present() {
+            tracing::error!(error = "Authenticator did not report the user as present");
+            return Err(WebauthnServiceError::InvalidResponse);
+        }
+
+        if pending_user_id.is_none() && !authenticator_data.is_user_verified() {
+            tracing::error!(
+                error = "Authenticator did not verify the user for a passwordless login"
+            );
+            return Err(WebauthnServiceError::InvalidResponse);
+        }
+
+        WebauthnUtil::verify_signature(
+            credential.public_key.as_slice(),
+            assertion.authenticator_data.as_slice(),
+            assertion.client_data_json.as_slice(),
+            assertion.signature.as_slice(),
+        )
+        .map_err(WebauthnServiceError::from)?;
+
+        Self::use_credential(
+            db_context,
+            webauthn_credential_repository,
+            &credential,
+            authenticator_data.sign_count as i64,
+        )
+        .await?;
+
+        let session_token = SessionTokenService::create_session_token(
+            db_context,
+            session_token_repository,
+            &credential.user_id,
+            false,
+        )
+        .await
+        .map_err(WebauthnServiceError::from)?;
+
+        tracing::info!(
+            "User successfully authenticated with a passkey with ID: {}",
+            credential.user_id
+        );
+
+        Ok(session_token)
+    }
+
+    pub async fn get_credentials(
+        db_context: &Arc<DbContext>,
+        webauthn_credential_repository: &dyn WebauthnCredentialRepository,
+        user_id: &Uuid,
+    ) -> Result<Vec<WebauthnCredentialModel>, WebauthnServiceError> {
+        tracing::trace!(method = "get_credentials", ?user_id);
+
+        webauthn_credential_repository
+            .get_all_by_user_id(db_context, user_id)
+            .await
+            .map_err(WebauthnServiceError::from)
+    }
+
+    pub async fn delete_credential(
+        db_context: &Arc<DbContext>,
+        webauthn_credential_repository: &dyn WebauthnCredentialRepository,
+        user_id: &Uuid,
+        credential_id: &str,
+    ) -> Result<(), WebauthnServiceError> {
+        tracing::trace!(method = "delete_credential", ?user_id, credential_id);
+
+        webauthn_credential_repository
+            .delete_by_user_id_and_credential_id(db_context, user_id, credential_id)
+            .await
+            .map_err(WebauthnServiceError::from)?;
+
+        tracing::info!("Passkey deleted for user with ID: {}", user_id);
+
+        Ok(())
+    }
+
+    async fn create_challenge(
+        db_context: &Arc<DbContext>,
+        webauthn_challenge_repository: &dyn WebauthnChallengeRepository,
+        user_id: Option<&Uuid>,
+        ceremony: &str,
+    ) -> Result<WebauthnChallengeModel, WebauthnServiceError> {
+        let challenge = WebauthnUtil::generate_challenge().map_err(WebauthnServiceError::from)?;
+        let expires_at =
+            (Utc::now() + Duration::minutes(WEBAUTHN_CHALLENGE_TTL_MINUTES)).timestamp_millis();
+
+        let challenge = WebauthnChallengeModel::new(
+            Uuid::new_v4().to_string().as_str(),
+            challenge.as_str(),
+            user_id,
+            ceremony,
+            expires_at,
+        );
+
+        webauthn_challenge_repository
+            .create(db_context, &challenge)
+            .await
+            .map_err(WebauthnServiceError::from)
+    }
+
+    /// spends the challenge, so it can not be answered twice even if this answer is rejected
+    async fn take_challenge(
+        db_context: &Arc<DbContext>,
+        webauthn_challenge_repository: &dyn WebauthnChallengeRepository,
+        challenge_id: &str,
+        ceremony: &str,
+    ) -> Result<WebauthnChallengeModel, WebauthnServiceError> {
+        let challenge = webauthn_challenge_repository
+            .take_by_id(db_context, challenge_id)
+            .await
+            .map_err(|err| match err {
+                RepositoryError::QueryFailed(QueryFailure::NotFound) => {
+                    tracing::error!(error = "Unknown or expired webauthn challenge");
+                    WebauthnServiceError::InvalidChallenge
+                }
+                err => WebauthnServiceError::from(err),
+            })?;
+
+        if challenge.ceremony != ceremony {
+            tracing::error!(error = "Webauthn challenge was issued for a different ceremony");
+            return Err(WebauthnServiceError::InvalidChallenge);
+        }
+
+        Ok(challenge)
+    }
+
+    /// Records the signature counter the authenticator reported. Authenticators that count
+    /// report a higher value on every use, so one that does not move forward means another copy
+    /// of the credential has been used since, and the login is refused. Authenticators that
+    /// never count always report 0, and are let through.
+    async fn use_credential(
+        db_context: &Arc<DbContext>,
+        webauthn_credential_repository: &dyn WebauthnCredentialRepository,
+        credential: &WebauthnCredentialModel,
+        sign_count: i64,
+    ) -> Result<(), WebauthnServiceError> {
+        if (sign_count != 0 || credential.sign_count != 0) && sign_count <= credential.sign_count {
+            tracing::error!(
+                error = "Passkey signature counter did not increase, the authenticator may have been cloned",
+                credential_id = credential.credential_id,
+                user_id = credential.user_id.to_string(),
+                stored_sign_count = credential.sign_count,
+                sign_count,
+            );
+
+            return Err(WebauthnServiceError::ClonedAuthenticator);
+        }
+
+        webauthn_credential_repository
+            .use_by_id(db_context, credential.id, credential.sign_count, sign_count)
+            .await
+            .map_err(|err| match err {
+                // another login moved the counter on since it was read
+                RepositoryError::QueryFailed(QueryFailure::NotUpdated) => {
+                    WebauthnServiceError::ClonedAuthenticator
+                }
+                err => WebauthnServiceError::from(err),
+            })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WebauthnServiceError {
+    #[error("WEBAUTHN SERVICE ERROR :: Not Found")]
+    NotFound,
+    #[error("WEBAUTHN SERVICE ERROR :: Already Exists")]
+    AlreadyExists,
+    #[error("WEBAUTHN SERVICE ERROR :: Invalid Challenge")]
+    InvalidChallenge,
+    #[error("WEBAUTHN SERVICE ERROR :: Invalid Response")]
+    InvalidResponse,
+    #[error("WEBAUTHN SERVICE ERROR :: Cloned Authenticator")]
+    ClonedAuthenticator,
+    #[error("WEBAUTHN SERVICE ERROR :: Bad Token")]
+    Token,
+
+    #[error("WEBAUTHN SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for WebauthnServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            RepositoryError::QueryFailed(query_err) => match query_err {
+                QueryFailure::NotFound | QueryFailure::NotDeleted => Self::NotFound,
+                QueryFailure::AlreadyExists => Self::AlreadyExists,
+                QueryFailure::NotUpdated => Self::InvalidResponse,
+
+                QueryFailure::NotCreated => Self::InternalError,
+            },
+
+            RepositoryError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl From<SessionTokenServiceError> for WebauthnServiceError {
+    fn from(err: SessionTokenServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            SessionTokenServiceError::NotFound => Self::Token,
+            SessionTokenServiceError::NotDeleted => Self::Token,
+
+            SessionTokenServiceError::NotCreated => Self::InternalError,
+            SessionTokenServiceError::InternalError => Self::InternalError,
+        }
+    }
+}
+
+impl From<WebauthnError> for WebauthnServiceError {
+    fn from(err: WebauthnError) -> Self {
+        tracing::error!(error = ?err);
+
+        match err {
+            WebauthnError::Random => Self::InternalError,
+
+            _ => Self::InvalidResponse,
+        }
+    }
+}
@@ -1,14 +1,227 @@
-use std::env;
+use std::{env, sync::Arc};
 
 use chrono::Duration;
 use dotenvy::dotenv;
+use rand::RngCore;
+
+use crate::utils::breached_passwords::BreachedPasswords;
+
+/// Where everything but sessions is stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageBackend {
+    Postgres,
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+    /// kept in process memory along with sessions, whatever the session store, for tests. Never
+    /// read from the environment, as everything is lost on restart
+    InMemory,
+}
+
+/// Where sessions, session tokens and the challenges of passkey ceremonies are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionStore {
+    Redis,
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+}
+
+/// How mail, e.g. the links verifying an email address, is delivered.
+#[derive(Clone)]
+pub enum MailTransport {
+    Smtp(SmtpConfig),
+    /// dropped into the directory as .eml files, for local development
+    File(String),
+    /// kept in memory, for tests
+    InMemory,
+}
+
+#[derive(Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// whether to upgrade a plain text connection with STARTTLS, rather than connecting over TLS
+    /// from the start
+    pub starttls: bool,
+}
+
+/// What an account is kept from doing until the user has verified its email address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmailVerificationPolicy {
+    Optional,
+    /// granting consent to oauth2 clients, and being authorized for them
+    Consent,
+    /// logging in at all, which rules out consent as well
+    Login,
+}
+
+impl EmailVerificationPolicy {
+    pub fn is_required_for_login(&self) -> bool {
+        *self == Self::Login
+    }
+
+    pub fn is_required_for_consent(&self) -> bool {
+        *self != Self::Optional
+    }
+}
+
+/// The Argon2id parameters new password hashes are made with. Hashes made with anything else,
+/// bcrypt included, are replaced the next time their user logs in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PasswordHashingConfig {
+    /// memory cost in KiB
+    pub memory_kib: u32,
+    /// time cost, as the number of passes over the memory
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for PasswordHashingConfig {
+    /// the first of the configurations OWASP recommends for Argon2id
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+/// How failed logins are throttled. Once an account, or an address logins come from, reaches its
+/// number of failures, it is locked out for `lockout`, doubling with every failure after up to
+/// `max_lockout`. Failures are forgotten `failure_window` after the last one, or after the
+/// lockout they led to has run out.
+#[derive(Clone, Copy, Debug)]
+pub struct LoginThrottleConfig {
+    pub max_failures_per_account: i64,
+    pub max_failures_per_address: i64,
+    pub lockout: Duration,
+    pub max_lockout: Duration,
+    pub failure_window: Duration,
+}
+
+impl Default for LoginThrottleConfig {
+    fn default() -> Self {
+        Self {
+            max_failures_per_account: 5,
+            max_failures_per_address: 50,
+            lockout: Duration::seconds(30),
+            max_lockout: Duration::hours(1),
+            failure_window: Duration::minutes(15),
+        }
+    }
+}
+
+/// What a new password has to satisfy, whether it is set on registration, through a reset or by
+/// a change.
+#[derive(Clone)]
+pub struct PasswordPolicy {
+    /// in characters
+    pub min_length: usize,
+    /// in characters
+    pub max_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    /// anything but a letter or a digit
+    pub require_symbol: bool,
+    /// whether the password may contain the user's email address, or the part of it before the @
+    pub forbid_email: bool,
+    /// passwords known from breaches, which are turned down whatever else they satisfy
+    pub breached_passwords: Option<Arc<BreachedPasswords>>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            max_length: 128,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            require_symbol: false,
+            forbid_email: true,
+            breached_passwords: None,
+        }
+    }
+}
+
+/// How the user an upstream identity provider signed in is found out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FederationProtocol {
+    /// from the id token the provider issues alongside the access token, checked against its
+    /// published keys
+    Oidc,
+    /// by calling the provider's userinfo endpoint with the access token, for providers that do
+    /// not issue id tokens
+    OAuth2,
+}
+
+/// An upstream identity provider users can sign in with, e.g. Google, GitHub or another lockrs.
+#[derive(Clone, Debug)]
+pub struct FederationProvider {
+    /// the name the provider is referred to by in urls, and linked identities are kept under
+    pub name: String,
+    pub protocol: FederationProtocol,
+    pub client_id: String,
+    pub client_secret: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    /// the `iss` id tokens have to carry, for oidc providers
+    pub issuer: Option<String>,
+    /// where the keys id tokens are signed with are published, for oidc providers
+    pub jwks_uri: Option<String>,
+    /// where the user is read from, for oauth2 providers
+    pub userinfo_endpoint: Option<String>,
+    pub scopes: Vec<String>,
+    /// the claim identifying the user at the provider, e.g. `id` for GitHub
+    pub subject_claim: String,
+    /// whether signing in as a user whose email is already registered links the accounts, when
+    /// the provider has verified the address, rather than being turned down
+    pub link_by_email: bool,
+}
 
 #[derive(Clone)]
 pub struct AppConfig {
+    pub storage_backend: StorageBackend,
+    pub session_store: SessionStore,
     pub postgres_url: String,
     pub redis_url: String,
+    /// the path of the sqlite database file, used by either backend when set to sqlite
+    pub sqlite_url: String,
     pub key_interval: Duration,
     pub auth_interval: Duration,
+    pub gc_interval: Duration,
+    pub gc_batch_size: i64,
+    pub gc_retention: Duration,
+    /// the number of clients, plus the known scopes, kept in the local lookup cache, which is
+    /// turned off when 0
+    pub cache_capacity: usize,
+    pub cache_ttl: Duration,
+    /// whether cached lookups are also shared between instances through redis
+    pub cache_redis: bool,
+    /// the domain passkeys are scoped to, which has to be the origin's host or a parent of it
+    pub webauthn_rp_id: String,
+    /// the origin the frontend runs passkey ceremonies from
+    pub webauthn_origin: String,
+    /// the base url of the frontend, which links sent by mail point to
+    pub frontend_url: String,
+    pub mail_transport: MailTransport,
+    /// the address mail is sent from
+    pub mail_from: String,
+    /// the key signing the tokens in links sent by mail
+    pub link_signing_key: Vec<u8>,
+    pub email_verification: EmailVerificationPolicy,
+    pub password_hashing: PasswordHashingConfig,
+    pub password_policy: PasswordPolicy,
+    pub login_throttle: LoginThrottleConfig,
+    /// the bearer token the admin endpoints take, which are turned off when not set
+    pub admin_api_key: Option<String>,
+    pub federation_providers: Vec<FederationProvider>,
+    /// whether clients may register plain http backchannel notification endpoints, and ones on
+    /// loopback or private addresses, which is only ever meant for local development
+    pub allow_private_notification_endpoints: bool,
 }
 
 impl AppConfig {
@@ -17,23 +230,154 @@ impl AppConfig {
         _redis_url: &str,
         key_interval: &Duration,
         auth_interval: &Duration,
+        gc_interval: &Duration,
+        gc_batch_size: i64,
+        gc_retention: &Duration,
     ) -> Self {
         Self {
+            storage_backend: StorageBackend::Postgres,
+            session_store: SessionStore::Redis,
             postgres_url: postgres_url.to_owned(),
             redis_url: postgres_url.to_owned(),
+            sqlite_url: String::new(),
             key_interval: key_interval.to_owned(),
             auth_interval: auth_interval.to_owned(),
+            gc_interval: gc_interval.to_owned(),
+            gc_batch_size,
+            gc_retention: gc_retention.to_owned(),
+            cache_capacity: 1000,
+            cache_ttl: Duration::seconds(60),
+            cache_redis: false,
+            webauthn_rp_id: String::from("localhost"),
+            webauthn_origin: String::from("http://localhost:8000"),
+            frontend_url: String::from("http://localhost:8000"),
+            mail_transport: MailTransport::File(String::from("mail")),
+            mail_from: String::from("no-reply@localhost"),
+            link_signing_key: Self::random_link_signing_key(),
+            email_verification: EmailVerificationPolicy::Optional,
+            password_hashing: PasswordHashingConfig::default(),
+            password_policy: PasswordPolicy::default(),
+            login_throttle: LoginThrottleConfig::default(),
+            admin_api_key: None,
+            federation_providers: Vec::new(),
+            allow_private_notification_endpoints: false,
+        }
+    }
+
+    pub fn federation_provider(&self, name: &str) -> Option<&FederationProvider> {
+        self.federation_providers
+            .iter()
+            .find(|provider| provider.name == name)
+    }
+
+    /// Reads a bool from the environment, panicking on anything but `true` or `false`.
+    fn bool_var(name: &str) -> Option<bool> {
+        env::var(name).ok().map(|value| {
+            value
+                .parse::<bool>()
+                .unwrap_or_else(|_| panic!("{} must be a bool!", name))
+        })
+    }
+
+    /// Reads the provider configured under `FEDERATION_<NAME>_*`, panicking when it is incomplete.
+    fn federation_provider_vars(name: &str) -> FederationProvider {
+        let prefix = format!("FEDERATION_{}", name.to_uppercase().replace('-', "_"));
+        let var = |key: &str| env::var(format!("{}_{}", prefix, key)).ok();
+        let required_var =
+            |key: &str| var(key).unwrap_or_else(|| panic!("{}_{} must be set!", prefix, key));
+
+        let protocol = match var("PROTOCOL").as_deref() {
+            Some("oidc") | None => FederationProtocol::Oidc,
+            Some("oauth2") => FederationProtocol::OAuth2,
+            Some(value) => panic!("{}_PROTOCOL {} is not supported!", prefix, value),
+        };
+
+        let (issuer, jwks_uri, userinfo_endpoint) = match protocol {
+            FederationProtocol::Oidc => (
+                Some(required_var("ISSUER")),
+                Some(required_var("JWKS_URI")),
+                None,
+            ),
+            FederationProtocol::OAuth2 => (None, None, Some(required_var("USERINFO_ENDPOINT"))),
+        };
+
+        let scopes = var("SCOPES")
+            .unwrap_or_else(|| match protocol {
+                FederationProtocol::Oidc => String::from("openid email"),
+                FederationProtocol::OAuth2 => String::new(),
+            })
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+
+        FederationProvider {
+            name: name.to_owned(),
+            protocol,
+            client_id: required_var("CLIENT_ID"),
+            client_secret: required_var("CLIENT_SECRET"),
+            authorization_endpoint: required_var("AUTHORIZATION_ENDPOINT"),
+            token_endpoint: required_var("TOKEN_ENDPOINT"),
+            issuer,
+            jwks_uri,
+            userinfo_endpoint,
+            scopes,
+            subject_claim: var("SUBJECT_CLAIM").unwrap_or_else(|| String::from("sub")),
+            link_by_email: Self::bool_var(format!("{}_LINK_BY_EMAIL", prefix).as_str())
+                .unwrap_or(false),
         }
     }
+
+    /// A key only this instance knows, so links it mails out stop working once it restarts. Only
+    /// the default config uses one, `from_env` requires `LINK_SIGNING_KEY`.
+    fn random_link_signing_key() -> Vec<u8> {
+        let mut key = vec![0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+
+        key
+    }
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         dotenv().ok();
 
-        let postgres_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set!");
+        let storage_backend = match env::var("STORAGE_BACKEND").as_deref() {
+            Ok("postgres") | Err(_) => StorageBackend::Postgres,
+            #[cfg(feature = "sqlite")]
+            Ok("sqlite") => StorageBackend::Sqlite,
+            Ok(value) => panic!("STORAGE_BACKEND {} is not supported by this build!", value),
+        };
 
-        let redis_url = env::var("REDIS_URL").expect("REDIS_URL must be set!");
+        // a sqlite deployment keeps its sessions in the same file unless told otherwise
+        let session_store = match env::var("SESSION_STORE").as_deref() {
+            Ok("redis") => SessionStore::Redis,
+            #[cfg(feature = "sqlite")]
+            Ok("sqlite") => SessionStore::Sqlite,
+            Ok(value) => panic!("SESSION_STORE {} is not supported by this build!", value),
+            Err(_) => match storage_backend {
+                #[cfg(feature = "sqlite")]
+                StorageBackend::Sqlite => SessionStore::Sqlite,
+                _ => SessionStore::Redis,
+            },
+        };
+
+        let postgres_url = match storage_backend {
+            StorageBackend::Postgres => {
+                env::var("DATABASE_URL").expect("DATABASE_URL must be set!")
+            }
+            _ => String::new(),
+        };
+
+        let cache_redis = env::var("CACHE_REDIS")
+            .map(|value| value.parse::<bool>().expect("CACHE_REDIS must be a bool!"))
+            .unwrap_or(false);
+
+        let redis_url = match session_store == SessionStore::Redis || cache_redis {
+            true => env::var("REDIS_URL").expect("REDIS_URL must be set!"),
+            false => String::new(),
+        };
+
+        let sqlite_url = env::var("SQLITE_URL").unwrap_or_else(|_| String::from("lockrs.db"));
 
         let key_interval_sec = env::var("KEY_INTERVAL")
             .expect("KEY_INTERVAL must be set!")
@@ -47,11 +391,236 @@ impl Default for AppConfig {
             .expect("AUTH_INTERVAL must be an i64!");
         let auth_interval = Duration::seconds(auth_interval_sec);
 
+        let gc_interval_sec = env::var("GC_INTERVAL")
+            .map(|value| value.parse::<i64>().expect("GC_INTERVAL must be an i64!"))
+            .unwrap_or(60 * 60);
+        let gc_interval = Duration::seconds(gc_interval_sec);
+
+        let gc_batch_size = env::var("GC_BATCH_SIZE")
+            .map(|value| value.parse::<i64>().expect("GC_BATCH_SIZE must be an i64!"))
+            .unwrap_or(1000);
+
+        // expired rows are kept around for a while so they can still be looked at when
+        // investigating a token after the fact
+        let gc_retention_sec = env::var("GC_RETENTION")
+            .map(|value| value.parse::<i64>().expect("GC_RETENTION must be an i64!"))
+            .unwrap_or(60 * 60 * 24);
+        let gc_retention = Duration::seconds(gc_retention_sec);
+
+        let cache_capacity = env::var("CACHE_CAPACITY")
+            .map(|value| {
+                value
+                    .parse::<usize>()
+                    .expect("CACHE_CAPACITY must be a usize!")
+            })
+            .unwrap_or(1000);
+
+        // invalidations are broadcast on every write, so the ttl only bounds how long a lookup can
+        // go stale when a row changes some other way, e.g. a cascading delete
+        let cache_ttl_sec = env::var("CACHE_TTL")
+            .map(|value| value.parse::<i64>().expect("CACHE_TTL must be an i64!"))
+            .unwrap_or(60);
+        let cache_ttl = Duration::seconds(cache_ttl_sec);
+
+        let webauthn_rp_id =
+            env::var("WEBAUTHN_RP_ID").unwrap_or_else(|_| String::from("localhost"));
+        let webauthn_origin =
+            env::var("WEBAUTHN_ORIGIN").unwrap_or_else(|_| String::from("http://localhost:8000"));
+
+        let frontend_url =
+            env::var("FRONTEND_URL").unwrap_or_else(|_| String::from("http://localhost:8000"));
+
+        let mail_transport = match env::var("MAILER").as_deref() {
+            Ok("smtp") => {
+                let starttls = env::var("SMTP_STARTTLS")
+                    .map(|value| {
+                        value
+                            .parse::<bool>()
+                            .expect("SMTP_STARTTLS must be a bool!")
+                    })
+                    .unwrap_or(false);
+
+                let port = env::var("SMTP_PORT")
+                    .map(|value| value.parse::<u16>().expect("SMTP_PORT must be a u16!"))
+                    .unwrap_or(if starttls { 587 } else { 465 });
+
+                MailTransport::Smtp(SmtpConfig {
+                    host: env::var("SMTP_HOST").expect("SMTP_HOST must be set!"),
+                    port,
+                    username: env::var("SMTP_USERNAME").ok(),
+                    password: env::var("SMTP_PASSWORD").ok(),
+                    starttls,
+                })
+            }
+            Ok("file") | Err(_) => {
+                MailTransport::File(env::var("MAIL_DIR").unwrap_or_else(|_| String::from("mail")))
+            }
+            Ok("memory") => MailTransport::InMemory,
+            Ok(value) => panic!("MAILER {} is not supported!", value),
+        };
+
+        let mail_from =
+            env::var("MAIL_FROM").unwrap_or_else(|_| String::from("no-reply@localhost"));
+
+        // every instance has to share the key for a link to work wherever it lands, and across
+        // restarts, so there is no random fallback outside of tests
+        let link_signing_key = env::var("LINK_SIGNING_KEY")
+            .expect("LINK_SIGNING_KEY must be set!")
+            .into_bytes();
+
+        let email_verification = match env::var("EMAIL_VERIFICATION").as_deref() {
+            Ok("optional") | Err(_) => EmailVerificationPolicy::Optional,
+            Ok("consent") => EmailVerificationPolicy::Consent,
+            Ok("login") => EmailVerificationPolicy::Login,
+            Ok(value) => panic!("EMAIL_VERIFICATION {} is not supported!", value),
+        };
+
+        let default_password_hashing = PasswordHashingConfig::default();
+        let password_hashing = PasswordHashingConfig {
+            memory_kib: env::var("ARGON2_MEMORY")
+                .map(|value| value.parse::<u32>().expect("ARGON2_MEMORY must be a u32!"))
+                .unwrap_or(default_password_hashing.memory_kib),
+            iterations: env::var("ARGON2_ITERATIONS")
+                .map(|value| {
+                    value
+                        .parse::<u32>()
+                        .expect("ARGON2_ITERATIONS must be a u32!")
+                })
+                .unwrap_or(default_password_hashing.iterations),
+            parallelism: env::var("ARGON2_PARALLELISM")
+                .map(|value| {
+                    value
+                        .parse::<u32>()
+                        .expect("ARGON2_PARALLELISM must be a u32!")
+                })
+                .unwrap_or(default_password_hashing.parallelism),
+        };
+
+        let default_password_policy = PasswordPolicy::default();
+        let password_policy = PasswordPolicy {
+            min_length: env::var("PASSWORD_MIN_LENGTH")
+                .map(|value| {
+                    value
+                        .parse::<usize>()
+                        .expect("PASSWORD_MIN_LENGTH must be a usize!")
+                })
+                .unwrap_or(default_password_policy.min_length),
+            max_length: env::var("PASSWORD_MAX_LENGTH")
+                .map(|value| {
+                    value
+                        .parse::<usize>()
+                        .expect("PASSWORD_MAX_LENGTH must be a usize!")
+                })
+                .unwrap_or(default_password_policy.max_length),
+            require_lowercase: Self::bool_var("PASSWORD_REQUIRE_LOWERCASE")
+                .unwrap_or(default_password_policy.require_lowercase),
+            require_uppercase: Self::bool_var("PASSWORD_REQUIRE_UPPERCASE")
+                .unwrap_or(default_password_policy.require_uppercase),
+            require_digit: Self::bool_var("PASSWORD_REQUIRE_DIGIT")
+                .unwrap_or(default_password_policy.require_digit),
+            require_symbol: Self::bool_var("PASSWORD_REQUIRE_SYMBOL")
+                .unwrap_or(default_password_policy.require_symbol),
+            forbid_email: Self::bool_var("PASSWORD_FORBID_EMAIL")
+                .unwrap_or(default_password_policy.forbid_email),
+            // the corpus stays on disk, and is searched there for every new password
+            breached_passwords: env::var("BREACHED_PASSWORDS_FILE").ok().map(|path| {
+                let breached_passwords = BreachedPasswords::load(path.as_str())
+                    .unwrap_or_else(|err| panic!("Failed to load {}: {}", path, err));
+
+                tracing::info!("Checking new passwords against the hashes in {}", path);
+
+                Arc::new(breached_passwords)
+            }),
+        };
+
+        let default_login_throttle = LoginThrottleConfig::default();
+        let login_throttle = LoginThrottleConfig {
+            max_failures_per_account: env::var("LOGIN_MAX_FAILURES")
+                .map(|value| {
+                    value
+                        .parse::<i64>()
+                        .expect("LOGIN_MAX_FAILURES must be an i64!")
+                })
+                .unwrap_or(default_login_throttle.max_failures_per_account),
+            max_failures_per_address: env::var("LOGIN_MAX_FAILURES_PER_ADDRESS")
+                .map(|value| {
+                    value
+                        .parse::<i64>()
+                        .expect("LOGIN_MAX_FAILURES_PER_ADDRESS must be an i64!")
+                })
+                .unwrap_or(default_login_throttle.max_failures_per_address),
+            lockout: env::var("LOGIN_LOCKOUT")
+                .map(|value| {
+                    Duration::seconds(value.parse::<i64>().expect("LOGIN_LOCKOUT must be an i64!"))
+                })
+                .unwrap_or(default_login_throttle.lockout),
+            max_lockout: env::var("LOGIN_MAX_LOCKOUT")
+                .map(|value| {
+                    Duration::seconds(
+                        value
+                            .parse::<i64>()
+                            .expect("LOGIN_MAX_LOCKOUT must be an i64!"),
+                    )
+                })
+                .unwrap_or(default_login_throttle.max_lockout),
+            failure_window: env::var("LOGIN_FAILURE_WINDOW")
+                .map(|value| {
+                    Duration::seconds(
+                        value
+                            .parse::<i64>()
+                            .expect("LOGIN_FAILURE_WINDOW must be an i64!"),
+                    )
+                })
+                .unwrap_or(default_login_throttle.failure_window),
+        };
+
+        let admin_api_key = env::var("ADMIN_API_KEY").ok();
+
+        let federation_providers = env::var("FEDERATION_PROVIDERS")
+            .map(|names| {
+                names
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(Self::federation_provider_vars)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let allow_private_notification_endpoints =
+            Self::bool_var("ALLOW_PRIVATE_NOTIFICATION_ENDPOINTS").unwrap_or(false);
+
+        if allow_private_notification_endpoints {
+            tracing::warn!("Clients may register private backchannel notification endpoints");
+        }
+
         Self {
+            storage_backend,
+            session_store,
             postgres_url,
             redis_url,
+            sqlite_url,
             key_interval,
             auth_interval,
+            gc_interval,
+            gc_batch_size,
+            gc_retention,
+            cache_capacity,
+            cache_ttl,
+            cache_redis,
+            webauthn_rp_id,
+            webauthn_origin,
+            frontend_url,
+            mail_transport,
+            mail_from,
+            link_signing_key,
+            email_verification,
+            password_hashing,
+            password_policy,
+            login_throttle,
+            admin_api_key,
+            federation_providers,
+            allow_private_notification_endpoints,
         }
     }
 }
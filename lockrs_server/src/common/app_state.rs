@@ -1,9 +1,20 @@
 use std::sync::Arc;
 
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite::repositories::{
+    SqliteFederationStateRepository, SqliteLoginAttemptRepository, SqliteMagicLinkRepository,
+    SqlitePasswordResetTokenRepository, SqlitePushedAuthorizationRequestRepository,
+    SqliteSessionRepository, SqliteSessionTokenRepository, SqliteWebauthnChallengeRepository,
+};
 use crate::{
-    db::{pg::repositories::*, redis::repositories::*, DbContext, RepositoryContainer},
+    api::v1::mailers::{FileMailer, InMemoryMailer, Mailer, SmtpMailer},
+    db::{
+        cache::RepositoryCache, memory::InMemoryStore, redis::repositories::*, DbContext,
+        RepositoryContainer,
+    },
+    oauth2::v1::notifiers::{AuthenticationDeviceNotifier, LocalAuthenticationDeviceNotifier},
     utils::jwt::{JwtUtil, RotatingKey},
-    AppConfig,
+    AppConfig, MailTransport, SessionStore, StorageBackend,
 };
 
 #[derive(Clone)]
@@ -12,6 +23,8 @@ pub struct AppState {
     pub jwt_util: Arc<JwtUtil>,
     pub repository_container: Arc<RepositoryContainer>,
     pub db_context: Arc<DbContext>,
+    pub authentication_device_notifier: Arc<dyn AuthenticationDeviceNotifier>,
+    pub mailer: Arc<dyn Mailer>,
 }
 
 impl std::fmt::Debug for AppState {
@@ -29,31 +42,132 @@ impl AppState {
         let key = RotatingKey::new(&key_duration, &overlap_duration);
         let jwt_util = JwtUtil::new(key);
 
-        let postgres_url = config.postgres_url.clone();
-        let redis_url = config.redis_url.clone();
-
-        let repository_container = RepositoryContainer {
-            access_token_repository: Box::new(PgAccessTokenRepository),
-            authorization_code_repository: Box::new(PgAuthorizationCodeRepository),
-            client_repository: Box::new(PgClientRepository),
-            client_auth_repository: Box::new(PgClientAuthRepository),
-            device_authorization_repository: Box::new(PgDeviceAuthorizationRepository),
-            redirect_repository: Box::new(PgRedirectUriRepository),
-            refresh_token_repository: Box::new(PgRefreshTokenRepository),
-            scope_repository: Box::new(PgScopeRepository),
-            session_repository: Box::new(RedisSessionRepository),
-            session_token_repository: Box::new(RedisSessionTokenRepository),
-            user_auth_repository: Box::new(PgUserAuthRepository),
-            user_repository: Box::new(PgUserRepository),
+        let (mut repository_container, mut db_context) = match config.storage_backend {
+            StorageBackend::Postgres => (
+                RepositoryContainer::pg(),
+                DbContext::default().with_pg_pool(config.postgres_url.as_str(), 5),
+            ),
+            #[cfg(feature = "sqlite")]
+            StorageBackend::Sqlite => (
+                RepositoryContainer::sqlite(),
+                DbContext::default().with_sqlite_pool(config.sqlite_url.as_str(), 5),
+            ),
+            // nothing to connect to, and sessions are kept in memory too
+            StorageBackend::InMemory => {
+                return Self::in_memory(Some(config), Arc::new(InMemoryStore::default()))
+            }
         };
 
-        let db_context = DbContext::new(postgres_url.as_str(), 5, redis_url.as_str(), 5).await;
+        match config.session_store {
+            SessionStore::Redis => {
+                repository_container.session_repository = Box::new(RedisSessionRepository);
+                repository_container.session_token_repository =
+                    Box::new(RedisSessionTokenRepository);
+                repository_container.webauthn_challenge_repository =
+                    Box::new(RedisWebauthnChallengeRepository);
+                repository_container.password_reset_token_repository =
+                    Box::new(RedisPasswordResetTokenRepository);
+                repository_container.login_attempt_repository =
+                    Box::new(RedisLoginAttemptRepository);
+                repository_container.magic_link_repository = Box::new(RedisMagicLinkRepository);
+                repository_container.federation_state_repository =
+                    Box::new(RedisFederationStateRepository);
+                repository_container.pushed_authorization_request_repository =
+                    Box::new(RedisPushedAuthorizationRequestRepository);
+                db_context = db_context.with_redis_pool(config.redis_url.as_str(), 5);
+            }
+            #[cfg(feature = "sqlite")]
+            SessionStore::Sqlite => {
+                repository_container.session_repository = Box::new(SqliteSessionRepository);
+                repository_container.session_token_repository =
+                    Box::new(SqliteSessionTokenRepository);
+                repository_container.webauthn_challenge_repository =
+                    Box::new(SqliteWebauthnChallengeRepository);
+                repository_container.password_reset_token_repository =
+                    Box::new(SqlitePasswordResetTokenRepository);
+                repository_container.login_attempt_repository =
+                    Box::new(SqliteLoginAttemptRepository);
+                repository_container.magic_link_repository = Box::new(SqliteMagicLinkRepository);
+                repository_container.federation_state_repository =
+                    Box::new(SqliteFederationStateRepository);
+                repository_container.pushed_authorization_request_repository =
+                    Box::new(SqlitePushedAuthorizationRequestRepository);
+
+                if config.storage_backend != StorageBackend::Sqlite {
+                    db_context = db_context.with_sqlite_pool(config.sqlite_url.as_str(), 5);
+                }
+            }
+        }
+
+        // the shared tier needs redis even when sessions are kept elsewhere
+        if config.cache_redis && config.session_store != SessionStore::Redis {
+            db_context = db_context.with_redis_pool(config.redis_url.as_str(), 5);
+        }
+
+        let repository_container = Self::with_cache(&config, repository_container);
+        let mailer = Self::mailer(&config);
 
         AppState {
             config,
             jwt_util: Arc::new(jwt_util),
             repository_container: Arc::new(repository_container),
             db_context: Arc::new(db_context),
+            authentication_device_notifier: Arc::new(LocalAuthenticationDeviceNotifier::default()),
+            mailer,
         }
     }
+
+    /// An `AppState` backed by the in-memory repositories instead of postgres and redis.
+    pub fn in_memory(config: Option<AppConfig>, store: Arc<InMemoryStore>) -> Self {
+        let config = config.unwrap_or_default();
+
+        let key = RotatingKey::new(&config.key_interval, &config.auth_interval);
+        let jwt_util = JwtUtil::new(key);
+
+        let repository_container = Self::with_cache(&config, RepositoryContainer::in_memory(store));
+        let mailer = Self::mailer(&config);
+
+        AppState {
+            config,
+            jwt_util: Arc::new(jwt_util),
+            repository_container: Arc::new(repository_container),
+            db_context: Arc::new(DbContext::in_memory()),
+            authentication_device_notifier: Arc::new(LocalAuthenticationDeviceNotifier::default()),
+            mailer,
+        }
+    }
+
+    fn mailer(config: &AppConfig) -> Arc<dyn Mailer> {
+        match &config.mail_transport {
+            MailTransport::Smtp(smtp) => Arc::new(SmtpMailer::new(smtp, config.mail_from.as_str())),
+            MailTransport::File(dir) => {
+                Arc::new(FileMailer::new(dir.as_str(), config.mail_from.as_str()))
+            }
+            MailTransport::InMemory => Arc::new(InMemoryMailer::default()),
+        }
+    }
+
+    /// Puts the lookups made on every oauth2 request behind a cache, unless it is turned off.
+    fn with_cache(
+        config: &AppConfig,
+        repository_container: RepositoryContainer,
+    ) -> RepositoryContainer {
+        if config.cache_capacity == 0 {
+            return repository_container;
+        }
+
+        let ttl = config.cache_ttl.to_std().unwrap_or_default();
+        let cache = match config.cache_redis {
+            true => RepositoryCache::new(config.cache_capacity, ttl).with_redis(),
+            false => RepositoryCache::new(config.cache_capacity, ttl),
+        };
+        let cache = Arc::new(cache);
+
+        // every instance sharing a redis is told about writes made on the others
+        if !config.redis_url.is_empty() {
+            cache.subscribe(config.redis_url.as_str());
+        }
+
+        repository_container.with_cache(cache)
+    }
 }
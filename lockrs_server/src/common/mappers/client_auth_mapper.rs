@@ -10,7 +10,7 @@ impl ClientAuthMapper {
         ClientAuthModel::new(
             &pg_client.user_id,
             pg_client.id.as_str(),
-            pg_client.secret.as_deref(),
+            pg_client.is_public,
             pg_client.name.as_str(),
             pg_client.description.as_str(),
             pg_client.homepage_url.as_str(),
@@ -21,7 +21,7 @@ impl ClientAuthMapper {
         ClientModel::new(
             &client_auth.user_id,
             client_auth.id.as_str(),
-            client_auth.secret.is_none(),
+            client_auth.is_public,
             client_auth.name.as_str(),
             client_auth.description.as_str(),
             client_auth.homepage_url.as_str(),
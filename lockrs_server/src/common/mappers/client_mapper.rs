@@ -7,7 +7,7 @@ impl ClientMapper {
         ClientModel::new(
             &pg_client.user_id,
             pg_client.id.as_str(),
-            pg_client.secret.is_none(),
+            pg_client.is_public,
             pg_client.name.as_str(),
             pg_client.description.as_str(),
             pg_client.homepage_url.as_str(),
@@ -24,7 +24,6 @@ mod tests {
     #[test]
     fn it_should_map_pg_confidential_client() {
         let id = String::from("CLIENT_ID");
-        let secret = Some(String::from("CLIENT_SECRET"));
         let user_id = Uuid::new_v4();
         let name = String::from("CLIENT_NAME");
         let description = String::from("CLIENT_DESCRIPTION");
@@ -32,7 +31,6 @@ mod tests {
 
         let pg_client = PgClient {
             id: id.clone(),
-            secret,
             user_id,
             is_public: false,
             name: name.clone(),
@@ -57,7 +55,6 @@ mod tests {
     #[test]
     fn it_should_map_pg_public_client() {
         let id = String::from("CLIENT_ID");
-        let secret = None;
         let user_id = Uuid::new_v4();
         let name = String::from("CLIENT_NAME");
         let description = String::from("CLIENT_DESCRIPTION");
@@ -65,7 +62,6 @@ mod tests {
 
         let pg_client = PgClient {
             id: id.clone(),
-            secret,
             user_id,
             is_public: true,
             name: name.clone(),
@@ -0,0 +1,155 @@
+use std::str::FromStr;
+
+use chrono::Duration;
+
+use crate::{
+    db::pg::models::PgClientPolicy,
+    models::{ClientPolicyModel, GrantType},
+};
+
+pub struct ClientPolicyMapper;
+
+impl ClientPolicyMapper {
+    pub fn from_pg(pg_client_policy: PgClientPolicy) -> ClientPolicyModel {
+        let grant_types = pg_client_policy
+            .grant_types
+            .iter()
+            .flatten()
+            .filter_map(|grant_type| {
+                GrantType::from_str(grant_type)
+                    .map_err(|_| {
+                        tracing::error!(error = "Unknown grant type", grant_type);
+                    })
+                    .ok()
+            })
+            .collect::<Vec<GrantType>>();
+
+        ClientPolicyModel::new(
+            pg_client_policy.client_id.as_str(),
+            &grant_types,
+            &Duration::seconds(pg_client_policy.access_token_lifetime.into()),
+            &Duration::seconds(pg_client_policy.refresh_token_lifetime.into()),
+            &Duration::seconds(pg_client_policy.id_token_lifetime.into()),
+            pg_client_policy
+                .absolute_refresh_lifetime
+                .map(|seconds| Duration::seconds(seconds.into()))
+                .as_ref(),
+            pg_client_policy
+                .sliding_refresh_window
+                .map(|seconds| Duration::seconds(seconds.into()))
+                .as_ref(),
+            pg_client_policy.always_issue_refresh_token,
+            pg_client_policy
+                .backchannel_client_notification_endpoint
+                .as_deref(),
+        )
+    }
+
+    pub fn to_pg(client_policy: &ClientPolicyModel) -> PgClientPolicy {
+        let to_seconds =
+            |duration: &Duration| i32::try_from(duration.num_seconds()).unwrap_or(i32::MAX);
+
+        PgClientPolicy {
+            client_id: client_policy.client_id.to_owned(),
+            grant_types: client_policy
+                .grant_types
+                .iter()
+                .map(|grant_type| Some(grant_type.as_str().to_owned()))
+                .collect(),
+            access_token_lifetime: to_seconds(&client_policy.access_token_lifetime),
+            refresh_token_lifetime: to_seconds(&client_policy.refresh_token_lifetime),
+            id_token_lifetime: to_seconds(&client_policy.id_token_lifetime),
+            absolute_refresh_lifetime: client_policy
+                .absolute_refresh_lifetime
+                .as_ref()
+                .map(to_seconds),
+            sliding_refresh_window: client_policy
+                .sliding_refresh_window
+                .as_ref()
+                .map(to_seconds),
+            always_issue_refresh_token: client_policy.always_issue_refresh_token,
+            backchannel_client_notification_endpoint: client_policy
+                .backchannel_client_notification_endpoint
+                .to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_map_pg() {
+        let client_id = String::from("CLIENT_ID");
+
+        let pg_client_policy = PgClientPolicy {
+            client_id: client_id.clone(),
+            grant_types: vec![
+                Some(String::from("client_credentials")),
+                Some(String::from("refresh_token")),
+            ],
+            access_token_lifetime: 300,
+            refresh_token_lifetime: 3600,
+            id_token_lifetime: 600,
+            absolute_refresh_lifetime: Some(86400),
+            sliding_refresh_window: None,
+            always_issue_refresh_token: true,
+            backchannel_client_notification_endpoint: Some(String::from(
+                "https://client.example.com/cb",
+            )),
+        };
+
+        let actual_client_policy = ClientPolicyMapper::from_pg(pg_client_policy);
+
+        let expected_client_policy = ClientPolicyModel::new(
+            client_id.as_str(),
+            &[GrantType::ClientCredentials, GrantType::RefreshToken],
+            &Duration::minutes(5),
+            &Duration::hours(1),
+            &Duration::minutes(10),
+            Some(&Duration::days(1)),
+            None,
+            true,
+            Some("https://client.example.com/cb"),
+        );
+
+        assert_eq!(actual_client_policy, expected_client_policy);
+    }
+
+    #[test]
+    fn it_should_map_pg_skipping_unknown_grant_types() {
+        let client_id = String::from("CLIENT_ID");
+
+        let pg_client_policy = PgClientPolicy {
+            client_id: client_id.clone(),
+            grant_types: vec![
+                Some(String::from("password")),
+                Some(String::from("urn:ietf:params:oauth:grant-type:device_code")),
+            ],
+            access_token_lifetime: 600,
+            refresh_token_lifetime: 86400,
+            id_token_lifetime: 3600,
+            absolute_refresh_lifetime: None,
+            sliding_refresh_window: Some(3600),
+            always_issue_refresh_token: false,
+            backchannel_client_notification_endpoint: None,
+        };
+
+        let actual_client_policy = ClientPolicyMapper::from_pg(pg_client_policy);
+
+        let expected_client_policy = ClientPolicyModel::new(
+            client_id.as_str(),
+            &[GrantType::DeviceCode],
+            &Duration::minutes(10),
+            &Duration::hours(24),
+            &Duration::hours(1),
+            None,
+            Some(&Duration::hours(1)),
+            false,
+            None,
+        );
+
+        assert_eq!(actual_client_policy, expected_client_policy);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{db::pg::models::PgClientSecret, models::ClientSecretModel};
+
+pub struct ClientSecretMapper;
+
+impl ClientSecretMapper {
+    pub fn from_pg(pg_client_secret: PgClientSecret) -> ClientSecretModel {
+        ClientSecretModel::new(
+            pg_client_secret.id,
+            pg_client_secret.client_id.as_str(),
+            pg_client_secret.salt.as_str(),
+            pg_client_secret.secret_hash.as_str(),
+            &pg_client_secret.created_at,
+            pg_client_secret.expires_at.as_ref(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::*;
+
+    #[test]
+    fn it_should_map_pg() {
+        let id = 1;
+        let client_id = String::from("CLIENT_ID");
+        let salt = String::from("SALT");
+        let secret_hash = String::from("SECRET_HASH");
+        let created_at = Utc::now().naive_utc();
+        let expires_at = Some(created_at + Duration::days(1));
+
+        let pg_client_secret = PgClientSecret {
+            id,
+            client_id: client_id.clone(),
+            salt: salt.clone(),
+            secret_hash: secret_hash.clone(),
+            created_at,
+            expires_at,
+        };
+
+        let actual_client_secret = ClientSecretMapper::from_pg(pg_client_secret);
+
+        let expected_client_secret = ClientSecretModel::new(
+            id,
+            client_id.as_str(),
+            salt.as_str(),
+            secret_hash.as_str(),
+            &created_at,
+            expires_at.as_ref(),
+        );
+
+        assert_eq!(actual_client_secret, expected_client_secret);
+    }
+}
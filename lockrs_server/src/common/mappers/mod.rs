@@ -1,6 +1,11 @@
 mod client_auth_mapper;
 mod client_mapper;
+mod client_policy_mapper;
+mod client_secret_mapper;
 mod redirect_mapper;
 mod user_mapper;
 
-pub use self::{client_auth_mapper::*, client_mapper::*, redirect_mapper::*, user_mapper::*};
+pub use self::{
+    client_auth_mapper::*, client_mapper::*, client_policy_mapper::*, client_secret_mapper::*,
+    redirect_mapper::*, user_mapper::*,
+};
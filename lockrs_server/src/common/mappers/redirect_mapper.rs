@@ -1,6 +1,11 @@
+use std::str::FromStr;
+
 use url::Url;
 
-use crate::{db::pg::models::PgRedirectUri, models::RedirectModel};
+use crate::{
+    db::pg::models::PgRedirectUri,
+    models::{RedirectMatchMode, RedirectModel},
+};
 
 pub struct RedirectMapper;
 
@@ -11,6 +16,13 @@ impl RedirectMapper {
             pg_redirect.client_id.as_str(),
             &Url::parse(&pg_redirect.uri)
                 .unwrap_or_else(|_| panic!("invalid url stored in database: {}", pg_redirect.id)),
+            RedirectMatchMode::from_str(&pg_redirect.match_mode).unwrap_or_else(|_| {
+                tracing::error!(
+                    error = "Unknown redirect match mode",
+                    match_mode = pg_redirect.match_mode
+                );
+                RedirectMatchMode::Exact
+            }),
         )
     }
 }
@@ -36,11 +48,38 @@ mod tests {
             uri: uri.to_string(),
             created_at: created_at.naive_utc(),
             updated_at: updated_at.naive_utc(),
+            match_mode: String::from("exact"),
+        };
+
+        let actual_redirect = RedirectMapper::from_pg(pg_redirect);
+
+        let expected_redirect =
+            RedirectModel::new(&id, client_id.as_str(), &uri, RedirectMatchMode::Exact);
+
+        assert_eq!(actual_redirect, expected_redirect);
+    }
+
+    #[test]
+    fn it_should_map_pg_loopback() {
+        let id = uuid!("00000000-0000-0000-0000-000000000000");
+        let client_id = String::from("CLIENT_ID");
+        let uri = Url::parse("http://127.0.0.1/oauth2/callback").unwrap();
+        let created_at = Utc::now();
+        let updated_at = Utc::now();
+
+        let pg_redirect = PgRedirectUri {
+            id,
+            client_id: client_id.clone(),
+            uri: uri.to_string(),
+            created_at: created_at.naive_utc(),
+            updated_at: updated_at.naive_utc(),
+            match_mode: String::from("loopback"),
         };
 
         let actual_redirect = RedirectMapper::from_pg(pg_redirect);
 
-        let expected_redirect = RedirectModel::new(&id, client_id.as_str(), &uri);
+        let expected_redirect =
+            RedirectModel::new(&id, client_id.as_str(), &uri, RedirectMatchMode::Loopback);
 
         assert_eq!(actual_redirect, expected_redirect);
     }
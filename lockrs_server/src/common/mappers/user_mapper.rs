@@ -4,7 +4,7 @@ pub struct UserMapper;
 
 impl UserMapper {
     pub fn from_pg(pg_user: PgUser) -> UserModel {
-        UserModel::new(pg_user.id, pg_user.email.as_str())
+        UserModel::new(pg_user.id, pg_user.email.as_str(), pg_user.email_verified)
     }
 
     pub fn into_pg(user: UserModel, password_hash: String) -> PgUser {
@@ -12,6 +12,8 @@ impl UserMapper {
             id: user.id,
             email: user.email,
             password_hash,
+            email_verified: user.email_verified,
+            password_version: 0,
         }
     }
 }
@@ -32,11 +34,13 @@ mod tests {
             id,
             email: email.clone(),
             password_hash,
+            email_verified: true,
+            password_version: 0,
         };
 
         let actual_user = UserMapper::from_pg(pg_user);
 
-        let expected_user = UserModel::new(id, email.as_str());
+        let expected_user = UserModel::new(id, email.as_str(), true);
 
         assert_eq!(actual_user, expected_user);
     }
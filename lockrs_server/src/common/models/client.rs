@@ -1,9 +1,10 @@
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::db::pg::schema::clients;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct ClientModel {
     pub user_id: Uuid,
     pub id: String,
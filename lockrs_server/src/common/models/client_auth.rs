@@ -1,10 +1,12 @@
+use serde::{Deserialize, Serialize};
 use url::Url;
 use uuid::Uuid;
 
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ClientAuthModel {
     pub user_id: Uuid,
     pub id: String,
-    pub secret: Option<String>,
+    pub is_public: bool,
     pub name: String,
     pub description: String,
     pub homepage_url: String,
@@ -14,7 +16,7 @@ impl ClientAuthModel {
     pub fn new(
         user_id: &Uuid,
         id: &str,
-        secret: Option<&str>,
+        is_public: bool,
         name: &str,
         description: &str,
         homepage_url: &str,
@@ -22,7 +24,7 @@ impl ClientAuthModel {
         Self {
             user_id: user_id.to_owned(),
             id: id.to_owned(),
-            secret: secret.map(|s| s.to_owned()),
+            is_public,
             name: name.to_owned(),
             description: description.to_owned(),
             homepage_url: homepage_url.to_owned(),
@@ -30,16 +32,6 @@ impl ClientAuthModel {
     }
 }
 
-impl std::fmt::Debug for ClientAuthModel {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "ClientAuthModel: {{ {:?}, {:?}, secret: ********, {:?}, {:?}, {:?} }}",
-            self.user_id, self.id, self.name, self.description, self.homepage_url,
-        )
-    }
-}
-
 #[derive(Debug)]
 pub struct ClientRegistration {
     pub user_id: Uuid,
@@ -0,0 +1,163 @@
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GrantType {
+    #[serde(rename = "authorization_code")]
+    AuthorizationCode,
+    #[serde(rename = "urn:ietf:params:oauth:grant-type:device_code")]
+    DeviceCode,
+    #[serde(rename = "urn:openid:params:grant-type:ciba")]
+    Ciba,
+    #[serde(rename = "client_credentials")]
+    ClientCredentials,
+    #[serde(rename = "refresh_token")]
+    RefreshToken,
+}
+
+impl GrantType {
+    pub const ALL: [Self; 5] = [
+        Self::AuthorizationCode,
+        Self::DeviceCode,
+        Self::Ciba,
+        Self::ClientCredentials,
+        Self::RefreshToken,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AuthorizationCode => "authorization_code",
+            Self::DeviceCode => "urn:ietf:params:oauth:grant-type:device_code",
+            Self::Ciba => "urn:openid:params:grant-type:ciba",
+            Self::ClientCredentials => "client_credentials",
+            Self::RefreshToken => "refresh_token",
+        }
+    }
+}
+
+impl std::str::FromStr for GrantType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|grant_type| grant_type.as_str() == s)
+            .ok_or(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ClientPolicyModel {
+    pub client_id: String,
+    pub grant_types: Vec<GrantType>,
+    pub access_token_lifetime: Duration,
+    pub refresh_token_lifetime: Duration,
+    pub id_token_lifetime: Duration,
+    /// how long a refresh grant may be kept alive through rotation, measured from the original grant
+    pub absolute_refresh_lifetime: Option<Duration>,
+    /// when set, each rotation extends the refresh token by this much instead of keeping the
+    /// expiry of the token it replaces
+    pub sliding_refresh_window: Option<Duration>,
+    /// issue refresh tokens for user grants even when `offline_access` was not granted
+    pub always_issue_refresh_token: bool,
+    /// where the results of ping and push mode backchannel authentication are sent, which is
+    /// never taken from the request itself
+    pub backchannel_client_notification_endpoint: Option<String>,
+}
+
+impl ClientPolicyModel {
+    pub const OFFLINE_ACCESS_SCOPE: &'static str = "offline_access";
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        client_id: &str,
+        grant_types: &[GrantType],
+        access_token_lifetime: &Duration,
+        refresh_token_lifetime: &Duration,
+        id_token_lifetime: &Duration,
+        absolute_refresh_lifetime: Option<&Duration>,
+        sliding_refresh_window: Option<&Duration>,
+        always_issue_refresh_token: bool,
+        backchannel_client_notification_endpoint: Option<&str>,
+    ) -> Self {
+        Self {
+            client_id: client_id.to_owned(),
+            grant_types: grant_types.to_vec(),
+            access_token_lifetime: access_token_lifetime.to_owned(),
+            refresh_token_lifetime: refresh_token_lifetime.to_owned(),
+            id_token_lifetime: id_token_lifetime.to_owned(),
+            absolute_refresh_lifetime: absolute_refresh_lifetime.map(|d| d.to_owned()),
+            sliding_refresh_window: sliding_refresh_window.map(|d| d.to_owned()),
+            always_issue_refresh_token,
+            backchannel_client_notification_endpoint: backchannel_client_notification_endpoint
+                .map(|endpoint| endpoint.to_owned()),
+        }
+    }
+
+    /// the policy applied to clients that have not configured one
+    pub fn default_for(client_id: &str) -> Self {
+        Self::new(
+            client_id,
+            &GrantType::ALL,
+            &Duration::minutes(10),
+            &Duration::hours(24),
+            &Duration::hours(1),
+            None,
+            None,
+            false,
+            None,
+        )
+    }
+
+    pub fn allows(&self, grant_type: GrantType) -> bool {
+        self.grant_types.contains(&grant_type)
+    }
+
+    /// rfc: https://www.rfc-editor.org/rfc/rfc6749#section-4.4.3
+    pub fn issues_refresh_token(&self, grant_type: GrantType, scopes: &[String]) -> bool {
+        // a refresh token the client may not redeem is never useful
+        if !self.allows(GrantType::RefreshToken) {
+            return false;
+        }
+
+        match grant_type {
+            GrantType::ClientCredentials => false,
+            GrantType::RefreshToken => true,
+            _ => {
+                self.always_issue_refresh_token
+                    || scopes
+                        .iter()
+                        .any(|scope| scope == Self::OFFLINE_ACCESS_SCOPE)
+            }
+        }
+    }
+
+    pub fn is_refresh_grant_expired(
+        &self,
+        now: &NaiveDateTime,
+        grant_created_at: &NaiveDateTime,
+    ) -> bool {
+        match self.absolute_refresh_lifetime {
+            Some(lifetime) => *grant_created_at + lifetime <= *now,
+            None => false,
+        }
+    }
+
+    pub fn refresh_token_expires_at(
+        &self,
+        now: &NaiveDateTime,
+        grant_created_at: &NaiveDateTime,
+        previous_expires_at: Option<&NaiveDateTime>,
+    ) -> NaiveDateTime {
+        let expires_at = match (previous_expires_at, self.sliding_refresh_window) {
+            (None, _) => *now + self.refresh_token_lifetime,
+            (Some(_), Some(window)) => *now + window,
+            (Some(previous_expires_at), None) => *previous_expires_at,
+        };
+
+        match self.absolute_refresh_lifetime {
+            Some(lifetime) => expires_at.min(*grant_created_at + lifetime),
+            None => expires_at,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Serialize, Deserialize)]
+pub struct ClientSecretModel {
+    pub id: i32,
+    pub client_id: String,
+    pub salt: String,
+    pub secret_hash: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+impl ClientSecretModel {
+    pub fn new(
+        id: i32,
+        client_id: &str,
+        salt: &str,
+        secret_hash: &str,
+        created_at: &NaiveDateTime,
+        expires_at: Option<&NaiveDateTime>,
+    ) -> Self {
+        Self {
+            id,
+            client_id: client_id.to_owned(),
+            salt: salt.to_owned(),
+            secret_hash: secret_hash.to_owned(),
+            created_at: created_at.to_owned(),
+            expires_at: expires_at.map(|e| e.to_owned()),
+        }
+    }
+}
+
+impl std::fmt::Debug for ClientSecretModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ClientSecretModel: {{ {:?}, {:?}, salt: ********, secret_hash: ********, {:?}, {:?} }}",
+            self.id, self.client_id, self.created_at, self.expires_at,
+        )
+    }
+}
+
+pub struct ClientSecretCreateModel {
+    pub client_id: String,
+    pub salt: String,
+    pub secret_hash: String,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+impl ClientSecretCreateModel {
+    pub fn new(
+        client_id: &str,
+        salt: &str,
+        secret_hash: &str,
+        expires_at: Option<&NaiveDateTime>,
+    ) -> Self {
+        Self {
+            client_id: client_id.to_owned(),
+            salt: salt.to_owned(),
+            secret_hash: secret_hash.to_owned(),
+            expires_at: expires_at.map(|e| e.to_owned()),
+        }
+    }
+}
+
+impl std::fmt::Debug for ClientSecretCreateModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ClientSecretCreateModel: {{ {:?}, salt: ********, secret_hash: ********, {:?} }}",
+            self.client_id, self.expires_at,
+        )
+    }
+}
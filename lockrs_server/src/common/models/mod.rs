@@ -1,6 +1,10 @@
 mod client;
 mod client_auth;
+mod client_policy;
+mod client_secret;
 mod redirect;
 mod user;
 
-pub use self::{client::*, client_auth::*, redirect::*, user::*};
+pub use self::{
+    client::*, client_auth::*, client_policy::*, client_secret::*, redirect::*, user::*,
+};
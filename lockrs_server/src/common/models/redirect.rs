@@ -1,19 +1,80 @@
-use url::Url;
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+use url::{Host, Url};
 use uuid::Uuid;
 
-#[derive(Debug, PartialEq)]
+/// rfc: https://www.rfc-editor.org/rfc/rfc8252#section-7
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedirectMatchMode {
+    #[default]
+    Exact,
+    Loopback,
+    PrivateUse,
+}
+
+impl RedirectMatchMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::Loopback => "loopback",
+            Self::PrivateUse => "private_use",
+        }
+    }
+
+    pub fn matches(&self, registered: &Url, requested: &Url) -> bool {
+        match self {
+            Self::Exact | Self::PrivateUse => registered == requested,
+            // native apps bind an ephemeral port, so everything but the port must match
+            Self::Loopback => {
+                Self::is_loopback_ip(requested)
+                    && registered.scheme() == requested.scheme()
+                    && registered.host() == requested.host()
+                    && registered.path() == requested.path()
+                    && registered.query() == requested.query()
+                    && requested.fragment().is_none()
+            }
+        }
+    }
+
+    pub fn is_loopback_ip(uri: &Url) -> bool {
+        match uri.host() {
+            Some(Host::Ipv4(ip)) => IpAddr::V4(ip).is_loopback(),
+            Some(Host::Ipv6(ip)) => IpAddr::V6(ip).is_loopback(),
+            _ => false,
+        }
+    }
+}
+
+impl std::str::FromStr for RedirectMatchMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "exact" => Ok(Self::Exact),
+            "loopback" => Ok(Self::Loopback),
+            "private_use" => Ok(Self::PrivateUse),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct RedirectModel {
     pub id: Uuid,
     pub client_id: String,
     pub uri: Url,
+    pub match_mode: RedirectMatchMode,
 }
 
 impl RedirectModel {
-    pub fn new(id: &Uuid, client_id: &str, uri: &Url) -> Self {
+    pub fn new(id: &Uuid, client_id: &str, uri: &Url, match_mode: RedirectMatchMode) -> Self {
         Self {
             id: id.to_owned(),
             client_id: client_id.to_owned(),
             uri: uri.to_owned(),
+            match_mode,
         }
     }
 }
@@ -22,13 +83,15 @@ impl RedirectModel {
 pub struct RedirectCreateModel {
     pub client_id: String,
     pub uri: Url,
+    pub match_mode: RedirectMatchMode,
 }
 
 impl RedirectCreateModel {
-    pub fn new(client_id: &str, uri: &Url) -> Self {
+    pub fn new(client_id: &str, uri: &Url, match_mode: RedirectMatchMode) -> Self {
         Self {
             client_id: client_id.to_owned(),
             uri: uri.to_owned(),
+            match_mode,
         }
     }
 }
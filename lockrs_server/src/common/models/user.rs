@@ -7,13 +7,16 @@ use crate::db::pg::schema::users;
 pub struct UserModel {
     pub id: Uuid,
     pub email: String,
+    /// whether the user has proven they own `email`, by following the link mailed to it
+    pub email_verified: bool,
 }
 
 impl UserModel {
-    pub fn new(id: Uuid, email: &str) -> Self {
+    pub fn new(id: Uuid, email: &str, email_verified: bool) -> Self {
         Self {
             id,
             email: email.to_owned(),
+            email_verified,
         }
     }
 }
@@ -22,12 +25,14 @@ impl UserModel {
 #[diesel(primary_key(id), table_name = users)]
 pub struct UserUpdateModel {
     pub email: Option<String>,
+    pub email_verified: Option<bool>,
 }
 
 impl UserUpdateModel {
-    pub fn new(email: Option<&str>) -> Self {
+    pub fn new(email: Option<&str>, email_verified: Option<bool>) -> Self {
         Self {
             email: email.map(|s| s.to_owned()),
+            email_verified,
         }
     }
 }
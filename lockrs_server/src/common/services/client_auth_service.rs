@@ -1,7 +1,11 @@
 use std::sync::Arc;
 
 use base64::{engine::general_purpose, Engine as _};
-use ring::rand::{SecureRandom, SystemRandom};
+use chrono::{Duration, NaiveDateTime, Utc};
+use ring::{
+    constant_time, digest,
+    rand::{SecureRandom, SystemRandom},
+};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -11,17 +15,23 @@ use crate::{
         DbContext,
     },
     mappers::ClientAuthMapper,
-    models::{ClientAuthModel, ClientModel, ClientRegistration, RedirectCreateModel},
+    models::{
+        ClientAuthModel, ClientModel, ClientRegistration, ClientSecretCreateModel,
+        ClientSecretModel, RedirectCreateModel, RedirectMatchMode,
+    },
+    services::RedirectService,
 };
 
 pub struct ClientAuthService;
 
 impl ClientAuthService {
+    /// returns the client along with its plaintext secret, which is not stored and can never be
+    /// retrieved again
     pub async fn register(
         db_context: &Arc<DbContext>,
         client_auth_repository: &dyn ClientAuthRepository,
         new_client: ClientRegistration,
-    ) -> Result<ClientModel, ClientAuthServiceError> {
+    ) -> Result<(ClientModel, Option<String>), ClientAuthServiceError> {
         tracing::trace!(
             method = "register",
             client = ?new_client,
@@ -36,22 +46,38 @@ impl ClientAuthService {
         let client_create = ClientAuthModel::new(
             &new_client.user_id,
             id.as_str(),
-            secret.as_deref(),
+            new_client.is_public,
             new_client.name.as_str(),
             new_client.description.as_str(),
             new_client.homepage_url.to_string().as_str(),
         );
 
-        let redirect_create = RedirectCreateModel::new(id.as_str(), &new_client.redirect_url);
+        RedirectService::validate_redirect(&new_client.redirect_url, RedirectMatchMode::Exact)
+            .map_err(|_| ClientAuthServiceError::InvalidRedirect)?;
+
+        let redirect_create = RedirectCreateModel::new(
+            id.as_str(),
+            &new_client.redirect_url,
+            RedirectMatchMode::Exact,
+        );
+
+        let secret_create = secret
+            .as_deref()
+            .map(|secret| Self::hash_secret(id.as_str(), secret, None));
 
         let client = client_auth_repository
-            .create(db_context, &client_create, &redirect_create)
+            .create(
+                db_context,
+                &client_create,
+                secret_create.as_ref(),
+                &redirect_create,
+            )
             .await
             .map_err(ClientAuthServiceError::from)?;
 
         tracing::info!("Client created: {:?}", client,);
 
-        Ok(ClientAuthMapper::into_client(client))
+        Ok((ClientAuthMapper::into_client(client), secret))
     }
 
     pub async fn authenticate(
@@ -63,10 +89,32 @@ impl ClientAuthService {
         tracing::trace!(method = "verify_credentials", id);
 
         let client = client_auth_repository
-            .get_by_credentials(db_context, id, secret)
+            .get_by_id(db_context, id)
             .await
             .map_err(ClientAuthServiceError::from)?;
 
+        match (client.is_public, secret) {
+            (true, None) => (),
+            (false, Some(secret)) => {
+                let client_secrets = client_auth_repository
+                    .get_active_secrets_by_client_id(db_context, id)
+                    .await
+                    .map_err(ClientAuthServiceError::from)?;
+
+                if !client_secrets
+                    .iter()
+                    .any(|client_secret| Self::verify_secret(secret, client_secret))
+                {
+                    tracing::error!(error = "Client secret did not match any active secret");
+                    return Err(ClientAuthServiceError::NotFound);
+                }
+            }
+            _ => {
+                tracing::error!(error = "Client credentials do not match the client type");
+                return Err(ClientAuthServiceError::NotFound);
+            }
+        }
+
         tracing::info!("Client authenticated: {:?}", client);
 
         Ok(ClientAuthMapper::into_client(client))
@@ -92,6 +140,78 @@ impl ClientAuthService {
         Ok(client)
     }
 
+    /// issues a new secret, keeping the client's other secrets valid for `overlap` so that
+    /// deployments can switch over without downtime
+    pub async fn rotate_secret(
+        db_context: &Arc<DbContext>,
+        client_auth_repository: &dyn ClientAuthRepository,
+        client_id: &str,
+        expires_in: Option<Duration>,
+        overlap: Duration,
+    ) -> Result<(String, ClientSecretModel), ClientAuthServiceError> {
+        tracing::trace!(method = "rotate_secret", client_id, ?expires_in, ?overlap);
+
+        if expires_in.is_some_and(|expires_in| expires_in <= Duration::zero())
+            || overlap < Duration::zero()
+        {
+            return Err(ClientAuthServiceError::InvalidLifetime);
+        }
+
+        let client = client_auth_repository
+            .get_by_id(db_context, client_id)
+            .await
+            .map_err(ClientAuthServiceError::from)?;
+
+        if client.is_public {
+            tracing::error!(error = "Public clients may not hold secrets");
+            return Err(ClientAuthServiceError::PublicClient);
+        }
+
+        let now = Utc::now().naive_utc();
+        let expires_at = expires_in.map(|expires_in| now + expires_in);
+
+        let secret = Self::generate_random_string();
+        let secret_create = Self::hash_secret(client_id, secret.as_str(), expires_at.as_ref());
+
+        let client_secret = client_auth_repository
+            .rotate_secret(db_context, &secret_create, &(now + overlap))
+            .await
+            .map_err(ClientAuthServiceError::from)?;
+
+        tracing::info!("Client secret rotated: {:?}", client_secret);
+
+        Ok((secret, client_secret))
+    }
+
+    pub fn hash_secret(
+        client_id: &str,
+        secret: &str,
+        expires_at: Option<&NaiveDateTime>,
+    ) -> ClientSecretCreateModel {
+        let salt = Self::generate_random_string();
+        let secret_hash = Self::digest_secret(salt.as_str(), secret);
+
+        ClientSecretCreateModel::new(client_id, salt.as_str(), secret_hash.as_str(), expires_at)
+    }
+
+    fn verify_secret(secret: &str, client_secret: &ClientSecretModel) -> bool {
+        let secret_hash = Self::digest_secret(client_secret.salt.as_str(), secret);
+
+        constant_time::verify_slices_are_equal(
+            secret_hash.as_bytes(),
+            client_secret.secret_hash.as_bytes(),
+        )
+        .is_ok()
+    }
+
+    fn digest_secret(salt: &str, secret: &str) -> String {
+        let mut context = digest::Context::new(&digest::SHA256);
+        context.update(salt.as_bytes());
+        context.update(secret.as_bytes());
+
+        general_purpose::URL_SAFE_NO_PAD.encode(context.finish())
+    }
+
     pub fn generate_random_string() -> String {
         let mut buffer = [0u8; 24];
         let rng = SystemRandom::new();
@@ -106,6 +226,12 @@ pub enum ClientAuthServiceError {
     NotFound,
     #[error("CLIENT AUTH SERVICE ERROR :: Invalid User")]
     InvalidUser,
+    #[error("CLIENT AUTH SERVICE ERROR :: Invalid redirect")]
+    InvalidRedirect,
+    #[error("CLIENT AUTH SERVICE ERROR :: Public client")]
+    PublicClient,
+    #[error("CLIENT AUTH SERVICE ERROR :: Invalid lifetime")]
+    InvalidLifetime,
 
     #[error("CLIENT AUTH SERVICE ERROR :: Internal Error")]
     InternalError,
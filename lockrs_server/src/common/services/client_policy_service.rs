@@ -0,0 +1,175 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+use thiserror::Error;
+use url::Url;
+
+use crate::{
+    db::{
+        repositories::{ClientPolicyRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    models::ClientPolicyModel,
+    utils::public_endpoint::{PublicEndpointError, PublicEndpointUtil},
+};
+
+pub struct ClientPolicyService;
+
+impl ClientPolicyService {
+    pub async fn get_policy(
+        db_context: &Arc<DbContext>,
+        client_policy_repository: &dyn ClientPolicyRepository,
+        client_id: &str,
+    ) -> Result<ClientPolicyModel, ClientPolicyServiceError> {
+        tracing::trace!(method = "get_policy", client_id);
+
+        match client_policy_repository
+            .get_by_client_id(db_context, client_id)
+            .await
+            .map_err(ClientPolicyServiceError::from)
+        {
+            Ok(client_policy) => Ok(client_policy),
+            Err(ClientPolicyServiceError::NotFound) => {
+                Ok(ClientPolicyModel::default_for(client_id))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// `allow_private_notification_endpoints` lets the backchannel client notification endpoint
+    /// be plain http, or on a loopback or private address, for local development
+    pub async fn set_policy(
+        db_context: &Arc<DbContext>,
+        client_policy_repository: &dyn ClientPolicyRepository,
+        client_policy: &ClientPolicyModel,
+        allow_private_notification_endpoints: bool,
+    ) -> Result<ClientPolicyModel, ClientPolicyServiceError> {
+        tracing::trace!(method = "set_policy", ?client_policy);
+
+        Self::validate_policy(client_policy, allow_private_notification_endpoints)?;
+
+        let client_policy = client_policy_repository
+            .upsert(db_context, client_policy)
+            .await
+            .map_err(ClientPolicyServiceError::from)?;
+
+        tracing::info!(
+            "Client policy updated: {{ client_id: {}, policy: {:?} }}",
+            &client_policy.client_id,
+            &client_policy,
+        );
+
+        Ok(client_policy)
+    }
+
+    pub async fn reset_policy(
+        db_context: &Arc<DbContext>,
+        client_policy_repository: &dyn ClientPolicyRepository,
+        client_id: &str,
+    ) -> Result<(), ClientPolicyServiceError> {
+        tracing::trace!(method = "reset_policy", client_id);
+
+        client_policy_repository
+            .delete_by_client_id(db_context, client_id)
+            .await
+            .map_err(ClientPolicyServiceError::from)?;
+
+        tracing::info!("Client policy reset: {}", client_id);
+
+        Ok(())
+    }
+
+    pub fn validate_policy(
+        client_policy: &ClientPolicyModel,
+        allow_private_notification_endpoints: bool,
+    ) -> Result<(), ClientPolicyServiceError> {
+        let is_valid_lifetime = |lifetime: &Duration| {
+            lifetime.num_seconds() > 0 && lifetime.num_seconds() <= i64::from(i32::MAX)
+        };
+
+        let lifetimes = [
+            Some(&client_policy.access_token_lifetime),
+            Some(&client_policy.refresh_token_lifetime),
+            Some(&client_policy.id_token_lifetime),
+            client_policy.absolute_refresh_lifetime.as_ref(),
+            client_policy.sliding_refresh_window.as_ref(),
+        ];
+
+        if !lifetimes.into_iter().flatten().all(is_valid_lifetime) {
+            tracing::error!(error = "Client policy lifetimes must be positive");
+            return Err(ClientPolicyServiceError::InvalidPolicy);
+        }
+
+        if client_policy.grant_types.is_empty() {
+            tracing::error!(error = "Client policy must allow at least one grant type");
+            return Err(ClientPolicyServiceError::InvalidPolicy);
+        }
+
+        // an absolute lifetime shorter than the per-token lifetimes could never take effect as configured
+        if let Some(absolute_refresh_lifetime) = client_policy.absolute_refresh_lifetime {
+            let exceeds_absolute = client_policy.refresh_token_lifetime > absolute_refresh_lifetime
+                || client_policy
+                    .sliding_refresh_window
+                    .is_some_and(|window| window > absolute_refresh_lifetime);
+
+            if exceeds_absolute {
+                tracing::error!(error = "Refresh lifetimes exceed the absolute refresh lifetime");
+                return Err(ClientPolicyServiceError::InvalidPolicy);
+            }
+        }
+
+        // the server posts tokens to this endpoint from inside its network, so a client may not
+        // point it anywhere a stranger on the internet couldn't reach
+        if let Some(endpoint) = client_policy
+            .backchannel_client_notification_endpoint
+            .as_deref()
+        {
+            Url::parse(endpoint)
+                .map_err(|_| PublicEndpointError::NotHttps)
+                .and_then(|endpoint| {
+                    PublicEndpointUtil::validate(&endpoint, allow_private_notification_endpoints)
+                })
+                .map_err(|err| {
+                    tracing::error!(error = %err);
+                    ClientPolicyServiceError::InvalidNotificationEndpoint
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ClientPolicyServiceError {
+    #[error("CLIENT POLICY SERVICE ERROR :: Invalid Policy")]
+    InvalidPolicy,
+    #[error("CLIENT POLICY SERVICE ERROR :: Invalid Notification Endpoint")]
+    InvalidNotificationEndpoint,
+    #[error("CLIENT POLICY SERVICE ERROR :: Not Created")]
+    NotCreated,
+    #[error("CLIENT POLICY SERVICE ERROR :: Not Found")]
+    NotFound,
+    #[error("CLIENT POLICY SERVICE ERROR :: Not Deleted")]
+    NotDeleted,
+
+    #[error("CLIENT POLICY SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for ClientPolicyServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            RepositoryError::QueryFailed(query_err) => match query_err {
+                QueryFailure::NotCreated => Self::NotCreated,
+                QueryFailure::NotFound => Self::NotFound,
+                QueryFailure::NotDeleted => Self::NotDeleted,
+
+                _ => Self::InternalError,
+            },
+
+            RepositoryError::InternalError => Self::InternalError,
+        }
+    }
+}
@@ -1,6 +1,10 @@
 mod client_auth_service;
+mod client_policy_service;
 mod client_service;
 mod redirect_service;
 mod user_service;
 
-pub use self::{client_auth_service::*, client_service::*, redirect_service::*, user_service::*};
+pub use self::{
+    client_auth_service::*, client_policy_service::*, client_service::*, redirect_service::*,
+    user_service::*,
+};
@@ -9,7 +9,7 @@ use crate::{
         repositories::{QueryFailure, RedirectUriRepository, RepositoryError},
         DbContext,
     },
-    models::{RedirectCreateModel, RedirectModel},
+    models::{RedirectCreateModel, RedirectMatchMode, RedirectModel},
 };
 
 pub struct RedirectService;
@@ -22,8 +22,13 @@ impl RedirectService {
     ) -> Result<RedirectModel, RedirectServiceError> {
         tracing::trace!(method = "create_redirect", ?new_redirect,);
 
-        let redirect_create =
-            RedirectCreateModel::new(new_redirect.client_id.as_str(), &new_redirect.uri);
+        Self::validate_redirect(&new_redirect.uri, new_redirect.match_mode)?;
+
+        let redirect_create = RedirectCreateModel::new(
+            new_redirect.client_id.as_str(),
+            &new_redirect.uri,
+            new_redirect.match_mode,
+        );
 
         let redirect = redirect_repository
             .create(db_context, &redirect_create)
@@ -61,9 +66,15 @@ impl RedirectService {
         );
 
         let redirect = redirect_repository
-            .get_by_uri(db_context, client_id, uri)
+            .get_all_by_client_id(db_context, client_id)
             .await
-            .map_err(RedirectServiceError::from)?;
+            .map_err(RedirectServiceError::from)?
+            .into_iter()
+            .find(|redirect| redirect.match_mode.matches(&redirect.uri, uri))
+            .ok_or_else(|| {
+                tracing::error!(error = "No registered redirect matches the requested uri");
+                RedirectServiceError::NotFound
+            })?;
 
         tracing::debug!(
             "Redirect verified: {{ id: {}, uri: {}, client_id: {} }}",
@@ -75,6 +86,48 @@ impl RedirectService {
         Ok(redirect)
     }
 
+    /// Rejects redirect uris that are unsafe to register under the requested match mode.
+    pub fn validate_redirect(
+        uri: &Url,
+        match_mode: RedirectMatchMode,
+    ) -> Result<(), RedirectServiceError> {
+        tracing::trace!(method = "validate_redirect", %uri, ?match_mode);
+
+        if uri.fragment().is_some() {
+            tracing::error!(error = "Redirect uri must not contain a fragment");
+            return Err(RedirectServiceError::InvalidRedirect);
+        }
+
+        if uri.host_str().is_some_and(|host| host.contains('*')) {
+            tracing::error!(error = "Redirect uri must not contain a wildcard host");
+            return Err(RedirectServiceError::InvalidRedirect);
+        }
+
+        let is_valid = match match_mode {
+            RedirectMatchMode::Exact => match uri.scheme() {
+                "https" => true,
+                "http" => {
+                    RedirectMatchMode::is_loopback_ip(uri) || uri.host_str() == Some("localhost")
+                }
+                _ => false,
+            },
+            RedirectMatchMode::Loopback => {
+                uri.scheme() == "http" && RedirectMatchMode::is_loopback_ip(uri)
+            }
+            // private-use schemes must be based on a domain name under the client's control
+            RedirectMatchMode::PrivateUse => {
+                !matches!(uri.scheme(), "http" | "https") && uri.scheme().contains('.')
+            }
+        };
+
+        if !is_valid {
+            tracing::error!(error = "Redirect uri is not allowed for the requested match mode");
+            return Err(RedirectServiceError::InvalidRedirect);
+        }
+
+        Ok(())
+    }
+
     pub async fn get_user_id_from_redirect_id(
         db_context: &Arc<DbContext>,
         redirect_repository: &dyn RedirectUriRepository,
@@ -143,6 +196,8 @@ pub enum RedirectServiceError {
     NotCreated,
     #[error("REDIRECT SERVICE ERROR :: Redirect not found")]
     NotFound,
+    #[error("REDIRECT SERVICE ERROR :: Redirect uri not allowed")]
+    InvalidRedirect,
     #[error("REDIRECT SERVICE ERROR :: Minimum client redirect violation")]
     TooFewRedirects,
     #[error("REDIRECT SERVICE ERROR :: Failed to delete redirect")]
@@ -0,0 +1,186 @@
+//! A local copy of a breached password corpus, e.g. the SHA-1 download of Have I Been Pwned's
+//! Pwned Passwords ordered by hash, with one `<sha-1 hex>[:<count>]` line per password. The file
+//! is never read into memory: every lookup binary searches it on disk, reading the few dozen
+//! lines it lands on along the way, which is what lets a corpus of a billion hashes be used at
+//! all.
+
+use std::{
+    cmp::Ordering,
+    fs::File,
+    io::{self, BufRead, BufReader, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use ring::digest;
+
+/// the length of a SHA-1 in hex
+const HASH_LENGTH: usize = 40;
+
+/// how many lines from the start of a corpus file are checked to be in order when it is loaded,
+/// as a file that isn't sorted by hash would silently let breached passwords through
+const SORT_CHECK_LINES: usize = 1000;
+
+/// a buffer a little longer than a line, as a search reads a line or two at every offset it
+/// seeks to
+const LINE_BUFFER_SIZE: usize = 128;
+
+enum Table {
+    /// sorted upper case hashes, for a corpus small enough to be kept in memory
+    Memory(Vec<String>),
+    /// a corpus file sorted by hash, searched on disk
+    File { path: PathBuf, size: u64 },
+}
+
+pub struct BreachedPasswords {
+    table: Table,
+}
+
+impl BreachedPasswords {
+    /// Opens a corpus file, which has to be sorted by hash. Only its first lines are read, to
+    /// check that they are in order.
+    pub fn load<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        let file = File::open(&path)?;
+        let size = file.metadata()?.len();
+
+        let mut previous = None::<String>;
+        for line in BufReader::new(file).lines().take(SORT_CHECK_LINES) {
+            let Some(hash) = Self::parse_line(line?.as_str())
+            else {
+                continue;
+            };
+
+            if previous.as_ref().is_some_and(|previous| previous > &hash) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the breached password corpus is not sorted by hash",
+                ));
+            }
+
+            previous = Some(hash);
+        }
+
+        Ok(Self {
+            table: Table::File { path, size },
+        })
+    }
+
+    /// Reads the corpus from its lines, in any order, skipping any that are not a SHA-1 in hex.
+    pub fn from_lines<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut hashes = lines
+            .into_iter()
+            .filter_map(|line| Self::parse_line(line.as_ref()))
+            .collect::<Vec<String>>();
+
+        hashes.sort_unstable();
+        hashes.dedup();
+
+        Self {
+            table: Table::Memory(hashes),
+        }
+    }
+
+    /// Whether the password is in the corpus. A corpus file that can no longer be read lets every
+    /// password through rather than turning them all down.
+    pub fn contains(&self, password: &str) -> bool {
+        let hash = Self::sha1_hex(password);
+
+        match &self.table {
+            Table::Memory(hashes) => hashes.binary_search(&hash).is_ok(),
+            Table::File { path, size } => Self::search_file(path, *size, hash.as_str())
+                .unwrap_or_else(|err| {
+                    tracing::error!(error = %err, path = %path.display());
+                    false
+                }),
+        }
+    }
+
+    /// Narrows `[low, high)` down to the line starting with `hash`. Only offsets that lines start
+    /// at are ever kept as `low`, while `high` can fall in the middle of a line, as it only bounds
+    /// where the line can start.
+    fn search_file(path: &Path, size: u64, hash: &str) -> io::Result<bool> {
+        let mut reader = BufReader::with_capacity(LINE_BUFFER_SIZE, File::open(path)?);
+        let mut line = String::new();
+
+        let (mut low, mut high) = (0, size);
+        while low < high {
+            let middle = low + (high - low) / 2;
+
+            let Some((line_hash, next)) = Self::read_hash_from(&mut reader, middle, &mut line)?
+            else {
+                high = middle;
+                continue;
+            };
+
+            match line_hash.as_str().cmp(hash) {
+                Ordering::Equal => return Ok(true),
+                Ordering::Less => low = next,
+                Ordering::Greater => high = middle,
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Reads the hash of the first line starting at or after `offset`, skipping any that don't
+    /// start with one, e.g. a trailing blank line. Returns it along with the offset of the line
+    /// after it, or `None` past the last hash.
+    fn read_hash_from(
+        reader: &mut BufReader<File>,
+        offset: u64,
+        line: &mut String,
+    ) -> io::Result<Option<(String, u64)>> {
+        let mut position = offset;
+
+        // the byte before `offset` tells whether a line starts right at it
+        if offset > 0 {
+            reader.seek(SeekFrom::Start(offset - 1))?;
+
+            let mut skipped = Vec::new();
+            position = offset - 1 + reader.read_until(b'\n', &mut skipped)? as u64;
+        } else {
+            reader.seek(SeekFrom::Start(0))?;
+        }
+
+        loop {
+            line.clear();
+            let read = reader.read_line(line)?;
+            if read == 0 {
+                return Ok(None);
+            }
+
+            position += read as u64;
+
+            if let Some(hash) = Self::parse_line(line.as_str()) {
+                return Ok(Some((hash, position)));
+            }
+        }
+    }
+
+    /// the hash a line of the corpus starts with, in upper case
+    fn parse_line(line: &str) -> Option<String> {
+        let hash = line.split(':').next().unwrap_or_default().trim();
+
+        if hash.len() != HASH_LENGTH || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        Some(hash.to_ascii_uppercase())
+    }
+
+    /// the SHA-1 of the password in upper case hex, as the corpus lists it
+    pub fn sha1_hex(password: &str) -> String {
+        digest::digest(&digest::SHA1_FOR_LEGACY_USE_ONLY, password.as_bytes())
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect()
+    }
+}
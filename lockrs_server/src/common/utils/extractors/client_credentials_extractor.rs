@@ -66,20 +66,17 @@ fn query_into_hashmap(query: &str) -> HashMap<String, Option<String>> {
 }
 
 fn get_client_from_query(query: Option<&str>) -> Option<ClientLoginCredentials> {
-    let Some(query) = query
-    else {
+    let Some(query) = query else {
         return None;
     };
 
     let query_hash = query_into_hashmap(query);
 
-    let Some(client_id) = query_hash.get("client_id")
-    else {
+    let Some(client_id) = query_hash.get("client_id") else {
         return None;
     };
 
-    let Some(client_id_val) = client_id
-    else {
+    let Some(client_id_val) = client_id else {
         return None;
     };
 
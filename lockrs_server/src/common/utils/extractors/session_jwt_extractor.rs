@@ -29,8 +29,7 @@ where
                 StatusCode::UNAUTHORIZED
             })?;
 
-        let Some(jwt) = cookies.get(JwtUtil::cookie_name())
-        else {
+        let Some(jwt) = cookies.get(JwtUtil::cookie_name()) else {
             tracing::debug!("missing session jwt cookie");
             return Err(StatusCode::UNAUTHORIZED);
         };
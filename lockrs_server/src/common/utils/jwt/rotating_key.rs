@@ -75,8 +75,7 @@ impl RotatingKey {
         }
 
         let inactive_key = self.inactive_key.load();
-        let Some(inactive_key) = inactive_key.as_ref()
-        else {
+        let Some(inactive_key) = inactive_key.as_ref() else {
             return;
         };
 
@@ -1,2 +1,7 @@
+pub mod breached_passwords;
 pub mod extractors;
 pub mod jwt;
+pub mod public_endpoint;
+pub mod signed_token;
+pub mod totp;
+pub mod webauthn;
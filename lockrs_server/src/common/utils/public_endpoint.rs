@@ -0,0 +1,137 @@
+//! Checks on the urls a client registers for the server to call, e.g. the endpoint backchannel
+//! authentication results are sent to. Such a request carries tokens and comes from inside the
+//! network, so a client must not be able to aim it at anything but a public https endpoint, like
+//! a port on the server itself or another host on its private network.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use thiserror::Error;
+use url::{Host, Url};
+
+pub struct PublicEndpointUtil;
+
+impl PublicEndpointUtil {
+    /// Checks what can be told from the url alone: that it is https, without credentials, and
+    /// does not name a loopback or private host. `allow_private` lets anything http(s) through,
+    /// for local development.
+    pub fn validate(url: &Url, allow_private: bool) -> Result<(), PublicEndpointError> {
+        if allow_private {
+            return match url.scheme() {
+                "https" | "http" if url.has_host() => Ok(()),
+                _ => Err(PublicEndpointError::NotHttps),
+            };
+        }
+
+        if url.scheme() != "https" {
+            return Err(PublicEndpointError::NotHttps);
+        }
+
+        if !url.username().is_empty() || url.password().is_some() {
+            return Err(PublicEndpointError::NotPublic);
+        }
+
+        match url.host() {
+            Some(Host::Domain(domain)) => {
+                let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+
+                if domain == "localhost" || domain.ends_with(".localhost") {
+                    return Err(PublicEndpointError::NotPublic);
+                }
+
+                Ok(())
+            }
+            Some(Host::Ipv4(ip)) if Self::is_public(&IpAddr::V4(ip)) => Ok(()),
+            Some(Host::Ipv6(ip)) if Self::is_public(&IpAddr::V6(ip)) => Ok(()),
+            _ => Err(PublicEndpointError::NotPublic),
+        }
+    }
+
+    /// Resolves the host of a url that passed `validate`, failing unless every address it
+    /// resolves to is public. The request has to be sent to these addresses, rather than
+    /// resolving the host again, or the answer could change in between.
+    pub async fn resolve(
+        url: &Url,
+        allow_private: bool,
+    ) -> Result<Vec<SocketAddr>, PublicEndpointError> {
+        Self::validate(url, allow_private)?;
+
+        let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default())
+        else {
+            return Err(PublicEndpointError::Unresolved);
+        };
+
+        // an ipv6 host is bracketed in the url, but not in what is looked up
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+
+        let addrs = tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|_| PublicEndpointError::Unresolved)?
+            .collect::<Vec<SocketAddr>>();
+
+        if addrs.is_empty() {
+            return Err(PublicEndpointError::Unresolved);
+        }
+
+        if !allow_private && !addrs.iter().all(|addr| Self::is_public(&addr.ip())) {
+            return Err(PublicEndpointError::NotPublic);
+        }
+
+        Ok(addrs)
+    }
+
+    /// whether the address can be reached over the internet, as opposed to only from the host
+    /// itself or its own network
+    pub fn is_public(ip: &IpAddr) -> bool {
+        match ip {
+            IpAddr::V4(ip) => Self::is_public_v4(ip),
+            IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+                Some(ip) => Self::is_public_v4(&ip),
+                None => Self::is_public_v6(ip),
+            },
+        }
+    }
+
+    fn is_public_v4(ip: &Ipv4Addr) -> bool {
+        let [first, second, ..] = ip.octets();
+
+        !(ip.is_unspecified()
+            || ip.is_loopback()
+            || ip.is_private()
+            || ip.is_link_local()
+            || ip.is_broadcast()
+            || ip.is_documentation()
+            || ip.is_multicast()
+            // "this network", 0.0.0.0/8
+            || first == 0
+            // shared address space for carrier-grade NAT, 100.64.0.0/10
+            || (first == 100 && (second & 0b1100_0000) == 64)
+            // benchmarking, 198.18.0.0/15
+            || (first == 198 && (second & 0b1111_1110) == 18)
+            // reserved, 240.0.0.0/4
+            || first >= 240)
+    }
+
+    fn is_public_v6(ip: &Ipv6Addr) -> bool {
+        let [first, ..] = ip.segments();
+
+        !(ip.is_unspecified()
+            || ip.is_loopback()
+            || ip.is_multicast()
+            // unique local, fc00::/7
+            || (first & 0xfe00) == 0xfc00
+            // link local, fe80::/10
+            || (first & 0xffc0) == 0xfe80
+            // documentation, 2001:db8::/32
+            || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PublicEndpointError {
+    #[error("PUBLIC ENDPOINT ERROR :: Not https")]
+    NotHttps,
+    #[error("PUBLIC ENDPOINT ERROR :: Not a public address")]
+    NotPublic,
+    #[error("PUBLIC ENDPOINT ERROR :: Host could not be resolved")]
+    Unresolved,
+}
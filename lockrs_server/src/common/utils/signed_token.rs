@@ -0,0 +1,93 @@
+//! Short lived tokens handed out in links, e.g. to verify an email address. Instead of being
+//! stored, a token carries its own claims and expiry under an HMAC-SHA256 tag, so any instance
+//! configured with the same key can check it.
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::Utc;
+use ring::hmac;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+pub struct SignedTokenUtil;
+
+impl SignedTokenUtil {
+    /// Signs `claims` until `expires_at`, a unix timestamp in seconds. `purpose` is signed along
+    /// with them, so a token handed out for one thing can never be used for another.
+    pub fn sign<T>(
+        key: &[u8],
+        purpose: &str,
+        claims: &T,
+        expires_at: i64,
+    ) -> Result<String, SignedTokenError>
+    where
+        T: Serialize,
+    {
+        let payload = SignedTokenPayload {
+            purpose: purpose.to_owned(),
+            claims,
+            exp: expires_at,
+        };
+
+        let payload = serde_json::to_vec(&payload).map_err(|_| SignedTokenError::Encode)?;
+        let payload = general_purpose::URL_SAFE_NO_PAD.encode(payload);
+        let tag = hmac::sign(&Self::key(key), payload.as_bytes());
+
+        Ok(format!(
+            "{}.{}",
+            payload,
+            general_purpose::URL_SAFE_NO_PAD.encode(tag.as_ref())
+        ))
+    }
+
+    /// Returns the claims of a token signed with `key` for `purpose`, as long as it has not
+    /// expired.
+    pub fn verify<T>(key: &[u8], purpose: &str, token: &str) -> Result<T, SignedTokenError>
+    where
+        T: DeserializeOwned,
+    {
+        let Some((payload, tag)) = token.split_once('.')
+        else {
+            return Err(SignedTokenError::InvalidToken);
+        };
+
+        let tag = general_purpose::URL_SAFE_NO_PAD
+            .decode(tag)
+            .map_err(|_| SignedTokenError::InvalidToken)?;
+
+        hmac::verify(&Self::key(key), payload.as_bytes(), tag.as_slice())
+            .map_err(|_| SignedTokenError::InvalidToken)?;
+
+        let payload = general_purpose::URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| SignedTokenError::InvalidToken)?;
+        let payload = serde_json::from_slice::<SignedTokenPayload<T>>(payload.as_slice())
+            .map_err(|_| SignedTokenError::InvalidToken)?;
+
+        if payload.purpose != purpose {
+            return Err(SignedTokenError::InvalidToken);
+        }
+
+        if payload.exp <= Utc::now().timestamp() {
+            return Err(SignedTokenError::Expired);
+        }
+
+        Ok(payload.claims)
+    }
+
+    fn key(key: &[u8]) -> hmac::Key {
+        hmac::Key::new(hmac::HMAC_SHA256, key)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SignedTokenPayload<T> {
+    purpose: String,
+    claims: T,
+    exp: i64,
+}
+
+#[derive(Debug)]
+pub enum SignedTokenError {
+    InvalidToken,
+    Expired,
+    Encode,
+}
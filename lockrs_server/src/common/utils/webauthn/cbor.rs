@@ -0,0 +1,178 @@
+//! Just enough of a CBOR (RFC 8949) decoder to read attestation objects and COSE keys.
+//! Authenticators encode both canonically, so indefinite lengths and floats are rejected rather
+//! than supported.
+
+/// how deeply items may nest, well past anything an authenticator sends, so that a crafted
+/// payload can not exhaust the stack
+const MAX_DEPTH: usize = 16;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CborValue {
+    Integer(i64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<CborValue>),
+    Map(Vec<(CborValue, CborValue)>),
+    Bool(bool),
+    Null,
+}
+
+impl CborValue {
+    /// the value stored under the integer `key`, if this is a map
+    pub fn get_by_int(&self, key: i64) -> Option<&CborValue> {
+        self.get(|k| k == &CborValue::Integer(key))
+    }
+
+    /// the value stored under the text `key`, if this is a map
+    pub fn get_by_text(&self, key: &str) -> Option<&CborValue> {
+        self.get(|k| matches!(k, CborValue::Text(text) if text == key))
+    }
+
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Self::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(value) => Some(value.as_slice()),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Text(value) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    fn get<F>(&self, predicate: F) -> Option<&CborValue>
+    where
+        F: Fn(&CborValue) -> bool,
+    {
+        match self {
+            Self::Map(entries) => entries
+                .iter()
+                .find(|(key, _)| predicate(key))
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes the first item in `bytes`, returning it alongside the number of bytes it took up, as
+/// a COSE key in authenticator data is followed by whatever extensions the authenticator added.
+pub fn decode(bytes: &[u8]) -> Result<(CborValue, usize), CborError> {
+    let mut decoder = Decoder { bytes, position: 0 };
+    let value = decoder.decode_item(0)?;
+
+    Ok((value, decoder.position))
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn decode_item(&mut self, depth: usize) -> Result<CborValue, CborError> {
+        if depth > MAX_DEPTH {
+            return Err(CborError::Unsupported);
+        }
+
+        let initial = self.take(1)?[0];
+        let major_type = initial >> 5;
+        let argument = self.argument(initial & 0x1f)?;
+
+        match major_type {
+            0 => i64::try_from(argument)
+                .map(CborValue::Integer)
+                .map_err(|_| CborError::Unsupported),
+            1 => i64::try_from(argument)
+                .map(|value| CborValue::Integer(-1 - value))
+                .map_err(|_| CborError::Unsupported),
+            2 => Ok(CborValue::Bytes(self.take_len(argument)?.to_vec())),
+            3 => String::from_utf8(self.take_len(argument)?.to_vec())
+                .map(CborValue::Text)
+                .map_err(|_| CborError::Invalid),
+            4 => {
+                let len = self.item_count(argument)?;
+
+                (0..len)
+                    .map(|_| self.decode_item(depth + 1))
+                    .collect::<Result<Vec<CborValue>, CborError>>()
+                    .map(CborValue::Array)
+            }
+            5 => {
+                let len = self.item_count(argument)?;
+
+                (0..len)
+                    .map(|_| Ok((self.decode_item(depth + 1)?, self.decode_item(depth + 1)?)))
+                    .collect::<Result<Vec<(CborValue, CborValue)>, CborError>>()
+                    .map(CborValue::Map)
+            }
+            // tags only annotate the item that follows them
+            6 => self.decode_item(depth + 1),
+            _ => match initial & 0x1f {
+                20 => Ok(CborValue::Bool(false)),
+                21 => Ok(CborValue::Bool(true)),
+                22 | 23 => Ok(CborValue::Null),
+                _ => Err(CborError::Unsupported),
+            },
+        }
+    }
+
+    /// the argument following the initial byte, a length, count or the value itself
+    fn argument(&mut self, additional_info: u8) -> Result<u64, CborError> {
+        let len = match additional_info {
+            0..=23 => return Ok(additional_info as u64),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err(CborError::Unsupported),
+        };
+
+        Ok(self
+            .take(len)?
+            .iter()
+            .fold(0u64, |value, byte| (value << 8) | *byte as u64))
+    }
+
+    /// every item takes up at least one byte, so a count past the bytes left is never valid
+    fn item_count(&self, argument: u64) -> Result<usize, CborError> {
+        usize::try_from(argument)
+            .ok()
+            .filter(|len| *len <= self.bytes.len() - self.position)
+            .ok_or(CborError::UnexpectedEnd)
+    }
+
+    fn take_len(&mut self, argument: u64) -> Result<&'a [u8], CborError> {
+        let len = usize::try_from(argument).map_err(|_| CborError::UnexpectedEnd)?;
+
+        self.take(len)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CborError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(CborError::UnexpectedEnd)?;
+
+        let taken = &self.bytes[self.position..end];
+        self.position = end;
+
+        Ok(taken)
+    }
+}
+
+#[derive(Debug)]
+pub enum CborError {
+    UnexpectedEnd,
+    Unsupported,
+    Invalid,
+}
@@ -0,0 +1,294 @@
+//! Verification of the responses to WebAuthn registration and authentication ceremonies, for
+//! passkeys signing with ES256, EdDSA or RS256. Only `none` attestation is asked for, so the
+//! attestation statement of a new credential is not verified: any authenticator is trusted to be
+//! what it says it is, as most relying parties outside of enterprise deployments do.
+
+mod cbor;
+
+use base64::{engine::general_purpose, Engine as _};
+use ring::{
+    constant_time, digest,
+    rand::{SecureRandom, SystemRandom},
+    signature::{self, RsaPublicKeyComponents, UnparsedPublicKey},
+};
+use serde::Deserialize;
+
+pub use self::cbor::*;
+
+/// the name the authenticator shows alongside a passkey
+pub const WEBAUTHN_RP_NAME: &str = "lockrs";
+
+/// the COSE algorithm identifiers of the supported signatures, in order of preference
+pub const COSE_ALG_ES256: i64 = -7;
+pub const COSE_ALG_EDDSA: i64 = -8;
+pub const COSE_ALG_RS256: i64 = -257;
+pub const SUPPORTED_ALGORITHMS: [i64; 3] = [COSE_ALG_ES256, COSE_ALG_EDDSA, COSE_ALG_RS256];
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+const CHALLENGE_LEN: usize = 32;
+/// rpIdHash, flags and signCount
+const AUTHENTICATOR_DATA_LEN: usize = 37;
+/// aaguid and credentialIdLength
+const ATTESTED_CREDENTIAL_DATA_LEN: usize = 18;
+
+// the COSE key parameters, RFC 9053
+const COSE_KEY_KTY: i64 = 1;
+const COSE_KEY_ALG: i64 = 3;
+const COSE_KTY_OKP: i64 = 1;
+const COSE_KTY_EC2: i64 = 2;
+const COSE_KTY_RSA: i64 = 3;
+const COSE_KEY_CRV: i64 = -1;
+const COSE_KEY_X: i64 = -2;
+const COSE_KEY_Y: i64 = -3;
+const COSE_CRV_P256: i64 = 1;
+const COSE_CRV_ED25519: i64 = 6;
+const COSE_RSA_N: i64 = -1;
+const COSE_RSA_E: i64 = -2;
+
+pub struct WebauthnUtil;
+
+impl WebauthnUtil {
+    /// Returns a new random challenge, base64url encoded without padding as it comes back in the
+    /// client data.
+    pub fn generate_challenge() -> Result<String, WebauthnError> {
+        let mut buffer = [0u8; CHALLENGE_LEN];
+        SystemRandom::new()
+            .fill(&mut buffer)
+            .map_err(|_| WebauthnError::Random)?;
+
+        Ok(general_purpose::URL_SAFE_NO_PAD.encode(buffer))
+    }
+
+    /// Checks the client data the browser signed over is for the expected ceremony, challenge
+    /// and origin.
+    pub fn verify_client_data(
+        client_data_json: &[u8],
+        ceremony: &str,
+        challenge: &str,
+        origin: &str,
+    ) -> Result<(), WebauthnError> {
+        let client_data = serde_json::from_slice::<CollectedClientData>(client_data_json)
+            .map_err(|_| WebauthnError::InvalidClientData)?;
+
+        if client_data.ceremony != ceremony
+            || client_data.origin != origin
+            || client_data.cross_origin
+        {
+            return Err(WebauthnError::InvalidClientData);
+        }
+
+        constant_time::verify_slices_are_equal(
+            client_data.challenge.as_bytes(),
+            challenge.as_bytes(),
+        )
+        .map_err(|_| WebauthnError::InvalidClientData)
+    }
+
+    /// Reads the authenticator data out of the attestation object returned on registration.
+    pub fn parse_attestation_object(
+        attestation_object: &[u8],
+    ) -> Result<AuthenticatorData, WebauthnError> {
+        let (attestation_object, _) =
+            decode(attestation_object).map_err(|_| WebauthnError::InvalidAttestation)?;
+
+        let authenticator_data = attestation_object
+            .get_by_text("authData")
+            .and_then(CborValue::as_bytes)
+            .ok_or(WebauthnError::InvalidAttestation)?;
+
+        Self::parse_authenticator_data(authenticator_data)
+    }
+
+    pub fn parse_authenticator_data(bytes: &[u8]) -> Result<AuthenticatorData, WebauthnError> {
+        if bytes.len() < AUTHENTICATOR_DATA_LEN {
+            return Err(WebauthnError::InvalidAuthenticatorData);
+        }
+
+        let rp_id_hash = bytes[0..32].to_vec();
+        let flags = bytes[32];
+        let sign_count = u32::from_be_bytes([bytes[33], bytes[34], bytes[35], bytes[36]]);
+
+        let attested_credential = match flags & FLAG_ATTESTED_CREDENTIAL_DATA {
+            0 => None,
+            _ => Some(Self::parse_attested_credential(
+                &bytes[AUTHENTICATOR_DATA_LEN..],
+            )?),
+        };
+
+        Ok(AuthenticatorData {
+            rp_id_hash,
+            flags,
+            sign_count,
+            attested_credential,
+        })
+    }
+
+    /// Whether the authenticator data was produced for `rp_id`.
+    pub fn verify_rp_id_hash(
+        authenticator_data: &AuthenticatorData,
+        rp_id: &str,
+    ) -> Result<(), WebauthnError> {
+        let expected = digest::digest(&digest::SHA256, rp_id.as_bytes());
+
+        constant_time::verify_slices_are_equal(
+            authenticator_data.rp_id_hash.as_slice(),
+            expected.as_ref(),
+        )
+        .map_err(|_| WebauthnError::InvalidAuthenticatorData)
+    }
+
+    /// Returns the algorithm of a COSE encoded public key, if it is one that can be verified.
+    pub fn public_key_algorithm(public_key: &[u8]) -> Result<i64, WebauthnError> {
+        Ok(Self::parse_public_key(public_key)?.algorithm())
+    }
+
+    /// Verifies an assertion signature, made over the authenticator data followed by the sha256
+    /// of the client data, against a COSE encoded public key.
+    pub fn verify_signature(
+        public_key: &[u8],
+        authenticator_data: &[u8],
+        client_data_json: &[u8],
+        signature: &[u8],
+    ) -> Result<(), WebauthnError> {
+        let client_data_hash = digest::digest(&digest::SHA256, client_data_json);
+        let message = [authenticator_data, client_data_hash.as_ref()].concat();
+
+        let verified = match Self::parse_public_key(public_key)? {
+            CosePublicKey::Es256(point) => {
+                UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_ASN1, point)
+                    .verify(message.as_slice(), signature)
+            }
+            CosePublicKey::Ed25519(point) => UnparsedPublicKey::new(&signature::ED25519, point)
+                .verify(message.as_slice(), signature),
+            CosePublicKey::Rs256 { n, e } => RsaPublicKeyComponents { n, e }.verify(
+                &signature::RSA_PKCS1_2048_8192_SHA256,
+                message.as_slice(),
+                signature,
+            ),
+        };
+
+        verified.map_err(|_| WebauthnError::InvalidSignature)
+    }
+
+    fn parse_attested_credential(bytes: &[u8]) -> Result<AttestedCredential, WebauthnError> {
+        if bytes.len() < ATTESTED_CREDENTIAL_DATA_LEN {
+            return Err(WebauthnError::InvalidAuthenticatorData);
+        }
+
+        let credential_id_len = u16::from_be_bytes([bytes[16], bytes[17]]) as usize;
+        let credential_id = bytes
+            .get(ATTESTED_CREDENTIAL_DATA_LEN..ATTESTED_CREDENTIAL_DATA_LEN + credential_id_len)
+            .ok_or(WebauthnError::InvalidAuthenticatorData)?;
+
+        let public_key = &bytes[ATTESTED_CREDENTIAL_DATA_LEN + credential_id_len..];
+        let (_, public_key_len) =
+            decode(public_key).map_err(|_| WebauthnError::InvalidAuthenticatorData)?;
+
+        Ok(AttestedCredential {
+            credential_id: credential_id.to_vec(),
+            public_key: public_key[..public_key_len].to_vec(),
+        })
+    }
+
+    fn parse_public_key(public_key: &[u8]) -> Result<CosePublicKey, WebauthnError> {
+        let (key, _) = decode(public_key).map_err(|_| WebauthnError::UnsupportedAlgorithm)?;
+
+        let int_param = |label| key.get_by_int(label).and_then(CborValue::as_integer);
+        let bytes_param = |label| {
+            key.get_by_int(label)
+                .and_then(CborValue::as_bytes)
+                .map(|bytes| bytes.to_vec())
+                .ok_or(WebauthnError::UnsupportedAlgorithm)
+        };
+
+        match (int_param(COSE_KEY_KTY), int_param(COSE_KEY_ALG)) {
+            (Some(COSE_KTY_EC2), Some(COSE_ALG_ES256))
+                if int_param(COSE_KEY_CRV) == Some(COSE_CRV_P256) =>
+            {
+                // ring takes the uncompressed SEC1 encoding of the point
+                let point = [
+                    vec![0x04],
+                    bytes_param(COSE_KEY_X)?,
+                    bytes_param(COSE_KEY_Y)?,
+                ];
+
+                Ok(CosePublicKey::Es256(point.concat()))
+            }
+            (Some(COSE_KTY_OKP), Some(COSE_ALG_EDDSA))
+                if int_param(COSE_KEY_CRV) == Some(COSE_CRV_ED25519) =>
+            {
+                Ok(CosePublicKey::Ed25519(bytes_param(COSE_KEY_X)?))
+            }
+            (Some(COSE_KTY_RSA), Some(COSE_ALG_RS256)) => Ok(CosePublicKey::Rs256 {
+                n: bytes_param(COSE_RSA_N)?,
+                e: bytes_param(COSE_RSA_E)?,
+            }),
+            _ => Err(WebauthnError::UnsupportedAlgorithm),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    ceremony: String,
+    challenge: String,
+    origin: String,
+    #[serde(default, rename = "crossOrigin")]
+    cross_origin: bool,
+}
+
+enum CosePublicKey {
+    Es256(Vec<u8>),
+    Ed25519(Vec<u8>),
+    Rs256 { n: Vec<u8>, e: Vec<u8> },
+}
+
+impl CosePublicKey {
+    fn algorithm(&self) -> i64 {
+        match self {
+            Self::Es256(_) => COSE_ALG_ES256,
+            Self::Ed25519(_) => COSE_ALG_EDDSA,
+            Self::Rs256 { .. } => COSE_ALG_RS256,
+        }
+    }
+}
+
+pub struct AuthenticatorData {
+    pub rp_id_hash: Vec<u8>,
+    pub flags: u8,
+    pub sign_count: u32,
+    /// the new credential, present on registration
+    pub attested_credential: Option<AttestedCredential>,
+}
+
+impl AuthenticatorData {
+    pub fn is_user_present(&self) -> bool {
+        self.flags & FLAG_USER_PRESENT != 0
+    }
+
+    /// whether the authenticator checked who the user is, e.g. with a PIN or biometric, rather
+    /// than only that someone is there
+    pub fn is_user_verified(&self) -> bool {
+        self.flags & FLAG_USER_VERIFIED != 0
+    }
+}
+
+pub struct AttestedCredential {
+    pub credential_id: Vec<u8>,
+    /// the COSE encoded public key
+    pub public_key: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum WebauthnError {
+    InvalidClientData,
+    InvalidAuthenticatorData,
+    InvalidAttestation,
+    InvalidSignature,
+    UnsupportedAlgorithm,
+    Random,
+}
@@ -0,0 +1,6 @@
+pub mod repositories;
+
+mod lru_cache;
+mod repository_cache;
+
+pub use self::{lru_cache::*, repository_cache::*};
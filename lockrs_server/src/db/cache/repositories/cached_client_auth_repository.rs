@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+
+use crate::{
+    db::{
+        cache::RepositoryCache,
+        repositories::{ClientAuthRepository, RepositoryError},
+        DbContext,
+    },
+    models::{ClientAuthModel, ClientSecretCreateModel, ClientSecretModel, RedirectCreateModel},
+};
+
+pub struct CachedClientAuthRepository {
+    inner: Box<dyn ClientAuthRepository>,
+    cache: Arc<RepositoryCache>,
+}
+
+impl CachedClientAuthRepository {
+    pub fn new(inner: Box<dyn ClientAuthRepository>, cache: Arc<RepositoryCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl ClientAuthRepository for CachedClientAuthRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_create: &ClientAuthModel,
+        secret_create: Option<&ClientSecretCreateModel>,
+        redirect_create: &RedirectCreateModel,
+    ) -> Result<ClientAuthModel, RepositoryError> {
+        let client = self
+            .inner
+            .create(db_context, client_create, secret_create, redirect_create)
+            .await?;
+
+        // lookups of an unknown client may have cached an empty list of redirects or secrets
+        let group = RepositoryCache::client_group(client.id.as_str());
+        self.cache.invalidate(db_context, group.as_str()).await;
+
+        Ok(client)
+    }
+
+    async fn get_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<ClientAuthModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", id);
+
+        let group = RepositoryCache::client_group(id);
+
+        self.cache
+            .get_or_load(db_context, group.as_str(), "client_auth", || {
+                self.inner.get_by_id(db_context, id)
+            })
+            .await
+    }
+
+    async fn get_active_secrets_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<ClientSecretModel>, RepositoryError> {
+        tracing::trace!(method = "get_active_secrets_by_client_id", client_id);
+
+        let group = RepositoryCache::client_group(client_id);
+
+        let client_secrets = self
+            .cache
+            .get_or_load(db_context, group.as_str(), "secrets", || {
+                self.inner
+                    .get_active_secrets_by_client_id(db_context, client_id)
+            })
+            .await?;
+
+        // a secret that was active when it was cached may have expired since
+        let now = Utc::now().naive_utc();
+
+        Ok(client_secrets
+            .into_iter()
+            .filter(|client_secret| {
+                client_secret
+                    .expires_at
+                    .is_none_or(|expires_at| expires_at > now)
+            })
+            .collect::<Vec<ClientSecretModel>>())
+    }
+
+    async fn rotate_secret(
+        &self,
+        db_context: &Arc<DbContext>,
+        secret_create: &ClientSecretCreateModel,
+        previous_expires_at: &NaiveDateTime,
+    ) -> Result<ClientSecretModel, RepositoryError> {
+        let client_secret = self
+            .inner
+            .rotate_secret(db_context, secret_create, previous_expires_at)
+            .await?;
+
+        let group = RepositoryCache::client_group(secret_create.client_id.as_str());
+        self.cache.invalidate(db_context, group.as_str()).await;
+
+        Ok(client_secret)
+    }
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        cache::RepositoryCache,
+        repositories::{ClientRepository, RepositoryError},
+        DbContext,
+    },
+    models::{ClientModel, ClientUpdateModel},
+};
+
+pub struct CachedClientRepository {
+    inner: Box<dyn ClientRepository>,
+    cache: Arc<RepositoryCache>,
+}
+
+impl CachedClientRepository {
+    pub fn new(inner: Box<dyn ClientRepository>, cache: Arc<RepositoryCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl ClientRepository for CachedClientRepository {
+    async fn get_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<ClientModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", id);
+
+        let group = RepositoryCache::client_group(id);
+
+        self.cache
+            .get_or_load(db_context, group.as_str(), "client", || {
+                self.inner.get_by_id(db_context, id)
+            })
+            .await
+    }
+
+    async fn get_all_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<Vec<ClientModel>, RepositoryError> {
+        self.inner.get_all_by_user_id(db_context, id).await
+    }
+
+    async fn update_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &str,
+        client_update: &ClientUpdateModel,
+    ) -> Result<ClientModel, RepositoryError> {
+        let client = self
+            .inner
+            .update_by_id(db_context, id, client_update)
+            .await?;
+
+        let group = RepositoryCache::client_group(id);
+        self.cache.invalidate(db_context, group.as_str()).await;
+
+        Ok(client)
+    }
+
+    async fn delete_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<(), RepositoryError> {
+        self.inner.delete_by_id(db_context, id).await?;
+
+        let group = RepositoryCache::client_group(id);
+        self.cache.invalidate(db_context, group.as_str()).await;
+
+        Ok(())
+    }
+}
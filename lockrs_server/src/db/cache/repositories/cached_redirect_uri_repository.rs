@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        cache::RepositoryCache,
+        repositories::{RedirectUriRepository, RepositoryError},
+        DbContext,
+    },
+    models::{RedirectCreateModel, RedirectModel},
+};
+
+pub struct CachedRedirectUriRepository {
+    inner: Box<dyn RedirectUriRepository>,
+    cache: Arc<RepositoryCache>,
+}
+
+impl CachedRedirectUriRepository {
+    pub fn new(inner: Box<dyn RedirectUriRepository>, cache: Arc<RepositoryCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl RedirectUriRepository for CachedRedirectUriRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        redirect_create: &RedirectCreateModel,
+    ) -> Result<RedirectModel, RepositoryError> {
+        let redirect = self.inner.create(db_context, redirect_create).await?;
+
+        let group = RepositoryCache::client_group(redirect.client_id.as_str());
+        self.cache.invalidate(db_context, group.as_str()).await;
+
+        Ok(redirect)
+    }
+
+    async fn get_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<RedirectModel, RepositoryError> {
+        self.inner.get_by_id(db_context, id).await
+    }
+
+    async fn get_by_uri(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        uri: &Url,
+    ) -> Result<RedirectModel, RepositoryError> {
+        self.inner.get_by_uri(db_context, client_id, uri).await
+    }
+
+    async fn get_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<Uuid, RepositoryError> {
+        self.inner.get_user_id(db_context, id).await
+    }
+
+    async fn get_all_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<RedirectModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_client_id", client_id);
+
+        let group = RepositoryCache::client_group(client_id);
+
+        self.cache
+            .get_or_load(db_context, group.as_str(), "redirects", || {
+                self.inner.get_all_by_client_id(db_context, client_id)
+            })
+            .await
+    }
+
+    async fn delete_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        // the client is only known from the redirect, which is gone once deleted
+        let redirect = self.inner.get_by_id(db_context, id).await?;
+
+        self.inner.delete_by_id(db_context, id).await?;
+
+        let group = RepositoryCache::client_group(redirect.client_id.as_str());
+        self.cache.invalidate(db_context, group.as_str()).await;
+
+        Ok(())
+    }
+}
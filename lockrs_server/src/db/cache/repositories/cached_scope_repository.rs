@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    db::{
+        cache::RepositoryCache,
+        repositories::{RepositoryError, ScopeRepository},
+        DbContext,
+    },
+    oauth2::v1::models::{
+        AllowedScopeModel, ScopeCreateModel, ScopeDefinitionModel, ScopeModel, ScopeUpdateModel,
+    },
+};
+
+pub struct CachedScopeRepository {
+    inner: Box<dyn ScopeRepository>,
+    cache: Arc<RepositoryCache>,
+}
+
+impl CachedScopeRepository {
+    pub fn new(inner: Box<dyn ScopeRepository>, cache: Arc<RepositoryCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl ScopeRepository for CachedScopeRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        scope_create: &ScopeCreateModel,
+    ) -> Result<ScopeDefinitionModel, RepositoryError> {
+        let scope = self.inner.create(db_context, scope_create).await?;
+
+        self.cache
+            .invalidate(db_context, RepositoryCache::SCOPES_GROUP)
+            .await;
+
+        Ok(scope)
+    }
+
+    async fn get_from_list(
+        &self,
+        db_context: &Arc<DbContext>,
+        scopes_list: &[String],
+    ) -> Result<ScopeModel, RepositoryError> {
+        tracing::trace!(
+            method = "get_from_list",
+            scopes = ?scopes_list
+        );
+
+        let field = scopes_list.join(" ");
+
+        self.cache
+            .get_or_load(
+                db_context,
+                RepositoryCache::SCOPES_GROUP,
+                field.as_str(),
+                || self.inner.get_from_list(db_context, scopes_list),
+            )
+            .await
+    }
+
+    async fn get_all_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<ScopeDefinitionModel>, RepositoryError> {
+        self.inner.get_all_by_client_id(db_context, client_id).await
+    }
+
+    async fn get_allowed_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<AllowedScopeModel>, RepositoryError> {
+        tracing::trace!(method = "get_allowed_by_client_id", client_id);
+
+        let group = RepositoryCache::client_group(client_id);
+
+        self.cache
+            .get_or_load(db_context, group.as_str(), "allowed_scopes", || {
+                self.inner.get_allowed_by_client_id(db_context, client_id)
+            })
+            .await
+    }
+
+    async fn replace_allowed_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        allowed_scopes: &[AllowedScopeModel],
+    ) -> Result<Vec<AllowedScopeModel>, RepositoryError> {
+        let allowed_scopes = self
+            .inner
+            .replace_allowed_by_client_id(db_context, client_id, allowed_scopes)
+            .await?;
+
+        let group = RepositoryCache::client_group(client_id);
+        self.cache.invalidate(db_context, group.as_str()).await;
+
+        Ok(allowed_scopes)
+    }
+
+    async fn update_by_name(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
+        scope_update: &ScopeUpdateModel,
+    ) -> Result<ScopeDefinitionModel, RepositoryError> {
+        let scope = self
+            .inner
+            .update_by_name(db_context, client_id, name, scope_update)
+            .await?;
+
+        self.cache
+            .invalidate(db_context, RepositoryCache::SCOPES_GROUP)
+            .await;
+
+        Ok(scope)
+    }
+
+    async fn delete_by_name(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
+    ) -> Result<(), RepositoryError> {
+        self.inner
+            .delete_by_name(db_context, client_id, name)
+            .await?;
+
+        // deleting a scope also takes it off every allowlist it is on. A client scope is only ever
+        // allowed for the client that owns it, but a global scope may be allowed for any client
+        if name.contains(':') {
+            self.cache
+                .invalidate(db_context, RepositoryCache::SCOPES_GROUP)
+                .await;
+
+            let group = RepositoryCache::client_group(client_id);
+            self.cache.invalidate(db_context, group.as_str()).await;
+        } else {
+            self.cache.invalidate_all(db_context).await;
+        }
+
+        Ok(())
+    }
+}
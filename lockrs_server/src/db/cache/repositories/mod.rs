@@ -0,0 +1,9 @@
+mod cached_client_auth_repository;
+mod cached_client_repository;
+mod cached_redirect_uri_repository;
+mod cached_scope_repository;
+
+pub use self::{
+    cached_client_auth_repository::*, cached_client_repository::*,
+    cached_redirect_uri_repository::*, cached_scope_repository::*,
+};
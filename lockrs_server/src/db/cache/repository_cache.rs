@@ -0,0 +1,372 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use futures_util::{FutureExt, StreamExt};
+use redis::AsyncCommands;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::db::{
+    cache::LruCache, repositories::RepositoryError, DbContext, ManagedAsyncRedisConnection,
+};
+
+/// the redis channel every instance listens on for groups to drop from its local cache
+pub const CACHE_INVALIDATION_CHANNEL: &str = "cache:invalidate";
+
+/// the group broadcast to drop every group at once
+const ALL_GROUPS: &str = "*";
+
+/// how long to wait before subscribing again after losing the redis connection
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+struct CachedValue {
+    json: String,
+    cached_at: Instant,
+}
+
+/// A read-through cache for the lookups made on every oauth2 request, kept in a process-local LRU
+/// and optionally in redis as well so that a new instance starts warm.
+///
+/// Entries are grouped, e.g. everything cached about a client shares the client's group, and are
+/// only ever invalidated a group at a time. Invalidations are broadcast through redis pub/sub
+/// whenever redis is configured, so a write made on one instance reaches the local cache of every
+/// other instance. Entries also expire after `ttl`, which bounds how stale a lookup can be if a
+/// broadcast is missed or a row changes without going through a cached repository.
+pub struct RepositoryCache {
+    local: Mutex<LruCache<String, HashMap<String, CachedValue>>>,
+    ttl: Duration,
+    use_redis: bool,
+    /// bumped on every invalidation, so a lookup that raced a write doesn't cache what it read
+    epoch: AtomicU64,
+}
+
+impl RepositoryCache {
+    pub const SCOPES_GROUP: &'static str = "scopes";
+
+    /// `capacity` is the number of groups kept in the local cache.
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            local: Mutex::new(LruCache::new(capacity)),
+            ttl,
+            use_redis: false,
+            epoch: AtomicU64::new(0),
+        }
+    }
+
+    /// Also keeps entries in redis, shared by every instance.
+    pub fn with_redis(self) -> Self {
+        Self {
+            use_redis: true,
+            ..self
+        }
+    }
+
+    pub fn client_group(client_id: &str) -> String {
+        format!("client:{}", client_id)
+    }
+
+    /// Returns the cached value of `field` in `group`, calling `load` and caching what it returns
+    /// on a miss. Errors are never cached.
+    pub async fn get_or_load<T, F, Fut>(
+        &self,
+        db_context: &Arc<DbContext>,
+        group: &str,
+        field: &str,
+        load: F,
+    ) -> Result<T, RepositoryError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, RepositoryError>>,
+    {
+        // a unit of work may read rows it has yet to commit, or that it will roll back
+        if db_context.in_transaction() {
+            return load().await;
+        }
+
+        if let Some(value) = self.get(db_context, group, field).await {
+            return Ok(value);
+        }
+
+        metrics::increment_counter!("lockrs_cache_misses_total");
+
+        let epoch = self.epoch.load(Ordering::Acquire);
+        let value = load().await?;
+
+        self.put(db_context, group, field, &value, epoch).await;
+
+        Ok(value)
+    }
+
+    /// Drops every entry in `group`, here and on every other instance. Inside a unit of work this
+    /// waits for it to commit, as until then another request could cache the row as it was.
+    pub async fn invalidate(self: &Arc<Self>, db_context: &Arc<DbContext>, group: &str) {
+        tracing::trace!(method = "invalidate", group);
+
+        let cache = self.clone();
+        let group = group.to_owned();
+
+        db_context
+            .after_commit(move |db_context| {
+                async move { cache.broadcast_invalidation(&db_context, &group).await }.boxed()
+            })
+            .await;
+    }
+
+    /// Drops every entry in every group, here and on every other instance, for writes that may
+    /// touch any group, e.g. removing a global scope from the allowlist of every client.
+    pub async fn invalidate_all(self: &Arc<Self>, db_context: &Arc<DbContext>) {
+        self.invalidate(db_context, ALL_GROUPS).await;
+    }
+
+    async fn broadcast_invalidation(&self, db_context: &Arc<DbContext>, group: &str) {
+        self.invalidate_local(group);
+
+        // without redis there is neither a shared tier nor any other instance to tell
+        let Ok(mut conn) = db_context.get_redis_connection().await
+        else {
+            return;
+        };
+
+        let mut pipeline = redis::pipe();
+        if self.use_redis {
+            let keys = match group {
+                ALL_GROUPS => Self::get_redis_keys(&mut conn).await,
+                group => vec![Self::into_redis_key(group)],
+            };
+
+            if !keys.is_empty() {
+                pipeline.del(keys).ignore();
+            }
+        }
+        pipeline.publish(CACHE_INVALIDATION_CHANNEL, group).ignore();
+
+        if let Err(err) = pipeline.query_async::<_, ()>(&mut conn).await {
+            tracing::error!(error = %err, group, "Failed to broadcast a cache invalidation");
+        }
+    }
+
+    /// Listens for invalidations broadcast by other instances, for as long as the returned task
+    /// runs.
+    pub fn subscribe(self: &Arc<Self>, redis_url: &str) -> JoinHandle<()> {
+        let cache = self.clone();
+        let redis_url = redis_url.to_owned();
+
+        tokio::spawn(async move {
+            loop {
+                if let Err(err) = cache.listen(redis_url.as_str()).await {
+                    tracing::error!(error = %err, "Cache invalidation subscription failed");
+                }
+
+                // anything broadcast while disconnected was missed, so nothing cached locally can
+                // be trusted any more
+                cache.clear_local();
+
+                tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+            }
+        })
+    }
+
+    async fn listen(&self, redis_url: &str) -> redis::RedisResult<()> {
+        let client = redis::Client::open(redis_url)?;
+        let mut pubsub = client.get_async_connection().await?.into_pubsub();
+        pubsub.subscribe(CACHE_INVALIDATION_CHANNEL).await?;
+
+        // an invalidation may have been broadcast before the subscription took effect
+        self.clear_local();
+
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            let group = message.get_payload::<String>()?;
+            self.invalidate_local(group.as_str());
+        }
+
+        Ok(())
+    }
+
+    /// every group kept in redis, scanned rather than listed with KEYS so redis is never blocked
+    async fn get_redis_keys(conn: &mut ManagedAsyncRedisConnection) -> Vec<String> {
+        match conn
+            .scan_match::<_, String>(Self::into_redis_key(ALL_GROUPS))
+            .await
+        {
+            Ok(keys) => keys.collect::<Vec<String>>().await,
+            Err(err) => {
+                tracing::error!(error = %err, "Failed to list cached groups in redis");
+                Vec::new()
+            }
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        db_context: &Arc<DbContext>,
+        group: &str,
+        field: &str,
+    ) -> Option<T> {
+        let json = match self.get_local(group, field) {
+            Some(json) => {
+                metrics::increment_counter!("lockrs_cache_hits_total", "tier" => "local");
+                json
+            }
+            None => {
+                let epoch = self.epoch.load(Ordering::Acquire);
+                let json = self.get_redis(db_context, group, field).await?;
+                metrics::increment_counter!("lockrs_cache_hits_total", "tier" => "redis");
+
+                self.put_local(group, field, json.as_str(), epoch);
+                json
+            }
+        };
+
+        match serde_json::from_str::<T>(json.as_str()) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                // e.g. an entry written by an instance running an older version of a model
+                tracing::error!(
+                    error = %err,
+                    group,
+                    field,
+                    "Invalid JSON data format for cached value"
+                );
+                self.invalidate_local(group);
+
+                None
+            }
+        }
+    }
+
+    async fn put<T: Serialize>(
+        &self,
+        db_context: &Arc<DbContext>,
+        group: &str,
+        field: &str,
+        value: &T,
+        epoch: u64,
+    ) {
+        let json = match serde_json::to_string(value) {
+            Ok(json) => json,
+            Err(err) => {
+                tracing::error!(error = %err, group, field, "Failed to serialize cached value");
+                return;
+            }
+        };
+
+        if !self.put_local(group, field, json.as_str(), epoch) || !self.use_redis {
+            return;
+        }
+
+        let Ok(mut conn) = db_context.get_redis_connection().await
+        else {
+            return;
+        };
+
+        let key = Self::into_redis_key(group);
+        let result = redis::pipe()
+            .hset(key.as_str(), field, json.as_str())
+            .ignore()
+            .expire(key.as_str(), self.ttl.as_secs() as usize)
+            .ignore()
+            .query_async::<_, ()>(&mut conn)
+            .await;
+
+        if let Err(err) = result {
+            tracing::error!(error = %err, group, field, "Failed to store cached value in redis");
+        }
+    }
+
+    async fn get_redis(
+        &self,
+        db_context: &Arc<DbContext>,
+        group: &str,
+        field: &str,
+    ) -> Option<String> {
+        if !self.use_redis {
+            return None;
+        }
+
+        let mut conn = db_context.get_redis_connection().await.ok()?;
+
+        redis::cmd("HGET")
+            .arg(Self::into_redis_key(group))
+            .arg(field)
+            .query_async::<_, Option<String>>(&mut conn)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = %err, group, field, "Failed to read cached value from redis");
+            })
+            .ok()
+            .flatten()
+    }
+
+    fn get_local(&self, group: &str, field: &str) -> Option<String> {
+        let mut local = self.local.lock().unwrap();
+        let entries = local.get_mut(&group.to_owned())?;
+
+        match entries.get(field) {
+            Some(cached) if cached.cached_at.elapsed() < self.ttl => Some(cached.json.to_owned()),
+            Some(_) => {
+                entries.remove(field);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// caches `json` unless `group` was invalidated since `epoch`, returning whether it did
+    fn put_local(&self, group: &str, field: &str, json: &str, epoch: u64) -> bool {
+        let mut local = self.local.lock().unwrap();
+
+        if self.epoch.load(Ordering::Acquire) != epoch {
+            return false;
+        }
+
+        let cached = CachedValue {
+            json: json.to_owned(),
+            cached_at: Instant::now(),
+        };
+
+        match local.get_mut(&group.to_owned()) {
+            Some(entries) => {
+                entries.insert(field.to_owned(), cached);
+            }
+            None => {
+                local.insert(
+                    group.to_owned(),
+                    HashMap::from([(field.to_owned(), cached)]),
+                );
+            }
+        }
+
+        true
+    }
+
+    fn invalidate_local(&self, group: &str) {
+        if group == ALL_GROUPS {
+            return self.clear_local();
+        }
+
+        let mut local = self.local.lock().unwrap();
+
+        self.epoch.fetch_add(1, Ordering::AcqRel);
+        local.remove(&group.to_owned());
+    }
+
+    fn clear_local(&self) {
+        let mut local = self.local.lock().unwrap();
+
+        self.epoch.fetch_add(1, Ordering::AcqRel);
+        local.clear();
+    }
+
+    fn into_redis_key(group: &str) -> String {
+        format!("cache:{}", group)
+    }
+}
@@ -1,13 +1,31 @@
-use std::time::Duration;
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+    time::Duration,
+};
 
 use deadpool::managed::Timeouts;
 use deadpool_redis::{Config, PoolConfig};
 use deadpool_runtime::Runtime;
-use diesel_async::pooled_connection::{
-    deadpool::{Object, Pool},
-    AsyncDieselConnectionManager,
+#[cfg(feature = "sqlite")]
+use diesel::{
+    connection::{AnsiTransactionManager, SimpleConnection, TransactionManager as _},
+    r2d2::{ConnectionManager, CustomizeConnection, PooledConnection},
+    SqliteConnection,
+};
+use diesel_async::{
+    pooled_connection::{
+        deadpool::{Object, Pool},
+        AsyncDieselConnectionManager,
+    },
+    AsyncConnection, TransactionManager,
 };
+#[cfg(feature = "sqlite")]
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use futures_util::future::BoxFuture;
+use scoped_futures::ScopedBoxFuture;
 use thiserror::Error;
+use tokio::sync::{Mutex, OwnedMutexGuard};
 
 type AsyncPgPool = Pool<AsyncPgConnection>;
 type AsyncRedisPool = deadpool_redis::Pool;
@@ -17,9 +35,32 @@ pub type AsyncPgConnection = diesel_async::AsyncPgConnection;
 pub type ManagedAsyncPgConnection = Object<AsyncPgConnection>;
 pub type ManagedAsyncRedisConnection = deadpool_redis::Connection;
 
+type PgTransactionManager = <AsyncPgConnection as AsyncConnection>::TransactionManager;
+
+type AfterCommit = Box<dyn FnOnce(Arc<DbContext>) -> BoxFuture<'static, ()> + Send>;
+
+#[cfg(feature = "sqlite")]
+type SqlitePool = diesel::r2d2::Pool<ConnectionManager<SqliteConnection>>;
+
+#[cfg(feature = "sqlite")]
+pub type ManagedSqliteConnection = PooledConnection<ConnectionManager<SqliteConnection>>;
+
+#[cfg(feature = "sqlite")]
+pub const SQLITE_MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations_sqlite/");
+
+#[derive(Default)]
 pub struct DbContext {
-    pg_pool: AsyncPgPool,
-    redis_pool: AsyncRedisPool,
+    pg_pool: Option<AsyncPgPool>,
+    redis_pool: Option<AsyncRedisPool>,
+    /// the connection every pg query is run on while inside a unit of work
+    pg_transaction: Option<Arc<Mutex<ManagedAsyncPgConnection>>>,
+    #[cfg(feature = "sqlite")]
+    sqlite_pool: Option<SqlitePool>,
+    /// the connection every sqlite query is run on while inside a unit of work
+    #[cfg(feature = "sqlite")]
+    sqlite_transaction: Option<Arc<std::sync::Mutex<ManagedSqliteConnection>>>,
+    /// what to run once the outermost unit of work commits, shared by every nested unit of work
+    after_commit: Option<Arc<std::sync::Mutex<Vec<AfterCommit>>>>,
 }
 
 impl DbContext {
@@ -29,9 +70,68 @@ impl DbContext {
         redis_url: &str,
         redis_pool_size: usize,
     ) -> Self {
+        Self::default()
+            .with_pg_pool(pg_url, pg_pool_size)
+            .with_redis_pool(redis_url, redis_pool_size)
+    }
+
+    /// A context with no connection pools, for use with the in-memory repositories, which keep
+    /// their own state.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    pub fn with_pg_pool(self, pg_url: &str, pg_pool_size: usize) -> Self {
+        Self {
+            pg_pool: Some(Self::create_pg_pool(pg_url, &pg_pool_size)),
+            ..self
+        }
+    }
+
+    pub fn with_redis_pool(self, redis_url: &str, redis_pool_size: usize) -> Self {
+        Self {
+            redis_pool: Some(Self::create_redis_pool(redis_url, &redis_pool_size)),
+            ..self
+        }
+    }
+
+    /// Whether queries run through this context are part of a unit of work that has yet to commit.
+    pub fn in_transaction(&self) -> bool {
+        #[cfg(feature = "sqlite")]
+        if self.sqlite_transaction.is_some() {
+            return true;
+        }
+
+        self.pg_transaction.is_some()
+    }
+
+    /// Runs `callback` once the unit of work this context belongs to has committed, or straight
+    /// away outside of one. If the unit of work rolls back, `callback` never runs.
+    pub async fn after_commit<F>(self: &Arc<Self>, callback: F)
+    where
+        F: FnOnce(Arc<DbContext>) -> BoxFuture<'static, ()> + Send + 'static,
+    {
+        match &self.after_commit {
+            Some(after_commit) => after_commit.lock().unwrap().push(Box::new(callback)),
+            None => callback(self.clone()).await,
+        }
+    }
+
+    async fn run_after_commit(self: &Arc<Self>, after_commit: &std::sync::Mutex<Vec<AfterCommit>>) {
+        let callbacks = std::mem::take(&mut *after_commit.lock().unwrap());
+
+        for callback in callbacks {
+            callback(self.clone()).await;
+        }
+    }
+
+    /// Adds a pool of connections to the sqlite database at `sqlite_url`, creating the file and
+    /// running any pending sqlite migrations first.
+    #[cfg(feature = "sqlite")]
+    pub fn with_sqlite_pool(self, sqlite_url: &str, sqlite_pool_size: usize) -> Self {
         Self {
-            pg_pool: Self::create_pg_pool(pg_url, &pg_pool_size),
-            redis_pool: Self::create_redis_pool(redis_url, &redis_pool_size),
+            sqlite_pool: Some(Self::create_sqlite_pool(sqlite_url, &sqlite_pool_size)),
+            ..self
         }
     }
 
@@ -63,8 +163,46 @@ impl DbContext {
             .expect("Could not build redis connection pool")
     }
 
-    pub async fn get_pg_connection(&self) -> Result<ManagedAsyncPgConnection, DbContextError> {
-        self.pg_pool.get().await.map_err(|_| {
+    #[cfg(feature = "sqlite")]
+    fn create_sqlite_pool(url: &str, pool_size: &usize) -> SqlitePool {
+        let sqlite_manager = ConnectionManager::<SqliteConnection>::new(url);
+
+        let sqlite_pool = diesel::r2d2::Pool::builder()
+            .max_size(*pool_size as u32)
+            .connection_timeout(Duration::from_millis(5000))
+            .connection_customizer(Box::new(SqliteConnectionCustomizer))
+            .build(sqlite_manager)
+            .expect("Could not build sqlite connection pool");
+
+        let mut conn = sqlite_pool
+            .get()
+            .expect("Could not get a sqlite connection to migrate");
+
+        conn.run_pending_migrations(SQLITE_MIGRATIONS)
+            .expect("Could not run the sqlite migrations");
+
+        sqlite_pool
+    }
+
+    pub async fn get_pg_connection(&self) -> Result<PgConnectionGuard, DbContextError> {
+        match &self.pg_transaction {
+            Some(pg_transaction) => Ok(PgConnectionGuard::Transaction(
+                pg_transaction.clone().lock_owned().await,
+            )),
+            None => self
+                .get_pooled_pg_connection()
+                .await
+                .map(PgConnectionGuard::Pooled),
+        }
+    }
+
+    async fn get_pooled_pg_connection(&self) -> Result<ManagedAsyncPgConnection, DbContextError> {
+        let Some(pg_pool) = &self.pg_pool
+        else {
+            return Err(DbContextError::NotConfigured);
+        };
+
+        pg_pool.get().await.map_err(|_| {
             let msg = "PG POOL CONNECTION FAILED";
             tracing::error!(error = msg);
 
@@ -72,10 +210,222 @@ impl DbContext {
         })
     }
 
+    /// Runs `callback` as a single unit of work. Every repository call made with the
+    /// `DbContext` passed to `callback` shares one pg connection and transaction, which is
+    /// committed if `callback` succeeds and rolled back if it fails. A unit of work started
+    /// inside another becomes a savepoint of the outer transaction.
+    ///
+    /// Without a pg or sqlite pool, i.e. with the in-memory repositories, `callback` simply runs:
+    /// nothing is rolled back, so the writes it made before failing are kept.
+    pub async fn transaction<'a, R, E, F>(self: &Arc<Self>, callback: F) -> Result<R, E>
+    where
+        F: for<'r> FnOnce(&'r Arc<DbContext>) -> ScopedBoxFuture<'a, 'r, Result<R, E>> + Send + 'a,
+        E: From<DbContextError> + Send + 'a,
+        R: Send + 'a,
+    {
+        if self.pg_pool.is_none() {
+            #[cfg(feature = "sqlite")]
+            if self.sqlite_pool.is_some() {
+                return self.sqlite_transaction(callback).await;
+            }
+
+            // there is no transaction to run without a pool, e.g. with the in-memory repositories
+            return callback(self).await;
+        }
+
+        let is_outermost = self.pg_transaction.is_none();
+        let pg_transaction = match &self.pg_transaction {
+            Some(pg_transaction) => pg_transaction.clone(),
+            None => Arc::new(Mutex::new(self.get_pooled_pg_connection().await?)),
+        };
+        let after_commit = self.after_commit.clone().unwrap_or_default();
+
+        PgTransactionManager::begin_transaction(&mut **pg_transaction.lock().await)
+            .await
+            .map_err(DbContextError::from)?;
+
+        let unit_of_work = Arc::new(Self {
+            pg_pool: self.pg_pool.clone(),
+            redis_pool: self.redis_pool.clone(),
+            pg_transaction: Some(pg_transaction.clone()),
+            #[cfg(feature = "sqlite")]
+            sqlite_pool: self.sqlite_pool.clone(),
+            #[cfg(feature = "sqlite")]
+            sqlite_transaction: self.sqlite_transaction.clone(),
+            after_commit: Some(after_commit.clone()),
+        });
+
+        let result = callback(&unit_of_work).await;
+
+        let value = {
+            let conn = &mut **pg_transaction.lock().await;
+
+            match result {
+                Ok(value) => {
+                    PgTransactionManager::commit_transaction(conn)
+                        .await
+                        .map_err(DbContextError::from)?;
+
+                    value
+                }
+                Err(err) => {
+                    if let Err(rollback_err) =
+                        PgTransactionManager::rollback_transaction(conn).await
+                    {
+                        tracing::error!(error = %rollback_err);
+                    }
+
+                    return Err(err);
+                }
+            }
+        };
+
+        if is_outermost {
+            self.run_after_commit(&after_commit).await;
+        }
+
+        Ok(value)
+    }
+
+    /// The sqlite counterpart of `transaction`. The outermost unit of work takes the write lock up
+    /// front with `BEGIN IMMEDIATE`, so two units of work never deadlock upgrading a read lock.
+    #[cfg(feature = "sqlite")]
+    async fn sqlite_transaction<'a, R, E, F>(self: &Arc<Self>, callback: F) -> Result<R, E>
+    where
+        F: for<'r> FnOnce(&'r Arc<DbContext>) -> ScopedBoxFuture<'a, 'r, Result<R, E>> + Send + 'a,
+        E: From<DbContextError> + Send + 'a,
+        R: Send + 'a,
+    {
+        let (sqlite_transaction, is_outermost) = match &self.sqlite_transaction {
+            Some(sqlite_transaction) => (sqlite_transaction.clone(), false),
+            None => {
+                let conn = self.with_pooled_sqlite_connection(|conn| conn).await?;
+
+                (Arc::new(std::sync::Mutex::new(conn)), true)
+            }
+        };
+        let after_commit = self.after_commit.clone().unwrap_or_default();
+
+        let unit_of_work = Arc::new(Self {
+            pg_pool: None,
+            redis_pool: self.redis_pool.clone(),
+            pg_transaction: None,
+            sqlite_pool: self.sqlite_pool.clone(),
+            sqlite_transaction: Some(sqlite_transaction),
+            after_commit: Some(after_commit.clone()),
+        });
+
+        unit_of_work
+            .with_sqlite_connection(move |conn| match is_outermost {
+                true => AnsiTransactionManager::begin_transaction_sql(conn, "BEGIN IMMEDIATE"),
+                false => AnsiTransactionManager::begin_transaction(conn),
+            })
+            .await?
+            .map_err(DbContextError::from)?;
+
+        let result = callback(&unit_of_work).await;
+
+        match result {
+            Ok(value) => {
+                unit_of_work
+                    .with_sqlite_connection(AnsiTransactionManager::commit_transaction)
+                    .await?
+                    .map_err(DbContextError::from)?;
+
+                if is_outermost {
+                    self.run_after_commit(&after_commit).await;
+                }
+
+                Ok(value)
+            }
+            Err(err) => {
+                let rollback = unit_of_work
+                    .with_sqlite_connection(AnsiTransactionManager::rollback_transaction)
+                    .await;
+
+                if let Ok(Err(rollback_err)) = rollback {
+                    tracing::error!(error = %rollback_err);
+                }
+
+                Err(err)
+            }
+        }
+    }
+
+    /// Runs `callback` with a sqlite connection on the blocking thread pool, as every sqlite
+    /// query blocks. Inside a unit of work the connection is the unit of work's.
+    #[cfg(feature = "sqlite")]
+    pub async fn with_sqlite_connection<R, F>(&self, callback: F) -> Result<R, DbContextError>
+    where
+        F: FnOnce(&mut SqliteConnection) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let Some(sqlite_transaction) = self.sqlite_transaction.clone()
+        else {
+            return self
+                .with_pooled_sqlite_connection(move |mut conn| callback(&mut conn))
+                .await;
+        };
+
+        Self::spawn_blocking(move || {
+            let mut conn = sqlite_transaction.lock().map_err(|_| {
+                let msg = "SQLITE TRANSACTION LOCK POISONED";
+                tracing::error!(error = msg);
+
+                DbContextError::ConnectionFailed
+            })?;
+
+            Ok(callback(&mut conn))
+        })
+        .await
+    }
+
+    #[cfg(feature = "sqlite")]
+    async fn with_pooled_sqlite_connection<R, F>(&self, callback: F) -> Result<R, DbContextError>
+    where
+        F: FnOnce(ManagedSqliteConnection) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let Some(sqlite_pool) = self.sqlite_pool.clone()
+        else {
+            return Err(DbContextError::NotConfigured);
+        };
+
+        Self::spawn_blocking(move || {
+            let conn = sqlite_pool.get().map_err(|_| {
+                let msg = "SQLITE POOL CONNECTION FAILED";
+                tracing::error!(error = msg);
+
+                DbContextError::ConnectionFailed
+            })?;
+
+            Ok(callback(conn))
+        })
+        .await
+    }
+
+    #[cfg(feature = "sqlite")]
+    async fn spawn_blocking<R, F>(callback: F) -> Result<R, DbContextError>
+    where
+        F: FnOnce() -> Result<R, DbContextError> + Send + 'static,
+        R: Send + 'static,
+    {
+        tokio::task::spawn_blocking(callback).await.map_err(|err| {
+            tracing::error!(error = %err);
+
+            DbContextError::ConnectionFailed
+        })?
+    }
+
     pub async fn get_redis_connection(
         &self,
     ) -> Result<ManagedAsyncRedisConnection, DbContextError> {
-        self.redis_pool.get().await.map_err(|_| {
+        let Some(redis_pool) = &self.redis_pool
+        else {
+            return Err(DbContextError::NotConfigured);
+        };
+
+        redis_pool.get().await.map_err(|_| {
             let msg = "REDIS POOL CONNECTION FAILED";
             tracing::error!(error = msg);
 
@@ -84,8 +434,63 @@ impl DbContext {
     }
 }
 
+/// Sets up every pooled sqlite connection: foreign keys have to be turned on per connection for
+/// the `ON DELETE CASCADE`s to apply, and a busy connection waits for the write lock rather than
+/// failing straight away.
+#[cfg(feature = "sqlite")]
+#[derive(Debug)]
+struct SqliteConnectionCustomizer;
+
+#[cfg(feature = "sqlite")]
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for SqliteConnectionCustomizer {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        conn.batch_execute(
+            "PRAGMA foreign_keys = ON; PRAGMA busy_timeout = 5000; PRAGMA journal_mode = WAL;",
+        )
+        .map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
+/// A pg connection checked out of the pool, or the connection of the unit of work in progress.
+pub enum PgConnectionGuard {
+    Pooled(ManagedAsyncPgConnection),
+    Transaction(OwnedMutexGuard<ManagedAsyncPgConnection>),
+}
+
+impl Deref for PgConnectionGuard {
+    type Target = AsyncPgConnection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Pooled(conn) => conn,
+            Self::Transaction(conn) => conn,
+        }
+    }
+}
+
+impl DerefMut for PgConnectionGuard {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Pooled(conn) => conn,
+            Self::Transaction(conn) => conn,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum DbContextError {
     #[error("DB CONTEXT ERROR :: Failed to get connection from connection pool")]
     ConnectionFailed,
+    #[error("DB CONTEXT ERROR :: No connection pool is configured")]
+    NotConfigured,
+    #[error("DB CONTEXT ERROR :: Failed to begin, commit or roll back a transaction")]
+    TransactionFailed,
+}
+
+impl From<diesel::result::Error> for DbContextError {
+    fn from(err: diesel::result::Error) -> Self {
+        tracing::error!(error = %err);
+
+        Self::TransactionFailed
+    }
 }
@@ -0,0 +1,8 @@
+use base64::{engine::general_purpose, Engine as _};
+use ring::digest;
+
+/// Returns the value persisted in place of a bearer credential (tokens, codes), so that a leaked
+/// database cannot be replayed against the server: base64url(sha256(value)) without padding.
+pub fn digest_token(value: &str) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(digest::digest(&digest::SHA256, value.as_bytes()))
+}
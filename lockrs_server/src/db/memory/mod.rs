@@ -0,0 +1,5 @@
+pub mod repositories;
+
+mod store;
+
+pub use self::store::*;
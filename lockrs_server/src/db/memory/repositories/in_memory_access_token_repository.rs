@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+
+use crate::{
+    db::{
+        digest_token,
+        memory::{expect_one_deleted, is_live, query_failed, to_pg_list, InMemoryStore},
+        pg::models::PgAccessToken,
+        repositories::{AccessTokenRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::{AccessTokenMapper, AuthorizationDetailMapper},
+        models::{AccessTokenCreateModel, AccessTokenModel},
+    },
+};
+
+pub struct InMemoryAccessTokenRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl AccessTokenRepository for InMemoryAccessTokenRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        token_create: &AccessTokenCreateModel,
+    ) -> Result<AccessTokenModel, RepositoryError> {
+        tracing::trace!(method = "create");
+
+        let mut tables = self.store.lock()?;
+
+        if !tables.has_client(token_create.client_id.as_str())
+            || token_create
+                .user_id
+                .is_some_and(|user_id| !tables.has_user(&user_id))
+        {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "access_tokens violates a foreign key constraint",
+            ));
+        }
+
+        let pg_token = PgAccessToken {
+            id: tables.next_id(),
+            token: digest_token(token_create.token.as_str()),
+            client_id: token_create.client_id.to_owned(),
+            user_id: token_create.user_id,
+            created_at: Utc::now().naive_utc(),
+            expires_at: token_create.expires_at,
+            scopes: to_pg_list(&token_create.scopes),
+            authorization_details: AuthorizationDetailMapper::vec_to_pg_value(
+                &token_create.authorization_details,
+            ),
+        };
+
+        tables.access_tokens.push(pg_token.clone());
+
+        let mut token = AccessTokenMapper::from_pg(pg_token);
+        token.token = token_create.token.to_owned();
+
+        Ok(token)
+    }
+
+    async fn get_by_token(
+        &self,
+        _db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<AccessTokenModel, RepositoryError> {
+        tracing::trace!(method = "get_by_token");
+
+        let tables = self.store.lock()?;
+        let digest = digest_token(token);
+
+        let pg_token = tables
+            .access_tokens
+            .iter()
+            .find(|access_token| {
+                access_token.token == digest
+                    && is_live(&access_token.created_at, &access_token.expires_at)
+            })
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "access token not found"))?;
+
+        let mut access_token = AccessTokenMapper::from_pg(pg_token);
+        access_token.token = token.to_owned();
+
+        Ok(access_token)
+    }
+
+    async fn delete_by_token(
+        &self,
+        _db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_token");
+
+        let mut tables = self.store.lock()?;
+        let digest = digest_token(token);
+
+        expect_one_deleted(tables.delete_access_tokens(|access_token| access_token.token == digest))
+    }
+
+    async fn delete_expired(
+        &self,
+        _db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "delete_expired", ?expired_before, limit);
+
+        let mut tables = self.store.lock()?;
+
+        // refresh tokens cascade from their access token, so keep any still referenced
+        let expired_ids = tables
+            .access_tokens
+            .iter()
+            .filter(|access_token| &access_token.expires_at < expired_before)
+            .filter(|access_token| {
+                !tables
+                    .refresh_tokens
+                    .iter()
+                    .any(|refresh_token| refresh_token.access_token_id == access_token.id)
+            })
+            .take(limit as usize)
+            .map(|access_token| access_token.id)
+            .collect::<Vec<i32>>();
+
+        Ok(tables.delete_access_tokens(|access_token| expired_ids.contains(&access_token.id)))
+    }
+}
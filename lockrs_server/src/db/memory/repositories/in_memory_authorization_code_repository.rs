@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+
+use crate::{
+    db::{
+        digest_token,
+        memory::{is_live, query_failed, to_pg_list, InMemoryStore},
+        pg::models::PgAuthorizationCode,
+        repositories::{AuthorizationCodeRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::{AuthorizationCodeMapper, AuthorizationDetailMapper},
+        models::{AuthorizationCodeCreateModel, AuthorizationCodeModel},
+    },
+};
+
+pub struct InMemoryAuthorizationCodeRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl AuthorizationCodeRepository for InMemoryAuthorizationCodeRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        auth_code_create: &AuthorizationCodeCreateModel,
+    ) -> Result<AuthorizationCodeModel, RepositoryError> {
+        tracing::trace!(method = "create");
+
+        let mut tables = self.store.lock()?;
+
+        if !tables.has_client(auth_code_create.client_id.as_str())
+            || !tables.has_user(&auth_code_create.user_id)
+        {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "authorization_codes violates a foreign key constraint",
+            ));
+        }
+
+        let pg_code = PgAuthorizationCode {
+            id: tables.next_id(),
+            code: digest_token(auth_code_create.code.as_str()),
+            challenge: auth_code_create.challenge.to_owned(),
+            is_challenge_plain: auth_code_create.is_challenge_plain,
+            client_id: auth_code_create.client_id.to_owned(),
+            user_id: auth_code_create.user_id,
+            redirect_uri: auth_code_create.redirect_uri.to_string(),
+            created_at: Utc::now().naive_utc(),
+            expires_at: auth_code_create.expires_at,
+            used: false,
+            scopes: to_pg_list(&auth_code_create.scopes),
+            authorization_details: AuthorizationDetailMapper::vec_to_pg_value(
+                &auth_code_create.authorization_details,
+            ),
+        };
+
+        tables.authorization_codes.push(pg_code.clone());
+
+        let mut code = AuthorizationCodeMapper::from_pg(pg_code);
+        code.code = auth_code_create.code.to_owned();
+
+        Ok(code)
+    }
+
+    async fn get_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: i32,
+    ) -> Result<AuthorizationCodeModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", id);
+
+        let tables = self.store.lock()?;
+
+        tables
+            .authorization_codes
+            .iter()
+            .find(|authorization_code| authorization_code.id == id)
+            .cloned()
+            .map(AuthorizationCodeMapper::from_pg)
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "authorization code not found"))
+    }
+
+    async fn get_by_code(
+        &self,
+        _db_context: &Arc<DbContext>,
+        code: &str,
+    ) -> Result<AuthorizationCodeModel, RepositoryError> {
+        tracing::trace!(method = "get_by_code");
+
+        let tables = self.store.lock()?;
+        let digest = digest_token(code);
+
+        let pg_code = tables
+            .authorization_codes
+            .iter()
+            .find(|authorization_code| {
+                authorization_code.code == digest
+                    && is_live(
+                        &authorization_code.created_at,
+                        &authorization_code.expires_at,
+                    )
+                    && !authorization_code.used
+            })
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "authorization code not found"))?;
+
+        let mut authorization_code = AuthorizationCodeMapper::from_pg(pg_code);
+        authorization_code.code = code.to_owned();
+
+        Ok(authorization_code)
+    }
+
+    async fn use_by_code(
+        &self,
+        _db_context: &Arc<DbContext>,
+        code: &str,
+    ) -> Result<AuthorizationCodeModel, RepositoryError> {
+        tracing::trace!(method = "use_by_code");
+
+        let mut tables = self.store.lock()?;
+        let digest = digest_token(code);
+
+        let pg_code = tables
+            .authorization_codes
+            .iter_mut()
+            .find(|authorization_code| {
+                authorization_code.code == digest
+                    && is_live(
+                        &authorization_code.created_at,
+                        &authorization_code.expires_at,
+                    )
+                    && !authorization_code.used
+            })
+            .map(|authorization_code| {
+                authorization_code.used = true;
+                authorization_code.clone()
+            })
+            .ok_or_else(|| {
+                query_failed(QueryFailure::NotUpdated, "authorization code not updated")
+            })?;
+
+        let mut authorization_code = AuthorizationCodeMapper::from_pg(pg_code);
+        authorization_code.code = code.to_owned();
+
+        Ok(authorization_code)
+    }
+
+    async fn delete_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: i32,
+    ) -> Result<AuthorizationCodeModel, RepositoryError> {
+        tracing::trace!(method = "delete_by_id", id);
+
+        let mut tables = self.store.lock()?;
+
+        let index = tables
+            .authorization_codes
+            .iter()
+            .position(|authorization_code| authorization_code.id == id)
+            .ok_or_else(|| {
+                query_failed(QueryFailure::NotDeleted, "authorization code not deleted")
+            })?;
+
+        let pg_code = tables.authorization_codes.remove(index);
+
+        Ok(AuthorizationCodeMapper::from_pg(pg_code))
+    }
+
+    async fn delete_expired(
+        &self,
+        _db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "delete_expired", ?expired_before, limit);
+
+        let mut tables = self.store.lock()?;
+
+        let expired_ids = tables
+            .authorization_codes
+            .iter()
+            .filter(|authorization_code| &authorization_code.expires_at < expired_before)
+            .take(limit as usize)
+            .map(|authorization_code| authorization_code.id)
+            .collect::<Vec<i32>>();
+
+        tables
+            .authorization_codes
+            .retain(|authorization_code| !expired_ids.contains(&authorization_code.id));
+
+        Ok(expired_ids.len())
+    }
+}
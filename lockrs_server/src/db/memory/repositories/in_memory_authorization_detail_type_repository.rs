@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    db::{
+        memory::{expect_one_deleted, query_failed, InMemoryStore},
+        pg::models::PgAuthorizationDetailType,
+        repositories::{AuthorizationDetailTypeRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::AuthorizationDetailMapper,
+        models::{AuthorizationDetailTypeCreateModel, AuthorizationDetailTypeModel},
+    },
+};
+
+pub struct InMemoryAuthorizationDetailTypeRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl AuthorizationDetailTypeRepository for InMemoryAuthorizationDetailTypeRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        type_create: &AuthorizationDetailTypeCreateModel,
+    ) -> Result<AuthorizationDetailTypeModel, RepositoryError> {
+        tracing::trace!(method = "create", ?type_create);
+
+        let schema = serde_json::to_value(&type_create.schema).map_err(|err| {
+            tracing::error!(error = %err);
+            RepositoryError::QueryFailed(QueryFailure::NotCreated)
+        })?;
+
+        let mut tables = self.store.lock()?;
+
+        if tables
+            .authorization_detail_types
+            .iter()
+            .any(|authorization_detail_type| {
+                authorization_detail_type.client_id == type_create.client_id
+                    && authorization_detail_type.name == type_create.name
+            })
+        {
+            return Err(query_failed(
+                QueryFailure::AlreadyExists,
+                "authorization_detail_types violates a unique constraint",
+            ));
+        }
+
+        if !tables.has_client(type_create.client_id.as_str()) {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "authorization_detail_types violates a foreign key constraint",
+            ));
+        }
+
+        let pg_type = PgAuthorizationDetailType {
+            name: type_create.name.to_owned(),
+            client_id: type_create.client_id.to_owned(),
+            description: type_create.description.to_owned(),
+            schema,
+        };
+
+        tables.authorization_detail_types.push(pg_type.clone());
+
+        Ok(AuthorizationDetailMapper::type_from_pg(pg_type))
+    }
+
+    async fn get_from_list(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+        names: &[String],
+    ) -> Result<Vec<AuthorizationDetailTypeModel>, RepositoryError> {
+        tracing::trace!(method = "get_from_list", client_id, ?names);
+
+        let tables = self.store.lock()?;
+
+        Ok(tables
+            .authorization_detail_types
+            .iter()
+            .filter(|authorization_detail_type| {
+                authorization_detail_type.client_id == client_id
+                    && names.contains(&authorization_detail_type.name)
+            })
+            .cloned()
+            .map(AuthorizationDetailMapper::type_from_pg)
+            .collect::<Vec<AuthorizationDetailTypeModel>>())
+    }
+
+    async fn get_all_by_client_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<AuthorizationDetailTypeModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_client_id", client_id);
+
+        let tables = self.store.lock()?;
+
+        Ok(tables
+            .authorization_detail_types
+            .iter()
+            .filter(|authorization_detail_type| authorization_detail_type.client_id == client_id)
+            .cloned()
+            .map(AuthorizationDetailMapper::type_from_pg)
+            .collect::<Vec<AuthorizationDetailTypeModel>>())
+    }
+
+    async fn delete_by_name(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_name", client_id, name);
+
+        let mut tables = self.store.lock()?;
+
+        let before = tables.authorization_detail_types.len();
+        tables
+            .authorization_detail_types
+            .retain(|authorization_detail_type| {
+                authorization_detail_type.client_id != client_id
+                    || authorization_detail_type.name != name
+            });
+
+        expect_one_deleted(before - tables.authorization_detail_types.len())
+    }
+}
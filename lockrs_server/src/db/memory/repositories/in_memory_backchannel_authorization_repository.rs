@@ -0,0 +1,216 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+
+use crate::{
+    db::{
+        memory::{expect_one_deleted, is_live, query_failed, to_pg_list, InMemoryStore},
+        pg::models::PgBackchannelAuthorization,
+        repositories::{BackchannelAuthorizationRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::{AuthorizationDetailMapper, BackchannelAuthorizationMapper},
+        models::{
+            BackchannelAuthorizationCreateModel, BackchannelAuthorizationModel,
+            BackchannelAuthorizationStatus,
+        },
+    },
+};
+
+pub struct InMemoryBackchannelAuthorizationRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl BackchannelAuthorizationRepository for InMemoryBackchannelAuthorizationRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        backchannel_authorization_create: &BackchannelAuthorizationCreateModel,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "create", ?backchannel_authorization_create);
+
+        let mut tables = self.store.lock()?;
+
+        if tables
+            .backchannel_authorizations
+            .iter()
+            .any(|backchannel_authorization| {
+                backchannel_authorization.auth_req_id
+                    == backchannel_authorization_create.auth_req_id
+            })
+        {
+            return Err(query_failed(
+                QueryFailure::AlreadyExists,
+                "backchannel_authorizations violates a unique constraint",
+            ));
+        }
+
+        if !tables.has_client(backchannel_authorization_create.client_id.as_str())
+            || !tables.has_user(&backchannel_authorization_create.user_id)
+        {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "backchannel_authorizations violates a foreign key constraint",
+            ));
+        }
+
+        let pg_backchannel_authorization = PgBackchannelAuthorization {
+            id: tables.next_id(),
+            auth_req_id: backchannel_authorization_create.auth_req_id.to_owned(),
+            client_id: backchannel_authorization_create.client_id.to_owned(),
+            user_id: backchannel_authorization_create.user_id,
+            binding_message: backchannel_authorization_create.binding_message.to_owned(),
+            delivery_mode: backchannel_authorization_create
+                .delivery_mode
+                .as_str()
+                .to_owned(),
+            client_notification_token: backchannel_authorization_create
+                .client_notification_token
+                .to_owned(),
+            client_notification_endpoint: backchannel_authorization_create
+                .client_notification_endpoint
+                .to_owned(),
+            status: BackchannelAuthorizationStatus::Pending.as_str().to_owned(),
+            poll_interval: backchannel_authorization_create.poll_interval,
+            created_at: Utc::now().naive_utc(),
+            expires_at: backchannel_authorization_create.expires_at,
+            last_polled_at: None,
+            scopes: to_pg_list(&backchannel_authorization_create.scopes),
+            authorization_details: AuthorizationDetailMapper::vec_to_pg_value(
+                &backchannel_authorization_create.authorization_details,
+            ),
+        };
+
+        tables
+            .backchannel_authorizations
+            .push(pg_backchannel_authorization.clone());
+
+        Ok(BackchannelAuthorizationMapper::from_pg(
+            pg_backchannel_authorization,
+        ))
+    }
+
+    async fn get_by_auth_req_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "get_by_auth_req_id");
+
+        let tables = self.store.lock()?;
+
+        let pg_backchannel_authorization = tables
+            .backchannel_authorizations
+            .iter()
+            .find(|backchannel_authorization| {
+                backchannel_authorization.auth_req_id == auth_req_id
+                    && is_live(
+                        &backchannel_authorization.created_at,
+                        &backchannel_authorization.expires_at,
+                    )
+            })
+            .cloned()
+            .ok_or_else(|| {
+                query_failed(
+                    QueryFailure::NotFound,
+                    "backchannel authorization not found",
+                )
+            })?;
+
+        Ok(BackchannelAuthorizationMapper::from_pg(
+            pg_backchannel_authorization,
+        ))
+    }
+
+    async fn update_status_by_auth_req_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+        status: BackchannelAuthorizationStatus,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "update_status_by_auth_req_id", ?status);
+
+        let mut tables = self.store.lock()?;
+        let now = Utc::now().naive_utc();
+
+        // only a pending request may be resolved, and only once
+        let pg_backchannel_authorization = tables
+            .backchannel_authorizations
+            .iter_mut()
+            .find(|backchannel_authorization| {
+                backchannel_authorization.auth_req_id == auth_req_id
+                    && backchannel_authorization.status
+                        == BackchannelAuthorizationStatus::Pending.as_str()
+                    && backchannel_authorization.expires_at > now
+            })
+            .map(|backchannel_authorization| {
+                backchannel_authorization.status = status.as_str().to_owned();
+                backchannel_authorization.clone()
+            })
+            .ok_or_else(|| {
+                query_failed(
+                    QueryFailure::NotUpdated,
+                    "backchannel authorization not updated",
+                )
+            })?;
+
+        Ok(BackchannelAuthorizationMapper::from_pg(
+            pg_backchannel_authorization,
+        ))
+    }
+
+    async fn update_last_polled_at_by_auth_req_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+        last_polled_at: &NaiveDateTime,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError> {
+        tracing::trace!(
+            method = "update_last_polled_at_by_auth_req_id",
+            ?last_polled_at
+        );
+
+        let mut tables = self.store.lock()?;
+
+        let pg_backchannel_authorization = tables
+            .backchannel_authorizations
+            .iter_mut()
+            .find(|backchannel_authorization| backchannel_authorization.auth_req_id == auth_req_id)
+            .map(|backchannel_authorization| {
+                backchannel_authorization.last_polled_at = Some(*last_polled_at);
+                backchannel_authorization.clone()
+            })
+            .ok_or_else(|| {
+                query_failed(
+                    QueryFailure::NotUpdated,
+                    "backchannel authorization not updated",
+                )
+            })?;
+
+        Ok(BackchannelAuthorizationMapper::from_pg(
+            pg_backchannel_authorization,
+        ))
+    }
+
+    async fn delete_by_auth_req_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_auth_req_id");
+
+        let mut tables = self.store.lock()?;
+
+        let before = tables.backchannel_authorizations.len();
+        tables
+            .backchannel_authorizations
+            .retain(|backchannel_authorization| {
+                backchannel_authorization.auth_req_id != auth_req_id
+            });
+
+        expect_one_deleted(before - tables.backchannel_authorizations.len())
+    }
+}
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+
+use crate::{
+    db::{
+        memory::{query_failed, InMemoryStore},
+        pg::models::PgClient,
+        repositories::{ClientAuthRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    mappers::{ClientAuthMapper, ClientSecretMapper},
+    models::{
+        ClientAuthModel, ClientSecretCreateModel, ClientSecretModel, RedirectCreateModel,
+        RedirectMatchMode,
+    },
+};
+
+pub struct InMemoryClientAuthRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl ClientAuthRepository for InMemoryClientAuthRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_create: &ClientAuthModel,
+        secret_create: Option<&ClientSecretCreateModel>,
+        redirect_create: &RedirectCreateModel,
+    ) -> Result<ClientAuthModel, RepositoryError> {
+        tracing::trace!(
+            method = "create",
+            user_id = ?client_create.user_id,
+            id = client_create.id,
+            redirect_uri = ?redirect_create.uri
+        );
+
+        let mut tables = self.store.lock()?;
+
+        if tables.has_client(client_create.id.as_str()) {
+            return Err(query_failed(
+                QueryFailure::AlreadyExists,
+                "clients violates a unique constraint",
+            ));
+        }
+
+        if !tables.has_user(&client_create.user_id) {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "clients violates a foreign key constraint",
+            ));
+        }
+
+        let pg_client = PgClient {
+            id: client_create.id.to_owned(),
+            user_id: client_create.user_id,
+            is_public: client_create.is_public,
+            name: client_create.name.to_owned(),
+            description: client_create.description.to_owned(),
+            homepage_url: client_create.homepage_url.to_string(),
+        };
+
+        tables.clients.push(pg_client.clone());
+
+        // the inserts after the client are rolled back together with it, as in a transaction
+        let dependents = secret_create
+            .map(|secret_create| tables.insert_client_secret(secret_create).map(|_| ()))
+            .unwrap_or(Ok(()))
+            .and_then(|_| {
+                tables.insert_redirect_uri(
+                    redirect_create.client_id.as_str(),
+                    redirect_create.uri.as_str(),
+                    RedirectMatchMode::Exact.as_str(),
+                )
+            });
+
+        if let Err(err) = dependents {
+            tables.delete_clients(|client| client.id == client_create.id);
+
+            return Err(err);
+        }
+
+        Ok(ClientAuthMapper::from_pg(pg_client))
+    }
+
+    async fn get_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<ClientAuthModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", id);
+
+        let tables = self.store.lock()?;
+
+        let pg_client = tables
+            .clients
+            .iter()
+            .find(|client| client.id == id)
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "client not found"))?;
+
+        Ok(ClientAuthMapper::from_pg(pg_client))
+    }
+
+    async fn get_active_secrets_by_client_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<ClientSecretModel>, RepositoryError> {
+        tracing::trace!(method = "get_active_secrets_by_client_id", client_id);
+
+        let tables = self.store.lock()?;
+        let now = Utc::now().naive_utc();
+
+        Ok(tables
+            .client_secrets
+            .iter()
+            .filter(|client_secret| {
+                client_secret.client_id == client_id
+                    && client_secret
+                        .expires_at
+                        .is_none_or(|expires_at| expires_at > now)
+            })
+            .cloned()
+            .map(ClientSecretMapper::from_pg)
+            .collect::<Vec<ClientSecretModel>>())
+    }
+
+    async fn rotate_secret(
+        &self,
+        _db_context: &Arc<DbContext>,
+        secret_create: &ClientSecretCreateModel,
+        previous_expires_at: &NaiveDateTime,
+    ) -> Result<ClientSecretModel, RepositoryError> {
+        tracing::trace!(
+            method = "rotate_secret",
+            ?secret_create,
+            ?previous_expires_at
+        );
+
+        let mut tables = self.store.lock()?;
+
+        if !tables.has_client(secret_create.client_id.as_str()) {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "client_secrets violates a foreign key constraint",
+            ));
+        }
+
+        // secrets that already expire sooner keep their expiry
+        for client_secret in tables.client_secrets.iter_mut().filter(|client_secret| {
+            client_secret.client_id == secret_create.client_id
+                && client_secret
+                    .expires_at
+                    .is_none_or(|expires_at| &expires_at > previous_expires_at)
+        }) {
+            client_secret.expires_at = Some(*previous_expires_at);
+        }
+
+        let pg_client_secret = tables.insert_client_secret(secret_create)?;
+
+        Ok(ClientSecretMapper::from_pg(pg_client_secret))
+    }
+}
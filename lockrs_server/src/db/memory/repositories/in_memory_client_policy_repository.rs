@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    db::{
+        memory::{expect_one_deleted, query_failed, InMemoryStore},
+        repositories::{ClientPolicyRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    mappers::ClientPolicyMapper,
+    models::ClientPolicyModel,
+};
+
+pub struct InMemoryClientPolicyRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl ClientPolicyRepository for InMemoryClientPolicyRepository {
+    async fn upsert(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_policy: &ClientPolicyModel,
+    ) -> Result<ClientPolicyModel, RepositoryError> {
+        tracing::trace!(method = "upsert", ?client_policy);
+
+        let mut tables = self.store.lock()?;
+
+        if !tables.has_client(client_policy.client_id.as_str()) {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "client_policies violates a foreign key constraint",
+            ));
+        }
+
+        let pg_client_policy = ClientPolicyMapper::to_pg(client_policy);
+
+        tables
+            .client_policies
+            .retain(|existing| existing.client_id != pg_client_policy.client_id);
+        tables.client_policies.push(pg_client_policy.clone());
+
+        Ok(ClientPolicyMapper::from_pg(pg_client_policy))
+    }
+
+    async fn get_by_client_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<ClientPolicyModel, RepositoryError> {
+        tracing::trace!(method = "get_by_client_id", client_id);
+
+        let tables = self.store.lock()?;
+
+        let pg_client_policy = tables
+            .client_policies
+            .iter()
+            .find(|client_policy| client_policy.client_id == client_id)
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "client policy not found"))?;
+
+        Ok(ClientPolicyMapper::from_pg(pg_client_policy))
+    }
+
+    async fn delete_by_client_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_client_id", client_id);
+
+        let mut tables = self.store.lock()?;
+
+        let before = tables.client_policies.len();
+        tables
+            .client_policies
+            .retain(|client_policy| client_policy.client_id != client_id);
+
+        expect_one_deleted(before - tables.client_policies.len())
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        memory::{expect_one_deleted, query_failed, InMemoryStore},
+        pg::models::PgClient,
+        repositories::{ClientRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    mappers::ClientMapper,
+    models::{ClientModel, ClientUpdateModel},
+};
+
+pub struct InMemoryClientRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl ClientRepository for InMemoryClientRepository {
+    async fn get_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<ClientModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", id);
+
+        let tables = self.store.lock()?;
+
+        let pg_client = tables
+            .clients
+            .iter()
+            .find(|client| client.id == id)
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "client not found"))?;
+
+        Ok(ClientMapper::from_pg(pg_client))
+    }
+
+    async fn get_all_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<Vec<ClientModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_user_id", ?id);
+
+        let tables = self.store.lock()?;
+
+        Ok(tables
+            .clients
+            .iter()
+            .filter(|client| &client.user_id == id)
+            .cloned()
+            .map(ClientMapper::from_pg)
+            .collect::<Vec<ClientModel>>())
+    }
+
+    async fn update_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &str,
+        client_update: &ClientUpdateModel,
+    ) -> Result<ClientModel, RepositoryError> {
+        tracing::trace!(
+            method = "update_by_id",
+            id,
+            client = ?client_update
+        );
+
+        let mut tables = self.store.lock()?;
+
+        let pg_client = tables
+            .clients
+            .iter_mut()
+            .find(|client| client.id == id)
+            .map(|client| {
+                if let Some(name) = &client_update.name {
+                    client.name = name.to_owned();
+                }
+
+                if let Some(description) = &client_update.description {
+                    client.description = description.to_owned();
+                }
+
+                if let Some(homepage_url) = &client_update.homepage_url {
+                    client.homepage_url = homepage_url.to_owned();
+                }
+
+                client.clone()
+            })
+            .ok_or_else(|| query_failed(QueryFailure::NotUpdated, "client not updated"))?;
+
+        Ok(ClientMapper::from_pg(pg_client))
+    }
+
+    async fn delete_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_id", id);
+
+        let mut tables = self.store.lock()?;
+
+        expect_one_deleted(tables.delete_clients(|client: &PgClient| client.id == id))
+    }
+}
@@ -0,0 +1,157 @@
+use std::{cmp::Reverse, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        memory::{expect_one_deleted, query_failed, to_pg_list, InMemoryStore},
+        pg::models::PgConsent,
+        repositories::{ConsentRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::{AuthorizationDetailMapper, ConsentMapper},
+        models::{ConsentCreateModel, ConsentModel},
+    },
+};
+
+pub struct InMemoryConsentRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl ConsentRepository for InMemoryConsentRepository {
+    async fn upsert(
+        &self,
+        _db_context: &Arc<DbContext>,
+        consent_create: &ConsentCreateModel,
+    ) -> Result<ConsentModel, RepositoryError> {
+        tracing::trace!(method = "upsert", ?consent_create);
+
+        let mut tables = self.store.lock()?;
+
+        if !tables.has_user(&consent_create.user_id)
+            || !tables.has_client(consent_create.client_id.as_str())
+        {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "consents violates a foreign key constraint",
+            ));
+        }
+
+        let now = Utc::now().naive_utc();
+        let scopes = to_pg_list(&consent_create.scopes);
+        let authorization_details =
+            AuthorizationDetailMapper::vec_to_pg_value(&consent_create.authorization_details);
+
+        let existing = tables.consents.iter_mut().find(|consent| {
+            consent.user_id == consent_create.user_id
+                && consent.client_id == consent_create.client_id
+        });
+
+        let pg_consent = match existing {
+            Some(consent) => {
+                consent.scopes = scopes;
+                consent.granted_at = now;
+                consent.expires_at = consent_create.expires_at;
+                consent.authorization_details = authorization_details;
+
+                consent.clone()
+            }
+            None => {
+                let consent = PgConsent {
+                    id: tables.next_id(),
+                    user_id: consent_create.user_id,
+                    client_id: consent_create.client_id.to_owned(),
+                    scopes,
+                    granted_at: now,
+                    expires_at: consent_create.expires_at,
+                    authorization_details,
+                };
+
+                tables.consents.push(consent.clone());
+
+                consent
+            }
+        };
+
+        Ok(ConsentMapper::from_pg(pg_consent))
+    }
+
+    async fn get_by_user_id_and_client_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        client_id: &str,
+    ) -> Result<ConsentModel, RepositoryError> {
+        tracing::trace!(method = "get_by_user_id_and_client_id", ?user_id, client_id);
+
+        let tables = self.store.lock()?;
+        let now = Utc::now().naive_utc();
+
+        let pg_consent = tables
+            .consents
+            .iter()
+            .find(|consent| {
+                &consent.user_id == user_id
+                    && consent.client_id == client_id
+                    && consent.expires_at.is_none_or(|expires_at| expires_at > now)
+            })
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "consent not found"))?;
+
+        Ok(ConsentMapper::from_pg(pg_consent))
+    }
+
+    async fn get_all_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<Vec<ConsentModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_user_id", ?user_id);
+
+        let tables = self.store.lock()?;
+        let now = Utc::now().naive_utc();
+
+        let mut pg_consents = tables
+            .consents
+            .iter()
+            .filter(|consent| {
+                &consent.user_id == user_id
+                    && consent.expires_at.is_none_or(|expires_at| expires_at > now)
+            })
+            .cloned()
+            .collect::<Vec<PgConsent>>();
+
+        pg_consents.sort_by_key(|consent| Reverse(consent.granted_at));
+
+        Ok(pg_consents
+            .into_iter()
+            .map(ConsentMapper::from_pg)
+            .collect::<Vec<ConsentModel>>())
+    }
+
+    async fn delete_by_user_id_and_client_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        client_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(
+            method = "delete_by_user_id_and_client_id",
+            ?user_id,
+            client_id
+        );
+
+        let mut tables = self.store.lock()?;
+
+        let before = tables.consents.len();
+        tables
+            .consents
+            .retain(|consent| &consent.user_id != user_id || consent.client_id != client_id);
+
+        expect_one_deleted(before - tables.consents.len())
+    }
+}
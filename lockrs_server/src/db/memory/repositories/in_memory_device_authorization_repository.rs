@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+
+use crate::{
+    db::{
+        digest_token,
+        memory::{expect_one_deleted, is_live, query_failed, to_pg_list, InMemoryStore},
+        pg::models::PgDeviceAuthorization,
+        repositories::{DeviceAuthorizationRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::DeviceAuthorizationMapper,
+        models::{DeviceAuthorizationCreateModel, DeviceAuthorizationModel},
+    },
+};
+
+pub struct InMemoryDeviceAuthorizationRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl DeviceAuthorizationRepository for InMemoryDeviceAuthorizationRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        device_authorization_create: &DeviceAuthorizationCreateModel,
+    ) -> Result<DeviceAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "create");
+
+        let mut tables = self.store.lock()?;
+        let digest = digest_token(device_authorization_create.device_code.as_str());
+
+        if tables
+            .device_authorizations
+            .iter()
+            .any(|device_authorization| {
+                device_authorization.user_code == device_authorization_create.user_code
+                    || device_authorization.device_code == digest
+            })
+        {
+            return Err(query_failed(
+                QueryFailure::AlreadyExists,
+                "device_authorizations violates a unique constraint",
+            ));
+        }
+
+        if !tables.has_client(device_authorization_create.client_id.as_str()) {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "device_authorizations violates a foreign key constraint",
+            ));
+        }
+
+        let pg_device_authorization = PgDeviceAuthorization {
+            id: tables.next_id(),
+            client_id: device_authorization_create.client_id.to_owned(),
+            user_code: device_authorization_create.user_code.to_owned(),
+            device_code: digest,
+            created_at: Utc::now().naive_utc(),
+            expires_at: device_authorization_create.expires_at,
+            scopes: to_pg_list(&device_authorization_create.scopes),
+        };
+
+        tables
+            .device_authorizations
+            .push(pg_device_authorization.clone());
+
+        let mut device_authorization = DeviceAuthorizationMapper::from_pg(pg_device_authorization);
+        device_authorization.device_code = device_authorization_create.device_code.to_owned();
+
+        Ok(device_authorization)
+    }
+
+    async fn get_by_user_code(
+        &self,
+        _db_context: &Arc<DbContext>,
+        code: &str,
+    ) -> Result<DeviceAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "get_by_user_code");
+
+        let tables = self.store.lock()?;
+
+        let pg_device_authorization = tables
+            .device_authorizations
+            .iter()
+            .find(|device_authorization| {
+                device_authorization.user_code == code
+                    && is_live(
+                        &device_authorization.created_at,
+                        &device_authorization.expires_at,
+                    )
+            })
+            .cloned()
+            .ok_or_else(|| {
+                query_failed(QueryFailure::NotFound, "device authorization not found")
+            })?;
+
+        Ok(DeviceAuthorizationMapper::from_pg(pg_device_authorization))
+    }
+
+    async fn get_by_device_code(
+        &self,
+        _db_context: &Arc<DbContext>,
+        code: &str,
+    ) -> Result<DeviceAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "get_by_device_code");
+
+        let tables = self.store.lock()?;
+        let digest = digest_token(code);
+
+        let pg_device_authorization = tables
+            .device_authorizations
+            .iter()
+            .find(|device_authorization| {
+                device_authorization.device_code == digest
+                    && is_live(
+                        &device_authorization.created_at,
+                        &device_authorization.expires_at,
+                    )
+            })
+            .cloned()
+            .ok_or_else(|| {
+                query_failed(QueryFailure::NotFound, "device authorization not found")
+            })?;
+
+        let mut device_authorization = DeviceAuthorizationMapper::from_pg(pg_device_authorization);
+        device_authorization.device_code = code.to_owned();
+
+        Ok(device_authorization)
+    }
+
+    async fn delete_by_device_code(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_device_code");
+
+        let mut tables = self.store.lock()?;
+        let digest = digest_token(id);
+
+        let before = tables.device_authorizations.len();
+        tables
+            .device_authorizations
+            .retain(|device_authorization| device_authorization.device_code != digest);
+
+        expect_one_deleted(before - tables.device_authorizations.len())
+    }
+
+    async fn delete_expired(
+        &self,
+        _db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "delete_expired", ?expired_before, limit);
+
+        let mut tables = self.store.lock()?;
+
+        let expired_ids = tables
+            .device_authorizations
+            .iter()
+            .filter(|device_authorization| &device_authorization.expires_at < expired_before)
+            .take(limit as usize)
+            .map(|device_authorization| device_authorization.id)
+            .collect::<Vec<i32>>();
+
+        tables
+            .device_authorizations
+            .retain(|device_authorization| !expired_ids.contains(&device_authorization.id));
+
+        Ok(expired_ids.len())
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+
+use crate::{
+    api::v1::{
+        mappers::FederatedIdentityMapper,
+        models::{FederatedIdentityCreateModel, FederatedIdentityModel},
+    },
+    db::{
+        memory::{query_failed, InMemoryStore},
+        pg::models::PgFederatedIdentity,
+        repositories::{FederatedIdentityRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+};
+
+pub struct InMemoryFederatedIdentityRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl FederatedIdentityRepository for InMemoryFederatedIdentityRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        identity_create: &FederatedIdentityCreateModel,
+    ) -> Result<FederatedIdentityModel, RepositoryError> {
+        tracing::trace!(method = "create", ?identity_create);
+
+        let mut tables = self.store.lock()?;
+
+        if tables.federated_identities.iter().any(|identity| {
+            identity.provider == identity_create.provider
+                && identity.subject == identity_create.subject
+        }) {
+            return Err(query_failed(
+                QueryFailure::AlreadyExists,
+                "federated_identities violates a unique constraint",
+            ));
+        }
+
+        if !tables.has_user(&identity_create.user_id) {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "federated_identities violates a foreign key constraint",
+            ));
+        }
+
+        let pg_identity = PgFederatedIdentity {
+            id: tables.next_id(),
+            user_id: identity_create.user_id,
+            provider: identity_create.provider.to_owned(),
+            subject: identity_create.subject.to_owned(),
+            created_at: Utc::now().naive_utc(),
+        };
+
+        tables.federated_identities.push(pg_identity.clone());
+
+        Ok(FederatedIdentityMapper::from_pg(pg_identity))
+    }
+
+    async fn get_by_provider_and_subject(
+        &self,
+        _db_context: &Arc<DbContext>,
+        provider: &str,
+        subject: &str,
+    ) -> Result<FederatedIdentityModel, RepositoryError> {
+        tracing::trace!(method = "get_by_provider_and_subject", provider, subject);
+
+        let tables = self.store.lock()?;
+
+        tables
+            .federated_identities
+            .iter()
+            .find(|identity| identity.provider == provider && identity.subject == subject)
+            .cloned()
+            .map(FederatedIdentityMapper::from_pg)
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "federated identity not found"))
+    }
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api::v1::models::FederationStateModel,
+    db::{
+        digest::digest_token,
+        memory::{query_failed, InMemoryStore},
+        repositories::{FederationStateRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+};
+
+pub struct InMemoryFederationStateRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl FederationStateRepository for InMemoryFederationStateRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        state: &str,
+        federation_state: &FederationStateModel,
+    ) -> Result<FederationStateModel, RepositoryError> {
+        tracing::trace!(method = "create", ?federation_state);
+
+        let mut tables = self.store.lock()?;
+        tables
+            .federation_states
+            .insert(digest_token(state), federation_state.clone());
+
+        Ok(federation_state.clone())
+    }
+
+    async fn take_by_state(
+        &self,
+        _db_context: &Arc<DbContext>,
+        state: &str,
+    ) -> Result<FederationStateModel, RepositoryError> {
+        tracing::trace!(method = "take_by_state");
+
+        let mut tables = self.store.lock()?;
+        let now = Utc::now().timestamp_millis();
+
+        tables
+            .federation_states
+            .remove(&digest_token(state))
+            .filter(|federation_state| federation_state.expires_at > now)
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "federation state not found"))
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api::v1::models::LoginAttemptModel,
+    db::{
+        memory::{query_failed, InMemoryStore},
+        repositories::{LoginAttemptRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+};
+
+pub struct InMemoryLoginAttemptRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl LoginAttemptRepository for InMemoryLoginAttemptRepository {
+    async fn get_by_key(
+        &self,
+        _db_context: &Arc<DbContext>,
+        key: &str,
+    ) -> Result<LoginAttemptModel, RepositoryError> {
+        tracing::trace!(method = "get_by_key", key);
+
+        let tables = self.store.lock()?;
+        let now = Utc::now().timestamp_millis();
+
+        tables
+            .login_attempts
+            .get(key)
+            .filter(|login_attempt| login_attempt.expires_at > now)
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "login attempts not found"))
+    }
+
+    async fn record_failure_by_key(
+        &self,
+        _db_context: &Arc<DbContext>,
+        key: &str,
+        expires_at: i64,
+    ) -> Result<LoginAttemptModel, RepositoryError> {
+        tracing::trace!(method = "record_failure_by_key", key, expires_at);
+
+        let mut tables = self.store.lock()?;
+        let now = Utc::now().timestamp_millis();
+
+        let login_attempt = tables
+            .login_attempts
+            .entry(key.to_owned())
+            .or_insert_with(|| LoginAttemptModel::new(0, None, expires_at));
+
+        // an expired counter starts over, like a redis key that has expired
+        if login_attempt.expires_at <= now {
+            *login_attempt = LoginAttemptModel::new(0, None, expires_at);
+        }
+
+        login_attempt.failures += 1;
+        login_attempt.expires_at = expires_at;
+
+        Ok(login_attempt.clone())
+    }
+
+    async fn lock_by_key(
+        &self,
+        _db_context: &Arc<DbContext>,
+        key: &str,
+        locked_until: i64,
+        expires_at: i64,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "lock_by_key", key, locked_until, expires_at);
+
+        let mut tables = self.store.lock()?;
+
+        let login_attempt = tables
+            .login_attempts
+            .entry(key.to_owned())
+            .or_insert_with(|| LoginAttemptModel::new(0, None, expires_at));
+
+        login_attempt.locked_until = Some(locked_until);
+        login_attempt.expires_at = expires_at;
+
+        Ok(())
+    }
+
+    async fn delete_by_key(
+        &self,
+        _db_context: &Arc<DbContext>,
+        key: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_key", key);
+
+        let mut tables = self.store.lock()?;
+        tables.login_attempts.remove(key);
+
+        Ok(())
+    }
+}
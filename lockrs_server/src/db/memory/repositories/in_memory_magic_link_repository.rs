@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api::v1::models::MagicLinkModel,
+    db::{
+        memory::{query_failed, InMemoryStore},
+        repositories::{MagicLinkRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+};
+
+pub struct InMemoryMagicLinkRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl MagicLinkRepository for InMemoryMagicLinkRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        magic_link: &MagicLinkModel,
+    ) -> Result<MagicLinkModel, RepositoryError> {
+        tracing::trace!(method = "create", ?magic_link);
+
+        let mut tables = self.store.lock()?;
+        tables
+            .magic_links
+            .insert(magic_link.email.to_owned(), magic_link.clone());
+
+        Ok(magic_link.clone())
+    }
+
+    async fn take_by_email(
+        &self,
+        _db_context: &Arc<DbContext>,
+        email: &str,
+    ) -> Result<MagicLinkModel, RepositoryError> {
+        tracing::trace!(method = "take_by_email", email);
+
+        let mut tables = self.store.lock()?;
+        let now = Utc::now().timestamp_millis();
+
+        tables
+            .magic_links
+            .remove(email)
+            .filter(|magic_link| magic_link.expires_at > now)
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "magic link not found"))
+    }
+}
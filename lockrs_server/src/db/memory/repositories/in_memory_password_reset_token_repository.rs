@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api::v1::models::PasswordResetTokenModel,
+    db::{
+        digest::digest_token,
+        memory::{query_failed, InMemoryStore},
+        repositories::{PasswordResetTokenRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+};
+
+pub struct InMemoryPasswordResetTokenRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for InMemoryPasswordResetTokenRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        token: &str,
+        reset_token: &PasswordResetTokenModel,
+    ) -> Result<PasswordResetTokenModel, RepositoryError> {
+        tracing::trace!(method = "create", ?reset_token);
+
+        let mut tables = self.store.lock()?;
+        tables
+            .password_reset_tokens
+            .insert(digest_token(token), reset_token.clone());
+
+        Ok(reset_token.clone())
+    }
+
+    async fn take_by_token(
+        &self,
+        _db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<PasswordResetTokenModel, RepositoryError> {
+        tracing::trace!(method = "take_by_token");
+
+        let mut tables = self.store.lock()?;
+        let now = Utc::now().timestamp_millis();
+
+        tables
+            .password_reset_tokens
+            .remove(&digest_token(token))
+            .filter(|reset_token| reset_token.expires_at > now)
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "password reset token not found"))
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    db::{
+        digest::digest_token,
+        memory::{query_failed, InMemoryStore},
+        repositories::{PushedAuthorizationRequestRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::models::AuthorizationRequestModel,
+};
+
+pub struct InMemoryPushedAuthorizationRequestRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl PushedAuthorizationRequestRepository for InMemoryPushedAuthorizationRequestRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        request_uri: &str,
+        authorization_request: &AuthorizationRequestModel,
+        expires_at: i64,
+    ) -> Result<AuthorizationRequestModel, RepositoryError> {
+        tracing::trace!(method = "create", ?authorization_request, expires_at);
+
+        let mut tables = self.store.lock()?;
+        tables.pushed_authorization_requests.insert(
+            digest_token(request_uri),
+            (expires_at, authorization_request.clone()),
+        );
+
+        Ok(authorization_request.clone())
+    }
+
+    async fn get_by_request_uri(
+        &self,
+        _db_context: &Arc<DbContext>,
+        request_uri: &str,
+    ) -> Result<AuthorizationRequestModel, RepositoryError> {
+        tracing::trace!(method = "get_by_request_uri");
+
+        let tables = self.store.lock()?;
+        let now = Utc::now().timestamp_millis();
+
+        tables
+            .pushed_authorization_requests
+            .get(&digest_token(request_uri))
+            .filter(|(expires_at, _)| *expires_at > now)
+            .map(|(_, authorization_request)| authorization_request.clone())
+            .ok_or_else(|| {
+                query_failed(
+                    QueryFailure::NotFound,
+                    "pushed authorization request not found",
+                )
+            })
+    }
+
+    async fn take_by_request_uri(
+        &self,
+        _db_context: &Arc<DbContext>,
+        request_uri: &str,
+    ) -> Result<AuthorizationRequestModel, RepositoryError> {
+        tracing::trace!(method = "take_by_request_uri");
+
+        let mut tables = self.store.lock()?;
+        let now = Utc::now().timestamp_millis();
+
+        tables
+            .pushed_authorization_requests
+            .remove(&digest_token(request_uri))
+            .filter(|(expires_at, _)| *expires_at > now)
+            .map(|(_, authorization_request)| authorization_request)
+            .ok_or_else(|| {
+                query_failed(
+                    QueryFailure::NotFound,
+                    "pushed authorization request not found",
+                )
+            })
+    }
+}
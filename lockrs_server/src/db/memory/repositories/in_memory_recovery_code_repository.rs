@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+use uuid::Uuid;
+
+use crate::db::{
+    memory::{query_failed, InMemoryStore},
+    pg::models::PgRecoveryCode,
+    repositories::{QueryFailure, RecoveryCodeRepository, RepositoryError},
+    DbContext,
+};
+
+pub struct InMemoryRecoveryCodeRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl RecoveryCodeRepository for InMemoryRecoveryCodeRepository {
+    async fn replace_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        code_hashes: &[String],
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(
+            method = "replace_by_user_id",
+            ?user_id,
+            count = code_hashes.len()
+        );
+
+        let mut tables = self.store.lock()?;
+
+        if !tables.has_user(user_id) {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "recovery_codes violates a foreign key constraint",
+            ));
+        }
+
+        tables
+            .recovery_codes
+            .retain(|recovery_code| &recovery_code.user_id != user_id);
+
+        let now = Utc::now().naive_utc();
+
+        for code_hash in code_hashes {
+            let id = tables.next_id();
+
+            tables.recovery_codes.push(PgRecoveryCode {
+                id,
+                user_id: *user_id,
+                code_hash: code_hash.to_owned(),
+                created_at: now,
+                used_at: None,
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn use_by_user_id_and_hash(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        code_hash: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "use_by_user_id_and_hash", ?user_id);
+
+        let mut tables = self.store.lock()?;
+
+        let recovery_code = tables
+            .recovery_codes
+            .iter_mut()
+            .find(|recovery_code| {
+                &recovery_code.user_id == user_id
+                    && recovery_code.code_hash == code_hash
+                    && recovery_code.used_at.is_none()
+            })
+            .ok_or_else(|| query_failed(QueryFailure::NotUpdated, "recovery code not updated"))?;
+
+        recovery_code.used_at = Some(Utc::now().naive_utc());
+
+        Ok(())
+    }
+
+    async fn delete_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_user_id", ?user_id);
+
+        let mut tables = self.store.lock()?;
+        tables
+            .recovery_codes
+            .retain(|recovery_code| &recovery_code.user_id != user_id);
+
+        Ok(())
+    }
+}
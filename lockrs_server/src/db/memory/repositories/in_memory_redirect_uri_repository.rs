@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        memory::{expect_one_deleted, query_failed, InMemoryStore},
+        repositories::{QueryFailure, RedirectUriRepository, RepositoryError},
+        DbContext,
+    },
+    mappers::RedirectMapper,
+    models::{RedirectCreateModel, RedirectModel},
+};
+
+pub struct InMemoryRedirectUriRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl RedirectUriRepository for InMemoryRedirectUriRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        redirect_create: &RedirectCreateModel,
+    ) -> Result<RedirectModel, RepositoryError> {
+        tracing::trace!(
+            method = "create",
+            redirect = ?redirect_create
+        );
+
+        let mut tables = self.store.lock()?;
+
+        let pg_redirect = tables.insert_redirect_uri(
+            redirect_create.client_id.as_str(),
+            redirect_create.uri.as_str(),
+            redirect_create.match_mode.as_str(),
+        )?;
+
+        Ok(RedirectMapper::from_pg(pg_redirect))
+    }
+
+    async fn get_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<RedirectModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", ?id);
+
+        let tables = self.store.lock()?;
+
+        let pg_redirect = tables
+            .redirect_uris
+            .iter()
+            .find(|redirect_uri| &redirect_uri.id == id)
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "redirect uri not found"))?;
+
+        Ok(RedirectMapper::from_pg(pg_redirect))
+    }
+
+    async fn get_by_uri(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+        uri: &Url,
+    ) -> Result<RedirectModel, RepositoryError> {
+        tracing::trace!(method = "get_by_uri", client_id, ?uri);
+
+        let tables = self.store.lock()?;
+
+        let pg_redirect = tables
+            .redirect_uris
+            .iter()
+            .find(|redirect_uri| {
+                redirect_uri.client_id == client_id && redirect_uri.uri == uri.as_str()
+            })
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "redirect uri not found"))?;
+
+        Ok(RedirectMapper::from_pg(pg_redirect))
+    }
+
+    async fn get_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<Uuid, RepositoryError> {
+        tracing::trace!(method = "get_user_id", ?id);
+
+        let tables = self.store.lock()?;
+
+        tables
+            .redirect_uris
+            .iter()
+            .find(|redirect_uri| &redirect_uri.id == id)
+            .and_then(|redirect_uri| {
+                tables
+                    .clients
+                    .iter()
+                    .find(|client| client.id == redirect_uri.client_id)
+            })
+            .map(|client| client.user_id)
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "redirect uri not found"))
+    }
+
+    async fn get_all_by_client_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<RedirectModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_client_id", client_id);
+
+        let tables = self.store.lock()?;
+
+        Ok(tables
+            .redirect_uris
+            .iter()
+            .filter(|redirect_uri| redirect_uri.client_id == client_id)
+            .cloned()
+            .map(RedirectMapper::from_pg)
+            .collect::<Vec<RedirectModel>>())
+    }
+
+    async fn delete_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_id", ?id);
+
+        let mut tables = self.store.lock()?;
+
+        let deleted = tables
+            .redirect_uris
+            .iter()
+            .filter(|redirect_uri| &redirect_uri.id == id)
+            .map(|redirect_uri| {
+                (
+                    redirect_uri.client_id.to_owned(),
+                    redirect_uri.uri.to_owned(),
+                )
+            })
+            .collect::<Vec<(String, String)>>();
+
+        tables
+            .redirect_uris
+            .retain(|redirect_uri| &redirect_uri.id != id);
+
+        // authorization codes cascade from the redirect they were issued for
+        tables.authorization_codes.retain(|authorization_code| {
+            !deleted.iter().any(|(client_id, uri)| {
+                &authorization_code.client_id == client_id
+                    && &authorization_code.redirect_uri == uri
+            })
+        });
+
+        expect_one_deleted(deleted.len())
+    }
+}
@@ -0,0 +1,234 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        digest_token,
+        memory::{expect_one_deleted, is_live, query_failed, to_pg_list, InMemoryStore},
+        pg::models::PgRefreshToken,
+        repositories::{QueryFailure, RefreshTokenRepository, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::{AuthorizationDetailMapper, RefreshTokenMapper},
+        models::{RefreshTokenCreateModel, RefreshTokenModel},
+    },
+};
+
+pub struct InMemoryRefreshTokenRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl RefreshTokenRepository for InMemoryRefreshTokenRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        token_create: &RefreshTokenCreateModel,
+    ) -> Result<RefreshTokenModel, RepositoryError> {
+        tracing::trace!(method = "create");
+
+        let mut tables = self.store.lock()?;
+        let digest = digest_token(token_create.token.as_str());
+
+        if tables.refresh_tokens.iter().any(|refresh_token| {
+            refresh_token.token == digest
+                || refresh_token.access_token_id == token_create.access_token_id
+        }) {
+            return Err(query_failed(
+                QueryFailure::AlreadyExists,
+                "refresh_tokens violates a unique constraint",
+            ));
+        }
+
+        if !tables
+            .access_tokens
+            .iter()
+            .any(|access_token| access_token.id == token_create.access_token_id)
+            || !tables.has_client(token_create.client_id.as_str())
+            || token_create
+                .user_id
+                .is_some_and(|user_id| !tables.has_user(&user_id))
+        {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "refresh_tokens violates a foreign key constraint",
+            ));
+        }
+
+        let pg_token = PgRefreshToken {
+            id: tables.next_id(),
+            access_token_id: token_create.access_token_id,
+            token: digest,
+            client_id: token_create.client_id.to_owned(),
+            user_id: token_create.user_id,
+            created_at: Utc::now().naive_utc(),
+            expires_at: token_create.expires_at,
+            used: false,
+            scopes: to_pg_list(&token_create.scopes),
+            authorization_details: AuthorizationDetailMapper::vec_to_pg_value(
+                &token_create.authorization_details,
+            ),
+            grant_created_at: token_create.grant_created_at,
+        };
+
+        tables.refresh_tokens.push(pg_token.clone());
+
+        let mut token = RefreshTokenMapper::from_pg(pg_token);
+        token.token = token_create.token.to_owned();
+
+        Ok(token)
+    }
+
+    async fn get_by_token(
+        &self,
+        _db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<RefreshTokenModel, RepositoryError> {
+        tracing::trace!(method = "get_by_token");
+
+        let tables = self.store.lock()?;
+        let digest = digest_token(token);
+
+        let pg_token = tables
+            .refresh_tokens
+            .iter()
+            .find(|refresh_token| {
+                refresh_token.token == digest
+                    && is_live(&refresh_token.created_at, &refresh_token.expires_at)
+                    && !refresh_token.used
+            })
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "refresh token not found"))?;
+
+        let mut refresh_token = RefreshTokenMapper::from_pg(pg_token);
+        refresh_token.token = token.to_owned();
+
+        Ok(refresh_token)
+    }
+
+    async fn use_by_token(
+        &self,
+        _db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<RefreshTokenModel, RepositoryError> {
+        tracing::trace!(method = "use_by_token");
+
+        let mut tables = self.store.lock()?;
+        let digest = digest_token(token);
+
+        let pg_token = tables
+            .refresh_tokens
+            .iter_mut()
+            .find(|refresh_token| {
+                refresh_token.token == digest
+                    && is_live(&refresh_token.created_at, &refresh_token.expires_at)
+                    && !refresh_token.used
+            })
+            .map(|refresh_token| {
+                refresh_token.used = true;
+                refresh_token.clone()
+            })
+            .ok_or_else(|| query_failed(QueryFailure::NotUpdated, "refresh token not updated"))?;
+
+        let mut refresh_token = RefreshTokenMapper::from_pg(pg_token);
+        refresh_token.token = token.to_owned();
+
+        Ok(refresh_token)
+    }
+
+    async fn revoke_all_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "revoke_all_by_user_id", ?user_id);
+
+        let mut tables = self.store.lock()?;
+
+        let mut revoked = 0;
+
+        for refresh_token in tables.refresh_tokens.iter_mut().filter(|refresh_token| {
+            refresh_token.user_id.as_ref() == Some(user_id) && !refresh_token.used
+        }) {
+            refresh_token.used = true;
+            revoked += 1;
+        }
+
+        Ok(revoked)
+    }
+
+    async fn revoke_all_by_user_id_and_client_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        client_id: &str,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(
+            method = "revoke_all_by_user_id_and_client_id",
+            ?user_id,
+            client_id
+        );
+
+        let mut tables = self.store.lock()?;
+
+        let mut revoked = 0;
+
+        for refresh_token in tables.refresh_tokens.iter_mut().filter(|refresh_token| {
+            refresh_token.user_id.as_ref() == Some(user_id)
+                && refresh_token.client_id == client_id
+                && !refresh_token.used
+        }) {
+            refresh_token.used = true;
+            revoked += 1;
+        }
+
+        Ok(revoked)
+    }
+
+    async fn delete_by_token(
+        &self,
+        _db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_token");
+
+        let mut tables = self.store.lock()?;
+        let digest = digest_token(token);
+
+        let before = tables.refresh_tokens.len();
+        tables
+            .refresh_tokens
+            .retain(|refresh_token| refresh_token.token != digest);
+
+        expect_one_deleted(before - tables.refresh_tokens.len())
+    }
+
+    async fn delete_expired(
+        &self,
+        _db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "delete_expired", ?expired_before, limit);
+
+        let mut tables = self.store.lock()?;
+
+        let expired_ids = tables
+            .refresh_tokens
+            .iter()
+            .filter(|refresh_token| &refresh_token.expires_at < expired_before)
+            .take(limit as usize)
+            .map(|refresh_token| refresh_token.id)
+            .collect::<Vec<i32>>();
+
+        tables
+            .refresh_tokens
+            .retain(|refresh_token| !expired_ids.contains(&refresh_token.id));
+
+        Ok(expired_ids.len())
+    }
+}
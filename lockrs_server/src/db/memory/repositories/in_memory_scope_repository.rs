@@ -0,0 +1,255 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    db::{
+        memory::{expect_one_deleted, query_failed, InMemoryStore},
+        pg::models::{PgAllowedScope, PgScope},
+        repositories::{QueryFailure, RepositoryError, ScopeRepository},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::ScopeMapper,
+        models::{
+            AllowedScopeModel, ScopeCreateModel, ScopeDefinitionModel, ScopeModel, ScopeUpdateModel,
+        },
+    },
+};
+
+pub struct InMemoryScopeRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl ScopeRepository for InMemoryScopeRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        scope_create: &ScopeCreateModel,
+    ) -> Result<ScopeDefinitionModel, RepositoryError> {
+        tracing::trace!(method = "create", ?scope_create);
+
+        let mut tables = self.store.lock()?;
+
+        if tables
+            .scopes
+            .iter()
+            .any(|scope| scope.name == scope_create.scope)
+        {
+            return Err(query_failed(
+                QueryFailure::AlreadyExists,
+                "scopes violates a unique constraint",
+            ));
+        }
+
+        if !tables.has_client(scope_create.client_id.as_str()) {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "scopes violates a foreign key constraint",
+            ));
+        }
+
+        let pg_scope = PgScope {
+            id: tables.next_id(),
+            name: scope_create.scope.to_owned(),
+            description: scope_create.description.to_owned(),
+            client_id: Some(scope_create.client_id.to_owned()),
+            consent_text: scope_create.consent_text.to_owned(),
+        };
+
+        tables.scopes.push(pg_scope.clone());
+
+        Ok(ScopeMapper::from_pg(pg_scope))
+    }
+
+    async fn get_from_list(
+        &self,
+        _db_context: &Arc<DbContext>,
+        scopes_list: &[String],
+    ) -> Result<ScopeModel, RepositoryError> {
+        tracing::trace!(
+            method = "get_from_list",
+            scopes = ?scopes_list
+        );
+
+        let tables = self.store.lock()?;
+
+        let scopes = tables
+            .scopes
+            .iter()
+            .filter(|scope| scopes_list.contains(&scope.name))
+            .map(|scope| scope.name.to_owned())
+            .collect::<Vec<String>>();
+
+        Ok(ScopeModel::new(scopes.as_slice()))
+    }
+
+    async fn get_all_by_client_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<ScopeDefinitionModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_client_id", client_id);
+
+        let tables = self.store.lock()?;
+
+        let mut pg_scopes = tables
+            .scopes
+            .iter()
+            .filter(|scope| scope.client_id.as_deref() == Some(client_id))
+            .cloned()
+            .collect::<Vec<PgScope>>();
+
+        pg_scopes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(pg_scopes
+            .into_iter()
+            .map(ScopeMapper::from_pg)
+            .collect::<Vec<ScopeDefinitionModel>>())
+    }
+
+    async fn get_allowed_by_client_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<AllowedScopeModel>, RepositoryError> {
+        tracing::trace!(method = "get_allowed_by_client_id", client_id);
+
+        let tables = self.store.lock()?;
+
+        let mut pg_allowed_scopes = tables
+            .allowed_scopes
+            .iter()
+            .filter(|allowed_scope| allowed_scope.client_id == client_id)
+            .cloned()
+            .collect::<Vec<PgAllowedScope>>();
+
+        pg_allowed_scopes.sort_by(|a, b| a.scope.cmp(&b.scope));
+
+        Ok(pg_allowed_scopes
+            .into_iter()
+            .map(ScopeMapper::allowed_from_pg)
+            .collect::<Vec<AllowedScopeModel>>())
+    }
+
+    async fn replace_allowed_by_client_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+        allowed_scopes: &[AllowedScopeModel],
+    ) -> Result<Vec<AllowedScopeModel>, RepositoryError> {
+        tracing::trace!(
+            method = "replace_allowed_by_client_id",
+            client_id,
+            ?allowed_scopes
+        );
+
+        let mut tables = self.store.lock()?;
+
+        let pg_allowed_scopes = allowed_scopes
+            .iter()
+            .map(|allowed_scope| PgAllowedScope {
+                client_id: client_id.to_owned(),
+                scope: allowed_scope.scope.to_owned(),
+                is_default: allowed_scope.is_default,
+            })
+            .collect::<Vec<PgAllowedScope>>();
+
+        // validate the whole set before touching the table, so a rejected replace leaves the
+        // previous allow list in place
+        for (index, pg_allowed_scope) in pg_allowed_scopes.iter().enumerate() {
+            if pg_allowed_scopes[..index]
+                .iter()
+                .any(|other| other.scope == pg_allowed_scope.scope)
+            {
+                return Err(query_failed(
+                    QueryFailure::AlreadyExists,
+                    "allowed_scopes violates a unique constraint",
+                ));
+            }
+
+            if !tables.has_client(client_id)
+                || !tables
+                    .scopes
+                    .iter()
+                    .any(|scope| scope.name == pg_allowed_scope.scope)
+            {
+                return Err(query_failed(
+                    QueryFailure::NotCreated,
+                    "allowed_scopes violates a foreign key constraint",
+                ));
+            }
+        }
+
+        tables
+            .allowed_scopes
+            .retain(|allowed_scope| allowed_scope.client_id != client_id);
+        tables
+            .allowed_scopes
+            .extend(pg_allowed_scopes.iter().cloned());
+
+        Ok(pg_allowed_scopes
+            .into_iter()
+            .map(ScopeMapper::allowed_from_pg)
+            .collect::<Vec<AllowedScopeModel>>())
+    }
+
+    async fn update_by_name(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
+        scope_update: &ScopeUpdateModel,
+    ) -> Result<ScopeDefinitionModel, RepositoryError> {
+        tracing::trace!(method = "update_by_name", client_id, name, ?scope_update);
+
+        let mut tables = self.store.lock()?;
+
+        let pg_scope = tables
+            .scopes
+            .iter_mut()
+            .find(|scope| scope.client_id.as_deref() == Some(client_id) && scope.name == name)
+            .map(|scope| {
+                if let Some(description) = &scope_update.description {
+                    scope.description = description.to_owned();
+                }
+
+                if let Some(consent_text) = &scope_update.consent_text {
+                    scope.consent_text = Some(consent_text.to_owned());
+                }
+
+                scope.clone()
+            })
+            .ok_or_else(|| query_failed(QueryFailure::NotUpdated, "scope not updated"))?;
+
+        Ok(ScopeMapper::from_pg(pg_scope))
+    }
+
+    async fn delete_by_name(
+        &self,
+        _db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_name", client_id, name);
+
+        let mut tables = self.store.lock()?;
+
+        let before = tables.scopes.len();
+        tables
+            .scopes
+            .retain(|scope| scope.client_id.as_deref() != Some(client_id) || scope.name != name);
+
+        let deleted = before - tables.scopes.len();
+
+        // allowed scopes cascade from the scope they grant
+        if deleted == 1 {
+            tables
+                .allowed_scopes
+                .retain(|allowed_scope| allowed_scope.scope != name);
+        }
+
+        expect_one_deleted(deleted)
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::models::SessionModel,
+    db::{
+        memory::{query_failed, InMemoryStore},
+        repositories::{QueryFailure, RepositoryError, SessionRepository},
+        DbContext,
+    },
+};
+
+pub struct InMemorySessionRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+impl InMemorySessionRepository {
+    /// stores the session under its user and moves the expiry of every session of the user to
+    /// the session's, like `HSET` followed by `PEXPIREAT` on the user's hash
+    fn put(&self, session: &SessionModel) -> Result<SessionModel, RepositoryError> {
+        let mut tables = self.store.lock()?;
+
+        let (expires_at, sessions) = tables.sessions.entry(session.user_id).or_default();
+        *expires_at = session.expires_at;
+        sessions.insert(session.id.to_owned(), session.clone());
+
+        Ok(session.clone())
+    }
+}
+
+#[async_trait]
+impl SessionRepository for InMemorySessionRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        session: &SessionModel,
+    ) -> Result<SessionModel, RepositoryError> {
+        tracing::trace!(method = "create");
+
+        self.put(session)
+    }
+
+    async fn get_by_hash(
+        &self,
+        _db_context: &Arc<DbContext>,
+        session_id: &str,
+        user_id: &Uuid,
+    ) -> Result<SessionModel, RepositoryError> {
+        tracing::trace!(method = "get_by_hash");
+
+        let tables = self.store.lock()?;
+        let now = Utc::now().timestamp_millis();
+
+        tables
+            .sessions
+            .get(user_id)
+            .filter(|(expires_at, _)| *expires_at > now)
+            .and_then(|(_, sessions)| sessions.get(session_id))
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "session not found"))
+    }
+
+    async fn update(
+        &self,
+        _db_context: &Arc<DbContext>,
+        session: &SessionModel,
+    ) -> Result<SessionModel, RepositoryError> {
+        tracing::trace!(method = "update");
+
+        self.put(session)
+    }
+
+    async fn delete_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_user_id", ?id);
+
+        let mut tables = self.store.lock()?;
+        tables.sessions.remove(id);
+
+        Ok(())
+    }
+
+    async fn delete_others_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &Uuid,
+        session_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_others_by_user_id", ?id);
+
+        let mut tables = self.store.lock()?;
+        if let Some((_, sessions)) = tables.sessions.get_mut(id) {
+            sessions.retain(|key, _| key == session_id);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api::v1::models::SessionTokenModel,
+    db::{
+        memory::{expect_one_deleted, query_failed, InMemoryStore},
+        repositories::{QueryFailure, RepositoryError, SessionTokenRepository},
+        DbContext,
+    },
+};
+
+pub struct InMemorySessionTokenRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl SessionTokenRepository for InMemorySessionTokenRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        token: &SessionTokenModel,
+    ) -> Result<SessionTokenModel, RepositoryError> {
+        tracing::trace!(method = "create");
+
+        let mut tables = self.store.lock()?;
+        tables
+            .session_tokens
+            .insert(token.token.to_owned(), token.clone());
+
+        Ok(token.clone())
+    }
+
+    async fn get_by_token(
+        &self,
+        _db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<SessionTokenModel, RepositoryError> {
+        tracing::trace!(method = "get_by_token");
+
+        let tables = self.store.lock()?;
+        let now = Utc::now().timestamp_millis();
+
+        tables
+            .session_tokens
+            .get(token)
+            .filter(|session_token| session_token.expires_at > now)
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "session token not found"))
+    }
+
+    async fn delete_by_token(
+        &self,
+        _db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_token");
+
+        let mut tables = self.store.lock()?;
+        let now = Utc::now().timestamp_millis();
+
+        // an expired key is already gone from redis, so deleting it affects nothing
+        let deleted = tables
+            .session_tokens
+            .remove(token)
+            .filter(|session_token| session_token.expires_at > now)
+            .map_or(0, |_| 1);
+
+        expect_one_deleted(deleted)
+    }
+}
@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        mappers::TotpMapper,
+        models::{TotpCreateModel, TotpModel},
+    },
+    db::{
+        memory::{expect_one_deleted, query_failed, InMemoryStore},
+        pg::models::PgTotpSecret,
+        repositories::{QueryFailure, RepositoryError, TotpRepository},
+        DbContext,
+    },
+};
+
+pub struct InMemoryTotpRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl TotpRepository for InMemoryTotpRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        totp_create: &TotpCreateModel,
+    ) -> Result<TotpModel, RepositoryError> {
+        tracing::trace!(method = "create", ?totp_create);
+
+        let mut tables = self.store.lock()?;
+
+        if !tables.has_user(&totp_create.user_id) {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "totp_secrets violates a foreign key constraint",
+            ));
+        }
+
+        tables.totp_secrets.retain(|totp_secret| {
+            totp_secret.user_id != totp_create.user_id || totp_secret.confirmed_at.is_some()
+        });
+
+        if tables
+            .totp_secrets
+            .iter()
+            .any(|totp_secret| totp_secret.user_id == totp_create.user_id)
+        {
+            return Err(query_failed(
+                QueryFailure::AlreadyExists,
+                "totp_secrets violates a unique constraint",
+            ));
+        }
+
+        let pg_totp_secret = PgTotpSecret {
+            user_id: totp_create.user_id,
+            secret: totp_create.secret.to_owned(),
+            confirmed_at: None,
+            last_used_step: None,
+            created_at: Utc::now().naive_utc(),
+        };
+
+        tables.totp_secrets.push(pg_totp_secret.clone());
+
+        Ok(TotpMapper::from_pg(pg_totp_secret))
+    }
+
+    async fn get_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<TotpModel, RepositoryError> {
+        tracing::trace!(method = "get_by_user_id", ?user_id);
+
+        let tables = self.store.lock()?;
+
+        tables
+            .totp_secrets
+            .iter()
+            .find(|totp_secret| &totp_secret.user_id == user_id)
+            .cloned()
+            .map(TotpMapper::from_pg)
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "totp secret not found"))
+    }
+
+    async fn confirm_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        step: i64,
+    ) -> Result<TotpModel, RepositoryError> {
+        tracing::trace!(method = "confirm_by_user_id", ?user_id, step);
+
+        let mut tables = self.store.lock()?;
+
+        let totp_secret = tables
+            .totp_secrets
+            .iter_mut()
+            .find(|totp_secret| {
+                &totp_secret.user_id == user_id && totp_secret.confirmed_at.is_none()
+            })
+            .ok_or_else(|| query_failed(QueryFailure::NotUpdated, "totp secret not updated"))?;
+
+        totp_secret.confirmed_at = Some(Utc::now().naive_utc());
+        totp_secret.last_used_step = Some(step);
+
+        Ok(TotpMapper::from_pg(totp_secret.clone()))
+    }
+
+    async fn use_step_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        step: i64,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "use_step_by_user_id", ?user_id, step);
+
+        let mut tables = self.store.lock()?;
+
+        let totp_secret = tables
+            .totp_secrets
+            .iter_mut()
+            .find(|totp_secret| {
+                &totp_secret.user_id == user_id
+                    && totp_secret.confirmed_at.is_some()
+                    && totp_secret
+                        .last_used_step
+                        .is_none_or(|last_used_step| last_used_step < step)
+            })
+            .ok_or_else(|| query_failed(QueryFailure::NotUpdated, "totp secret not updated"))?;
+
+        totp_secret.last_used_step = Some(step);
+
+        Ok(())
+    }
+
+    async fn delete_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_user_id", ?user_id);
+
+        let mut tables = self.store.lock()?;
+
+        let count = tables.totp_secrets.len();
+        tables
+            .totp_secrets
+            .retain(|totp_secret| &totp_secret.user_id != user_id);
+
+        expect_one_deleted(count - tables.totp_secrets.len())
+    }
+}
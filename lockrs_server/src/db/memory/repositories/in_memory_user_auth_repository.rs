@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        mappers::UserAuthMapper,
+        models::{UserAuthModel, UserRegisterModel},
+    },
+    db::{
+        memory::{query_failed, InMemoryStore, InMemoryTables},
+        pg::models::PgUser,
+        repositories::{QueryFailure, RepositoryError, UserAuthRepository},
+        DbContext,
+    },
+};
+
+pub struct InMemoryUserAuthRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+impl InMemoryUserAuthRepository {
+    fn insert(tables: &mut InMemoryTables, pg_user: PgUser) -> Result<PgUser, RepositoryError> {
+        if tables
+            .users
+            .iter()
+            .any(|user| user.id == pg_user.id || user.email == pg_user.email)
+        {
+            return Err(query_failed(
+                QueryFailure::AlreadyExists,
+                "users violates a unique constraint",
+            ));
+        }
+
+        tables.users.push(pg_user.clone());
+
+        Ok(pg_user)
+    }
+}
+
+#[async_trait]
+impl UserAuthRepository for InMemoryUserAuthRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_create: &UserRegisterModel,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "create", email = user_create.email);
+
+        let mut tables = self.store.lock()?;
+
+        let pg_user = Self::insert(
+            &mut tables,
+            PgUser {
+                id: Uuid::new_v4(),
+                email: user_create.email.to_owned(),
+                password_hash: user_create.password_hash.to_owned(),
+                email_verified: false,
+                password_version: 0,
+            },
+        )?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+
+    async fn create_raw(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user: &UserAuthModel,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::warn!(method = "create_raw", ?user);
+
+        let mut tables = self.store.lock()?;
+
+        let pg_user = Self::insert(
+            &mut tables,
+            PgUser {
+                id: user.id,
+                email: user.email.to_owned(),
+                password_hash: user.password_hash.to_owned(),
+                email_verified: user.email_verified,
+                password_version: user.password_version,
+            },
+        )?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+
+    async fn get_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", ?id);
+
+        let tables = self.store.lock()?;
+
+        let pg_user = tables
+            .users
+            .iter()
+            .find(|user| &user.id == id)
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "user not found"))?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+
+    async fn get_by_email(
+        &self,
+        _db_context: &Arc<DbContext>,
+        email: &str,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "get_by_email", email);
+
+        let tables = self.store.lock()?;
+
+        let pg_user = tables
+            .users
+            .iter()
+            .find(|user| user.email == email)
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "user not found"))?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+
+    async fn update_password_hash_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &Uuid,
+        password_hash: &str,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "update_password_hash_by_id", ?id);
+
+        let mut tables = self.store.lock()?;
+
+        let pg_user = tables
+            .users
+            .iter_mut()
+            .find(|user| &user.id == id)
+            .map(|user| {
+                user.password_hash = password_hash.to_owned();
+                user.clone()
+            })
+            .ok_or_else(|| query_failed(QueryFailure::NotUpdated, "user not updated"))?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+
+    async fn change_password_hash_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &Uuid,
+        password_hash: &str,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "change_password_hash_by_id", ?id);
+
+        let mut tables = self.store.lock()?;
+
+        let pg_user = tables
+            .users
+            .iter_mut()
+            .find(|user| &user.id == id)
+            .map(|user| {
+                user.password_hash = password_hash.to_owned();
+                user.password_version += 1;
+                user.clone()
+            })
+            .ok_or_else(|| query_failed(QueryFailure::NotUpdated, "user not updated"))?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+}
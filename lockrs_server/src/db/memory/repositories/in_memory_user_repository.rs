@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        memory::{expect_one_deleted, query_failed, InMemoryStore},
+        repositories::{QueryFailure, RepositoryError, UserRepository},
+        DbContext,
+    },
+    mappers::UserMapper,
+    models::{UserModel, UserUpdateModel},
+};
+
+pub struct InMemoryUserRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn get_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<UserModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", ?id);
+
+        let tables = self.store.lock()?;
+
+        let pg_user = tables
+            .users
+            .iter()
+            .find(|user| &user.id == id)
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "user not found"))?;
+
+        Ok(UserMapper::from_pg(pg_user))
+    }
+
+    async fn get_by_email(
+        &self,
+        _db_context: &Arc<DbContext>,
+        email: &str,
+    ) -> Result<UserModel, RepositoryError> {
+        tracing::trace!(method = "get_by_email", email);
+
+        let tables = self.store.lock()?;
+
+        let pg_user = tables
+            .users
+            .iter()
+            .find(|user| user.email == email)
+            .cloned()
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "user not found"))?;
+
+        Ok(UserMapper::from_pg(pg_user))
+    }
+
+    async fn update_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &Uuid,
+        update_user: &UserUpdateModel,
+    ) -> Result<UserModel, RepositoryError> {
+        tracing::trace!(method = "update_by_id", ?id);
+
+        let mut tables = self.store.lock()?;
+
+        if let Some(email) = &update_user.email {
+            if tables
+                .users
+                .iter()
+                .any(|user| &user.id != id && &user.email == email)
+            {
+                return Err(query_failed(
+                    QueryFailure::AlreadyExists,
+                    "users violates a unique constraint",
+                ));
+            }
+        }
+
+        let pg_user = tables
+            .users
+            .iter_mut()
+            .find(|user| &user.id == id)
+            .map(|user| {
+                if let Some(email) = &update_user.email {
+                    user.email = email.to_owned();
+                }
+
+                if let Some(email_verified) = update_user.email_verified {
+                    user.email_verified = email_verified;
+                }
+
+                user.clone()
+            })
+            .ok_or_else(|| query_failed(QueryFailure::NotUpdated, "user not updated"))?;
+
+        Ok(UserMapper::from_pg(pg_user))
+    }
+
+    async fn delete_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_id", ?id);
+
+        let mut tables = self.store.lock()?;
+
+        expect_one_deleted(tables.delete_users(|user| &user.id == id))
+    }
+}
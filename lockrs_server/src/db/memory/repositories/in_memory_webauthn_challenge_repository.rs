@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api::v1::models::WebauthnChallengeModel,
+    db::{
+        memory::{query_failed, InMemoryStore},
+        repositories::{QueryFailure, RepositoryError, WebauthnChallengeRepository},
+        DbContext,
+    },
+};
+
+pub struct InMemoryWebauthnChallengeRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl WebauthnChallengeRepository for InMemoryWebauthnChallengeRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        challenge: &WebauthnChallengeModel,
+    ) -> Result<WebauthnChallengeModel, RepositoryError> {
+        tracing::trace!(method = "create", ?challenge);
+
+        let mut tables = self.store.lock()?;
+        tables
+            .webauthn_challenges
+            .insert(challenge.id.to_owned(), challenge.clone());
+
+        Ok(challenge.clone())
+    }
+
+    async fn take_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<WebauthnChallengeModel, RepositoryError> {
+        tracing::trace!(method = "take_by_id", id);
+
+        let mut tables = self.store.lock()?;
+        let now = Utc::now().timestamp_millis();
+
+        tables
+            .webauthn_challenges
+            .remove(id)
+            .filter(|challenge| challenge.expires_at > now)
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "webauthn challenge not found"))
+    }
+}
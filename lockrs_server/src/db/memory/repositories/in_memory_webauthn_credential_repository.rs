@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        mappers::WebauthnCredentialMapper,
+        models::{WebauthnCredentialCreateModel, WebauthnCredentialModel},
+    },
+    db::{
+        memory::{expect_one_deleted, query_failed, InMemoryStore},
+        pg::models::PgWebauthnCredential,
+        repositories::{QueryFailure, RepositoryError, WebauthnCredentialRepository},
+        DbContext,
+    },
+};
+
+pub struct InMemoryWebauthnCredentialRepository {
+    pub store: Arc<InMemoryStore>,
+}
+
+#[async_trait]
+impl WebauthnCredentialRepository for InMemoryWebauthnCredentialRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        credential_create: &WebauthnCredentialCreateModel,
+    ) -> Result<WebauthnCredentialModel, RepositoryError> {
+        tracing::trace!(method = "create", ?credential_create);
+
+        let mut tables = self.store.lock()?;
+
+        if tables
+            .webauthn_credentials
+            .iter()
+            .any(|credential| credential.credential_id == credential_create.credential_id)
+        {
+            return Err(query_failed(
+                QueryFailure::AlreadyExists,
+                "webauthn_credentials violates a unique constraint",
+            ));
+        }
+
+        if !tables.has_user(&credential_create.user_id) {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "webauthn_credentials violates a foreign key constraint",
+            ));
+        }
+
+        let pg_credential = PgWebauthnCredential {
+            id: tables.next_id(),
+            user_id: credential_create.user_id,
+            credential_id: credential_create.credential_id.to_owned(),
+            public_key: credential_create.public_key.to_owned(),
+            sign_count: credential_create.sign_count,
+            created_at: Utc::now().naive_utc(),
+            last_used_at: None,
+        };
+
+        tables.webauthn_credentials.push(pg_credential.clone());
+
+        Ok(WebauthnCredentialMapper::from_pg(pg_credential))
+    }
+
+    async fn get_by_credential_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        credential_id: &str,
+    ) -> Result<WebauthnCredentialModel, RepositoryError> {
+        tracing::trace!(method = "get_by_credential_id", credential_id);
+
+        let tables = self.store.lock()?;
+
+        tables
+            .webauthn_credentials
+            .iter()
+            .find(|credential| credential.credential_id == credential_id)
+            .cloned()
+            .map(WebauthnCredentialMapper::from_pg)
+            .ok_or_else(|| query_failed(QueryFailure::NotFound, "webauthn credential not found"))
+    }
+
+    async fn get_all_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<Vec<WebauthnCredentialModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_user_id", ?user_id);
+
+        let tables = self.store.lock()?;
+
+        Ok(tables
+            .webauthn_credentials
+            .iter()
+            .filter(|credential| &credential.user_id == user_id)
+            .cloned()
+            .map(WebauthnCredentialMapper::from_pg)
+            .collect())
+    }
+
+    async fn use_by_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        id: i32,
+        previous_sign_count: i64,
+        sign_count: i64,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "use_by_id", id, previous_sign_count, sign_count);
+
+        let mut tables = self.store.lock()?;
+
+        let credential = tables
+            .webauthn_credentials
+            .iter_mut()
+            .find(|credential| credential.id == id && credential.sign_count == previous_sign_count)
+            .ok_or_else(|| {
+                query_failed(QueryFailure::NotUpdated, "webauthn credential not updated")
+            })?;
+
+        credential.sign_count = sign_count;
+        credential.last_used_at = Some(Utc::now().naive_utc());
+
+        Ok(())
+    }
+
+    async fn delete_by_user_id_and_credential_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        credential_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(
+            method = "delete_by_user_id_and_credential_id",
+            ?user_id,
+            credential_id
+        );
+
+        let mut tables = self.store.lock()?;
+
+        let count = tables.webauthn_credentials.len();
+        tables.webauthn_credentials.retain(|credential| {
+            &credential.user_id != user_id || credential.credential_id != credential_id
+        });
+
+        expect_one_deleted(count - tables.webauthn_credentials.len())
+    }
+}
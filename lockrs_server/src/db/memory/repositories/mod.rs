@@ -0,0 +1,43 @@
+mod in_memory_access_token_repository;
+mod in_memory_authorization_code_repository;
+mod in_memory_authorization_detail_type_repository;
+mod in_memory_backchannel_authorization_repository;
+mod in_memory_client_auth_repository;
+mod in_memory_client_policy_repository;
+mod in_memory_client_repository;
+mod in_memory_consent_repository;
+mod in_memory_device_authorization_repository;
+mod in_memory_federated_identity_repository;
+mod in_memory_federation_state_repository;
+mod in_memory_login_attempt_repository;
+mod in_memory_magic_link_repository;
+mod in_memory_password_reset_token_repository;
+mod in_memory_pushed_authorization_request_repository;
+mod in_memory_recovery_code_repository;
+mod in_memory_redirect_uri_repository;
+mod in_memory_refresh_token_repository;
+mod in_memory_scope_repository;
+mod in_memory_session_repository;
+mod in_memory_session_token_repository;
+mod in_memory_totp_repository;
+mod in_memory_user_auth_repository;
+mod in_memory_user_repository;
+mod in_memory_webauthn_challenge_repository;
+mod in_memory_webauthn_credential_repository;
+
+pub use self::{
+    in_memory_access_token_repository::*, in_memory_authorization_code_repository::*,
+    in_memory_authorization_detail_type_repository::*,
+    in_memory_backchannel_authorization_repository::*, in_memory_client_auth_repository::*,
+    in_memory_client_policy_repository::*, in_memory_client_repository::*,
+    in_memory_consent_repository::*, in_memory_device_authorization_repository::*,
+    in_memory_federated_identity_repository::*, in_memory_federation_state_repository::*,
+    in_memory_login_attempt_repository::*, in_memory_magic_link_repository::*,
+    in_memory_password_reset_token_repository::*,
+    in_memory_pushed_authorization_request_repository::*, in_memory_recovery_code_repository::*,
+    in_memory_redirect_uri_repository::*, in_memory_refresh_token_repository::*,
+    in_memory_scope_repository::*, in_memory_session_repository::*,
+    in_memory_session_token_repository::*, in_memory_totp_repository::*,
+    in_memory_user_auth_repository::*, in_memory_user_repository::*,
+    in_memory_webauthn_challenge_repository::*, in_memory_webauthn_credential_repository::*,
+};
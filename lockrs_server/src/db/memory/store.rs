@@ -0,0 +1,338 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+};
+
+use chrono::{NaiveDateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    api::v1::models::{
+        FederationStateModel, LoginAttemptModel, MagicLinkModel, PasswordResetTokenModel,
+        SessionModel, SessionTokenModel, WebauthnChallengeModel,
+    },
+    db::{
+        pg::models::{
+            PgAccessToken, PgAllowedScope, PgAuthorizationCode, PgAuthorizationDetailType,
+            PgBackchannelAuthorization, PgClient, PgClientPolicy, PgClientSecret, PgConsent,
+            PgDeviceAuthorization, PgFederatedIdentity, PgRecoveryCode, PgRedirectUri,
+            PgRefreshToken, PgScope, PgTotpSecret, PgUser, PgWebauthnCredential,
+        },
+        repositories::{QueryFailure, RepositoryError},
+    },
+    models::ClientSecretCreateModel,
+    oauth2::v1::models::AuthorizationRequestModel,
+};
+
+/// The rows of every table, kept in the same shape the pg repositories read and write so the
+/// in-memory repositories can share the pg mappers.
+#[derive(Default)]
+pub struct InMemoryTables {
+    pub access_tokens: Vec<PgAccessToken>,
+    pub allowed_scopes: Vec<PgAllowedScope>,
+    pub authorization_codes: Vec<PgAuthorizationCode>,
+    pub authorization_detail_types: Vec<PgAuthorizationDetailType>,
+    pub backchannel_authorizations: Vec<PgBackchannelAuthorization>,
+    pub client_policies: Vec<PgClientPolicy>,
+    pub client_secrets: Vec<PgClientSecret>,
+    pub clients: Vec<PgClient>,
+    pub consents: Vec<PgConsent>,
+    pub device_authorizations: Vec<PgDeviceAuthorization>,
+    pub federated_identities: Vec<PgFederatedIdentity>,
+    pub recovery_codes: Vec<PgRecoveryCode>,
+    pub redirect_uris: Vec<PgRedirectUri>,
+    pub refresh_tokens: Vec<PgRefreshToken>,
+    pub scopes: Vec<PgScope>,
+    pub totp_secrets: Vec<PgTotpSecret>,
+    pub users: Vec<PgUser>,
+    pub webauthn_credentials: Vec<PgWebauthnCredential>,
+
+    /// sessions by user, alongside the millisecond timestamp the user's sessions expire at
+    pub sessions: HashMap<Uuid, (i64, HashMap<String, SessionModel>)>,
+    pub session_tokens: HashMap<String, SessionTokenModel>,
+    /// unfinished sign-ins with upstream identity providers by the digest of their state
+    pub federation_states: HashMap<String, FederationStateModel>,
+    /// failed login counters by the key they are counted against
+    pub login_attempts: HashMap<String, LoginAttemptModel>,
+    /// sign-in links by the email they were sent to
+    pub magic_links: HashMap<String, MagicLinkModel>,
+    /// password reset tokens by the digest of the token
+    pub password_reset_tokens: HashMap<String, PasswordResetTokenModel>,
+    /// pushed authorization requests by the digest of their request_uri, alongside the
+    /// millisecond timestamp they expire at
+    pub pushed_authorization_requests: HashMap<String, (i64, AuthorizationRequestModel)>,
+    pub webauthn_challenges: HashMap<String, WebauthnChallengeModel>,
+
+    sequence: i32,
+}
+
+impl InMemoryTables {
+    /// the next value of the shared `SERIAL` sequence
+    pub fn next_id(&mut self) -> i32 {
+        self.sequence += 1;
+        self.sequence
+    }
+
+    pub fn has_client(&self, client_id: &str) -> bool {
+        self.clients.iter().any(|client| client.id == client_id)
+    }
+
+    pub fn has_user(&self, user_id: &Uuid) -> bool {
+        self.users.iter().any(|user| &user.id == user_id)
+    }
+
+    pub fn insert_redirect_uri(
+        &mut self,
+        client_id: &str,
+        uri: &str,
+        match_mode: &str,
+    ) -> Result<PgRedirectUri, RepositoryError> {
+        if self
+            .redirect_uris
+            .iter()
+            .any(|redirect_uri| redirect_uri.client_id == client_id && redirect_uri.uri == uri)
+        {
+            return Err(query_failed(
+                QueryFailure::AlreadyExists,
+                "redirect_uris violates a unique constraint",
+            ));
+        }
+
+        if !self.has_client(client_id) {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "redirect_uris violates a foreign key constraint",
+            ));
+        }
+
+        let now = Utc::now().naive_utc();
+
+        let pg_redirect = PgRedirectUri {
+            id: Uuid::new_v4(),
+            client_id: client_id.to_owned(),
+            uri: uri.to_owned(),
+            created_at: now,
+            updated_at: now,
+            match_mode: match_mode.to_owned(),
+        };
+
+        self.redirect_uris.push(pg_redirect.clone());
+
+        Ok(pg_redirect)
+    }
+
+    pub fn insert_client_secret(
+        &mut self,
+        secret_create: &ClientSecretCreateModel,
+    ) -> Result<PgClientSecret, RepositoryError> {
+        if !self.has_client(secret_create.client_id.as_str()) {
+            return Err(query_failed(
+                QueryFailure::NotCreated,
+                "client_secrets violates a foreign key constraint",
+            ));
+        }
+
+        let pg_client_secret = PgClientSecret {
+            id: self.next_id(),
+            client_id: secret_create.client_id.to_owned(),
+            salt: secret_create.salt.to_owned(),
+            secret_hash: secret_create.secret_hash.to_owned(),
+            created_at: Utc::now().naive_utc(),
+            expires_at: secret_create.expires_at,
+        };
+
+        self.client_secrets.push(pg_client_secret.clone());
+
+        Ok(pg_client_secret)
+    }
+
+    /// deletes the matching access tokens, cascading to their refresh tokens
+    pub fn delete_access_tokens<F>(&mut self, predicate: F) -> usize
+    where
+        F: Fn(&PgAccessToken) -> bool,
+    {
+        let deleted_ids = self
+            .access_tokens
+            .iter()
+            .filter(|access_token| predicate(access_token))
+            .map(|access_token| access_token.id)
+            .collect::<Vec<i32>>();
+
+        self.access_tokens
+            .retain(|access_token| !deleted_ids.contains(&access_token.id));
+        self.refresh_tokens
+            .retain(|refresh_token| !deleted_ids.contains(&refresh_token.access_token_id));
+
+        deleted_ids.len()
+    }
+
+    /// deletes the matching clients, cascading to every row that references them
+    pub fn delete_clients<F>(&mut self, predicate: F) -> usize
+    where
+        F: Fn(&PgClient) -> bool,
+    {
+        let deleted_ids = self
+            .clients
+            .iter()
+            .filter(|client| predicate(client))
+            .map(|client| client.id.to_owned())
+            .collect::<Vec<String>>();
+
+        let references = |client_id: &String| deleted_ids.contains(client_id);
+
+        self.clients.retain(|client| !references(&client.id));
+        self.delete_access_tokens(|access_token| references(&access_token.client_id));
+        self.allowed_scopes
+            .retain(|allowed_scope| !references(&allowed_scope.client_id));
+        self.authorization_codes
+            .retain(|authorization_code| !references(&authorization_code.client_id));
+        self.authorization_detail_types
+            .retain(|authorization_detail_type| !references(&authorization_detail_type.client_id));
+        self.backchannel_authorizations
+            .retain(|backchannel_authorization| !references(&backchannel_authorization.client_id));
+        self.client_policies
+            .retain(|client_policy| !references(&client_policy.client_id));
+        self.client_secrets
+            .retain(|client_secret| !references(&client_secret.client_id));
+        self.consents
+            .retain(|consent| !references(&consent.client_id));
+        self.device_authorizations
+            .retain(|device_authorization| !references(&device_authorization.client_id));
+        self.redirect_uris
+            .retain(|redirect_uri| !references(&redirect_uri.client_id));
+        self.refresh_tokens
+            .retain(|refresh_token| !references(&refresh_token.client_id));
+
+        let deleted_scopes = self
+            .scopes
+            .iter()
+            .filter(|scope| scope.client_id.as_ref().is_some_and(references))
+            .map(|scope| scope.name.to_owned())
+            .collect::<Vec<String>>();
+
+        self.scopes
+            .retain(|scope| !deleted_scopes.contains(&scope.name));
+        self.allowed_scopes
+            .retain(|allowed_scope| !deleted_scopes.contains(&allowed_scope.scope));
+
+        deleted_ids.len()
+    }
+
+    /// deletes the matching users, cascading to every row that references them
+    pub fn delete_users<F>(&mut self, predicate: F) -> usize
+    where
+        F: Fn(&PgUser) -> bool,
+    {
+        let deleted_ids = self
+            .users
+            .iter()
+            .filter(|user| predicate(user))
+            .map(|user| user.id)
+            .collect::<Vec<Uuid>>();
+
+        let references = |user_id: &Uuid| deleted_ids.contains(user_id);
+
+        self.users.retain(|user| !references(&user.id));
+        self.delete_clients(|client| references(&client.user_id));
+        self.delete_access_tokens(|access_token| {
+            access_token.user_id.as_ref().is_some_and(references)
+        });
+        self.authorization_codes
+            .retain(|authorization_code| !references(&authorization_code.user_id));
+        self.backchannel_authorizations
+            .retain(|backchannel_authorization| !references(&backchannel_authorization.user_id));
+        self.consents
+            .retain(|consent| !references(&consent.user_id));
+        self.federated_identities
+            .retain(|identity| !references(&identity.user_id));
+        self.recovery_codes
+            .retain(|recovery_code| !references(&recovery_code.user_id));
+        self.refresh_tokens
+            .retain(|refresh_token| !refresh_token.user_id.as_ref().is_some_and(references));
+        self.totp_secrets
+            .retain(|totp_secret| !references(&totp_secret.user_id));
+        self.webauthn_credentials
+            .retain(|credential| !references(&credential.user_id));
+
+        deleted_ids.len()
+    }
+}
+
+/// Shared state for the in-memory repositories. Every repository built from the same store
+/// sees the same rows, so e.g. a client created through the `ClientAuthRepository` can be read
+/// back through the `ClientRepository`.
+pub struct InMemoryStore {
+    tables: Mutex<InMemoryTables>,
+}
+
+impl Default for InMemoryStore {
+    fn default() -> Self {
+        let mut tables = InMemoryTables::default();
+
+        // the scopes seeded by the scopes migration
+        for (name, description) in [
+            ("read", "Allows the client application to read user data, such as their profile information or email address."),
+            ("write", "Allows the client application to create or modify user data, such as adding or editing user comments."),
+            ("delete", "Allows the client application to delete user data, such as removing user comments."),
+            ("offline_access", "Allows the client application to access the user's resources even when the user is not actively logged in."),
+            ("openid", "Allows the client application to obtain the user's OpenID identifier, which can be used to authenticate the user on other systems."),
+            ("profile", "Allows the client application to obtain the user's profile information, such as their name and profile picture."),
+        ] {
+            let id = tables.next_id();
+
+            tables.scopes.push(PgScope {
+                id,
+                name: name.to_owned(),
+                description: description.to_owned(),
+                client_id: None,
+                consent_text: None,
+            });
+        }
+
+        Self {
+            tables: Mutex::new(tables),
+        }
+    }
+}
+
+impl InMemoryStore {
+    pub fn lock(&self) -> Result<MutexGuard<'_, InMemoryTables>, RepositoryError> {
+        self.tables.lock().map_err(|_| {
+            let msg = "IN MEMORY STORE LOCK POISONED";
+            tracing::error!(error = msg);
+
+            RepositoryError::InternalError
+        })
+    }
+}
+
+/// logs and builds the error a pg repository would surface for a violated constraint or a miss
+pub fn query_failed(failure: QueryFailure, msg: &str) -> RepositoryError {
+    tracing::error!(error = msg);
+
+    RepositoryError::QueryFailed(failure)
+}
+
+pub fn expect_one_deleted(affected_rows: usize) -> Result<(), RepositoryError> {
+    if affected_rows != 1 {
+        let msg = format!(
+            "Expected 1 row to be affected by delete, but found {}",
+            affected_rows
+        );
+
+        return Err(query_failed(QueryFailure::NotDeleted, msg.as_str()));
+    }
+
+    Ok(())
+}
+
+/// `created_at < now < expires_at`, the window every pg read of a short lived grant filters on
+pub fn is_live(created_at: &NaiveDateTime, expires_at: &NaiveDateTime) -> bool {
+    let now = Utc::now().naive_utc();
+
+    created_at <= &now && expires_at > &now
+}
+
+pub fn to_pg_list(values: &[String]) -> Vec<Option<String>> {
+    values.iter().map(|value| Some(value.to_owned())).collect()
+}
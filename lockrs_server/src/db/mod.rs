@@ -1,8 +1,13 @@
+pub mod cache;
+pub mod memory;
 pub mod pg;
 pub mod redis;
 pub mod repositories;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 
 mod context;
+mod digest;
 mod repository_container;
 
-pub use self::{context::*, repository_container::*};
+pub use self::{context::*, digest::*, repository_container::*};
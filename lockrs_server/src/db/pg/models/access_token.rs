@@ -4,7 +4,7 @@ use uuid::Uuid;
 
 use crate::db::pg::schema::access_tokens;
 
-#[derive(Debug, Queryable, Insertable)]
+#[derive(Clone, Debug, Queryable, Insertable)]
 #[diesel(primary_key(id), table_name = access_tokens)]
 pub struct PgAccessToken {
     pub id: i32,
@@ -14,4 +14,5 @@ pub struct PgAccessToken {
     pub created_at: NaiveDateTime,
     pub expires_at: NaiveDateTime,
     pub scopes: Vec<Option<String>>,
+    pub authorization_details: serde_json::Value,
 }
@@ -0,0 +1,11 @@
+use diesel::prelude::*;
+
+use crate::db::pg::schema::allowed_scopes;
+
+#[derive(Clone, Debug, Queryable, Insertable)]
+#[diesel(primary_key(client_id, scope), table_name = allowed_scopes)]
+pub struct PgAllowedScope {
+    pub client_id: String,
+    pub scope: String,
+    pub is_default: bool,
+}
@@ -4,7 +4,7 @@ use uuid::Uuid;
 
 use crate::db::pg::schema::authorization_codes;
 
-#[derive(Debug, Queryable, Insertable)]
+#[derive(Clone, Debug, Queryable, Insertable)]
 #[diesel(primary_key(id), table_name = authorization_codes)]
 pub struct PgAuthorizationCode {
     pub id: i32,
@@ -18,4 +18,5 @@ pub struct PgAuthorizationCode {
     pub expires_at: NaiveDateTime,
     pub used: bool,
     pub scopes: Vec<Option<String>>,
+    pub authorization_details: serde_json::Value,
 }
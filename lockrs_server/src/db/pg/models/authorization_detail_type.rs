@@ -0,0 +1,12 @@
+use diesel::prelude::*;
+
+use crate::db::pg::schema::authorization_detail_types;
+
+#[derive(Clone, Debug, Queryable, Insertable)]
+#[diesel(primary_key(name), table_name = authorization_detail_types)]
+pub struct PgAuthorizationDetailType {
+    pub name: String,
+    pub client_id: String,
+    pub description: String,
+    pub schema: serde_json::Value,
+}
@@ -0,0 +1,25 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::pg::schema::backchannel_authorizations;
+
+#[derive(Clone, Debug, Queryable, Insertable, Identifiable)]
+#[diesel(primary_key(id), table_name = backchannel_authorizations)]
+pub struct PgBackchannelAuthorization {
+    pub id: i32,
+    pub auth_req_id: String,
+    pub client_id: String,
+    pub user_id: Uuid,
+    pub binding_message: Option<String>,
+    pub delivery_mode: String,
+    pub client_notification_token: Option<String>,
+    pub client_notification_endpoint: Option<String>,
+    pub status: String,
+    pub poll_interval: i32,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub last_polled_at: Option<NaiveDateTime>,
+    pub scopes: Vec<Option<String>>,
+    pub authorization_details: serde_json::Value,
+}
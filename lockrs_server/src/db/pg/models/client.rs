@@ -3,11 +3,10 @@ use uuid::Uuid;
 
 use crate::db::pg::schema::clients;
 
-#[derive(Debug, Queryable, Insertable, Identifiable, Selectable)]
+#[derive(Clone, Debug, Queryable, Insertable, Identifiable, Selectable)]
 #[diesel(primary_key(id), table_name = clients)]
 pub struct PgClient {
     pub id: String,
-    pub secret: Option<String>,
     pub user_id: Uuid,
     pub is_public: bool,
     pub name: String,
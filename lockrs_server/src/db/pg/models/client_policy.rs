@@ -0,0 +1,17 @@
+use diesel::prelude::*;
+
+use crate::db::pg::schema::client_policies;
+
+#[derive(Clone, Debug, Queryable, Insertable)]
+#[diesel(primary_key(client_id), table_name = client_policies)]
+pub struct PgClientPolicy {
+    pub client_id: String,
+    pub grant_types: Vec<Option<String>>,
+    pub access_token_lifetime: i32,
+    pub refresh_token_lifetime: i32,
+    pub id_token_lifetime: i32,
+    pub absolute_refresh_lifetime: Option<i32>,
+    pub sliding_refresh_window: Option<i32>,
+    pub always_issue_refresh_token: bool,
+    pub backchannel_client_notification_endpoint: Option<String>,
+}
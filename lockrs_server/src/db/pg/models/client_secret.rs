@@ -0,0 +1,15 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::db::pg::schema::client_secrets;
+
+#[derive(Clone, Debug, Queryable, Insertable, Identifiable)]
+#[diesel(primary_key(id), table_name = client_secrets)]
+pub struct PgClientSecret {
+    pub id: i32,
+    pub client_id: String,
+    pub salt: String,
+    pub secret_hash: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+}
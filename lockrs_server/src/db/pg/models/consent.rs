@@ -0,0 +1,17 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::pg::schema::consents;
+
+#[derive(Clone, Debug, Queryable, Insertable, Identifiable)]
+#[diesel(primary_key(id), table_name = consents)]
+pub struct PgConsent {
+    pub id: i32,
+    pub user_id: Uuid,
+    pub client_id: String,
+    pub scopes: Vec<Option<String>>,
+    pub granted_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub authorization_details: serde_json::Value,
+}
@@ -3,7 +3,7 @@ use diesel::prelude::*;
 
 use crate::db::pg::schema::device_authorizations;
 
-#[derive(Debug, Queryable, Insertable, Identifiable)]
+#[derive(Clone, Debug, Queryable, Insertable, Identifiable)]
 #[diesel(primary_key(id), table_name = device_authorizations)]
 pub struct PgDeviceAuthorization {
     pub id: i32,
@@ -0,0 +1,15 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::pg::schema::federated_identities;
+
+#[derive(Clone, Debug, Queryable, Insertable, Identifiable)]
+#[diesel(primary_key(id), table_name = federated_identities)]
+pub struct PgFederatedIdentity {
+    pub id: i32,
+    pub user_id: Uuid,
+    pub provider: String,
+    pub subject: String,
+    pub created_at: NaiveDateTime,
+}
@@ -1,13 +1,25 @@
 mod access_token;
+mod allowed_scope;
 mod authorization_code;
+mod authorization_detail_type;
+mod backchannel_authorization;
 mod client;
+mod client_policy;
+mod client_secret;
+mod consent;
 mod device_authorization;
+mod federated_identity;
+mod recovery_code;
 mod redirect_uri;
 mod refresh_token;
 mod scope;
+mod totp_secret;
 mod user;
+mod webauthn_credential;
 
 pub use self::{
-    access_token::*, authorization_code::*, client::*, device_authorization::*, redirect_uri::*,
-    refresh_token::*, scope::*, user::*,
+    access_token::*, allowed_scope::*, authorization_code::*, authorization_detail_type::*,
+    backchannel_authorization::*, client::*, client_policy::*, client_secret::*, consent::*,
+    device_authorization::*, federated_identity::*, recovery_code::*, redirect_uri::*,
+    refresh_token::*, scope::*, totp_secret::*, user::*, webauthn_credential::*,
 };
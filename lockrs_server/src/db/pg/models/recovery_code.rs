@@ -0,0 +1,15 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::pg::schema::recovery_codes;
+
+#[derive(Clone, Debug, Queryable, Insertable, Identifiable)]
+#[diesel(primary_key(id), table_name = recovery_codes)]
+pub struct PgRecoveryCode {
+    pub id: i32,
+    pub user_id: Uuid,
+    pub code_hash: String,
+    pub created_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+}
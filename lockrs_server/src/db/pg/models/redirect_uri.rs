@@ -4,7 +4,7 @@ use uuid::Uuid;
 
 use crate::db::pg::{models::PgClient, schema::redirect_uris};
 
-#[derive(Debug, Queryable, Insertable, Associations, Identifiable, Selectable)]
+#[derive(Clone, Debug, Queryable, Insertable, Associations, Identifiable, Selectable)]
 #[diesel(belongs_to(PgClient, foreign_key = client_id))]
 #[diesel(primary_key(id), table_name = redirect_uris)]
 pub struct PgRedirectUri {
@@ -13,4 +13,5 @@ pub struct PgRedirectUri {
     pub uri: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub match_mode: String,
 }
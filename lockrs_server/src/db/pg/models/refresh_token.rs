@@ -4,7 +4,7 @@ use uuid::Uuid;
 
 use crate::db::pg::schema::refresh_tokens;
 
-#[derive(Debug, Queryable, Insertable)]
+#[derive(Clone, Debug, Queryable, Insertable)]
 #[diesel(primary_key(id), table_name = refresh_tokens)]
 pub struct PgRefreshToken {
     pub id: i32,
@@ -16,4 +16,6 @@ pub struct PgRefreshToken {
     pub expires_at: NaiveDateTime,
     pub used: bool,
     pub scopes: Vec<Option<String>>,
+    pub authorization_details: serde_json::Value,
+    pub grant_created_at: NaiveDateTime,
 }
@@ -2,11 +2,12 @@ use diesel::prelude::*;
 
 use crate::db::pg::schema::scopes;
 
-#[derive(Debug, Queryable, Insertable)]
+#[derive(Clone, Debug, Queryable, Insertable)]
 #[diesel(primary_key(id), table_name = scopes)]
 pub struct PgScope {
     pub id: i32,
     pub name: String,
     pub description: String,
     pub client_id: Option<String>,
+    pub consent_text: Option<String>,
 }
@@ -3,10 +3,12 @@ use uuid::Uuid;
 
 use crate::db::pg::schema::users;
 
-#[derive(Debug, Queryable, Insertable)]
+#[derive(Clone, Debug, Queryable, Insertable)]
 #[diesel(primary_key(id), table_name = users)]
 pub struct PgUser {
     pub id: Uuid,
     pub email: String,
     pub password_hash: String,
+    pub email_verified: bool,
+    pub password_version: i32,
 }
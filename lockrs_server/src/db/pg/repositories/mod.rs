@@ -1,17 +1,29 @@
 mod pg_access_token_repository;
 mod pg_authorization_code_repository;
+mod pg_authorization_detail_type_repository;
+mod pg_backchannel_authorization_repository;
 mod pg_client_auth_repository;
+mod pg_client_policy_repository;
 mod pg_client_repository;
+mod pg_consent_repository;
 mod pg_device_authorization_repository;
+mod pg_federated_identity_repository;
+mod pg_recovery_code_repository;
 mod pg_redirect_uri_repository;
 mod pg_refresh_token_repository;
 mod pg_scope_repository;
+mod pg_totp_repository;
 mod pg_user_auth_repository;
 mod pg_user_repository;
+mod pg_webauthn_credential_repository;
 
 pub use self::{
     pg_access_token_repository::*, pg_authorization_code_repository::*,
-    pg_client_auth_repository::*, pg_client_repository::*, pg_device_authorization_repository::*,
+    pg_authorization_detail_type_repository::*, pg_backchannel_authorization_repository::*,
+    pg_client_auth_repository::*, pg_client_policy_repository::*, pg_client_repository::*,
+    pg_consent_repository::*, pg_device_authorization_repository::*,
+    pg_federated_identity_repository::*, pg_recovery_code_repository::*,
     pg_redirect_uri_repository::*, pg_refresh_token_repository::*, pg_scope_repository::*,
-    pg_user_auth_repository::*, pg_user_repository::*,
+    pg_totp_repository::*, pg_user_auth_repository::*, pg_user_repository::*,
+    pg_webauthn_credential_repository::*,
 };
@@ -1,18 +1,26 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use chrono::offset::Utc;
-use diesel::prelude::*;
-use diesel_async::RunQueryDsl;
+use chrono::{offset::Utc, NaiveDateTime};
+use diesel::{
+    dsl::{exists, not},
+    prelude::*,
+};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use scoped_futures::ScopedFutureExt;
 
 use crate::{
     db::{
-        pg::{models::PgAccessToken, schema::access_tokens},
+        digest_token,
+        pg::{
+            models::PgAccessToken,
+            schema::{access_tokens, refresh_tokens},
+        },
         repositories::{AccessTokenRepository, QueryFailure, RepositoryError},
         DbContext,
     },
     oauth2::v1::{
-        mappers::AccessTokenMapper,
+        mappers::{AccessTokenMapper, AuthorizationDetailMapper},
         models::{AccessTokenCreateModel, AccessTokenModel},
     },
 };
@@ -36,17 +44,24 @@ impl AccessTokenRepository for PgAccessTokenRepository {
 
         let pg_token = diesel::insert_into(access_tokens::table)
             .values((
-                access_tokens::token.eq(&token_create.token),
+                access_tokens::token.eq(digest_token(token_create.token.as_str())),
                 access_tokens::client_id.eq(&token_create.client_id),
                 access_tokens::user_id.eq(&token_create.user_id),
                 access_tokens::expires_at.eq(&token_create.expires_at),
                 access_tokens::scopes.eq(&token_create.scopes),
+                access_tokens::authorization_details.eq(
+                    AuthorizationDetailMapper::vec_to_pg_value(&token_create.authorization_details),
+                ),
             ))
             .get_result::<PgAccessToken>(conn)
             .await
             .map_err(RepositoryError::map_diesel_create)?;
 
-        Ok(AccessTokenMapper::from_pg(pg_token))
+        // only the digest is stored, so hand the caller back the token they issued
+        let mut token = AccessTokenMapper::from_pg(pg_token);
+        token.token = token_create.token.to_owned();
+
+        Ok(token)
     }
 
     async fn get_by_token(
@@ -65,14 +80,17 @@ impl AccessTokenRepository for PgAccessTokenRepository {
         let now = Utc::now().naive_utc();
 
         let pg_token = access_tokens::table
-            .filter(access_tokens::token.eq(token))
+            .filter(access_tokens::token.eq(digest_token(token)))
             .filter(access_tokens::created_at.lt(&now))
             .filter(access_tokens::expires_at.gt(&now))
             .first::<PgAccessToken>(conn)
             .await
-            .map_err(RepositoryError::map_diesel_update)?;
+            .map_err(RepositoryError::map_diesel_found)?;
 
-        Ok(AccessTokenMapper::from_pg(pg_token))
+        let mut access_token = AccessTokenMapper::from_pg(pg_token);
+        access_token.token = token.to_owned();
+
+        Ok(access_token)
     }
 
     async fn delete_by_token(
@@ -89,7 +107,7 @@ impl AccessTokenRepository for PgAccessTokenRepository {
             .map_err(RepositoryError::from)?;
 
         let affected_rows = diesel::delete(access_tokens::table)
-            .filter(access_tokens::token.eq(token))
+            .filter(access_tokens::token.eq(digest_token(token)))
             .execute(conn)
             .await
             .map_err(RepositoryError::map_diesel_delete)?;
@@ -106,4 +124,47 @@ impl AccessTokenRepository for PgAccessTokenRepository {
 
         Ok(())
     }
+
+    async fn delete_expired(
+        &self,
+        db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "delete_expired", ?expired_before, limit);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // each batch locks a bounded set of rows, skipping any another request holds, and commits
+        // before the next so no lock is held for longer than a single short delete
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let expired_ids = access_tokens::table
+                    .select(access_tokens::id)
+                    .filter(access_tokens::expires_at.lt(expired_before))
+                    // refresh tokens cascade from their access token, so keep any still referenced
+                    .filter(not(exists(
+                        refresh_tokens::table
+                            .filter(refresh_tokens::access_token_id.eq(access_tokens::id)),
+                    )))
+                    .limit(limit)
+                    .for_update()
+                    .skip_locked()
+                    .load::<i32>(conn)
+                    .await?;
+
+                diesel::delete(access_tokens::table)
+                    .filter(access_tokens::id.eq_any(expired_ids))
+                    .execute(conn)
+                    .await
+            }
+            .scope_boxed()
+        })
+        .await
+        .map_err(RepositoryError::map_diesel_delete)
+    }
 }
@@ -1,13 +1,22 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use scoped_futures::ScopedFutureExt;
 
 use crate::{
     db::{
+        digest_token,
+        pg::{models::PgAuthorizationCode, schema::authorization_codes},
         repositories::{AuthorizationCodeRepository, RepositoryError},
         DbContext,
     },
-    oauth2::v1::models::{AuthorizationCodeCreateModel, AuthorizationCodeModel},
+    oauth2::v1::{
+        mappers::{AuthorizationCodeMapper, AuthorizationDetailMapper},
+        models::{AuthorizationCodeCreateModel, AuthorizationCodeModel},
+    },
 };
 
 pub struct PgAuthorizationCodeRepository;
@@ -16,41 +25,184 @@ pub struct PgAuthorizationCodeRepository;
 impl AuthorizationCodeRepository for PgAuthorizationCodeRepository {
     async fn create(
         &self,
-        _db_context: &Arc<DbContext>,
-        _auth_code_create: &AuthorizationCodeCreateModel,
+        db_context: &Arc<DbContext>,
+        auth_code_create: &AuthorizationCodeCreateModel,
     ) -> Result<AuthorizationCodeModel, RepositoryError> {
         tracing::trace!(method = "create");
 
-        todo!();
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_code = diesel::insert_into(authorization_codes::table)
+            .values((
+                authorization_codes::code.eq(digest_token(auth_code_create.code.as_str())),
+                authorization_codes::challenge.eq(&auth_code_create.challenge),
+                authorization_codes::is_challenge_plain.eq(auth_code_create.is_challenge_plain),
+                authorization_codes::client_id.eq(&auth_code_create.client_id),
+                authorization_codes::user_id.eq(&auth_code_create.user_id),
+                authorization_codes::redirect_uri.eq(auth_code_create.redirect_uri.as_str()),
+                authorization_codes::expires_at.eq(&auth_code_create.expires_at),
+                authorization_codes::scopes.eq(&auth_code_create.scopes),
+                authorization_codes::authorization_details.eq(
+                    AuthorizationDetailMapper::vec_to_pg_value(
+                        &auth_code_create.authorization_details,
+                    ),
+                ),
+            ))
+            .get_result::<PgAuthorizationCode>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        // only the digest is stored, so hand the caller back the code they issued
+        let mut code = AuthorizationCodeMapper::from_pg(pg_code);
+        code.code = auth_code_create.code.to_owned();
+
+        Ok(code)
     }
 
     async fn get_by_id(
         &self,
-        _db_context: &Arc<DbContext>,
-        _id: &str,
+        db_context: &Arc<DbContext>,
+        id: i32,
     ) -> Result<AuthorizationCodeModel, RepositoryError> {
-        tracing::trace!(method = "get_by_id");
+        tracing::trace!(method = "get_by_id", id);
 
-        todo!();
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_code = authorization_codes::table
+            .filter(authorization_codes::id.eq(id))
+            .first::<PgAuthorizationCode>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(AuthorizationCodeMapper::from_pg(pg_code))
     }
 
     async fn get_by_code(
         &self,
-        _db_context: &Arc<DbContext>,
-        _code: &str,
+        db_context: &Arc<DbContext>,
+        code: &str,
     ) -> Result<AuthorizationCodeModel, RepositoryError> {
         tracing::trace!(method = "get_by_code");
 
-        todo!();
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let now = Utc::now().naive_utc();
+
+        let pg_code = authorization_codes::table
+            .filter(authorization_codes::code.eq(digest_token(code)))
+            .filter(authorization_codes::created_at.lt(&now))
+            .filter(authorization_codes::expires_at.gt(&now))
+            .filter(authorization_codes::used.eq(false))
+            .first::<PgAuthorizationCode>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        let mut authorization_code = AuthorizationCodeMapper::from_pg(pg_code);
+        authorization_code.code = code.to_owned();
+
+        Ok(authorization_code)
+    }
+
+    async fn use_by_code(
+        &self,
+        db_context: &Arc<DbContext>,
+        code: &str,
+    ) -> Result<AuthorizationCodeModel, RepositoryError> {
+        tracing::trace!(method = "use_by_code");
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let now = Utc::now().naive_utc();
+
+        let pg_code = diesel::update(authorization_codes::table)
+            .filter(authorization_codes::code.eq(digest_token(code)))
+            .filter(authorization_codes::created_at.lt(&now))
+            .filter(authorization_codes::expires_at.gt(&now))
+            .filter(authorization_codes::used.eq(false))
+            .set(authorization_codes::used.eq(true))
+            .get_result::<PgAuthorizationCode>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        let mut authorization_code = AuthorizationCodeMapper::from_pg(pg_code);
+        authorization_code.code = code.to_owned();
+
+        Ok(authorization_code)
     }
 
     async fn delete_by_id(
         &self,
-        _db_context: &Arc<DbContext>,
-        _id: &str,
+        db_context: &Arc<DbContext>,
+        id: i32,
     ) -> Result<AuthorizationCodeModel, RepositoryError> {
-        tracing::trace!(method = "delete_by_id");
+        tracing::trace!(method = "delete_by_id", id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_code = diesel::delete(authorization_codes::table)
+            .filter(authorization_codes::id.eq(id))
+            .get_result::<PgAuthorizationCode>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        Ok(AuthorizationCodeMapper::from_pg(pg_code))
+    }
+
+    async fn delete_expired(
+        &self,
+        db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "delete_expired", ?expired_before, limit);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // each batch locks a bounded set of rows, skipping any another request holds, and commits
+        // before the next so no lock is held for longer than a single short delete
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let expired_ids = authorization_codes::table
+                    .select(authorization_codes::id)
+                    .filter(authorization_codes::expires_at.lt(expired_before))
+                    .limit(limit)
+                    .for_update()
+                    .skip_locked()
+                    .load::<i32>(conn)
+                    .await?;
 
-        todo!();
+                diesel::delete(authorization_codes::table)
+                    .filter(authorization_codes::id.eq_any(expired_ids))
+                    .execute(conn)
+                    .await
+            }
+            .scope_boxed()
+        })
+        .await
+        .map_err(RepositoryError::map_diesel_delete)
     }
 }
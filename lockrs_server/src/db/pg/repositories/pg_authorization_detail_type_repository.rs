@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    db::{
+        pg::{models::PgAuthorizationDetailType, schema::authorization_detail_types},
+        repositories::{AuthorizationDetailTypeRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::AuthorizationDetailMapper,
+        models::{AuthorizationDetailTypeCreateModel, AuthorizationDetailTypeModel},
+    },
+};
+
+pub struct PgAuthorizationDetailTypeRepository;
+
+#[async_trait]
+impl AuthorizationDetailTypeRepository for PgAuthorizationDetailTypeRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        type_create: &AuthorizationDetailTypeCreateModel,
+    ) -> Result<AuthorizationDetailTypeModel, RepositoryError> {
+        tracing::trace!(method = "create", ?type_create);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let schema = serde_json::to_value(&type_create.schema).map_err(|err| {
+            tracing::error!(error = %err);
+            RepositoryError::QueryFailed(QueryFailure::NotCreated)
+        })?;
+
+        let pg_type = diesel::insert_into(authorization_detail_types::table)
+            .values((
+                authorization_detail_types::name.eq(&type_create.name),
+                authorization_detail_types::client_id.eq(&type_create.client_id),
+                authorization_detail_types::description.eq(&type_create.description),
+                authorization_detail_types::schema.eq(schema),
+            ))
+            .get_result::<PgAuthorizationDetailType>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(AuthorizationDetailMapper::type_from_pg(pg_type))
+    }
+
+    async fn get_from_list(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        names: &[String],
+    ) -> Result<Vec<AuthorizationDetailTypeModel>, RepositoryError> {
+        tracing::trace!(method = "get_from_list", client_id, ?names);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_types = authorization_detail_types::table
+            .filter(authorization_detail_types::client_id.eq(client_id))
+            .filter(authorization_detail_types::name.eq_any(names))
+            .load::<PgAuthorizationDetailType>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(pg_types
+            .into_iter()
+            .map(AuthorizationDetailMapper::type_from_pg)
+            .collect::<Vec<AuthorizationDetailTypeModel>>())
+    }
+
+    async fn get_all_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<AuthorizationDetailTypeModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_client_id", client_id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_types = authorization_detail_types::table
+            .filter(authorization_detail_types::client_id.eq(client_id))
+            .load::<PgAuthorizationDetailType>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(pg_types
+            .into_iter()
+            .map(AuthorizationDetailMapper::type_from_pg)
+            .collect::<Vec<AuthorizationDetailTypeModel>>())
+    }
+
+    async fn delete_by_name(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_name", client_id, name);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let affected_rows = diesel::delete(authorization_detail_types::table)
+            .filter(authorization_detail_types::client_id.eq(client_id))
+            .filter(authorization_detail_types::name.eq(name))
+            .execute(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    db::{
+        pg::{models::PgBackchannelAuthorization, schema::backchannel_authorizations},
+        repositories::{BackchannelAuthorizationRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::{AuthorizationDetailMapper, BackchannelAuthorizationMapper},
+        models::{
+            BackchannelAuthorizationCreateModel, BackchannelAuthorizationModel,
+            BackchannelAuthorizationStatus,
+        },
+    },
+};
+
+pub struct PgBackchannelAuthorizationRepository;
+
+#[async_trait]
+impl BackchannelAuthorizationRepository for PgBackchannelAuthorizationRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        backchannel_authorization_create: &BackchannelAuthorizationCreateModel,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "create", ?backchannel_authorization_create);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_backchannel_authorization = diesel::insert_into(backchannel_authorizations::table)
+            .values((
+                backchannel_authorizations::auth_req_id
+                    .eq(&backchannel_authorization_create.auth_req_id),
+                backchannel_authorizations::client_id
+                    .eq(&backchannel_authorization_create.client_id),
+                backchannel_authorizations::user_id.eq(&backchannel_authorization_create.user_id),
+                backchannel_authorizations::binding_message
+                    .eq(&backchannel_authorization_create.binding_message),
+                backchannel_authorizations::delivery_mode
+                    .eq(backchannel_authorization_create.delivery_mode.as_str()),
+                backchannel_authorizations::client_notification_token
+                    .eq(&backchannel_authorization_create.client_notification_token),
+                backchannel_authorizations::client_notification_endpoint
+                    .eq(&backchannel_authorization_create.client_notification_endpoint),
+                backchannel_authorizations::poll_interval
+                    .eq(&backchannel_authorization_create.poll_interval),
+                backchannel_authorizations::expires_at
+                    .eq(&backchannel_authorization_create.expires_at),
+                backchannel_authorizations::scopes.eq(&backchannel_authorization_create.scopes),
+                backchannel_authorizations::authorization_details.eq(
+                    AuthorizationDetailMapper::vec_to_pg_value(
+                        &backchannel_authorization_create.authorization_details,
+                    ),
+                ),
+            ))
+            .get_result::<PgBackchannelAuthorization>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(BackchannelAuthorizationMapper::from_pg(
+            pg_backchannel_authorization,
+        ))
+    }
+
+    async fn get_by_auth_req_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "get_by_auth_req_id");
+
+        let now = Utc::now().naive_utc();
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_backchannel_authorization = backchannel_authorizations::table
+            .filter(backchannel_authorizations::auth_req_id.eq(auth_req_id))
+            .filter(backchannel_authorizations::created_at.lt(now))
+            .filter(backchannel_authorizations::expires_at.gt(now))
+            .first::<PgBackchannelAuthorization>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(BackchannelAuthorizationMapper::from_pg(
+            pg_backchannel_authorization,
+        ))
+    }
+
+    async fn update_status_by_auth_req_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+        status: BackchannelAuthorizationStatus,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "update_status_by_auth_req_id", ?status);
+
+        let now = Utc::now().naive_utc();
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // only a pending request may be resolved, and only once
+        let pg_backchannel_authorization = diesel::update(backchannel_authorizations::table)
+            .filter(backchannel_authorizations::auth_req_id.eq(auth_req_id))
+            .filter(
+                backchannel_authorizations::status
+                    .eq(BackchannelAuthorizationStatus::Pending.as_str()),
+            )
+            .filter(backchannel_authorizations::expires_at.gt(now))
+            .set(backchannel_authorizations::status.eq(status.as_str()))
+            .get_result::<PgBackchannelAuthorization>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(BackchannelAuthorizationMapper::from_pg(
+            pg_backchannel_authorization,
+        ))
+    }
+
+    async fn update_last_polled_at_by_auth_req_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+        last_polled_at: &NaiveDateTime,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError> {
+        tracing::trace!(
+            method = "update_last_polled_at_by_auth_req_id",
+            ?last_polled_at
+        );
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_backchannel_authorization = diesel::update(backchannel_authorizations::table)
+            .filter(backchannel_authorizations::auth_req_id.eq(auth_req_id))
+            .set(backchannel_authorizations::last_polled_at.eq(last_polled_at))
+            .get_result::<PgBackchannelAuthorization>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(BackchannelAuthorizationMapper::from_pg(
+            pg_backchannel_authorization,
+        ))
+    }
+
+    async fn delete_by_auth_req_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_auth_req_id");
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let affected_rows = diesel::delete(backchannel_authorizations::table)
+            .filter(backchannel_authorizations::auth_req_id.eq(auth_req_id))
+            .execute(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
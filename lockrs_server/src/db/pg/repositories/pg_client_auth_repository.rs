@@ -1,21 +1,23 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
 use diesel::prelude::*;
 use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection, RunQueryDsl};
 
 use crate::{
     db::{
         pg::{
-            models::{PgClient, PgRedirectUri},
+            models::{PgClient, PgClientSecret, PgRedirectUri},
+            schema::client_secrets,
             schema::clients,
             schema::redirect_uris,
         },
         repositories::{ClientAuthRepository, RepositoryError},
         DbContext,
     },
-    mappers::ClientAuthMapper,
-    models::{ClientAuthModel, RedirectCreateModel},
+    mappers::{ClientAuthMapper, ClientSecretMapper},
+    models::{ClientAuthModel, ClientSecretCreateModel, ClientSecretModel, RedirectCreateModel},
 };
 
 pub struct PgClientAuthRepository;
@@ -26,6 +28,7 @@ impl ClientAuthRepository for PgClientAuthRepository {
         &self,
         db_context: &Arc<DbContext>,
         client_create: &ClientAuthModel,
+        secret_create: Option<&ClientSecretCreateModel>,
         redirect_create: &RedirectCreateModel,
     ) -> Result<ClientAuthModel, RepositoryError> {
         tracing::trace!(
@@ -47,9 +50,8 @@ impl ClientAuthRepository for PgClientAuthRepository {
                     let client = diesel::insert_into(clients::table)
                         .values((
                             clients::id.eq(&client_create.id),
-                            clients::secret.eq(&client_create.secret),
                             clients::user_id.eq(&client_create.user_id),
-                            clients::is_public.eq(client_create.secret.is_none()),
+                            clients::is_public.eq(client_create.is_public),
                             clients::name.eq(&client_create.name),
                             clients::description.eq(&client_create.description),
                             clients::homepage_url.eq(&client_create.homepage_url.to_string()),
@@ -57,6 +59,18 @@ impl ClientAuthRepository for PgClientAuthRepository {
                         .get_result::<PgClient>(conn)
                         .await?;
 
+                    if let Some(secret_create) = secret_create {
+                        diesel::insert_into(client_secrets::table)
+                            .values((
+                                client_secrets::client_id.eq(&secret_create.client_id),
+                                client_secrets::salt.eq(&secret_create.salt),
+                                client_secrets::secret_hash.eq(&secret_create.secret_hash),
+                                client_secrets::expires_at.eq(&secret_create.expires_at),
+                            ))
+                            .execute(conn)
+                            .await?;
+                    }
+
                     diesel::insert_into(redirect_uris::table)
                         .values((
                             redirect_uris::client_id.eq(&redirect_create.client_id),
@@ -75,22 +89,12 @@ impl ClientAuthRepository for PgClientAuthRepository {
         Ok(ClientAuthMapper::from_pg(pg_client))
     }
 
-    async fn get_by_credentials(
+    async fn get_by_id(
         &self,
         db_context: &Arc<DbContext>,
         id: &str,
-        secret: Option<&str>,
     ) -> Result<ClientAuthModel, RepositoryError> {
-        tracing::trace!(method = "get_by_credentials", id);
-
-        let mut query = clients::table
-            .into_boxed()
-            .filter(clients::id.eq(&id))
-            .filter(clients::is_public.eq(secret.is_none()));
-
-        if let Some(secret) = secret {
-            query = query.filter(clients::secret.eq(secret));
-        }
+        tracing::trace!(method = "get_by_id", id);
 
         let conn = &mut db_context
             .as_ref()
@@ -98,11 +102,95 @@ impl ClientAuthRepository for PgClientAuthRepository {
             .await
             .map_err(RepositoryError::from)?;
 
-        let pg_client = query
+        let pg_client = clients::table
+            .filter(clients::id.eq(id))
             .first::<PgClient>(conn)
             .await
             .map_err(RepositoryError::map_diesel_found)?;
 
         Ok(ClientAuthMapper::from_pg(pg_client))
     }
+
+    async fn get_active_secrets_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<ClientSecretModel>, RepositoryError> {
+        tracing::trace!(method = "get_active_secrets_by_client_id", client_id);
+
+        let now = Utc::now().naive_utc();
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_client_secrets = client_secrets::table
+            .filter(client_secrets::client_id.eq(client_id))
+            .filter(
+                client_secrets::expires_at
+                    .is_null()
+                    .or(client_secrets::expires_at.gt(now)),
+            )
+            .load::<PgClientSecret>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(pg_client_secrets
+            .into_iter()
+            .map(ClientSecretMapper::from_pg)
+            .collect::<Vec<ClientSecretModel>>())
+    }
+
+    async fn rotate_secret(
+        &self,
+        db_context: &Arc<DbContext>,
+        secret_create: &ClientSecretCreateModel,
+        previous_expires_at: &NaiveDateTime,
+    ) -> Result<ClientSecretModel, RepositoryError> {
+        tracing::trace!(
+            method = "rotate_secret",
+            ?secret_create,
+            ?previous_expires_at
+        );
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_client_secret = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                async move {
+                    // secrets that already expire sooner keep their expiry
+                    diesel::update(client_secrets::table)
+                        .filter(client_secrets::client_id.eq(&secret_create.client_id))
+                        .filter(
+                            client_secrets::expires_at
+                                .is_null()
+                                .or(client_secrets::expires_at.gt(previous_expires_at)),
+                        )
+                        .set(client_secrets::expires_at.eq(previous_expires_at))
+                        .execute(conn)
+                        .await?;
+
+                    diesel::insert_into(client_secrets::table)
+                        .values((
+                            client_secrets::client_id.eq(&secret_create.client_id),
+                            client_secrets::salt.eq(&secret_create.salt),
+                            client_secrets::secret_hash.eq(&secret_create.secret_hash),
+                            client_secrets::expires_at.eq(&secret_create.expires_at),
+                        ))
+                        .get_result::<PgClientSecret>(conn)
+                        .await
+                }
+                .scope_boxed()
+            })
+            .await
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(ClientSecretMapper::from_pg(pg_client_secret))
+    }
 }
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::{pg::upsert::excluded, prelude::*};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    db::{
+        pg::{models::PgClientPolicy, schema::client_policies},
+        repositories::{ClientPolicyRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    mappers::ClientPolicyMapper,
+    models::ClientPolicyModel,
+};
+
+pub struct PgClientPolicyRepository;
+
+#[async_trait]
+impl ClientPolicyRepository for PgClientPolicyRepository {
+    async fn upsert(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_policy: &ClientPolicyModel,
+    ) -> Result<ClientPolicyModel, RepositoryError> {
+        tracing::trace!(method = "upsert", ?client_policy);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_client_policy = diesel::insert_into(client_policies::table)
+            .values(ClientPolicyMapper::to_pg(client_policy))
+            .on_conflict(client_policies::client_id)
+            .do_update()
+            .set((
+                client_policies::grant_types.eq(excluded(client_policies::grant_types)),
+                client_policies::access_token_lifetime
+                    .eq(excluded(client_policies::access_token_lifetime)),
+                client_policies::refresh_token_lifetime
+                    .eq(excluded(client_policies::refresh_token_lifetime)),
+                client_policies::id_token_lifetime.eq(excluded(client_policies::id_token_lifetime)),
+                client_policies::absolute_refresh_lifetime
+                    .eq(excluded(client_policies::absolute_refresh_lifetime)),
+                client_policies::sliding_refresh_window
+                    .eq(excluded(client_policies::sliding_refresh_window)),
+                client_policies::always_issue_refresh_token
+                    .eq(excluded(client_policies::always_issue_refresh_token)),
+                client_policies::backchannel_client_notification_endpoint.eq(excluded(
+                    client_policies::backchannel_client_notification_endpoint,
+                )),
+            ))
+            .get_result::<PgClientPolicy>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(ClientPolicyMapper::from_pg(pg_client_policy))
+    }
+
+    async fn get_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<ClientPolicyModel, RepositoryError> {
+        tracing::trace!(method = "get_by_client_id", client_id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_client_policy = client_policies::table
+            .filter(client_policies::client_id.eq(client_id))
+            .first::<PgClientPolicy>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(ClientPolicyMapper::from_pg(pg_client_policy))
+    }
+
+    async fn delete_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_client_id", client_id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let affected_rows = diesel::delete(client_policies::table)
+            .filter(client_policies::client_id.eq(client_id))
+            .execute(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
@@ -1,12 +1,14 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use chrono::offset::Utc;
+use chrono::{offset::Utc, NaiveDateTime};
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use scoped_futures::ScopedFutureExt;
 
 use crate::{
     db::{
+        digest_token,
         pg::{models::PgDeviceAuthorization, schema::device_authorizations},
         repositories::{DeviceAuthorizationRepository, RepositoryError},
         DbContext,
@@ -38,7 +40,9 @@ impl DeviceAuthorizationRepository for PgDeviceAuthorizationRepository {
             .values((
                 device_authorizations::client_id.eq(&device_authorization_create.client_id),
                 device_authorizations::user_code.eq(&device_authorization_create.user_code),
-                device_authorizations::device_code.eq(&device_authorization_create.device_code),
+                device_authorizations::device_code.eq(digest_token(
+                    device_authorization_create.device_code.as_str(),
+                )),
                 device_authorizations::expires_at.eq(&device_authorization_create.expires_at),
                 device_authorizations::scopes.eq(&device_authorization_create.scopes),
             ))
@@ -46,7 +50,11 @@ impl DeviceAuthorizationRepository for PgDeviceAuthorizationRepository {
             .await
             .map_err(RepositoryError::map_diesel_create)?;
 
-        Ok(DeviceAuthorizationMapper::from_pg(pg_device_authorization))
+        // only the digest is stored, so hand the caller back the device code they issued
+        let mut device_authorization = DeviceAuthorizationMapper::from_pg(pg_device_authorization);
+        device_authorization.device_code = device_authorization_create.device_code.to_owned();
+
+        Ok(device_authorization)
     }
 
     async fn get_by_user_code(
@@ -65,13 +73,14 @@ impl DeviceAuthorizationRepository for PgDeviceAuthorizationRepository {
             .map_err(RepositoryError::from)?;
 
         let pg_device_authorization = device_authorizations::table
-            .filter(device_authorizations::device_code.eq(code))
+            .filter(device_authorizations::user_code.eq(code))
             .filter(device_authorizations::created_at.lt(now))
             .filter(device_authorizations::expires_at.gt(now))
             .first::<PgDeviceAuthorization>(conn)
             .await
             .map_err(RepositoryError::map_diesel_found)?;
 
+        // the device code cannot be recovered from its digest here, only the user code is usable
         Ok(DeviceAuthorizationMapper::from_pg(pg_device_authorization))
     }
 
@@ -91,14 +100,17 @@ impl DeviceAuthorizationRepository for PgDeviceAuthorizationRepository {
             .map_err(RepositoryError::from)?;
 
         let pg_device_authorization = device_authorizations::table
-            .filter(device_authorizations::user_code.eq(code))
+            .filter(device_authorizations::device_code.eq(digest_token(code)))
             .filter(device_authorizations::created_at.lt(now))
             .filter(device_authorizations::expires_at.gt(now))
             .first::<PgDeviceAuthorization>(conn)
             .await
             .map_err(RepositoryError::map_diesel_found)?;
 
-        Ok(DeviceAuthorizationMapper::from_pg(pg_device_authorization))
+        let mut device_authorization = DeviceAuthorizationMapper::from_pg(pg_device_authorization);
+        device_authorization.device_code = code.to_owned();
+
+        Ok(device_authorization)
     }
 
     async fn delete_by_device_code(
@@ -110,4 +122,42 @@ impl DeviceAuthorizationRepository for PgDeviceAuthorizationRepository {
 
         todo!();
     }
+
+    async fn delete_expired(
+        &self,
+        db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "delete_expired", ?expired_before, limit);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // each batch locks a bounded set of rows, skipping any another request holds, and commits
+        // before the next so no lock is held for longer than a single short delete
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let expired_ids = device_authorizations::table
+                    .select(device_authorizations::id)
+                    .filter(device_authorizations::expires_at.lt(expired_before))
+                    .limit(limit)
+                    .for_update()
+                    .skip_locked()
+                    .load::<i32>(conn)
+                    .await?;
+
+                diesel::delete(device_authorizations::table)
+                    .filter(device_authorizations::id.eq_any(expired_ids))
+                    .execute(conn)
+                    .await
+            }
+            .scope_boxed()
+        })
+        .await
+        .map_err(RepositoryError::map_diesel_delete)
+    }
 }
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    api::v1::{
+        mappers::FederatedIdentityMapper,
+        models::{FederatedIdentityCreateModel, FederatedIdentityModel},
+    },
+    db::{
+        pg::{models::PgFederatedIdentity, schema::federated_identities},
+        repositories::{FederatedIdentityRepository, RepositoryError},
+        DbContext,
+    },
+};
+
+pub struct PgFederatedIdentityRepository;
+
+#[async_trait]
+impl FederatedIdentityRepository for PgFederatedIdentityRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        identity_create: &FederatedIdentityCreateModel,
+    ) -> Result<FederatedIdentityModel, RepositoryError> {
+        tracing::trace!(method = "create", ?identity_create);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_identity = diesel::insert_into(federated_identities::table)
+            .values((
+                federated_identities::user_id.eq(&identity_create.user_id),
+                federated_identities::provider.eq(&identity_create.provider),
+                federated_identities::subject.eq(&identity_create.subject),
+            ))
+            .get_result::<PgFederatedIdentity>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(FederatedIdentityMapper::from_pg(pg_identity))
+    }
+
+    async fn get_by_provider_and_subject(
+        &self,
+        db_context: &Arc<DbContext>,
+        provider: &str,
+        subject: &str,
+    ) -> Result<FederatedIdentityModel, RepositoryError> {
+        tracing::trace!(method = "get_by_provider_and_subject", provider, subject);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_identity = federated_identities::table
+            .filter(federated_identities::provider.eq(provider))
+            .filter(federated_identities::subject.eq(subject))
+            .first::<PgFederatedIdentity>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(FederatedIdentityMapper::from_pg(pg_identity))
+    }
+}
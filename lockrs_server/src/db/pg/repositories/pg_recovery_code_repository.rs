@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::db::{
+    pg::schema::recovery_codes,
+    repositories::{QueryFailure, RecoveryCodeRepository, RepositoryError},
+    DbContext,
+};
+
+pub struct PgRecoveryCodeRepository;
+
+#[async_trait]
+impl RecoveryCodeRepository for PgRecoveryCodeRepository {
+    async fn replace_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        code_hashes: &[String],
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(
+            method = "replace_by_user_id",
+            ?user_id,
+            count = code_hashes.len()
+        );
+
+        let values = code_hashes
+            .iter()
+            .map(|code_hash| {
+                (
+                    recovery_codes::user_id.eq(user_id),
+                    recovery_codes::code_hash.eq(code_hash),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        conn.transaction::<(), RepositoryError, _>(|conn| {
+            async move {
+                diesel::delete(recovery_codes::table)
+                    .filter(recovery_codes::user_id.eq(user_id))
+                    .execute(conn)
+                    .await
+                    .map_err(RepositoryError::map_diesel_delete)?;
+
+                diesel::insert_into(recovery_codes::table)
+                    .values(values)
+                    .execute(conn)
+                    .await
+                    .map_err(RepositoryError::map_diesel_create)?;
+
+                Ok(())
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    async fn use_by_user_id_and_hash(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        code_hash: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "use_by_user_id_and_hash", ?user_id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let affected_rows = diesel::update(recovery_codes::table)
+            .filter(recovery_codes::user_id.eq(user_id))
+            .filter(recovery_codes::code_hash.eq(code_hash))
+            .filter(recovery_codes::used_at.is_null())
+            .set(recovery_codes::used_at.eq(Utc::now().naive_utc()))
+            .execute(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by update, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotUpdated));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_user_id", ?user_id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        diesel::delete(recovery_codes::table)
+            .filter(recovery_codes::user_id.eq(user_id))
+            .execute(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        Ok(())
+    }
+}
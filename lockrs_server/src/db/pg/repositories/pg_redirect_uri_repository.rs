@@ -44,6 +44,7 @@ impl RedirectUriRepository for PgRedirectUriRepository {
             .values((
                 redirect_uris::client_id.eq(&redirect_create.client_id),
                 redirect_uris::uri.eq(redirect_create.uri.to_string()),
+                redirect_uris::match_mode.eq(redirect_create.match_mode.as_str()),
             ))
             .get_result::<PgRedirectUri>(conn)
             .await
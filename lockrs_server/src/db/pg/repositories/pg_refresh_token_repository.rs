@@ -1,19 +1,21 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use chrono::offset::Utc;
+use chrono::{offset::Utc, NaiveDateTime};
 use diesel::prelude::*;
 use diesel_async::{AsyncConnection, RunQueryDsl};
 use scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
 
 use crate::{
     db::{
+        digest_token,
         pg::{models::PgRefreshToken, schema::refresh_tokens},
         repositories::{QueryFailure, RefreshTokenRepository, RepositoryError},
         DbContext,
     },
     oauth2::v1::{
-        mappers::RefreshTokenMapper,
+        mappers::{AuthorizationDetailMapper, RefreshTokenMapper},
         models::{RefreshTokenCreateModel, RefreshTokenModel},
     },
 };
@@ -37,17 +39,26 @@ impl RefreshTokenRepository for PgRefreshTokenRepository {
 
         let pg_token = diesel::insert_into(refresh_tokens::table)
             .values((
-                refresh_tokens::token.eq(&token_create.token),
+                refresh_tokens::token.eq(digest_token(token_create.token.as_str())),
+                refresh_tokens::access_token_id.eq(&token_create.access_token_id),
                 refresh_tokens::client_id.eq(&token_create.client_id),
                 refresh_tokens::user_id.eq(&token_create.user_id),
                 refresh_tokens::expires_at.eq(&token_create.expires_at),
                 refresh_tokens::scopes.eq(&token_create.scopes),
+                refresh_tokens::authorization_details.eq(
+                    AuthorizationDetailMapper::vec_to_pg_value(&token_create.authorization_details),
+                ),
+                refresh_tokens::grant_created_at.eq(&token_create.grant_created_at),
             ))
             .get_result::<PgRefreshToken>(conn)
             .await
             .map_err(RepositoryError::map_diesel_create)?;
 
-        Ok(RefreshTokenMapper::from_pg(pg_token))
+        // only the digest is stored, so hand the caller back the token they issued
+        let mut token = RefreshTokenMapper::from_pg(pg_token);
+        token.token = token_create.token.to_owned();
+
+        Ok(token)
     }
 
     async fn get_by_token(
@@ -66,7 +77,7 @@ impl RefreshTokenRepository for PgRefreshTokenRepository {
         let now = Utc::now().naive_utc();
 
         let pg_token = refresh_tokens::table
-            .filter(refresh_tokens::token.eq(token))
+            .filter(refresh_tokens::token.eq(digest_token(token)))
             .filter(refresh_tokens::created_at.lt(&now))
             .filter(refresh_tokens::expires_at.gt(&now))
             .filter(refresh_tokens::used.eq(false))
@@ -74,7 +85,10 @@ impl RefreshTokenRepository for PgRefreshTokenRepository {
             .await
             .map_err(RepositoryError::map_diesel_found)?;
 
-        Ok(RefreshTokenMapper::from_pg(pg_token))
+        let mut refresh_token = RefreshTokenMapper::from_pg(pg_token);
+        refresh_token.token = token.to_owned();
+
+        Ok(refresh_token)
     }
 
     async fn use_by_token(
@@ -93,7 +107,7 @@ impl RefreshTokenRepository for PgRefreshTokenRepository {
         let now = Utc::now().naive_utc();
 
         let pg_token = diesel::update(refresh_tokens::table)
-            .filter(refresh_tokens::token.eq(token))
+            .filter(refresh_tokens::token.eq(digest_token(token)))
             .filter(refresh_tokens::created_at.lt(&now))
             .filter(refresh_tokens::expires_at.gt(&now))
             .filter(refresh_tokens::used.eq(false))
@@ -102,7 +116,62 @@ impl RefreshTokenRepository for PgRefreshTokenRepository {
             .await
             .map_err(RepositoryError::map_diesel_update)?;
 
-        Ok(RefreshTokenMapper::from_pg(pg_token))
+        let mut refresh_token = RefreshTokenMapper::from_pg(pg_token);
+        refresh_token.token = token.to_owned();
+
+        Ok(refresh_token)
+    }
+
+    async fn revoke_all_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "revoke_all_by_user_id", ?user_id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        diesel::update(refresh_tokens::table)
+            .filter(refresh_tokens::user_id.eq(user_id))
+            .filter(refresh_tokens::used.eq(false))
+            .set(refresh_tokens::used.eq(true))
+            .execute(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_update)
+    }
+
+    async fn revoke_all_by_user_id_and_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        client_id: &str,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(
+            method = "revoke_all_by_user_id_and_client_id",
+            ?user_id,
+            client_id
+        );
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // a used token can no longer be exchanged, so marking every outstanding token as used
+        // revokes the grant
+        diesel::update(refresh_tokens::table)
+            .filter(refresh_tokens::user_id.eq(user_id))
+            .filter(refresh_tokens::client_id.eq(client_id))
+            .filter(refresh_tokens::used.eq(false))
+            .set(refresh_tokens::used.eq(true))
+            .execute(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_update)
     }
 
     async fn delete_by_token(
@@ -121,7 +190,7 @@ impl RefreshTokenRepository for PgRefreshTokenRepository {
         conn.transaction::<(), RepositoryError, _>(|conn| {
             async move {
                 let affected_rows = diesel::delete(refresh_tokens::table)
-                    .filter(refresh_tokens::token.eq(token))
+                    .filter(refresh_tokens::token.eq(digest_token(token)))
                     .execute(conn)
                     .await
                     .map_err(RepositoryError::map_diesel_delete)?;
@@ -142,4 +211,42 @@ impl RefreshTokenRepository for PgRefreshTokenRepository {
         })
         .await
     }
+
+    async fn delete_expired(
+        &self,
+        db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "delete_expired", ?expired_before, limit);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // each batch locks a bounded set of rows, skipping any another request holds, and commits
+        // before the next so no lock is held for longer than a single short delete
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let expired_ids = refresh_tokens::table
+                    .select(refresh_tokens::id)
+                    .filter(refresh_tokens::expires_at.lt(expired_before))
+                    .limit(limit)
+                    .for_update()
+                    .skip_locked()
+                    .load::<i32>(conn)
+                    .await?;
+
+                diesel::delete(refresh_tokens::table)
+                    .filter(refresh_tokens::id.eq_any(expired_ids))
+                    .execute(conn)
+                    .await
+            }
+            .scope_boxed()
+        })
+        .await
+        .map_err(RepositoryError::map_diesel_delete)
+    }
 }
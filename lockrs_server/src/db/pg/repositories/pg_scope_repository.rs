@@ -2,15 +2,24 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use scoped_futures::ScopedFutureExt;
 
 use crate::{
     db::{
-        pg::schema::scopes,
-        repositories::{RepositoryError, ScopeRepository},
+        pg::{
+            models::{PgAllowedScope, PgScope},
+            schema::{allowed_scopes, scopes},
+        },
+        repositories::{QueryFailure, RepositoryError, ScopeRepository},
         DbContext,
     },
-    oauth2::v1::models::{ScopeCreateModel, ScopeModel},
+    oauth2::v1::{
+        mappers::ScopeMapper,
+        models::{
+            AllowedScopeModel, ScopeCreateModel, ScopeDefinitionModel, ScopeModel, ScopeUpdateModel,
+        },
+    },
 };
 
 pub struct PgScopeRepository;
@@ -19,12 +28,29 @@ pub struct PgScopeRepository;
 impl ScopeRepository for PgScopeRepository {
     async fn create(
         &self,
-        _db_context: &Arc<DbContext>,
-        _scope_create: &ScopeCreateModel,
-    ) -> Result<ScopeModel, RepositoryError> {
-        tracing::trace!(method = "create");
+        db_context: &Arc<DbContext>,
+        scope_create: &ScopeCreateModel,
+    ) -> Result<ScopeDefinitionModel, RepositoryError> {
+        tracing::trace!(method = "create", ?scope_create);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_scope = diesel::insert_into(scopes::table)
+            .values((
+                scopes::name.eq(&scope_create.scope),
+                scopes::description.eq(&scope_create.description),
+                scopes::client_id.eq(&scope_create.client_id),
+                scopes::consent_text.eq(&scope_create.consent_text),
+            ))
+            .get_result::<PgScope>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_create)?;
 
-        todo!();
+        Ok(ScopeMapper::from_pg(pg_scope))
     }
 
     async fn get_from_list(
@@ -53,13 +79,167 @@ impl ScopeRepository for PgScopeRepository {
         Ok(ScopeModel::new(pg_scopes.as_slice()))
     }
 
+    async fn get_all_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<ScopeDefinitionModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_client_id", client_id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_scopes = scopes::table
+            .filter(scopes::client_id.eq(client_id))
+            .order(scopes::name.asc())
+            .load::<PgScope>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(pg_scopes
+            .into_iter()
+            .map(ScopeMapper::from_pg)
+            .collect::<Vec<ScopeDefinitionModel>>())
+    }
+
+    async fn get_allowed_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<AllowedScopeModel>, RepositoryError> {
+        tracing::trace!(method = "get_allowed_by_client_id", client_id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_allowed_scopes = allowed_scopes::table
+            .filter(allowed_scopes::client_id.eq(client_id))
+            .order(allowed_scopes::scope.asc())
+            .load::<PgAllowedScope>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(pg_allowed_scopes
+            .into_iter()
+            .map(ScopeMapper::allowed_from_pg)
+            .collect::<Vec<AllowedScopeModel>>())
+    }
+
+    async fn replace_allowed_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        allowed_scopes: &[AllowedScopeModel],
+    ) -> Result<Vec<AllowedScopeModel>, RepositoryError> {
+        tracing::trace!(
+            method = "replace_allowed_by_client_id",
+            client_id,
+            ?allowed_scopes
+        );
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_allowed_scopes = allowed_scopes
+            .iter()
+            .map(|allowed_scope| PgAllowedScope {
+                client_id: client_id.to_owned(),
+                scope: allowed_scope.scope.to_owned(),
+                is_default: allowed_scope.is_default,
+            })
+            .collect::<Vec<PgAllowedScope>>();
+
+        let pg_allowed_scopes = conn
+            .transaction::<Vec<PgAllowedScope>, RepositoryError, _>(|conn| {
+                async move {
+                    diesel::delete(allowed_scopes::table)
+                        .filter(allowed_scopes::client_id.eq(client_id))
+                        .execute(conn)
+                        .await
+                        .map_err(RepositoryError::map_diesel_delete)?;
+
+                    diesel::insert_into(allowed_scopes::table)
+                        .values(&pg_allowed_scopes)
+                        .get_results::<PgAllowedScope>(conn)
+                        .await
+                        .map_err(RepositoryError::map_diesel_create)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(pg_allowed_scopes
+            .into_iter()
+            .map(ScopeMapper::allowed_from_pg)
+            .collect::<Vec<AllowedScopeModel>>())
+    }
+
+    async fn update_by_name(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
+        scope_update: &ScopeUpdateModel,
+    ) -> Result<ScopeDefinitionModel, RepositoryError> {
+        tracing::trace!(method = "update_by_name", client_id, name, ?scope_update);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_scope = diesel::update(scopes::table)
+            .filter(scopes::client_id.eq(client_id))
+            .filter(scopes::name.eq(name))
+            .set(scope_update)
+            .get_result::<PgScope>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(ScopeMapper::from_pg(pg_scope))
+    }
+
     async fn delete_by_name(
         &self,
-        _db_context: &Arc<DbContext>,
-        _id: &str,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
     ) -> Result<(), RepositoryError> {
-        tracing::trace!(method = "delete_by_name",);
+        tracing::trace!(method = "delete_by_name", client_id, name);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let affected_rows = diesel::delete(scopes::table)
+            .filter(scopes::client_id.eq(client_id))
+            .filter(scopes::name.eq(name))
+            .execute(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
 
-        todo!();
+        Ok(())
     }
 }
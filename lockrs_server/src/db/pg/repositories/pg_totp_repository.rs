@@ -0,0 +1,190 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        mappers::TotpMapper,
+        models::{TotpCreateModel, TotpModel},
+    },
+    db::{
+        pg::{models::PgTotpSecret, schema::totp_secrets},
+        repositories::{QueryFailure, RepositoryError, TotpRepository},
+        DbContext,
+    },
+};
+
+pub struct PgTotpRepository;
+
+#[async_trait]
+impl TotpRepository for PgTotpRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        totp_create: &TotpCreateModel,
+    ) -> Result<TotpModel, RepositoryError> {
+        tracing::trace!(method = "create", ?totp_create);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_totp_secret = conn
+            .transaction::<PgTotpSecret, RepositoryError, _>(|conn| {
+                async move {
+                    // a confirmed enrollment is left in place, so the insert below violates the
+                    // primary key
+                    diesel::delete(totp_secrets::table)
+                        .filter(totp_secrets::user_id.eq(&totp_create.user_id))
+                        .filter(totp_secrets::confirmed_at.is_null())
+                        .execute(conn)
+                        .await
+                        .map_err(RepositoryError::map_diesel_delete)?;
+
+                    diesel::insert_into(totp_secrets::table)
+                        .values((
+                            totp_secrets::user_id.eq(&totp_create.user_id),
+                            totp_secrets::secret.eq(&totp_create.secret),
+                        ))
+                        .get_result::<PgTotpSecret>(conn)
+                        .await
+                        .map_err(RepositoryError::map_diesel_create)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(TotpMapper::from_pg(pg_totp_secret))
+    }
+
+    async fn get_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<TotpModel, RepositoryError> {
+        tracing::trace!(method = "get_by_user_id", ?user_id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_totp_secret = totp_secrets::table
+            .filter(totp_secrets::user_id.eq(user_id))
+            .first::<PgTotpSecret>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(TotpMapper::from_pg(pg_totp_secret))
+    }
+
+    async fn confirm_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        step: i64,
+    ) -> Result<TotpModel, RepositoryError> {
+        tracing::trace!(method = "confirm_by_user_id", ?user_id, step);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_totp_secret = diesel::update(totp_secrets::table)
+            .filter(totp_secrets::user_id.eq(user_id))
+            .filter(totp_secrets::confirmed_at.is_null())
+            .set((
+                totp_secrets::confirmed_at.eq(Utc::now().naive_utc()),
+                totp_secrets::last_used_step.eq(step),
+            ))
+            .get_result::<PgTotpSecret>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(TotpMapper::from_pg(pg_totp_secret))
+    }
+
+    async fn use_step_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        step: i64,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "use_step_by_user_id", ?user_id, step);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // compared and set in one statement, so two requests racing with the same code can not
+        // both be accepted
+        let affected_rows = diesel::update(totp_secrets::table)
+            .filter(totp_secrets::user_id.eq(user_id))
+            .filter(totp_secrets::confirmed_at.is_not_null())
+            .filter(
+                totp_secrets::last_used_step
+                    .is_null()
+                    .or(totp_secrets::last_used_step.lt(step)),
+            )
+            .set(totp_secrets::last_used_step.eq(step))
+            .execute(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by update, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotUpdated));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_user_id", ?user_id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let affected_rows = diesel::delete(totp_secrets::table)
+            .filter(totp_secrets::user_id.eq(user_id))
+            .execute(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
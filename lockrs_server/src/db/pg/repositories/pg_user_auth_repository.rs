@@ -3,6 +3,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use diesel::prelude::*;
 use diesel_async::RunQueryDsl;
+use uuid::Uuid;
 
 use crate::{
     api::v1::{
@@ -67,6 +68,28 @@ impl UserAuthRepository for PgUserAuthRepository {
         Ok(UserAuthMapper::from_pg(pg_user))
     }
 
+    async fn get_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", ?id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_user = users::table
+            .filter(users::id.eq(id))
+            .first::<PgUser>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+
     async fn get_by_email(
         &self,
         db_context: &Arc<DbContext>,
@@ -88,4 +111,55 @@ impl UserAuthRepository for PgUserAuthRepository {
 
         Ok(UserAuthMapper::from_pg(pg_user))
     }
+
+    async fn update_password_hash_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+        password_hash: &str,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "update_password_hash_by_id", ?id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_user = diesel::update(users::table)
+            .filter(users::id.eq(id))
+            .set(users::password_hash.eq(password_hash))
+            .get_result::<PgUser>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+
+    async fn change_password_hash_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+        password_hash: &str,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "change_password_hash_by_id", ?id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_pg_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let pg_user = diesel::update(users::table)
+            .filter(users::id.eq(id))
+            .set((
+                users::password_hash.eq(password_hash),
+                users::password_version.eq(users::password_version + 1),
+            ))
+            .get_result::<PgUser>(conn)
+            .await
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
 }
@@ -11,6 +11,27 @@ diesel::table! {
         created_at -> Timestamp,
         expires_at -> Timestamp,
         scopes -> Array<Nullable<Text>>,
+        authorization_details -> Jsonb,
+    }
+}
+
+diesel::table! {
+    allowed_scopes (client_id, scope) {
+        #[max_length = 32]
+        client_id -> Varchar,
+        scope -> Varchar,
+        is_default -> Bool,
+    }
+}
+
+diesel::table! {
+    authorization_detail_types (client_id, name) {
+        #[max_length = 64]
+        name -> Varchar,
+        #[max_length = 32]
+        client_id -> Varchar,
+        description -> Text,
+        schema -> Jsonb,
     }
 }
 
@@ -30,6 +51,61 @@ diesel::table! {
         expires_at -> Timestamp,
         used -> Bool,
         scopes -> Array<Nullable<Text>>,
+        authorization_details -> Jsonb,
+    }
+}
+
+diesel::table! {
+    backchannel_authorizations (id) {
+        id -> Int4,
+        #[max_length = 44]
+        auth_req_id -> Varchar,
+        #[max_length = 32]
+        client_id -> Varchar,
+        user_id -> Uuid,
+        #[max_length = 64]
+        binding_message -> Nullable<Varchar>,
+        #[max_length = 4]
+        delivery_mode -> Varchar,
+        client_notification_token -> Nullable<Text>,
+        client_notification_endpoint -> Nullable<Text>,
+        #[max_length = 8]
+        status -> Varchar,
+        poll_interval -> Int4,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        last_polled_at -> Nullable<Timestamp>,
+        scopes -> Array<Nullable<Text>>,
+        authorization_details -> Jsonb,
+    }
+}
+
+diesel::table! {
+    client_policies (client_id) {
+        #[max_length = 32]
+        client_id -> Varchar,
+        grant_types -> Array<Nullable<Text>>,
+        access_token_lifetime -> Int4,
+        refresh_token_lifetime -> Int4,
+        id_token_lifetime -> Int4,
+        absolute_refresh_lifetime -> Nullable<Int4>,
+        sliding_refresh_window -> Nullable<Int4>,
+        always_issue_refresh_token -> Bool,
+        backchannel_client_notification_endpoint -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    client_secrets (id) {
+        id -> Int4,
+        #[max_length = 32]
+        client_id -> Varchar,
+        #[max_length = 32]
+        salt -> Varchar,
+        #[max_length = 43]
+        secret_hash -> Varchar,
+        created_at -> Timestamp,
+        expires_at -> Nullable<Timestamp>,
     }
 }
 
@@ -37,8 +113,6 @@ diesel::table! {
     clients (id) {
         #[max_length = 32]
         id -> Varchar,
-        #[max_length = 32]
-        secret -> Nullable<Varchar>,
         user_id -> Uuid,
         is_public -> Bool,
         name -> Text,
@@ -48,6 +122,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    consents (id) {
+        id -> Int4,
+        user_id -> Uuid,
+        #[max_length = 32]
+        client_id -> Varchar,
+        scopes -> Array<Nullable<Text>>,
+        granted_at -> Timestamp,
+        expires_at -> Nullable<Timestamp>,
+        authorization_details -> Jsonb,
+    }
+}
+
 diesel::table! {
     device_authorizations (id) {
         id -> Int4,
@@ -63,6 +150,29 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    federated_identities (id) {
+        id -> Int4,
+        user_id -> Uuid,
+        #[max_length = 255]
+        provider -> Varchar,
+        #[max_length = 255]
+        subject -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    recovery_codes (id) {
+        id -> Int4,
+        user_id -> Uuid,
+        #[max_length = 43]
+        code_hash -> Varchar,
+        created_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     redirect_uris (id) {
         id -> Uuid,
@@ -71,6 +181,8 @@ diesel::table! {
         uri -> Text,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        #[max_length = 16]
+        match_mode -> Varchar,
     }
 }
 
@@ -87,6 +199,8 @@ diesel::table! {
         expires_at -> Timestamp,
         used -> Bool,
         scopes -> Array<Nullable<Text>>,
+        authorization_details -> Jsonb,
+        grant_created_at -> Timestamp,
     }
 }
 
@@ -97,6 +211,18 @@ diesel::table! {
         description -> Text,
         #[max_length = 32]
         client_id -> Nullable<Varchar>,
+        consent_text -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    totp_secrets (user_id) {
+        user_id -> Uuid,
+        #[max_length = 32]
+        secret -> Varchar,
+        confirmed_at -> Nullable<Timestamp>,
+        last_used_step -> Nullable<Int8>,
+        created_at -> Timestamp,
     }
 }
 
@@ -107,28 +233,65 @@ diesel::table! {
         email -> Varchar,
         #[max_length = 256]
         password_hash -> Varchar,
+        email_verified -> Bool,
+        password_version -> Int4,
+    }
+}
+
+diesel::table! {
+    webauthn_credentials (id) {
+        id -> Int4,
+        user_id -> Uuid,
+        #[max_length = 1366]
+        credential_id -> Varchar,
+        public_key -> Bytea,
+        sign_count -> Int8,
+        created_at -> Timestamp,
+        last_used_at -> Nullable<Timestamp>,
     }
 }
 
 diesel::joinable!(access_tokens -> clients (client_id));
 diesel::joinable!(access_tokens -> users (user_id));
+diesel::joinable!(allowed_scopes -> clients (client_id));
+diesel::joinable!(authorization_detail_types -> clients (client_id));
 diesel::joinable!(authorization_codes -> clients (client_id));
 diesel::joinable!(authorization_codes -> users (user_id));
+diesel::joinable!(backchannel_authorizations -> clients (client_id));
+diesel::joinable!(backchannel_authorizations -> users (user_id));
+diesel::joinable!(client_policies -> clients (client_id));
+diesel::joinable!(client_secrets -> clients (client_id));
 diesel::joinable!(clients -> users (user_id));
+diesel::joinable!(consents -> clients (client_id));
+diesel::joinable!(consents -> users (user_id));
 diesel::joinable!(device_authorizations -> clients (client_id));
+diesel::joinable!(federated_identities -> users (user_id));
 diesel::joinable!(redirect_uris -> clients (client_id));
+diesel::joinable!(recovery_codes -> users (user_id));
 diesel::joinable!(refresh_tokens -> access_tokens (access_token_id));
 diesel::joinable!(refresh_tokens -> clients (client_id));
 diesel::joinable!(refresh_tokens -> users (user_id));
 diesel::joinable!(scopes -> clients (client_id));
+diesel::joinable!(totp_secrets -> users (user_id));
+diesel::joinable!(webauthn_credentials -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     access_tokens,
+    allowed_scopes,
     authorization_codes,
+    authorization_detail_types,
+    backchannel_authorizations,
+    client_policies,
+    client_secrets,
     clients,
+    consents,
     device_authorizations,
+    federated_identities,
+    recovery_codes,
     redirect_uris,
     refresh_tokens,
     scopes,
+    totp_secrets,
     users,
+    webauthn_credentials,
 );
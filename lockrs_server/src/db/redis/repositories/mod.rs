@@ -1,4 +1,15 @@
+mod redis_federation_state_repository;
+mod redis_login_attempt_repository;
+mod redis_magic_link_repository;
+mod redis_password_reset_token_repository;
+mod redis_pushed_authorization_request_repository;
 mod redis_session_repository;
 mod redis_session_token_repository;
+mod redis_webauthn_challenge_repository;
 
-pub use self::{redis_session_repository::*, redis_session_token_repository::*};
+pub use self::{
+    redis_federation_state_repository::*, redis_login_attempt_repository::*,
+    redis_magic_link_repository::*, redis_password_reset_token_repository::*,
+    redis_pushed_authorization_request_repository::*, redis_session_repository::*,
+    redis_session_token_repository::*, redis_webauthn_challenge_repository::*,
+};
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::v1::models::FederationStateModel,
+    db::{
+        digest::digest_token,
+        repositories::{FederationStateRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+};
+
+pub struct RedisFederationStateRepository;
+
+impl RedisFederationStateRepository {
+    fn into_redis_key(state: &str) -> String {
+        format!("federation_state:{}", digest_token(state))
+    }
+}
+
+#[async_trait]
+impl FederationStateRepository for RedisFederationStateRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        state: &str,
+        federation_state: &FederationStateModel,
+    ) -> Result<FederationStateModel, RepositoryError> {
+        tracing::trace!(method = "create", ?federation_state);
+
+        let key = Self::into_redis_key(state);
+        let value = serde_json::to_string(federation_state).unwrap();
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        redis::cmd("SET")
+            .arg(key.as_str())
+            .arg(value.as_str())
+            .arg("PXAT")
+            .arg(federation_state.expires_at)
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis_create)?;
+
+        Ok(federation_state.clone())
+    }
+
+    async fn take_by_state(
+        &self,
+        db_context: &Arc<DbContext>,
+        state: &str,
+    ) -> Result<FederationStateModel, RepositoryError> {
+        tracing::trace!(method = "take_by_state");
+
+        let key = Self::into_redis_key(state);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // read and deleted in one transaction, so a sign-in can only ever be completed once
+        let (value,): (Option<String>,) = redis::pipe()
+            .atomic()
+            .get(key.as_str())
+            .del(key.as_str())
+            .ignore()
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis)?;
+
+        let Some(value) = value
+        else {
+            tracing::error!(error = "federation state not found");
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotFound));
+        };
+
+        serde_json::from_str(value.as_str()).map_err(|_| {
+            tracing::error!(error = "Invalid JSON data format for data stored at federation state");
+
+            RepositoryError::InternalError
+        })
+    }
+}
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::v1::models::LoginAttemptModel,
+    db::{
+        repositories::{LoginAttemptRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+};
+
+pub struct RedisLoginAttemptRepository;
+
+impl RedisLoginAttemptRepository {
+    fn into_redis_key(key: &str) -> String {
+        format!("login_attempts:{}", key)
+    }
+}
+
+#[async_trait]
+impl LoginAttemptRepository for RedisLoginAttemptRepository {
+    async fn get_by_key(
+        &self,
+        db_context: &Arc<DbContext>,
+        key: &str,
+    ) -> Result<LoginAttemptModel, RepositoryError> {
+        tracing::trace!(method = "get_by_key", key);
+
+        let redis_key = Self::into_redis_key(key);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let (failures, locked_until, expires_at): (Option<i64>, Option<i64>, Option<i64>) =
+            redis::cmd("HMGET")
+                .arg(redis_key.as_str())
+                .arg("failures")
+                .arg("locked_until")
+                .arg("expires_at")
+                .query_async(conn)
+                .await
+                .map_err(RepositoryError::map_redis)?;
+
+        let (Some(failures), Some(expires_at)) = (failures, expires_at)
+        else {
+            tracing::error!(error = "login attempts not found");
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotFound));
+        };
+
+        Ok(LoginAttemptModel::new(failures, locked_until, expires_at))
+    }
+
+    async fn record_failure_by_key(
+        &self,
+        db_context: &Arc<DbContext>,
+        key: &str,
+        expires_at: i64,
+    ) -> Result<LoginAttemptModel, RepositoryError> {
+        tracing::trace!(method = "record_failure_by_key", key, expires_at);
+
+        let redis_key = Self::into_redis_key(key);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // counted in one transaction, so concurrent failures are never lost
+        let (failures, locked_until): (i64, Option<i64>) = redis::pipe()
+            .atomic()
+            .hincr(redis_key.as_str(), "failures", 1)
+            .hset(redis_key.as_str(), "expires_at", expires_at)
+            .ignore()
+            .cmd("PEXPIREAT")
+            .arg(redis_key.as_str())
+            .arg(expires_at)
+            .ignore()
+            .hget(redis_key.as_str(), "locked_until")
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis)?;
+
+        Ok(LoginAttemptModel::new(failures, locked_until, expires_at))
+    }
+
+    async fn lock_by_key(
+        &self,
+        db_context: &Arc<DbContext>,
+        key: &str,
+        locked_until: i64,
+        expires_at: i64,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "lock_by_key", key, locked_until, expires_at);
+
+        let redis_key = Self::into_redis_key(key);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        redis::pipe()
+            .atomic()
+            .hset(redis_key.as_str(), "locked_until", locked_until)
+            .ignore()
+            .hset(redis_key.as_str(), "expires_at", expires_at)
+            .ignore()
+            .cmd("PEXPIREAT")
+            .arg(redis_key.as_str())
+            .arg(expires_at)
+            .ignore()
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis)?;
+
+        Ok(())
+    }
+
+    async fn delete_by_key(
+        &self,
+        db_context: &Arc<DbContext>,
+        key: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_key", key);
+
+        let redis_key = Self::into_redis_key(key);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        redis::cmd("DEL")
+            .arg(redis_key.as_str())
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis)?;
+
+        Ok(())
+    }
+}
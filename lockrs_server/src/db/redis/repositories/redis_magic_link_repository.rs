@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::v1::models::MagicLinkModel,
+    db::{
+        repositories::{MagicLinkRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+};
+
+pub struct RedisMagicLinkRepository;
+
+impl RedisMagicLinkRepository {
+    fn into_redis_key(email: &str) -> String {
+        format!("magic_link:{}", email)
+    }
+}
+
+#[async_trait]
+impl MagicLinkRepository for RedisMagicLinkRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        magic_link: &MagicLinkModel,
+    ) -> Result<MagicLinkModel, RepositoryError> {
+        tracing::trace!(method = "create", ?magic_link);
+
+        let key = Self::into_redis_key(magic_link.email.as_str());
+        let value = serde_json::to_string(magic_link).unwrap();
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        redis::cmd("SET")
+            .arg(key.as_str())
+            .arg(value.as_str())
+            .arg("PXAT")
+            .arg(magic_link.expires_at)
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis_create)?;
+
+        Ok(magic_link.clone())
+    }
+
+    async fn take_by_email(
+        &self,
+        db_context: &Arc<DbContext>,
+        email: &str,
+    ) -> Result<MagicLinkModel, RepositoryError> {
+        tracing::trace!(method = "take_by_email", email);
+
+        let key = Self::into_redis_key(email);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // read and deleted in one transaction, so a link can only ever be used once
+        let (value,): (Option<String>,) = redis::pipe()
+            .atomic()
+            .get(key.as_str())
+            .del(key.as_str())
+            .ignore()
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis)?;
+
+        let Some(value) = value else {
+            tracing::error!(error = "magic link not found");
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotFound));
+        };
+
+        serde_json::from_str(value.as_str()).map_err(|_| {
+            tracing::error!(error = "Invalid JSON data format for data stored at magic link");
+
+            RepositoryError::InternalError
+        })
+    }
+}
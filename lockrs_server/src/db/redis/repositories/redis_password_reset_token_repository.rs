@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::v1::models::PasswordResetTokenModel,
+    db::{
+        digest::digest_token,
+        repositories::{PasswordResetTokenRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+};
+
+pub struct RedisPasswordResetTokenRepository;
+
+impl RedisPasswordResetTokenRepository {
+    fn into_redis_key(token: &str) -> String {
+        format!("password_reset_token:{}", digest_token(token))
+    }
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for RedisPasswordResetTokenRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+        reset_token: &PasswordResetTokenModel,
+    ) -> Result<PasswordResetTokenModel, RepositoryError> {
+        tracing::trace!(method = "create", ?reset_token);
+
+        let key = Self::into_redis_key(token);
+        let value = serde_json::to_string(reset_token).unwrap();
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        redis::cmd("SET")
+            .arg(key.as_str())
+            .arg(value.as_str())
+            .arg("PXAT")
+            .arg(reset_token.expires_at)
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis_create)?;
+
+        Ok(reset_token.clone())
+    }
+
+    async fn take_by_token(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<PasswordResetTokenModel, RepositoryError> {
+        tracing::trace!(method = "take_by_token");
+
+        let key = Self::into_redis_key(token);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // read and deleted in one transaction, so a token can only ever be used once
+        let (value,): (Option<String>,) = redis::pipe()
+            .atomic()
+            .get(key.as_str())
+            .del(key.as_str())
+            .ignore()
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis)?;
+
+        let Some(value) = value
+        else {
+            tracing::error!(error = "password reset token not found");
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotFound));
+        };
+
+        serde_json::from_str(value.as_str()).map_err(|_| {
+            tracing::error!(
+                error = "Invalid JSON data format for data stored at password reset token"
+            );
+
+            RepositoryError::InternalError
+        })
+    }
+}
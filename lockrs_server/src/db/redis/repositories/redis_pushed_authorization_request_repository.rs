@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    db::{
+        digest::digest_token,
+        repositories::{PushedAuthorizationRequestRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::models::AuthorizationRequestModel,
+};
+
+pub struct RedisPushedAuthorizationRequestRepository;
+
+impl RedisPushedAuthorizationRequestRepository {
+    fn into_redis_key(request_uri: &str) -> String {
+        format!("pushed_authorization_request:{}", digest_token(request_uri))
+    }
+
+    fn from_redis_value(
+        value: Option<String>,
+    ) -> Result<AuthorizationRequestModel, RepositoryError> {
+        let Some(value) = value
+        else {
+            tracing::error!(error = "pushed authorization request not found");
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotFound));
+        };
+
+        serde_json::from_str(value.as_str()).map_err(|_| {
+            tracing::error!(
+                error = "Invalid JSON data format for data stored at pushed authorization request"
+            );
+
+            RepositoryError::InternalError
+        })
+    }
+}
+
+#[async_trait]
+impl PushedAuthorizationRequestRepository for RedisPushedAuthorizationRequestRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        request_uri: &str,
+        authorization_request: &AuthorizationRequestModel,
+        expires_at: i64,
+    ) -> Result<AuthorizationRequestModel, RepositoryError> {
+        tracing::trace!(method = "create", ?authorization_request, expires_at);
+
+        let key = Self::into_redis_key(request_uri);
+        let value = serde_json::to_string(authorization_request).unwrap();
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        redis::cmd("SET")
+            .arg(key.as_str())
+            .arg(value.as_str())
+            .arg("PXAT")
+            .arg(expires_at)
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis_create)?;
+
+        Ok(authorization_request.clone())
+    }
+
+    async fn get_by_request_uri(
+        &self,
+        db_context: &Arc<DbContext>,
+        request_uri: &str,
+    ) -> Result<AuthorizationRequestModel, RepositoryError> {
+        tracing::trace!(method = "get_by_request_uri");
+
+        let key = Self::into_redis_key(request_uri);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let value: Option<String> = redis::cmd("GET")
+            .arg(key.as_str())
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis)?;
+
+        Self::from_redis_value(value)
+    }
+
+    async fn take_by_request_uri(
+        &self,
+        db_context: &Arc<DbContext>,
+        request_uri: &str,
+    ) -> Result<AuthorizationRequestModel, RepositoryError> {
+        tracing::trace!(method = "take_by_request_uri");
+
+        let key = Self::into_redis_key(request_uri);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // read and deleted in one transaction, so a request can only ever be completed once
+        let (value,): (Option<String>,) = redis::pipe()
+            .atomic()
+            .get(key.as_str())
+            .del(key.as_str())
+            .ignore()
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis)?;
+
+        Self::from_redis_value(value)
+    }
+}
@@ -182,4 +182,46 @@ impl SessionRepository for RedisSessionRepository {
 
         Ok(())
     }
+
+    async fn delete_others_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+        session_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_others_by_user_id", ?id);
+
+        let user_key = Self::into_user_key(id);
+        let session_key = Self::into_session_key(session_id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        let session_keys: Vec<String> = redis::cmd("HKEYS")
+            .arg(user_key.as_str())
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis)?;
+
+        let other_session_keys = session_keys
+            .into_iter()
+            .filter(|key| key != &session_key)
+            .collect::<Vec<String>>();
+
+        if other_session_keys.is_empty() {
+            return Ok(());
+        }
+
+        redis::cmd("HDEL")
+            .arg(user_key.as_str())
+            .arg(other_session_keys)
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis)?;
+
+        Ok(())
+    }
 }
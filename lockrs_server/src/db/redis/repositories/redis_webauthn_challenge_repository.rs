@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::v1::models::WebauthnChallengeModel,
+    db::{
+        repositories::{QueryFailure, RepositoryError, WebauthnChallengeRepository},
+        DbContext,
+    },
+};
+
+pub struct RedisWebauthnChallengeRepository;
+
+impl RedisWebauthnChallengeRepository {
+    fn into_redis_key(id: &str) -> String {
+        format!("webauthn_challenge:{}", id)
+    }
+}
+
+#[async_trait]
+impl WebauthnChallengeRepository for RedisWebauthnChallengeRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        challenge: &WebauthnChallengeModel,
+    ) -> Result<WebauthnChallengeModel, RepositoryError> {
+        tracing::trace!(method = "create", ?challenge);
+
+        let key = Self::into_redis_key(challenge.id.as_str());
+        let value = serde_json::to_string(challenge).unwrap();
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        redis::cmd("SET")
+            .arg(key.as_str())
+            .arg(value.as_str())
+            .arg("PXAT")
+            .arg(challenge.expires_at)
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis_create)?;
+
+        Ok(challenge.clone())
+    }
+
+    async fn take_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<WebauthnChallengeModel, RepositoryError> {
+        tracing::trace!(method = "take_by_id", id);
+
+        let key = Self::into_redis_key(id);
+
+        let conn = &mut db_context
+            .as_ref()
+            .get_redis_connection()
+            .await
+            .map_err(RepositoryError::from)?;
+
+        // read and deleted in one transaction, so a challenge can only ever be taken once
+        let (value,): (Option<String>,) = redis::pipe()
+            .atomic()
+            .get(key.as_str())
+            .del(key.as_str())
+            .ignore()
+            .query_async(conn)
+            .await
+            .map_err(RepositoryError::map_redis)?;
+
+        let Some(value) = value
+        else {
+            tracing::error!(error = "webauthn challenge not found");
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotFound));
+        };
+
+        serde_json::from_str(value.as_str()).map_err(|_| {
+            let msg = format!(
+                "Invalid JSON data format for data stored at challenge {}",
+                id
+            );
+
+            tracing::error!(error = msg);
+
+            RepositoryError::InternalError
+        })
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 
 use crate::{
     db::{repositories::RepositoryError, DbContext},
@@ -24,4 +25,10 @@ pub trait AccessTokenRepository: Send + Sync {
         db_context: &Arc<DbContext>,
         token: &str,
     ) -> Result<(), RepositoryError>;
+    async fn delete_expired(
+        &self,
+        db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError>;
 }
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 
 use crate::{
     db::{repositories::RepositoryError, DbContext},
@@ -17,16 +18,28 @@ pub trait AuthorizationCodeRepository: Send + Sync {
     async fn get_by_id(
         &self,
         db_context: &Arc<DbContext>,
-        id: &str,
+        id: i32,
     ) -> Result<AuthorizationCodeModel, RepositoryError>;
     async fn get_by_code(
         &self,
         db_context: &Arc<DbContext>,
         code: &str,
     ) -> Result<AuthorizationCodeModel, RepositoryError>;
+    /// marks a live, unused code as used, so it can only ever be exchanged once
+    async fn use_by_code(
+        &self,
+        db_context: &Arc<DbContext>,
+        code: &str,
+    ) -> Result<AuthorizationCodeModel, RepositoryError>;
     async fn delete_by_id(
         &self,
         db_context: &Arc<DbContext>,
-        id: &str,
+        id: i32,
     ) -> Result<AuthorizationCodeModel, RepositoryError>;
+    async fn delete_expired(
+        &self,
+        db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError>;
 }
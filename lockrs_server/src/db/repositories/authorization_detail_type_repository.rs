@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    db::{repositories::RepositoryError, DbContext},
+    oauth2::v1::models::{AuthorizationDetailTypeCreateModel, AuthorizationDetailTypeModel},
+};
+
+#[async_trait]
+pub trait AuthorizationDetailTypeRepository: Send + Sync {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        type_create: &AuthorizationDetailTypeCreateModel,
+    ) -> Result<AuthorizationDetailTypeModel, RepositoryError>;
+    async fn get_from_list(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        names: &[String],
+    ) -> Result<Vec<AuthorizationDetailTypeModel>, RepositoryError>;
+    async fn get_all_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<AuthorizationDetailTypeModel>, RepositoryError>;
+    async fn delete_by_name(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
+    ) -> Result<(), RepositoryError>;
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use crate::{
+    db::{repositories::RepositoryError, DbContext},
+    oauth2::v1::models::{
+        BackchannelAuthorizationCreateModel, BackchannelAuthorizationModel,
+        BackchannelAuthorizationStatus,
+    },
+};
+
+#[async_trait]
+pub trait BackchannelAuthorizationRepository: Send + Sync {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        backchannel_authorization_create: &BackchannelAuthorizationCreateModel,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError>;
+    async fn get_by_auth_req_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError>;
+    async fn update_status_by_auth_req_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+        status: BackchannelAuthorizationStatus,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError>;
+    async fn update_last_polled_at_by_auth_req_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+        last_polled_at: &NaiveDateTime,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError>;
+    async fn delete_by_auth_req_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+    ) -> Result<(), RepositoryError>;
+}
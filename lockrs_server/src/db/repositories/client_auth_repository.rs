@@ -1,10 +1,11 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 
 use crate::{
     db::{repositories::RepositoryError, DbContext},
-    models::{ClientAuthModel, RedirectCreateModel},
+    models::{ClientAuthModel, ClientSecretCreateModel, ClientSecretModel, RedirectCreateModel},
 };
 
 #[async_trait]
@@ -13,12 +14,25 @@ pub trait ClientAuthRepository: Send + Sync {
         &self,
         db_context: &Arc<DbContext>,
         client_create: &ClientAuthModel,
+        secret_create: Option<&ClientSecretCreateModel>,
         redirect_create: &RedirectCreateModel,
     ) -> Result<ClientAuthModel, RepositoryError>;
-    async fn get_by_credentials(
+    async fn get_by_id(
         &self,
         db_context: &Arc<DbContext>,
         id: &str,
-        secret: Option<&str>,
     ) -> Result<ClientAuthModel, RepositoryError>;
+    async fn get_active_secrets_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<ClientSecretModel>, RepositoryError>;
+    /// stores a new secret and caps every other active secret of the client at
+    /// `previous_expires_at`
+    async fn rotate_secret(
+        &self,
+        db_context: &Arc<DbContext>,
+        secret_create: &ClientSecretCreateModel,
+        previous_expires_at: &NaiveDateTime,
+    ) -> Result<ClientSecretModel, RepositoryError>;
 }
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    db::{repositories::RepositoryError, DbContext},
+    models::ClientPolicyModel,
+};
+
+#[async_trait]
+pub trait ClientPolicyRepository: Send + Sync {
+    async fn upsert(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_policy: &ClientPolicyModel,
+    ) -> Result<ClientPolicyModel, RepositoryError>;
+    async fn get_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<ClientPolicyModel, RepositoryError>;
+    async fn delete_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<(), RepositoryError>;
+}
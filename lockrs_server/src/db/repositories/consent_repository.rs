@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    db::{repositories::RepositoryError, DbContext},
+    oauth2::v1::models::{ConsentCreateModel, ConsentModel},
+};
+
+#[async_trait]
+pub trait ConsentRepository: Send + Sync {
+    async fn upsert(
+        &self,
+        db_context: &Arc<DbContext>,
+        consent_create: &ConsentCreateModel,
+    ) -> Result<ConsentModel, RepositoryError>;
+    async fn get_by_user_id_and_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        client_id: &str,
+    ) -> Result<ConsentModel, RepositoryError>;
+    async fn get_all_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<Vec<ConsentModel>, RepositoryError>;
+    async fn delete_by_user_id_and_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        client_id: &str,
+    ) -> Result<(), RepositoryError>;
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 
 use crate::{
     db::{repositories::RepositoryError, DbContext},
@@ -29,4 +30,10 @@ pub trait DeviceAuthorizationRepository: Send + Sync {
         db_context: &Arc<DbContext>,
         id: &str,
     ) -> Result<(), RepositoryError>;
+    async fn delete_expired(
+        &self,
+        db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError>;
 }
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::v1::models::{FederatedIdentityCreateModel, FederatedIdentityModel},
+    db::{repositories::RepositoryError, DbContext},
+};
+
+#[async_trait]
+pub trait FederatedIdentityRepository: Send + Sync {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        identity_create: &FederatedIdentityCreateModel,
+    ) -> Result<FederatedIdentityModel, RepositoryError>;
+    async fn get_by_provider_and_subject(
+        &self,
+        db_context: &Arc<DbContext>,
+        provider: &str,
+        subject: &str,
+    ) -> Result<FederatedIdentityModel, RepositoryError>;
+}
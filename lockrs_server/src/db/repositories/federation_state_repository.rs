@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::v1::models::FederationStateModel,
+    db::{repositories::RepositoryError, DbContext},
+};
+
+#[async_trait]
+pub trait FederationStateRepository: Send + Sync {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        state: &str,
+        federation_state: &FederationStateModel,
+    ) -> Result<FederationStateModel, RepositoryError>;
+    /// Removes and returns the unexpired sign-in kept under `state`, so each can be completed
+    /// only once.
+    async fn take_by_state(
+        &self,
+        db_context: &Arc<DbContext>,
+        state: &str,
+    ) -> Result<FederationStateModel, RepositoryError>;
+}
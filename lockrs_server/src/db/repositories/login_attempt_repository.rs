@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::v1::models::LoginAttemptModel,
+    db::{repositories::RepositoryError, DbContext},
+};
+
+/// Failed login counters, by a key naming what they are counted against, e.g. an account or an
+/// address. Counters are forgotten on their own once they expire.
+#[async_trait]
+pub trait LoginAttemptRepository: Send + Sync {
+    async fn get_by_key(
+        &self,
+        db_context: &Arc<DbContext>,
+        key: &str,
+    ) -> Result<LoginAttemptModel, RepositoryError>;
+    /// Counts one more failure against the key in a single step, keeping the count around until
+    /// `expires_at`, and returns the counter as it stands after.
+    async fn record_failure_by_key(
+        &self,
+        db_context: &Arc<DbContext>,
+        key: &str,
+        expires_at: i64,
+    ) -> Result<LoginAttemptModel, RepositoryError>;
+    async fn lock_by_key(
+        &self,
+        db_context: &Arc<DbContext>,
+        key: &str,
+        locked_until: i64,
+        expires_at: i64,
+    ) -> Result<(), RepositoryError>;
+    async fn delete_by_key(
+        &self,
+        db_context: &Arc<DbContext>,
+        key: &str,
+    ) -> Result<(), RepositoryError>;
+}
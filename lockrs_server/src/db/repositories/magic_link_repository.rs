@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::v1::models::MagicLinkModel,
+    db::{repositories::RepositoryError, DbContext},
+};
+
+#[async_trait]
+pub trait MagicLinkRepository: Send + Sync {
+    /// Stores the user's sign-in link, in place of any they had outstanding.
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        magic_link: &MagicLinkModel,
+    ) -> Result<MagicLinkModel, RepositoryError>;
+    /// Removes and returns the unexpired sign-in link sent to `email`, so each can be used only
+    /// once.
+    async fn take_by_email(
+        &self,
+        db_context: &Arc<DbContext>,
+        email: &str,
+    ) -> Result<MagicLinkModel, RepositoryError>;
+}
@@ -1,20 +1,40 @@
 mod access_token_repository;
 mod authorization_code_repository;
+mod authorization_detail_type_repository;
+mod backchannel_authorization_repository;
 mod client_auth_repository;
+mod client_policy_repository;
 mod client_repository;
+mod consent_repository;
 mod device_authorization_repository;
+mod federated_identity_repository;
+mod federation_state_repository;
+mod login_attempt_repository;
+mod magic_link_repository;
+mod password_reset_token_repository;
+mod pushed_authorization_request_repository;
+mod recovery_code_repository;
 mod redirect_uri_repository;
 mod refresh_token_repository;
 mod repository_error;
 mod scope_repository;
 mod session_repository;
 mod session_token_repository;
+mod totp_repository;
 mod user_auth_repository;
 mod user_repository;
+mod webauthn_challenge_repository;
+mod webauthn_credential_repository;
 
 pub use self::{
-    access_token_repository::*, authorization_code_repository::*, client_auth_repository::*,
-    client_repository::*, device_authorization_repository::*, redirect_uri_repository::*,
-    refresh_token_repository::*, repository_error::*, scope_repository::*, session_repository::*,
-    session_token_repository::*, user_auth_repository::*, user_repository::*,
+    access_token_repository::*, authorization_code_repository::*,
+    authorization_detail_type_repository::*, backchannel_authorization_repository::*,
+    client_auth_repository::*, client_policy_repository::*, client_repository::*,
+    consent_repository::*, device_authorization_repository::*, federated_identity_repository::*,
+    federation_state_repository::*, login_attempt_repository::*, magic_link_repository::*,
+    password_reset_token_repository::*, pushed_authorization_request_repository::*,
+    recovery_code_repository::*, redirect_uri_repository::*, refresh_token_repository::*,
+    repository_error::*, scope_repository::*, session_repository::*, session_token_repository::*,
+    totp_repository::*, user_auth_repository::*, user_repository::*,
+    webauthn_challenge_repository::*, webauthn_credential_repository::*,
 };
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::v1::models::PasswordResetTokenModel,
+    db::{repositories::RepositoryError, DbContext},
+};
+
+#[async_trait]
+pub trait PasswordResetTokenRepository: Send + Sync {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+        reset_token: &PasswordResetTokenModel,
+    ) -> Result<PasswordResetTokenModel, RepositoryError>;
+    /// Removes and returns what an unexpired token was issued for, so each token can be used
+    /// only once.
+    async fn take_by_token(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<PasswordResetTokenModel, RepositoryError>;
+}
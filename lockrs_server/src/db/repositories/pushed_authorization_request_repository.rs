@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    db::{repositories::RepositoryError, DbContext},
+    oauth2::v1::models::AuthorizationRequestModel,
+};
+
+#[async_trait]
+pub trait PushedAuthorizationRequestRepository: Send + Sync {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        request_uri: &str,
+        authorization_request: &AuthorizationRequestModel,
+        expires_at: i64,
+    ) -> Result<AuthorizationRequestModel, RepositoryError>;
+    /// Returns the unexpired request kept under `request_uri`, leaving it in place so the user
+    /// can come back to it after consenting.
+    async fn get_by_request_uri(
+        &self,
+        db_context: &Arc<DbContext>,
+        request_uri: &str,
+    ) -> Result<AuthorizationRequestModel, RepositoryError>;
+    /// Removes and returns the unexpired request kept under `request_uri`, so that only one code
+    /// is ever issued for it.
+    async fn take_by_request_uri(
+        &self,
+        db_context: &Arc<DbContext>,
+        request_uri: &str,
+    ) -> Result<AuthorizationRequestModel, RepositoryError>;
+}
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::{repositories::RepositoryError, DbContext};
+
+#[async_trait]
+pub trait RecoveryCodeRepository: Send + Sync {
+    /// Replaces every recovery code of the user, used or not, with `code_hashes`.
+    async fn replace_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        code_hashes: &[String],
+    ) -> Result<(), RepositoryError>;
+    /// Marks an unused recovery code of the user as used, failing with `NotUpdated` if there is
+    /// no such code.
+    async fn use_by_user_id_and_hash(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        code_hash: &str,
+    ) -> Result<(), RepositoryError>;
+    async fn delete_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<(), RepositoryError>;
+}
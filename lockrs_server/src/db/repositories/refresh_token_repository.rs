@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
 
 use crate::{
     db::{repositories::RepositoryError, DbContext},
@@ -24,9 +26,26 @@ pub trait RefreshTokenRepository: Send + Sync {
         db_context: &Arc<DbContext>,
         token: &str,
     ) -> Result<RefreshTokenModel, RepositoryError>;
+    async fn revoke_all_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<usize, RepositoryError>;
+    async fn revoke_all_by_user_id_and_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        client_id: &str,
+    ) -> Result<usize, RepositoryError>;
     async fn delete_by_token(
         &self,
         db_context: &Arc<DbContext>,
         token: &str,
     ) -> Result<(), RepositoryError>;
+    async fn delete_expired(
+        &self,
+        db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError>;
 }
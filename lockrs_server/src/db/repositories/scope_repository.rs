@@ -4,7 +4,9 @@ use async_trait::async_trait;
 
 use crate::{
     db::{repositories::RepositoryError, DbContext},
-    oauth2::v1::models::{ScopeCreateModel, ScopeModel},
+    oauth2::v1::models::{
+        AllowedScopeModel, ScopeCreateModel, ScopeDefinitionModel, ScopeModel, ScopeUpdateModel,
+    },
 };
 
 #[async_trait]
@@ -13,15 +15,39 @@ pub trait ScopeRepository: Send + Sync {
         &self,
         db_context: &Arc<DbContext>,
         scope_create: &ScopeCreateModel,
-    ) -> Result<ScopeModel, RepositoryError>;
+    ) -> Result<ScopeDefinitionModel, RepositoryError>;
     async fn get_from_list(
         &self,
         db_context: &Arc<DbContext>,
         scopes_list: &[String],
     ) -> Result<ScopeModel, RepositoryError>;
+    async fn get_all_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<ScopeDefinitionModel>, RepositoryError>;
+    async fn get_allowed_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<AllowedScopeModel>, RepositoryError>;
+    async fn replace_allowed_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        allowed_scopes: &[AllowedScopeModel],
+    ) -> Result<Vec<AllowedScopeModel>, RepositoryError>;
+    async fn update_by_name(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
+        scope_update: &ScopeUpdateModel,
+    ) -> Result<ScopeDefinitionModel, RepositoryError>;
     async fn delete_by_name(
         &self,
         db_context: &Arc<DbContext>,
-        id: &str,
+        client_id: &str,
+        name: &str,
     ) -> Result<(), RepositoryError>;
 }
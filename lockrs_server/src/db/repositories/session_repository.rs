@@ -31,4 +31,11 @@ pub trait SessionRepository: Send + Sync {
         db_context: &Arc<DbContext>,
         id: &Uuid,
     ) -> Result<(), RepositoryError>;
+    /// ends every session of the user but `session_id`
+    async fn delete_others_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+        session_id: &str,
+    ) -> Result<(), RepositoryError>;
 }
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::models::{TotpCreateModel, TotpModel},
+    db::{repositories::RepositoryError, DbContext},
+};
+
+#[async_trait]
+pub trait TotpRepository: Send + Sync {
+    /// Replaces any unconfirmed enrollment of the user, failing with `AlreadyExists` if the user
+    /// has a confirmed one.
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        totp_create: &TotpCreateModel,
+    ) -> Result<TotpModel, RepositoryError>;
+    async fn get_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<TotpModel, RepositoryError>;
+    /// Confirms an unconfirmed enrollment with the first code it accepted, generated for `step`.
+    async fn confirm_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        step: i64,
+    ) -> Result<TotpModel, RepositoryError>;
+    /// Records a code generated for `step` as used, failing with `NotUpdated` unless `step` is
+    /// later than that of the last code used, so that no code is accepted twice.
+    async fn use_step_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        step: i64,
+    ) -> Result<(), RepositoryError>;
+    async fn delete_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<(), RepositoryError>;
+}
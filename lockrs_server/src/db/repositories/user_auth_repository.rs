@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use uuid::Uuid;
 
 use crate::{
     api::v1::models::{UserAuthModel, UserRegisterModel},
@@ -21,9 +22,32 @@ pub trait UserAuthRepository: Send + Sync {
         user_model: &UserAuthModel,
     ) -> Result<UserAuthModel, RepositoryError>;
 
+    async fn get_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<UserAuthModel, RepositoryError>;
+
     async fn get_by_email(
         &self,
         db_context: &Arc<DbContext>,
         email: &str,
     ) -> Result<UserAuthModel, RepositoryError>;
+
+    /// Replaces the hash of the same password, e.g. when rehashing it with new parameters, keeping
+    /// its version.
+    async fn update_password_hash_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+        password_hash: &str,
+    ) -> Result<UserAuthModel, RepositoryError>;
+
+    /// Sets a new password, bumping its version.
+    async fn change_password_hash_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+        password_hash: &str,
+    ) -> Result<UserAuthModel, RepositoryError>;
 }
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::v1::models::WebauthnChallengeModel,
+    db::{repositories::RepositoryError, DbContext},
+};
+
+#[async_trait]
+pub trait WebauthnChallengeRepository: Send + Sync {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        challenge: &WebauthnChallengeModel,
+    ) -> Result<WebauthnChallengeModel, RepositoryError>;
+    /// Removes and returns an unexpired challenge, so each challenge can be answered only once.
+    async fn take_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<WebauthnChallengeModel, RepositoryError>;
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::models::{WebauthnCredentialCreateModel, WebauthnCredentialModel},
+    db::{repositories::RepositoryError, DbContext},
+};
+
+#[async_trait]
+pub trait WebauthnCredentialRepository: Send + Sync {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        credential_create: &WebauthnCredentialCreateModel,
+    ) -> Result<WebauthnCredentialModel, RepositoryError>;
+    async fn get_by_credential_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        credential_id: &str,
+    ) -> Result<WebauthnCredentialModel, RepositoryError>;
+    async fn get_all_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<Vec<WebauthnCredentialModel>, RepositoryError>;
+    /// Records a use of the credential that reported `sign_count`, failing with `NotUpdated` if
+    /// the stored count is no longer `previous_sign_count`, so that two assertions racing with
+    /// the same counter can not both be accepted.
+    async fn use_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: i32,
+        previous_sign_count: i64,
+        sign_count: i64,
+    ) -> Result<(), RepositoryError>;
+    async fn delete_by_user_id_and_credential_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        credential_id: &str,
+    ) -> Result<(), RepositoryError>;
+}
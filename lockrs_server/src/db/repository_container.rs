@@ -1,16 +1,227 @@
-use crate::db::repositories::*;
+use std::sync::Arc;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite::repositories::*;
+use crate::db::{
+    cache::{repositories::*, RepositoryCache},
+    memory::{repositories::*, InMemoryStore},
+    pg::repositories::*,
+    redis::repositories::*,
+    repositories::*,
+};
 
 pub struct RepositoryContainer {
     pub access_token_repository: Box<dyn AccessTokenRepository>,
     pub authorization_code_repository: Box<dyn AuthorizationCodeRepository>,
+    pub authorization_detail_type_repository: Box<dyn AuthorizationDetailTypeRepository>,
+    pub backchannel_authorization_repository: Box<dyn BackchannelAuthorizationRepository>,
     pub client_repository: Box<dyn ClientRepository>,
     pub client_auth_repository: Box<dyn ClientAuthRepository>,
+    pub client_policy_repository: Box<dyn ClientPolicyRepository>,
+    pub consent_repository: Box<dyn ConsentRepository>,
     pub device_authorization_repository: Box<dyn DeviceAuthorizationRepository>,
+    pub federated_identity_repository: Box<dyn FederatedIdentityRepository>,
+    pub federation_state_repository: Box<dyn FederationStateRepository>,
+    pub login_attempt_repository: Box<dyn LoginAttemptRepository>,
+    pub magic_link_repository: Box<dyn MagicLinkRepository>,
+    pub password_reset_token_repository: Box<dyn PasswordResetTokenRepository>,
+    pub pushed_authorization_request_repository: Box<dyn PushedAuthorizationRequestRepository>,
+    pub recovery_code_repository: Box<dyn RecoveryCodeRepository>,
     pub redirect_repository: Box<dyn RedirectUriRepository>,
     pub refresh_token_repository: Box<dyn RefreshTokenRepository>,
     pub scope_repository: Box<dyn ScopeRepository>,
     pub session_repository: Box<dyn SessionRepository>,
     pub session_token_repository: Box<dyn SessionTokenRepository>,
+    pub totp_repository: Box<dyn TotpRepository>,
     pub user_auth_repository: Box<dyn UserAuthRepository>,
     pub user_repository: Box<dyn UserRepository>,
+    pub webauthn_challenge_repository: Box<dyn WebauthnChallengeRepository>,
+    pub webauthn_credential_repository: Box<dyn WebauthnCredentialRepository>,
+}
+
+impl RepositoryContainer {
+    /// The pg repositories, with sessions, webauthn challenges, password reset tokens, sign-in
+    /// links, unfinished federated sign-ins, pushed authorization requests and failed login
+    /// counters kept in redis.
+    pub fn pg() -> Self {
+        Self {
+            access_token_repository: Box::new(PgAccessTokenRepository),
+            authorization_code_repository: Box::new(PgAuthorizationCodeRepository),
+            authorization_detail_type_repository: Box::new(PgAuthorizationDetailTypeRepository),
+            backchannel_authorization_repository: Box::new(PgBackchannelAuthorizationRepository),
+            client_repository: Box::new(PgClientRepository),
+            client_auth_repository: Box::new(PgClientAuthRepository),
+            client_policy_repository: Box::new(PgClientPolicyRepository),
+            consent_repository: Box::new(PgConsentRepository),
+            device_authorization_repository: Box::new(PgDeviceAuthorizationRepository),
+            federated_identity_repository: Box::new(PgFederatedIdentityRepository),
+            federation_state_repository: Box::new(RedisFederationStateRepository),
+            login_attempt_repository: Box::new(RedisLoginAttemptRepository),
+            magic_link_repository: Box::new(RedisMagicLinkRepository),
+            password_reset_token_repository: Box::new(RedisPasswordResetTokenRepository),
+            pushed_authorization_request_repository: Box::new(
+                RedisPushedAuthorizationRequestRepository,
+            ),
+            recovery_code_repository: Box::new(PgRecoveryCodeRepository),
+            redirect_repository: Box::new(PgRedirectUriRepository),
+            refresh_token_repository: Box::new(PgRefreshTokenRepository),
+            scope_repository: Box::new(PgScopeRepository),
+            session_repository: Box::new(RedisSessionRepository),
+            session_token_repository: Box::new(RedisSessionTokenRepository),
+            totp_repository: Box::new(PgTotpRepository),
+            user_auth_repository: Box::new(PgUserAuthRepository),
+            user_repository: Box::new(PgUserRepository),
+            webauthn_challenge_repository: Box::new(RedisWebauthnChallengeRepository),
+            webauthn_credential_repository: Box::new(PgWebauthnCredentialRepository),
+        }
+    }
+
+    /// The sqlite repositories, with sessions kept in sqlite as well.
+    #[cfg(feature = "sqlite")]
+    pub fn sqlite() -> Self {
+        Self {
+            access_token_repository: Box::new(SqliteAccessTokenRepository),
+            authorization_code_repository: Box::new(SqliteAuthorizationCodeRepository),
+            authorization_detail_type_repository: Box::new(SqliteAuthorizationDetailTypeRepository),
+            backchannel_authorization_repository: Box::new(
+                SqliteBackchannelAuthorizationRepository,
+            ),
+            client_repository: Box::new(SqliteClientRepository),
+            client_auth_repository: Box::new(SqliteClientAuthRepository),
+            client_policy_repository: Box::new(SqliteClientPolicyRepository),
+            consent_repository: Box::new(SqliteConsentRepository),
+            device_authorization_repository: Box::new(SqliteDeviceAuthorizationRepository),
+            federated_identity_repository: Box::new(SqliteFederatedIdentityRepository),
+            federation_state_repository: Box::new(SqliteFederationStateRepository),
+            login_attempt_repository: Box::new(SqliteLoginAttemptRepository),
+            magic_link_repository: Box::new(SqliteMagicLinkRepository),
+            password_reset_token_repository: Box::new(SqlitePasswordResetTokenRepository),
+            pushed_authorization_request_repository: Box::new(
+                SqlitePushedAuthorizationRequestRepository,
+            ),
+            recovery_code_repository: Box::new(SqliteRecoveryCodeRepository),
+            redirect_repository: Box::new(SqliteRedirectUriRepository),
+            refresh_token_repository: Box::new(SqliteRefreshTokenRepository),
+            scope_repository: Box::new(SqliteScopeRepository),
+            session_repository: Box::new(SqliteSessionRepository),
+            session_token_repository: Box::new(SqliteSessionTokenRepository),
+            totp_repository: Box::new(SqliteTotpRepository),
+            user_auth_repository: Box::new(SqliteUserAuthRepository),
+            user_repository: Box::new(SqliteUserRepository),
+            webauthn_challenge_repository: Box::new(SqliteWebauthnChallengeRepository),
+            webauthn_credential_repository: Box::new(SqliteWebauthnCredentialRepository),
+        }
+    }
+
+    /// A container of in-memory repositories that all share `store`, so tests can run services
+    /// and controllers without postgres or redis. The store has no transactions, so a unit of
+    /// work that fails keeps whatever it wrote.
+    pub fn in_memory(store: Arc<InMemoryStore>) -> Self {
+        Self {
+            access_token_repository: Box::new(InMemoryAccessTokenRepository {
+                store: store.clone(),
+            }),
+            authorization_code_repository: Box::new(InMemoryAuthorizationCodeRepository {
+                store: store.clone(),
+            }),
+            authorization_detail_type_repository: Box::new(
+                InMemoryAuthorizationDetailTypeRepository {
+                    store: store.clone(),
+                },
+            ),
+            backchannel_authorization_repository: Box::new(
+                InMemoryBackchannelAuthorizationRepository {
+                    store: store.clone(),
+                },
+            ),
+            client_repository: Box::new(InMemoryClientRepository {
+                store: store.clone(),
+            }),
+            client_auth_repository: Box::new(InMemoryClientAuthRepository {
+                store: store.clone(),
+            }),
+            client_policy_repository: Box::new(InMemoryClientPolicyRepository {
+                store: store.clone(),
+            }),
+            consent_repository: Box::new(InMemoryConsentRepository {
+                store: store.clone(),
+            }),
+            device_authorization_repository: Box::new(InMemoryDeviceAuthorizationRepository {
+                store: store.clone(),
+            }),
+            federated_identity_repository: Box::new(InMemoryFederatedIdentityRepository {
+                store: store.clone(),
+            }),
+            federation_state_repository: Box::new(InMemoryFederationStateRepository {
+                store: store.clone(),
+            }),
+            login_attempt_repository: Box::new(InMemoryLoginAttemptRepository {
+                store: store.clone(),
+            }),
+            magic_link_repository: Box::new(InMemoryMagicLinkRepository {
+                store: store.clone(),
+            }),
+            password_reset_token_repository: Box::new(InMemoryPasswordResetTokenRepository {
+                store: store.clone(),
+            }),
+            pushed_authorization_request_repository: Box::new(
+                InMemoryPushedAuthorizationRequestRepository {
+                    store: store.clone(),
+                },
+            ),
+            recovery_code_repository: Box::new(InMemoryRecoveryCodeRepository {
+                store: store.clone(),
+            }),
+            redirect_repository: Box::new(InMemoryRedirectUriRepository {
+                store: store.clone(),
+            }),
+            refresh_token_repository: Box::new(InMemoryRefreshTokenRepository {
+                store: store.clone(),
+            }),
+            scope_repository: Box::new(InMemoryScopeRepository {
+                store: store.clone(),
+            }),
+            session_repository: Box::new(InMemorySessionRepository {
+                store: store.clone(),
+            }),
+            session_token_repository: Box::new(InMemorySessionTokenRepository {
+                store: store.clone(),
+            }),
+            totp_repository: Box::new(InMemoryTotpRepository {
+                store: store.clone(),
+            }),
+            user_auth_repository: Box::new(InMemoryUserAuthRepository {
+                store: store.clone(),
+            }),
+            user_repository: Box::new(InMemoryUserRepository {
+                store: store.clone(),
+            }),
+            webauthn_challenge_repository: Box::new(InMemoryWebauthnChallengeRepository {
+                store: store.clone(),
+            }),
+            webauthn_credential_repository: Box::new(InMemoryWebauthnCredentialRepository {
+                store,
+            }),
+        }
+    }
+
+    /// Puts the client, redirect and scope lookups made on every oauth2 request behind `cache`.
+    pub fn with_cache(self, cache: Arc<RepositoryCache>) -> Self {
+        Self {
+            client_repository: Box::new(CachedClientRepository::new(
+                self.client_repository,
+                cache.clone(),
+            )),
+            client_auth_repository: Box::new(CachedClientAuthRepository::new(
+                self.client_auth_repository,
+                cache.clone(),
+            )),
+            redirect_repository: Box::new(CachedRedirectUriRepository::new(
+                self.redirect_repository,
+                cache.clone(),
+            )),
+            scope_repository: Box::new(CachedScopeRepository::new(self.scope_repository, cache)),
+            ..self
+        }
+    }
 }
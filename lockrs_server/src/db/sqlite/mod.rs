@@ -0,0 +1,3 @@
+pub mod repositories;
+pub mod schema;
+pub mod sql_types;
@@ -0,0 +1,41 @@
+mod sqlite_access_token_repository;
+mod sqlite_authorization_code_repository;
+mod sqlite_authorization_detail_type_repository;
+mod sqlite_backchannel_authorization_repository;
+mod sqlite_client_auth_repository;
+mod sqlite_client_policy_repository;
+mod sqlite_client_repository;
+mod sqlite_consent_repository;
+mod sqlite_device_authorization_repository;
+mod sqlite_federated_identity_repository;
+mod sqlite_federation_state_repository;
+mod sqlite_login_attempt_repository;
+mod sqlite_magic_link_repository;
+mod sqlite_password_reset_token_repository;
+mod sqlite_pushed_authorization_request_repository;
+mod sqlite_recovery_code_repository;
+mod sqlite_redirect_uri_repository;
+mod sqlite_refresh_token_repository;
+mod sqlite_scope_repository;
+mod sqlite_session_repository;
+mod sqlite_session_token_repository;
+mod sqlite_totp_repository;
+mod sqlite_user_auth_repository;
+mod sqlite_user_repository;
+mod sqlite_webauthn_challenge_repository;
+mod sqlite_webauthn_credential_repository;
+
+pub use self::{
+    sqlite_access_token_repository::*, sqlite_authorization_code_repository::*,
+    sqlite_authorization_detail_type_repository::*, sqlite_backchannel_authorization_repository::*,
+    sqlite_client_auth_repository::*, sqlite_client_policy_repository::*,
+    sqlite_client_repository::*, sqlite_consent_repository::*,
+    sqlite_device_authorization_repository::*, sqlite_federated_identity_repository::*,
+    sqlite_federation_state_repository::*, sqlite_login_attempt_repository::*,
+    sqlite_magic_link_repository::*, sqlite_password_reset_token_repository::*,
+    sqlite_pushed_authorization_request_repository::*, sqlite_recovery_code_repository::*,
+    sqlite_redirect_uri_repository::*, sqlite_refresh_token_repository::*,
+    sqlite_scope_repository::*, sqlite_session_repository::*, sqlite_session_token_repository::*,
+    sqlite_totp_repository::*, sqlite_user_auth_repository::*, sqlite_user_repository::*,
+    sqlite_webauthn_challenge_repository::*, sqlite_webauthn_credential_repository::*,
+};
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+use diesel::{
+    dsl::{exists, not},
+    prelude::*,
+};
+
+use crate::{
+    db::{
+        digest_token,
+        pg::models::PgAccessToken,
+        repositories::{AccessTokenRepository, QueryFailure, RepositoryError},
+        sqlite::{
+            schema::{access_tokens, refresh_tokens},
+            sql_types::{JsonValue, ListValue, UuidValue},
+        },
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::{AccessTokenMapper, AuthorizationDetailMapper},
+        models::{AccessTokenCreateModel, AccessTokenModel},
+    },
+};
+
+pub struct SqliteAccessTokenRepository;
+
+#[async_trait]
+impl AccessTokenRepository for SqliteAccessTokenRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        token_create: &AccessTokenCreateModel,
+    ) -> Result<AccessTokenModel, RepositoryError> {
+        tracing::trace!(method = "create");
+
+        let token = digest_token(token_create.token.as_str());
+        let client_id = token_create.client_id.to_owned();
+        let user_id = token_create.user_id.map(UuidValue);
+        let expires_at = token_create.expires_at;
+        let scopes = ListValue::new(&token_create.scopes);
+        let authorization_details = JsonValue(AuthorizationDetailMapper::vec_to_pg_value(
+            &token_create.authorization_details,
+        ));
+
+        let pg_token = db_context
+            .with_sqlite_connection(move |conn| {
+                diesel::insert_into(access_tokens::table)
+                    .values((
+                        access_tokens::token.eq(token),
+                        access_tokens::client_id.eq(client_id),
+                        access_tokens::user_id.eq(user_id),
+                        access_tokens::created_at.eq(Utc::now().naive_utc()),
+                        access_tokens::expires_at.eq(expires_at),
+                        access_tokens::scopes.eq(scopes),
+                        access_tokens::authorization_details.eq(authorization_details),
+                    ))
+                    .get_result::<PgAccessToken>(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        // only the digest is stored, so hand the caller back the token they issued
+        let mut token = AccessTokenMapper::from_pg(pg_token);
+        token.token = token_create.token.to_owned();
+
+        Ok(token)
+    }
+
+    async fn get_by_token(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<AccessTokenModel, RepositoryError> {
+        tracing::trace!(method = "get_by_token");
+
+        let token_digest = digest_token(token);
+        let now = Utc::now().naive_utc();
+
+        let pg_token = db_context
+            .with_sqlite_connection(move |conn| {
+                access_tokens::table
+                    .filter(access_tokens::token.eq(token_digest))
+                    .filter(access_tokens::created_at.lt(now))
+                    .filter(access_tokens::expires_at.gt(now))
+                    .first::<PgAccessToken>(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        let mut access_token = AccessTokenMapper::from_pg(pg_token);
+        access_token.token = token.to_owned();
+
+        Ok(access_token)
+    }
+
+    async fn delete_by_token(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_token");
+
+        let token_digest = digest_token(token);
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| {
+                diesel::delete(access_tokens::table)
+                    .filter(access_tokens::token.eq(token_digest))
+                    .execute(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_expired(
+        &self,
+        db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "delete_expired", ?expired_before, limit);
+
+        let expired_before = *expired_before;
+
+        // sqlite has a single writer, so a bounded delete is all that keeps a batch short
+        db_context
+            .with_sqlite_connection(move |conn| {
+                let expired_ids = access_tokens::table
+                    .select(access_tokens::id)
+                    .filter(access_tokens::expires_at.lt(expired_before))
+                    // refresh tokens cascade from their access token, so keep any still referenced
+                    .filter(not(exists(
+                        refresh_tokens::table
+                            .filter(refresh_tokens::access_token_id.eq(access_tokens::id)),
+                    )))
+                    .limit(limit)
+                    .load::<i32>(conn)?;
+
+                diesel::delete(access_tokens::table)
+                    .filter(access_tokens::id.eq_any(expired_ids))
+                    .execute(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)
+    }
+}
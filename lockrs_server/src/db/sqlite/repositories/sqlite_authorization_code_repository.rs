@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+use diesel::prelude::*;
+
+use crate::{
+    db::{
+        digest_token,
+        pg::models::PgAuthorizationCode,
+        repositories::{AuthorizationCodeRepository, RepositoryError},
+        sqlite::{
+            schema::authorization_codes,
+            sql_types::{JsonValue, ListValue, UuidValue},
+        },
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::{AuthorizationCodeMapper, AuthorizationDetailMapper},
+        models::{AuthorizationCodeCreateModel, AuthorizationCodeModel},
+    },
+};
+
+pub struct SqliteAuthorizationCodeRepository;
+
+#[async_trait]
+impl AuthorizationCodeRepository for SqliteAuthorizationCodeRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_code_create: &AuthorizationCodeCreateModel,
+    ) -> Result<AuthorizationCodeModel, RepositoryError> {
+        tracing::trace!(method = "create");
+
+        let query = diesel::insert_into(authorization_codes::table).values((
+            authorization_codes::code.eq(digest_token(auth_code_create.code.as_str())),
+            authorization_codes::challenge.eq(auth_code_create.challenge.to_owned()),
+            authorization_codes::is_challenge_plain.eq(auth_code_create.is_challenge_plain),
+            authorization_codes::client_id.eq(auth_code_create.client_id.to_owned()),
+            authorization_codes::user_id.eq(UuidValue(auth_code_create.user_id)),
+            authorization_codes::redirect_uri.eq(auth_code_create.redirect_uri.to_string()),
+            authorization_codes::created_at.eq(Utc::now().naive_utc()),
+            authorization_codes::expires_at.eq(auth_code_create.expires_at),
+            authorization_codes::scopes.eq(ListValue::new(&auth_code_create.scopes)),
+            authorization_codes::authorization_details.eq(JsonValue(
+                AuthorizationDetailMapper::vec_to_pg_value(&auth_code_create.authorization_details),
+            )),
+        ));
+
+        let pg_code = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgAuthorizationCode>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        // only the digest is stored, so hand the caller back the code they issued
+        let mut code = AuthorizationCodeMapper::from_pg(pg_code);
+        code.code = auth_code_create.code.to_owned();
+
+        Ok(code)
+    }
+
+    async fn get_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: i32,
+    ) -> Result<AuthorizationCodeModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", id);
+
+        let query = authorization_codes::table.filter(authorization_codes::id.eq(id));
+
+        let pg_code = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgAuthorizationCode>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(AuthorizationCodeMapper::from_pg(pg_code))
+    }
+
+    async fn get_by_code(
+        &self,
+        db_context: &Arc<DbContext>,
+        code: &str,
+    ) -> Result<AuthorizationCodeModel, RepositoryError> {
+        tracing::trace!(method = "get_by_code");
+
+        let now = Utc::now().naive_utc();
+
+        let query = authorization_codes::table
+            .filter(authorization_codes::code.eq(digest_token(code)))
+            .filter(authorization_codes::created_at.lt(now))
+            .filter(authorization_codes::expires_at.gt(now))
+            .filter(authorization_codes::used.eq(false));
+
+        let pg_code = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgAuthorizationCode>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        let mut authorization_code = AuthorizationCodeMapper::from_pg(pg_code);
+        authorization_code.code = code.to_owned();
+
+        Ok(authorization_code)
+    }
+
+    async fn use_by_code(
+        &self,
+        db_context: &Arc<DbContext>,
+        code: &str,
+    ) -> Result<AuthorizationCodeModel, RepositoryError> {
+        tracing::trace!(method = "use_by_code");
+
+        let now = Utc::now().naive_utc();
+
+        let query = diesel::update(authorization_codes::table)
+            .filter(authorization_codes::code.eq(digest_token(code)))
+            .filter(authorization_codes::created_at.lt(now))
+            .filter(authorization_codes::expires_at.gt(now))
+            .filter(authorization_codes::used.eq(false))
+            .set(authorization_codes::used.eq(true));
+
+        let pg_code = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgAuthorizationCode>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        let mut authorization_code = AuthorizationCodeMapper::from_pg(pg_code);
+        authorization_code.code = code.to_owned();
+
+        Ok(authorization_code)
+    }
+
+    async fn delete_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: i32,
+    ) -> Result<AuthorizationCodeModel, RepositoryError> {
+        tracing::trace!(method = "delete_by_id", id);
+
+        let query =
+            diesel::delete(authorization_codes::table).filter(authorization_codes::id.eq(id));
+
+        let pg_code = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgAuthorizationCode>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        Ok(AuthorizationCodeMapper::from_pg(pg_code))
+    }
+
+    async fn delete_expired(
+        &self,
+        db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "delete_expired", ?expired_before, limit);
+
+        let expired_before = *expired_before;
+
+        db_context
+            .with_sqlite_connection(move |conn| {
+                let expired_ids = authorization_codes::table
+                    .select(authorization_codes::id)
+                    .filter(authorization_codes::expires_at.lt(expired_before))
+                    .limit(limit)
+                    .load::<i32>(conn)?;
+
+                diesel::delete(authorization_codes::table)
+                    .filter(authorization_codes::id.eq_any(expired_ids))
+                    .execute(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)
+    }
+}
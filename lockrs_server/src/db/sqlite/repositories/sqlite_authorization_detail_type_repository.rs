@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+
+use crate::{
+    db::{
+        pg::models::PgAuthorizationDetailType,
+        repositories::{AuthorizationDetailTypeRepository, QueryFailure, RepositoryError},
+        sqlite::{schema::authorization_detail_types, sql_types::JsonValue},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::AuthorizationDetailMapper,
+        models::{AuthorizationDetailTypeCreateModel, AuthorizationDetailTypeModel},
+    },
+};
+
+pub struct SqliteAuthorizationDetailTypeRepository;
+
+#[async_trait]
+impl AuthorizationDetailTypeRepository for SqliteAuthorizationDetailTypeRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        type_create: &AuthorizationDetailTypeCreateModel,
+    ) -> Result<AuthorizationDetailTypeModel, RepositoryError> {
+        tracing::trace!(method = "create", ?type_create);
+
+        let schema = serde_json::to_value(&type_create.schema).map_err(|err| {
+            tracing::error!(error = %err);
+            RepositoryError::QueryFailed(QueryFailure::NotCreated)
+        })?;
+
+        let name = type_create.name.to_owned();
+        let client_id = type_create.client_id.to_owned();
+        let description = type_create.description.to_owned();
+
+        let pg_type = db_context
+            .with_sqlite_connection(move |conn| {
+                diesel::insert_into(authorization_detail_types::table)
+                    .values((
+                        authorization_detail_types::name.eq(name),
+                        authorization_detail_types::client_id.eq(client_id),
+                        authorization_detail_types::description.eq(description),
+                        authorization_detail_types::schema.eq(JsonValue(schema)),
+                    ))
+                    .get_result::<PgAuthorizationDetailType>(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(AuthorizationDetailMapper::type_from_pg(pg_type))
+    }
+
+    async fn get_from_list(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        names: &[String],
+    ) -> Result<Vec<AuthorizationDetailTypeModel>, RepositoryError> {
+        tracing::trace!(method = "get_from_list", client_id, ?names);
+
+        let client_id = client_id.to_owned();
+        let names = names.to_vec();
+
+        let pg_types = db_context
+            .with_sqlite_connection(move |conn| {
+                authorization_detail_types::table
+                    .filter(authorization_detail_types::client_id.eq(client_id))
+                    .filter(authorization_detail_types::name.eq_any(names))
+                    .load::<PgAuthorizationDetailType>(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(pg_types
+            .into_iter()
+            .map(AuthorizationDetailMapper::type_from_pg)
+            .collect::<Vec<AuthorizationDetailTypeModel>>())
+    }
+
+    async fn get_all_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<AuthorizationDetailTypeModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_client_id", client_id);
+
+        let client_id = client_id.to_owned();
+
+        let pg_types = db_context
+            .with_sqlite_connection(move |conn| {
+                authorization_detail_types::table
+                    .filter(authorization_detail_types::client_id.eq(client_id))
+                    .load::<PgAuthorizationDetailType>(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(pg_types
+            .into_iter()
+            .map(AuthorizationDetailMapper::type_from_pg)
+            .collect::<Vec<AuthorizationDetailTypeModel>>())
+    }
+
+    async fn delete_by_name(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_name", client_id, name);
+
+        let client_id = client_id.to_owned();
+        let name = name.to_owned();
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| {
+                diesel::delete(authorization_detail_types::table)
+                    .filter(authorization_detail_types::client_id.eq(client_id))
+                    .filter(authorization_detail_types::name.eq(name))
+                    .execute(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+use diesel::prelude::*;
+
+use crate::{
+    db::{
+        pg::models::PgBackchannelAuthorization,
+        repositories::{BackchannelAuthorizationRepository, QueryFailure, RepositoryError},
+        sqlite::{
+            schema::backchannel_authorizations,
+            sql_types::{JsonValue, ListValue, UuidValue},
+        },
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::{AuthorizationDetailMapper, BackchannelAuthorizationMapper},
+        models::{
+            BackchannelAuthorizationCreateModel, BackchannelAuthorizationModel,
+            BackchannelAuthorizationStatus,
+        },
+    },
+};
+
+pub struct SqliteBackchannelAuthorizationRepository;
+
+#[async_trait]
+impl BackchannelAuthorizationRepository for SqliteBackchannelAuthorizationRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        backchannel_authorization_create: &BackchannelAuthorizationCreateModel,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "create", ?backchannel_authorization_create);
+
+        let query = diesel::insert_into(backchannel_authorizations::table).values((
+            backchannel_authorizations::auth_req_id
+                .eq(backchannel_authorization_create.auth_req_id.to_owned()),
+            backchannel_authorizations::client_id
+                .eq(backchannel_authorization_create.client_id.to_owned()),
+            backchannel_authorizations::user_id
+                .eq(UuidValue(backchannel_authorization_create.user_id)),
+            backchannel_authorizations::binding_message
+                .eq(backchannel_authorization_create.binding_message.to_owned()),
+            backchannel_authorizations::delivery_mode
+                .eq(backchannel_authorization_create.delivery_mode.as_str()),
+            backchannel_authorizations::client_notification_token.eq(
+                backchannel_authorization_create
+                    .client_notification_token
+                    .to_owned(),
+            ),
+            backchannel_authorizations::client_notification_endpoint.eq(
+                backchannel_authorization_create
+                    .client_notification_endpoint
+                    .to_owned(),
+            ),
+            backchannel_authorizations::poll_interval
+                .eq(backchannel_authorization_create.poll_interval),
+            backchannel_authorizations::created_at.eq(Utc::now().naive_utc()),
+            backchannel_authorizations::expires_at.eq(backchannel_authorization_create.expires_at),
+            backchannel_authorizations::scopes
+                .eq(ListValue::new(&backchannel_authorization_create.scopes)),
+            backchannel_authorizations::authorization_details.eq(JsonValue(
+                AuthorizationDetailMapper::vec_to_pg_value(
+                    &backchannel_authorization_create.authorization_details,
+                ),
+            )),
+        ));
+
+        let pg_backchannel_authorization = db_context
+            .with_sqlite_connection(move |conn| {
+                query.get_result::<PgBackchannelAuthorization>(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(BackchannelAuthorizationMapper::from_pg(
+            pg_backchannel_authorization,
+        ))
+    }
+
+    async fn get_by_auth_req_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "get_by_auth_req_id");
+
+        let now = Utc::now().naive_utc();
+
+        let query = backchannel_authorizations::table
+            .filter(backchannel_authorizations::auth_req_id.eq(auth_req_id.to_owned()))
+            .filter(backchannel_authorizations::created_at.lt(now))
+            .filter(backchannel_authorizations::expires_at.gt(now));
+
+        let pg_backchannel_authorization = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgBackchannelAuthorization>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(BackchannelAuthorizationMapper::from_pg(
+            pg_backchannel_authorization,
+        ))
+    }
+
+    async fn update_status_by_auth_req_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+        status: BackchannelAuthorizationStatus,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "update_status_by_auth_req_id", ?status);
+
+        let now = Utc::now().naive_utc();
+
+        // only a pending request may be resolved, and only once
+        let query = diesel::update(backchannel_authorizations::table)
+            .filter(backchannel_authorizations::auth_req_id.eq(auth_req_id.to_owned()))
+            .filter(
+                backchannel_authorizations::status
+                    .eq(BackchannelAuthorizationStatus::Pending.as_str()),
+            )
+            .filter(backchannel_authorizations::expires_at.gt(now))
+            .set(backchannel_authorizations::status.eq(status.as_str()));
+
+        let pg_backchannel_authorization = db_context
+            .with_sqlite_connection(move |conn| {
+                query.get_result::<PgBackchannelAuthorization>(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(BackchannelAuthorizationMapper::from_pg(
+            pg_backchannel_authorization,
+        ))
+    }
+
+    async fn update_last_polled_at_by_auth_req_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+        last_polled_at: &NaiveDateTime,
+    ) -> Result<BackchannelAuthorizationModel, RepositoryError> {
+        tracing::trace!(
+            method = "update_last_polled_at_by_auth_req_id",
+            ?last_polled_at
+        );
+
+        let query = diesel::update(backchannel_authorizations::table)
+            .filter(backchannel_authorizations::auth_req_id.eq(auth_req_id.to_owned()))
+            .set(backchannel_authorizations::last_polled_at.eq(*last_polled_at));
+
+        let pg_backchannel_authorization = db_context
+            .with_sqlite_connection(move |conn| {
+                query.get_result::<PgBackchannelAuthorization>(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(BackchannelAuthorizationMapper::from_pg(
+            pg_backchannel_authorization,
+        ))
+    }
+
+    async fn delete_by_auth_req_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        auth_req_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_auth_req_id");
+
+        let query = diesel::delete(backchannel_authorizations::table)
+            .filter(backchannel_authorizations::auth_req_id.eq(auth_req_id.to_owned()));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
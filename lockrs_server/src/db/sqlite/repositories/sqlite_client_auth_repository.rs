@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        pg::models::{PgClient, PgClientSecret},
+        repositories::{ClientAuthRepository, RepositoryError},
+        sqlite::{
+            schema::{client_secrets, clients, redirect_uris},
+            sql_types::UuidValue,
+        },
+        DbContext,
+    },
+    mappers::{ClientAuthMapper, ClientSecretMapper},
+    models::{ClientAuthModel, ClientSecretCreateModel, ClientSecretModel, RedirectCreateModel},
+};
+
+pub struct SqliteClientAuthRepository;
+
+#[async_trait]
+impl ClientAuthRepository for SqliteClientAuthRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_create: &ClientAuthModel,
+        secret_create: Option<&ClientSecretCreateModel>,
+        redirect_create: &RedirectCreateModel,
+    ) -> Result<ClientAuthModel, RepositoryError> {
+        tracing::trace!(
+            method = "create",
+            user_id = ?client_create.user_id,
+            id = client_create.id,
+            redirect_uri = ?redirect_create.uri
+        );
+
+        let now = Utc::now().naive_utc();
+
+        let client_query = diesel::insert_into(clients::table).values((
+            clients::id.eq(client_create.id.to_owned()),
+            clients::user_id.eq(UuidValue(client_create.user_id)),
+            clients::is_public.eq(client_create.is_public),
+            clients::name.eq(client_create.name.to_owned()),
+            clients::description.eq(client_create.description.to_owned()),
+            clients::homepage_url.eq(client_create.homepage_url.to_string()),
+        ));
+
+        let secret_query = secret_create.map(|secret_create| {
+            diesel::insert_into(client_secrets::table).values((
+                client_secrets::client_id.eq(secret_create.client_id.to_owned()),
+                client_secrets::salt.eq(secret_create.salt.to_owned()),
+                client_secrets::secret_hash.eq(secret_create.secret_hash.to_owned()),
+                client_secrets::created_at.eq(now),
+                client_secrets::expires_at.eq(secret_create.expires_at),
+            ))
+        });
+
+        let redirect_query = diesel::insert_into(redirect_uris::table).values((
+            redirect_uris::id.eq(UuidValue(Uuid::new_v4())),
+            redirect_uris::client_id.eq(redirect_create.client_id.to_owned()),
+            redirect_uris::uri.eq(redirect_create.uri.to_string()),
+            redirect_uris::created_at.eq(now),
+            redirect_uris::updated_at.eq(now),
+        ));
+
+        let pg_client = db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    let client = client_query.get_result::<PgClient>(conn)?;
+
+                    if let Some(secret_query) = secret_query {
+                        secret_query.execute(conn)?;
+                    }
+
+                    redirect_query.execute(conn)?;
+
+                    Ok(client)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(ClientAuthMapper::from_pg(pg_client))
+    }
+
+    async fn get_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<ClientAuthModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", id);
+
+        let query = clients::table.filter(clients::id.eq(id.to_owned()));
+
+        let pg_client = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgClient>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(ClientAuthMapper::from_pg(pg_client))
+    }
+
+    async fn get_active_secrets_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<ClientSecretModel>, RepositoryError> {
+        tracing::trace!(method = "get_active_secrets_by_client_id", client_id);
+
+        let now = Utc::now().naive_utc();
+
+        let query = client_secrets::table
+            .filter(client_secrets::client_id.eq(client_id.to_owned()))
+            .filter(
+                client_secrets::expires_at
+                    .is_null()
+                    .or(client_secrets::expires_at.gt(now)),
+            );
+
+        let pg_client_secrets = db_context
+            .with_sqlite_connection(move |conn| query.load::<PgClientSecret>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(pg_client_secrets
+            .into_iter()
+            .map(ClientSecretMapper::from_pg)
+            .collect::<Vec<ClientSecretModel>>())
+    }
+
+    async fn rotate_secret(
+        &self,
+        db_context: &Arc<DbContext>,
+        secret_create: &ClientSecretCreateModel,
+        previous_expires_at: &NaiveDateTime,
+    ) -> Result<ClientSecretModel, RepositoryError> {
+        tracing::trace!(
+            method = "rotate_secret",
+            ?secret_create,
+            ?previous_expires_at
+        );
+
+        // secrets that already expire sooner keep their expiry
+        let expire_query = diesel::update(client_secrets::table)
+            .filter(client_secrets::client_id.eq(secret_create.client_id.to_owned()))
+            .filter(
+                client_secrets::expires_at
+                    .is_null()
+                    .or(client_secrets::expires_at.gt(*previous_expires_at)),
+            )
+            .set(client_secrets::expires_at.eq(*previous_expires_at));
+
+        let secret_query = diesel::insert_into(client_secrets::table).values((
+            client_secrets::client_id.eq(secret_create.client_id.to_owned()),
+            client_secrets::salt.eq(secret_create.salt.to_owned()),
+            client_secrets::secret_hash.eq(secret_create.secret_hash.to_owned()),
+            client_secrets::created_at.eq(Utc::now().naive_utc()),
+            client_secrets::expires_at.eq(secret_create.expires_at),
+        ));
+
+        let pg_client_secret = db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    expire_query.execute(conn)?;
+
+                    secret_query.get_result::<PgClientSecret>(conn)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(ClientSecretMapper::from_pg(pg_client_secret))
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::{prelude::*, upsert::excluded};
+
+use crate::{
+    db::{
+        pg::models::PgClientPolicy,
+        repositories::{ClientPolicyRepository, QueryFailure, RepositoryError},
+        sqlite::{schema::client_policies, sql_types::ListValue},
+        DbContext,
+    },
+    mappers::ClientPolicyMapper,
+    models::ClientPolicyModel,
+};
+
+pub struct SqliteClientPolicyRepository;
+
+#[async_trait]
+impl ClientPolicyRepository for SqliteClientPolicyRepository {
+    async fn upsert(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_policy: &ClientPolicyModel,
+    ) -> Result<ClientPolicyModel, RepositoryError> {
+        tracing::trace!(method = "upsert", ?client_policy);
+
+        let pg_client_policy = ClientPolicyMapper::to_pg(client_policy);
+        let grant_types = pg_client_policy
+            .grant_types
+            .into_iter()
+            .flatten()
+            .collect::<Vec<String>>();
+
+        let query = diesel::insert_into(client_policies::table)
+            .values((
+                client_policies::client_id.eq(pg_client_policy.client_id),
+                client_policies::grant_types.eq(ListValue(grant_types)),
+                client_policies::access_token_lifetime.eq(pg_client_policy.access_token_lifetime),
+                client_policies::refresh_token_lifetime.eq(pg_client_policy.refresh_token_lifetime),
+                client_policies::id_token_lifetime.eq(pg_client_policy.id_token_lifetime),
+                client_policies::absolute_refresh_lifetime
+                    .eq(pg_client_policy.absolute_refresh_lifetime),
+                client_policies::sliding_refresh_window.eq(pg_client_policy.sliding_refresh_window),
+                client_policies::always_issue_refresh_token
+                    .eq(pg_client_policy.always_issue_refresh_token),
+                client_policies::backchannel_client_notification_endpoint
+                    .eq(pg_client_policy.backchannel_client_notification_endpoint),
+            ))
+            .on_conflict(client_policies::client_id)
+            .do_update()
+            .set((
+                client_policies::grant_types.eq(excluded(client_policies::grant_types)),
+                client_policies::access_token_lifetime
+                    .eq(excluded(client_policies::access_token_lifetime)),
+                client_policies::refresh_token_lifetime
+                    .eq(excluded(client_policies::refresh_token_lifetime)),
+                client_policies::id_token_lifetime.eq(excluded(client_policies::id_token_lifetime)),
+                client_policies::absolute_refresh_lifetime
+                    .eq(excluded(client_policies::absolute_refresh_lifetime)),
+                client_policies::sliding_refresh_window
+                    .eq(excluded(client_policies::sliding_refresh_window)),
+                client_policies::always_issue_refresh_token
+                    .eq(excluded(client_policies::always_issue_refresh_token)),
+                client_policies::backchannel_client_notification_endpoint.eq(excluded(
+                    client_policies::backchannel_client_notification_endpoint,
+                )),
+            ));
+
+        let pg_client_policy = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgClientPolicy>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(ClientPolicyMapper::from_pg(pg_client_policy))
+    }
+
+    async fn get_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<ClientPolicyModel, RepositoryError> {
+        tracing::trace!(method = "get_by_client_id", client_id);
+
+        let query =
+            client_policies::table.filter(client_policies::client_id.eq(client_id.to_owned()));
+
+        let pg_client_policy = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgClientPolicy>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(ClientPolicyMapper::from_pg(pg_client_policy))
+    }
+
+    async fn delete_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_client_id", client_id);
+
+        let query = diesel::delete(client_policies::table)
+            .filter(client_policies::client_id.eq(client_id.to_owned()));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
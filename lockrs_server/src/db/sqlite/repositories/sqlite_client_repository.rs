@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        pg::models::PgClient,
+        repositories::{ClientRepository, QueryFailure, RepositoryError},
+        sqlite::{schema::clients, sql_types::UuidValue},
+        DbContext,
+    },
+    mappers::ClientMapper,
+    models::{ClientModel, ClientUpdateModel},
+};
+
+pub struct SqliteClientRepository;
+
+#[async_trait]
+impl ClientRepository for SqliteClientRepository {
+    async fn get_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<ClientModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", id);
+
+        let query = clients::table.filter(clients::id.eq(id.to_owned()));
+
+        let pg_client = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgClient>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(ClientMapper::from_pg(pg_client))
+    }
+
+    async fn get_all_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<Vec<ClientModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_user_id", ?id);
+
+        let query = clients::table.filter(clients::user_id.eq(UuidValue(*id)));
+
+        let clients = db_context
+            .with_sqlite_connection(move |conn| query.load::<PgClient>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(clients
+            .into_iter()
+            .map(ClientMapper::from_pg)
+            .collect::<Vec<ClientModel>>())
+    }
+
+    async fn update_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &str,
+        client_update: &ClientUpdateModel,
+    ) -> Result<ClientModel, RepositoryError> {
+        tracing::trace!(
+            method = "update_by_id",
+            id,
+            client = ?client_update
+        );
+
+        // `ClientUpdateModel` is a changeset for the pg table, so set the same columns by hand
+        let query = diesel::update(clients::table)
+            .filter(clients::id.eq(id.to_owned()))
+            .set((
+                client_update
+                    .name
+                    .clone()
+                    .map(|name| clients::name.eq(name)),
+                client_update
+                    .description
+                    .clone()
+                    .map(|description| clients::description.eq(description)),
+                client_update
+                    .homepage_url
+                    .clone()
+                    .map(|homepage_url| clients::homepage_url.eq(homepage_url)),
+            ));
+
+        let pg_client = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgClient>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(ClientMapper::from_pg(pg_client))
+    }
+
+    async fn delete_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_id", id);
+
+        let query = diesel::delete(clients::table).filter(clients::id.eq(id.to_owned()));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
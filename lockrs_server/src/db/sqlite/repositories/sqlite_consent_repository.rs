@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        pg::models::PgConsent,
+        repositories::{ConsentRepository, QueryFailure, RepositoryError},
+        sqlite::{
+            schema::consents,
+            sql_types::{JsonValue, ListValue, UuidValue},
+        },
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::{AuthorizationDetailMapper, ConsentMapper},
+        models::{ConsentCreateModel, ConsentModel},
+    },
+};
+
+pub struct SqliteConsentRepository;
+
+#[async_trait]
+impl ConsentRepository for SqliteConsentRepository {
+    async fn upsert(
+        &self,
+        db_context: &Arc<DbContext>,
+        consent_create: &ConsentCreateModel,
+    ) -> Result<ConsentModel, RepositoryError> {
+        tracing::trace!(method = "upsert", ?consent_create);
+
+        let now = Utc::now().naive_utc();
+        let authorization_details =
+            AuthorizationDetailMapper::vec_to_pg_value(&consent_create.authorization_details);
+
+        let query = diesel::insert_into(consents::table)
+            .values((
+                consents::user_id.eq(UuidValue(consent_create.user_id)),
+                consents::client_id.eq(consent_create.client_id.to_owned()),
+                consents::scopes.eq(ListValue::new(&consent_create.scopes)),
+                consents::granted_at.eq(now),
+                consents::expires_at.eq(consent_create.expires_at),
+                consents::authorization_details.eq(JsonValue(authorization_details.clone())),
+            ))
+            .on_conflict((consents::user_id, consents::client_id))
+            .do_update()
+            .set((
+                consents::scopes.eq(ListValue::new(&consent_create.scopes)),
+                consents::granted_at.eq(now),
+                consents::expires_at.eq(consent_create.expires_at),
+                consents::authorization_details.eq(JsonValue(authorization_details)),
+            ));
+
+        let pg_consent = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgConsent>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(ConsentMapper::from_pg(pg_consent))
+    }
+
+    async fn get_by_user_id_and_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        client_id: &str,
+    ) -> Result<ConsentModel, RepositoryError> {
+        tracing::trace!(method = "get_by_user_id_and_client_id", ?user_id, client_id);
+
+        let now = Utc::now().naive_utc();
+
+        let query = consents::table
+            .filter(consents::user_id.eq(UuidValue(*user_id)))
+            .filter(consents::client_id.eq(client_id.to_owned()))
+            .filter(
+                consents::expires_at
+                    .is_null()
+                    .or(consents::expires_at.gt(now)),
+            );
+
+        let pg_consent = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgConsent>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(ConsentMapper::from_pg(pg_consent))
+    }
+
+    async fn get_all_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<Vec<ConsentModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_user_id", ?user_id);
+
+        let now = Utc::now().naive_utc();
+
+        let query = consents::table
+            .filter(consents::user_id.eq(UuidValue(*user_id)))
+            .filter(
+                consents::expires_at
+                    .is_null()
+                    .or(consents::expires_at.gt(now)),
+            )
+            .order(consents::granted_at.desc());
+
+        let pg_consents = db_context
+            .with_sqlite_connection(move |conn| query.load::<PgConsent>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(pg_consents
+            .into_iter()
+            .map(ConsentMapper::from_pg)
+            .collect::<Vec<ConsentModel>>())
+    }
+
+    async fn delete_by_user_id_and_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        client_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(
+            method = "delete_by_user_id_and_client_id",
+            ?user_id,
+            client_id
+        );
+
+        let query = diesel::delete(consents::table)
+            .filter(consents::user_id.eq(UuidValue(*user_id)))
+            .filter(consents::client_id.eq(client_id.to_owned()));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
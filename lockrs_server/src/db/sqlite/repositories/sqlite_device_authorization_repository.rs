@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+use diesel::prelude::*;
+
+use crate::{
+    db::{
+        digest_token,
+        pg::models::PgDeviceAuthorization,
+        repositories::{DeviceAuthorizationRepository, QueryFailure, RepositoryError},
+        sqlite::{schema::device_authorizations, sql_types::ListValue},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::DeviceAuthorizationMapper,
+        models::{DeviceAuthorizationCreateModel, DeviceAuthorizationModel},
+    },
+};
+
+pub struct SqliteDeviceAuthorizationRepository;
+
+#[async_trait]
+impl DeviceAuthorizationRepository for SqliteDeviceAuthorizationRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        device_authorization_create: &DeviceAuthorizationCreateModel,
+    ) -> Result<DeviceAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "create");
+
+        let query = diesel::insert_into(device_authorizations::table).values((
+            device_authorizations::client_id.eq(device_authorization_create.client_id.to_owned()),
+            device_authorizations::user_code.eq(device_authorization_create.user_code.to_owned()),
+            device_authorizations::device_code.eq(digest_token(
+                device_authorization_create.device_code.as_str(),
+            )),
+            device_authorizations::created_at.eq(Utc::now().naive_utc()),
+            device_authorizations::expires_at.eq(device_authorization_create.expires_at),
+            device_authorizations::scopes.eq(ListValue::new(&device_authorization_create.scopes)),
+        ));
+
+        let pg_device_authorization = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgDeviceAuthorization>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        // only the digest is stored, so hand the caller back the device code they issued
+        let mut device_authorization = DeviceAuthorizationMapper::from_pg(pg_device_authorization);
+        device_authorization.device_code = device_authorization_create.device_code.to_owned();
+
+        Ok(device_authorization)
+    }
+
+    async fn get_by_user_code(
+        &self,
+        db_context: &Arc<DbContext>,
+        code: &str,
+    ) -> Result<DeviceAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "get_by_user_code");
+
+        let now = Utc::now().naive_utc();
+
+        let query = device_authorizations::table
+            .filter(device_authorizations::user_code.eq(code.to_owned()))
+            .filter(device_authorizations::created_at.lt(now))
+            .filter(device_authorizations::expires_at.gt(now));
+
+        let pg_device_authorization = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgDeviceAuthorization>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        // the device code cannot be recovered from its digest here, only the user code is usable
+        Ok(DeviceAuthorizationMapper::from_pg(pg_device_authorization))
+    }
+
+    async fn get_by_device_code(
+        &self,
+        db_context: &Arc<DbContext>,
+        code: &str,
+    ) -> Result<DeviceAuthorizationModel, RepositoryError> {
+        tracing::trace!(method = "get_by_device_code");
+
+        let now = Utc::now().naive_utc();
+
+        let query = device_authorizations::table
+            .filter(device_authorizations::device_code.eq(digest_token(code)))
+            .filter(device_authorizations::created_at.lt(now))
+            .filter(device_authorizations::expires_at.gt(now));
+
+        let pg_device_authorization = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgDeviceAuthorization>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        let mut device_authorization = DeviceAuthorizationMapper::from_pg(pg_device_authorization);
+        device_authorization.device_code = code.to_owned();
+
+        Ok(device_authorization)
+    }
+
+    async fn delete_by_device_code(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_device_code");
+
+        let query = diesel::delete(device_authorizations::table)
+            .filter(device_authorizations::device_code.eq(digest_token(id)));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_expired(
+        &self,
+        db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "delete_expired", ?expired_before, limit);
+
+        let expired_before = *expired_before;
+
+        db_context
+            .with_sqlite_connection(move |conn| {
+                let expired_ids = device_authorizations::table
+                    .select(device_authorizations::id)
+                    .filter(device_authorizations::expires_at.lt(expired_before))
+                    .limit(limit)
+                    .load::<i32>(conn)?;
+
+                diesel::delete(device_authorizations::table)
+                    .filter(device_authorizations::id.eq_any(expired_ids))
+                    .execute(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+use diesel::prelude::*;
+
+use crate::{
+    api::v1::{
+        mappers::FederatedIdentityMapper,
+        models::{FederatedIdentityCreateModel, FederatedIdentityModel},
+    },
+    db::{
+        pg::models::PgFederatedIdentity,
+        repositories::{FederatedIdentityRepository, RepositoryError},
+        sqlite::{schema::federated_identities, sql_types::UuidValue},
+        DbContext,
+    },
+};
+
+pub struct SqliteFederatedIdentityRepository;
+
+#[async_trait]
+impl FederatedIdentityRepository for SqliteFederatedIdentityRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        identity_create: &FederatedIdentityCreateModel,
+    ) -> Result<FederatedIdentityModel, RepositoryError> {
+        tracing::trace!(method = "create", ?identity_create);
+
+        let query = diesel::insert_into(federated_identities::table).values((
+            federated_identities::user_id.eq(UuidValue(identity_create.user_id)),
+            federated_identities::provider.eq(identity_create.provider.to_owned()),
+            federated_identities::subject.eq(identity_create.subject.to_owned()),
+            federated_identities::created_at.eq(Utc::now().naive_utc()),
+        ));
+
+        let pg_identity = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgFederatedIdentity>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(FederatedIdentityMapper::from_pg(pg_identity))
+    }
+
+    async fn get_by_provider_and_subject(
+        &self,
+        db_context: &Arc<DbContext>,
+        provider: &str,
+        subject: &str,
+    ) -> Result<FederatedIdentityModel, RepositoryError> {
+        tracing::trace!(method = "get_by_provider_and_subject", provider, subject);
+
+        let query = federated_identities::table
+            .filter(federated_identities::provider.eq(provider.to_owned()))
+            .filter(federated_identities::subject.eq(subject.to_owned()));
+
+        let pg_identity = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgFederatedIdentity>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(FederatedIdentityMapper::from_pg(pg_identity))
+    }
+}
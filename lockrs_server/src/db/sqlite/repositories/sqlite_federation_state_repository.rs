@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+
+use crate::{
+    api::v1::models::FederationStateModel,
+    db::{
+        digest::digest_token,
+        repositories::{FederationStateRepository, RepositoryError},
+        sqlite::schema::federation_states,
+        DbContext,
+    },
+};
+
+pub struct SqliteFederationStateRepository;
+
+#[async_trait]
+impl FederationStateRepository for SqliteFederationStateRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        state: &str,
+        federation_state: &FederationStateModel,
+    ) -> Result<FederationStateModel, RepositoryError> {
+        tracing::trace!(method = "create", ?federation_state);
+
+        // nothing expires the rows on its own the way redis expires keys, so clear out the
+        // abandoned sign-ins as new ones come in
+        let purge_query = diesel::delete(federation_states::table)
+            .filter(federation_states::expires_at.le(Utc::now().timestamp_millis()));
+
+        let query = diesel::insert_into(federation_states::table).values((
+            federation_states::state.eq(digest_token(state)),
+            federation_states::provider.eq(federation_state.provider.to_owned()),
+            federation_states::nonce.eq(federation_state.nonce.to_owned()),
+            federation_states::code_verifier.eq(federation_state.code_verifier.to_owned()),
+            federation_states::binding_digest.eq(federation_state.binding_digest.to_owned()),
+            federation_states::expires_at.eq(federation_state.expires_at),
+        ));
+
+        db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    purge_query.execute(conn)?;
+                    query.execute(conn)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(federation_state.clone())
+    }
+
+    async fn take_by_state(
+        &self,
+        db_context: &Arc<DbContext>,
+        state: &str,
+    ) -> Result<FederationStateModel, RepositoryError> {
+        tracing::trace!(method = "take_by_state");
+
+        let digest = digest_token(state);
+        let now = Utc::now().timestamp_millis();
+
+        let select_query = federation_states::table
+            .select((
+                federation_states::provider,
+                federation_states::nonce,
+                federation_states::code_verifier,
+                federation_states::binding_digest,
+                federation_states::expires_at,
+            ))
+            .filter(federation_states::state.eq(digest.to_owned()))
+            .filter(federation_states::expires_at.gt(now));
+
+        let delete_query =
+            diesel::delete(federation_states::table).filter(federation_states::state.eq(digest));
+
+        let (provider, nonce, code_verifier, binding_digest, expires_at) = db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    let row = select_query.first::<(String, String, String, String, i64)>(conn)?;
+                    delete_query.execute(conn)?;
+
+                    Ok(row)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(FederationStateModel::new(
+            provider.as_str(),
+            nonce.as_str(),
+            code_verifier.as_str(),
+            binding_digest.as_str(),
+            expires_at,
+        ))
+    }
+}
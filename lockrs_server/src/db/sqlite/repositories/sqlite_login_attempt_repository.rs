@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+
+use crate::{
+    api::v1::models::LoginAttemptModel,
+    db::{
+        repositories::{LoginAttemptRepository, RepositoryError},
+        sqlite::schema::login_attempts,
+        DbContext,
+    },
+};
+
+pub struct SqliteLoginAttemptRepository;
+
+#[async_trait]
+impl LoginAttemptRepository for SqliteLoginAttemptRepository {
+    async fn get_by_key(
+        &self,
+        db_context: &Arc<DbContext>,
+        key: &str,
+    ) -> Result<LoginAttemptModel, RepositoryError> {
+        tracing::trace!(method = "get_by_key", key);
+
+        let query = login_attempts::table
+            .select((
+                login_attempts::failures,
+                login_attempts::locked_until,
+                login_attempts::expires_at,
+            ))
+            .filter(login_attempts::key.eq(key.to_owned()))
+            .filter(login_attempts::expires_at.gt(Utc::now().timestamp_millis()));
+
+        let (failures, locked_until, expires_at) = db_context
+            .with_sqlite_connection(move |conn| query.first::<(i64, Option<i64>, i64)>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(LoginAttemptModel::new(failures, locked_until, expires_at))
+    }
+
+    async fn record_failure_by_key(
+        &self,
+        db_context: &Arc<DbContext>,
+        key: &str,
+        expires_at: i64,
+    ) -> Result<LoginAttemptModel, RepositoryError> {
+        tracing::trace!(method = "record_failure_by_key", key, expires_at);
+
+        // nothing expires the rows on its own the way redis expires keys, so clear out the
+        // expired counters first, which also starts this one over if it has expired
+        let purge_query = diesel::delete(login_attempts::table)
+            .filter(login_attempts::expires_at.le(Utc::now().timestamp_millis()));
+
+        let upsert_query = diesel::insert_into(login_attempts::table)
+            .values((
+                login_attempts::key.eq(key.to_owned()),
+                login_attempts::failures.eq(1),
+                login_attempts::expires_at.eq(expires_at),
+            ))
+            .on_conflict(login_attempts::key)
+            .do_update()
+            .set((
+                login_attempts::failures.eq(login_attempts::failures + 1),
+                login_attempts::expires_at.eq(expires_at),
+            ));
+
+        let select_query = login_attempts::table
+            .select((login_attempts::failures, login_attempts::locked_until))
+            .filter(login_attempts::key.eq(key.to_owned()));
+
+        let (failures, locked_until) = db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    purge_query.execute(conn)?;
+                    upsert_query.execute(conn)?;
+                    select_query.first::<(i64, Option<i64>)>(conn)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(LoginAttemptModel::new(failures, locked_until, expires_at))
+    }
+
+    async fn lock_by_key(
+        &self,
+        db_context: &Arc<DbContext>,
+        key: &str,
+        locked_until: i64,
+        expires_at: i64,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "lock_by_key", key, locked_until, expires_at);
+
+        let query = diesel::update(login_attempts::table)
+            .filter(login_attempts::key.eq(key.to_owned()))
+            .set((
+                login_attempts::locked_until.eq(Some(locked_until)),
+                login_attempts::expires_at.eq(expires_at),
+            ));
+
+        db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(())
+    }
+
+    async fn delete_by_key(
+        &self,
+        db_context: &Arc<DbContext>,
+        key: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_key", key);
+
+        let query =
+            diesel::delete(login_attempts::table).filter(login_attempts::key.eq(key.to_owned()));
+
+        db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::models::MagicLinkModel,
+    db::{
+        repositories::{MagicLinkRepository, RepositoryError},
+        sqlite::{schema::magic_links, sql_types::UuidValue},
+        DbContext,
+    },
+};
+
+pub struct SqliteMagicLinkRepository;
+
+#[async_trait]
+impl MagicLinkRepository for SqliteMagicLinkRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        magic_link: &MagicLinkModel,
+    ) -> Result<MagicLinkModel, RepositoryError> {
+        tracing::trace!(method = "create", ?magic_link);
+
+        // nothing expires the rows on its own the way redis expires keys, so clear out the
+        // expired links as new ones come in, along with the one this replaces
+        let purge_query = diesel::delete(magic_links::table).filter(
+            magic_links::expires_at
+                .le(Utc::now().timestamp_millis())
+                .or(magic_links::email.eq(magic_link.email.to_owned())),
+        );
+
+        let query = diesel::insert_into(magic_links::table).values((
+            magic_links::email.eq(magic_link.email.to_owned()),
+            magic_links::user_id.eq(UuidValue(magic_link.user_id)),
+            magic_links::nonce_digest.eq(magic_link.nonce_digest.to_owned()),
+            magic_links::code_digest.eq(magic_link.code_digest.to_owned()),
+            magic_links::binding_digest.eq(magic_link.binding_digest.to_owned()),
+            magic_links::failed_attempts.eq(magic_link.failed_attempts),
+            magic_links::expires_at.eq(magic_link.expires_at),
+        ));
+
+        db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    purge_query.execute(conn)?;
+                    query.execute(conn)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(magic_link.clone())
+    }
+
+    async fn take_by_email(
+        &self,
+        db_context: &Arc<DbContext>,
+        email: &str,
+    ) -> Result<MagicLinkModel, RepositoryError> {
+        tracing::trace!(method = "take_by_email", email);
+
+        let now = Utc::now().timestamp_millis();
+
+        let select_query = magic_links::table
+            .select((
+                magic_links::user_id,
+                magic_links::nonce_digest,
+                magic_links::code_digest,
+                magic_links::binding_digest,
+                magic_links::failed_attempts,
+                magic_links::expires_at,
+            ))
+            .filter(magic_links::email.eq(email.to_owned()))
+            .filter(magic_links::expires_at.gt(now));
+
+        let delete_query =
+            diesel::delete(magic_links::table).filter(magic_links::email.eq(email.to_owned()));
+
+        let (user_id, nonce_digest, code_digest, binding_digest, failed_attempts, expires_at) =
+            db_context
+                .with_sqlite_connection(move |conn| {
+                    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                        let row =
+                            select_query.first::<(Uuid, String, String, String, i64, i64)>(conn)?;
+                        delete_query.execute(conn)?;
+
+                        Ok(row)
+                    })
+                })
+                .await
+                .map_err(RepositoryError::from)?
+                .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(MagicLinkModel::new(
+            &user_id,
+            email,
+            nonce_digest.as_str(),
+            code_digest.as_str(),
+            binding_digest.as_str(),
+            failed_attempts,
+            expires_at,
+        ))
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::models::PasswordResetTokenModel,
+    db::{
+        digest::digest_token,
+        repositories::{PasswordResetTokenRepository, RepositoryError},
+        sqlite::{schema::password_reset_tokens, sql_types::UuidValue},
+        DbContext,
+    },
+};
+
+pub struct SqlitePasswordResetTokenRepository;
+
+#[async_trait]
+impl PasswordResetTokenRepository for SqlitePasswordResetTokenRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+        reset_token: &PasswordResetTokenModel,
+    ) -> Result<PasswordResetTokenModel, RepositoryError> {
+        tracing::trace!(method = "create", ?reset_token);
+
+        // nothing expires the rows on its own the way redis expires keys, so clear out the
+        // expired tokens as new ones come in
+        let purge_query = diesel::delete(password_reset_tokens::table)
+            .filter(password_reset_tokens::expires_at.le(Utc::now().timestamp_millis()));
+
+        let query = diesel::insert_into(password_reset_tokens::table).values((
+            password_reset_tokens::token.eq(digest_token(token)),
+            password_reset_tokens::user_id.eq(UuidValue(reset_token.user_id)),
+            password_reset_tokens::email.eq(reset_token.email.to_owned()),
+            password_reset_tokens::password_version.eq(reset_token.password_version),
+            password_reset_tokens::expires_at.eq(reset_token.expires_at),
+        ));
+
+        db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    purge_query.execute(conn)?;
+                    query.execute(conn)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(reset_token.clone())
+    }
+
+    async fn take_by_token(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<PasswordResetTokenModel, RepositoryError> {
+        tracing::trace!(method = "take_by_token");
+
+        let digest = digest_token(token);
+        let now = Utc::now().timestamp_millis();
+
+        let select_query = password_reset_tokens::table
+            .select((
+                password_reset_tokens::user_id,
+                password_reset_tokens::email,
+                password_reset_tokens::password_version,
+                password_reset_tokens::expires_at,
+            ))
+            .filter(password_reset_tokens::token.eq(digest.to_owned()))
+            .filter(password_reset_tokens::expires_at.gt(now));
+
+        let delete_query = diesel::delete(password_reset_tokens::table)
+            .filter(password_reset_tokens::token.eq(digest));
+
+        let (user_id, email, password_version, expires_at) = db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    let row = select_query.first::<(Uuid, String, i32, i64)>(conn)?;
+                    delete_query.execute(conn)?;
+
+                    Ok(row)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(PasswordResetTokenModel::new(
+            &user_id,
+            email.as_str(),
+            password_version,
+            expires_at,
+        ))
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+
+use crate::{
+    db::{
+        digest::digest_token,
+        repositories::{PushedAuthorizationRequestRepository, QueryFailure, RepositoryError},
+        sqlite::schema::pushed_authorization_requests,
+        DbContext,
+    },
+    oauth2::v1::models::AuthorizationRequestModel,
+};
+
+pub struct SqlitePushedAuthorizationRequestRepository;
+
+impl SqlitePushedAuthorizationRequestRepository {
+    fn from_row(request: String) -> Result<AuthorizationRequestModel, RepositoryError> {
+        serde_json::from_str(request.as_str()).map_err(|_| {
+            tracing::error!(
+                error = "Invalid JSON data format for data stored at pushed authorization request"
+            );
+
+            RepositoryError::InternalError
+        })
+    }
+}
+
+#[async_trait]
+impl PushedAuthorizationRequestRepository for SqlitePushedAuthorizationRequestRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        request_uri: &str,
+        authorization_request: &AuthorizationRequestModel,
+        expires_at: i64,
+    ) -> Result<AuthorizationRequestModel, RepositoryError> {
+        tracing::trace!(method = "create", ?authorization_request, expires_at);
+
+        let request = serde_json::to_string(authorization_request).map_err(|err| {
+            tracing::error!(error = %err);
+            RepositoryError::QueryFailed(QueryFailure::NotCreated)
+        })?;
+
+        // nothing expires the rows on its own the way redis expires keys, so clear out the
+        // abandoned requests as new ones come in
+        let purge_query = diesel::delete(pushed_authorization_requests::table)
+            .filter(pushed_authorization_requests::expires_at.le(Utc::now().timestamp_millis()));
+
+        let query = diesel::insert_into(pushed_authorization_requests::table).values((
+            pushed_authorization_requests::request_uri.eq(digest_token(request_uri)),
+            pushed_authorization_requests::request.eq(request),
+            pushed_authorization_requests::expires_at.eq(expires_at),
+        ));
+
+        db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    purge_query.execute(conn)?;
+                    query.execute(conn)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(authorization_request.clone())
+    }
+
+    async fn get_by_request_uri(
+        &self,
+        db_context: &Arc<DbContext>,
+        request_uri: &str,
+    ) -> Result<AuthorizationRequestModel, RepositoryError> {
+        tracing::trace!(method = "get_by_request_uri");
+
+        let query = pushed_authorization_requests::table
+            .select(pushed_authorization_requests::request)
+            .filter(pushed_authorization_requests::request_uri.eq(digest_token(request_uri)))
+            .filter(pushed_authorization_requests::expires_at.gt(Utc::now().timestamp_millis()));
+
+        let request = db_context
+            .with_sqlite_connection(move |conn| query.first::<String>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Self::from_row(request)
+    }
+
+    async fn take_by_request_uri(
+        &self,
+        db_context: &Arc<DbContext>,
+        request_uri: &str,
+    ) -> Result<AuthorizationRequestModel, RepositoryError> {
+        tracing::trace!(method = "take_by_request_uri");
+
+        let digest = digest_token(request_uri);
+
+        let select_query = pushed_authorization_requests::table
+            .select(pushed_authorization_requests::request)
+            .filter(pushed_authorization_requests::request_uri.eq(digest.to_owned()))
+            .filter(pushed_authorization_requests::expires_at.gt(Utc::now().timestamp_millis()));
+
+        let delete_query = diesel::delete(pushed_authorization_requests::table)
+            .filter(pushed_authorization_requests::request_uri.eq(digest));
+
+        let request = db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    let request = select_query.first::<String>(conn)?;
+                    delete_query.execute(conn)?;
+
+                    Ok(request)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Self::from_row(request)
+    }
+}
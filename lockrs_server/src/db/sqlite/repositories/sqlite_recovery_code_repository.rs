@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::{
+    repositories::{QueryFailure, RecoveryCodeRepository, RepositoryError},
+    sqlite::{schema::recovery_codes, sql_types::UuidValue},
+    DbContext,
+};
+
+pub struct SqliteRecoveryCodeRepository;
+
+#[async_trait]
+impl RecoveryCodeRepository for SqliteRecoveryCodeRepository {
+    async fn replace_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        code_hashes: &[String],
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(
+            method = "replace_by_user_id",
+            ?user_id,
+            count = code_hashes.len()
+        );
+
+        let now = Utc::now().naive_utc();
+
+        let delete_query = diesel::delete(recovery_codes::table)
+            .filter(recovery_codes::user_id.eq(UuidValue(*user_id)));
+
+        let insert_query = diesel::insert_into(recovery_codes::table).values(
+            code_hashes
+                .iter()
+                .map(|code_hash| {
+                    (
+                        recovery_codes::user_id.eq(UuidValue(*user_id)),
+                        recovery_codes::code_hash.eq(code_hash.to_owned()),
+                        recovery_codes::created_at.eq(now),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    delete_query.execute(conn)?;
+                    insert_query.execute(conn)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(())
+    }
+
+    async fn use_by_user_id_and_hash(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        code_hash: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "use_by_user_id_and_hash", ?user_id);
+
+        let query = diesel::update(recovery_codes::table)
+            .filter(recovery_codes::user_id.eq(UuidValue(*user_id)))
+            .filter(recovery_codes::code_hash.eq(code_hash.to_owned()))
+            .filter(recovery_codes::used_at.is_null())
+            .set(recovery_codes::used_at.eq(Utc::now().naive_utc()));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by update, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotUpdated));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_user_id", ?user_id);
+
+        let query = diesel::delete(recovery_codes::table)
+            .filter(recovery_codes::user_id.eq(UuidValue(*user_id)));
+
+        db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        Ok(())
+    }
+}
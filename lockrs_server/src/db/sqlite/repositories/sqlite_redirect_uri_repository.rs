@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+use diesel::prelude::*;
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        pg::models::PgRedirectUri,
+        repositories::{QueryFailure, RedirectUriRepository, RepositoryError},
+        sqlite::{
+            schema::{clients, redirect_uris},
+            sql_types::UuidValue,
+        },
+        DbContext,
+    },
+    mappers::RedirectMapper,
+    models::{RedirectCreateModel, RedirectModel},
+};
+
+pub struct SqliteRedirectUriRepository;
+
+#[async_trait]
+impl RedirectUriRepository for SqliteRedirectUriRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        redirect_create: &RedirectCreateModel,
+    ) -> Result<RedirectModel, RepositoryError> {
+        tracing::trace!(
+            method = "create",
+            redirect = ?redirect_create
+        );
+
+        let now = Utc::now().naive_utc();
+
+        let query = diesel::insert_into(redirect_uris::table).values((
+            redirect_uris::id.eq(UuidValue(Uuid::new_v4())),
+            redirect_uris::client_id.eq(redirect_create.client_id.to_owned()),
+            redirect_uris::uri.eq(redirect_create.uri.to_string()),
+            redirect_uris::created_at.eq(now),
+            redirect_uris::updated_at.eq(now),
+            redirect_uris::match_mode.eq(redirect_create.match_mode.as_str()),
+        ));
+
+        let pg_redirect = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgRedirectUri>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(RedirectMapper::from_pg(pg_redirect))
+    }
+
+    async fn get_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<RedirectModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", ?id);
+
+        let query = redirect_uris::table.filter(redirect_uris::id.eq(UuidValue(*id)));
+
+        let pg_redirect = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgRedirectUri>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(RedirectMapper::from_pg(pg_redirect))
+    }
+
+    async fn get_by_uri(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        uri: &Url,
+    ) -> Result<RedirectModel, RepositoryError> {
+        tracing::trace!(method = "get_by_uri", client_id, ?uri);
+
+        let query = redirect_uris::table
+            .filter(redirect_uris::client_id.eq(client_id.to_owned()))
+            .filter(redirect_uris::uri.eq(uri.to_string()));
+
+        let db_redirect = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgRedirectUri>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(RedirectMapper::from_pg(db_redirect))
+    }
+
+    async fn get_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<Uuid, RepositoryError> {
+        tracing::trace!(method = "get_user_id", ?id);
+
+        let query = redirect_uris::table
+            .inner_join(clients::table)
+            .filter(redirect_uris::id.eq(UuidValue(*id)))
+            .select(clients::user_id);
+
+        db_context
+            .with_sqlite_connection(move |conn| query.first::<Uuid>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)
+    }
+
+    async fn get_all_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<RedirectModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_client_id", client_id);
+
+        let query = redirect_uris::table.filter(redirect_uris::client_id.eq(client_id.to_owned()));
+
+        let db_redirects = db_context
+            .with_sqlite_connection(move |conn| query.load::<PgRedirectUri>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(db_redirects
+            .into_iter()
+            .map(RedirectMapper::from_pg)
+            .collect::<Vec<RedirectModel>>())
+    }
+
+    async fn delete_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_id", ?id);
+
+        let query =
+            diesel::delete(redirect_uris::table).filter(redirect_uris::id.eq(UuidValue(*id)));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{offset::Utc, NaiveDateTime};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        digest_token,
+        pg::models::PgRefreshToken,
+        repositories::{QueryFailure, RefreshTokenRepository, RepositoryError},
+        sqlite::{
+            schema::refresh_tokens,
+            sql_types::{JsonValue, ListValue, UuidValue},
+        },
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::{AuthorizationDetailMapper, RefreshTokenMapper},
+        models::{RefreshTokenCreateModel, RefreshTokenModel},
+    },
+};
+
+pub struct SqliteRefreshTokenRepository;
+
+#[async_trait]
+impl RefreshTokenRepository for SqliteRefreshTokenRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        token_create: &RefreshTokenCreateModel,
+    ) -> Result<RefreshTokenModel, RepositoryError> {
+        tracing::trace!(method = "create",);
+
+        let query = diesel::insert_into(refresh_tokens::table).values((
+            refresh_tokens::token.eq(digest_token(token_create.token.as_str())),
+            refresh_tokens::access_token_id.eq(token_create.access_token_id),
+            refresh_tokens::client_id.eq(token_create.client_id.to_owned()),
+            refresh_tokens::user_id.eq(token_create.user_id.map(UuidValue)),
+            refresh_tokens::created_at.eq(Utc::now().naive_utc()),
+            refresh_tokens::expires_at.eq(token_create.expires_at),
+            refresh_tokens::scopes.eq(ListValue::new(&token_create.scopes)),
+            refresh_tokens::authorization_details.eq(JsonValue(
+                AuthorizationDetailMapper::vec_to_pg_value(&token_create.authorization_details),
+            )),
+            refresh_tokens::grant_created_at.eq(token_create.grant_created_at),
+        ));
+
+        let pg_token = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgRefreshToken>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        // only the digest is stored, so hand the caller back the token they issued
+        let mut token = RefreshTokenMapper::from_pg(pg_token);
+        token.token = token_create.token.to_owned();
+
+        Ok(token)
+    }
+
+    async fn get_by_token(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<RefreshTokenModel, RepositoryError> {
+        tracing::trace!(method = "get_by_token",);
+
+        let now = Utc::now().naive_utc();
+
+        let query = refresh_tokens::table
+            .filter(refresh_tokens::token.eq(digest_token(token)))
+            .filter(refresh_tokens::created_at.lt(now))
+            .filter(refresh_tokens::expires_at.gt(now))
+            .filter(refresh_tokens::used.eq(false));
+
+        let pg_token = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgRefreshToken>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        let mut refresh_token = RefreshTokenMapper::from_pg(pg_token);
+        refresh_token.token = token.to_owned();
+
+        Ok(refresh_token)
+    }
+
+    async fn use_by_token(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<RefreshTokenModel, RepositoryError> {
+        tracing::trace!(method = "use_by_token",);
+
+        let now = Utc::now().naive_utc();
+
+        let query = diesel::update(refresh_tokens::table)
+            .filter(refresh_tokens::token.eq(digest_token(token)))
+            .filter(refresh_tokens::created_at.lt(now))
+            .filter(refresh_tokens::expires_at.gt(now))
+            .filter(refresh_tokens::used.eq(false))
+            .set(refresh_tokens::used.eq(true));
+
+        let pg_token = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgRefreshToken>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        let mut refresh_token = RefreshTokenMapper::from_pg(pg_token);
+        refresh_token.token = token.to_owned();
+
+        Ok(refresh_token)
+    }
+
+    async fn revoke_all_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "revoke_all_by_user_id", ?user_id);
+
+        let query = diesel::update(refresh_tokens::table)
+            .filter(refresh_tokens::user_id.eq(UuidValue(*user_id)))
+            .filter(refresh_tokens::used.eq(false))
+            .set(refresh_tokens::used.eq(true));
+
+        db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)
+    }
+
+    async fn revoke_all_by_user_id_and_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        client_id: &str,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(
+            method = "revoke_all_by_user_id_and_client_id",
+            ?user_id,
+            client_id
+        );
+
+        // a used token can no longer be exchanged, so marking every outstanding token as used
+        // revokes the grant
+        let query = diesel::update(refresh_tokens::table)
+            .filter(refresh_tokens::user_id.eq(UuidValue(*user_id)))
+            .filter(refresh_tokens::client_id.eq(client_id.to_owned()))
+            .filter(refresh_tokens::used.eq(false))
+            .set(refresh_tokens::used.eq(true));
+
+        db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)
+    }
+
+    async fn delete_by_token(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_token",);
+
+        let query = diesel::delete(refresh_tokens::table)
+            .filter(refresh_tokens::token.eq(digest_token(token)));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_expired(
+        &self,
+        db_context: &Arc<DbContext>,
+        expired_before: &NaiveDateTime,
+        limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        tracing::trace!(method = "delete_expired", ?expired_before, limit);
+
+        let expired_before = *expired_before;
+
+        db_context
+            .with_sqlite_connection(move |conn| {
+                let expired_ids = refresh_tokens::table
+                    .select(refresh_tokens::id)
+                    .filter(refresh_tokens::expires_at.lt(expired_before))
+                    .limit(limit)
+                    .load::<i32>(conn)?;
+
+                diesel::delete(refresh_tokens::table)
+                    .filter(refresh_tokens::id.eq_any(expired_ids))
+                    .execute(conn)
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)
+    }
+}
@@ -0,0 +1,232 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+
+use crate::{
+    db::{
+        pg::models::{PgAllowedScope, PgScope},
+        repositories::{QueryFailure, RepositoryError, ScopeRepository},
+        sqlite::schema::{allowed_scopes, scopes},
+        DbContext,
+    },
+    oauth2::v1::{
+        mappers::ScopeMapper,
+        models::{
+            AllowedScopeModel, ScopeCreateModel, ScopeDefinitionModel, ScopeModel, ScopeUpdateModel,
+        },
+    },
+};
+
+pub struct SqliteScopeRepository;
+
+#[async_trait]
+impl ScopeRepository for SqliteScopeRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        scope_create: &ScopeCreateModel,
+    ) -> Result<ScopeDefinitionModel, RepositoryError> {
+        tracing::trace!(method = "create", ?scope_create);
+
+        let query = diesel::insert_into(scopes::table).values((
+            scopes::name.eq(scope_create.scope.to_owned()),
+            scopes::description.eq(scope_create.description.to_owned()),
+            scopes::client_id.eq(scope_create.client_id.to_owned()),
+            scopes::consent_text.eq(scope_create.consent_text.to_owned()),
+        ));
+
+        let pg_scope = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgScope>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(ScopeMapper::from_pg(pg_scope))
+    }
+
+    async fn get_from_list(
+        &self,
+        db_context: &Arc<DbContext>,
+        scopes_list: &[String],
+    ) -> Result<ScopeModel, RepositoryError> {
+        tracing::trace!(
+            method = "get_from_list",
+            scopes = ?scopes_list
+        );
+
+        let query = scopes::table
+            .select(scopes::name)
+            .filter(scopes::name.eq_any(scopes_list.to_vec()));
+
+        let pg_scopes = db_context
+            .with_sqlite_connection(move |conn| query.load::<String>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(ScopeModel::new(pg_scopes.as_slice()))
+    }
+
+    async fn get_all_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<ScopeDefinitionModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_client_id", client_id);
+
+        let query = scopes::table
+            .filter(scopes::client_id.eq(client_id.to_owned()))
+            .order(scopes::name.asc());
+
+        let pg_scopes = db_context
+            .with_sqlite_connection(move |conn| query.load::<PgScope>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(pg_scopes
+            .into_iter()
+            .map(ScopeMapper::from_pg)
+            .collect::<Vec<ScopeDefinitionModel>>())
+    }
+
+    async fn get_allowed_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+    ) -> Result<Vec<AllowedScopeModel>, RepositoryError> {
+        tracing::trace!(method = "get_allowed_by_client_id", client_id);
+
+        let query = allowed_scopes::table
+            .filter(allowed_scopes::client_id.eq(client_id.to_owned()))
+            .order(allowed_scopes::scope.asc());
+
+        let pg_allowed_scopes = db_context
+            .with_sqlite_connection(move |conn| query.load::<PgAllowedScope>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(pg_allowed_scopes
+            .into_iter()
+            .map(ScopeMapper::allowed_from_pg)
+            .collect::<Vec<AllowedScopeModel>>())
+    }
+
+    async fn replace_allowed_by_client_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        allowed_scopes: &[AllowedScopeModel],
+    ) -> Result<Vec<AllowedScopeModel>, RepositoryError> {
+        tracing::trace!(
+            method = "replace_allowed_by_client_id",
+            client_id,
+            ?allowed_scopes
+        );
+
+        let delete_query = diesel::delete(allowed_scopes::table)
+            .filter(allowed_scopes::client_id.eq(client_id.to_owned()));
+
+        let insert_queries = allowed_scopes
+            .iter()
+            .map(|allowed_scope| {
+                diesel::insert_into(allowed_scopes::table).values((
+                    allowed_scopes::client_id.eq(client_id.to_owned()),
+                    allowed_scopes::scope.eq(allowed_scope.scope.to_owned()),
+                    allowed_scopes::is_default.eq(allowed_scope.is_default),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        let pg_allowed_scopes = db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<Vec<PgAllowedScope>, RepositoryError, _>(|conn| {
+                    delete_query
+                        .execute(conn)
+                        .map_err(RepositoryError::map_diesel_delete)?;
+
+                    insert_queries
+                        .into_iter()
+                        .map(|insert_query| {
+                            insert_query
+                                .get_result::<PgAllowedScope>(conn)
+                                .map_err(RepositoryError::map_diesel_create)
+                        })
+                        .collect()
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)??;
+
+        Ok(pg_allowed_scopes
+            .into_iter()
+            .map(ScopeMapper::allowed_from_pg)
+            .collect::<Vec<AllowedScopeModel>>())
+    }
+
+    async fn update_by_name(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
+        scope_update: &ScopeUpdateModel,
+    ) -> Result<ScopeDefinitionModel, RepositoryError> {
+        tracing::trace!(method = "update_by_name", client_id, name, ?scope_update);
+
+        // `ScopeUpdateModel` is a changeset for the pg table, so set the same columns by hand
+        let query = diesel::update(scopes::table)
+            .filter(scopes::client_id.eq(client_id.to_owned()))
+            .filter(scopes::name.eq(name.to_owned()))
+            .set((
+                scope_update
+                    .description
+                    .clone()
+                    .map(|description| scopes::description.eq(description)),
+                scope_update
+                    .consent_text
+                    .clone()
+                    .map(|consent_text| scopes::consent_text.eq(consent_text)),
+            ));
+
+        let pg_scope = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgScope>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(ScopeMapper::from_pg(pg_scope))
+    }
+
+    async fn delete_by_name(
+        &self,
+        db_context: &Arc<DbContext>,
+        client_id: &str,
+        name: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_name", client_id, name);
+
+        let query = diesel::delete(scopes::table)
+            .filter(scopes::client_id.eq(client_id.to_owned()))
+            .filter(scopes::name.eq(name.to_owned()));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::{prelude::*, upsert::excluded};
+use uuid::Uuid;
+
+use crate::{
+    api::v1::models::SessionModel,
+    db::{
+        repositories::{RepositoryError, SessionRepository},
+        sqlite::{schema::sessions, sql_types::UuidValue},
+        DbContext,
+    },
+};
+
+pub struct SqliteSessionRepository;
+
+impl SqliteSessionRepository {
+    /// stores the session under its user and moves the expiry of every session of the user to
+    /// the session's, like `HSET` followed by `PEXPIREAT` on the user's redis hash
+    async fn put(
+        db_context: &Arc<DbContext>,
+        session: &SessionModel,
+    ) -> Result<SessionModel, RepositoryError> {
+        // nothing expires the rows on its own the way redis expires keys, so clear out the
+        // expired sessions of every user as new ones come in
+        let purge_query = diesel::delete(sessions::table)
+            .filter(sessions::expires_at.le(Utc::now().timestamp_millis()));
+
+        let put_query = diesel::insert_into(sessions::table)
+            .values((
+                sessions::id.eq(session.id.to_owned()),
+                sessions::user_id.eq(UuidValue(session.user_id)),
+                sessions::expires_at.eq(session.expires_at),
+            ))
+            .on_conflict((sessions::user_id, sessions::id))
+            .do_update()
+            .set(sessions::expires_at.eq(excluded(sessions::expires_at)));
+
+        let expire_query = diesel::update(sessions::table)
+            .filter(sessions::user_id.eq(UuidValue(session.user_id)))
+            .set(sessions::expires_at.eq(session.expires_at));
+
+        db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    purge_query.execute(conn)?;
+                    put_query.execute(conn)?;
+                    expire_query.execute(conn)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(session.clone())
+    }
+}
+
+#[async_trait]
+impl SessionRepository for SqliteSessionRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        session: &SessionModel,
+    ) -> Result<SessionModel, RepositoryError> {
+        tracing::trace!(method = "create");
+
+        Self::put(db_context, session).await
+    }
+
+    async fn get_by_hash(
+        &self,
+        db_context: &Arc<DbContext>,
+        session_id: &str,
+        user_id: &Uuid,
+    ) -> Result<SessionModel, RepositoryError> {
+        tracing::trace!(method = "get_by_hash");
+
+        let now = Utc::now().timestamp_millis();
+
+        let query = sessions::table
+            .select((sessions::id, sessions::user_id, sessions::expires_at))
+            .filter(sessions::user_id.eq(UuidValue(*user_id)))
+            .filter(sessions::id.eq(session_id.to_owned()))
+            .filter(sessions::expires_at.gt(now));
+
+        let (id, user_id, expires_at) = db_context
+            .with_sqlite_connection(move |conn| query.first::<(String, Uuid, i64)>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(SessionModel::new(id.as_str(), &user_id, expires_at))
+    }
+
+    async fn update(
+        &self,
+        db_context: &Arc<DbContext>,
+        session: &SessionModel,
+    ) -> Result<SessionModel, RepositoryError> {
+        tracing::trace!(method = "update");
+
+        Self::put(db_context, session).await
+    }
+
+    async fn delete_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_user_id", ?id);
+
+        let query = diesel::delete(sessions::table).filter(sessions::user_id.eq(UuidValue(*id)));
+
+        db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        Ok(())
+    }
+
+    async fn delete_others_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+        session_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_others_by_user_id", ?id);
+
+        let query = diesel::delete(sessions::table)
+            .filter(sessions::user_id.eq(UuidValue(*id)))
+            .filter(sessions::id.ne(session_id.to_owned()));
+
+        db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        Ok(())
+    }
+}
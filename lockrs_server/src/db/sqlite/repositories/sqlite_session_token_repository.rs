@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::models::SessionTokenModel,
+    db::{
+        repositories::{QueryFailure, RepositoryError, SessionTokenRepository},
+        sqlite::{schema::session_tokens, sql_types::UuidValue},
+        DbContext,
+    },
+};
+
+pub struct SqliteSessionTokenRepository;
+
+#[async_trait]
+impl SessionTokenRepository for SqliteSessionTokenRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &SessionTokenModel,
+    ) -> Result<SessionTokenModel, RepositoryError> {
+        tracing::trace!(method = "create");
+
+        // nothing expires the rows on its own the way redis expires keys, so clear out the
+        // expired tokens as new ones come in
+        let purge_query = diesel::delete(session_tokens::table)
+            .filter(session_tokens::expires_at.le(Utc::now().timestamp_millis()));
+
+        // `SET` overwrites whatever was stored at the key before
+        let query = diesel::replace_into(session_tokens::table).values((
+            session_tokens::token.eq(token.token.to_owned()),
+            session_tokens::user_id.eq(UuidValue(token.user_id)),
+            session_tokens::expires_at.eq(token.expires_at),
+            session_tokens::mfa_pending.eq(token.mfa_pending),
+        ));
+
+        db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    purge_query.execute(conn)?;
+                    query.execute(conn)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(token.clone())
+    }
+
+    async fn get_by_token(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<SessionTokenModel, RepositoryError> {
+        tracing::trace!(method = "get_by_token");
+
+        let now = Utc::now().timestamp_millis();
+
+        let query = session_tokens::table
+            .select((
+                session_tokens::user_id,
+                session_tokens::expires_at,
+                session_tokens::mfa_pending,
+            ))
+            .filter(session_tokens::token.eq(token.to_owned()))
+            .filter(session_tokens::expires_at.gt(now));
+
+        let (user_id, expires_at, mfa_pending) = db_context
+            .with_sqlite_connection(move |conn| query.first::<(Uuid, i64, bool)>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(SessionTokenModel::new(
+            token,
+            &user_id,
+            expires_at,
+            mfa_pending,
+        ))
+    }
+
+    async fn delete_by_token(
+        &self,
+        db_context: &Arc<DbContext>,
+        token: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_token");
+
+        let now = Utc::now().timestamp_millis();
+
+        // an expired key is already gone from redis, so deleting it affects nothing
+        let query = diesel::delete(session_tokens::table)
+            .filter(session_tokens::token.eq(token.to_owned()))
+            .filter(session_tokens::expires_at.gt(now));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
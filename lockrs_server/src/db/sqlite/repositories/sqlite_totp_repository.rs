@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        mappers::TotpMapper,
+        models::{TotpCreateModel, TotpModel},
+    },
+    db::{
+        pg::models::PgTotpSecret,
+        repositories::{QueryFailure, RepositoryError, TotpRepository},
+        sqlite::{schema::totp_secrets, sql_types::UuidValue},
+        DbContext,
+    },
+};
+
+pub struct SqliteTotpRepository;
+
+#[async_trait]
+impl TotpRepository for SqliteTotpRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        totp_create: &TotpCreateModel,
+    ) -> Result<TotpModel, RepositoryError> {
+        tracing::trace!(method = "create", ?totp_create);
+
+        // a confirmed enrollment is left in place, so the insert below violates the primary key
+        let delete_query = diesel::delete(totp_secrets::table)
+            .filter(totp_secrets::user_id.eq(UuidValue(totp_create.user_id)))
+            .filter(totp_secrets::confirmed_at.is_null());
+
+        let insert_query = diesel::insert_into(totp_secrets::table).values((
+            totp_secrets::user_id.eq(UuidValue(totp_create.user_id)),
+            totp_secrets::secret.eq(totp_create.secret.to_owned()),
+            totp_secrets::created_at.eq(Utc::now().naive_utc()),
+        ));
+
+        let pg_totp_secret = db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    delete_query.execute(conn)?;
+
+                    insert_query.get_result::<PgTotpSecret>(conn)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(TotpMapper::from_pg(pg_totp_secret))
+    }
+
+    async fn get_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<TotpModel, RepositoryError> {
+        tracing::trace!(method = "get_by_user_id", ?user_id);
+
+        let query = totp_secrets::table.filter(totp_secrets::user_id.eq(UuidValue(*user_id)));
+
+        let pg_totp_secret = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgTotpSecret>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(TotpMapper::from_pg(pg_totp_secret))
+    }
+
+    async fn confirm_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        step: i64,
+    ) -> Result<TotpModel, RepositoryError> {
+        tracing::trace!(method = "confirm_by_user_id", ?user_id, step);
+
+        let query = diesel::update(totp_secrets::table)
+            .filter(totp_secrets::user_id.eq(UuidValue(*user_id)))
+            .filter(totp_secrets::confirmed_at.is_null())
+            .set((
+                totp_secrets::confirmed_at.eq(Utc::now().naive_utc()),
+                totp_secrets::last_used_step.eq(step),
+            ));
+
+        let pg_totp_secret = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgTotpSecret>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(TotpMapper::from_pg(pg_totp_secret))
+    }
+
+    async fn use_step_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        step: i64,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "use_step_by_user_id", ?user_id, step);
+
+        let query = diesel::update(totp_secrets::table)
+            .filter(totp_secrets::user_id.eq(UuidValue(*user_id)))
+            .filter(totp_secrets::confirmed_at.is_not_null())
+            .filter(
+                totp_secrets::last_used_step
+                    .is_null()
+                    .or(totp_secrets::last_used_step.lt(step)),
+            )
+            .set(totp_secrets::last_used_step.eq(step));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by update, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotUpdated));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_user_id", ?user_id);
+
+        let query = diesel::delete(totp_secrets::table)
+            .filter(totp_secrets::user_id.eq(UuidValue(*user_id)));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
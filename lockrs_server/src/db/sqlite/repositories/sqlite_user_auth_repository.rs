@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        mappers::UserAuthMapper,
+        models::{UserAuthModel, UserRegisterModel},
+    },
+    db::{
+        pg::models::PgUser,
+        repositories::{RepositoryError, UserAuthRepository},
+        sqlite::{schema::users, sql_types::UuidValue},
+        DbContext,
+    },
+};
+
+pub struct SqliteUserAuthRepository;
+
+#[async_trait]
+impl UserAuthRepository for SqliteUserAuthRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_create: &UserRegisterModel,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "create", email = user_create.email);
+
+        // sqlite has no `uuid_generate_v4()` to default the id to
+        let query = diesel::insert_into(users::table).values((
+            users::id.eq(UuidValue(Uuid::new_v4())),
+            users::email.eq(user_create.email.to_owned()),
+            users::password_hash.eq(user_create.password_hash.to_owned()),
+        ));
+
+        let pg_user = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgUser>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+
+    async fn create_raw(
+        &self,
+        db_context: &Arc<DbContext>,
+        user: &UserAuthModel,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::warn!(method = "create_raw", ?user);
+
+        let query = diesel::insert_into(users::table).values((
+            users::id.eq(UuidValue(user.id)),
+            users::email.eq(user.email.to_owned()),
+            users::password_hash.eq(user.password_hash.to_owned()),
+            users::email_verified.eq(user.email_verified),
+            users::password_version.eq(user.password_version),
+        ));
+
+        let pg_user = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgUser>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+
+    async fn get_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", ?id);
+
+        let query = users::table.filter(users::id.eq(UuidValue(*id)));
+
+        let pg_user = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgUser>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+
+    async fn get_by_email(
+        &self,
+        db_context: &Arc<DbContext>,
+        email: &str,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "get_by_email", email);
+
+        let query = users::table.filter(users::email.eq(email.to_owned()));
+
+        let pg_user = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgUser>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+
+    async fn update_password_hash_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+        password_hash: &str,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "update_password_hash_by_id", ?id);
+
+        let query = diesel::update(users::table)
+            .filter(users::id.eq(UuidValue(*id)))
+            .set(users::password_hash.eq(password_hash.to_owned()));
+
+        let pg_user = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgUser>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+
+    async fn change_password_hash_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+        password_hash: &str,
+    ) -> Result<UserAuthModel, RepositoryError> {
+        tracing::trace!(method = "change_password_hash_by_id", ?id);
+
+        let query = diesel::update(users::table)
+            .filter(users::id.eq(UuidValue(*id)))
+            .set((
+                users::password_hash.eq(password_hash.to_owned()),
+                users::password_version.eq(users::password_version + 1),
+            ));
+
+        let pg_user = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgUser>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(UserAuthMapper::from_pg(pg_user))
+    }
+}
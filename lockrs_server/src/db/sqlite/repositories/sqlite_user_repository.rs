@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        pg::models::PgUser,
+        repositories::{QueryFailure, RepositoryError, UserRepository},
+        sqlite::{schema::users, sql_types::UuidValue},
+        DbContext,
+    },
+    mappers::UserMapper,
+    models::{UserModel, UserUpdateModel},
+};
+
+pub struct SqliteUserRepository;
+
+#[async_trait]
+impl UserRepository for SqliteUserRepository {
+    async fn get_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<UserModel, RepositoryError> {
+        tracing::trace!(method = "get_by_id", ?id);
+
+        let query = users::table.filter(users::id.eq(UuidValue(*id)));
+
+        let pg_user = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgUser>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(UserMapper::from_pg(pg_user))
+    }
+
+    async fn get_by_email(
+        &self,
+        db_context: &Arc<DbContext>,
+        email: &str,
+    ) -> Result<UserModel, RepositoryError> {
+        tracing::trace!(method = "get_by_email", email);
+
+        let query = users::table.filter(users::email.eq(email.to_owned()));
+
+        let pg_user = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgUser>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(UserMapper::from_pg(pg_user))
+    }
+
+    async fn update_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+        update_user: &UserUpdateModel,
+    ) -> Result<UserModel, RepositoryError> {
+        tracing::trace!(method = "update_by_id", ?id);
+
+        // `UserUpdateModel` is a changeset for the pg table, so set the same columns by hand
+        let query = diesel::update(users::table)
+            .filter(users::id.eq(UuidValue(*id)))
+            .set((
+                update_user
+                    .email
+                    .clone()
+                    .map(|email| users::email.eq(email)),
+                update_user
+                    .email_verified
+                    .map(|email_verified| users::email_verified.eq(email_verified)),
+            ));
+
+        let pg_user = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgUser>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        Ok(UserMapper::from_pg(pg_user))
+    }
+
+    async fn delete_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "delete_by_id", ?id);
+
+        let query = diesel::delete(users::table.filter(users::id.eq(UuidValue(*id))));
+
+        let rows_affected = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if rows_affected != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                rows_affected
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
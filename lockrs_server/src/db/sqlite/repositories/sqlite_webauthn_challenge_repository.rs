@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::models::WebauthnChallengeModel,
+    db::{
+        repositories::{RepositoryError, WebauthnChallengeRepository},
+        sqlite::{schema::webauthn_challenges, sql_types::UuidValue},
+        DbContext,
+    },
+};
+
+pub struct SqliteWebauthnChallengeRepository;
+
+#[async_trait]
+impl WebauthnChallengeRepository for SqliteWebauthnChallengeRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        challenge: &WebauthnChallengeModel,
+    ) -> Result<WebauthnChallengeModel, RepositoryError> {
+        tracing::trace!(method = "create", ?challenge);
+
+        // nothing expires the rows on its own the way redis expires keys, so clear out the
+        // expired challenges as new ones come in
+        let purge_query = diesel::delete(webauthn_challenges::table)
+            .filter(webauthn_challenges::expires_at.le(Utc::now().timestamp_millis()));
+
+        let query = diesel::insert_into(webauthn_challenges::table).values((
+            webauthn_challenges::id.eq(challenge.id.to_owned()),
+            webauthn_challenges::challenge.eq(challenge.challenge.to_owned()),
+            webauthn_challenges::user_id.eq(challenge.user_id.map(UuidValue)),
+            webauthn_challenges::ceremony.eq(challenge.ceremony.to_owned()),
+            webauthn_challenges::expires_at.eq(challenge.expires_at),
+        ));
+
+        db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    purge_query.execute(conn)?;
+                    query.execute(conn)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(challenge.clone())
+    }
+
+    async fn take_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: &str,
+    ) -> Result<WebauthnChallengeModel, RepositoryError> {
+        tracing::trace!(method = "take_by_id", id);
+
+        let now = Utc::now().timestamp_millis();
+
+        let select_query = webauthn_challenges::table
+            .select((
+                webauthn_challenges::challenge,
+                webauthn_challenges::user_id,
+                webauthn_challenges::ceremony,
+                webauthn_challenges::expires_at,
+            ))
+            .filter(webauthn_challenges::id.eq(id.to_owned()))
+            .filter(webauthn_challenges::expires_at.gt(now));
+
+        let delete_query = diesel::delete(webauthn_challenges::table)
+            .filter(webauthn_challenges::id.eq(id.to_owned()));
+
+        let (challenge, user_id, ceremony, expires_at) = db_context
+            .with_sqlite_connection(move |conn| {
+                conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    let row = select_query.first::<(String, Option<Uuid>, String, i64)>(conn)?;
+                    delete_query.execute(conn)?;
+
+                    Ok(row)
+                })
+            })
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(WebauthnChallengeModel::new(
+            id,
+            challenge.as_str(),
+            user_id.as_ref(),
+            ceremony.as_str(),
+            expires_at,
+        ))
+    }
+}
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::offset::Utc;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    api::v1::{
+        mappers::WebauthnCredentialMapper,
+        models::{WebauthnCredentialCreateModel, WebauthnCredentialModel},
+    },
+    db::{
+        pg::models::PgWebauthnCredential,
+        repositories::{QueryFailure, RepositoryError, WebauthnCredentialRepository},
+        sqlite::{schema::webauthn_credentials, sql_types::UuidValue},
+        DbContext,
+    },
+};
+
+pub struct SqliteWebauthnCredentialRepository;
+
+#[async_trait]
+impl WebauthnCredentialRepository for SqliteWebauthnCredentialRepository {
+    async fn create(
+        &self,
+        db_context: &Arc<DbContext>,
+        credential_create: &WebauthnCredentialCreateModel,
+    ) -> Result<WebauthnCredentialModel, RepositoryError> {
+        tracing::trace!(method = "create", ?credential_create);
+
+        let query = diesel::insert_into(webauthn_credentials::table).values((
+            webauthn_credentials::user_id.eq(UuidValue(credential_create.user_id)),
+            webauthn_credentials::credential_id.eq(credential_create.credential_id.to_owned()),
+            webauthn_credentials::public_key.eq(credential_create.public_key.to_owned()),
+            webauthn_credentials::sign_count.eq(credential_create.sign_count),
+            webauthn_credentials::created_at.eq(Utc::now().naive_utc()),
+        ));
+
+        let pg_credential = db_context
+            .with_sqlite_connection(move |conn| query.get_result::<PgWebauthnCredential>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_create)?;
+
+        Ok(WebauthnCredentialMapper::from_pg(pg_credential))
+    }
+
+    async fn get_by_credential_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        credential_id: &str,
+    ) -> Result<WebauthnCredentialModel, RepositoryError> {
+        tracing::trace!(method = "get_by_credential_id", credential_id);
+
+        let query = webauthn_credentials::table
+            .filter(webauthn_credentials::credential_id.eq(credential_id.to_owned()));
+
+        let pg_credential = db_context
+            .with_sqlite_connection(move |conn| query.first::<PgWebauthnCredential>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(WebauthnCredentialMapper::from_pg(pg_credential))
+    }
+
+    async fn get_all_by_user_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+    ) -> Result<Vec<WebauthnCredentialModel>, RepositoryError> {
+        tracing::trace!(method = "get_all_by_user_id", ?user_id);
+
+        let query = webauthn_credentials::table
+            .filter(webauthn_credentials::user_id.eq(UuidValue(*user_id)))
+            .order(webauthn_credentials::id.asc());
+
+        let pg_credentials = db_context
+            .with_sqlite_connection(move |conn| query.load::<PgWebauthnCredential>(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_found)?;
+
+        Ok(pg_credentials
+            .into_iter()
+            .map(WebauthnCredentialMapper::from_pg)
+            .collect())
+    }
+
+    async fn use_by_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        id: i32,
+        previous_sign_count: i64,
+        sign_count: i64,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(method = "use_by_id", id, previous_sign_count, sign_count);
+
+        let query = diesel::update(webauthn_credentials::table)
+            .filter(webauthn_credentials::id.eq(id))
+            .filter(webauthn_credentials::sign_count.eq(previous_sign_count))
+            .set((
+                webauthn_credentials::sign_count.eq(sign_count),
+                webauthn_credentials::last_used_at.eq(Utc::now().naive_utc()),
+            ));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_update)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by update, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotUpdated));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_by_user_id_and_credential_id(
+        &self,
+        db_context: &Arc<DbContext>,
+        user_id: &Uuid,
+        credential_id: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::trace!(
+            method = "delete_by_user_id_and_credential_id",
+            ?user_id,
+            credential_id
+        );
+
+        let query = diesel::delete(webauthn_credentials::table)
+            .filter(webauthn_credentials::user_id.eq(UuidValue(*user_id)))
+            .filter(webauthn_credentials::credential_id.eq(credential_id.to_owned()));
+
+        let affected_rows = db_context
+            .with_sqlite_connection(move |conn| query.execute(conn))
+            .await
+            .map_err(RepositoryError::from)?
+            .map_err(RepositoryError::map_diesel_delete)?;
+
+        if affected_rows != 1 {
+            let msg = format!(
+                "Expected 1 row to be affected by delete, but found {}",
+                affected_rows
+            );
+
+            tracing::error!(error = msg);
+            return Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,417 @@
+// the sqlite counterpart of `pg::schema`, kept by hand as the diesel cli can not print the
+// custom text types
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    access_tokens (id) {
+        id -> Integer,
+        token -> Text,
+        client_id -> Text,
+        user_id -> Nullable<TextUuid>,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        scopes -> TextList,
+        authorization_details -> TextJson,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    allowed_scopes (client_id, scope) {
+        client_id -> Text,
+        scope -> Text,
+        is_default -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    authorization_detail_types (client_id, name) {
+        name -> Text,
+        client_id -> Text,
+        description -> Text,
+        schema -> TextJson,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    authorization_codes (id) {
+        id -> Integer,
+        code -> Text,
+        challenge -> Text,
+        is_challenge_plain -> Bool,
+        client_id -> Text,
+        user_id -> TextUuid,
+        redirect_uri -> Text,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        used -> Bool,
+        scopes -> TextList,
+        authorization_details -> TextJson,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    backchannel_authorizations (id) {
+        id -> Integer,
+        auth_req_id -> Text,
+        client_id -> Text,
+        user_id -> TextUuid,
+        binding_message -> Nullable<Text>,
+        delivery_mode -> Text,
+        client_notification_token -> Nullable<Text>,
+        client_notification_endpoint -> Nullable<Text>,
+        status -> Text,
+        poll_interval -> Integer,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        last_polled_at -> Nullable<Timestamp>,
+        scopes -> TextList,
+        authorization_details -> TextJson,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    client_policies (client_id) {
+        client_id -> Text,
+        grant_types -> TextList,
+        access_token_lifetime -> Integer,
+        refresh_token_lifetime -> Integer,
+        id_token_lifetime -> Integer,
+        absolute_refresh_lifetime -> Nullable<Integer>,
+        sliding_refresh_window -> Nullable<Integer>,
+        always_issue_refresh_token -> Bool,
+        backchannel_client_notification_endpoint -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    client_secrets (id) {
+        id -> Integer,
+        client_id -> Text,
+        salt -> Text,
+        secret_hash -> Text,
+        created_at -> Timestamp,
+        expires_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    clients (id) {
+        id -> Text,
+        user_id -> TextUuid,
+        is_public -> Bool,
+        name -> Text,
+        description -> Text,
+        homepage_url -> Text,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    consents (id) {
+        id -> Integer,
+        user_id -> TextUuid,
+        client_id -> Text,
+        scopes -> TextList,
+        granted_at -> Timestamp,
+        expires_at -> Nullable<Timestamp>,
+        authorization_details -> TextJson,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    device_authorizations (id) {
+        id -> Integer,
+        client_id -> Text,
+        user_code -> Text,
+        device_code -> Text,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        scopes -> TextList,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    redirect_uris (id) {
+        id -> TextUuid,
+        client_id -> Text,
+        uri -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        match_mode -> Text,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    refresh_tokens (id) {
+        id -> Integer,
+        access_token_id -> Integer,
+        token -> Text,
+        client_id -> Text,
+        user_id -> Nullable<TextUuid>,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        used -> Bool,
+        scopes -> TextList,
+        authorization_details -> TextJson,
+        grant_created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    scopes (id) {
+        id -> Integer,
+        name -> Text,
+        description -> Text,
+        client_id -> Nullable<Text>,
+        consent_text -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    users (id) {
+        id -> TextUuid,
+        email -> Text,
+        password_hash -> Text,
+        email_verified -> Bool,
+        password_version -> Integer,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    sessions (user_id, id) {
+        id -> Text,
+        user_id -> TextUuid,
+        expires_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    session_tokens (token) {
+        token -> Text,
+        user_id -> TextUuid,
+        expires_at -> BigInt,
+        mfa_pending -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    totp_secrets (user_id) {
+        user_id -> TextUuid,
+        secret -> Text,
+        confirmed_at -> Nullable<Timestamp>,
+        last_used_step -> Nullable<BigInt>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    recovery_codes (id) {
+        id -> Integer,
+        user_id -> TextUuid,
+        code_hash -> Text,
+        created_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    webauthn_credentials (id) {
+        id -> Integer,
+        user_id -> TextUuid,
+        credential_id -> Text,
+        public_key -> Binary,
+        sign_count -> BigInt,
+        created_at -> Timestamp,
+        last_used_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    webauthn_challenges (id) {
+        id -> Text,
+        challenge -> Text,
+        user_id -> Nullable<TextUuid>,
+        ceremony -> Text,
+        expires_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    password_reset_tokens (token) {
+        token -> Text,
+        user_id -> TextUuid,
+        email -> Text,
+        password_version -> Integer,
+        expires_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    login_attempts (key) {
+        key -> Text,
+        failures -> BigInt,
+        locked_until -> Nullable<BigInt>,
+        expires_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    magic_links (email) {
+        email -> Text,
+        user_id -> TextUuid,
+        nonce_digest -> Text,
+        code_digest -> Text,
+        binding_digest -> Text,
+        failed_attempts -> BigInt,
+        expires_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::db::sqlite::sql_types::*;
+
+    federated_identities (id) {
+        id -> Integer,
+        user_id -> TextUuid,
+        provider -> Text,
+        subject -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    federation_states (state) {
+        state -> Text,
+        provider -> Text,
+        nonce -> Text,
+        code_verifier -> Text,
+        binding_digest -> Text,
+        expires_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    pushed_authorization_requests (request_uri) {
+        request_uri -> Text,
+        request -> Text,
+        expires_at -> BigInt,
+    }
+}
+
+diesel::joinable!(access_tokens -> clients (client_id));
+diesel::joinable!(access_tokens -> users (user_id));
+diesel::joinable!(allowed_scopes -> clients (client_id));
+diesel::joinable!(authorization_detail_types -> clients (client_id));
+diesel::joinable!(authorization_codes -> clients (client_id));
+diesel::joinable!(authorization_codes -> users (user_id));
+diesel::joinable!(backchannel_authorizations -> clients (client_id));
+diesel::joinable!(backchannel_authorizations -> users (user_id));
+diesel::joinable!(client_policies -> clients (client_id));
+diesel::joinable!(client_secrets -> clients (client_id));
+diesel::joinable!(clients -> users (user_id));
+diesel::joinable!(consents -> clients (client_id));
+diesel::joinable!(consents -> users (user_id));
+diesel::joinable!(device_authorizations -> clients (client_id));
+diesel::joinable!(federated_identities -> users (user_id));
+diesel::joinable!(redirect_uris -> clients (client_id));
+diesel::joinable!(recovery_codes -> users (user_id));
+diesel::joinable!(refresh_tokens -> access_tokens (access_token_id));
+diesel::joinable!(refresh_tokens -> clients (client_id));
+diesel::joinable!(refresh_tokens -> users (user_id));
+diesel::joinable!(scopes -> clients (client_id));
+diesel::joinable!(totp_secrets -> users (user_id));
+diesel::joinable!(webauthn_credentials -> users (user_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    access_tokens,
+    allowed_scopes,
+    authorization_codes,
+    authorization_detail_types,
+    backchannel_authorizations,
+    client_policies,
+    client_secrets,
+    clients,
+    consents,
+    device_authorizations,
+    federated_identities,
+    federation_states,
+    login_attempts,
+    magic_links,
+    password_reset_tokens,
+    pushed_authorization_requests,
+    recovery_codes,
+    redirect_uris,
+    refresh_tokens,
+    scopes,
+    session_tokens,
+    sessions,
+    totp_secrets,
+    users,
+    webauthn_challenges,
+    webauthn_credentials,
+);
@@ -0,0 +1,94 @@
+//! sqlite has no uuid, array or json columns, so those are stored as text. The sql types here let
+//! the pg models load straight from the sqlite tables, so the sqlite repositories can share the
+//! pg mappers, and the value types bind the matching text when writing.
+
+use diesel::{
+    deserialize::{self, FromSql},
+    expression::AsExpression,
+    query_builder::QueryId,
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types::{SqlType, Text},
+    sqlite::{Sqlite, SqliteValue},
+};
+use uuid::Uuid;
+
+/// a uuid stored as its hyphenated text
+#[derive(Clone, Copy, Debug, Default, QueryId, SqlType)]
+#[diesel(sqlite_type(name = "Text"))]
+pub struct TextUuid;
+
+/// a list of strings stored as a json array, in place of a pg `TEXT[]`
+#[derive(Clone, Copy, Debug, Default, QueryId, SqlType)]
+#[diesel(sqlite_type(name = "Text"))]
+pub struct TextList;
+
+/// a json document stored as text, in place of a pg `JSONB`
+#[derive(Clone, Copy, Debug, Default, QueryId, SqlType)]
+#[diesel(sqlite_type(name = "Text"))]
+pub struct TextJson;
+
+impl FromSql<TextUuid, Sqlite> for Uuid {
+    fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        let text = <String as FromSql<Text, Sqlite>>::from_sql(value)?;
+
+        Ok(Uuid::parse_str(text.as_str())?)
+    }
+}
+
+impl FromSql<TextList, Sqlite> for Vec<Option<String>> {
+    fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        let text = <String as FromSql<Text, Sqlite>>::from_sql(value)?;
+
+        Ok(serde_json::from_str(text.as_str())?)
+    }
+}
+
+impl FromSql<TextJson, Sqlite> for serde_json::Value {
+    fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        let text = <String as FromSql<Text, Sqlite>>::from_sql(value)?;
+
+        Ok(serde_json::from_str(text.as_str())?)
+    }
+}
+
+#[derive(Debug, AsExpression)]
+#[diesel(sql_type = TextUuid)]
+pub struct UuidValue(pub Uuid);
+
+impl ToSql<TextUuid, Sqlite> for UuidValue {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(self.0.hyphenated().to_string());
+
+        Ok(IsNull::No)
+    }
+}
+
+#[derive(Debug, AsExpression)]
+#[diesel(sql_type = TextList)]
+pub struct ListValue(pub Vec<String>);
+
+impl ListValue {
+    pub fn new(values: &[String]) -> Self {
+        Self(values.to_vec())
+    }
+}
+
+impl ToSql<TextList, Sqlite> for ListValue {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(serde_json::to_string(&self.0)?);
+
+        Ok(IsNull::No)
+    }
+}
+
+#[derive(Debug, AsExpression)]
+#[diesel(sql_type = TextJson)]
+pub struct JsonValue(pub serde_json::Value);
+
+impl ToSql<TextJson, Sqlite> for JsonValue {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(serde_json::to_string(&self.0)?);
+
+        Ok(IsNull::No)
+    }
+}
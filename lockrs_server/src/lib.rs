@@ -7,23 +7,30 @@ pub mod oauth2;
 mod middlewares;
 mod routes;
 
-use axum::{routing::IntoMakeService, Router};
+use axum::{extract::connect_info::IntoMakeServiceWithConnectInfo, Router};
 use hyper::{server::conn::AddrIncoming, Server};
-use std::net::TcpListener;
+use std::net::{SocketAddr, TcpListener};
 
 pub use self::common::*;
-pub type AppServer = Server<AddrIncoming, IntoMakeService<Router>>;
+pub type AppServer = Server<AddrIncoming, IntoMakeServiceWithConnectInfo<Router, SocketAddr>>;
 
 pub async fn run(
     listener: TcpListener,
     state: Option<AppState>,
 ) -> Result<AppServer, hyper::Error> {
-    let state = state.unwrap_or(AppState::new(None).await);
+    // only build the default state when none is given, so an in-memory state never needs the
+    // database environment
+    let state = match state {
+        Some(state) => state,
+        None => AppState::new(None).await,
+    };
 
     let app = routes::routes(&state).with_state(state);
     let app = middlewares::with_middleware_stack(app);
 
-    let server = axum::Server::from_tcp(listener)?.serve(app.into_make_service());
+    // the peer address is what failed logins are counted against, besides the account
+    let server = axum::Server::from_tcp(listener)?
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>());
 
     Ok(server)
 }
@@ -1,14 +1,64 @@
-use std::net::TcpListener;
+use std::{env, net::TcpListener, process};
 
-use lockrs_server::run;
+use lockrs_server::{oauth2::v1::services::ReaperService, run, AppState};
+use metrics_exporter_prometheus::PrometheusBuilder;
 
 /// rfc: https://www.rfc-editor.org/rfc/rfc6749#section-4
 #[tokio::main]
 async fn main() {
+    match env::args().nth(1).as_deref() {
+        Some("gc") => gc().await,
+        _ => serve().await,
+    }
+}
+
+async fn serve() {
     let listener = TcpListener::bind("127.0.0.1:9000").expect("Failed to bind to port");
     let addr = listener.local_addr().unwrap();
     tracing::info!("listening at {}", addr);
     println!("listening at {}", addr);
-    let app = run(listener, None).await.expect("Failed to bind address.");
+
+    // scraped by prometheus, e.g. for the rows the reaper purges and the cache hit rate
+    PrometheusBuilder::new()
+        .with_http_listener(([127, 0, 0, 1], 9001))
+        .install()
+        .expect("Failed to install the metrics exporter");
+
+    let state = AppState::new(None).await;
+    ReaperService::spawn(state.clone());
+
+    let app = run(listener, Some(state))
+        .await
+        .expect("Failed to bind address.");
     app.await;
 }
+
+/// runs a single pass of the expired token reaper, e.g. from a cron job. No metrics exporter is
+/// installed for a single pass, so the counts are printed instead
+async fn gc() {
+    let state = AppState::new(None).await;
+    let repository_container = state.repository_container.as_ref();
+
+    let report = ReaperService::purge_expired(
+        &state.db_context,
+        &*repository_container.access_token_repository,
+        &*repository_container.refresh_token_repository,
+        &*repository_container.authorization_code_repository,
+        &*repository_container.device_authorization_repository,
+        state.config.gc_batch_size,
+        &state.config.gc_retention,
+    )
+    .await
+    .unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+
+    println!(
+        "purged {} access tokens, {} refresh tokens, {} authorization codes and {} device authorizations",
+        report.access_tokens,
+        report.refresh_tokens,
+        report.authorization_codes,
+        report.device_authorizations
+    );
+}
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRef, FromRequestParts},
+    http::{request::Parts, StatusCode},
+};
+use ring::constant_time;
+
+use crate::{utils::extractors::BearerAuth, AppState};
+
+pub struct AdminAuthGuard;
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminAuthGuard
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        tracing::trace!(method = "from_request_parts",);
+
+        // without a key configured, the admin endpoints don't exist as far as anyone can tell
+        let app_state = AppState::from_ref(state);
+        let Some(admin_api_key) = app_state.config.admin_api_key.as_ref()
+        else {
+            tracing::debug!("admin api key not configured");
+            return Err(StatusCode::NOT_FOUND);
+        };
+
+        let BearerAuth(token) = BearerAuth::from_request_parts(&mut *parts, state)
+            .await
+            .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+        constant_time::verify_slices_are_equal(token.as_bytes(), admin_api_key.as_bytes())
+            .map_err(|_| {
+                tracing::debug!("admin api key does not match");
+                StatusCode::UNAUTHORIZED
+            })?;
+
+        Ok(Self)
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use axum::{
     extract::{FromRef, FromRequestParts, Path},
@@ -41,24 +43,27 @@ where
         })?;
 
         // validate user/permissions
-        let Path(path_client_id) = Path::<(String,)>::from_request_parts(&mut *parts, state)
-            .await
-            .map_err(|err| {
-                tracing::debug!("bad path wildcard: {:?}", err);
-                StatusCode::NOT_FOUND
-            })?;
+        let Path(path_params) =
+            Path::<HashMap<String, String>>::from_request_parts(&mut *parts, state)
+                .await
+                .map_err(|err| {
+                    tracing::debug!("bad path wildcard: {:?}", err);
+                    StatusCode::NOT_FOUND
+                })?;
+
+        let Some(path_client_id) = path_params.get("client_id") else {
+            tracing::debug!("missing client_id in path");
+            return Err(StatusCode::NOT_FOUND);
+        };
 
         let client_repository = &*app_state.repository_container.as_ref().client_repository;
-        let client = ClientService::get_client_by_id(
-            db_context,
-            client_repository,
-            path_client_id.0.as_str(),
-        )
-        .await
-        .map_err(|err| {
-            tracing::debug!("client not found: {:?}", err);
-            StatusCode::NOT_FOUND
-        })?;
+        let client =
+            ClientService::get_client_by_id(db_context, client_repository, path_client_id.as_str())
+                .await
+                .map_err(|err| {
+                    tracing::debug!("client not found: {:?}", err);
+                    StatusCode::NOT_FOUND
+                })?;
 
         if session.user_id != client.user_id {
             tracing::debug!("user in auth does not match user in client");
@@ -1,8 +1,10 @@
+mod admin_auth_guard;
 mod client_auth_guard;
 mod redirect_auth_guard;
 mod session_auth_guard;
 mod user_auth_guard;
 
 pub use self::{
-    client_auth_guard::*, redirect_auth_guard::*, session_auth_guard::*, user_auth_guard::*,
+    admin_auth_guard::*, client_auth_guard::*, redirect_auth_guard::*, session_auth_guard::*,
+    user_auth_guard::*,
 };
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use axum::{
     extract::{FromRef, FromRequestParts, Path},
@@ -39,14 +41,23 @@ where
         })?;
 
         // validate user/permissions
-        let Path(path_user_id) = Path::<(Uuid,)>::from_request_parts(&mut *parts, state)
-            .await
-            .map_err(|err| {
-                tracing::debug!("bad path wildcard: {:?}", err);
-                StatusCode::NOT_FOUND
-            })?;
-
-        if session.user_id != path_user_id.0 {
+        let Path(path_params) =
+            Path::<HashMap<String, String>>::from_request_parts(&mut *parts, state)
+                .await
+                .map_err(|err| {
+                    tracing::debug!("bad path wildcard: {:?}", err);
+                    StatusCode::NOT_FOUND
+                })?;
+
+        let Some(path_user_id) = path_params
+            .get("user_id")
+            .and_then(|user_id| Uuid::parse_str(user_id).ok())
+        else {
+            tracing::debug!("missing or invalid user_id in path");
+            return Err(StatusCode::NOT_FOUND);
+        };
+
+        if session.user_id != path_user_id {
             tracing::debug!("user in auth does not match user in path");
             return Err(StatusCode::UNAUTHORIZED);
         }
@@ -1,25 +1,42 @@
 use axum::{
     extract::{Query, State},
     http::StatusCode,
-    response::{IntoResponse, Redirect},
+    response::{IntoResponse, Redirect, Response},
 };
 use serde::Deserialize;
 use url::Url;
 
 use crate::{
-    oauth2::v1::services::{ScopeService, ScopeServiceError},
+    api::v1::services::{
+        EmailVerificationService, EmailVerificationServiceError, SessionService,
+        SessionServiceError,
+    },
+    oauth2::v1::{
+        models::{AuthorizationRequestModel, ScopeModel},
+        responses::{ConsentRequiredResponse, PushedAuthorizationResponse},
+        services::{
+            AuthorizationCodeService, AuthorizationCodeServiceError, AuthorizationDetailService,
+            AuthorizationDetailServiceError, AuthorizationRequestService,
+            AuthorizationRequestServiceError, ConsentService, ConsentServiceError, ScopeService,
+            ScopeServiceError,
+        },
+    },
     services::{ClientAuthService, ClientAuthServiceError, RedirectService, RedirectServiceError},
-    utils::extractors::ExtractClientCredentials,
+    utils::extractors::{ExtractClientCredentials, SessionJwt},
     AppState,
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct AuthorizeRequest {
-    pub response_type: String,
-    pub redirect_uri: Url,
-    pub code_challenge: String,
-    pub code_challenge_method: String,
-    pub scope: String,
+    pub response_type: Option<String>,
+    pub redirect_uri: Option<Url>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub scope: Option<String>,
+    pub authorization_details: Option<String>,
+    pub state: Option<String>,
+    /// stands in for every other parameter with a request the client pushed beforehand
+    pub request_uri: Option<String>,
 }
 
 pub struct AuthorizeController;
@@ -28,16 +45,158 @@ impl AuthorizeController {
     pub async fn handle(
         State(state): State<AppState>,
         ExtractClientCredentials(client_credentials): ExtractClientCredentials,
+        session: Option<SessionJwt>,
         Query(params): Query<AuthorizeRequest>,
-    ) -> impl IntoResponse {
+    ) -> Result<Response, AuthorizeControllerError> {
         tracing::trace!(
             method = "handle",
             params = ?params
         );
 
-        if &params.response_type != "code" {
-            tracing::error!(error = "Invalid Response Type Requested!");
-            return Err(AuthorizeControllerError::InvalidResponseType);
+        let db_context = &state.db_context;
+        let pushed_authorization_request_repository = &*state
+            .repository_container
+            .as_ref()
+            .pushed_authorization_request_repository;
+
+        let authorization_request = match params.request_uri.as_deref() {
+            // the client authenticated when it pushed the request, so its id is all that is
+            // needed to tie the two together
+            Some(request_uri) => AuthorizationRequestService::get(
+                db_context,
+                pushed_authorization_request_repository,
+                &client_credentials.id,
+                request_uri,
+            )
+            .await
+            .map_err(AuthorizeControllerError::from)?,
+            None => {
+                let client_auth_repository =
+                    &*state.repository_container.as_ref().client_auth_repository;
+
+                let client = ClientAuthService::authenticate(
+                    db_context,
+                    client_auth_repository,
+                    &client_credentials.id,
+                    client_credentials.secret.as_deref(),
+                )
+                .await
+                .map_err(AuthorizeControllerError::from)?;
+
+                Self::validate_request(&state, &client.id, params.clone()).await?
+            }
+        };
+
+        let Some(SessionJwt(session)) = session
+        else {
+            tracing::error!(error = "Authorization requested without a user session");
+            return Err(AuthorizeControllerError::LoginRequired);
+        };
+
+        let session_repository = &*state.repository_container.as_ref().session_repository;
+        SessionService::get_session(
+            db_context,
+            session_repository,
+            &session.user_id,
+            session.id.as_str(),
+        )
+        .await
+        .map_err(AuthorizeControllerError::from)?;
+
+        if state.config.email_verification.is_required_for_consent() {
+            let user_repository = &*state.repository_container.as_ref().user_repository;
+            EmailVerificationService::ensure_verified(
+                db_context,
+                user_repository,
+                &session.user_id,
+            )
+            .await
+            .map_err(AuthorizeControllerError::from)?;
+        }
+
+        // the consent prompt is only shown when the user has not already granted every scope and
+        // authorization detail
+        let consent_repository = &*state.repository_container.as_ref().consent_repository;
+        let is_granted = ConsentService::is_granted(
+            db_context,
+            consent_repository,
+            &session.user_id,
+            &authorization_request.client_id,
+            &ScopeModel::new(&authorization_request.scopes),
+            &authorization_request.authorization_details,
+        )
+        .await
+        .map_err(AuthorizeControllerError::from)?;
+
+        if !is_granted {
+            tracing::debug!("Consent required for requested scopes and authorization details");
+            return Ok(ConsentRequiredResponse::new(
+                &authorization_request.client_id,
+                &authorization_request.scopes,
+                &authorization_request.authorization_details,
+                params.request_uri.as_deref(),
+            )
+            .into_response());
+        }
+
+        // a pushed request is spent once a code is issued for it
+        if let Some(request_uri) = params.request_uri.as_deref() {
+            AuthorizationRequestService::take(
+                db_context,
+                pushed_authorization_request_repository,
+                &authorization_request.client_id,
+                request_uri,
+            )
+            .await
+            .map_err(AuthorizeControllerError::from)?;
+        }
+
+        let authorization_code_repository = &*state
+            .repository_container
+            .as_ref()
+            .authorization_code_repository;
+        let authorization_code = AuthorizationCodeService::create(
+            db_context,
+            authorization_code_repository,
+            &authorization_request.client_id,
+            &session.user_id,
+            authorization_request.code_challenge.as_str(),
+            authorization_request.is_challenge_plain,
+            &authorization_request.redirect_uri,
+            ScopeModel::new(&authorization_request.scopes),
+            &authorization_request.authorization_details,
+        )
+        .await
+        .map_err(AuthorizeControllerError::from)?;
+
+        let mut redirect_uri = authorization_request.redirect_uri;
+        redirect_uri
+            .query_pairs_mut()
+            .append_pair("code", authorization_code.code.as_str());
+
+        if let Some(client_state) = authorization_request.state.as_deref() {
+            redirect_uri
+                .query_pairs_mut()
+                .append_pair("state", client_state);
+        }
+
+        Ok(Redirect::to(redirect_uri.as_str()).into_response())
+    }
+
+    /// rfc: https://www.rfc-editor.org/rfc/rfc9126
+    pub async fn push(
+        State(state): State<AppState>,
+        ExtractClientCredentials(client_credentials): ExtractClientCredentials,
+        Query(params): Query<AuthorizeRequest>,
+    ) -> Result<PushedAuthorizationResponse, AuthorizeControllerError> {
+        tracing::trace!(
+            method = "push",
+            params = ?params
+        );
+
+        if params.request_uri.is_some() {
+            tracing::error!(error = "Request uri sent to the pushed authorization endpoint");
+            return Err(AuthorizeControllerError::InvalidRequestUri);
         }
 
         let db_context = &state.db_context;
@@ -52,27 +211,107 @@ impl AuthorizeController {
         .await
         .map_err(AuthorizeControllerError::from)?;
 
-        // validate redirect uri, inform the user of the problem instead of redirecting
-        let redirect_repository = &*state.repository_container.as_ref().redirect_repository;
-        RedirectService::verify_redirect(
+        let authorization_request = Self::validate_request(&state, &client.id, params).await?;
+
+        let pushed_authorization_request_repository = &*state
+            .repository_container
+            .as_ref()
+            .pushed_authorization_request_repository;
+        let (request_uri, expires_in) = AuthorizationRequestService::push(
             db_context,
-            redirect_repository,
-            &client.id,
-            &params.redirect_uri,
+            pushed_authorization_request_repository,
+            &authorization_request,
         )
         .await
         .map_err(AuthorizeControllerError::from)?;
 
-        let scope_repository = &*state.repository_container.as_ref().scope_repository;
-        let _scopes = ScopeService::get_from_list(db_context, scope_repository, &params.scope)
+        Ok(PushedAuthorizationResponse {
+            request_uri,
+            expires_in,
+        })
+    }
+
+    /// Checks every parameter of a request sent straight to `/authorize` or pushed ahead of it.
+    async fn validate_request(
+        state: &AppState,
+        client_id: &str,
+        params: AuthorizeRequest,
+    ) -> Result<AuthorizationRequestModel, AuthorizeControllerError> {
+        if params.response_type.as_deref() != Some("code") {
+            tracing::error!(error = "Invalid Response Type Requested!");
+            return Err(AuthorizeControllerError::InvalidResponseType);
+        }
+
+        let is_challenge_plain = match params.code_challenge_method.as_deref() {
+            Some("S256") => false,
+            Some("plain") => true,
+            _ => {
+                tracing::error!(error = "Invalid Code Challenge Method Requested!");
+                return Err(AuthorizeControllerError::InvalidCodeChallengeMethod);
+            }
+        };
+
+        // rfc: https://www.rfc-editor.org/rfc/rfc7636#section-4.2
+        let Some(code_challenge) = params
+            .code_challenge
+            .filter(|code_challenge| (43..=128).contains(&code_challenge.len()))
+        else {
+            tracing::error!(error = "Code challenge is missing or has an invalid length");
+            return Err(AuthorizeControllerError::InvalidCodeChallenge);
+        };
+
+        let Some(redirect_uri) = params.redirect_uri
+        else {
+            tracing::error!(error = "Authorization requested without a redirect uri");
+            return Err(AuthorizeControllerError::InvalidRedirectUri);
+        };
+
+        let db_context = &state.db_context;
+
+        // validate redirect uri, inform the user of the problem instead of redirecting
+        let redirect_repository = &*state.repository_container.as_ref().redirect_repository;
+        RedirectService::verify_redirect(db_context, redirect_repository, client_id, &redirect_uri)
             .await
             .map_err(AuthorizeControllerError::from)?;
 
-        let _is_plain = !params.code_challenge_method.eq("S256");
+        let scope_repository = &*state.repository_container.as_ref().scope_repository;
+        let scopes = ScopeService::get_for_client(
+            db_context,
+            scope_repository,
+            client_id,
+            params.scope.as_deref(),
+        )
+        .await
+        .map_err(AuthorizeControllerError::from)?;
+
+        let authorization_details = match params.authorization_details.as_deref() {
+            Some(authorization_details) => {
+                let authorization_detail_type_repository = &*state
+                    .repository_container
+                    .as_ref()
+                    .authorization_detail_type_repository;
 
-        // stash data before redirect
+                AuthorizationDetailService::get_from_json(
+                    db_context,
+                    authorization_detail_type_repository,
+                    client_id,
+                    authorization_details,
+                )
+                .await
+                .map_err(AuthorizeControllerError::from)?
+            }
+            None => Vec::new(),
+        };
 
-        Ok(Redirect::to(params.redirect_uri.as_str()))
+        Ok(AuthorizationRequestModel::new(
+            client_id,
+            &redirect_uri,
+            code_challenge.as_str(),
+            is_challenge_plain,
+            &scopes,
+            &authorization_details,
+            params.state.as_deref(),
+        ))
     }
 }
 
@@ -81,7 +320,12 @@ pub enum AuthorizeControllerError {
     InvalidClient,
     InvalidRedirectUri,
     InvalidScopes,
+    InvalidAuthorizationDetails,
     InvalidCodeChallengeMethod,
+    InvalidCodeChallenge,
+    InvalidRequestUri,
+    LoginRequired,
+    EmailNotVerified,
 
     InternalError,
 }
@@ -89,6 +333,9 @@ pub enum AuthorizeControllerError {
 impl AuthorizeControllerError {
     pub fn error_code(&self) -> StatusCode {
         match self {
+            Self::LoginRequired => StatusCode::UNAUTHORIZED,
+            Self::EmailNotVerified => StatusCode::FORBIDDEN,
+
             Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
 
             _ => StatusCode::BAD_REQUEST,
@@ -100,8 +347,13 @@ impl AuthorizeControllerError {
             Self::InvalidResponseType => "The requested response type is invalid. Only the \"code\" response type is supported on this server.",
             Self::InvalidClient => "The provided client credentials are invalid.",
             Self::InvalidRedirectUri => "The provided redirect uri is not recognized by the server for the provided client.",
-            Self::InvalidScopes => "The provided scopes are invalid.",
+            Self::InvalidScopes => "The requested scope is invalid, unknown, or not allowed for the client.",
+            Self::InvalidAuthorizationDetails => "The provided authorization_details are invalid.",
             Self::InvalidCodeChallengeMethod => "The provided code challenge method is unsupported. Only \"plain\" or \"S256\" code challenge methods are supported by this server",
+            Self::InvalidCodeChallenge => "The provided code challenge is invalid. It must be between 43 and 128 characters long.",
+            Self::InvalidRequestUri => "The provided request_uri is invalid, expired, or was not pushed by the client.",
+            Self::LoginRequired => "The user must be logged in to authorize the client.",
+            Self::EmailNotVerified => "The user must verify their email address before the client can be authorized.",
 
             Self::InternalError => "An error occurred processing your request. Please try again later.",
         }
@@ -141,6 +393,68 @@ impl From<ScopeServiceError> for AuthorizeControllerError {
     }
 }
 
+impl From<AuthorizationDetailServiceError> for AuthorizeControllerError {
+    fn from(err: AuthorizationDetailServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            AuthorizationDetailServiceError::InvalidAuthorizationDetails
+            | AuthorizationDetailServiceError::UnknownType => Self::InvalidAuthorizationDetails,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<AuthorizationCodeServiceError> for AuthorizeControllerError {
+    fn from(err: AuthorizationCodeServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<AuthorizationRequestServiceError> for AuthorizeControllerError {
+    fn from(err: AuthorizationRequestServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            AuthorizationRequestServiceError::InvalidRequestUri => Self::InvalidRequestUri,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<SessionServiceError> for AuthorizeControllerError {
+    fn from(err: SessionServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            SessionServiceError::NotFound => Self::LoginRequired,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<EmailVerificationServiceError> for AuthorizeControllerError {
+    fn from(err: EmailVerificationServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            EmailVerificationServiceError::NotVerified => Self::EmailNotVerified,
+            EmailVerificationServiceError::NotFound => Self::LoginRequired,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<ConsentServiceError> for AuthorizeControllerError {
+    fn from(err: ConsentServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
 impl IntoResponse for AuthorizeControllerError {
     fn into_response(self) -> axum::response::Response {
         (self.error_code(), self.error_message()).into_response()
@@ -0,0 +1,280 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+
+use crate::{
+    oauth2::v1::{
+        models::BackchannelDeliveryMode,
+        notifiers::AuthenticationDeviceNotifierError,
+        responses::BackchannelAuthenticationResponse,
+        services::{
+            AuthorizationDetailService, AuthorizationDetailServiceError,
+            BackchannelAuthorizationService, BackchannelAuthorizationServiceError, ScopeService,
+            ScopeServiceError,
+        },
+    },
+    services::{
+        ClientAuthService, ClientAuthServiceError, ClientPolicyService, ClientPolicyServiceError,
+        UserService,
+    },
+    utils::extractors::ExtractClientCredentials,
+    AppState,
+};
+
+#[derive(Deserialize)]
+pub struct BackchannelAuthenticationRequest {
+    pub scope: Option<String>,
+    pub login_hint: String,
+    pub binding_message: Option<String>,
+    pub authorization_details: Option<String>,
+
+    // ping and push delivery modes
+    pub backchannel_token_delivery_mode: Option<BackchannelDeliveryMode>,
+    pub client_notification_token: Option<String>,
+}
+
+impl std::fmt::Debug for BackchannelAuthenticationRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "BackchannelAuthenticationRequest: {{ {:?}, login_hint: ********, {:?}, {:?}, {:?}, client_notification_token: ******** }}",
+            self.scope,
+            self.binding_message,
+            self.authorization_details,
+            self.backchannel_token_delivery_mode,
+        )
+    }
+}
+
+pub struct BackchannelAuthenticationController;
+
+impl BackchannelAuthenticationController {
+    pub async fn handle(
+        State(state): State<AppState>,
+        ExtractClientCredentials(client_credentials): ExtractClientCredentials,
+        Query(params): Query<BackchannelAuthenticationRequest>,
+    ) -> Result<BackchannelAuthenticationResponse, BackchannelAuthenticationControllerError> {
+        tracing::trace!(
+            method = "handle",
+            params = ?params
+        );
+
+        let db_context = &state.db_context;
+        let client_auth_repository = &*state.repository_container.as_ref().client_auth_repository;
+
+        let client = ClientAuthService::authenticate(
+            db_context,
+            client_auth_repository,
+            &client_credentials.id,
+            client_credentials.secret.as_deref(),
+        )
+        .await
+        .map_err(BackchannelAuthenticationControllerError::from)?;
+
+        if client.is_public {
+            tracing::error!(error = "Public client attempted backchannel authentication");
+            return Err(BackchannelAuthenticationControllerError::InvalidClient);
+        }
+
+        let scope_repository = &*state.repository_container.as_ref().scope_repository;
+        let scopes = ScopeService::get_for_client(
+            db_context,
+            scope_repository,
+            &client.id,
+            params.scope.as_deref(),
+        )
+        .await
+        .map_err(BackchannelAuthenticationControllerError::from)?;
+
+        let authorization_details = match params.authorization_details.as_deref() {
+            Some(authorization_details) => {
+                let authorization_detail_type_repository = &*state
+                    .repository_container
+                    .as_ref()
+                    .authorization_detail_type_repository;
+
+                AuthorizationDetailService::get_from_json(
+                    db_context,
+                    authorization_detail_type_repository,
+                    &client.id,
+                    authorization_details,
+                )
+                .await
+                .map_err(BackchannelAuthenticationControllerError::from)?
+            }
+            None => Vec::new(),
+        };
+
+        let delivery_mode = params
+            .backchannel_token_delivery_mode
+            .unwrap_or(BackchannelDeliveryMode::Poll);
+
+        // notifications only ever go to the endpoint registered in the client's policy, which was
+        // checked to be a public https endpoint when it was set
+        let client_policy_repository =
+            &*state.repository_container.as_ref().client_policy_repository;
+        let client_policy =
+            ClientPolicyService::get_policy(db_context, client_policy_repository, &client.id)
+                .await
+                .map_err(BackchannelAuthenticationControllerError::from)?;
+
+        let user_repository = &*state.repository_container.as_ref().user_repository;
+        // the hint identifies a person, so it is kept out of the logs, and every way the lookup can
+        // fail reads the same to the client
+        let user = UserService::get_user_by_email(db_context, user_repository, &params.login_hint)
+            .await
+            .map_err(|err| {
+                tracing::debug!(error = %err, "Login hint did not resolve to a user");
+                BackchannelAuthenticationControllerError::UnknownUserId
+            })?;
+
+        let backchannel_authorization_repository = &*state
+            .repository_container
+            .as_ref()
+            .backchannel_authorization_repository;
+
+        let backchannel_authorization =
+            BackchannelAuthorizationService::create_backchannel_authorization(
+                db_context,
+                backchannel_authorization_repository,
+                &client.id,
+                &user.id,
+                params.binding_message.as_deref(),
+                delivery_mode,
+                params.client_notification_token.as_deref(),
+                client_policy
+                    .backchannel_client_notification_endpoint
+                    .as_deref(),
+                scopes,
+                &authorization_details,
+            )
+            .await
+            .map_err(BackchannelAuthenticationControllerError::from)?;
+
+        state
+            .authentication_device_notifier
+            .notify(&user, &backchannel_authorization)
+            .await
+            .map_err(BackchannelAuthenticationControllerError::from)?;
+
+        Ok(BackchannelAuthenticationResponse::new(
+            &backchannel_authorization,
+            BackchannelAuthorizationService::EXPIRES_IN_SECONDS,
+        ))
+    }
+}
+
+pub enum BackchannelAuthenticationControllerError {
+    InvalidClient,
+    InvalidScopes,
+    InvalidAuthorizationDetails,
+    InvalidBindingMessage,
+    InvalidClientNotification,
+    UnknownUserId,
+
+    BadRequest,
+    InternalError,
+}
+
+impl BackchannelAuthenticationControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidClient => StatusCode::UNAUTHORIZED,
+
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::InvalidClient => "The provided client credentials are invalid.",
+            Self::InvalidScopes => "The requested scope is invalid, unknown, or not allowed for the client.",
+            Self::InvalidAuthorizationDetails => "The provided authorization_details are invalid.",
+            Self::InvalidBindingMessage => "The provided binding_message is invalid.",
+            Self::InvalidClientNotification => "The \"ping\" and \"push\" delivery modes require a \"client_notification_token\" and a \"backchannel_client_notification_endpoint\" registered in the client's policy.",
+            Self::UnknownUserId => "unknown_user_id: The user could not be identified from the provided login_hint.",
+
+            Self::BadRequest => "Unable to perform the requested operation.",
+            Self::InternalError => "An error occurred processing your request. Please try again later.",
+        }
+    }
+}
+
+impl From<BackchannelAuthorizationServiceError> for BackchannelAuthenticationControllerError {
+    fn from(err: BackchannelAuthorizationServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            BackchannelAuthorizationServiceError::InvalidBindingMessage => {
+                Self::InvalidBindingMessage
+            }
+            BackchannelAuthorizationServiceError::MissingClientNotification => {
+                Self::InvalidClientNotification
+            }
+            BackchannelAuthorizationServiceError::NotCreated => Self::BadRequest,
+
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<AuthenticationDeviceNotifierError> for BackchannelAuthenticationControllerError {
+    fn from(err: AuthenticationDeviceNotifierError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<ClientAuthServiceError> for BackchannelAuthenticationControllerError {
+    fn from(err: ClientAuthServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            ClientAuthServiceError::NotFound => Self::InvalidClient,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<ClientPolicyServiceError> for BackchannelAuthenticationControllerError {
+    fn from(err: ClientPolicyServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<ScopeServiceError> for BackchannelAuthenticationControllerError {
+    fn from(err: ScopeServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            ScopeServiceError::InvalidScopes => Self::InvalidScopes,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<AuthorizationDetailServiceError> for BackchannelAuthenticationControllerError {
+    fn from(err: AuthorizationDetailServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            AuthorizationDetailServiceError::InvalidAuthorizationDetails
+            | AuthorizationDetailServiceError::UnknownType => Self::InvalidAuthorizationDetails,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for BackchannelAuthenticationControllerError {
+    fn into_response(self) -> axum::response::Response {
+        (self.error_code(), self.error_message()).into_response()
+    }
+}
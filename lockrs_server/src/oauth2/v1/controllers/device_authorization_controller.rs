@@ -20,7 +20,7 @@ use crate::{
 
 #[derive(Debug, Deserialize)]
 pub struct DeviceAuthorizationRequest {
-    scope: String,
+    scope: Option<String>,
 }
 
 pub struct DeviceAuthorizationController;
@@ -39,7 +39,7 @@ impl DeviceAuthorizationController {
         let db_context = &state.db_context;
         let client_auth_repository = &*state.repository_container.as_ref().client_auth_repository;
 
-        ClientAuthService::authenticate(
+        let client = ClientAuthService::authenticate(
             db_context,
             client_auth_repository,
             &client_credentials.id,
@@ -49,9 +49,14 @@ impl DeviceAuthorizationController {
         .map_err(DeviceAuthorizationControllerError::from)?;
 
         let scope_repository = &*state.repository_container.as_ref().scope_repository;
-        let scopes = ScopeService::get_from_list(db_context, scope_repository, &params.scope)
-            .await
-            .map_err(DeviceAuthorizationControllerError::from)?;
+        let scopes = ScopeService::get_for_client(
+            db_context,
+            scope_repository,
+            &client.id,
+            params.scope.as_deref(),
+        )
+        .await
+        .map_err(DeviceAuthorizationControllerError::from)?;
 
         let device_authorization_repository = &*state
             .repository_container
@@ -96,7 +101,9 @@ impl DeviceAuthorizationControllerError {
     pub fn error_message(&self) -> &'static str {
         match self {
             Self::InvalidClient => "The provided client credentials are invalid.",
-            Self::InvalidScopes => "The provided scopes are invalid.",
+            Self::InvalidScopes => {
+                "The requested scope is invalid, unknown, or not allowed for the client."
+            }
 
             Self::BadRequest => "Unable to perform the requested operation.",
             Self::InternalError => {
@@ -0,0 +1,129 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+
+use crate::{
+    oauth2::v1::{
+        responses::IntrospectionResponse,
+        services::{AccessTokenService, AccessTokenServiceError},
+    },
+    services::{ClientAuthService, ClientAuthServiceError},
+    utils::extractors::ExtractClientCredentials,
+    AppState,
+};
+
+#[derive(Deserialize)]
+pub struct IntrospectionRequest {
+    pub token: String,
+    pub token_type_hint: Option<String>,
+}
+
+impl std::fmt::Debug for IntrospectionRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "IntrospectionRequest: {{ token: ********, {:?} }}",
+            self.token_type_hint,
+        )
+    }
+}
+
+pub struct IntrospectionController;
+
+impl IntrospectionController {
+    pub async fn handle(
+        State(state): State<AppState>,
+        ExtractClientCredentials(client_credentials): ExtractClientCredentials,
+        Query(params): Query<IntrospectionRequest>,
+    ) -> Result<IntrospectionResponse, IntrospectionControllerError> {
+        tracing::trace!(
+            method = "handle",
+            params = ?params
+        );
+
+        let db_context = &state.db_context;
+        let client_auth_repository = &*state.repository_container.as_ref().client_auth_repository;
+
+        let client = ClientAuthService::authenticate(
+            db_context,
+            client_auth_repository,
+            client_credentials.id.as_str(),
+            client_credentials.secret.as_deref(),
+        )
+        .await
+        .map_err(IntrospectionControllerError::from)?;
+
+        if client.is_public {
+            tracing::error!(error = "Public client attempted to introspect a token");
+            return Err(IntrospectionControllerError::InvalidClient);
+        }
+
+        let access_token_repository = &*state.repository_container.as_ref().access_token_repository;
+
+        match AccessTokenService::verify_token(
+            db_context,
+            access_token_repository,
+            params.token.as_str(),
+        )
+        .await
+        {
+            Ok(access_token) => Ok(IntrospectionResponse::new(access_token)),
+            Err(AccessTokenServiceError::NotFound) => Ok(IntrospectionResponse::inactive()),
+            Err(err) => Err(IntrospectionControllerError::from(err)),
+        }
+    }
+}
+
+pub enum IntrospectionControllerError {
+    InvalidClient,
+
+    InternalError,
+}
+
+impl IntrospectionControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidClient => StatusCode::UNAUTHORIZED,
+
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::InvalidClient => "The provided client credentials are invalid.",
+
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<AccessTokenServiceError> for IntrospectionControllerError {
+    fn from(err: AccessTokenServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
+impl From<ClientAuthServiceError> for IntrospectionControllerError {
+    fn from(err: ClientAuthServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            ClientAuthServiceError::NotFound => Self::InvalidClient,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for IntrospectionControllerError {
+    fn into_response(self) -> axum::response::Response {
+        (self.error_code(), self.error_message()).into_response()
+    }
+}
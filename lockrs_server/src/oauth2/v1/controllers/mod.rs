@@ -1,5 +1,12 @@
 mod authorize_controller;
+mod backchannel_authentication_controller;
 mod device_authorization_controller;
+mod introspection_controller;
 mod token_controller;
+mod userinfo_controller;
 
-pub use self::{authorize_controller::*, device_authorization_controller::*, token_controller::*};
+pub use self::{
+    authorize_controller::*, backchannel_authentication_controller::*,
+    device_authorization_controller::*, introspection_controller::*, token_controller::*,
+    userinfo_controller::*,
+};
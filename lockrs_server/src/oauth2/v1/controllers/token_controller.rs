@@ -1,20 +1,29 @@
+use std::str::FromStr;
+
 use axum::{
     extract::{Query, State},
     http::StatusCode,
     response::IntoResponse,
 };
+use chrono::Utc;
+use scoped_futures::ScopedFutureExt;
 use serde::Deserialize;
 use url::Url;
 
 use crate::{
-    models::ClientModel,
-    oauth2::v1::models::ScopeModel,
+    db::DbContextError,
+    models::{ClientModel, ClientPolicyModel, GrantType},
+    oauth2::v1::models::{AuthorizationDetailModel, ScopeModel},
     oauth2::v1::responses::TokenResponse,
     oauth2::v1::services::{
-        RefreshTokenService, RefreshTokenServiceError, ScopeService, ScopeServiceError,
-        TokenService, TokenServiceError,
+        AuthorizationCodeService, AuthorizationCodeServiceError, AuthorizationDetailService,
+        AuthorizationDetailServiceError, BackchannelAuthorizationService,
+        BackchannelAuthorizationServiceError, RefreshTokenService, RefreshTokenServiceError,
+        ScopeService, ScopeServiceError, TokenService, TokenServiceError,
+    },
+    services::{
+        ClientAuthService, ClientAuthServiceError, ClientPolicyService, ClientPolicyServiceError,
     },
-    services::{ClientAuthService, ClientAuthServiceError},
     utils::extractors::ExtractClientCredentials,
     AppState,
 };
@@ -23,7 +32,10 @@ use crate::{
 pub struct TokenRequest {
     // required
     pub grant_type: String,
-    pub scope: String,
+    pub scope: Option<String>,
+
+    // rich authorization requests
+    pub authorization_details: Option<String>,
 
     // authorization code
     pub redirect_uri: Option<Url>,
@@ -33,6 +45,9 @@ pub struct TokenRequest {
     // device authorization
     pub device_code: Option<String>,
 
+    // backchannel authentication
+    pub auth_req_id: Option<String>,
+
     // refresh token
     pub refresh_token: Option<String>,
 }
@@ -62,23 +77,75 @@ impl TokenController {
         .await
         .map_err(TokenControllerError::from)?;
 
-        let scope_repository = &*state.repository_container.as_ref().scope_repository;
+        let Ok(grant_type) = GrantType::from_str(params.grant_type.as_str()) else {
+            tracing::error!(error = "Invalid grant type supplied.");
+            return Err(TokenControllerError::InvalidGrantType);
+        };
+
+        let client_policy_repository =
+            &*state.repository_container.as_ref().client_policy_repository;
 
-        let scopes =
-            ScopeService::get_from_list(db_context, scope_repository, params.scope.as_str())
+        let client_policy =
+            ClientPolicyService::get_policy(db_context, client_policy_repository, &client.id)
                 .await
                 .map_err(TokenControllerError::from)?;
 
-        let token: TokenResponse = match params.grant_type.as_str() {
-            "authorization_code" => Self::authorization_code_token(state).await,
-            "urn:ietf:params:oauth:grant-type:device_code" => {
-                Self::device_authorization_token(state).await
+        if !client_policy.allows(grant_type) {
+            tracing::error!(
+                error = "Client attempted a grant type not allowed by its policy",
+                client = client.id,
+                grant_type = grant_type.as_str()
+            );
+            return Err(TokenControllerError::GrantTypeNotAllowed);
+        }
+
+        let authorization_details = match params.authorization_details.as_deref() {
+            Some(authorization_details) => {
+                let authorization_detail_type_repository = &*state
+                    .repository_container
+                    .as_ref()
+                    .authorization_detail_type_repository;
+
+                AuthorizationDetailService::get_from_json(
+                    db_context,
+                    authorization_detail_type_repository,
+                    &client.id,
+                    authorization_details,
+                )
+                .await
+                .map_err(TokenControllerError::from)?
+            }
+            None => Vec::new(),
+        };
+
+        let token: TokenResponse = match grant_type {
+            GrantType::AuthorizationCode => {
+                Self::authorization_code_token(
+                    state,
+                    client,
+                    client_policy,
+                    authorization_details,
+                    params,
+                )
+                .await
+            }
+            GrantType::DeviceCode => Self::device_authorization_token(state).await,
+            GrantType::Ciba => {
+                Self::backchannel_authentication_token(state, client, client_policy, params).await
+            }
+            GrantType::ClientCredentials => {
+                Self::client_credentials_token(
+                    state,
+                    client,
+                    client_policy,
+                    authorization_details,
+                    params,
+                )
+                .await
             }
-            "client_credentials" => Self::client_credentials_token(state, client, scopes).await,
-            "refresh_token" => Self::refresh_token(state, client, scopes, params).await,
-            _ => {
-                tracing::error!(error = "Invalid grant type supplied.");
-                Err(TokenControllerError::InvalidGrantType)
+            GrantType::RefreshToken => {
+                Self::refresh_token(state, client, client_policy, authorization_details, params)
+                    .await
             }
         }?;
 
@@ -86,14 +153,99 @@ impl TokenController {
     }
 
     pub async fn authorization_code_token(
-        _state: AppState,
+        state: AppState,
+        client: ClientModel,
+        client_policy: ClientPolicyModel,
+        authorization_details: Vec<AuthorizationDetailModel>,
+        params: TokenRequest,
     ) -> Result<TokenResponse, TokenControllerError> {
-        tracing::trace!(method = "authorization_code_token");
+        tracing::trace!(
+            method = "authorization_code_token",
+            client = client.id,
+            authorization_details = ?authorization_details,
+            params = ?params
+        );
 
-        // validate params
-        // store/cache param info
-        // redirect to frontend login with param info cache in params
-        todo!();
+        let (Some(code), Some(code_verifier), Some(redirect_uri)) =
+            (params.code, params.code_verifier, params.redirect_uri)
+        else {
+            tracing::error!(error = "Missing code, code_verifier or redirect_uri in request");
+            return Err(TokenControllerError::MissingCode);
+        };
+
+        let db_context = &state.db_context;
+
+        // the code is only spent if tokens are issued for it, so a failure part way through
+        // leaves the client with a code it can retry with
+        let token = db_context
+            .transaction::<_, TokenControllerError, _>(|db_context| {
+                async move {
+                    let authorization_code_repository = &*state
+                        .repository_container
+                        .as_ref()
+                        .authorization_code_repository;
+
+                    let authorization_code = AuthorizationCodeService::redeem(
+                        db_context,
+                        authorization_code_repository,
+                        &client.id,
+                        code.as_str(),
+                        &redirect_uri,
+                        code_verifier.as_str(),
+                    )
+                    .await
+                    .map_err(TokenControllerError::from)?;
+
+                    // the token request may narrow the authorization details that were
+                    // consented to, but never widen them
+                    let authorization_details = match authorization_details.is_empty() {
+                        true => authorization_code.authorization_details.to_vec(),
+                        false => {
+                            if !AuthorizationDetailService::is_subset(
+                                &authorization_details,
+                                &authorization_code.authorization_details,
+                            ) {
+                                tracing::error!(
+                                    error = "Requested authorization details exceed grant"
+                                );
+                                return Err(TokenControllerError::InvalidAuthorizationDetails);
+                            }
+
+                            authorization_details
+                        }
+                    };
+
+                    let access_token_repository =
+                        &*state.repository_container.as_ref().access_token_repository;
+                    let refresh_token_repository =
+                        &*state.repository_container.as_ref().refresh_token_repository;
+
+                    TokenService::create_token(
+                        db_context,
+                        access_token_repository,
+                        refresh_token_repository,
+                        &client_policy,
+                        GrantType::AuthorizationCode,
+                        Some(&authorization_code.user_id),
+                        ScopeModel::new(&authorization_code.scopes),
+                        &authorization_details,
+                        None,
+                    )
+                    .await
+                    .map_err(TokenControllerError::from)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(TokenResponse {
+            token_type: token.token_type,
+            expires_in: token.expires_in,
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            scopes: token.scopes,
+            authorization_details: token.authorization_details,
+        })
     }
 
     pub async fn device_authorization_token(
@@ -108,15 +260,108 @@ impl TokenController {
         todo!();
     }
 
+    pub async fn backchannel_authentication_token(
+        state: AppState,
+        client: ClientModel,
+        client_policy: ClientPolicyModel,
+        params: TokenRequest,
+    ) -> Result<TokenResponse, TokenControllerError> {
+        tracing::trace!(
+            method = "backchannel_authentication_token",
+            client = client.id,
+            params = ?params
+        );
+
+        if client.is_public {
+            tracing::error!(error = "Public client attempted backchannel authentication grant");
+            return Err(TokenControllerError::InvalidClient);
+        }
+
+        let Some(auth_req_id) = params.auth_req_id else {
+            tracing::error!(error = "Missing auth_req_id in request");
+            return Err(TokenControllerError::MissingAuthReqId);
+        };
+
+        let db_context = &state.db_context;
+        let backchannel_authorization_repository = &*state
+            .repository_container
+            .as_ref()
+            .backchannel_authorization_repository;
+
+        // kept out of the unit of work, as a pending or denied request is still updated by the
+        // poll that fails
+        let backchannel_authorization = BackchannelAuthorizationService::poll(
+            db_context,
+            backchannel_authorization_repository,
+            &client.id,
+            &auth_req_id,
+        )
+        .await
+        .map_err(TokenControllerError::from)?;
+
+        // the approval is only spent if tokens are issued for it, so a failure part way through
+        // leaves the client with a request it can poll again
+        let token = db_context
+            .transaction::<_, TokenControllerError, _>(|db_context| {
+                async move {
+                    let backchannel_authorization_repository = &*state
+                        .repository_container
+                        .as_ref()
+                        .backchannel_authorization_repository;
+
+                    BackchannelAuthorizationService::consume(
+                        db_context,
+                        backchannel_authorization_repository,
+                        &auth_req_id,
+                    )
+                    .await
+                    .map_err(TokenControllerError::from)?;
+
+                    let access_token_repository =
+                        &*state.repository_container.as_ref().access_token_repository;
+                    let refresh_token_repository =
+                        &*state.repository_container.as_ref().refresh_token_repository;
+
+                    TokenService::create_token(
+                        db_context,
+                        access_token_repository,
+                        refresh_token_repository,
+                        &client_policy,
+                        GrantType::Ciba,
+                        Some(&backchannel_authorization.user_id),
+                        ScopeModel::new(&backchannel_authorization.scopes),
+                        &backchannel_authorization.authorization_details,
+                        None,
+                    )
+                    .await
+                    .map_err(TokenControllerError::from)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(TokenResponse {
+            token_type: token.token_type,
+            expires_in: token.expires_in,
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            scopes: token.scopes,
+            authorization_details: token.authorization_details,
+        })
+    }
+
     pub async fn client_credentials_token(
         state: AppState,
         client: ClientModel,
-        scopes: ScopeModel,
+        client_policy: ClientPolicyModel,
+        authorization_details: Vec<AuthorizationDetailModel>,
+        params: TokenRequest,
     ) -> Result<TokenResponse, TokenControllerError> {
         tracing::trace!(
             method = "client_credentials_token",
             client = client.id,
-            scopes = ?scopes
+            authorization_details = ?authorization_details,
+            params = ?params
         );
 
         if client.is_public {
@@ -125,6 +370,17 @@ impl TokenController {
         }
 
         let db_context = &state.db_context;
+        let scope_repository = &*state.repository_container.as_ref().scope_repository;
+
+        let scopes = ScopeService::get_for_client(
+            db_context,
+            scope_repository,
+            &client.id,
+            params.scope.as_deref(),
+        )
+        .await
+        .map_err(TokenControllerError::from)?;
+
         let access_token_repository = &*state.repository_container.as_ref().access_token_repository;
         let refresh_token_repository =
             &*state.repository_container.as_ref().refresh_token_repository;
@@ -133,9 +389,12 @@ impl TokenController {
             db_context,
             access_token_repository,
             refresh_token_repository,
-            &client.id,
+            &client_policy,
+            GrantType::ClientCredentials,
             None,
             scopes,
+            &authorization_details,
+            None,
         )
         .await
         .map_err(TokenControllerError::from)?;
@@ -146,49 +405,126 @@ impl TokenController {
             access_token: token.access_token,
             refresh_token: token.refresh_token,
             scopes: token.scopes,
+            authorization_details: token.authorization_details,
         })
     }
 
     pub async fn refresh_token(
         state: AppState,
         client: ClientModel,
-        scopes: ScopeModel,
+        client_policy: ClientPolicyModel,
+        authorization_details: Vec<AuthorizationDetailModel>,
         params: TokenRequest,
     ) -> Result<TokenResponse, TokenControllerError> {
         tracing::trace!(
             method = "refresh_token",
             client = client.id,
-            scopes = ?scopes,
+            authorization_details = ?authorization_details,
             params = ?params
         );
 
-        let Some(token) = params.refresh_token
-        else {
+        let Some(token) = params.refresh_token else {
             tracing::error!(error = "Missing refresh token in request");
             return Err(TokenControllerError::MissingRefreshToken);
         };
 
         let db_context = &state.db_context;
-        let refresh_token_repository =
-            &*state.repository_container.as_ref().refresh_token_repository;
 
-        let refresh_token =
-            RefreshTokenService::use_token(db_context, refresh_token_repository, token.as_str())
-                .await
-                .map_err(TokenControllerError::from)?;
-
-        let access_token_repository = &*state.repository_container.as_ref().access_token_repository;
-
-        let token = TokenService::create_token(
-            db_context,
-            access_token_repository,
-            refresh_token_repository,
-            &client.id,
-            refresh_token.user_id.as_ref(),
-            scopes,
-        )
-        .await
-        .map_err(TokenControllerError::from)?;
+        // the old token is only spent if the new pair is issued, so a failure part way through
+        // rotation leaves the client with a refresh token it can retry with
+        let token = db_context
+            .transaction::<_, TokenControllerError, _>(|db_context| {
+                async move {
+                    let refresh_token_repository =
+                        &*state.repository_container.as_ref().refresh_token_repository;
+
+                    let refresh_token = RefreshTokenService::use_token(
+                        db_context,
+                        refresh_token_repository,
+                        token.as_str(),
+                    )
+                    .await
+                    .map_err(TokenControllerError::from)?;
+
+                    if refresh_token.client_id != client.id {
+                        tracing::error!(error = "Refresh token was issued to a different client");
+                        return Err(TokenControllerError::InvalidRefreshToken);
+                    }
+
+                    let now = Utc::now().naive_utc();
+
+                    if client_policy.is_refresh_grant_expired(&now, &refresh_token.grant_created_at)
+                    {
+                        tracing::error!(error = "Refresh grant exceeded its absolute lifetime");
+                        return Err(TokenControllerError::InvalidRefreshToken);
+                    }
+
+                    // a refresh may narrow the granted scopes, but never widen them
+                    let scopes = match params.scope.as_deref() {
+                        Some(scope) => {
+                            let scope_repository =
+                                &*state.repository_container.as_ref().scope_repository;
+
+                            let scopes = ScopeService::get_for_client(
+                                db_context,
+                                scope_repository,
+                                &client.id,
+                                Some(scope),
+                            )
+                            .await
+                            .map_err(TokenControllerError::from)?;
+
+                            if scopes
+                                .iter()
+                                .any(|scope| !refresh_token.scopes.contains(scope))
+                            {
+                                tracing::error!(error = "Requested scopes exceed grant");
+                                return Err(TokenControllerError::InvalidScopes);
+                            }
+
+                            scopes
+                        }
+                        None => ScopeModel::new(&refresh_token.scopes),
+                    };
+
+                    // a refresh may narrow the granted authorization details, but never widen them
+                    let authorization_details = match authorization_details.is_empty() {
+                        true => refresh_token.authorization_details.to_vec(),
+                        false => {
+                            if !AuthorizationDetailService::is_subset(
+                                &authorization_details,
+                                &refresh_token.authorization_details,
+                            ) {
+                                tracing::error!(
+                                    error = "Requested authorization details exceed grant"
+                                );
+                                return Err(TokenControllerError::InvalidAuthorizationDetails);
+                            }
+
+                            authorization_details
+                        }
+                    };
+
+                    let access_token_repository =
+                        &*state.repository_container.as_ref().access_token_repository;
+
+                    TokenService::create_token(
+                        db_context,
+                        access_token_repository,
+                        refresh_token_repository,
+                        &client_policy,
+                        GrantType::RefreshToken,
+                        refresh_token.user_id.as_ref(),
+                        scopes,
+                        &authorization_details,
+                        Some(&refresh_token),
+                    )
+                    .await
+                    .map_err(TokenControllerError::from)
+                }
+                .scope_boxed()
+            })
+            .await?;
 
         Ok(TokenResponse {
             token_type: token.token_type,
@@ -196,6 +532,7 @@ impl TokenController {
             access_token: token.access_token,
             refresh_token: token.refresh_token,
             scopes: token.scopes,
+            authorization_details: token.authorization_details,
         })
     }
 }
@@ -203,9 +540,19 @@ impl TokenController {
 pub enum TokenControllerError {
     InvalidClient,
     InvalidGrantType,
+    GrantTypeNotAllowed,
     InvalidScopes,
+    InvalidAuthorizationDetails,
+    MissingCode,
+    InvalidCode,
     MissingRefreshToken,
     InvalidRefreshToken,
+    MissingAuthReqId,
+    InvalidAuthReqId,
+    AuthorizationPending,
+    SlowDown,
+    AccessDenied,
+    UnauthorizedClient,
 
     BadRequest,
     InternalError,
@@ -214,7 +561,7 @@ pub enum TokenControllerError {
 impl TokenControllerError {
     pub fn error_code(&self) -> StatusCode {
         match self {
-            Self::InvalidClient => StatusCode::UNAUTHORIZED,
+            Self::InvalidClient | Self::UnauthorizedClient => StatusCode::UNAUTHORIZED,
 
             Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
 
@@ -225,10 +572,20 @@ impl TokenControllerError {
     pub fn error_message(&self) -> &'static str {
         match self {
             Self::InvalidClient => "The provided client is invalid.",
-            Self::InvalidGrantType => "The provided grant_type is invalid. This server supports \"authorization_code\", \"urn:ietf:params:oauth:grant-type:device_code\", \"client_credentials\", and \"refresh_token.\"",
-            Self::InvalidScopes => "The provided scopes are invalid.",
+            Self::InvalidGrantType => "The provided grant_type is invalid. This server supports \"authorization_code\", \"urn:ietf:params:oauth:grant-type:device_code\", \"urn:openid:params:grant-type:ciba\", \"client_credentials\", and \"refresh_token.\"",
+            Self::GrantTypeNotAllowed => "The client is not authorized to use the provided grant_type.",
+            Self::InvalidScopes => "The requested scope is invalid, unknown, or not allowed for the client.",
+            Self::InvalidAuthorizationDetails => "The provided authorization_details are invalid.",
+            Self::MissingCode => "The request is missing the \"code\", \"code_verifier\" or \"redirect_uri\" parameter.",
+            Self::InvalidCode => "The provided code is invalid, expired, has already been used, or was not issued for this client, redirect_uri and code_verifier.",
             Self::MissingRefreshToken => "The request is missing the \"refresh_token\" parameter.",
             Self::InvalidRefreshToken => "The provided refresh_token is invalid.",
+            Self::MissingAuthReqId => "The request is missing the \"auth_req_id\" parameter.",
+            Self::InvalidAuthReqId => "The provided auth_req_id is invalid or has expired.",
+            Self::AuthorizationPending => "The authorization request is still pending as the user has not yet been authenticated.",
+            Self::SlowDown => "The authorization request is still pending and polling should occur less frequently.",
+            Self::AccessDenied => "The user denied the authorization request.",
+            Self::UnauthorizedClient => "The client is not authorized to use this grant type for the provided auth_req_id.",
 
             Self::BadRequest => "Unable to perform the requested operation.",
             Self::InternalError => "An error has occurred while processing your request. Please try again later.",
@@ -236,6 +593,14 @@ impl TokenControllerError {
     }
 }
 
+impl From<DbContextError> for TokenControllerError {
+    fn from(err: DbContextError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
 impl From<TokenServiceError> for TokenControllerError {
     fn from(err: TokenServiceError) -> Self {
         tracing::error!(error = %err);
@@ -247,6 +612,17 @@ impl From<TokenServiceError> for TokenControllerError {
     }
 }
 
+impl From<AuthorizationCodeServiceError> for TokenControllerError {
+    fn from(err: AuthorizationCodeServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            AuthorizationCodeServiceError::InvalidCode => Self::InvalidCode,
+            _ => Self::InternalError,
+        }
+    }
+}
+
 impl From<RefreshTokenServiceError> for TokenControllerError {
     fn from(err: RefreshTokenServiceError) -> Self {
         tracing::error!(error = %err);
@@ -258,6 +634,37 @@ impl From<RefreshTokenServiceError> for TokenControllerError {
     }
 }
 
+impl From<BackchannelAuthorizationServiceError> for TokenControllerError {
+    fn from(err: BackchannelAuthorizationServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            // an approval another token request has already exchanged
+            BackchannelAuthorizationServiceError::NotFound
+            | BackchannelAuthorizationServiceError::NotDeleted => Self::InvalidAuthReqId,
+            BackchannelAuthorizationServiceError::InvalidDeliveryMode => Self::UnauthorizedClient,
+            BackchannelAuthorizationServiceError::AuthorizationPending => {
+                Self::AuthorizationPending
+            }
+            BackchannelAuthorizationServiceError::SlowDown => Self::SlowDown,
+            BackchannelAuthorizationServiceError::AccessDenied => Self::AccessDenied,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl From<AuthorizationDetailServiceError> for TokenControllerError {
+    fn from(err: AuthorizationDetailServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            AuthorizationDetailServiceError::InvalidAuthorizationDetails
+            | AuthorizationDetailServiceError::UnknownType => Self::InvalidAuthorizationDetails,
+            _ => Self::InternalError,
+        }
+    }
+}
+
 impl From<ClientAuthServiceError> for TokenControllerError {
     fn from(err: ClientAuthServiceError) -> Self {
         tracing::error!(error = %err);
@@ -269,12 +676,20 @@ impl From<ClientAuthServiceError> for TokenControllerError {
     }
 }
 
+impl From<ClientPolicyServiceError> for TokenControllerError {
+    fn from(err: ClientPolicyServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
 impl From<ScopeServiceError> for TokenControllerError {
     fn from(err: ScopeServiceError) -> Self {
         tracing::error!(error = %err);
 
         match err {
-            ScopeServiceError::InvalidScopes => Self::InvalidClient,
+            ScopeServiceError::InvalidScopes => Self::InvalidScopes,
             _ => Self::InternalError,
         }
     }
@@ -0,0 +1,121 @@
+use axum::{
+    extract::State,
+    http::{header::WWW_AUTHENTICATE, StatusCode},
+    response::IntoResponse,
+};
+
+use crate::{
+    oauth2::v1::{
+        responses::UserinfoResponse,
+        services::{AccessTokenService, AccessTokenServiceError},
+    },
+    utils::extractors::BearerAuth,
+    AppState,
+};
+
+/// the scope an access token needs for the user's email to be shared
+const EMAIL_SCOPE: &str = "email";
+
+pub struct UserinfoController;
+
+impl UserinfoController {
+    /// Answers who the user behind an access token is, so lockrs can be signed in with by
+    /// another relying party, another lockrs included. The email is only shared with tokens
+    /// granted the `email` scope.
+    pub async fn handle(
+        State(state): State<AppState>,
+        BearerAuth(token): BearerAuth,
+    ) -> Result<UserinfoResponse, UserinfoControllerError> {
+        tracing::trace!(method = "handle");
+
+        let db_context = &state.db_context;
+        let access_token_repository = &*state.repository_container.as_ref().access_token_repository;
+        let user_repository = &*state.repository_container.as_ref().user_repository;
+
+        let access_token =
+            AccessTokenService::verify_token(db_context, access_token_repository, token.as_str())
+                .await
+                .map_err(UserinfoControllerError::from)?;
+
+        // client credentials tokens are not issued for anyone
+        let Some(user_id) = access_token.user_id
+        else {
+            tracing::error!(error = "Access token was not issued for a user");
+            return Err(UserinfoControllerError::InvalidToken);
+        };
+
+        if !access_token.scopes.iter().any(|scope| scope == EMAIL_SCOPE) {
+            return Ok(UserinfoResponse {
+                sub: user_id,
+                email: None,
+                email_verified: None,
+            });
+        }
+
+        let user = user_repository
+            .get_by_id(db_context, &user_id)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = %err);
+                UserinfoControllerError::InvalidToken
+            })?;
+
+        Ok(UserinfoResponse {
+            sub: user.id,
+            email: Some(user.email),
+            email_verified: Some(user.email_verified),
+        })
+    }
+}
+
+pub enum UserinfoControllerError {
+    InvalidToken,
+
+    InternalError,
+}
+
+impl UserinfoControllerError {
+    pub fn error_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidToken => StatusCode::UNAUTHORIZED,
+
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::InvalidToken => "The provided access token is invalid or expired.",
+
+            Self::InternalError => {
+                "An error has occurred while processing your request. Please try again later."
+            }
+        }
+    }
+}
+
+impl From<AccessTokenServiceError> for UserinfoControllerError {
+    fn from(err: AccessTokenServiceError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            AccessTokenServiceError::NotFound => Self::InvalidToken,
+            _ => Self::InternalError,
+        }
+    }
+}
+
+impl IntoResponse for UserinfoControllerError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            // rfc: https://www.rfc-editor.org/rfc/rfc6750#section-3
+            Self::InvalidToken => (
+                self.error_code(),
+                [(WWW_AUTHENTICATE, "Bearer error=\"invalid_token\"")],
+                self.error_message(),
+            )
+                .into_response(),
+            _ => (self.error_code(), self.error_message()).into_response(),
+        }
+    }
+}
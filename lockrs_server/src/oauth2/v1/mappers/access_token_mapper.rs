@@ -1,6 +1,6 @@
 use crate::{db::pg::models::PgAccessToken, oauth2::v1::models::AccessTokenModel};
 
-use super::ScopeMapper;
+use super::{AuthorizationDetailMapper, ScopeMapper};
 
 pub struct AccessTokenMapper;
 
@@ -13,6 +13,7 @@ impl AccessTokenMapper {
             pg_token.user_id.as_ref(),
             &pg_token.expires_at,
             ScopeMapper::pg_list_to_vec(&pg_token.scopes).as_slice(),
+            AuthorizationDetailMapper::pg_value_to_vec(&pg_token.authorization_details).as_slice(),
         )
     }
 }
@@ -22,6 +23,7 @@ mod tests {
     use super::*;
 
     use chrono::{Duration, Utc};
+    use serde_json::json;
     use uuid::Uuid;
 
     #[test]
@@ -42,6 +44,7 @@ mod tests {
             created_at,
             expires_at,
             scopes,
+            authorization_details: json!([]),
         };
 
         let actual_token = AccessTokenMapper::from_pg(pg_token);
@@ -53,6 +56,7 @@ mod tests {
             user_id.as_ref(),
             &expires_at,
             &[String::from("read"), String::from("write")],
+            &[],
         );
 
         assert_eq!(actual_token, expected_token);
@@ -76,6 +80,7 @@ mod tests {
             created_at,
             expires_at,
             scopes,
+            authorization_details: json!([]),
         };
 
         let actual_token = AccessTokenMapper::from_pg(pg_token);
@@ -87,6 +92,7 @@ mod tests {
             user_id.as_ref(),
             &expires_at,
             &[String::from("read"), String::from("write")],
+            &[],
         );
 
         assert_eq!(actual_token, expected_token);
@@ -0,0 +1,84 @@
+use url::Url;
+
+use crate::{db::pg::models::PgAuthorizationCode, oauth2::v1::models::AuthorizationCodeModel};
+
+use super::{AuthorizationDetailMapper, ScopeMapper};
+
+pub struct AuthorizationCodeMapper;
+
+impl AuthorizationCodeMapper {
+    pub fn from_pg(pg_code: PgAuthorizationCode) -> AuthorizationCodeModel {
+        AuthorizationCodeModel::new(
+            pg_code.id,
+            pg_code.client_id.as_str(),
+            &pg_code.user_id,
+            pg_code.code.as_str(),
+            pg_code.challenge.as_str(),
+            pg_code.is_challenge_plain,
+            &Url::parse(&pg_code.redirect_uri)
+                .unwrap_or_else(|_| panic!("invalid url stored in database: {}", pg_code.id)),
+            &pg_code.expires_at,
+            ScopeMapper::pg_list_to_vec(&pg_code.scopes).as_slice(),
+            AuthorizationDetailMapper::pg_value_to_vec(&pg_code.authorization_details).as_slice(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{Duration, Utc};
+    use serde_json::{json, Map};
+    use uuid::Uuid;
+
+    use crate::oauth2::v1::models::AuthorizationDetailModel;
+
+    #[test]
+    fn it_should_map_pg() {
+        let id = 1;
+        let code = String::from("CODE");
+        let challenge = String::from("CHALLENGE");
+        let client_id = String::from("CLIENT_ID");
+        let user_id = Uuid::new_v4();
+        let redirect_uri = Url::parse("http://127.0.0.1/callback").unwrap();
+        let created_at = Utc::now().naive_utc();
+        let expires_at = created_at + Duration::minutes(1);
+        let scopes = vec![Some(String::from("read")), Some(String::from("write"))];
+
+        let pg_code = PgAuthorizationCode {
+            id,
+            code: code.clone(),
+            challenge: challenge.clone(),
+            is_challenge_plain: false,
+            client_id: client_id.clone(),
+            user_id,
+            redirect_uri: redirect_uri.to_string(),
+            created_at,
+            expires_at,
+            used: false,
+            scopes,
+            authorization_details: json!([{ "type": "payment_initiation", "amount": 500 }]),
+        };
+
+        let actual_code = AuthorizationCodeMapper::from_pg(pg_code);
+
+        let mut fields = Map::new();
+        fields.insert(String::from("amount"), json!(500));
+
+        let expected_code = AuthorizationCodeModel::new(
+            id,
+            client_id.as_str(),
+            &user_id,
+            code.as_str(),
+            challenge.as_str(),
+            false,
+            &redirect_uri,
+            &expires_at,
+            &[String::from("read"), String::from("write")],
+            &[AuthorizationDetailModel::new("payment_initiation", &fields)],
+        );
+
+        assert_eq!(actual_code, expected_code);
+    }
+}
@@ -0,0 +1,135 @@
+use serde_json::Value;
+
+use crate::{
+    db::pg::models::PgAuthorizationDetailType,
+    oauth2::v1::models::{AuthorizationDetailModel, AuthorizationDetailTypeModel},
+};
+
+pub struct AuthorizationDetailMapper;
+
+impl AuthorizationDetailMapper {
+    pub fn pg_value_to_vec(authorization_details: &Value) -> Vec<AuthorizationDetailModel> {
+        serde_json::from_value(authorization_details.to_owned()).unwrap_or_else(|err| {
+            tracing::error!(error = %err);
+            Vec::new()
+        })
+    }
+
+    pub fn vec_to_pg_value(authorization_details: &[AuthorizationDetailModel]) -> Value {
+        serde_json::to_value(authorization_details).unwrap_or_else(|err| {
+            tracing::error!(error = %err);
+            Value::Array(Vec::new())
+        })
+    }
+
+    pub fn type_from_pg(pg_type: PgAuthorizationDetailType) -> AuthorizationDetailTypeModel {
+        AuthorizationDetailTypeModel {
+            name: pg_type.name,
+            client_id: pg_type.client_id,
+            description: pg_type.description,
+            schema: serde_json::from_value(pg_type.schema).unwrap_or_else(|err| {
+                tracing::error!(error = %err);
+                Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::oauth2::v1::models::{AuthorizationDetailFieldType, AuthorizationDetailSchema};
+
+    #[test]
+    fn it_should_map_pg_value() {
+        let pg_value = json!([
+            {
+                "type": "payment_initiation",
+                "actions": ["initiate"],
+                "instructedAmount": { "currency": "USD", "amount": "500.00" },
+            },
+        ]);
+
+        let actual_details = AuthorizationDetailMapper::pg_value_to_vec(&pg_value);
+
+        let expected_fields = json!({
+            "actions": ["initiate"],
+            "instructedAmount": { "currency": "USD", "amount": "500.00" },
+        });
+        let expected_details = vec![AuthorizationDetailModel::new(
+            "payment_initiation",
+            expected_fields.as_object().unwrap(),
+        )];
+
+        assert_eq!(actual_details, expected_details);
+    }
+
+    #[test]
+    fn it_should_map_invalid_pg_value_to_empty() {
+        let pg_value = json!({ "actions": ["initiate"] });
+
+        let actual_details = AuthorizationDetailMapper::pg_value_to_vec(&pg_value);
+
+        assert!(actual_details.is_empty());
+    }
+
+    #[test]
+    fn it_should_map_vec_to_pg_value() {
+        let fields = json!({ "actions": ["read"] });
+        let details = vec![AuthorizationDetailModel::new(
+            "account_information",
+            fields.as_object().unwrap(),
+        )];
+
+        let actual_value = AuthorizationDetailMapper::vec_to_pg_value(&details);
+
+        let expected_value = json!([{ "type": "account_information", "actions": ["read"] }]);
+
+        assert_eq!(actual_value, expected_value);
+    }
+
+    #[test]
+    fn it_should_map_pg_type() {
+        let name = String::from("payment_initiation");
+        let client_id = String::from("CLIENT_ID");
+        let description = String::from("DESCRIPTION");
+        let schema = json!({
+            "required": ["actions"],
+            "properties": { "actions": "array", "instructedAmount": "object" },
+        });
+
+        let pg_type = PgAuthorizationDetailType {
+            name: name.clone(),
+            client_id: client_id.clone(),
+            description: description.clone(),
+            schema,
+        };
+
+        let actual_type = AuthorizationDetailMapper::type_from_pg(pg_type);
+
+        let expected_schema = AuthorizationDetailSchema {
+            required: vec![String::from("actions")],
+            properties: [
+                (String::from("actions"), AuthorizationDetailFieldType::Array),
+                (
+                    String::from("instructedAmount"),
+                    AuthorizationDetailFieldType::Object,
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            additional_properties: false,
+        };
+        let expected_type = AuthorizationDetailTypeModel::new(
+            name.as_str(),
+            client_id.as_str(),
+            description.as_str(),
+            &expected_schema,
+        );
+
+        assert_eq!(actual_type, expected_type);
+    }
+}
@@ -0,0 +1,140 @@
+use std::str::FromStr;
+
+use crate::{
+    db::pg::models::PgBackchannelAuthorization,
+    oauth2::v1::models::{
+        BackchannelAuthorizationModel, BackchannelAuthorizationStatus, BackchannelDeliveryMode,
+    },
+};
+
+use super::{AuthorizationDetailMapper, ScopeMapper};
+
+pub struct BackchannelAuthorizationMapper;
+
+impl BackchannelAuthorizationMapper {
+    pub fn from_pg(pg_model: PgBackchannelAuthorization) -> BackchannelAuthorizationModel {
+        BackchannelAuthorizationModel {
+            id: pg_model.id,
+            auth_req_id: pg_model.auth_req_id,
+            client_id: pg_model.client_id,
+            user_id: pg_model.user_id,
+            binding_message: pg_model.binding_message,
+            delivery_mode: BackchannelDeliveryMode::from_str(&pg_model.delivery_mode)
+                .unwrap_or_else(|_| {
+                    tracing::error!(
+                        error = "Unknown backchannel delivery mode",
+                        delivery_mode = pg_model.delivery_mode
+                    );
+                    BackchannelDeliveryMode::Poll
+                }),
+            client_notification_token: pg_model.client_notification_token,
+            client_notification_endpoint: pg_model.client_notification_endpoint,
+            // an unknown status should never be treated as an approval
+            status: BackchannelAuthorizationStatus::from_str(&pg_model.status).unwrap_or_else(
+                |_| {
+                    tracing::error!(
+                        error = "Unknown backchannel authorization status",
+                        status = pg_model.status
+                    );
+                    BackchannelAuthorizationStatus::Denied
+                },
+            ),
+            poll_interval: pg_model.poll_interval,
+            expires_at: pg_model.expires_at,
+            last_polled_at: pg_model.last_polled_at,
+            scopes: ScopeMapper::pg_list_to_vec(&pg_model.scopes),
+            authorization_details: AuthorizationDetailMapper::pg_value_to_vec(
+                &pg_model.authorization_details,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn it_should_map_pg() {
+        let id = 1;
+        let auth_req_id = String::from("AUTH_REQ_ID");
+        let client_id = String::from("CLIENT_ID");
+        let user_id = Uuid::new_v4();
+        let binding_message = Some(String::from("W4SCT"));
+        let client_notification_token = Some(String::from("NOTIFICATION_TOKEN"));
+        let client_notification_endpoint = Some(String::from("https://127.0.0.1/cb"));
+        let created_at = Utc::now().naive_utc();
+        let expires_at = created_at + Duration::minutes(5);
+        let scopes = vec![Some(String::from("read")), Some(String::from("write"))];
+
+        let pg_auth = PgBackchannelAuthorization {
+            id,
+            auth_req_id: auth_req_id.clone(),
+            client_id: client_id.clone(),
+            user_id,
+            binding_message: binding_message.clone(),
+            delivery_mode: String::from("ping"),
+            client_notification_token: client_notification_token.clone(),
+            client_notification_endpoint: client_notification_endpoint.clone(),
+            status: String::from("approved"),
+            poll_interval: 5,
+            created_at,
+            expires_at,
+            last_polled_at: None,
+            scopes,
+            authorization_details: json!([]),
+        };
+
+        let actual_auth = BackchannelAuthorizationMapper::from_pg(pg_auth);
+
+        let expected_auth = BackchannelAuthorizationModel::new(
+            id,
+            auth_req_id.as_str(),
+            client_id.as_str(),
+            &user_id,
+            binding_message.as_deref(),
+            BackchannelDeliveryMode::Ping,
+            client_notification_token.as_deref(),
+            client_notification_endpoint.as_deref(),
+            BackchannelAuthorizationStatus::Approved,
+            5,
+            &expires_at,
+            None,
+            &[String::from("read"), String::from("write")],
+            &[],
+        );
+
+        assert_eq!(actual_auth, expected_auth);
+    }
+
+    #[test]
+    fn it_should_map_pg_unknown_status_to_denied() {
+        let created_at = Utc::now().naive_utc();
+
+        let pg_auth = PgBackchannelAuthorization {
+            id: 1,
+            auth_req_id: String::from("AUTH_REQ_ID"),
+            client_id: String::from("CLIENT_ID"),
+            user_id: Uuid::new_v4(),
+            binding_message: None,
+            delivery_mode: String::from("poll"),
+            client_notification_token: None,
+            client_notification_endpoint: None,
+            status: String::from("UNKNOWN"),
+            poll_interval: 5,
+            created_at,
+            expires_at: created_at + Duration::minutes(5),
+            last_polled_at: None,
+            scopes: vec![Some(String::from("read"))],
+            authorization_details: json!([]),
+        };
+
+        let actual_auth = BackchannelAuthorizationMapper::from_pg(pg_auth);
+
+        assert_eq!(actual_auth.status, BackchannelAuthorizationStatus::Denied);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{db::pg::models::PgConsent, oauth2::v1::models::ConsentModel};
+
+use super::{AuthorizationDetailMapper, ScopeMapper};
+
+pub struct ConsentMapper;
+
+impl ConsentMapper {
+    pub fn from_pg(pg_model: PgConsent) -> ConsentModel {
+        ConsentModel {
+            id: pg_model.id,
+            user_id: pg_model.user_id,
+            client_id: pg_model.client_id,
+            scopes: ScopeMapper::pg_list_to_vec(&pg_model.scopes),
+            granted_at: pg_model.granted_at,
+            expires_at: pg_model.expires_at,
+            authorization_details: AuthorizationDetailMapper::pg_value_to_vec(
+                &pg_model.authorization_details,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{Duration, Utc};
+    use serde_json::{json, Map};
+    use uuid::Uuid;
+
+    use crate::oauth2::v1::models::AuthorizationDetailModel;
+
+    #[test]
+    fn it_should_map_pg() {
+        let id = 1;
+        let user_id = Uuid::new_v4();
+        let client_id = String::from("CLIENT_ID");
+        let granted_at = Utc::now().naive_utc();
+        let expires_at = granted_at + Duration::days(30);
+        let scopes = vec![Some(String::from("read")), Some(String::from("write"))];
+
+        let pg_consent = PgConsent {
+            id,
+            user_id,
+            client_id: client_id.clone(),
+            scopes,
+            granted_at,
+            expires_at: Some(expires_at),
+            authorization_details: json!([{ "type": "payment_initiation", "amount": "10.00" }]),
+        };
+
+        let actual_consent = ConsentMapper::from_pg(pg_consent);
+
+        let mut fields = Map::new();
+        fields.insert(String::from("amount"), json!("10.00"));
+
+        let expected_consent = ConsentModel::new(
+            id,
+            &user_id,
+            client_id.as_str(),
+            &[String::from("read"), String::from("write")],
+            &granted_at,
+            Some(&expires_at),
+            &[AuthorizationDetailModel::new("payment_initiation", &fields)],
+        );
+
+        assert_eq!(actual_consent, expected_consent);
+    }
+}
@@ -1,9 +1,14 @@
 mod access_token_mapper;
+mod authorization_code_mapper;
+mod authorization_detail_mapper;
+mod backchannel_authorization_mapper;
+mod consent_mapper;
 mod device_authorization_mapper;
 mod refresh_token_mapper;
 mod scope_mapper;
 
 pub use self::{
-    access_token_mapper::*, device_authorization_mapper::*, refresh_token_mapper::*,
-    scope_mapper::*,
+    access_token_mapper::*, authorization_code_mapper::*, authorization_detail_mapper::*,
+    backchannel_authorization_mapper::*, consent_mapper::*, device_authorization_mapper::*,
+    refresh_token_mapper::*, scope_mapper::*,
 };
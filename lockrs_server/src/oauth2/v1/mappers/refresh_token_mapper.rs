@@ -1,6 +1,6 @@
 use crate::{db::pg::models::PgRefreshToken, oauth2::v1::models::RefreshTokenModel};
 
-use super::ScopeMapper;
+use super::{AuthorizationDetailMapper, ScopeMapper};
 
 pub struct RefreshTokenMapper;
 
@@ -14,6 +14,8 @@ impl RefreshTokenMapper {
             pg_token.user_id.as_ref(),
             &pg_token.expires_at,
             ScopeMapper::pg_list_to_vec(&pg_token.scopes).as_slice(),
+            AuthorizationDetailMapper::pg_value_to_vec(&pg_token.authorization_details).as_slice(),
+            &pg_token.grant_created_at,
         )
     }
 }
@@ -23,6 +25,7 @@ mod tests {
     use super::*;
 
     use chrono::{Duration, Utc};
+    use serde_json::json;
     use uuid::Uuid;
 
     #[test]
@@ -46,6 +49,8 @@ mod tests {
             expires_at,
             used: false,
             scopes,
+            authorization_details: json!([]),
+            grant_created_at: created_at,
         };
 
         let actual_token = RefreshTokenMapper::from_pg(pg_token);
@@ -58,6 +63,8 @@ mod tests {
             user_id.as_ref(),
             &expires_at,
             &[String::from("read"), String::from("write")],
+            &[],
+            &created_at,
         );
 
         assert_eq!(actual_token, expected_token);
@@ -84,6 +91,8 @@ mod tests {
             expires_at,
             used: false,
             scopes,
+            authorization_details: json!([]),
+            grant_created_at: created_at,
         };
 
         let actual_token = RefreshTokenMapper::from_pg(pg_token);
@@ -96,6 +105,8 @@ mod tests {
             user_id.as_ref(),
             &expires_at,
             &[String::from("read"), String::from("write")],
+            &[],
+            &created_at,
         );
 
         assert_eq!(actual_token, expected_token);
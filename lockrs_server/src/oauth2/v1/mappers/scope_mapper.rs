@@ -1,6 +1,27 @@
+use crate::{
+    db::pg::models::{PgAllowedScope, PgScope},
+    oauth2::v1::models::{AllowedScopeModel, ScopeDefinitionModel},
+};
+
 pub struct ScopeMapper;
 
 impl ScopeMapper {
+    pub fn from_pg(pg_scope: PgScope) -> ScopeDefinitionModel {
+        ScopeDefinitionModel {
+            name: pg_scope.name,
+            description: pg_scope.description,
+            consent_text: pg_scope.consent_text,
+            client_id: pg_scope.client_id,
+        }
+    }
+
+    pub fn allowed_from_pg(pg_allowed_scope: PgAllowedScope) -> AllowedScopeModel {
+        AllowedScopeModel {
+            scope: pg_allowed_scope.scope,
+            is_default: pg_allowed_scope.is_default,
+        }
+    }
+
     pub fn pg_list_to_vec(scopes: &[Option<String>]) -> Vec<String> {
         scopes
             .iter()
@@ -27,4 +48,41 @@ mod tests {
 
         assert_eq!(actual_scopes, expected_scopes);
     }
+
+    #[test]
+    fn it_should_map_pg_scope() {
+        let pg_scope = PgScope {
+            id: 1,
+            name: String::from("CLIENT_ID:photos.read"),
+            description: String::from("Read access to photos."),
+            client_id: Some(String::from("CLIENT_ID")),
+            consent_text: Some(String::from("View your photos")),
+        };
+
+        let actual_scope = ScopeMapper::from_pg(pg_scope);
+
+        let expected_scope = ScopeDefinitionModel::new(
+            "CLIENT_ID:photos.read",
+            "Read access to photos.",
+            Some("View your photos"),
+            Some("CLIENT_ID"),
+        );
+
+        assert_eq!(actual_scope, expected_scope);
+    }
+
+    #[test]
+    fn it_should_map_pg_allowed_scope() {
+        let pg_allowed_scope = PgAllowedScope {
+            client_id: String::from("CLIENT_ID"),
+            scope: String::from("openid"),
+            is_default: true,
+        };
+
+        let actual_allowed_scope = ScopeMapper::allowed_from_pg(pg_allowed_scope);
+
+        let expected_allowed_scope = AllowedScopeModel::new("openid", true);
+
+        assert_eq!(actual_allowed_scope, expected_allowed_scope);
+    }
 }
@@ -1,5 +1,6 @@
 pub mod controllers;
 pub mod mappers;
 pub mod models;
+pub mod notifiers;
 pub mod responses;
 pub mod services;
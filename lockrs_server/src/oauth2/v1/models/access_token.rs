@@ -1,6 +1,8 @@
 use chrono::NaiveDateTime;
 use uuid::Uuid;
 
+use super::AuthorizationDetailModel;
+
 #[derive(PartialEq)]
 pub struct AccessTokenModel {
     pub id: i32,
@@ -9,6 +11,7 @@ pub struct AccessTokenModel {
     pub user_id: Option<Uuid>,
     pub expires_at: NaiveDateTime,
     pub scopes: Vec<String>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
 }
 
 impl AccessTokenModel {
@@ -19,6 +22,7 @@ impl AccessTokenModel {
         user_id: Option<&Uuid>,
         expires_at: &NaiveDateTime,
         scopes: &[String],
+        authorization_details: &[AuthorizationDetailModel],
     ) -> Self {
         Self {
             id,
@@ -27,6 +31,7 @@ impl AccessTokenModel {
             user_id: user_id.map(|u| u.to_owned()),
             expires_at: expires_at.to_owned(),
             scopes: scopes.to_vec(),
+            authorization_details: authorization_details.to_vec(),
         }
     }
 }
@@ -35,8 +40,13 @@ impl std::fmt::Debug for AccessTokenModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "AccessTokenModel: {{ {:?}, token: ********, {:?}, {:?}, {:?}, {:?} }}",
-            self.id, self.client_id, self.user_id, self.expires_at, self.scopes,
+            "AccessTokenModel: {{ {:?}, token: ********, {:?}, {:?}, {:?}, {:?}, {:?} }}",
+            self.id,
+            self.client_id,
+            self.user_id,
+            self.expires_at,
+            self.scopes,
+            self.authorization_details,
         )
     }
 }
@@ -47,6 +57,7 @@ pub struct AccessTokenCreateModel {
     pub user_id: Option<Uuid>,
     pub expires_at: NaiveDateTime,
     pub scopes: Vec<String>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
 }
 
 impl AccessTokenCreateModel {
@@ -56,6 +67,7 @@ impl AccessTokenCreateModel {
         user_id: Option<&Uuid>,
         expires_at: &NaiveDateTime,
         scopes: &[String],
+        authorization_details: &[AuthorizationDetailModel],
     ) -> Self {
         Self {
             token: token.to_owned(),
@@ -63,6 +75,7 @@ impl AccessTokenCreateModel {
             user_id: user_id.map(|u| u.to_owned()),
             expires_at: expires_at.to_owned(),
             scopes: scopes.to_vec(),
+            authorization_details: authorization_details.to_vec(),
         }
     }
 }
@@ -71,8 +84,8 @@ impl std::fmt::Debug for AccessTokenCreateModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "AccessTokenCreateModel: {{ token: ********, {:?}, {:?}, {:?}, {:?} }}",
-            self.client_id, self.user_id, self.expires_at, self.scopes,
+            "AccessTokenCreateModel: {{ token: ********, {:?}, {:?}, {:?}, {:?}, {:?} }}",
+            self.client_id, self.user_id, self.expires_at, self.scopes, self.authorization_details,
         )
     }
 }
@@ -2,7 +2,11 @@ use chrono::NaiveDateTime;
 use url::Url;
 use uuid::Uuid;
 
+use super::AuthorizationDetailModel;
+
+#[derive(PartialEq)]
 pub struct AuthorizationCodeModel {
+    pub id: i32,
     pub client_id: String,
     pub user_id: Uuid,
     pub code: String,
@@ -11,11 +15,13 @@ pub struct AuthorizationCodeModel {
     pub redirect_uri: Url,
     pub expires_at: NaiveDateTime,
     pub scopes: Vec<String>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
 }
 
 impl AuthorizationCodeModel {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
+        id: i32,
         client_id: &str,
         user_id: &Uuid,
         code: &str,
@@ -24,8 +30,10 @@ impl AuthorizationCodeModel {
         redirect_uri: &Url,
         expires_at: &NaiveDateTime,
         scopes: &[String],
+        authorization_details: &[AuthorizationDetailModel],
     ) -> Self {
         Self {
+            id,
             client_id: client_id.to_owned(),
             user_id: user_id.to_owned(),
             code: code.to_owned(),
@@ -34,6 +42,7 @@ impl AuthorizationCodeModel {
             redirect_uri: redirect_uri.to_owned(),
             expires_at: expires_at.to_owned(),
             scopes: scopes.to_vec(),
+            authorization_details: authorization_details.to_vec(),
         }
     }
 }
@@ -42,42 +51,54 @@ impl std::fmt::Debug for AuthorizationCodeModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "AuthorizationCodeModel: {{ {:?}, {:?}, code: ********, challenge: ********, {:?}, {:?}, {:?}, {:?} }}",
+            "AuthorizationCodeModel: {{ {:?}, {:?}, {:?}, code: ********, challenge: ********, {:?}, {:?}, {:?}, {:?}, {:?} }}",
+            self.id,
             self.client_id,
             self.user_id,
             self.is_challenge_plain,
             self.redirect_uri,
             self.scopes,
+            self.authorization_details,
             self.expires_at,
         )
     }
 }
 
 pub struct AuthorizationCodeCreateModel {
-    client_id: String,
-    user_id: Uuid,
-    challenge: String,
-    is_challenge_plain: bool,
-    redirect_uri: Url,
-    scopes: Vec<String>,
+    pub code: String,
+    pub client_id: String,
+    pub user_id: Uuid,
+    pub challenge: String,
+    pub is_challenge_plain: bool,
+    pub redirect_uri: Url,
+    pub expires_at: NaiveDateTime,
+    pub scopes: Vec<String>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
 }
 
 impl AuthorizationCodeCreateModel {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        code: &str,
         client_id: &str,
         user_id: &Uuid,
         challenge: &str,
         is_challenge_plain: bool,
         redirect_uri: &Url,
+        expires_at: &NaiveDateTime,
         scopes: &[String],
+        authorization_details: &[AuthorizationDetailModel],
     ) -> Self {
         Self {
+            code: code.to_owned(),
             client_id: client_id.to_owned(),
             user_id: user_id.to_owned(),
             challenge: challenge.to_owned(),
             is_challenge_plain,
             redirect_uri: redirect_uri.to_owned(),
+            expires_at: expires_at.to_owned(),
             scopes: scopes.to_vec(),
+            authorization_details: authorization_details.to_vec(),
         }
     }
 }
@@ -86,8 +107,14 @@ impl std::fmt::Debug for AuthorizationCodeCreateModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "AuthorizationCodeModel: {{ {:?}, {:?}, challenge: ********, {:?}, {:?}, {:?} }}",
-            self.client_id, self.user_id, self.is_challenge_plain, self.redirect_uri, self.scopes,
+            "AuthorizationCodeCreateModel: {{ code: ********, {:?}, {:?}, challenge: ********, {:?}, {:?}, {:?}, {:?}, {:?} }}",
+            self.client_id,
+            self.user_id,
+            self.is_challenge_plain,
+            self.redirect_uri,
+            self.expires_at,
+            self.scopes,
+            self.authorization_details,
         )
     }
 }
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// rfc: https://www.rfc-editor.org/rfc/rfc9396#section-2
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuthorizationDetailModel {
+    #[serde(rename = "type")]
+    pub detail_type: String,
+    #[serde(flatten)]
+    pub fields: Map<String, Value>,
+}
+
+impl AuthorizationDetailModel {
+    pub fn new(detail_type: &str, fields: &Map<String, Value>) -> Self {
+        Self {
+            detail_type: detail_type.to_owned(),
+            fields: fields.to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthorizationDetailFieldType {
+    String,
+    Number,
+    Boolean,
+    Array,
+    Object,
+}
+
+impl AuthorizationDetailFieldType {
+    pub fn matches(&self, value: &Value) -> bool {
+        match self {
+            Self::String => value.is_string(),
+            Self::Number => value.is_number(),
+            Self::Boolean => value.is_boolean(),
+            Self::Array => value.is_array(),
+            Self::Object => value.is_object(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuthorizationDetailSchema {
+    #[serde(default)]
+    pub required: Vec<String>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, AuthorizationDetailFieldType>,
+    #[serde(default)]
+    pub additional_properties: bool,
+}
+
+impl AuthorizationDetailSchema {
+    pub fn is_valid(&self, detail: &AuthorizationDetailModel) -> bool {
+        let has_required = self
+            .required
+            .iter()
+            .all(|field| detail.fields.contains_key(field));
+
+        let has_valid_fields =
+            detail
+                .fields
+                .iter()
+                .all(|(name, value)| match self.properties.get(name) {
+                    Some(field_type) => field_type.matches(value),
+                    None => self.additional_properties,
+                });
+
+        has_required && has_valid_fields
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AuthorizationDetailTypeModel {
+    pub name: String,
+    pub client_id: String,
+    pub description: String,
+    pub schema: AuthorizationDetailSchema,
+}
+
+impl AuthorizationDetailTypeModel {
+    pub fn new(
+        name: &str,
+        client_id: &str,
+        description: &str,
+        schema: &AuthorizationDetailSchema,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            client_id: client_id.to_owned(),
+            description: description.to_owned(),
+            schema: schema.to_owned(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AuthorizationDetailTypeCreateModel {
+    pub name: String,
+    pub client_id: String,
+    pub description: String,
+    pub schema: AuthorizationDetailSchema,
+}
+
+impl AuthorizationDetailTypeCreateModel {
+    pub fn new(
+        name: &str,
+        client_id: &str,
+        description: &str,
+        schema: &AuthorizationDetailSchema,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            client_id: client_id.to_owned(),
+            description: description.to_owned(),
+            schema: schema.to_owned(),
+        }
+    }
+}
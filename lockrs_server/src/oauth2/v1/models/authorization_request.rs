@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::AuthorizationDetailModel;
+
+/// A validated request for an authorization code, either sent straight to `/authorize` or pushed
+/// ahead of it and kept under a `request_uri`.
+///
+/// rfc: https://www.rfc-editor.org/rfc/rfc9126
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct AuthorizationRequestModel {
+    pub client_id: String,
+    pub redirect_uri: Url,
+    pub code_challenge: String,
+    pub is_challenge_plain: bool,
+    pub scopes: Vec<String>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
+    /// the client's state, handed back on the redirect with the code
+    pub state: Option<String>,
+}
+
+impl AuthorizationRequestModel {
+    pub fn new(
+        client_id: &str,
+        redirect_uri: &Url,
+        code_challenge: &str,
+        is_challenge_plain: bool,
+        scopes: &[String],
+        authorization_details: &[AuthorizationDetailModel],
+        state: Option<&str>,
+    ) -> Self {
+        Self {
+            client_id: client_id.to_owned(),
+            redirect_uri: redirect_uri.to_owned(),
+            code_challenge: code_challenge.to_owned(),
+            is_challenge_plain,
+            scopes: scopes.to_vec(),
+            authorization_details: authorization_details.to_vec(),
+            state: state.map(String::from),
+        }
+    }
+}
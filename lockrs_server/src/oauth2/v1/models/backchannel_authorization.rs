@@ -0,0 +1,207 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::AuthorizationDetailModel;
+
+/// rfc: https://openid.net/specs/openid-client-initiated-backchannel-authentication-core-1_0.html#rfc.section.5
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackchannelDeliveryMode {
+    Poll,
+    Ping,
+    Push,
+}
+
+impl BackchannelDeliveryMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Poll => "poll",
+            Self::Ping => "ping",
+            Self::Push => "push",
+        }
+    }
+}
+
+impl std::str::FromStr for BackchannelDeliveryMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "poll" => Ok(Self::Poll),
+            "ping" => Ok(Self::Ping),
+            "push" => Ok(Self::Push),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackchannelAuthorizationStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+impl BackchannelAuthorizationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Denied => "denied",
+        }
+    }
+}
+
+impl std::str::FromStr for BackchannelAuthorizationStatus {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "approved" => Ok(Self::Approved),
+            "denied" => Ok(Self::Denied),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(PartialEq)]
+pub struct BackchannelAuthorizationModel {
+    pub id: i32,
+    pub auth_req_id: String,
+    pub client_id: String,
+    pub user_id: Uuid,
+    pub binding_message: Option<String>,
+    pub delivery_mode: BackchannelDeliveryMode,
+    pub client_notification_token: Option<String>,
+    pub client_notification_endpoint: Option<String>,
+    pub status: BackchannelAuthorizationStatus,
+    pub poll_interval: i32,
+    pub expires_at: NaiveDateTime,
+    pub last_polled_at: Option<NaiveDateTime>,
+    pub scopes: Vec<String>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
+}
+
+impl BackchannelAuthorizationModel {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: i32,
+        auth_req_id: &str,
+        client_id: &str,
+        user_id: &Uuid,
+        binding_message: Option<&str>,
+        delivery_mode: BackchannelDeliveryMode,
+        client_notification_token: Option<&str>,
+        client_notification_endpoint: Option<&str>,
+        status: BackchannelAuthorizationStatus,
+        poll_interval: i32,
+        expires_at: &NaiveDateTime,
+        last_polled_at: Option<&NaiveDateTime>,
+        scopes: &[String],
+        authorization_details: &[AuthorizationDetailModel],
+    ) -> Self {
+        Self {
+            id,
+            auth_req_id: auth_req_id.to_owned(),
+            client_id: client_id.to_owned(),
+            user_id: user_id.to_owned(),
+            binding_message: binding_message.map(|m| m.to_owned()),
+            delivery_mode,
+            client_notification_token: client_notification_token.map(|t| t.to_owned()),
+            client_notification_endpoint: client_notification_endpoint.map(|e| e.to_owned()),
+            status,
+            poll_interval,
+            expires_at: expires_at.to_owned(),
+            last_polled_at: last_polled_at.map(|p| p.to_owned()),
+            scopes: scopes.to_vec(),
+            authorization_details: authorization_details.to_vec(),
+        }
+    }
+}
+
+impl std::fmt::Debug for BackchannelAuthorizationModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "BackchannelAuthorizationModel: {{ {:?}, auth_req_id: ********, {:?}, {:?}, {:?}, {:?}, client_notification_token: ********, {:?}, {:?}, {:?}, {:?}, {:?}, {:?}, {:?} }}",
+            self.id,
+            self.client_id,
+            self.user_id,
+            self.binding_message,
+            self.delivery_mode,
+            self.client_notification_endpoint,
+            self.status,
+            self.poll_interval,
+            self.expires_at,
+            self.last_polled_at,
+            self.scopes,
+            self.authorization_details,
+        )
+    }
+}
+
+pub struct BackchannelAuthorizationCreateModel {
+    pub auth_req_id: String,
+    pub client_id: String,
+    pub user_id: Uuid,
+    pub binding_message: Option<String>,
+    pub delivery_mode: BackchannelDeliveryMode,
+    pub client_notification_token: Option<String>,
+    pub client_notification_endpoint: Option<String>,
+    pub poll_interval: i32,
+    pub expires_at: NaiveDateTime,
+    pub scopes: Vec<String>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
+}
+
+impl BackchannelAuthorizationCreateModel {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        auth_req_id: &str,
+        client_id: &str,
+        user_id: &Uuid,
+        binding_message: Option<&str>,
+        delivery_mode: BackchannelDeliveryMode,
+        client_notification_token: Option<&str>,
+        client_notification_endpoint: Option<&str>,
+        poll_interval: i32,
+        expires_at: &NaiveDateTime,
+        scopes: &[String],
+        authorization_details: &[AuthorizationDetailModel],
+    ) -> Self {
+        Self {
+            auth_req_id: auth_req_id.to_owned(),
+            client_id: client_id.to_owned(),
+            user_id: user_id.to_owned(),
+            binding_message: binding_message.map(|m| m.to_owned()),
+            delivery_mode,
+            client_notification_token: client_notification_token.map(|t| t.to_owned()),
+            client_notification_endpoint: client_notification_endpoint.map(|e| e.to_owned()),
+            poll_interval,
+            expires_at: expires_at.to_owned(),
+            scopes: scopes.to_vec(),
+            authorization_details: authorization_details.to_vec(),
+        }
+    }
+}
+
+impl std::fmt::Debug for BackchannelAuthorizationCreateModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "BackchannelAuthorizationCreateModel: {{ auth_req_id: ********, {:?}, {:?}, {:?}, {:?}, client_notification_token: ********, {:?}, {:?}, {:?}, {:?}, {:?} }}",
+            self.client_id,
+            self.user_id,
+            self.binding_message,
+            self.delivery_mode,
+            self.client_notification_endpoint,
+            self.poll_interval,
+            self.expires_at,
+            self.scopes,
+            self.authorization_details,
+        )
+    }
+}
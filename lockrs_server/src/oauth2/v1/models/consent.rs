@@ -0,0 +1,75 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+use super::AuthorizationDetailModel;
+
+#[derive(Debug, PartialEq)]
+pub struct ConsentModel {
+    pub id: i32,
+    pub user_id: Uuid,
+    pub client_id: String,
+    pub scopes: Vec<String>,
+    pub granted_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
+}
+
+impl ConsentModel {
+    pub fn new(
+        id: i32,
+        user_id: &Uuid,
+        client_id: &str,
+        scopes: &[String],
+        granted_at: &NaiveDateTime,
+        expires_at: Option<&NaiveDateTime>,
+        authorization_details: &[AuthorizationDetailModel],
+    ) -> Self {
+        Self {
+            id,
+            user_id: user_id.to_owned(),
+            client_id: client_id.to_owned(),
+            scopes: scopes.to_vec(),
+            granted_at: granted_at.to_owned(),
+            expires_at: expires_at.map(|e| e.to_owned()),
+            authorization_details: authorization_details.to_vec(),
+        }
+    }
+
+    pub fn covers(
+        &self,
+        scopes: &[String],
+        authorization_details: &[AuthorizationDetailModel],
+    ) -> bool {
+        scopes.iter().all(|scope| self.scopes.contains(scope))
+            && authorization_details
+                .iter()
+                .all(|detail| self.authorization_details.contains(detail))
+    }
+}
+
+#[derive(Debug)]
+pub struct ConsentCreateModel {
+    pub user_id: Uuid,
+    pub client_id: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
+}
+
+impl ConsentCreateModel {
+    pub fn new(
+        user_id: &Uuid,
+        client_id: &str,
+        scopes: &[String],
+        expires_at: Option<&NaiveDateTime>,
+        authorization_details: &[AuthorizationDetailModel],
+    ) -> Self {
+        Self {
+            user_id: user_id.to_owned(),
+            client_id: client_id.to_owned(),
+            scopes: scopes.to_vec(),
+            expires_at: expires_at.map(|e| e.to_owned()),
+            authorization_details: authorization_details.to_vec(),
+        }
+    }
+}
@@ -1,11 +1,17 @@
 mod access_token;
 mod authorization_code;
+mod authorization_detail;
+mod authorization_request;
+mod backchannel_authorization;
+mod consent;
 mod device_authorization;
+mod purge_report;
 mod refresh_token;
 mod scope;
 mod token;
 
 pub use self::{
-    access_token::*, authorization_code::*, device_authorization::*, refresh_token::*, scope::*,
-    token::*,
+    access_token::*, authorization_code::*, authorization_detail::*, authorization_request::*,
+    backchannel_authorization::*, consent::*, device_authorization::*, purge_report::*,
+    refresh_token::*, scope::*, token::*,
 };
@@ -0,0 +1,16 @@
+#[derive(Debug, Default, PartialEq)]
+pub struct PurgeReportModel {
+    pub access_tokens: usize,
+    pub refresh_tokens: usize,
+    pub authorization_codes: usize,
+    pub device_authorizations: usize,
+}
+
+impl PurgeReportModel {
+    pub fn total(&self) -> usize {
+        self.access_tokens
+            + self.refresh_tokens
+            + self.authorization_codes
+            + self.device_authorizations
+    }
+}
@@ -1,6 +1,8 @@
 use chrono::NaiveDateTime;
 use uuid::Uuid;
 
+use super::AuthorizationDetailModel;
+
 #[derive(PartialEq)]
 pub struct RefreshTokenModel {
     pub id: i32,
@@ -10,9 +12,12 @@ pub struct RefreshTokenModel {
     pub user_id: Option<Uuid>,
     pub expires_at: NaiveDateTime,
     pub scopes: Vec<String>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
+    pub grant_created_at: NaiveDateTime,
 }
 
 impl RefreshTokenModel {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: i32,
         access_token_id: i32,
@@ -21,6 +26,8 @@ impl RefreshTokenModel {
         user_id: Option<&Uuid>,
         expires_at: &NaiveDateTime,
         scopes: &[String],
+        authorization_details: &[AuthorizationDetailModel],
+        grant_created_at: &NaiveDateTime,
     ) -> Self {
         Self {
             id,
@@ -30,6 +37,8 @@ impl RefreshTokenModel {
             user_id: user_id.map(|u| u.to_owned()),
             expires_at: expires_at.to_owned(),
             scopes: scopes.to_vec(),
+            authorization_details: authorization_details.to_vec(),
+            grant_created_at: grant_created_at.to_owned(),
         }
     }
 }
@@ -38,13 +47,15 @@ impl std::fmt::Debug for RefreshTokenModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "RefreshTokenModel: {{ {:?}, {:?}, token: ********, {:?}, {:?}, {:?}, {:?} }}",
+            "RefreshTokenModel: {{ {:?}, {:?}, token: ********, {:?}, {:?}, {:?}, {:?}, {:?}, {:?} }}",
             self.id,
             self.access_token_id,
             self.client_id,
             self.user_id,
             self.expires_at,
             self.scopes,
+            self.authorization_details,
+            self.grant_created_at,
         )
     }
 }
@@ -56,9 +67,12 @@ pub struct RefreshTokenCreateModel {
     pub user_id: Option<Uuid>,
     pub expires_at: NaiveDateTime,
     pub scopes: Vec<String>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
+    pub grant_created_at: NaiveDateTime,
 }
 
 impl RefreshTokenCreateModel {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         token: &str,
         access_token_id: i32,
@@ -66,6 +80,8 @@ impl RefreshTokenCreateModel {
         user_id: Option<&Uuid>,
         expires_at: &NaiveDateTime,
         scopes: &[String],
+        authorization_details: &[AuthorizationDetailModel],
+        grant_created_at: &NaiveDateTime,
     ) -> Self {
         Self {
             token: token.to_owned(),
@@ -74,6 +90,8 @@ impl RefreshTokenCreateModel {
             user_id: user_id.map(|u| u.to_owned()),
             expires_at: expires_at.to_owned(),
             scopes: scopes.to_vec(),
+            authorization_details: authorization_details.to_vec(),
+            grant_created_at: grant_created_at.to_owned(),
         }
     }
 }
@@ -82,8 +100,14 @@ impl std::fmt::Debug for RefreshTokenCreateModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "RefreshTokenCreateModel: {{ token: ********, {:?}, {:?}, {:?}, {:?}, {:?} }}",
-            self.access_token_id, self.client_id, self.user_id, self.expires_at, self.scopes,
+            "RefreshTokenCreateModel: {{ token: ********, {:?}, {:?}, {:?}, {:?}, {:?}, {:?}, {:?} }}",
+            self.access_token_id,
+            self.client_id,
+            self.user_id,
+            self.expires_at,
+            self.scopes,
+            self.authorization_details,
+            self.grant_created_at,
         )
     }
 }
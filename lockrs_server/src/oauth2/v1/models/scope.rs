@@ -1,6 +1,11 @@
 use std::ops::Deref;
 
-#[derive(Clone, Debug)]
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::db::pg::schema::scopes;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ScopeModel {
     data: Vec<String>,
 }
@@ -21,17 +26,81 @@ impl Deref for ScopeModel {
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub struct ScopeDefinitionModel {
+    pub name: String,
+    pub description: String,
+    pub consent_text: Option<String>,
+    pub client_id: Option<String>,
+}
+
+impl ScopeDefinitionModel {
+    pub fn new(
+        name: &str,
+        description: &str,
+        consent_text: Option<&str>,
+        client_id: Option<&str>,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            description: description.to_owned(),
+            consent_text: consent_text.map(|c| c.to_owned()),
+            client_id: client_id.map(|c| c.to_owned()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AllowedScopeModel {
+    pub scope: String,
+    pub is_default: bool,
+}
+
+impl AllowedScopeModel {
+    pub fn new(scope: &str, is_default: bool) -> Self {
+        Self {
+            scope: scope.to_owned(),
+            is_default,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ScopeCreateModel {
     pub client_id: String,
     pub scope: String,
+    pub description: String,
+    pub consent_text: Option<String>,
 }
 
 impl ScopeCreateModel {
-    pub fn new(client_id: &str, scope: &str) -> Self {
+    pub fn new(
+        client_id: &str,
+        scope: &str,
+        description: &str,
+        consent_text: Option<&str>,
+    ) -> Self {
         Self {
             client_id: client_id.to_owned(),
             scope: scope.to_owned(),
+            description: description.to_owned(),
+            consent_text: consent_text.map(|c| c.to_owned()),
+        }
+    }
+}
+
+#[derive(AsChangeset, Debug)]
+#[diesel(table_name = scopes)]
+pub struct ScopeUpdateModel {
+    pub description: Option<String>,
+    pub consent_text: Option<String>,
+}
+
+impl ScopeUpdateModel {
+    pub fn new(description: Option<&str>, consent_text: Option<&str>) -> Self {
+        Self {
+            description: description.map(|d| d.to_owned()),
+            consent_text: consent_text.map(|c| c.to_owned()),
         }
     }
 }
@@ -1,11 +1,14 @@
 use uuid::Uuid;
 
+use super::AuthorizationDetailModel;
+
 pub struct TokenModel {
     pub token_type: String,
     pub expires_in: i64,
     pub access_token: String,
-    pub refresh_token: String,
+    pub refresh_token: Option<String>,
     pub scopes: String,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
 }
 
 impl TokenModel {
@@ -13,15 +16,17 @@ impl TokenModel {
         token_type: &str,
         expires_in: i64,
         access_token: &str,
-        refresh_token: &str,
+        refresh_token: Option<&str>,
         scopes: &str,
+        authorization_details: &[AuthorizationDetailModel],
     ) -> Self {
         Self {
             token_type: token_type.to_owned(),
             expires_in,
             access_token: access_token.to_owned(),
-            refresh_token: refresh_token.to_owned(),
+            refresh_token: refresh_token.map(|t| t.to_owned()),
             scopes: scopes.to_owned(),
+            authorization_details: authorization_details.to_vec(),
         }
     }
 }
@@ -30,8 +35,8 @@ impl std::fmt::Debug for TokenModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "TokenModel: {{ {:?}, {:?}, access_token: ********, refresh_token: ********, {:?} }}",
-            self.token_type, self.expires_in, self.scopes,
+            "TokenModel: {{ {:?}, {:?}, access_token: ********, refresh_token: ********, {:?}, {:?} }}",
+            self.token_type, self.expires_in, self.scopes, self.authorization_details,
         )
     }
 }
@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::{models::UserModel, oauth2::v1::models::BackchannelAuthorizationModel};
+
+/// Delivers a backchannel authentication request to a device owned by the user, so that the user
+/// can approve or deny it out of band.
+#[async_trait]
+pub trait AuthenticationDeviceNotifier: Send + Sync {
+    async fn notify(
+        &self,
+        user: &UserModel,
+        backchannel_authorization: &BackchannelAuthorizationModel,
+    ) -> Result<(), AuthenticationDeviceNotifierError>;
+}
+
+#[derive(Debug, Error)]
+pub enum AuthenticationDeviceNotifierError {
+    #[error("AUTHENTICATION DEVICE NOTIFIER ERROR :: Delivery failed")]
+    DeliveryFailed,
+}
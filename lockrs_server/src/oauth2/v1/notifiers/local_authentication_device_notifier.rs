@@ -0,0 +1,80 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{models::UserModel, oauth2::v1::models::BackchannelAuthorizationModel};
+
+use super::{AuthenticationDeviceNotifier, AuthenticationDeviceNotifierError};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthenticationDeviceNotification {
+    pub user_id: Uuid,
+    pub auth_req_id: String,
+    pub client_id: String,
+    pub binding_message: Option<String>,
+    pub scopes: Vec<String>,
+}
+
+/// Keeps delivered notifications in memory instead of sending them to a real device. Useful for
+/// local development and integration tests.
+#[derive(Debug, Default)]
+pub struct LocalAuthenticationDeviceNotifier {
+    notifications: Mutex<Vec<AuthenticationDeviceNotification>>,
+}
+
+impl LocalAuthenticationDeviceNotifier {
+    pub fn get_notifications(&self) -> Vec<AuthenticationDeviceNotification> {
+        self.notifications
+            .lock()
+            .map(|notifications| notifications.to_vec())
+            .unwrap_or_default()
+    }
+
+    pub fn get_latest_for_user(&self, user_id: &Uuid) -> Option<AuthenticationDeviceNotification> {
+        self.get_notifications()
+            .into_iter()
+            .rev()
+            .find(|notification| &notification.user_id == user_id)
+    }
+}
+
+#[async_trait]
+impl AuthenticationDeviceNotifier for LocalAuthenticationDeviceNotifier {
+    async fn notify(
+        &self,
+        user: &UserModel,
+        backchannel_authorization: &BackchannelAuthorizationModel,
+    ) -> Result<(), AuthenticationDeviceNotifierError> {
+        tracing::trace!(
+            method = "notify",
+            user_id = user.id.to_string(),
+            ?backchannel_authorization
+        );
+
+        let notification = AuthenticationDeviceNotification {
+            user_id: user.id,
+            auth_req_id: backchannel_authorization.auth_req_id.to_owned(),
+            client_id: backchannel_authorization.client_id.to_owned(),
+            binding_message: backchannel_authorization.binding_message.to_owned(),
+            scopes: backchannel_authorization.scopes.to_owned(),
+        };
+
+        self.notifications
+            .lock()
+            .map_err(|_| {
+                tracing::error!(error = "Local authentication device notifier lock poisoned");
+                AuthenticationDeviceNotifierError::DeliveryFailed
+            })?
+            .push(notification);
+
+        tracing::info!(
+            "Authentication device notified: {{ user_id: {}, client_id: {}, binding_message: {:?} }}",
+            user.id,
+            backchannel_authorization.client_id,
+            backchannel_authorization.binding_message
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+mod authentication_device_notifier;
+mod local_authentication_device_notifier;
+
+pub use self::{authentication_device_notifier::*, local_authentication_device_notifier::*};
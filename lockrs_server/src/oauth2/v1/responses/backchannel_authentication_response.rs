@@ -0,0 +1,34 @@
+use axum::{response::IntoResponse, Json};
+use serde::Serialize;
+
+use crate::oauth2::v1::models::{BackchannelAuthorizationModel, BackchannelDeliveryMode};
+
+#[derive(Debug, Serialize)]
+pub struct BackchannelAuthenticationResponse {
+    pub auth_req_id: String,
+    pub expires_in: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<i32>,
+}
+
+impl BackchannelAuthenticationResponse {
+    pub fn new(auth: &BackchannelAuthorizationModel, expires_in: i64) -> Self {
+        // push mode clients never poll the token endpoint, so no interval is advertised
+        let interval = match auth.delivery_mode {
+            BackchannelDeliveryMode::Push => None,
+            _ => Some(auth.poll_interval),
+        };
+
+        Self {
+            auth_req_id: auth.auth_req_id.to_owned(),
+            expires_in,
+            interval,
+        }
+    }
+}
+
+impl IntoResponse for BackchannelAuthenticationResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
@@ -0,0 +1,43 @@
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use serde::Serialize;
+
+use crate::oauth2::v1::models::AuthorizationDetailModel;
+
+/// Everything the consent page needs to show the user what the client is asking for.
+///
+/// rfc: https://openid.net/specs/openid-connect-core-1_0.html#AuthError
+#[derive(Debug, Serialize)]
+pub struct ConsentRequiredResponse {
+    pub error: &'static str,
+    pub error_description: &'static str,
+    pub client_id: String,
+    pub scopes: Vec<String>,
+    pub authorization_details: Vec<AuthorizationDetailModel>,
+    /// the pushed request to resume once consent is granted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_uri: Option<String>,
+}
+
+impl ConsentRequiredResponse {
+    pub fn new(
+        client_id: &str,
+        scopes: &[String],
+        authorization_details: &[AuthorizationDetailModel],
+        request_uri: Option<&str>,
+    ) -> Self {
+        Self {
+            error: "consent_required",
+            error_description: "The user must consent to the requested scopes and authorization details before continuing.",
+            client_id: client_id.to_owned(),
+            scopes: scopes.to_vec(),
+            authorization_details: authorization_details.to_vec(),
+            request_uri: request_uri.map(String::from),
+        }
+    }
+}
+
+impl IntoResponse for ConsentRequiredResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::FORBIDDEN, Json(self)).into_response()
+    }
+}
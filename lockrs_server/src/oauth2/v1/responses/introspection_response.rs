@@ -0,0 +1,55 @@
+use axum::{response::IntoResponse, Json};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::oauth2::v1::models::{AccessTokenModel, AuthorizationDetailModel};
+
+/// rfc: https://www.rfc-editor.org/rfc/rfc7662#section-2.2
+#[derive(Debug, Serialize)]
+pub struct IntrospectionResponse {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_type: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub authorization_details: Vec<AuthorizationDetailModel>,
+}
+
+impl IntrospectionResponse {
+    pub fn new(access_token: AccessTokenModel) -> Self {
+        Self {
+            active: true,
+            scope: Some(access_token.scopes.join(" ")),
+            client_id: Some(access_token.client_id),
+            sub: access_token.user_id,
+            exp: Some(access_token.expires_at.timestamp()),
+            token_type: Some(String::from("Bearer")),
+            authorization_details: access_token.authorization_details,
+        }
+    }
+
+    pub fn inactive() -> Self {
+        Self {
+            active: false,
+            scope: None,
+            client_id: None,
+            sub: None,
+            exp: None,
+            token_type: None,
+            authorization_details: Vec::new(),
+        }
+    }
+}
+
+impl IntoResponse for IntrospectionResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
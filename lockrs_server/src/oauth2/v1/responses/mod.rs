@@ -1,7 +1,14 @@
 mod authorization_code_response;
+mod backchannel_authentication_response;
+mod consent_required_response;
 mod device_authorization_response;
+mod introspection_response;
+mod pushed_authorization_response;
 mod token_response;
+mod userinfo_response;
 
 pub use self::{
-    authorization_code_response::*, device_authorization_response::*, token_response::*,
+    authorization_code_response::*, backchannel_authentication_response::*,
+    consent_required_response::*, device_authorization_response::*, introspection_response::*,
+    pushed_authorization_response::*, token_response::*, userinfo_response::*,
 };
@@ -0,0 +1,15 @@
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use serde::Serialize;
+
+/// rfc: https://www.rfc-editor.org/rfc/rfc9126#section-2.2
+#[derive(Debug, Serialize)]
+pub struct PushedAuthorizationResponse {
+    pub request_uri: String,
+    pub expires_in: i64,
+}
+
+impl IntoResponse for PushedAuthorizationResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::CREATED, Json(self)).into_response()
+    }
+}
@@ -1,13 +1,18 @@
 use axum::{response::IntoResponse, Json};
 use serde::Serialize;
 
+use crate::oauth2::v1::models::AuthorizationDetailModel;
+
 #[derive(Debug, Serialize)]
 pub struct TokenResponse {
     pub token_type: String, // usually just 'Bearer'
-    pub expires_in: i64,
-    pub access_token: String,  // 10 minutes
-    pub refresh_token: String, // 24 hours
+    pub expires_in: i64,    // seconds, per the client policy
+    pub access_token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
     pub scopes: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub authorization_details: Vec<AuthorizationDetailModel>,
 }
 
 impl IntoResponse for TokenResponse {
@@ -0,0 +1,20 @@
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The claims about the user an access token was issued for, in the shape relying parties expect
+/// from an oidc userinfo endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UserinfoResponse {
+    pub sub: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_verified: Option<bool>,
+}
+
+impl IntoResponse for UserinfoResponse {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
@@ -1,26 +1,135 @@
-use std::sync::Arc;
+use std::{ops::Deref, sync::Arc};
 
+use chrono::{Duration, Utc};
+use ring::constant_time;
 use thiserror::Error;
+use url::Url;
+use uuid::Uuid;
 
 use crate::{
     db::{
+        digest_token,
         repositories::{AuthorizationCodeRepository, QueryFailure, RepositoryError},
         DbContext,
     },
-    oauth2::v1::models::{AuthorizationCodeCreateModel, AuthorizationCodeModel},
+    oauth2::v1::{
+        models::{
+            AuthorizationCodeCreateModel, AuthorizationCodeModel, AuthorizationDetailModel,
+            ScopeModel,
+        },
+        services::TokenService,
+    },
 };
 
+/// how long a client has to exchange a code, which rfc 6749 recommends keeping under 10 minutes
+pub const AUTHORIZATION_CODE_TTL_MINUTES: i64 = 5;
+
 pub struct AuthorizationCodeService;
 
 impl AuthorizationCodeService {
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
-        _db_context: &Arc<DbContext>,
-        _authorization_code_repository: &dyn AuthorizationCodeRepository,
-        _new_code: AuthorizationCodeCreateModel,
-    ) -> AuthorizationCodeModel {
-        tracing::trace!(method = "create");
+        db_context: &Arc<DbContext>,
+        authorization_code_repository: &dyn AuthorizationCodeRepository,
+        client_id: &str,
+        user_id: &Uuid,
+        challenge: &str,
+        is_challenge_plain: bool,
+        redirect_uri: &Url,
+        scopes: ScopeModel,
+        authorization_details: &[AuthorizationDetailModel],
+    ) -> Result<AuthorizationCodeModel, AuthorizationCodeServiceError> {
+        tracing::trace!(
+            method = "create",
+            client_id,
+            ?user_id,
+            %redirect_uri,
+            ?scopes,
+            ?authorization_details
+        );
+
+        let code = TokenService::generate_opaque_token()
+            .map_err(|_| AuthorizationCodeServiceError::InternalError)?;
+        let expires_at =
+            (Utc::now() + Duration::minutes(AUTHORIZATION_CODE_TTL_MINUTES)).naive_utc();
+
+        let code_create = AuthorizationCodeCreateModel::new(
+            code.as_str(),
+            client_id,
+            user_id,
+            challenge,
+            is_challenge_plain,
+            redirect_uri,
+            &expires_at,
+            scopes.deref(),
+            authorization_details,
+        );
+
+        let code = authorization_code_repository
+            .create(db_context, &code_create)
+            .await
+            .map_err(AuthorizationCodeServiceError::from)?;
+
+        tracing::info!(
+            "Authorization Code created: {{ client_id: {}, expires_at: {}, scopes: {:?} }}",
+            &code.client_id,
+            &code.expires_at.timestamp(),
+            &code.scopes
+        );
+
+        Ok(code)
+    }
+
+    /// Spends the code, failing if it has expired, has already been used, or was issued to
+    /// another client, for another redirect uri, or against a challenge the verifier does not
+    /// answer.
+    pub async fn redeem(
+        db_context: &Arc<DbContext>,
+        authorization_code_repository: &dyn AuthorizationCodeRepository,
+        client_id: &str,
+        code: &str,
+        redirect_uri: &Url,
+        code_verifier: &str,
+    ) -> Result<AuthorizationCodeModel, AuthorizationCodeServiceError> {
+        tracing::trace!(method = "redeem", client_id, %redirect_uri);
+
+        let authorization_code = authorization_code_repository
+            .use_by_code(db_context, code)
+            .await
+            .map_err(AuthorizationCodeServiceError::from)?;
+
+        if authorization_code.client_id != client_id {
+            tracing::error!(error = "Authorization code was issued to a different client");
+            return Err(AuthorizationCodeServiceError::InvalidCode);
+        }
+
+        if &authorization_code.redirect_uri != redirect_uri {
+            tracing::error!(error = "Redirect uri does not match the one the code was issued for");
+            return Err(AuthorizationCodeServiceError::InvalidCode);
+        }
+
+        if !Self::verify_challenge(&authorization_code, code_verifier) {
+            tracing::error!(error = "Code verifier does not answer the code challenge");
+            return Err(AuthorizationCodeServiceError::InvalidCode);
+        }
+
+        Ok(authorization_code)
+    }
+
+    /// rfc: https://www.rfc-editor.org/rfc/rfc7636#section-4.6
+    fn verify_challenge(authorization_code: &AuthorizationCodeModel, code_verifier: &str) -> bool {
+        // an S256 challenge is base64url(sha256(verifier)), which is exactly the digest tokens
+        // are stored under
+        let expected = match authorization_code.is_challenge_plain {
+            true => code_verifier.to_owned(),
+            false => digest_token(code_verifier),
+        };
 
-        todo!();
+        constant_time::verify_slices_are_equal(
+            expected.as_bytes(),
+            authorization_code.challenge.as_bytes(),
+        )
+        .is_ok()
     }
 }
 
@@ -28,6 +137,8 @@ impl AuthorizationCodeService {
 pub enum AuthorizationCodeServiceError {
     #[error("AUTHORIZATION CODE SERVICE ERROR :: Not Created")]
     NotCreated,
+    #[error("AUTHORIZATION CODE SERVICE ERROR :: Invalid Code")]
+    InvalidCode,
 
     #[error("AUTHORIZATION CODE SERVICE ERROR :: Internal Error")]
     InternalError,
@@ -40,6 +151,7 @@ impl From<RepositoryError> for AuthorizationCodeServiceError {
         match err {
             RepositoryError::QueryFailed(query_err) => match query_err {
                 QueryFailure::NotCreated => Self::NotCreated,
+                QueryFailure::NotFound | QueryFailure::NotUpdated => Self::InvalidCode,
                 _ => Self::InternalError,
             },
 
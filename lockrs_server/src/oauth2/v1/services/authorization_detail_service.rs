@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::{
+    db::{
+        repositories::{AuthorizationDetailTypeRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::models::{
+        AuthorizationDetailModel, AuthorizationDetailTypeCreateModel, AuthorizationDetailTypeModel,
+    },
+};
+
+pub struct AuthorizationDetailService;
+
+impl AuthorizationDetailService {
+    pub async fn get_from_json(
+        db_context: &Arc<DbContext>,
+        authorization_detail_type_repository: &dyn AuthorizationDetailTypeRepository,
+        client_id: &str,
+        authorization_details: &str,
+    ) -> Result<Vec<AuthorizationDetailModel>, AuthorizationDetailServiceError> {
+        tracing::trace!(method = "get_from_json", client_id, authorization_details);
+
+        let details = serde_json::from_str::<Vec<AuthorizationDetailModel>>(authorization_details)
+            .map_err(|err| {
+                tracing::error!(error = %err);
+                AuthorizationDetailServiceError::InvalidAuthorizationDetails
+            })?;
+
+        let mut type_names = details
+            .iter()
+            .map(|d| d.detail_type.to_owned())
+            .collect::<Vec<String>>();
+        type_names.sort();
+        type_names.dedup();
+
+        let detail_types = authorization_detail_type_repository
+            .get_from_list(db_context, client_id, &type_names)
+            .await
+            .map_err(AuthorizationDetailServiceError::from)?;
+
+        for detail in details.iter() {
+            let Some(detail_type) = detail_types.iter().find(|t| t.name == detail.detail_type)
+            else {
+                tracing::error!(
+                    error = "Unknown authorization detail type requested",
+                    detail_type = detail.detail_type
+                );
+                return Err(AuthorizationDetailServiceError::UnknownType);
+            };
+
+            if !detail_type.schema.is_valid(detail) {
+                tracing::error!(
+                    error = "Authorization detail does not match registered schema",
+                    detail_type = detail.detail_type
+                );
+                return Err(AuthorizationDetailServiceError::InvalidAuthorizationDetails);
+            }
+        }
+
+        Ok(details)
+    }
+
+    pub fn is_subset(
+        requested: &[AuthorizationDetailModel],
+        granted: &[AuthorizationDetailModel],
+    ) -> bool {
+        requested.iter().all(|detail| granted.contains(detail))
+    }
+
+    pub async fn register_type(
+        db_context: &Arc<DbContext>,
+        authorization_detail_type_repository: &dyn AuthorizationDetailTypeRepository,
+        type_create: &AuthorizationDetailTypeCreateModel,
+    ) -> Result<AuthorizationDetailTypeModel, AuthorizationDetailServiceError> {
+        tracing::trace!(method = "register_type", ?type_create);
+
+        let detail_type = authorization_detail_type_repository
+            .create(db_context, type_create)
+            .await
+            .map_err(AuthorizationDetailServiceError::from)?;
+
+        tracing::info!("Authorization Detail Type created: {:?}", detail_type);
+
+        Ok(detail_type)
+    }
+
+    pub async fn get_types_by_client(
+        db_context: &Arc<DbContext>,
+        authorization_detail_type_repository: &dyn AuthorizationDetailTypeRepository,
+        client_id: &str,
+    ) -> Result<Vec<AuthorizationDetailTypeModel>, AuthorizationDetailServiceError> {
+        tracing::trace!(method = "get_types_by_client", client_id);
+
+        authorization_detail_type_repository
+            .get_all_by_client_id(db_context, client_id)
+            .await
+            .map_err(AuthorizationDetailServiceError::from)
+    }
+
+    pub async fn delete_type(
+        db_context: &Arc<DbContext>,
+        authorization_detail_type_repository: &dyn AuthorizationDetailTypeRepository,
+        client_id: &str,
+        name: &str,
+    ) -> Result<(), AuthorizationDetailServiceError> {
+        tracing::trace!(method = "delete_type", client_id, name);
+
+        authorization_detail_type_repository
+            .delete_by_name(db_context, client_id, name)
+            .await
+            .map_err(AuthorizationDetailServiceError::from)?;
+
+        tracing::info!(
+            "Authorization Detail Type deleted: {{ client_id: {}, name: {} }}",
+            client_id,
+            name
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AuthorizationDetailServiceError {
+    #[error("AUTHORIZATION DETAIL SERVICE ERROR :: Invalid authorization details")]
+    InvalidAuthorizationDetails,
+    #[error("AUTHORIZATION DETAIL SERVICE ERROR :: Unknown authorization detail type")]
+    UnknownType,
+    #[error("AUTHORIZATION DETAIL SERVICE ERROR :: Type already exists")]
+    AlreadyExists,
+    #[error("AUTHORIZATION DETAIL SERVICE ERROR :: Type not created")]
+    NotCreated,
+    #[error("AUTHORIZATION DETAIL SERVICE ERROR :: Type not found")]
+    NotFound,
+    #[error("AUTHORIZATION DETAIL SERVICE ERROR :: Type not deleted")]
+    NotDeleted,
+
+    #[error("AUTHORIZATION DETAIL SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for AuthorizationDetailServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            RepositoryError::QueryFailed(query_err) => match query_err {
+                QueryFailure::AlreadyExists => Self::AlreadyExists,
+                QueryFailure::NotCreated => Self::NotCreated,
+                QueryFailure::NotFound => Self::NotFound,
+                QueryFailure::NotDeleted => Self::NotDeleted,
+
+                _ => Self::InternalError,
+            },
+
+            RepositoryError::InternalError => Self::InternalError,
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use thiserror::Error;
+
+use crate::{
+    db::{
+        repositories::{PushedAuthorizationRequestRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::{models::AuthorizationRequestModel, services::TokenService},
+};
+
+/// rfc: https://www.rfc-editor.org/rfc/rfc9126#section-2.2
+const REQUEST_URI_PREFIX: &str = "urn:ietf:params:oauth:request_uri:";
+
+/// how long a client has to send the user to `/authorize` with a pushed request
+pub const PUSHED_AUTHORIZATION_REQUEST_TTL_SECONDS: i64 = 60;
+
+pub struct AuthorizationRequestService;
+
+impl AuthorizationRequestService {
+    /// Keeps the request under a new `request_uri`, returned alongside how many seconds it can be
+    /// used for.
+    pub async fn push(
+        db_context: &Arc<DbContext>,
+        pushed_authorization_request_repository: &dyn PushedAuthorizationRequestRepository,
+        authorization_request: &AuthorizationRequestModel,
+    ) -> Result<(String, i64), AuthorizationRequestServiceError> {
+        tracing::trace!(method = "push", ?authorization_request);
+
+        let request_uri = format!(
+            "{}{}",
+            REQUEST_URI_PREFIX,
+            TokenService::generate_opaque_token()
+                .map_err(|_| AuthorizationRequestServiceError::InternalError)?
+        );
+        let expires_at = (Utc::now() + Duration::seconds(PUSHED_AUTHORIZATION_REQUEST_TTL_SECONDS))
+            .timestamp_millis();
+
+        pushed_authorization_request_repository
+            .create(
+                db_context,
+                request_uri.as_str(),
+                authorization_request,
+                expires_at,
+            )
+            .await
+            .map_err(AuthorizationRequestServiceError::from)?;
+
+        tracing::info!(
+            "Authorization Request pushed: {{ client_id: {}, expires_at: {} }}",
+            authorization_request.client_id,
+            expires_at
+        );
+
+        Ok((request_uri, PUSHED_AUTHORIZATION_REQUEST_TTL_SECONDS))
+    }
+
+    /// Returns the request pushed under `request_uri`, as long as `client_id` pushed it.
+    pub async fn get(
+        db_context: &Arc<DbContext>,
+        pushed_authorization_request_repository: &dyn PushedAuthorizationRequestRepository,
+        client_id: &str,
+        request_uri: &str,
+    ) -> Result<AuthorizationRequestModel, AuthorizationRequestServiceError> {
+        tracing::trace!(method = "get", client_id);
+
+        let authorization_request = pushed_authorization_request_repository
+            .get_by_request_uri(db_context, request_uri)
+            .await
+            .map_err(AuthorizationRequestServiceError::from)?;
+
+        Self::ensure_client(authorization_request, client_id)
+    }
+
+    /// Spends the request pushed under `request_uri`, as long as `client_id` pushed it.
+    pub async fn take(
+        db_context: &Arc<DbContext>,
+        pushed_authorization_request_repository: &dyn PushedAuthorizationRequestRepository,
+        client_id: &str,
+        request_uri: &str,
+    ) -> Result<AuthorizationRequestModel, AuthorizationRequestServiceError> {
+        tracing::trace!(method = "take", client_id);
+
+        let authorization_request = pushed_authorization_request_repository
+            .take_by_request_uri(db_context, request_uri)
+            .await
+            .map_err(AuthorizationRequestServiceError::from)?;
+
+        Self::ensure_client(authorization_request, client_id)
+    }
+
+    fn ensure_client(
+        authorization_request: AuthorizationRequestModel,
+        client_id: &str,
+    ) -> Result<AuthorizationRequestModel, AuthorizationRequestServiceError> {
+        if authorization_request.client_id != client_id {
+            tracing::error!(error = "Request uri was pushed by a different client");
+            return Err(AuthorizationRequestServiceError::InvalidRequestUri);
+        }
+
+        Ok(authorization_request)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AuthorizationRequestServiceError {
+    #[error("AUTHORIZATION REQUEST SERVICE ERROR :: Not Created")]
+    NotCreated,
+    #[error("AUTHORIZATION REQUEST SERVICE ERROR :: Invalid Request Uri")]
+    InvalidRequestUri,
+
+    #[error("AUTHORIZATION REQUEST SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for AuthorizationRequestServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            RepositoryError::QueryFailed(query_err) => match query_err {
+                QueryFailure::NotCreated => Self::NotCreated,
+                QueryFailure::NotFound => Self::InvalidRequestUri,
+                _ => Self::InternalError,
+            },
+
+            _ => Self::InternalError,
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use std::{ops::Deref, sync::Arc};
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{Duration, Utc};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde_json::Value;
+use thiserror::Error;
+use url::{Host, Url};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        repositories::{BackchannelAuthorizationRepository, QueryFailure, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::models::{
+        AuthorizationDetailModel, BackchannelAuthorizationCreateModel,
+        BackchannelAuthorizationModel, BackchannelAuthorizationStatus, BackchannelDeliveryMode,
+        ScopeModel,
+    },
+    utils::public_endpoint::PublicEndpointUtil,
+};
+
+pub struct BackchannelAuthorizationService;
+
+impl BackchannelAuthorizationService {
+    pub const POLL_INTERVAL_SECONDS: i32 = 5;
+    pub const EXPIRES_IN_SECONDS: i64 = 300;
+    pub const MAX_BINDING_MESSAGE_LEN: usize = 64;
+    /// how many times a notification is sent before the client is given up on
+    pub const NOTIFICATION_ATTEMPTS: u32 = 3;
+    /// the wait before the first retry of a notification, doubled before each one after
+    pub const NOTIFICATION_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_backchannel_authorization(
+        db_context: &Arc<DbContext>,
+        backchannel_authorization_repository: &dyn BackchannelAuthorizationRepository,
+        client_id: &str,
+        user_id: &Uuid,
+        binding_message: Option<&str>,
+        delivery_mode: BackchannelDeliveryMode,
+        client_notification_token: Option<&str>,
+        client_notification_endpoint: Option<&str>,
+        scopes_model: ScopeModel,
+        authorization_details: &[AuthorizationDetailModel],
+    ) -> Result<BackchannelAuthorizationModel, BackchannelAuthorizationServiceError> {
+        tracing::trace!(
+            method = "create_backchannel_authorization",
+            client_id,
+            ?user_id,
+            binding_message,
+            ?delivery_mode,
+            scopes = ?scopes_model,
+            ?authorization_details
+        );
+
+        if binding_message.is_some_and(|m| m.chars().count() > Self::MAX_BINDING_MESSAGE_LEN) {
+            tracing::error!(error = "Binding message exceeds maximum length");
+            return Err(BackchannelAuthorizationServiceError::InvalidBindingMessage);
+        }
+
+        if delivery_mode != BackchannelDeliveryMode::Poll
+            && (client_notification_token.is_none() || client_notification_endpoint.is_none())
+        {
+            tracing::error!(
+                error =
+                    "Ping and push delivery modes require a client notification token and endpoint"
+            );
+            return Err(BackchannelAuthorizationServiceError::MissingClientNotification);
+        }
+
+        let expires_at = (Utc::now() + Duration::seconds(Self::EXPIRES_IN_SECONDS)).naive_utc();
+
+        let backchannel_authorization_create = BackchannelAuthorizationCreateModel::new(
+            Self::generate_auth_req_id()?.as_str(),
+            client_id,
+            user_id,
+            binding_message,
+            delivery_mode,
+            client_notification_token,
+            client_notification_endpoint,
+            Self::POLL_INTERVAL_SECONDS,
+            &expires_at,
+            scopes_model.deref(),
+            authorization_details,
+        );
+
+        let auth = backchannel_authorization_repository
+            .create(db_context, &backchannel_authorization_create)
+            .await
+            .map_err(BackchannelAuthorizationServiceError::from)?;
+
+        tracing::info!(
+            "Backchannel Authorization created: {{ client_id: {}, user_id: {}, delivery_mode: {:?}, expires_at: {}, scopes: {:?} }}",
+            client_id,
+            user_id,
+            delivery_mode,
+            auth.expires_at.timestamp(),
+            auth.scopes
+        );
+
+        Ok(auth)
+    }
+
+    pub async fn get_for_user(
+        db_context: &Arc<DbContext>,
+        backchannel_authorization_repository: &dyn BackchannelAuthorizationRepository,
+        user_id: &Uuid,
+        auth_req_id: &str,
+    ) -> Result<BackchannelAuthorizationModel, BackchannelAuthorizationServiceError> {
+        tracing::trace!(method = "get_for_user", ?user_id);
+
+        let auth = backchannel_authorization_repository
+            .get_by_auth_req_id(db_context, auth_req_id)
+            .await
+            .map_err(BackchannelAuthorizationServiceError::from)?;
+
+        if &auth.user_id != user_id {
+            tracing::error!(error = "Backchannel authorization requested by non-owning user");
+            return Err(BackchannelAuthorizationServiceError::NotFound);
+        }
+
+        Ok(auth)
+    }
+
+    pub async fn resolve(
+        db_context: &Arc<DbContext>,
+        backchannel_authorization_repository: &dyn BackchannelAuthorizationRepository,
+        user_id: &Uuid,
+        auth_req_id: &str,
+        approved: bool,
+    ) -> Result<BackchannelAuthorizationModel, BackchannelAuthorizationServiceError> {
+        tracing::trace!(method = "resolve", ?user_id, approved);
+
+        Self::get_for_user(
+            db_context,
+            backchannel_authorization_repository,
+            user_id,
+            auth_req_id,
+        )
+        .await?;
+
+        let status = match approved {
+            true => BackchannelAuthorizationStatus::Approved,
+            false => BackchannelAuthorizationStatus::Denied,
+        };
+
+        let auth = backchannel_authorization_repository
+            .update_status_by_auth_req_id(db_context, auth_req_id, status)
+            .await
+            .map_err(BackchannelAuthorizationServiceError::from)?;
+
+        tracing::info!(
+            "Backchannel Authorization resolved: {{ client_id: {}, user_id: {}, status: {:?} }}",
+            auth.client_id,
+            user_id,
+            auth.status
+        );
+
+        Ok(auth)
+    }
+
+    /// Handles a token request for a backchannel authorization. Returns the authorization once
+    /// the user has approved it, which the caller has to `consume` in the same unit of work as
+    /// the tokens it issues for it, so that it is neither exchanged twice nor lost if issuing
+    /// them fails.
+    pub async fn poll(
+        db_context: &Arc<DbContext>,
+        backchannel_authorization_repository: &dyn BackchannelAuthorizationRepository,
+        client_id: &str,
+        auth_req_id: &str,
+    ) -> Result<BackchannelAuthorizationModel, BackchannelAuthorizationServiceError> {
+        tracing::trace!(method = "poll", client_id);
+
+        let auth = backchannel_authorization_repository
+            .get_by_auth_req_id(db_context, auth_req_id)
+            .await
+            .map_err(BackchannelAuthorizationServiceError::from)?;
+
+        if auth.client_id != client_id {
+            tracing::error!(error = "Backchannel authorization polled by non-owning client");
+            return Err(BackchannelAuthorizationServiceError::NotFound);
+        }
+
+        if auth.delivery_mode == BackchannelDeliveryMode::Push {
+            tracing::error!(error = "Push mode backchannel authorization polled");
+            return Err(BackchannelAuthorizationServiceError::InvalidDeliveryMode);
+        }
+
+        match auth.status {
+            BackchannelAuthorizationStatus::Pending => {
+                let now = Utc::now().naive_utc();
+                let is_too_fast = auth.last_polled_at.is_some_and(|last_polled_at| {
+                    last_polled_at + Duration::seconds(auth.poll_interval.into()) > now
+                });
+
+                backchannel_authorization_repository
+                    .update_last_polled_at_by_auth_req_id(db_context, auth_req_id, &now)
+                    .await
+                    .map_err(BackchannelAuthorizationServiceError::from)?;
+
+                match is_too_fast {
+                    true => Err(BackchannelAuthorizationServiceError::SlowDown),
+                    false => Err(BackchannelAuthorizationServiceError::AuthorizationPending),
+                }
+            }
+            BackchannelAuthorizationStatus::Denied => {
+                Self::consume(
+                    db_context,
+                    backchannel_authorization_repository,
+                    auth_req_id,
+                )
+                .await?;
+
+                Err(BackchannelAuthorizationServiceError::AccessDenied)
+            }
+            BackchannelAuthorizationStatus::Approved => Ok(auth),
+        }
+    }
+
+    pub async fn consume(
+        db_context: &Arc<DbContext>,
+        backchannel_authorization_repository: &dyn BackchannelAuthorizationRepository,
+        auth_req_id: &str,
+    ) -> Result<(), BackchannelAuthorizationServiceError> {
+        tracing::trace!(method = "consume");
+
+        backchannel_authorization_repository
+            .delete_by_auth_req_id(db_context, auth_req_id)
+            .await
+            .map_err(BackchannelAuthorizationServiceError::from)
+    }
+
+    /// Sends a ping or push notification to the client notification endpoint registered with the
+    /// backchannel authorization. The endpoint is checked to be public again right before it is
+    /// called, the request goes to the addresses that check resolved, and redirects are not
+    /// followed, so the host cannot point it anywhere else in the meantime.
+    pub async fn notify_client(
+        auth: &BackchannelAuthorizationModel,
+        body: &Value,
+        allow_private_endpoints: bool,
+    ) -> Result<(), BackchannelAuthorizationServiceError> {
+        tracing::trace!(method = "notify_client", ?auth);
+
+        let (Some(endpoint), Some(token)) = (
+            auth.client_notification_endpoint.as_deref(),
+            auth.client_notification_token.as_deref(),
+        ) else {
+            tracing::error!(error = "Missing client notification endpoint or token");
+            return Err(BackchannelAuthorizationServiceError::MissingClientNotification);
+        };
+
+        let endpoint = Url::parse(endpoint).map_err(|err| {
+            tracing::error!(error = %err);
+            BackchannelAuthorizationServiceError::NotificationFailed
+        })?;
+
+        let addrs = PublicEndpointUtil::resolve(&endpoint, allow_private_endpoints)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = %err);
+                BackchannelAuthorizationServiceError::NotificationFailed
+            })?;
+
+        let mut client = reqwest::Client::builder().redirect(reqwest::redirect::Policy::none());
+
+        if let Some(Host::Domain(domain)) = endpoint.host() {
+            client = client.resolve_to_addrs(domain, &addrs);
+        }
+
+        let client = client.build().map_err(|err| {
+            tracing::error!(error = %err);
+            BackchannelAuthorizationServiceError::InternalError
+        })?;
+
+        let response = client
+            .post(endpoint)
+            .bearer_auth(token)
+            .json(body)
+            .timeout(std::time::Duration::from_secs(5))
+            .send()
+            .await
+            .map_err(|err| {
+                tracing::error!(error = %err);
+                BackchannelAuthorizationServiceError::NotificationFailed
+            })?;
+
+        if !response.status().is_success() {
+            tracing::error!(
+                error = "Client notification endpoint rejected notification",
+                status = response.status().as_u16()
+            );
+            return Err(BackchannelAuthorizationServiceError::NotificationFailed);
+        }
+
+        tracing::info!(
+            "Client notified of backchannel authorization: {{ client_id: {}, delivery_mode: {:?} }}",
+            auth.client_id,
+            auth.delivery_mode
+        );
+
+        Ok(())
+    }
+
+    /// Sends a notification like `notify_client`, retrying while the client notification endpoint
+    /// is unreachable or rejects it.
+    pub async fn notify_client_with_retry(
+        auth: &BackchannelAuthorizationModel,
+        body: &Value,
+        allow_private_endpoints: bool,
+    ) -> Result<(), BackchannelAuthorizationServiceError> {
+        tracing::trace!(method = "notify_client_with_retry", ?auth);
+
+        let mut attempt = 1;
+        let mut delay = Self::NOTIFICATION_RETRY_DELAY;
+
+        loop {
+            match Self::notify_client(auth, body, allow_private_endpoints).await {
+                Err(BackchannelAuthorizationServiceError::NotificationFailed)
+                    if attempt < Self::NOTIFICATION_ATTEMPTS =>
+                {
+                    tracing::warn!(
+                        "Retrying client notification: {{ client_id: {}, attempt: {} }}",
+                        auth.client_id,
+                        attempt
+                    );
+
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    pub fn generate_auth_req_id() -> Result<String, BackchannelAuthorizationServiceError> {
+        let mut buffer = [0u8; 32];
+        let rng = SystemRandom::new();
+        rng.fill(&mut buffer).map_err(|_| {
+            tracing::error!(error = "Filling SystemRandom failed on generate_auth_req_id",);
+
+            BackchannelAuthorizationServiceError::InternalError
+        })?;
+        let auth_req_id = general_purpose::URL_SAFE_NO_PAD.encode(buffer);
+
+        Ok(auth_req_id)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum BackchannelAuthorizationServiceError {
+    #[error("BACKCHANNEL AUTHORIZATION SERVICE ERROR :: Invalid binding message")]
+    InvalidBindingMessage,
+    #[error("BACKCHANNEL AUTHORIZATION SERVICE ERROR :: Missing client notification")]
+    MissingClientNotification,
+    #[error("BACKCHANNEL AUTHORIZATION SERVICE ERROR :: Invalid delivery mode")]
+    InvalidDeliveryMode,
+    #[error("BACKCHANNEL AUTHORIZATION SERVICE ERROR :: Authorization pending")]
+    AuthorizationPending,
+    #[error("BACKCHANNEL AUTHORIZATION SERVICE ERROR :: Slow down")]
+    SlowDown,
+    #[error("BACKCHANNEL AUTHORIZATION SERVICE ERROR :: Access denied")]
+    AccessDenied,
+    #[error("BACKCHANNEL AUTHORIZATION SERVICE ERROR :: Client notification failed")]
+    NotificationFailed,
+    #[error("BACKCHANNEL AUTHORIZATION SERVICE ERROR :: Not created")]
+    NotCreated,
+    #[error("BACKCHANNEL AUTHORIZATION SERVICE ERROR :: Not found")]
+    NotFound,
+    #[error("BACKCHANNEL AUTHORIZATION SERVICE ERROR :: Not updated")]
+    NotUpdated,
+    #[error("BACKCHANNEL AUTHORIZATION SERVICE ERROR :: Not deleted")]
+    NotDeleted,
+
+    #[error("BACKCHANNEL AUTHORIZATION SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for BackchannelAuthorizationServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            RepositoryError::QueryFailed(query_err) => match query_err {
+                QueryFailure::NotCreated => Self::NotCreated,
+                QueryFailure::NotFound => Self::NotFound,
+                QueryFailure::NotUpdated => Self::NotUpdated,
+                QueryFailure::NotDeleted => Self::NotDeleted,
+
+                _ => Self::InternalError,
+            },
+
+            RepositoryError::InternalError => Self::InternalError,
+        }
+    }
+}
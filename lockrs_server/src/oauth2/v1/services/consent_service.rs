@@ -0,0 +1,189 @@
+use std::{ops::Deref, sync::Arc};
+
+use chrono::{Duration, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        repositories::{ConsentRepository, QueryFailure, RefreshTokenRepository, RepositoryError},
+        DbContext,
+    },
+    oauth2::v1::models::{AuthorizationDetailModel, ConsentCreateModel, ConsentModel, ScopeModel},
+};
+
+pub struct ConsentService;
+
+impl ConsentService {
+    /// Records the scopes and authorization details a user has granted to a client. Anything
+    /// granted previously is kept, so that a grant only ever widens what the user has already
+    /// agreed to.
+    pub async fn grant(
+        db_context: &Arc<DbContext>,
+        consent_repository: &dyn ConsentRepository,
+        user_id: &Uuid,
+        client_id: &str,
+        scopes_model: ScopeModel,
+        authorization_details: &[AuthorizationDetailModel],
+        remember_for: Option<Duration>,
+    ) -> Result<ConsentModel, ConsentServiceError> {
+        tracing::trace!(
+            method = "grant",
+            ?user_id,
+            client_id,
+            scopes = ?scopes_model,
+            ?authorization_details,
+            ?remember_for
+        );
+
+        let (mut scopes, mut granted_details) = match consent_repository
+            .get_by_user_id_and_client_id(db_context, user_id, client_id)
+            .await
+        {
+            Ok(consent) => (consent.scopes, consent.authorization_details),
+            Err(RepositoryError::QueryFailed(QueryFailure::NotFound)) => (Vec::new(), Vec::new()),
+            Err(err) => return Err(ConsentServiceError::from(err)),
+        };
+
+        for scope in scopes_model.deref() {
+            if !scopes.contains(scope) {
+                scopes.push(scope.to_owned());
+            }
+        }
+
+        for detail in authorization_details {
+            if !granted_details.contains(detail) {
+                granted_details.push(detail.to_owned());
+            }
+        }
+
+        let expires_at = remember_for.map(|duration| (Utc::now() + duration).naive_utc());
+
+        let consent_create = ConsentCreateModel::new(
+            user_id,
+            client_id,
+            &scopes,
+            expires_at.as_ref(),
+            &granted_details,
+        );
+
+        let consent = consent_repository
+            .upsert(db_context, &consent_create)
+            .await
+            .map_err(ConsentServiceError::from)?;
+
+        tracing::info!(
+            "Consent granted: {{ user_id: {}, client_id: {}, scopes: {:?} }}",
+            user_id,
+            client_id,
+            consent.scopes
+        );
+
+        Ok(consent)
+    }
+
+    /// Returns whether the user has already granted the client every one of the requested scopes
+    /// and authorization details.
+    pub async fn is_granted(
+        db_context: &Arc<DbContext>,
+        consent_repository: &dyn ConsentRepository,
+        user_id: &Uuid,
+        client_id: &str,
+        scopes_model: &ScopeModel,
+        authorization_details: &[AuthorizationDetailModel],
+    ) -> Result<bool, ConsentServiceError> {
+        tracing::trace!(
+            method = "is_granted",
+            ?user_id,
+            client_id,
+            scopes = ?scopes_model,
+            ?authorization_details
+        );
+
+        match consent_repository
+            .get_by_user_id_and_client_id(db_context, user_id, client_id)
+            .await
+        {
+            Ok(consent) => Ok(consent.covers(scopes_model, authorization_details)),
+            Err(RepositoryError::QueryFailed(QueryFailure::NotFound)) => Ok(false),
+            Err(err) => Err(ConsentServiceError::from(err)),
+        }
+    }
+
+    pub async fn get_consents_for_user(
+        db_context: &Arc<DbContext>,
+        consent_repository: &dyn ConsentRepository,
+        user_id: &Uuid,
+    ) -> Result<Vec<ConsentModel>, ConsentServiceError> {
+        tracing::trace!(method = "get_consents_for_user", ?user_id);
+
+        consent_repository
+            .get_all_by_user_id(db_context, user_id)
+            .await
+            .map_err(ConsentServiceError::from)
+    }
+
+    /// Revokes a user's consent for a client, along with every refresh token issued to the client
+    /// on the user's behalf.
+    pub async fn revoke(
+        db_context: &Arc<DbContext>,
+        consent_repository: &dyn ConsentRepository,
+        refresh_token_repository: &dyn RefreshTokenRepository,
+        user_id: &Uuid,
+        client_id: &str,
+    ) -> Result<(), ConsentServiceError> {
+        tracing::trace!(method = "revoke", ?user_id, client_id);
+
+        // revoke tokens first, so a failure part way through never leaves live tokens behind a
+        // consent that no longer exists
+        let revoked_tokens = refresh_token_repository
+            .revoke_all_by_user_id_and_client_id(db_context, user_id, client_id)
+            .await
+            .map_err(ConsentServiceError::from)?;
+
+        consent_repository
+            .delete_by_user_id_and_client_id(db_context, user_id, client_id)
+            .await
+            .map_err(ConsentServiceError::from)?;
+
+        tracing::info!(
+            "Consent revoked: {{ user_id: {}, client_id: {}, revoked_tokens: {} }}",
+            user_id,
+            client_id,
+            revoked_tokens
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ConsentServiceError {
+    #[error("CONSENT SERVICE ERROR :: Not created")]
+    NotCreated,
+    #[error("CONSENT SERVICE ERROR :: Not found")]
+    NotFound,
+    #[error("CONSENT SERVICE ERROR :: Not deleted")]
+    NotDeleted,
+
+    #[error("CONSENT SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for ConsentServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        match err {
+            RepositoryError::QueryFailed(query_err) => match query_err {
+                QueryFailure::NotCreated => Self::NotCreated,
+                QueryFailure::NotFound => Self::NotFound,
+                QueryFailure::NotDeleted => Self::NotDeleted,
+
+                _ => Self::InternalError,
+            },
+
+            RepositoryError::InternalError => Self::InternalError,
+        }
+    }
+}
@@ -1,11 +1,18 @@
 mod access_token_service;
 mod authorization_code_service;
+mod authorization_detail_service;
+mod authorization_request_service;
+mod backchannel_authorization_service;
+mod consent_service;
 mod device_authorization_service;
+mod reaper_service;
 mod refresh_token_service;
 mod scope_service;
 mod token_service;
 
 pub use self::{
-    access_token_service::*, authorization_code_service::*, device_authorization_service::*,
-    refresh_token_service::*, scope_service::*, token_service::*,
+    access_token_service::*, authorization_code_service::*, authorization_detail_service::*,
+    authorization_request_service::*, backchannel_authorization_service::*, consent_service::*,
+    device_authorization_service::*, reaper_service::*, refresh_token_service::*, scope_service::*,
+    token_service::*,
 };
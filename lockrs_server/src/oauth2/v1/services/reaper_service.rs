@@ -0,0 +1,176 @@
+use std::{future::Future, sync::Arc};
+
+use chrono::{Duration, Utc};
+use thiserror::Error;
+use tokio::{
+    task::JoinHandle,
+    time::{self, MissedTickBehavior},
+};
+
+use crate::{
+    db::{
+        repositories::{
+            AccessTokenRepository, AuthorizationCodeRepository, DeviceAuthorizationRepository,
+            RefreshTokenRepository, RepositoryError,
+        },
+        DbContext,
+    },
+    oauth2::v1::models::PurgeReportModel,
+    AppState,
+};
+
+pub struct ReaperService;
+
+impl ReaperService {
+    /// Runs `purge_expired` every `gc_interval` for the lifetime of the server. A failed run is
+    /// logged and retried on the next tick.
+    pub fn spawn(state: AppState) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let period = state
+                .config
+                .gc_interval
+                .to_std()
+                .expect("GC_INTERVAL must be positive!");
+
+            let mut interval = time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                interval.tick().await;
+
+                let repository_container = state.repository_container.as_ref();
+
+                let purge_result = Self::purge_expired(
+                    &state.db_context,
+                    &*repository_container.access_token_repository,
+                    &*repository_container.refresh_token_repository,
+                    &*repository_container.authorization_code_repository,
+                    &*repository_container.device_authorization_repository,
+                    state.config.gc_batch_size,
+                    &state.config.gc_retention,
+                )
+                .await;
+
+                if let Err(err) = purge_result {
+                    tracing::error!(error = %err);
+                }
+            }
+        })
+    }
+
+    /// Deletes every row that expired more than `retention` ago, `batch_size` rows at a time.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn purge_expired(
+        db_context: &Arc<DbContext>,
+        access_token_repository: &dyn AccessTokenRepository,
+        refresh_token_repository: &dyn RefreshTokenRepository,
+        authorization_code_repository: &dyn AuthorizationCodeRepository,
+        device_authorization_repository: &dyn DeviceAuthorizationRepository,
+        batch_size: i64,
+        retention: &Duration,
+    ) -> Result<PurgeReportModel, ReaperServiceError> {
+        tracing::trace!(method = "purge_expired", batch_size, ?retention);
+
+        if batch_size < 1 {
+            return Err(ReaperServiceError::InvalidBatchSize);
+        }
+
+        let started_at = Utc::now();
+        let expired_before = &(started_at - *retention).naive_utc();
+
+        // refresh tokens go first, as an access token is only purged once no refresh token
+        // references it
+        let report = PurgeReportModel {
+            refresh_tokens: Self::purge_in_batches("refresh_tokens", batch_size, || {
+                refresh_token_repository.delete_expired(db_context, expired_before, batch_size)
+            })
+            .await?,
+            access_tokens: Self::purge_in_batches("access_tokens", batch_size, || {
+                access_token_repository.delete_expired(db_context, expired_before, batch_size)
+            })
+            .await?,
+            authorization_codes: Self::purge_in_batches("authorization_codes", batch_size, || {
+                authorization_code_repository.delete_expired(db_context, expired_before, batch_size)
+            })
+            .await?,
+            device_authorizations: Self::purge_in_batches(
+                "device_authorizations",
+                batch_size,
+                || {
+                    device_authorization_repository.delete_expired(
+                        db_context,
+                        expired_before,
+                        batch_size,
+                    )
+                },
+            )
+            .await?,
+        };
+
+        let elapsed = Utc::now() - started_at;
+
+        metrics::increment_counter!("lockrs_gc_runs_total");
+        metrics::histogram!(
+            "lockrs_gc_run_duration_seconds",
+            elapsed.num_milliseconds() as f64 / 1000.0
+        );
+
+        tracing::info!(
+            "Expired rows purged: {{ expired_before: {}, total: {}, report: {:?} }}",
+            expired_before.timestamp(),
+            report.total(),
+            report
+        );
+
+        Ok(report)
+    }
+
+    async fn purge_in_batches<F, Fut>(
+        table: &'static str,
+        batch_size: i64,
+        delete_batch: F,
+    ) -> Result<usize, ReaperServiceError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<usize, RepositoryError>>,
+    {
+        let mut purged = 0;
+
+        loop {
+            let deleted = delete_batch().await.map_err(|err| {
+                metrics::increment_counter!("lockrs_gc_errors_total", "table" => table);
+                ReaperServiceError::from(err)
+            })?;
+
+            purged += deleted;
+            metrics::counter!("lockrs_gc_purged_rows_total", deleted as u64, "table" => table);
+
+            // a short batch means nothing expired is left (or everything left is locked), and
+            // yielding between full batches keeps the reaper from starving request handlers
+            if deleted < batch_size as usize {
+                break;
+            }
+
+            tokio::task::yield_now().await;
+        }
+
+        Ok(purged)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ReaperServiceError {
+    #[error("REAPER SERVICE ERROR :: Invalid batch size")]
+    InvalidBatchSize,
+
+    #[error("REAPER SERVICE ERROR :: Internal Error")]
+    InternalError,
+}
+
+impl From<RepositoryError> for ReaperServiceError {
+    fn from(err: RepositoryError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
@@ -7,12 +7,17 @@ use crate::{
         repositories::{QueryFailure, RepositoryError, ScopeRepository},
         DbContext,
     },
-    oauth2::v1::models::ScopeModel,
+    oauth2::v1::models::{
+        AllowedScopeModel, ScopeCreateModel, ScopeDefinitionModel, ScopeModel, ScopeUpdateModel,
+    },
 };
 
 pub struct ScopeService;
 
 impl ScopeService {
+    pub const MAX_NAME_LEN: usize = 64;
+
+    /// Validates a space delimited list of scopes, failing if any one of them is unknown.
     pub async fn get_from_list(
         db_context: &Arc<DbContext>,
         scope_repository: &dyn ScopeRepository,
@@ -20,22 +25,302 @@ impl ScopeService {
     ) -> Result<ScopeModel, ScopeServiceError> {
         tracing::trace!(method = "get_from_list", scope);
 
-        let scopes_list = scope
-            .split(' ')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let scopes_list = Self::parse_list(scope);
+
+        Self::get_known(db_context, scope_repository, &scopes_list).await
+    }
+
+    /// Resolves the scopes requested by a client, falling back to the client's default scopes when
+    /// none are requested. Fails if any requested scope is unknown or not allowed for the client.
+    pub async fn get_for_client(
+        db_context: &Arc<DbContext>,
+        scope_repository: &dyn ScopeRepository,
+        client_id: &str,
+        scope: Option<&str>,
+    ) -> Result<ScopeModel, ScopeServiceError> {
+        tracing::trace!(method = "get_for_client", client_id, scope);
+
+        let allowed_scopes = scope_repository
+            .get_allowed_by_client_id(db_context, client_id)
+            .await
+            .map_err(ScopeServiceError::from)?;
+
+        let scopes_list = match scope {
+            Some(scope) => Self::parse_list(scope),
+            None => allowed_scopes
+                .iter()
+                .filter(|allowed_scope| allowed_scope.is_default)
+                .map(|allowed_scope| allowed_scope.scope.to_owned())
+                .collect::<Vec<String>>(),
+        };
+
+        // clients without an allowlist may use the global scopes and their own scopes
+        let is_allowed = |requested: &String| match allowed_scopes.is_empty() {
+            true => Self::is_available_to_client(client_id, requested),
+            false => allowed_scopes
+                .iter()
+                .any(|allowed_scope| &allowed_scope.scope == requested),
+        };
+
+        if let Some(requested) = scopes_list.iter().find(|requested| !is_allowed(requested)) {
+            tracing::error!(
+                error = "Requested scope not allowed for client",
+                scope = requested
+            );
+            return Err(ScopeServiceError::InvalidScopes);
+        }
+
+        Self::get_known(db_context, scope_repository, &scopes_list).await
+    }
+
+    pub async fn get_allowed_scopes(
+        db_context: &Arc<DbContext>,
+        scope_repository: &dyn ScopeRepository,
+        client_id: &str,
+    ) -> Result<Vec<AllowedScopeModel>, ScopeServiceError> {
+        tracing::trace!(method = "get_allowed_scopes", client_id);
 
         scope_repository
-            .get_from_list(db_context, &scopes_list)
+            .get_allowed_by_client_id(db_context, client_id)
             .await
             .map_err(ScopeServiceError::from)
     }
+
+    /// Replaces the scopes a client is allowed to request. Default scopes must also be allowed.
+    pub async fn set_allowed_scopes(
+        db_context: &Arc<DbContext>,
+        scope_repository: &dyn ScopeRepository,
+        client_id: &str,
+        allowed: &[String],
+        defaults: &[String],
+    ) -> Result<Vec<AllowedScopeModel>, ScopeServiceError> {
+        tracing::trace!(
+            method = "set_allowed_scopes",
+            client_id,
+            ?allowed,
+            ?defaults
+        );
+
+        if defaults.iter().any(|default| !allowed.contains(default)) {
+            tracing::error!(error = "Default scopes must be a subset of the allowed scopes");
+            return Err(ScopeServiceError::InvalidScopes);
+        }
+
+        // a client may never be allowed another client's private scopes
+        if allowed
+            .iter()
+            .any(|scope| !Self::is_available_to_client(client_id, scope))
+        {
+            tracing::error!(error = "Allowed scopes include a scope owned by another client");
+            return Err(ScopeServiceError::InvalidScopes);
+        }
+
+        let allowed = Self::get_known(db_context, scope_repository, allowed).await?;
+
+        let allowed_scopes = allowed
+            .iter()
+            .map(|scope| AllowedScopeModel::new(scope, defaults.contains(scope)))
+            .collect::<Vec<AllowedScopeModel>>();
+
+        let allowed_scopes = scope_repository
+            .replace_allowed_by_client_id(db_context, client_id, &allowed_scopes)
+            .await
+            .map_err(ScopeServiceError::from)?;
+
+        tracing::info!(
+            "Allowed scopes updated: {{ client_id: {}, allowed_scopes: {:?} }}",
+            client_id,
+            allowed_scopes
+        );
+
+        Ok(allowed_scopes)
+    }
+
+    pub async fn create_scope(
+        db_context: &Arc<DbContext>,
+        scope_repository: &dyn ScopeRepository,
+        client_id: &str,
+        name: &str,
+        description: &str,
+        consent_text: Option<&str>,
+    ) -> Result<ScopeDefinitionModel, ScopeServiceError> {
+        tracing::trace!(method = "create_scope", client_id, name);
+
+        Self::validate_name(name)?;
+
+        let scope_create = ScopeCreateModel::new(
+            client_id,
+            Self::namespaced_name(client_id, name).as_str(),
+            description,
+            consent_text,
+        );
+
+        let scope = scope_repository
+            .create(db_context, &scope_create)
+            .await
+            .map_err(ScopeServiceError::from)?;
+
+        tracing::info!("Scope created: {:?}", scope);
+
+        Ok(scope)
+    }
+
+    pub async fn get_scopes_by_client(
+        db_context: &Arc<DbContext>,
+        scope_repository: &dyn ScopeRepository,
+        client_id: &str,
+    ) -> Result<Vec<ScopeDefinitionModel>, ScopeServiceError> {
+        tracing::trace!(method = "get_scopes_by_client", client_id);
+
+        scope_repository
+            .get_all_by_client_id(db_context, client_id)
+            .await
+            .map_err(ScopeServiceError::from)
+    }
+
+    pub async fn update_scope(
+        db_context: &Arc<DbContext>,
+        scope_repository: &dyn ScopeRepository,
+        client_id: &str,
+        name: &str,
+        scope_update: &ScopeUpdateModel,
+    ) -> Result<ScopeDefinitionModel, ScopeServiceError> {
+        tracing::trace!(method = "update_scope", client_id, name, ?scope_update);
+
+        if scope_update.description.is_none() && scope_update.consent_text.is_none() {
+            tracing::error!(error = "Scope update contains no changes");
+            return Err(ScopeServiceError::NotUpdated);
+        }
+
+        let scope = scope_repository
+            .update_by_name(
+                db_context,
+                client_id,
+                Self::namespaced_name(client_id, name).as_str(),
+                scope_update,
+            )
+            .await
+            .map_err(ScopeServiceError::from)?;
+
+        tracing::info!("Scope updated: {:?}", scope);
+
+        Ok(scope)
+    }
+
+    pub async fn delete_scope(
+        db_context: &Arc<DbContext>,
+        scope_repository: &dyn ScopeRepository,
+        client_id: &str,
+        name: &str,
+    ) -> Result<(), ScopeServiceError> {
+        tracing::trace!(method = "delete_scope", client_id, name);
+
+        scope_repository
+            .delete_by_name(
+                db_context,
+                client_id,
+                Self::namespaced_name(client_id, name).as_str(),
+            )
+            .await
+            .map_err(ScopeServiceError::from)?;
+
+        tracing::info!(
+            "Scope deleted: {{ client_id: {}, name: {} }}",
+            client_id,
+            name
+        );
+
+        Ok(())
+    }
+
+    pub fn is_available_to_client(client_id: &str, scope: &str) -> bool {
+        match scope.split_once(':') {
+            Some((owner, _)) => owner == client_id,
+            None => true,
+        }
+    }
+
+    fn parse_list(scope: &str) -> Vec<String> {
+        let mut scopes_list = Vec::new();
+
+        for scope in scope.split_whitespace() {
+            if !scopes_list.iter().any(|s: &String| s == scope) {
+                scopes_list.push(scope.to_owned());
+            }
+        }
+
+        scopes_list
+    }
+
+    async fn get_known(
+        db_context: &Arc<DbContext>,
+        scope_repository: &dyn ScopeRepository,
+        scopes_list: &[String],
+    ) -> Result<ScopeModel, ScopeServiceError> {
+        if scopes_list.is_empty() {
+            tracing::error!(error = "No scopes requested");
+            return Err(ScopeServiceError::InvalidScopes);
+        }
+
+        let scopes = scope_repository
+            .get_from_list(db_context, scopes_list)
+            .await
+            .map_err(|err| match ScopeServiceError::from(err) {
+                ScopeServiceError::NotFound => ScopeServiceError::InvalidScopes,
+                err => err,
+            })?;
+
+        // unknown scopes are never silently dropped from a request
+        if scopes.len() != scopes_list.len() {
+            tracing::error!(
+                error = "Unknown scopes requested",
+                requested = ?scopes_list,
+                known = ?scopes
+            );
+            return Err(ScopeServiceError::InvalidScopes);
+        }
+
+        Ok(scopes)
+    }
+
+    /// Client scopes are prefixed with the owning client's id, so that they can never shadow a
+    /// global scope or a scope owned by another client.
+    pub fn namespaced_name(client_id: &str, name: &str) -> String {
+        format!("{}:{}", client_id, name)
+    }
+
+    pub fn validate_name(name: &str) -> Result<(), ScopeServiceError> {
+        let is_valid = !name.is_empty()
+            && name.len() <= Self::MAX_NAME_LEN
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "._-".contains(c));
+
+        if !is_valid {
+            tracing::error!(error = "Invalid scope name", name);
+            return Err(ScopeServiceError::InvalidName);
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Error)]
 pub enum ScopeServiceError {
     #[error("SCOPE SERVICE ERROR :: No valid scopes found")]
     InvalidScopes,
+    #[error("SCOPE SERVICE ERROR :: Invalid scope name")]
+    InvalidName,
+    #[error("SCOPE SERVICE ERROR :: Already exists")]
+    AlreadyExists,
+    #[error("SCOPE SERVICE ERROR :: Not created")]
+    NotCreated,
+    #[error("SCOPE SERVICE ERROR :: Not found")]
+    NotFound,
+    #[error("SCOPE SERVICE ERROR :: Not updated")]
+    NotUpdated,
+    #[error("SCOPE SERVICE ERROR :: Not deleted")]
+    NotDeleted,
 
     #[error("SCOPE SERVICE ERROR :: Internal Error")]
     InternalError,
@@ -47,9 +332,11 @@ impl From<RepositoryError> for ScopeServiceError {
 
         match err {
             RepositoryError::QueryFailed(query_err) => match query_err {
-                QueryFailure::NotFound => Self::InvalidScopes,
-
-                _ => Self::InternalError,
+                QueryFailure::AlreadyExists => Self::AlreadyExists,
+                QueryFailure::NotCreated => Self::NotCreated,
+                QueryFailure::NotFound => Self::NotFound,
+                QueryFailure::NotUpdated => Self::NotUpdated,
+                QueryFailure::NotDeleted => Self::NotDeleted,
             },
 
             RepositoryError::InternalError => Self::InternalError,
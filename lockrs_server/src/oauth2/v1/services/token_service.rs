@@ -1,18 +1,23 @@
 use std::{ops::Deref, sync::Arc};
 
 use base64::{engine::general_purpose, Engine as _};
-use chrono::{Duration, Utc};
+use chrono::Utc;
 use ring::rand::{SecureRandom, SystemRandom};
+use scoped_futures::ScopedFutureExt;
 use thiserror::Error;
 use uuid::Uuid;
 
 use crate::{
     db::{
         repositories::{AccessTokenRepository, RefreshTokenRepository},
-        DbContext,
+        DbContext, DbContextError,
     },
+    models::{ClientPolicyModel, GrantType},
     oauth2::v1::{
-        models::{AccessTokenCreateModel, RefreshTokenCreateModel, ScopeModel, TokenModel},
+        models::{
+            AccessTokenCreateModel, AuthorizationDetailModel, RefreshTokenCreateModel,
+            RefreshTokenModel, ScopeModel, TokenModel,
+        },
         services::{
             AccessTokenService, AccessTokenServiceError, RefreshTokenService,
             RefreshTokenServiceError,
@@ -23,63 +28,107 @@ use crate::{
 pub struct TokenService;
 
 impl TokenService {
+    /// a refresh token is only issued where the client policy allows one for `grant_type`.
+    /// `refreshed_token` is the refresh token being rotated, if any, so the new refresh token
+    /// stays within the lifetime of the original grant
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_token(
         db_context: &Arc<DbContext>,
         access_token_repository: &dyn AccessTokenRepository,
         refresh_token_repository: &dyn RefreshTokenRepository,
-        client_id: &str,
+        client_policy: &ClientPolicyModel,
+        grant_type: GrantType,
         user_id: Option<&Uuid>,
         scopes: ScopeModel,
+        authorization_details: &[AuthorizationDetailModel],
+        refreshed_token: Option<&RefreshTokenModel>,
     ) -> Result<TokenModel, TokenServiceError> {
-        tracing::trace!(method = "create_token", client_id, ?user_id, ?scopes);
-
-        let access_expiry = (Utc::now() + Duration::minutes(10)).naive_utc();
+        let client_id = client_policy.client_id.as_str();
 
-        let access_token_create = AccessTokenCreateModel::new(
-            Self::generate_opaque_token()?.as_str(),
+        tracing::trace!(
+            method = "create_token",
             client_id,
-            user_id,
-            &access_expiry,
-            scopes.deref(),
+            grant_type = grant_type.as_str(),
+            ?user_id,
+            ?scopes,
+            ?authorization_details,
+            ?refreshed_token
         );
 
-        let access_token = AccessTokenService::create_token(
-            db_context,
-            access_token_repository,
-            &access_token_create,
-        )
-        .await
-        .map_err(TokenServiceError::from)?;
-
-        let refresh_expiry = (Utc::now() + Duration::hours(24)).naive_utc();
+        let now = Utc::now().naive_utc();
+        let access_expiry = now + client_policy.access_token_lifetime;
 
-        let refresh_token_create = RefreshTokenCreateModel::new(
+        let access_token_create = AccessTokenCreateModel::new(
             Self::generate_opaque_token()?.as_str(),
-            access_token.id,
             client_id,
             user_id,
-            &refresh_expiry,
+            &access_expiry,
             scopes.deref(),
+            authorization_details,
         );
 
-        let refresh_token = RefreshTokenService::create_token(
-            db_context,
-            refresh_token_repository,
-            &refresh_token_create,
-        )
-        .await
-        .map_err(TokenServiceError::from)?;
+        let granted_scopes = scopes.deref();
+        let issues_refresh_token = client_policy.issues_refresh_token(grant_type, granted_scopes);
+
+        // the access and refresh token are issued as one unit of work, so a failure creating the
+        // refresh token never leaves an orphaned access token behind
+        let (access_token, refresh_token) = db_context
+            .transaction::<_, TokenServiceError, _>(|db_context| {
+                async move {
+                    let access_token = AccessTokenService::create_token(
+                        db_context,
+                        access_token_repository,
+                        &access_token_create,
+                    )
+                    .await
+                    .map_err(TokenServiceError::from)?;
+
+                    if !issues_refresh_token {
+                        return Ok((access_token, None));
+                    }
+
+                    let grant_created_at = refreshed_token
+                        .map(|refreshed_token| refreshed_token.grant_created_at)
+                        .unwrap_or(now);
+
+                    let refresh_expiry = client_policy.refresh_token_expires_at(
+                        &now,
+                        &grant_created_at,
+                        refreshed_token.map(|refreshed_token| &refreshed_token.expires_at),
+                    );
+
+                    let refresh_token_create = RefreshTokenCreateModel::new(
+                        Self::generate_opaque_token()?.as_str(),
+                        access_token.id,
+                        client_id,
+                        user_id,
+                        &refresh_expiry,
+                        granted_scopes,
+                        authorization_details,
+                        &grant_created_at,
+                    );
+
+                    let refresh_token = RefreshTokenService::create_token(
+                        db_context,
+                        refresh_token_repository,
+                        &refresh_token_create,
+                    )
+                    .await
+                    .map_err(TokenServiceError::from)?;
+
+                    Ok((access_token, Some(refresh_token.token)))
+                }
+                .scope_boxed()
+            })
+            .await?;
 
         let token = TokenModel::new(
             "Bearer",
-            5000,
+            client_policy.access_token_lifetime.num_seconds(),
             access_token.token.as_str(),
-            refresh_token.token.as_str(),
-            scopes
-                .deref()
-                .iter()
-                .fold(String::new(), |c, s| format!("{} {}", c, s))
-                .as_str(),
+            refresh_token.as_deref(),
+            scopes.join(" ").as_str(),
+            authorization_details,
         );
 
         tracing::info!(
@@ -91,6 +140,46 @@ impl TokenService {
         Ok(token)
     }
 
+    /// Deletes the access token, and refresh token if any, of a token pair issued by
+    /// `create_token`, e.g. when it never reached the client.
+    pub async fn revoke_token(
+        db_context: &Arc<DbContext>,
+        access_token_repository: &dyn AccessTokenRepository,
+        refresh_token_repository: &dyn RefreshTokenRepository,
+        token: &TokenModel,
+    ) -> Result<(), TokenServiceError> {
+        tracing::trace!(method = "revoke_token", scopes = token.scopes);
+
+        db_context
+            .transaction::<_, TokenServiceError, _>(|db_context| {
+                async move {
+                    if let Some(refresh_token) = token.refresh_token.as_deref() {
+                        RefreshTokenService::delete_token(
+                            db_context,
+                            refresh_token_repository,
+                            refresh_token,
+                        )
+                        .await
+                        .map_err(TokenServiceError::from)?;
+                    }
+
+                    AccessTokenService::delete_token(
+                        db_context,
+                        access_token_repository,
+                        token.access_token.as_str(),
+                    )
+                    .await
+                    .map_err(TokenServiceError::from)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        tracing::info!("Token revoked: {{ scopes: {:?} }}", &token.scopes);
+
+        Ok(())
+    }
+
     pub fn generate_opaque_token() -> Result<String, TokenServiceError> {
         let mut buffer = [0u8; 32];
         let rng = SystemRandom::new();
@@ -113,6 +202,14 @@ pub enum TokenServiceError {
     InternalError,
 }
 
+impl From<DbContextError> for TokenServiceError {
+    fn from(err: DbContextError) -> Self {
+        tracing::error!(error = %err);
+
+        Self::InternalError
+    }
+}
+
 impl From<AccessTokenServiceError> for TokenServiceError {
     fn from(err: AccessTokenServiceError) -> Self {
         tracing::error!(error = %err);
@@ -7,12 +7,16 @@ use hyper::StatusCode;
 
 use crate::{
     api::v1::controllers::{
-        ClientAuthController, ClientController, RedirectController, SessionController,
-        UserAuthController, UserController,
+        AccountController, AuthorizationDetailTypeController, BackchannelAuthorizationController,
+        ClientAuthController, ClientController, ClientPolicyController, ConsentController,
+        EmailVerificationController, FederationController, LockoutController, MagicLinkController,
+        MfaController, PasswordResetController, RedirectController, ScopeController,
+        SessionController, UserAuthController, UserController, WebauthnController,
     },
     middlewares::guards::*,
     oauth2::v1::controllers::{
-        AuthorizeController, DeviceAuthorizationController, TokenController,
+        AuthorizeController, BackchannelAuthenticationController, DeviceAuthorizationController,
+        IntrospectionController, TokenController, UserinfoController,
     },
     AppState,
 };
@@ -25,11 +29,18 @@ pub fn routes(state: &AppState) -> Router<AppState> {
             "/oauth2/v1",
             Router::new()
                 .route("/authorize", post(AuthorizeController::handle))
+                .route("/par", post(AuthorizeController::push))
                 .route(
                     "/device_authorization",
                     post(DeviceAuthorizationController::handle),
                 )
-                .route("/token", post(TokenController::handle)),
+                .route(
+                    "/bc-authorize",
+                    post(BackchannelAuthenticationController::handle),
+                )
+                .route("/token", post(TokenController::handle))
+                .route("/introspect", post(IntrospectionController::handle))
+                .route("/userinfo", get(UserinfoController::handle)),
         )
         // --------------------------------------   API ROUTES  ------------------------------------
         .nest(
@@ -41,7 +52,38 @@ pub fn routes(state: &AppState) -> Router<AppState> {
                         .route("/:client_id", get(ClientController::read))
                         .route("/:client_id", put(ClientController::update))
                         .route("/:client_id", delete(ClientController::delete))
+                        .route("/:client_id/policy", get(ClientPolicyController::read))
+                        .route("/:client_id/policy", put(ClientPolicyController::update))
+                        .route("/:client_id/policy", delete(ClientPolicyController::delete))
                         .route("/:client_id/redirects", get(RedirectController::read_all))
+                        .route(
+                            "/:client_id/secrets",
+                            post(ClientAuthController::rotate_secret),
+                        )
+                        .route(
+                            "/:client_id/authorization_details",
+                            get(AuthorizationDetailTypeController::read_all),
+                        )
+                        .route(
+                            "/:client_id/authorization_details",
+                            post(AuthorizationDetailTypeController::create),
+                        )
+                        .route(
+                            "/:client_id/authorization_details/:name",
+                            delete(AuthorizationDetailTypeController::delete),
+                        )
+                        .route("/:client_id/scopes", get(ScopeController::read_all))
+                        .route("/:client_id/scopes", post(ScopeController::create))
+                        .route("/:client_id/scopes/:name", put(ScopeController::update))
+                        .route("/:client_id/scopes/:name", delete(ScopeController::delete))
+                        .route(
+                            "/:client_id/allowed_scopes",
+                            get(ScopeController::read_allowed),
+                        )
+                        .route(
+                            "/:client_id/allowed_scopes",
+                            put(ScopeController::update_allowed),
+                        )
                         .layer(from_extractor_with_state::<ClientAuthGuard, AppState>(
                             state.clone(),
                         ))
@@ -64,6 +106,47 @@ pub fn routes(state: &AppState) -> Router<AppState> {
                         .route("/:user_id", put(UserController::update))
                         .route("/:user_id", delete(UserController::delete))
                         .route("/:user_id/clients", get(ClientController::read_all))
+                        .route(
+                            "/:user_id/backchannel_authorizations/:auth_req_id",
+                            get(BackchannelAuthorizationController::read),
+                        )
+                        .route(
+                            "/:user_id/backchannel_authorizations/:auth_req_id",
+                            put(BackchannelAuthorizationController::update),
+                        )
+                        .route("/:user_id/consents", get(ConsentController::read_all))
+                        .route("/:user_id/consents", post(ConsentController::create))
+                        .route(
+                            "/:user_id/consents/:client_id",
+                            delete(ConsentController::delete),
+                        )
+                        .route(
+                            "/:user_id/password",
+                            put(AccountController::change_password),
+                        )
+                        .route(
+                            "/:user_id/email",
+                            post(AccountController::request_email_change),
+                        )
+                        .route(
+                            "/:user_id/email",
+                            put(AccountController::confirm_email_change),
+                        )
+                        .route(
+                            "/:user_id/verify-email",
+                            post(EmailVerificationController::resend),
+                        )
+                        .route("/:user_id/mfa/totp", post(MfaController::enroll_totp))
+                        .route("/:user_id/mfa/totp", put(MfaController::confirm_totp))
+                        .route("/:user_id/mfa/totp", delete(MfaController::disable_totp))
+                        .route(
+                            "/:user_id/webauthn/credentials",
+                            get(WebauthnController::read_all),
+                        )
+                        .route(
+                            "/:user_id/webauthn/credentials/:credential_id",
+                            delete(WebauthnController::delete),
+                        )
                         .layer(from_extractor_with_state::<UserAuthGuard, AppState>(
                             state.clone(),
                         )),
@@ -84,7 +167,44 @@ pub fn routes(state: &AppState) -> Router<AppState> {
                     "/auth",
                     Router::new()
                         .route("/register", post(UserAuthController::register))
-                        .route("/login", post(UserAuthController::authenticate)),
+                        .route("/login", post(UserAuthController::authenticate))
+                        .route("/verify-email", post(EmailVerificationController::verify))
+                        .route("/password/forgot", post(PasswordResetController::forgot))
+                        .route("/password/reset", post(PasswordResetController::reset))
+                        .route("/magic-link", post(MagicLinkController::request))
+                        .route("/magic-link/redeem", post(MagicLinkController::redeem))
+                        .route("/federation", get(FederationController::read_all))
+                        .route("/federation/:provider", post(FederationController::begin))
+                        .route(
+                            "/federation/:provider/callback",
+                            post(FederationController::callback),
+                        )
+                        .route("/mfa", post(MfaController::verify))
+                        .route(
+                            "/webauthn/register/options",
+                            post(WebauthnController::registration_options),
+                        )
+                        .route("/webauthn/register", post(WebauthnController::register))
+                        .route(
+                            "/webauthn/login/options",
+                            post(WebauthnController::login_options),
+                        )
+                        .route("/webauthn/login", post(WebauthnController::login)),
+                )
+                .nest(
+                    "/admin",
+                    Router::new()
+                        .route(
+                            "/users/:user_id/lockout",
+                            delete(LockoutController::unlock_user),
+                        )
+                        .route(
+                            "/addresses/:address/lockout",
+                            delete(LockoutController::unlock_address),
+                        )
+                        .layer(from_extractor_with_state::<AdminAuthGuard, AppState>(
+                            state.clone(),
+                        )),
                 ),
         )
 }
@@ -0,0 +1,422 @@
+use hyper::{
+    header::{LINK, RETRY_AFTER},
+    StatusCode,
+};
+use lockrs_server::{api::v1::responses::UserResponse, AppConfig};
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::common::helpers::{TestApp, TestUser};
+
+async fn change_password(app: &TestApp, user: &TestUser, body: &Value) -> reqwest::Response {
+    app.get_client()
+        .put(&format!(
+            "{}/api/v1/users/{}/password",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .json(body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn request_email_change(
+    app: &TestApp,
+    user: &TestUser,
+    email: &str,
+    password: &str,
+) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/users/{}/email",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .json(&json!({
+            "email": email,
+            "current_password": password,
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn confirm_email_change(app: &TestApp, user: &TestUser, body: &Value) -> reqwest::Response {
+    app.get_client()
+        .put(&format!(
+            "{}/api/v1/users/{}/email",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .json(body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn login(app: &TestApp, email: &str, password: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/api/v1/auth/login", &app.get_address()))
+        .basic_auth(email, Some(password))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// the token in the link of the latest mail sent to `email`
+fn latest_token_for(app: &TestApp, email: &str) -> String {
+    let mail = app
+        .get_mailer()
+        .get_latest_for(email)
+        .expect("No confirmation mail was sent.");
+
+    let link = mail
+        .body
+        .split_whitespace()
+        .find_map(|word| Url::parse(word).ok())
+        .expect("Confirmation mail should contain a link.");
+
+    link.query_pairs()
+        .find(|(key, _)| key == "token")
+        .map(|(_, token)| token.into_owned())
+        .expect("Confirmation link should carry a token.")
+}
+
+/// whether the session is still active
+async fn is_active(app: &TestApp, user: &TestUser, session_id: &str) -> bool {
+    let state = app.get_state();
+
+    state
+        .repository_container
+        .session_repository
+        .get_by_hash(&state.db_context, session_id, user.get_id())
+        .await
+        .is_ok()
+}
+
+#[tokio::test]
+async fn change_password_sets_the_new_password_and_ends_other_sessions() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, other_session) = TestUser::generate_logged_in(&app).await;
+    let current_session = user.login(&app).await;
+
+    // Act
+    let response = change_password(
+        &app,
+        &user,
+        &json!({
+            "current_password": user.get_password(),
+            "new_password": "a new password",
+        }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    assert!(is_active(&app, &user, current_session.get_session_id()).await);
+    assert!(!is_active(&app, &user, other_session.get_session_id()).await);
+
+    let old_password_response = login(&app, user.get_email(), user.get_password()).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, old_password_response.status());
+
+    let new_password_response = login(&app, user.get_email(), "a new password").await;
+    assert_eq!(StatusCode::OK, new_password_response.status());
+}
+
+#[tokio::test]
+async fn change_password_keeps_other_sessions_when_asked_to() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, other_session) = TestUser::generate_logged_in(&app).await;
+    user.login(&app).await;
+
+    // Act
+    let response = change_password(
+        &app,
+        &user,
+        &json!({
+            "current_password": user.get_password(),
+            "new_password": "a new password",
+            "end_other_sessions": false,
+        }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+    assert!(is_active(&app, &user, other_session.get_session_id()).await);
+}
+
+#[tokio::test]
+async fn change_password_returns_a_403_for_a_wrong_current_password() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+
+    // Act
+    let response = change_password(
+        &app,
+        &user,
+        &json!({
+            "current_password": "not the password",
+            "new_password": "a new password",
+        }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    let login_response = login(&app, user.get_email(), user.get_password()).await;
+    assert_eq!(StatusCode::OK, login_response.status());
+}
+
+#[tokio::test]
+async fn change_password_locks_out_the_account_after_too_many_wrong_current_passwords() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(|config: &mut AppConfig| {
+        config.login_throttle.max_failures_per_account = 3;
+    })
+    .await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+
+    for _ in 0..3 {
+        let response = change_password(
+            &app,
+            &user,
+            &json!({
+                "current_password": "not the password",
+                "new_password": "a new password",
+            }),
+        )
+        .await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    }
+
+    // Act
+    let response = change_password(
+        &app,
+        &user,
+        &json!({
+            "current_password": user.get_password(),
+            "new_password": "a new password",
+        }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    assert!(response.headers().contains_key(RETRY_AFTER));
+
+    let login_response = login(&app, user.get_email(), user.get_password()).await;
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, login_response.status());
+}
+
+#[tokio::test]
+async fn update_user_is_deprecated_in_favour_of_the_password_and_email_endpoints() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+
+    // Act
+    let response = app
+        .get_client()
+        .put(&format!(
+            "{}/api/v1/users/{}",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .json(&json!({ "email": "new@example.com" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(StatusCode::GONE, response.status());
+    assert_eq!(
+        Some("true"),
+        response
+            .headers()
+            .get("deprecation")
+            .and_then(|value| value.to_str().ok())
+    );
+
+    let link = response
+        .headers()
+        .get(LINK)
+        .and_then(|value| value.to_str().ok())
+        .expect("Deprecation should link to the successors.");
+    assert!(link.contains(&format!("/api/v1/users/{}/password", user.get_id())));
+    assert!(link.contains(&format!("/api/v1/users/{}/email", user.get_id())));
+}
+
+#[tokio::test]
+async fn change_password_returns_a_401_without_a_session() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+
+    // Act
+    let response = change_password(
+        &app,
+        &user,
+        &json!({
+            "current_password": user.get_password(),
+            "new_password": "a new password",
+        }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}
+
+#[tokio::test]
+async fn change_email_swaps_the_address_once_confirmed_and_notifies_the_old_one() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, other_session) = TestUser::generate_logged_in(&app).await;
+    let current_session = user.login(&app).await;
+    let new_email = format!("new-{}", user.get_email());
+
+    let request_response =
+        request_email_change(&app, &user, new_email.as_str(), user.get_password()).await;
+    assert_eq!(StatusCode::ACCEPTED, request_response.status());
+
+    let token = latest_token_for(&app, new_email.as_str());
+
+    // Act
+    let response = confirm_email_change(&app, &user, &json!({ "token": token })).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let user_response = response
+        .json::<UserResponse>()
+        .await
+        .expect("Failed to read request body.");
+    assert_eq!(new_email, user_response.email);
+    assert!(user_response.email_verified);
+
+    let notification = app
+        .get_mailer()
+        .get_latest_for(user.get_email())
+        .expect("No notification was sent to the old address.");
+    assert!(notification.body.contains(new_email.as_str()));
+
+    assert!(is_active(&app, &user, current_session.get_session_id()).await);
+    assert!(!is_active(&app, &user, other_session.get_session_id()).await);
+
+    let login_response = login(&app, new_email.as_str(), user.get_password()).await;
+    assert_eq!(StatusCode::OK, login_response.status());
+}
+
+#[tokio::test]
+async fn change_email_leaves_the_address_alone_until_confirmed() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+    let new_email = format!("new-{}", user.get_email());
+
+    // Act
+    let response = request_email_change(&app, &user, new_email.as_str(), user.get_password()).await;
+
+    // Assert
+    assert_eq!(StatusCode::ACCEPTED, response.status());
+
+    let login_response = login(&app, user.get_email(), user.get_password()).await;
+    assert_eq!(StatusCode::OK, login_response.status());
+}
+
+#[tokio::test]
+async fn change_email_returns_a_409_for_an_address_in_use() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let other_user = TestUser::generate_stored(&app).await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+
+    // Act
+    let response =
+        request_email_change(&app, &user, other_user.get_email(), user.get_password()).await;
+
+    // Assert
+    assert_eq!(StatusCode::CONFLICT, response.status());
+    assert!(app
+        .get_mailer()
+        .get_latest_for(other_user.get_email())
+        .is_none());
+}
+
+#[tokio::test]
+async fn change_email_returns_a_403_for_a_wrong_current_password() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+    let new_email = format!("new-{}", user.get_email());
+
+    // Act
+    let response = request_email_change(&app, &user, new_email.as_str(), "not the password").await;
+
+    // Assert
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+    assert!(app
+        .get_mailer()
+        .get_latest_for(new_email.as_str())
+        .is_none());
+}
+
+#[tokio::test]
+async fn confirm_email_change_returns_a_400_once_the_address_changed_since() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+    let first_email = format!("first-{}", user.get_email());
+    let second_email = format!("second-{}", user.get_email());
+
+    request_email_change(&app, &user, first_email.as_str(), user.get_password()).await;
+    let first_token = latest_token_for(&app, first_email.as_str());
+    request_email_change(&app, &user, second_email.as_str(), user.get_password()).await;
+    let second_token = latest_token_for(&app, second_email.as_str());
+
+    assert_eq!(
+        StatusCode::OK,
+        confirm_email_change(&app, &user, &json!({ "token": second_token }))
+            .await
+            .status()
+    );
+
+    // Act
+    let response = confirm_email_change(&app, &user, &json!({ "token": first_token })).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn change_password_ends_other_sessions_with_sqlite_repositories() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let (user, other_session) = TestUser::generate_logged_in(&app).await;
+    let current_session = user.login(&app).await;
+
+    // Act
+    let response = change_password(
+        &app,
+        &user,
+        &json!({
+            "current_password": user.get_password(),
+            "new_password": "a new password",
+        }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    assert!(is_active(&app, &user, current_session.get_session_id()).await);
+    assert!(!is_active(&app, &user, other_session.get_session_id()).await);
+}
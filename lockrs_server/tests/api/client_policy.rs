@@ -0,0 +1,167 @@
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+async fn set_policy(app: &TestApp, client: &TestClient, body: &Value) -> reqwest::Response {
+    app.get_client()
+        .put(&format!(
+            "{}/api/v1/clients/{}/policy",
+            &app.get_address(),
+            client.get_id()
+        ))
+        .json(body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn request_token(app: &TestApp, client: &TestClient) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/oauth2/v1/token", &app.get_address()))
+        .basic_auth(client.get_id(), Some(client.get_secret()))
+        .query(&[("grant_type", "client_credentials"), ("scope", "read")])
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn read_policy_returns_the_default_policy_when_none_is_set() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    // Act
+    let response = app
+        .get_client()
+        .get(&format!(
+            "{}/api/v1/clients/{}/policy",
+            &app.get_address(),
+            client.get_id()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert_eq!(body["access_token_lifetime"], 600);
+    assert_eq!(body["refresh_token_lifetime"], 86400);
+    assert_eq!(body["grant_types"].as_array().map(|g| g.len()), Some(5));
+}
+
+#[tokio::test]
+async fn update_policy_returns_a_400_for_invalid_policies() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    let test_cases = vec![
+        json!({
+            "grant_types": [],
+            "access_token_lifetime": 600,
+            "refresh_token_lifetime": 86400,
+            "id_token_lifetime": 3600,
+        }),
+        json!({
+            "grant_types": ["client_credentials"],
+            "access_token_lifetime": 0,
+            "refresh_token_lifetime": 86400,
+            "id_token_lifetime": 3600,
+        }),
+        json!({
+            "grant_types": ["client_credentials"],
+            "access_token_lifetime": 600,
+            "refresh_token_lifetime": 86400,
+            "id_token_lifetime": 3600,
+            "absolute_refresh_lifetime": 3600,
+        }),
+    ];
+
+    for body in test_cases {
+        // Act
+        let response = set_policy(&app, &client, &body).await;
+
+        // Assert
+        assert_eq!(
+            StatusCode::BAD_REQUEST,
+            response.status(),
+            "The API did not fail with 400 Bad Request when the payload was {}.",
+            body
+        );
+    }
+}
+
+#[tokio::test]
+async fn token_returns_a_400_for_a_grant_type_outside_the_policy() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    set_policy(
+        &app,
+        &client,
+        &json!({
+            "grant_types": ["refresh_token"],
+            "access_token_lifetime": 600,
+            "refresh_token_lifetime": 86400,
+            "id_token_lifetime": 3600,
+        }),
+    )
+    .await;
+
+    // Act
+    let response = request_token(&app, &client).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn token_reports_the_access_token_lifetime_from_the_policy() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    set_policy(
+        &app,
+        &client,
+        &json!({
+            "grant_types": ["client_credentials", "refresh_token"],
+            "access_token_lifetime": 300,
+            "refresh_token_lifetime": 3600,
+            "id_token_lifetime": 3600,
+            "absolute_refresh_lifetime": 86400,
+            "sliding_refresh_window": 1800,
+        }),
+    )
+    .await;
+
+    // Act
+    let response = request_token(&app, &client).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert_eq!(body["expires_in"], 300);
+}
@@ -0,0 +1,111 @@
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+async fn rotate_secret(app: &TestApp, client: &TestClient, body: &Value) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/clients/{}/secrets",
+            &app.get_address(),
+            client.get_id()
+        ))
+        .json(body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn request_token(app: &TestApp, client_id: &str, secret: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/oauth2/v1/token", &app.get_address()))
+        .basic_auth(client_id, Some(secret))
+        .query(&[("grant_type", "client_credentials"), ("scope", "read")])
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn rotate_secret_returns_a_new_secret_and_keeps_the_old_one_during_the_overlap() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    // Act
+    let response = rotate_secret(&app, &client, &json!({})).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    let secret = body["secret"]
+        .as_str()
+        .expect("Rotation did not return the new secret.");
+
+    assert_ne!(secret, client.get_secret());
+    assert_eq!(
+        StatusCode::OK,
+        request_token(&app, client.get_id(), secret).await.status()
+    );
+    assert_eq!(
+        StatusCode::OK,
+        request_token(&app, client.get_id(), client.get_secret())
+            .await
+            .status()
+    );
+}
+
+#[tokio::test]
+async fn rotate_secret_without_overlap_revokes_the_old_secret() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    // Act
+    let response = rotate_secret(&app, &client, &json!({ "previous_secret_expires_in": 0 })).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        StatusCode::UNAUTHORIZED,
+        request_token(&app, client.get_id(), client.get_secret())
+            .await
+            .status()
+    );
+}
+
+#[tokio::test]
+async fn rotate_secret_returns_a_400_for_invalid_lifetimes() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    let test_cases = vec![
+        json!({ "expires_in": 0 }),
+        json!({ "previous_secret_expires_in": -1 }),
+    ];
+
+    for body in test_cases {
+        // Act
+        let response = rotate_secret(&app, &client, &body).await;
+
+        // Assert
+        assert_eq!(
+            StatusCode::BAD_REQUEST,
+            response.status(),
+            "The API did not fail with 400 Bad Request when the payload was {}.",
+            body
+        );
+    }
+}
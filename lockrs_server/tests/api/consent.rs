@@ -0,0 +1,136 @@
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+async fn grant_consent(app: &TestApp, user: &TestUser, client: &TestClient) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/users/{}/consents",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .json(&json!({
+            "client_id": client.get_id(),
+            "scope": "read",
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn list_consents(app: &TestApp, user: &TestUser) -> Value {
+    app.get_client()
+        .get(&format!(
+            "{}/api/v1/users/{}/consents",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.")
+}
+
+async fn request_authorization(app: &TestApp, client: &TestClient) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/oauth2/v1/authorize", &app.get_address()))
+        .basic_auth(client.get_id(), Some(client.get_secret()))
+        .query(&[
+            ("response_type", "code"),
+            ("redirect_uri", client.get_redirect_url().as_str()),
+            (
+                "code_challenge",
+                "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM",
+            ),
+            ("code_challenge_method", "S256"),
+            ("scope", "read"),
+        ])
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn create_consent_returns_a_200_and_lists_the_grant() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+
+    // Act
+    let response = grant_consent(&app, &user, &client).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = list_consents(&app, &user).await;
+    let consents = body["consents"]
+        .as_array()
+        .expect("Consent list should be an array.");
+
+    assert_eq!(1, consents.len());
+    assert_eq!(consents[0]["client_id"], client.get_id());
+    assert_eq!(consents[0]["scopes"], json!(["read"]));
+}
+
+#[tokio::test]
+async fn delete_consent_returns_a_204_and_removes_the_grant() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+    grant_consent(&app, &user, &client).await;
+
+    // Act
+    let response = app
+        .get_client()
+        .delete(&format!(
+            "{}/api/v1/users/{}/consents/{}",
+            &app.get_address(),
+            user.get_id(),
+            client.get_id()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let body = list_consents(&app, &user).await;
+    assert_eq!(body["consents"], json!([]));
+}
+
+#[tokio::test]
+async fn authorize_returns_a_403_without_consent() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    TestUser::generate_logged_in(&app).await;
+
+    // Act
+    let response = request_authorization(&app, &client).await;
+
+    // Assert
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+}
+
+#[tokio::test]
+async fn authorize_returns_a_401_without_a_session() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+
+    // Act
+    let response = request_authorization(&app, &client).await;
+
+    // Assert
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}
@@ -0,0 +1,225 @@
+use hyper::StatusCode;
+use lockrs_server::{
+    api::v1::{
+        mailers::{FileMailer, Mail, Mailer},
+        responses::UserResponse,
+    },
+    EmailVerificationPolicy,
+};
+use serde_json::json;
+use url::Url;
+use uuid::Uuid;
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+async fn register(app: &TestApp, user: &TestUser) -> UserResponse {
+    let response = app
+        .get_client()
+        .post(&format!("{}/api/v1/auth/register", &app.get_address()))
+        .json(&json!({
+            "email": user.get_email(),
+            "password": user.get_password(),
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(StatusCode::OK, response.status());
+
+    response
+        .json::<UserResponse>()
+        .await
+        .expect("Failed to read request body.")
+}
+
+/// the token in the link of the latest verification mail sent to `email`
+fn latest_token_for(app: &TestApp, email: &str) -> String {
+    let mail = app
+        .get_mailer()
+        .get_latest_for(email)
+        .expect("No verification mail was sent.");
+
+    let link = mail
+        .body
+        .split_whitespace()
+        .find_map(|word| Url::parse(word).ok())
+        .expect("Verification mail should contain a link.");
+
+    link.query_pairs()
+        .find(|(key, _)| key == "token")
+        .map(|(_, token)| token.into_owned())
+        .expect("Verification link should carry a token.")
+}
+
+async fn verify(app: &TestApp, token: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/api/v1/auth/verify-email", &app.get_address()))
+        .json(&json!({ "token": token }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn resend(app: &TestApp, user_id: &Uuid) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/users/{}/verify-email",
+            &app.get_address(),
+            user_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn login(app: &TestApp, user: &TestUser) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/api/v1/auth/login", &app.get_address()))
+        .basic_auth(user.get_email(), Some(user.get_password()))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn register_sends_a_link_that_verifies_the_email_address() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate();
+
+    let registered = register(&app, &user).await;
+    assert!(!registered.email_verified);
+
+    // Act
+    let token = latest_token_for(&app, user.get_email());
+    let response = verify(&app, token.as_str()).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let verified = response
+        .json::<UserResponse>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert_eq!(registered.id, verified.id);
+    assert!(verified.email_verified);
+}
+
+#[tokio::test]
+async fn verify_email_returns_a_400_for_a_tampered_token() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate();
+    register(&app, &user).await;
+
+    let token = latest_token_for(&app, user.get_email());
+    let (payload, _) = token.split_once('.').expect("Token should be signed.");
+
+    // Act
+    let response = verify(&app, format!("{}.forged", payload).as_str()).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn login_returns_a_403_until_the_email_is_verified_when_required() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(|config| {
+        config.email_verification = EmailVerificationPolicy::Login
+    })
+    .await;
+    let user = TestUser::generate();
+    register(&app, &user).await;
+
+    // Act
+    let unverified_response = login(&app, &user).await;
+
+    let token = latest_token_for(&app, user.get_email());
+    assert_eq!(StatusCode::OK, verify(&app, token.as_str()).await.status());
+
+    let verified_response = login(&app, &user).await;
+
+    // Assert
+    assert_eq!(StatusCode::FORBIDDEN, unverified_response.status());
+    assert_eq!(StatusCode::OK, verified_response.status());
+}
+
+#[tokio::test]
+async fn create_consent_returns_a_403_for_an_unverified_email_when_required() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(|config| {
+        config.email_verification = EmailVerificationPolicy::Consent
+    })
+    .await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+
+    // Act
+    let response = app
+        .get_client()
+        .post(&format!(
+            "{}/api/v1/users/{}/consents",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .json(&json!({
+            "client_id": client.get_id(),
+            "scope": "read",
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+}
+
+#[tokio::test]
+async fn resend_verification_returns_a_204_and_a_409_once_verified() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+
+    // Act
+    let resend_response = resend(&app, user.get_id()).await;
+
+    let token = latest_token_for(&app, user.get_email());
+    assert_eq!(StatusCode::OK, verify(&app, token.as_str()).await.status());
+
+    let verified_resend_response = resend(&app, user.get_id()).await;
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, resend_response.status());
+    assert_eq!(StatusCode::CONFLICT, verified_resend_response.status());
+}
+
+#[tokio::test]
+async fn file_mailer_drops_the_mail_into_its_directory() {
+    // Arrange
+    let dir = std::env::temp_dir().join(format!("lockrs-mail-{}", Uuid::new_v4()));
+    let mailer = FileMailer::new(
+        dir.to_str().expect("Temp dir should be valid UTF-8."),
+        "no-reply@lockrs.test",
+    );
+    let mail = Mail::new("user@lockrs.test", "Hello", "First line\nSecond line");
+
+    // Act
+    mailer.send(&mail).await.expect("Failed to send mail.");
+
+    // Assert
+    let entry = std::fs::read_dir(&dir)
+        .expect("Mail directory should exist.")
+        .next()
+        .expect("Mail directory should not be empty.")
+        .expect("Failed to read mail directory.");
+    let message = std::fs::read_to_string(entry.path()).expect("Failed to read mail.");
+
+    assert!(message.starts_with("From: no-reply@lockrs.test\r\nTo: user@lockrs.test\r\n"));
+    assert!(message.contains("Subject: Hello\r\n"));
+    assert!(message.ends_with("\r\n\r\nFirst line\r\nSecond line\r\n"));
+
+    std::fs::remove_dir_all(&dir).expect("Failed to remove mail directory.");
+}
@@ -0,0 +1,484 @@
+use hyper::StatusCode;
+use lockrs_server::{
+    api::v1::responses::{
+        FederationAuthorizationResponse, FederationProviderListResponse, SessionResponse,
+        SessionTokenResponse,
+    },
+    FederationProtocol,
+};
+use serde_json::json;
+use url::Url;
+use uuid::Uuid;
+
+use crate::common::{
+    helpers::{TestApp, TestUser},
+    mock_idp::{MockIdp, MockIdpUser},
+};
+
+/// Spawns the app with the mock identity provider configured as `mock`, over `protocol`.
+async fn spawn_with_idp(protocol: FederationProtocol, link_by_email: bool) -> (TestApp, MockIdp) {
+    let idp = MockIdp::spawn().await;
+    let mut provider = idp.provider("mock", protocol);
+    provider.link_by_email = link_by_email;
+
+    let app = TestApp::spawn_in_memory_with(|config| {
+        config.federation_providers = vec![provider];
+    })
+    .await;
+
+    (app, idp)
+}
+
+async fn begin(app: &TestApp, provider: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/auth/federation/{}",
+            &app.get_address(),
+            provider
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// signs in at the identity provider, returning the code and state it sends the user back with
+async fn authorize_upstream(authorization_url: &str) -> (String, String) {
+    let browser = reqwest::ClientBuilder::new()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("Failed to build http client.");
+
+    let response = browser
+        .get(authorization_url)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(StatusCode::FOUND, response.status());
+
+    let location = response
+        .headers()
+        .get(reqwest::header::LOCATION)
+        .and_then(|location| location.to_str().ok())
+        .and_then(|location| Url::parse(location).ok())
+        .expect("Identity provider should redirect back to the frontend.");
+
+    let query_value = |name: &str| {
+        location
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+            .unwrap_or_else(|| panic!("Redirect should carry a {}.", name))
+    };
+
+    (query_value("code"), query_value("state"))
+}
+
+async fn callback_with(
+    app: &TestApp,
+    client: &reqwest::Client,
+    provider: &str,
+    code: &str,
+    state: &str,
+) -> reqwest::Response {
+    client
+        .post(&format!(
+            "{}/api/v1/auth/federation/{}/callback",
+            &app.get_address(),
+            provider
+        ))
+        .json(&json!({ "code": code, "state": state }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// starts a sign-in with the provider, returning the code and state to complete it with
+async fn begin_and_authorize(app: &TestApp) -> (String, String) {
+    let response = begin(app, "mock").await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let authorization = response
+        .json::<FederationAuthorizationResponse>()
+        .await
+        .expect("Failed to read request body.");
+
+    authorize_upstream(authorization.authorization_url.as_str()).await
+}
+
+async fn sign_in(app: &TestApp) -> reqwest::Response {
+    let (code, state) = begin_and_authorize(app).await;
+
+    callback_with(app, app.get_client(), "mock", code.as_str(), state.as_str()).await
+}
+
+/// exchanges the session token from a sign-in for a session, returning who it is for
+async fn signed_in_user_id(app: &TestApp, response: reqwest::Response) -> Uuid {
+    assert_eq!(StatusCode::OK, response.status());
+
+    let session_token = response
+        .json::<SessionTokenResponse>()
+        .await
+        .expect("Failed to read request body.");
+
+    app.get_client()
+        .post(&format!("{}/api/v1/sessions", &app.get_address()))
+        .bearer_auth(session_token.session_token)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<SessionResponse>()
+        .await
+        .expect("Failed to read request body.")
+        .user_id
+}
+
+#[tokio::test]
+async fn read_all_returns_the_configured_providers() {
+    // Arrange
+    let (app, _idp) = spawn_with_idp(FederationProtocol::Oidc, false).await;
+
+    // Act
+    let response = app
+        .get_client()
+        .get(&format!("{}/api/v1/auth/federation", &app.get_address()))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let providers = response
+        .json::<FederationProviderListResponse>()
+        .await
+        .expect("Failed to read request body.");
+    assert_eq!(vec![String::from("mock")], providers.providers);
+}
+
+#[tokio::test]
+async fn begin_returns_an_authorization_url_with_state_nonce_and_pkce() {
+    // Arrange
+    let (app, _idp) = spawn_with_idp(FederationProtocol::Oidc, false).await;
+
+    // Act
+    let response = begin(&app, "mock").await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+    assert!(response.cookies().any(|cookie| {
+        cookie.name() == FederationAuthorizationResponse::cookie_name() && cookie.http_only()
+    }));
+
+    let authorization = response
+        .json::<FederationAuthorizationResponse>()
+        .await
+        .expect("Failed to read request body.");
+    let authorization_url = Url::parse(authorization.authorization_url.as_str())
+        .expect("Authorization url should be a url.");
+    let params = authorization_url
+        .query_pairs()
+        .into_owned()
+        .collect::<Vec<_>>();
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    assert_eq!(Some("code"), param("response_type"));
+    assert_eq!(Some("S256"), param("code_challenge_method"));
+    assert_eq!(
+        Some("http://localhost:8000/federation/mock/callback"),
+        param("redirect_uri")
+    );
+    assert!(param("state").is_some());
+    assert!(param("nonce").is_some());
+    assert!(param("code_challenge").is_some());
+}
+
+#[tokio::test]
+async fn begin_returns_a_404_for_an_unknown_provider() {
+    // Arrange
+    let (app, _idp) = spawn_with_idp(FederationProtocol::Oidc, false).await;
+
+    // Act
+    let response = begin(&app, "unknown").await;
+
+    // Assert
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn callback_provisions_a_user_for_a_new_identity() {
+    // Arrange
+    let (app, idp) = spawn_with_idp(FederationProtocol::Oidc, false).await;
+    let upstream_user = MockIdpUser::generate();
+    idp.set_user(&upstream_user);
+
+    // Act
+    let response = sign_in(&app).await;
+
+    // Assert
+    let user_id = signed_in_user_id(&app, response).await;
+
+    let state = app.get_state();
+    let user = state
+        .repository_container
+        .user_repository
+        .get_by_id(&state.db_context, &user_id)
+        .await
+        .expect("Failed to read back the user.");
+    assert_eq!(upstream_user.email, Some(user.email));
+    assert!(user.email_verified);
+
+    let identity = state
+        .repository_container
+        .federated_identity_repository
+        .get_by_provider_and_subject(&state.db_context, "mock", upstream_user.subject.as_str())
+        .await
+        .expect("Failed to read back the federated identity.");
+    assert_eq!(user_id, identity.user_id);
+}
+
+#[tokio::test]
+async fn callback_signs_in_the_same_user_again() {
+    // Arrange
+    let (app, idp) = spawn_with_idp(FederationProtocol::Oidc, false).await;
+    let mut upstream_user = MockIdpUser::generate();
+    idp.set_user(&upstream_user);
+
+    let first_user_id = signed_in_user_id(&app, sign_in(&app).await).await;
+
+    // the identity stays linked by its subject, whatever happens to the email upstream
+    upstream_user.email = Some(format!("{}@example.com", Uuid::new_v4().simple()));
+    idp.set_user(&upstream_user);
+
+    // Act
+    let response = sign_in(&app).await;
+
+    // Assert
+    assert_eq!(first_user_id, signed_in_user_id(&app, response).await);
+}
+
+#[tokio::test]
+async fn callback_returns_a_409_for_the_email_of_an_existing_user() {
+    // Arrange
+    let (app, idp) = spawn_with_idp(FederationProtocol::Oidc, false).await;
+    let user = TestUser::generate_stored(&app).await;
+
+    let mut upstream_user = MockIdpUser::generate();
+    upstream_user.email = Some(user.get_email().to_owned());
+    idp.set_user(&upstream_user);
+
+    // Act
+    let response = sign_in(&app).await;
+
+    // Assert
+    assert_eq!(StatusCode::CONFLICT, response.status());
+}
+
+#[tokio::test]
+async fn callback_links_an_existing_user_by_verified_email_when_allowed() {
+    // Arrange
+    let (app, idp) = spawn_with_idp(FederationProtocol::Oidc, true).await;
+    let user = TestUser::generate_stored(&app).await;
+
+    let mut upstream_user = MockIdpUser::generate();
+    upstream_user.email = Some(user.get_email().to_owned());
+    idp.set_user(&upstream_user);
+
+    // Act
+    let response = sign_in(&app).await;
+
+    // Assert
+    assert_eq!(user.get_id(), &signed_in_user_id(&app, response).await);
+}
+
+#[tokio::test]
+async fn callback_returns_a_409_for_an_unverified_email_even_when_linking_is_allowed() {
+    // Arrange
+    let (app, idp) = spawn_with_idp(FederationProtocol::Oidc, true).await;
+    let user = TestUser::generate_stored(&app).await;
+
+    let mut upstream_user = MockIdpUser::generate();
+    upstream_user.email = Some(user.get_email().to_owned());
+    upstream_user.email_verified = false;
+    idp.set_user(&upstream_user);
+
+    // Act
+    let response = sign_in(&app).await;
+
+    // Assert
+    assert_eq!(StatusCode::CONFLICT, response.status());
+}
+
+#[tokio::test]
+async fn callback_returns_a_400_without_an_email_to_provision_with() {
+    // Arrange
+    let (app, idp) = spawn_with_idp(FederationProtocol::Oidc, false).await;
+
+    let mut upstream_user = MockIdpUser::generate();
+    upstream_user.email = None;
+    idp.set_user(&upstream_user);
+
+    // Act
+    let response = sign_in(&app).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn callback_returns_a_401_for_an_id_token_with_another_nonce() {
+    // Arrange
+    let (app, idp) = spawn_with_idp(FederationProtocol::Oidc, false).await;
+    idp.set_nonce_override(Some("another-nonce"));
+
+    // Act
+    let response = sign_in(&app).await;
+
+    // Assert
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}
+
+#[tokio::test]
+async fn callback_returns_a_400_from_another_browser() {
+    // Arrange
+    let (app, _idp) = spawn_with_idp(FederationProtocol::Oidc, false).await;
+    let (code, state) = begin_and_authorize(&app).await;
+    let other_browser = reqwest::ClientBuilder::new()
+        .cookie_store(true)
+        .build()
+        .expect("Failed to build http client.");
+
+    // Act
+    let response = callback_with(&app, &other_browser, "mock", code.as_str(), state.as_str()).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn callback_can_only_be_completed_once() {
+    // Arrange
+    let (app, _idp) = spawn_with_idp(FederationProtocol::Oidc, false).await;
+    let (code, state) = begin_and_authorize(&app).await;
+
+    let first_response = callback_with(
+        &app,
+        app.get_client(),
+        "mock",
+        code.as_str(),
+        state.as_str(),
+    )
+    .await;
+    assert_eq!(StatusCode::OK, first_response.status());
+
+    // Act
+    let response = callback_with(
+        &app,
+        app.get_client(),
+        "mock",
+        code.as_str(),
+        state.as_str(),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn callback_returns_a_403_for_an_unverified_email_when_login_requires_it() {
+    // Arrange
+    let idp = MockIdp::spawn().await;
+    let provider = idp.provider("mock", FederationProtocol::Oidc);
+    let app = TestApp::spawn_in_memory_with(|config| {
+        config.federation_providers = vec![provider];
+        config.email_verification = lockrs_server::EmailVerificationPolicy::Login;
+    })
+    .await;
+
+    let mut upstream_user = MockIdpUser::generate();
+    upstream_user.email_verified = false;
+    idp.set_user(&upstream_user);
+
+    // Act
+    let response = sign_in(&app).await;
+
+    // Assert
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+}
+
+#[tokio::test]
+async fn callback_reads_the_user_from_an_oauth2_provider() {
+    // Arrange
+    let (app, idp) = spawn_with_idp(FederationProtocol::OAuth2, false).await;
+    let upstream_user = MockIdpUser::generate();
+    idp.set_user(&upstream_user);
+
+    // Act
+    let response = sign_in(&app).await;
+
+    // Assert
+    let user_id = signed_in_user_id(&app, response).await;
+
+    let state = app.get_state();
+    let user = state
+        .repository_container
+        .user_repository
+        .get_by_id(&state.db_context, &user_id)
+        .await
+        .expect("Failed to read back the user.");
+    assert_eq!(upstream_user.email, Some(user.email));
+    // GitHub style providers don't vouch for the address
+    assert!(!user.email_verified);
+
+    let identity = state
+        .repository_container
+        .federated_identity_repository
+        .get_by_provider_and_subject(&state.db_context, "mock", upstream_user.subject.as_str())
+        .await
+        .expect("Failed to read back the federated identity.");
+    assert_eq!(user_id, identity.user_id);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn callback_works_with_sqlite_repositories() {
+    // Arrange
+    let idp = MockIdp::spawn().await;
+    let provider = idp.provider("mock", FederationProtocol::Oidc);
+    let app = TestApp::spawn_sqlite_with(|config| {
+        config.federation_providers = vec![provider];
+    })
+    .await;
+    idp.set_user(&MockIdpUser::generate());
+
+    let (code, state) = begin_and_authorize(&app).await;
+
+    // Act
+    let response = callback_with(
+        &app,
+        app.get_client(),
+        "mock",
+        code.as_str(),
+        state.as_str(),
+    )
+    .await;
+
+    // Assert
+    let user_id = signed_in_user_id(&app, response).await;
+    assert_eq!(user_id, signed_in_user_id(&app, sign_in(&app).await).await);
+
+    let replayed_response = callback_with(
+        &app,
+        app.get_client(),
+        "mock",
+        code.as_str(),
+        state.as_str(),
+    )
+    .await;
+    assert_eq!(StatusCode::BAD_REQUEST, replayed_response.status());
+}
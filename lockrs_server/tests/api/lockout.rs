@@ -0,0 +1,248 @@
+use std::time::Duration;
+
+use hyper::{header::RETRY_AFTER, StatusCode};
+use lockrs_server::AppConfig;
+
+use crate::common::helpers::{TestApp, TestUser};
+
+const ADMIN_API_KEY: &str = "test-admin-api-key";
+
+async fn login(app: &TestApp, email: &str, password: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/api/v1/auth/login", &app.get_address()))
+        .basic_auth(email, Some(password))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn unlock(app: &TestApp, path: &str, api_key: &str) -> reqwest::Response {
+    app.get_client()
+        .delete(&format!("{}/api/v1/admin{}", &app.get_address(), path))
+        .bearer_auth(api_key)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// locks an account out after 3 failed logins for a second, doubling with every failure after
+fn with_strict_throttle(config: &mut AppConfig) {
+    config.login_throttle.max_failures_per_account = 3;
+    config.login_throttle.lockout = chrono::Duration::seconds(1);
+    config.admin_api_key = Some(String::from(ADMIN_API_KEY));
+}
+
+async fn fail_logins(app: &TestApp, email: &str, count: usize) {
+    for _ in 0..count {
+        let response = login(app, email, "not the password").await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    }
+}
+
+fn retry_after(response: &reqwest::Response) -> i64 {
+    response
+        .headers()
+        .get(RETRY_AFTER)
+        .expect("Lockout should carry a Retry-After header.")
+        .to_str()
+        .expect("Retry-After should be ascii.")
+        .parse()
+        .expect("Retry-After should be a number of seconds.")
+}
+
+#[tokio::test]
+async fn login_locks_out_an_account_after_too_many_failures() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(with_strict_throttle).await;
+    let user = TestUser::generate_stored(&app).await;
+    fail_logins(&app, user.get_email(), 3).await;
+
+    // Act
+    let response = login(&app, user.get_email(), user.get_password()).await;
+
+    // Assert
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    assert_eq!(1, retry_after(&response));
+}
+
+#[tokio::test]
+async fn login_locks_out_an_unknown_email_the_same_way() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(with_strict_throttle).await;
+    let user = TestUser::generate();
+    fail_logins(&app, user.get_email(), 3).await;
+
+    // Act
+    let response = login(&app, user.get_email(), user.get_password()).await;
+
+    // Assert
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    assert_eq!(1, retry_after(&response));
+}
+
+#[tokio::test]
+async fn login_doubles_the_lockout_with_every_failure_after_it() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(with_strict_throttle).await;
+    let user = TestUser::generate_stored(&app).await;
+    fail_logins(&app, user.get_email(), 3).await;
+
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    fail_logins(&app, user.get_email(), 1).await;
+
+    // Act
+    let response = login(&app, user.get_email(), user.get_password()).await;
+
+    // Assert
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    assert_eq!(2, retry_after(&response));
+}
+
+#[tokio::test]
+async fn login_forgets_the_failures_of_an_account_on_success() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(with_strict_throttle).await;
+    let user = TestUser::generate_stored(&app).await;
+    fail_logins(&app, user.get_email(), 2).await;
+
+    let login_response = login(&app, user.get_email(), user.get_password()).await;
+    assert_eq!(StatusCode::OK, login_response.status());
+
+    fail_logins(&app, user.get_email(), 2).await;
+
+    // Act
+    let response = login(&app, user.get_email(), user.get_password()).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+#[tokio::test]
+async fn login_locks_out_an_address_failing_across_accounts() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(|config| {
+        config.login_throttle.max_failures_per_address = 3;
+    })
+    .await;
+    let user = TestUser::generate_stored(&app).await;
+    for _ in 0..3 {
+        fail_logins(&app, TestUser::generate().get_email(), 1).await;
+    }
+
+    // Act
+    let response = login(&app, user.get_email(), user.get_password()).await;
+
+    // Assert
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+}
+
+#[tokio::test]
+async fn unlock_user_lets_a_locked_out_user_log_in_again() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(with_strict_throttle).await;
+    let user = TestUser::generate_stored(&app).await;
+    fail_logins(&app, user.get_email(), 3).await;
+
+    // Act
+    let response = unlock(
+        &app,
+        format!("/users/{}/lockout", user.get_id()).as_str(),
+        ADMIN_API_KEY,
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let login_response = login(&app, user.get_email(), user.get_password()).await;
+    assert_eq!(StatusCode::OK, login_response.status());
+}
+
+#[tokio::test]
+async fn unlock_address_lets_a_locked_out_address_log_in_again() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(|config| {
+        config.login_throttle.max_failures_per_address = 3;
+        config.admin_api_key = Some(String::from(ADMIN_API_KEY));
+    })
+    .await;
+    let user = TestUser::generate_stored(&app).await;
+    for _ in 0..3 {
+        fail_logins(&app, TestUser::generate().get_email(), 1).await;
+    }
+
+    // Act
+    let response = unlock(&app, "/addresses/127.0.0.1/lockout", ADMIN_API_KEY).await;
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let login_response = login(&app, user.get_email(), user.get_password()).await;
+    assert_eq!(StatusCode::OK, login_response.status());
+}
+
+#[tokio::test]
+async fn unlock_user_returns_a_401_for_a_wrong_api_key() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(with_strict_throttle).await;
+    let user = TestUser::generate_stored(&app).await;
+    fail_logins(&app, user.get_email(), 3).await;
+
+    // Act
+    let response = unlock(
+        &app,
+        format!("/users/{}/lockout", user.get_id()).as_str(),
+        "not the api key",
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let login_response = login(&app, user.get_email(), user.get_password()).await;
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, login_response.status());
+}
+
+#[tokio::test]
+async fn unlock_user_returns_a_404_without_an_api_key_configured() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+
+    // Act
+    let response = unlock(
+        &app,
+        format!("/users/{}/lockout", user.get_id()).as_str(),
+        ADMIN_API_KEY,
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn login_locks_out_an_account_with_sqlite_repositories() {
+    // Arrange
+    let app = TestApp::spawn_sqlite_with(with_strict_throttle).await;
+    let user = TestUser::generate_stored(&app).await;
+    fail_logins(&app, user.get_email(), 3).await;
+
+    // Act
+    let response = login(&app, user.get_email(), user.get_password()).await;
+
+    // Assert
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+
+    let unlock_response = unlock(
+        &app,
+        format!("/users/{}/lockout", user.get_id()).as_str(),
+        ADMIN_API_KEY,
+    )
+    .await;
+    assert_eq!(StatusCode::NO_CONTENT, unlock_response.status());
+
+    let login_response = login(&app, user.get_email(), user.get_password()).await;
+    assert_eq!(StatusCode::OK, login_response.status());
+}
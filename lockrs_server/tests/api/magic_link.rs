@@ -0,0 +1,285 @@
+use hyper::StatusCode;
+use lockrs_server::api::v1::responses::SessionTokenResponse;
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::common::helpers::{TestApp, TestUser};
+
+async fn request_link(app: &TestApp, email: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/api/v1/auth/magic-link", &app.get_address()))
+        .json(&json!({ "email": email }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn redeem_with(app: &TestApp, client: &reqwest::Client, body: Value) -> reqwest::Response {
+    client
+        .post(&format!(
+            "{}/api/v1/auth/magic-link/redeem",
+            &app.get_address()
+        ))
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn redeem(app: &TestApp, body: Value) -> reqwest::Response {
+    redeem_with(app, app.get_client(), body).await
+}
+
+/// asks for a sign-in link for the user, returning the token and code mailed to them
+async fn request_token_and_code(app: &TestApp, user: &TestUser) -> (String, String) {
+    let already_sent = app.count_mails_for(user.get_email());
+
+    assert_eq!(
+        StatusCode::ACCEPTED,
+        request_link(app, user.get_email()).await.status()
+    );
+
+    let mail = app.wait_for_mail_to(user.get_email(), already_sent).await;
+
+    let token = mail
+        .body
+        .split_whitespace()
+        .find_map(|word| Url::parse(word).ok())
+        .and_then(|link| {
+            link.query_pairs()
+                .find(|(key, _)| key == "token")
+                .map(|(_, token)| token.into_owned())
+        })
+        .expect("Sign-in mail should contain a link carrying a token.");
+
+    let code = mail
+        .body
+        .split_whitespace()
+        .find(|word| word.len() == 6 && word.chars().all(|c| c.is_ascii_digit()))
+        .map(String::from)
+        .expect("Sign-in mail should contain a code.");
+
+    (token, code)
+}
+
+#[tokio::test]
+async fn redeem_link_returns_a_session_token() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let (token, _) = request_token_and_code(&app, &user).await;
+
+    // Act
+    let response = redeem(&app, json!({ "token": token })).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let session_token = response
+        .json::<SessionTokenResponse>()
+        .await
+        .expect("Failed to read request body.");
+    assert!(!session_token.mfa_required);
+
+    let session_response = app
+        .get_client()
+        .post(&format!("{}/api/v1/sessions", &app.get_address()))
+        .bearer_auth(session_token.session_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(StatusCode::OK, session_response.status());
+}
+
+#[tokio::test]
+async fn redeem_code_returns_a_session_token() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let (_, code) = request_token_and_code(&app, &user).await;
+
+    // Act
+    let response = redeem(&app, json!({ "email": user.get_email(), "code": code })).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+#[tokio::test]
+async fn redeem_link_can_only_be_used_once() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let (token, code) = request_token_and_code(&app, &user).await;
+
+    let first_response = redeem(&app, json!({ "token": token })).await;
+    assert_eq!(StatusCode::OK, first_response.status());
+
+    // Act
+    let link_response = redeem(&app, json!({ "token": token })).await;
+    let code_response = redeem(&app, json!({ "email": user.get_email(), "code": code })).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, link_response.status());
+    assert_eq!(StatusCode::BAD_REQUEST, code_response.status());
+}
+
+#[tokio::test]
+async fn redeem_link_returns_a_400_from_another_browser() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let (token, _) = request_token_and_code(&app, &user).await;
+    let other_browser = reqwest::ClientBuilder::new()
+        .cookie_store(true)
+        .build()
+        .expect("Failed to build http client.");
+
+    // Act
+    let response = redeem_with(&app, &other_browser, json!({ "token": token })).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let original_response = redeem(&app, json!({ "token": token })).await;
+    assert_eq!(StatusCode::OK, original_response.status());
+}
+
+#[tokio::test]
+async fn redeem_code_returns_a_400_for_a_wrong_code() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let (_, code) = request_token_and_code(&app, &user).await;
+    let wrong_code = format!("{:06}", (code.parse::<u32>().unwrap() + 1) % 1_000_000);
+
+    // Act
+    let response = redeem(
+        &app,
+        json!({ "email": user.get_email(), "code": wrong_code }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let right_response = redeem(&app, json!({ "email": user.get_email(), "code": code })).await;
+    assert_eq!(StatusCode::OK, right_response.status());
+}
+
+#[tokio::test]
+async fn redeem_code_locks_out_after_too_many_wrong_codes() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(|config| {
+        config.login_throttle.max_failures_per_account = 3;
+    })
+    .await;
+    let user = TestUser::generate_stored(&app).await;
+    let (_, code) = request_token_and_code(&app, &user).await;
+    let wrong_code = format!("{:06}", (code.parse::<u32>().unwrap() + 1) % 1_000_000);
+
+    for _ in 0..3 {
+        let response = redeem(
+            &app,
+            json!({ "email": user.get_email(), "code": wrong_code }),
+        )
+        .await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+
+    // Act
+    let response = redeem(&app, json!({ "email": user.get_email(), "code": code })).await;
+
+    // Assert
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+}
+
+#[tokio::test]
+async fn request_link_replaces_the_outstanding_link() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let (first_token, _) = request_token_and_code(&app, &user).await;
+    let (second_token, _) = request_token_and_code(&app, &user).await;
+
+    // Act
+    let response = redeem(&app, json!({ "token": first_token })).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let second_response = redeem(&app, json!({ "token": second_token })).await;
+    assert_eq!(StatusCode::OK, second_response.status());
+}
+
+#[tokio::test]
+async fn request_link_returns_a_202_without_mailing_an_unknown_email() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate();
+
+    // Act
+    let response = request_link(&app, user.get_email()).await;
+
+    // Assert
+    assert_eq!(StatusCode::ACCEPTED, response.status());
+
+    // the lookup runs after the response, so give it the time to send anything it would
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    assert!(app.get_mailer().get_latest_for(user.get_email()).is_none());
+}
+
+#[tokio::test]
+async fn request_link_answers_before_mailing_a_known_email() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+
+    // Act
+    let response = request_link(&app, user.get_email()).await;
+
+    // Assert
+    assert_eq!(StatusCode::ACCEPTED, response.status());
+    app.wait_for_mail_to(user.get_email(), 0).await;
+}
+
+#[tokio::test]
+async fn redeem_link_verifies_the_email_address() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let (token, _) = request_token_and_code(&app, &user).await;
+
+    // Act
+    let response = redeem(&app, json!({ "token": token })).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let state = app.get_state();
+    let stored_user = state
+        .repository_container
+        .user_repository
+        .get_by_id(&state.db_context, user.get_id())
+        .await
+        .expect("Failed to read back the user.");
+    assert!(stored_user.email_verified);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn redeem_link_works_with_sqlite_repositories() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let user = TestUser::generate_stored(&app).await;
+    let (token, code) = request_token_and_code(&app, &user).await;
+
+    // Act
+    let response = redeem(&app, json!({ "token": token })).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let code_response = redeem(&app, json!({ "email": user.get_email(), "code": code })).await;
+    assert_eq!(StatusCode::BAD_REQUEST, code_response.status());
+}
@@ -0,0 +1,293 @@
+use hyper::StatusCode;
+use lockrs_server::{
+    api::v1::responses::{RecoveryCodesResponse, SessionTokenResponse, TotpEnrollmentResponse},
+    utils::totp::TotpUtil,
+};
+use serde_json::json;
+
+use crate::common::helpers::{TestApp, TestUser};
+
+async fn login_with_password(app: &TestApp, user: &TestUser) -> SessionTokenResponse {
+    app.get_client()
+        .post(&format!("{}/api/v1/auth/login", &app.get_address()))
+        .basic_auth(user.get_email(), Some(user.get_password()))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<SessionTokenResponse>()
+        .await
+        .expect("Failed to read request body.")
+}
+
+async fn verify_mfa(
+    app: &TestApp,
+    session_token: &str,
+    body: serde_json::Value,
+) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/api/v1/auth/mfa", &app.get_address()))
+        .bearer_auth(session_token)
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// enrolls and confirms a totp authenticator for the logged in `user`, returning the secret and
+/// recovery codes
+async fn enroll_totp(app: &TestApp, user: &TestUser) -> (String, Vec<String>) {
+    let totp_url = format!(
+        "{}/api/v1/users/{}/mfa/totp",
+        &app.get_address(),
+        user.get_id()
+    );
+
+    let enrollment = app
+        .get_client()
+        .post(&totp_url)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<TotpEnrollmentResponse>()
+        .await
+        .expect("Failed to read request body.");
+
+    let code = TotpUtil::generate_code(&enrollment.secret, TotpUtil::current_step())
+        .expect("Failed to generate TOTP code.");
+
+    let recovery_codes = app
+        .get_client()
+        .put(&totp_url)
+        .json(&json!({ "code": code }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<RecoveryCodesResponse>()
+        .await
+        .expect("Failed to read request body.")
+        .recovery_codes;
+
+    (enrollment.secret, recovery_codes)
+}
+
+#[tokio::test]
+async fn mfa_totp_enrollment_returns_a_provisioning_uri_and_recovery_codes() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+    let totp_url = format!(
+        "{}/api/v1/users/{}/mfa/totp",
+        &app.get_address(),
+        user.get_id()
+    );
+
+    // Act
+    let enroll_response = app
+        .get_client()
+        .post(&totp_url)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(StatusCode::OK, enroll_response.status());
+
+    let enrollment = enroll_response
+        .json::<TotpEnrollmentResponse>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert!(enrollment.provisioning_uri.starts_with("otpauth://totp/"));
+    assert!(enrollment
+        .provisioning_uri
+        .contains(format!("secret={}", enrollment.secret).as_str()));
+
+    // Act 2: confirm with an invalid code
+    let invalid_response = app
+        .get_client()
+        .put(&totp_url)
+        .json(&json!({ "code": "000000x" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert 2
+    assert_eq!(StatusCode::UNAUTHORIZED, invalid_response.status());
+
+    // Act 3: confirm with a code from the authenticator
+    let code = TotpUtil::generate_code(&enrollment.secret, TotpUtil::current_step())
+        .expect("Failed to generate TOTP code.");
+
+    let confirm_response = app
+        .get_client()
+        .put(&totp_url)
+        .json(&json!({ "code": code }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert 3
+    assert_eq!(StatusCode::OK, confirm_response.status());
+
+    let recovery_codes = confirm_response
+        .json::<RecoveryCodesResponse>()
+        .await
+        .expect("Failed to read request body.")
+        .recovery_codes;
+
+    assert_eq!(10, recovery_codes.len());
+
+    // Act 4: a confirmed enrollment can not be replaced without disabling it first
+    let reenroll_response = app
+        .get_client()
+        .post(&totp_url)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert 4
+    assert_eq!(StatusCode::CONFLICT, reenroll_response.status());
+}
+
+#[tokio::test]
+async fn mfa_login_requires_a_totp_code_once_enrolled() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+    let (secret, _) = enroll_totp(&app, &user).await;
+
+    // Act
+    let pending_token = login_with_password(&app, &user).await;
+
+    let session_response = app
+        .get_client()
+        .post(&format!("{}/api/v1/sessions", &app.get_address()))
+        .bearer_auth(&pending_token.session_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert!(pending_token.mfa_required);
+    assert_eq!(StatusCode::UNAUTHORIZED, session_response.status());
+
+    // Act 2: the code used to confirm the enrollment can not be replayed
+    let pending_token = login_with_password(&app, &user).await;
+    let used_code = TotpUtil::generate_code(&secret, TotpUtil::current_step())
+        .expect("Failed to generate TOTP code.");
+
+    let replay_response = verify_mfa(
+        &app,
+        &pending_token.session_token,
+        json!({ "code": used_code }),
+    )
+    .await;
+
+    // Assert 2
+    assert_eq!(StatusCode::UNAUTHORIZED, replay_response.status());
+
+    // Act 3
+    let pending_token = login_with_password(&app, &user).await;
+    let code = TotpUtil::generate_code(&secret, TotpUtil::current_step() + 1)
+        .expect("Failed to generate TOTP code.");
+
+    let verify_response =
+        verify_mfa(&app, &pending_token.session_token, json!({ "code": code })).await;
+
+    // Assert 3
+    assert_eq!(StatusCode::OK, verify_response.status());
+
+    let session_token = verify_response
+        .json::<SessionTokenResponse>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert!(!session_token.mfa_required);
+
+    let session_response = app
+        .get_client()
+        .post(&format!("{}/api/v1/sessions", &app.get_address()))
+        .bearer_auth(&session_token.session_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(StatusCode::OK, session_response.status());
+}
+
+#[tokio::test]
+async fn mfa_login_accepts_each_recovery_code_once() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+    let (_, recovery_codes) = enroll_totp(&app, &user).await;
+    let recovery_code = recovery_codes[0].to_uppercase();
+
+    // Act
+    let pending_token = login_with_password(&app, &user).await;
+    let verify_response = verify_mfa(
+        &app,
+        &pending_token.session_token,
+        json!({ "recovery_code": recovery_code }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, verify_response.status());
+
+    // Act 2
+    let pending_token = login_with_password(&app, &user).await;
+    let reuse_response = verify_mfa(
+        &app,
+        &pending_token.session_token,
+        json!({ "recovery_code": recovery_code }),
+    )
+    .await;
+
+    // Assert 2
+    assert_eq!(StatusCode::UNAUTHORIZED, reuse_response.status());
+}
+
+#[tokio::test]
+async fn mfa_verify_returns_a_401_for_a_token_not_pending_mfa() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let session_token = login_with_password(&app, &user).await;
+
+    // Act
+    let response = verify_mfa(
+        &app,
+        &session_token.session_token,
+        json!({ "code": "123456" }),
+    )
+    .await;
+
+    // Assert
+    assert!(!session_token.mfa_required);
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}
+
+#[tokio::test]
+async fn mfa_login_no_longer_requires_a_code_once_disabled() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+    enroll_totp(&app, &user).await;
+
+    // Act
+    let response = app
+        .get_client()
+        .delete(&format!(
+            "{}/api/v1/users/{}/mfa/totp",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+    assert!(!login_with_password(&app, &user).await.mfa_required);
+}
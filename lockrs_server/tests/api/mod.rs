@@ -1,2 +1,17 @@
+mod account;
+mod client_policy;
+mod client_secret;
+mod consent;
+mod email_verification;
+mod federation;
+mod lockout;
+mod magic_link;
+mod mfa;
+mod password_hashing;
+mod password_policy;
+mod password_reset;
+mod redirect;
+mod scope;
 mod session;
 mod user_auth;
+mod webauthn;
@@ -0,0 +1,140 @@
+use hyper::StatusCode;
+use lockrs_server::{
+    api::v1::{models::UserAuthModel, services::UserAuthService},
+    PasswordHashingConfig,
+};
+use serde_json::json;
+
+use crate::common::helpers::{TestApp, TestUser, TEST_PASSWORD_HASHING};
+
+async fn login(app: &TestApp, email: &str, password: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/api/v1/auth/login", &app.get_address()))
+        .basic_auth(email, Some(password))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// stores the user with the given password hash rather than one made by the app
+async fn store_with_hash(app: &TestApp, user: &TestUser, password_hash: &str) {
+    let state = app.get_state();
+
+    state
+        .repository_container
+        .user_auth_repository
+        .create_raw(
+            &state.db_context,
+            &UserAuthModel::new(user.get_id(), user.get_email(), password_hash, false, 0),
+        )
+        .await
+        .expect("Failed to store test user in user database.");
+}
+
+async fn stored_hash(app: &TestApp, user: &TestUser) -> String {
+    let state = app.get_state();
+
+    state
+        .repository_container
+        .user_auth_repository
+        .get_by_email(&state.db_context, user.get_email())
+        .await
+        .expect("Failed to read test user from user database.")
+        .password_hash
+}
+
+#[tokio::test]
+async fn register_stores_an_argon2id_hash() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate();
+
+    // Act
+    let response = app
+        .get_client()
+        .post(&format!("{}/api/v1/auth/register", &app.get_address()))
+        .json(&json!({
+            "email": user.get_email(),
+            "password": user.get_password(),
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let password_hash = stored_hash(&app, &user).await;
+    assert!(password_hash.starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+}
+
+#[tokio::test]
+async fn login_upgrades_a_legacy_bcrypt_hash() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate();
+    let bcrypt_hash =
+        bcrypt::hash(user.get_password(), 4).expect("Failed to hash password of test user.");
+    store_with_hash(&app, &user, bcrypt_hash.as_str()).await;
+
+    // Act
+    let response = login(&app, user.get_email(), user.get_password()).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let password_hash = stored_hash(&app, &user).await;
+    assert!(!UserAuthService::needs_rehash(
+        password_hash.as_str(),
+        &TEST_PASSWORD_HASHING
+    ));
+
+    let login_response = login(&app, user.get_email(), user.get_password()).await;
+    assert_eq!(StatusCode::OK, login_response.status());
+}
+
+#[tokio::test]
+async fn login_upgrades_a_hash_with_outdated_parameters() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate();
+    let outdated_hash = UserAuthService::hash_password(
+        user.get_password(),
+        &PasswordHashingConfig {
+            memory_kib: 512,
+            ..TEST_PASSWORD_HASHING
+        },
+    )
+    .expect("Failed to hash password of test user.");
+    store_with_hash(&app, &user, outdated_hash.as_str()).await;
+
+    // Act
+    let response = login(&app, user.get_email(), user.get_password()).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let password_hash = stored_hash(&app, &user).await;
+    assert_ne!(outdated_hash, password_hash);
+    assert!(!UserAuthService::needs_rehash(
+        password_hash.as_str(),
+        &TEST_PASSWORD_HASHING
+    ));
+}
+
+#[tokio::test]
+async fn login_keeps_a_legacy_hash_on_a_wrong_password() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate();
+    let bcrypt_hash =
+        bcrypt::hash(user.get_password(), 4).expect("Failed to hash password of test user.");
+    store_with_hash(&app, &user, bcrypt_hash.as_str()).await;
+
+    // Act
+    let response = login(&app, user.get_email(), "not the password").await;
+
+    // Assert
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    assert_eq!(bcrypt_hash, stored_hash(&app, &user).await);
+}
@@ -0,0 +1,320 @@
+use std::{fs, path::PathBuf, sync::Arc};
+
+use hyper::StatusCode;
+use lockrs_server::{
+    api::v1::{models::PasswordPolicyViolation, responses::PasswordPolicyResponse},
+    utils::breached_passwords::BreachedPasswords,
+};
+use serde_json::{json, Value};
+use url::Url;
+use uuid::Uuid;
+
+use crate::common::helpers::{TestApp, TestUser};
+
+async fn register(app: &TestApp, email: &str, password: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/api/v1/auth/register", &app.get_address()))
+        .json(&json!({
+            "email": email,
+            "password": password,
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn reset(app: &TestApp, token: &str, password: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/auth/password/reset",
+            &app.get_address()
+        ))
+        .json(&json!({
+            "token": token,
+            "password": password,
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn change_password(app: &TestApp, user: &TestUser, body: &Value) -> reqwest::Response {
+    app.get_client()
+        .put(&format!(
+            "{}/api/v1/users/{}/password",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .json(body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// asks for a reset of the user's password, returning the token mailed to them
+async fn request_reset_token(app: &TestApp, user: &TestUser) -> String {
+    let already_sent = app.count_mails_for(user.get_email());
+
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/auth/password/forgot",
+            &app.get_address()
+        ))
+        .json(&json!({ "email": user.get_email() }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    let mail = app.wait_for_mail_to(user.get_email(), already_sent).await;
+
+    let link = mail
+        .body
+        .split_whitespace()
+        .find_map(|word| Url::parse(word).ok())
+        .expect("Password reset mail should contain a link.");
+
+    link.query_pairs()
+        .find(|(key, _)| key == "token")
+        .map(|(_, token)| token.into_owned())
+        .expect("Password reset link should carry a token.")
+}
+
+/// the rules the password failed, as listed in the body of a 400
+/// writes a corpus file listing the passwords' hashes in the order given, as the download does
+fn write_corpus_file(hashes: &[String]) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("lockrs_corpus_{}.txt", Uuid::new_v4().as_simple()));
+    let lines = hashes
+        .iter()
+        .enumerate()
+        .map(|(count, hash)| format!("{}:{}\r\n", hash, count + 1))
+        .collect::<String>();
+
+    fs::write(&path, lines).expect("Failed to write corpus file.");
+
+    path
+}
+
+fn sorted_hashes_of(passwords: &[String]) -> Vec<String> {
+    let mut hashes = passwords
+        .iter()
+        .map(|password| BreachedPasswords::sha1_hex(password))
+        .collect::<Vec<String>>();
+    hashes.sort();
+
+    hashes
+}
+
+async fn violations_of(response: reqwest::Response) -> Vec<PasswordPolicyViolation> {
+    response
+        .json::<PasswordPolicyResponse>()
+        .await
+        .expect("Failed to read request body.")
+        .violations
+        .into_iter()
+        .map(|violation| violation.violation)
+        .collect()
+}
+
+#[tokio::test]
+async fn register_returns_a_400_listing_a_password_that_is_too_short() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate();
+
+    // Act
+    let response = register(&app, user.get_email(), "short").await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+    assert_eq!("too_short", body["violations"][0]["rule"]);
+    assert_eq!(8, body["violations"][0]["min_length"]);
+    assert!(body["violations"][0]["message"].is_string());
+}
+
+#[tokio::test]
+async fn register_returns_a_400_listing_every_missing_character_class() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(|config| {
+        config.password_policy.require_uppercase = true;
+        config.password_policy.require_digit = true;
+        config.password_policy.require_symbol = true;
+    })
+    .await;
+    let user = TestUser::generate();
+
+    // Act
+    let response = register(&app, user.get_email(), "only lowercase").await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        vec![
+            PasswordPolicyViolation::MissingUppercase,
+            PasswordPolicyViolation::MissingDigit,
+        ],
+        violations_of(response).await
+    );
+
+    let accepted_response = register(&app, user.get_email(), "Lowercase 4nd more").await;
+    assert_eq!(StatusCode::OK, accepted_response.status());
+}
+
+#[tokio::test]
+async fn register_returns_a_400_for_a_password_containing_the_email() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate();
+    let local_part = user
+        .get_email()
+        .split('@')
+        .next()
+        .expect("Test email should have a local part.");
+
+    // Act
+    let response = register(
+        &app,
+        user.get_email(),
+        format!("{}-password", local_part.to_uppercase()).as_str(),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        vec![PasswordPolicyViolation::ContainsEmail],
+        violations_of(response).await
+    );
+}
+
+#[tokio::test]
+async fn register_returns_a_400_for_a_breached_password() {
+    // Arrange
+    let breached_passwords =
+        BreachedPasswords::from_lines([format!("{}:42", BreachedPasswords::sha1_hex("password1"))]);
+    let app = TestApp::spawn_in_memory_with(|config| {
+        config.password_policy.breached_passwords = Some(Arc::new(breached_passwords));
+    })
+    .await;
+    let user = TestUser::generate();
+
+    // Act
+    let response = register(&app, user.get_email(), "password1").await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        vec![PasswordPolicyViolation::Breached],
+        violations_of(response).await
+    );
+}
+
+#[tokio::test]
+async fn register_returns_a_400_for_a_password_in_a_corpus_file() {
+    // Arrange
+    let passwords = (0..100)
+        .map(|i| format!("password{}", i))
+        .collect::<Vec<String>>();
+    let path = write_corpus_file(&sorted_hashes_of(&passwords));
+    let breached_passwords = BreachedPasswords::load(&path).expect("Failed to load corpus file.");
+    let app = TestApp::spawn_in_memory_with(|config| {
+        config.password_policy.breached_passwords = Some(Arc::new(breached_passwords));
+    })
+    .await;
+    let user = TestUser::generate();
+
+    // Act
+    let response = register(&app, user.get_email(), "password42").await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        vec![PasswordPolicyViolation::Breached],
+        violations_of(response).await
+    );
+}
+
+#[tokio::test]
+async fn corpus_file_is_searched_for_every_hash_it_lists() {
+    // Arrange
+    let passwords = (0..1000)
+        .map(|i| format!("password{}", i))
+        .collect::<Vec<String>>();
+    let path = write_corpus_file(&sorted_hashes_of(&passwords));
+
+    // Act
+    let breached_passwords = BreachedPasswords::load(&path).expect("Failed to load corpus file.");
+
+    // Assert
+    assert!(passwords
+        .iter()
+        .all(|password| breached_passwords.contains(password)));
+    assert!((1000..1100).all(|i| !breached_passwords.contains(&format!("password{}", i))));
+}
+
+#[tokio::test]
+async fn corpus_file_not_sorted_by_hash_is_refused() {
+    // Arrange
+    let passwords = (0..10)
+        .map(|i| format!("password{}", i))
+        .collect::<Vec<String>>();
+    let mut hashes = sorted_hashes_of(&passwords);
+    hashes.reverse();
+    let path = write_corpus_file(&hashes);
+
+    // Act
+    let result = BreachedPasswords::load(&path);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn reset_password_keeps_the_token_usable_after_a_policy_violation() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let token = request_reset_token(&app, &user).await;
+
+    let rejected_response = reset(&app, token.as_str(), "short").await;
+    assert_eq!(StatusCode::BAD_REQUEST, rejected_response.status());
+
+    // Act
+    let response = reset(&app, token.as_str(), "a long enough password").await;
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+}
+
+#[tokio::test]
+async fn change_password_returns_a_400_for_a_new_password_violating_the_policy() {
+    // Arrange
+    let app = TestApp::spawn_in_memory_with(|config| {
+        config.password_policy.max_length = 16;
+    })
+    .await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+
+    // Act
+    let response = change_password(
+        &app,
+        &user,
+        &json!({
+            "current_password": user.get_password(),
+            "new_password": "a password that is far too long",
+        }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        vec![PasswordPolicyViolation::TooLong { max_length: 16 }],
+        violations_of(response).await
+    );
+}
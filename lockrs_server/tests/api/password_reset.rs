@@ -0,0 +1,302 @@
+use chrono::{Duration, Utc};
+use hyper::StatusCode;
+use lockrs_server::{
+    api::v1::models::UserAuthModel,
+    oauth2::v1::models::{AccessTokenCreateModel, RefreshTokenCreateModel},
+};
+use serde_json::json;
+use url::Url;
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+async fn forgot(app: &TestApp, email: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/auth/password/forgot",
+            &app.get_address()
+        ))
+        .json(&json!({ "email": email }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn reset(app: &TestApp, token: &str, password: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/auth/password/reset",
+            &app.get_address()
+        ))
+        .json(&json!({
+            "token": token,
+            "password": password,
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn login(app: &TestApp, email: &str, password: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/api/v1/auth/login", &app.get_address()))
+        .basic_auth(email, Some(password))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// asks for a reset of the user's password, returning the token mailed to them
+async fn request_reset_token(app: &TestApp, user: &TestUser) -> String {
+    let already_sent = app.count_mails_for(user.get_email());
+
+    assert_eq!(
+        StatusCode::ACCEPTED,
+        forgot(app, user.get_email()).await.status()
+    );
+
+    let mail = app.wait_for_mail_to(user.get_email(), already_sent).await;
+
+    let link = mail
+        .body
+        .split_whitespace()
+        .find_map(|word| Url::parse(word).ok())
+        .expect("Password reset mail should contain a link.");
+
+    link.query_pairs()
+        .find(|(key, _)| key == "token")
+        .map(|(_, token)| token.into_owned())
+        .expect("Password reset link should carry a token.")
+}
+
+/// stores an access token and a refresh token issued to `client` on the user's behalf,
+/// returning the refresh token
+async fn issue_refresh_token(app: &TestApp, user: &TestUser, client: &TestClient) -> String {
+    let state = app.get_state();
+    let expires_at = (Utc::now() + Duration::hours(1)).naive_utc();
+
+    let access_token = state
+        .repository_container
+        .access_token_repository
+        .create(
+            &state.db_context,
+            &AccessTokenCreateModel::new(
+                "ACCESS_TOKEN",
+                client.get_id(),
+                Some(user.get_id()),
+                &expires_at,
+                &[],
+                &[],
+            ),
+        )
+        .await
+        .expect("Failed to store access token.");
+
+    state
+        .repository_container
+        .refresh_token_repository
+        .create(
+            &state.db_context,
+            &RefreshTokenCreateModel::new(
+                "REFRESH_TOKEN",
+                access_token.id,
+                client.get_id(),
+                Some(user.get_id()),
+                &expires_at,
+                &[],
+                &[],
+                &Utc::now().naive_utc(),
+            ),
+        )
+        .await
+        .expect("Failed to store refresh token.");
+
+    "REFRESH_TOKEN".to_string()
+}
+
+#[tokio::test]
+async fn forgot_password_returns_a_202_without_mailing_an_unknown_address() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+
+    // Act
+    let response = forgot(&app, "nobody@example.com").await;
+
+    // Assert
+    assert_eq!(StatusCode::ACCEPTED, response.status());
+
+    // the lookup runs after the response, so give it the time to send anything it would
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    assert!(app.get_mailer().get_mails().is_empty());
+}
+
+#[tokio::test]
+async fn forgot_password_answers_before_mailing_a_known_address() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+
+    // Act
+    let response = forgot(&app, user.get_email()).await;
+
+    // Assert
+    assert_eq!(StatusCode::ACCEPTED, response.status());
+    app.wait_for_mail_to(user.get_email(), 0).await;
+}
+
+#[tokio::test]
+async fn reset_password_sets_the_new_password_and_logs_the_user_out_everywhere() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+    let refresh_token = issue_refresh_token(&app, &user, &client).await;
+
+    let token = request_reset_token(&app, &user).await;
+
+    // Act
+    let response = reset(&app, token.as_str(), "a new password").await;
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let session_response = app
+        .get_client()
+        .get(&format!(
+            "{}/api/v1/users/{}",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(StatusCode::UNAUTHORIZED, session_response.status());
+
+    let state = app.get_state();
+    assert!(state
+        .repository_container
+        .refresh_token_repository
+        .use_by_token(&state.db_context, refresh_token.as_str())
+        .await
+        .is_err());
+
+    let old_password_response = login(&app, user.get_email(), user.get_password()).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, old_password_response.status());
+
+    let new_password_response = login(&app, user.get_email(), "a new password").await;
+    assert_eq!(StatusCode::OK, new_password_response.status());
+}
+
+#[tokio::test]
+async fn reset_password_returns_a_400_for_a_used_token() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let token = request_reset_token(&app, &user).await;
+
+    assert_eq!(
+        StatusCode::NO_CONTENT,
+        reset(&app, token.as_str(), "a new password").await.status()
+    );
+
+    // Act
+    let response = reset(&app, token.as_str(), "another password").await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn reset_password_returns_a_400_once_the_password_changed_since_the_request() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let first_token = request_reset_token(&app, &user).await;
+    let second_token = request_reset_token(&app, &user).await;
+
+    assert_eq!(
+        StatusCode::NO_CONTENT,
+        reset(&app, second_token.as_str(), "a new password")
+            .await
+            .status()
+    );
+
+    // Act
+    let response = reset(&app, first_token.as_str(), "another password").await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn reset_password_accepts_a_token_issued_before_the_password_was_rehashed_on_login() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate();
+    let bcrypt_hash =
+        bcrypt::hash(user.get_password(), 4).expect("Failed to hash password of test user.");
+
+    let state = app.get_state();
+    state
+        .repository_container
+        .user_auth_repository
+        .create_raw(
+            &state.db_context,
+            &UserAuthModel::new(
+                user.get_id(),
+                user.get_email(),
+                bcrypt_hash.as_str(),
+                false,
+                0,
+            ),
+        )
+        .await
+        .expect("Failed to store test user in user database.");
+
+    let token = request_reset_token(&app, &user).await;
+
+    assert_eq!(
+        StatusCode::OK,
+        login(&app, user.get_email(), user.get_password())
+            .await
+            .status()
+    );
+
+    // Act
+    let response = reset(&app, token.as_str(), "a new password").await;
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+}
+
+#[tokio::test]
+async fn reset_password_returns_a_400_for_a_short_password() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let token = request_reset_token(&app, &user).await;
+
+    // Act
+    let response = reset(&app, token.as_str(), "short").await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn reset_password_sets_the_new_password_with_sqlite_repositories() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let user = TestUser::generate_stored(&app).await;
+    let token = request_reset_token(&app, &user).await;
+
+    // Act
+    let response = reset(&app, token.as_str(), "a new password").await;
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let new_password_response = login(&app, user.get_email(), "a new password").await;
+    assert_eq!(StatusCode::OK, new_password_response.status());
+}
@@ -0,0 +1,85 @@
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+async fn create_redirect(app: &TestApp, body: &Value) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/api/v1/redirects", &app.get_address()))
+        .json(body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn create_redirect_returns_a_200_for_a_loopback_redirect() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    // Act
+    let response = create_redirect(
+        &app,
+        &json!({
+            "client_id": client.get_id(),
+            "uri": "http://127.0.0.1/native/callback",
+            "match_mode": "loopback",
+        }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert_eq!(body["match_mode"], "loopback");
+}
+
+#[tokio::test]
+async fn create_redirect_returns_a_400_for_unsafe_redirects() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    let test_cases = vec![
+        (
+            json!("https://example.com/callback#fragment"),
+            json!("exact"),
+        ),
+        (json!("http://example.com/callback"), json!("exact")),
+        (json!("https://*.example.com/callback"), json!("exact")),
+        (json!("http://localhost/callback"), json!("loopback")),
+        (json!("myapp:/callback"), json!("private_use")),
+    ];
+
+    for (uri, match_mode) in test_cases {
+        // Act
+        let response = create_redirect(
+            &app,
+            &json!({
+                "client_id": client.get_id(),
+                "uri": uri,
+                "match_mode": match_mode,
+            }),
+        )
+        .await;
+
+        // Assert
+        assert_eq!(
+            StatusCode::BAD_REQUEST,
+            response.status(),
+            "Registering {} with match mode {} should be rejected.",
+            uri,
+            match_mode
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+async fn create_scope(app: &TestApp, client: &TestClient, name: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/clients/{}/scopes",
+            &app.get_address(),
+            client.get_id()
+        ))
+        .json(&json!({
+            "name": name,
+            "description": "Read access to the user's photos.",
+            "consent_text": "View your photos",
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn create_scope_returns_a_200_with_a_namespaced_name() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    // Act
+    let response = create_scope(&app, &client, "photos.read").await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert_eq!(body["name"], format!("{}:photos.read", client.get_id()));
+    assert_eq!(body["consent_text"], "View your photos");
+}
+
+#[tokio::test]
+async fn create_scope_returns_a_400_for_invalid_names() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    let test_cases = vec!["", "openid other", "other_client:openid", "Photos"];
+
+    for name in test_cases {
+        // Act
+        let response = create_scope(&app, &client, name).await;
+
+        // Assert
+        assert_eq!(
+            StatusCode::BAD_REQUEST,
+            response.status(),
+            "Creating a scope named {:?} should be rejected.",
+            name
+        );
+    }
+}
+
+#[tokio::test]
+async fn delete_scope_returns_a_204_and_removes_the_scope() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+    create_scope(&app, &client, "photos.read").await;
+
+    // Act
+    let response = app
+        .get_client()
+        .delete(&format!(
+            "{}/api/v1/clients/{}/scopes/photos.read",
+            &app.get_address(),
+            client.get_id()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let body = app
+        .get_client()
+        .get(&format!(
+            "{}/api/v1/clients/{}/scopes",
+            &app.get_address(),
+            client.get_id()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert_eq!(body["scopes"], json!([]));
+}
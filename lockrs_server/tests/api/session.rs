@@ -23,6 +23,7 @@ async fn session_create_returns_a_200_for_valid_bearer_auth() {
             .repository_container
             .session_token_repository,
         test_user.get_id(),
+        false,
     )
     .await
     .expect("Unable to create session token.");
@@ -120,6 +121,7 @@ async fn session_create_returns_a_401_for_invalid_token() {
                 token: SessionTokenService::generate_session_token(),
                 user_id: *test_user.get_id(),
                 expires_at: chrono::Utc::now().timestamp() - 1,
+                mfa_pending: false,
             },
         )
         .await
@@ -133,6 +135,7 @@ async fn session_create_returns_a_401_for_invalid_token() {
             .repository_container
             .session_token_repository,
         test_user.get_id(),
+        false,
     )
     .await
     .expect("Failed to create used token.")
@@ -431,3 +434,50 @@ async fn max_one_session_exists_per_user() {
     // Assert
     assert_eq!(StatusCode::NOT_FOUND, response.status());
 }
+
+#[tokio::test]
+async fn session_create_returns_a_200_with_in_memory_repositories() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let test_user = TestUser::generate_stored(&app).await;
+
+    // Act
+    let auth_info = test_user.login(&app).await;
+
+    // Assert
+    assert_eq!(test_user.get_id(), auth_info.get_user_id());
+    assert!(auth_info.get_auth_cookie().is_some());
+
+    SessionService::get_session(
+        &app.get_state().db_context,
+        &*app.get_state().repository_container.session_repository,
+        test_user.get_id(),
+        auth_info.get_session_id(),
+    )
+    .await
+    .expect("Session not created.");
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn session_create_returns_a_200_with_sqlite_repositories() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let test_user = TestUser::generate_stored(&app).await;
+
+    // Act
+    let auth_info = test_user.login(&app).await;
+
+    // Assert
+    assert_eq!(test_user.get_id(), auth_info.get_user_id());
+    assert!(auth_info.get_auth_cookie().is_some());
+
+    SessionService::get_session(
+        &app.get_state().db_context,
+        &*app.get_state().repository_container.session_repository,
+        test_user.get_id(),
+        auth_info.get_session_id(),
+    )
+    .await
+    .expect("Session not created.");
+}
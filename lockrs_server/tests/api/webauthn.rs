@@ -0,0 +1,307 @@
+use hyper::StatusCode;
+use lockrs_server::api::v1::responses::{
+    SessionTokenResponse, WebauthnCredentialListResponse, WebauthnLoginOptionsResponse,
+    WebauthnRegistrationOptionsResponse,
+};
+use serde_json::json;
+
+use crate::common::{
+    authenticator::TestAuthenticator,
+    helpers::{TestApp, TestUser},
+};
+
+async fn registration_options(app: &TestApp) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/auth/webauthn/register/options",
+            &app.get_address()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn register(app: &TestApp, body: serde_json::Value) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/auth/webauthn/register",
+            &app.get_address()
+        ))
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// registers a new passkey for the logged in user
+async fn register_authenticator(app: &TestApp) -> TestAuthenticator {
+    let mut authenticator = TestAuthenticator::generate();
+
+    let options = registration_options(app)
+        .await
+        .json::<WebauthnRegistrationOptionsResponse>()
+        .await
+        .expect("Failed to read request body.");
+
+    let register_response = register(app, authenticator.register(&options)).await;
+    assert_eq!(StatusCode::OK, register_response.status());
+
+    authenticator
+}
+
+async fn login_options(app: &TestApp, pending_token: Option<&str>) -> WebauthnLoginOptionsResponse {
+    let request = app.get_client().post(&format!(
+        "{}/api/v1/auth/webauthn/login/options",
+        &app.get_address()
+    ));
+
+    let request = match pending_token {
+        Some(pending_token) => request.bearer_auth(pending_token),
+        None => request,
+    };
+
+    request
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<WebauthnLoginOptionsResponse>()
+        .await
+        .expect("Failed to read request body.")
+}
+
+async fn login(
+    app: &TestApp,
+    pending_token: Option<&str>,
+    body: serde_json::Value,
+) -> reqwest::Response {
+    let request = app
+        .get_client()
+        .post(&format!(
+            "{}/api/v1/auth/webauthn/login",
+            &app.get_address()
+        ))
+        .json(&body);
+
+    let request = match pending_token {
+        Some(pending_token) => request.bearer_auth(pending_token),
+        None => request,
+    };
+
+    request.send().await.expect("Failed to execute request.")
+}
+
+async fn start_session(app: &TestApp, session_token: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/api/v1/sessions", &app.get_address()))
+        .bearer_auth(session_token)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn webauthn_passkey_logs_in_without_a_password() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+    let mut authenticator = register_authenticator(&app).await;
+
+    // Act
+    let options = login_options(&app, None).await;
+    let login_response = login(&app, None, authenticator.sign(&options)).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, login_response.status());
+    assert_eq!("required", options.public_key.user_verification);
+    assert!(options.public_key.allow_credentials.is_empty());
+
+    let session_token = login_response
+        .json::<SessionTokenResponse>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert!(!session_token.mfa_required);
+
+    let session_response = start_session(&app, &session_token.session_token).await;
+    assert_eq!(StatusCode::OK, session_response.status());
+
+    // Act 2: the credential is listed with the counter it last signed with
+    let credentials = app
+        .get_client()
+        .get(&format!(
+            "{}/api/v1/users/{}/webauthn/credentials",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<WebauthnCredentialListResponse>()
+        .await
+        .expect("Failed to read request body.")
+        .credentials;
+
+    // Assert 2
+    assert_eq!(1, credentials.len());
+    assert_eq!(
+        authenticator.get_credential_id(),
+        credentials[0].credential_id
+    );
+    assert_eq!(1, credentials[0].sign_count);
+    assert!(credentials[0].last_used_at.is_some());
+}
+
+#[tokio::test]
+async fn webauthn_passkey_completes_a_password_login_as_a_second_factor() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+    let mut authenticator = register_authenticator(&app).await;
+
+    let pending_token = app
+        .get_client()
+        .post(&format!("{}/api/v1/auth/login", &app.get_address()))
+        .basic_auth(user.get_email(), Some(user.get_password()))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<SessionTokenResponse>()
+        .await
+        .expect("Failed to read request body.");
+
+    // Act
+    let options = login_options(&app, Some(&pending_token.session_token)).await;
+    let login_response = login(
+        &app,
+        Some(&pending_token.session_token),
+        authenticator.sign(&options),
+    )
+    .await;
+
+    // Assert
+    assert!(pending_token.mfa_required);
+    assert_eq!(1, options.public_key.allow_credentials.len());
+    assert_eq!(
+        authenticator.get_credential_id(),
+        options.public_key.allow_credentials[0].id
+    );
+    assert_eq!(StatusCode::OK, login_response.status());
+
+    let session_token = login_response
+        .json::<SessionTokenResponse>()
+        .await
+        .expect("Failed to read request body.");
+
+    let session_response = start_session(&app, &session_token.session_token).await;
+    assert_eq!(StatusCode::OK, session_response.status());
+
+    // Act 2: the pending token was used up by the passkey
+    let options = login_options(&app, None).await;
+    let replay_response = login(
+        &app,
+        Some(&pending_token.session_token),
+        authenticator.sign(&options),
+    )
+    .await;
+
+    // Assert 2
+    assert_eq!(StatusCode::UNAUTHORIZED, replay_response.status());
+}
+
+#[tokio::test]
+async fn webauthn_login_rejects_a_cloned_authenticator() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    TestUser::generate_logged_in(&app).await;
+    let mut authenticator = register_authenticator(&app).await;
+
+    let options = login_options(&app, None).await;
+    let login_response = login(&app, None, authenticator.sign(&options)).await;
+    assert_eq!(StatusCode::OK, login_response.status());
+
+    // Act
+    authenticator.set_sign_count(0);
+
+    let options = login_options(&app, None).await;
+    let login_response = login(&app, None, authenticator.sign(&options)).await;
+
+    // Assert
+    assert_eq!(StatusCode::UNAUTHORIZED, login_response.status());
+}
+
+#[tokio::test]
+async fn webauthn_login_rejects_a_replayed_challenge() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    TestUser::generate_logged_in(&app).await;
+    let mut authenticator = register_authenticator(&app).await;
+
+    let options = login_options(&app, None).await;
+    let login_response = login(&app, None, authenticator.sign(&options)).await;
+    assert_eq!(StatusCode::OK, login_response.status());
+
+    // Act
+    let replay_response = login(&app, None, authenticator.sign(&options)).await;
+
+    // Assert
+    assert_eq!(StatusCode::UNAUTHORIZED, replay_response.status());
+}
+
+#[tokio::test]
+async fn webauthn_registration_rejects_a_response_from_another_origin() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    TestUser::generate_logged_in(&app).await;
+    let mut authenticator = TestAuthenticator::generate();
+
+    let options = registration_options(&app)
+        .await
+        .json::<WebauthnRegistrationOptionsResponse>()
+        .await
+        .expect("Failed to read request body.");
+
+    // Act
+    let register_response = register(
+        &app,
+        authenticator.register_from(&options, "https://phishing.example.com"),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::UNAUTHORIZED, register_response.status());
+}
+
+#[tokio::test]
+async fn webauthn_registration_options_returns_a_401_without_a_session() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+
+    // Act
+    let options_response = registration_options(&app).await;
+
+    // Assert
+    assert_eq!(StatusCode::UNAUTHORIZED, options_response.status());
+}
+
+#[tokio::test]
+async fn webauthn_login_returns_a_400_for_a_malformed_body() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+
+    // Act
+    let login_response = login(
+        &app,
+        None,
+        json!({
+            "challenge_id": "unknown",
+            "credential_id": "unknown",
+            "client_data_json": "not base64!",
+            "authenticator_data": "",
+            "signature": "",
+        }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, login_response.status());
+}
@@ -0,0 +1,192 @@
+use base64::{engine::general_purpose, Engine as _};
+use lockrs_server::api::v1::responses::{
+    WebauthnLoginOptionsResponse, WebauthnRegistrationOptionsResponse,
+};
+use ring::{
+    digest,
+    rand::{SecureRandom, SystemRandom},
+    signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING},
+};
+use serde_json::json;
+
+pub const TEST_RP_ID: &str = "localhost";
+pub const TEST_ORIGIN: &str = "http://localhost:8000";
+
+/// user present and user verified
+const FLAGS: u8 = 0x05;
+/// attested credential data included
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+/// A software passkey holding a single ES256 credential, standing in for the browser and the
+/// authenticator in a ceremony.
+pub struct TestAuthenticator {
+    key_pair: EcdsaKeyPair,
+    credential_id: Vec<u8>,
+    sign_count: u32,
+}
+
+impl TestAuthenticator {
+    pub fn generate() -> Self {
+        let rng = SystemRandom::new();
+
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng)
+            .expect("Failed to generate key pair.");
+        let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref())
+            .expect("Failed to parse key pair.");
+
+        let mut credential_id = vec![0u8; 16];
+        rng.fill(&mut credential_id)
+            .expect("Failed to generate credential id.");
+
+        Self {
+            key_pair,
+            credential_id,
+            sign_count: 0,
+        }
+    }
+
+    pub fn get_credential_id(&self) -> String {
+        general_purpose::URL_SAFE_NO_PAD.encode(&self.credential_id)
+    }
+
+    /// rewinds the signature counter, as a copy of the credential taken earlier would
+    pub fn set_sign_count(&mut self, sign_count: u32) {
+        self.sign_count = sign_count;
+    }
+
+    /// The body to register the credential with, in answer to `options`.
+    pub fn register(&mut self, options: &WebauthnRegistrationOptionsResponse) -> serde_json::Value {
+        self.register_from(options, TEST_ORIGIN)
+    }
+
+    pub fn register_from(
+        &mut self,
+        options: &WebauthnRegistrationOptionsResponse,
+        origin: &str,
+    ) -> serde_json::Value {
+        let client_data_json = Self::client_data(
+            "webauthn.create",
+            options.public_key.challenge.as_str(),
+            origin,
+        );
+
+        let mut authenticator_data = self.authenticator_data(FLAGS | FLAG_ATTESTED_CREDENTIAL_DATA);
+        // an all zero aaguid, as sent with `none` attestation
+        authenticator_data.extend_from_slice(&[0u8; 16]);
+        authenticator_data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+        authenticator_data.extend_from_slice(&self.credential_id);
+        authenticator_data.extend_from_slice(&self.cose_public_key());
+
+        let mut attestation_object = Vec::new();
+        encode_header(&mut attestation_object, 5, 3);
+        encode_text(&mut attestation_object, "fmt");
+        encode_text(&mut attestation_object, "none");
+        encode_text(&mut attestation_object, "attStmt");
+        encode_header(&mut attestation_object, 5, 0);
+        encode_text(&mut attestation_object, "authData");
+        encode_bytes(&mut attestation_object, &authenticator_data);
+
+        json!({
+            "challenge_id": options.challenge_id,
+            "client_data_json": general_purpose::URL_SAFE_NO_PAD.encode(client_data_json),
+            "attestation_object": general_purpose::URL_SAFE_NO_PAD.encode(attestation_object),
+        })
+    }
+
+    /// The body to log in with, signing the challenge in `options`.
+    pub fn sign(&mut self, options: &WebauthnLoginOptionsResponse) -> serde_json::Value {
+        self.sign_count += 1;
+
+        let client_data_json = Self::client_data(
+            "webauthn.get",
+            options.public_key.challenge.as_str(),
+            TEST_ORIGIN,
+        );
+        let authenticator_data = self.authenticator_data(FLAGS);
+
+        let client_data_hash = digest::digest(&digest::SHA256, client_data_json.as_bytes());
+        let message = [authenticator_data.as_slice(), client_data_hash.as_ref()].concat();
+
+        let signature = self
+            .key_pair
+            .sign(&SystemRandom::new(), message.as_slice())
+            .expect("Failed to sign assertion.");
+
+        json!({
+            "challenge_id": options.challenge_id,
+            "credential_id": self.get_credential_id(),
+            "client_data_json": general_purpose::URL_SAFE_NO_PAD.encode(client_data_json),
+            "authenticator_data": general_purpose::URL_SAFE_NO_PAD.encode(authenticator_data),
+            "signature": general_purpose::URL_SAFE_NO_PAD.encode(signature.as_ref()),
+        })
+    }
+
+    fn client_data(ceremony: &str, challenge: &str, origin: &str) -> String {
+        json!({
+            "type": ceremony,
+            "challenge": challenge,
+            "origin": origin,
+            "crossOrigin": false,
+        })
+        .to_string()
+    }
+
+    fn authenticator_data(&self, flags: u8) -> Vec<u8> {
+        let mut authenticator_data = digest::digest(&digest::SHA256, TEST_RP_ID.as_bytes())
+            .as_ref()
+            .to_vec();
+        authenticator_data.push(flags);
+        authenticator_data.extend_from_slice(&self.sign_count.to_be_bytes());
+
+        authenticator_data
+    }
+
+    /// the public key as a COSE_Key map of kty EC2, alg ES256, crv P-256 and the point
+    fn cose_public_key(&self) -> Vec<u8> {
+        // the uncompressed point, 0x04 || x || y
+        let point = self.key_pair.public_key().as_ref();
+
+        let mut cose_key = Vec::new();
+        encode_header(&mut cose_key, 5, 5);
+        encode_int(&mut cose_key, 1);
+        encode_int(&mut cose_key, 2);
+        encode_int(&mut cose_key, 3);
+        encode_int(&mut cose_key, -7);
+        encode_int(&mut cose_key, -1);
+        encode_int(&mut cose_key, 1);
+        encode_int(&mut cose_key, -2);
+        encode_bytes(&mut cose_key, &point[1..33]);
+        encode_int(&mut cose_key, -3);
+        encode_bytes(&mut cose_key, &point[33..65]);
+
+        cose_key
+    }
+}
+
+fn encode_header(buffer: &mut Vec<u8>, major_type: u8, argument: usize) {
+    match argument {
+        0..=23 => buffer.push((major_type << 5) | argument as u8),
+        24..=0xff => buffer.extend_from_slice(&[(major_type << 5) | 24, argument as u8]),
+        _ => {
+            buffer.push((major_type << 5) | 25);
+            buffer.extend_from_slice(&(argument as u16).to_be_bytes());
+        }
+    }
+}
+
+fn encode_int(buffer: &mut Vec<u8>, value: i64) {
+    match value {
+        0.. => encode_header(buffer, 0, value as usize),
+        _ => encode_header(buffer, 1, (-1 - value) as usize),
+    }
+}
+
+fn encode_bytes(buffer: &mut Vec<u8>, bytes: &[u8]) {
+    encode_header(buffer, 2, bytes.len());
+    buffer.extend_from_slice(bytes);
+}
+
+fn encode_text(buffer: &mut Vec<u8>, text: &str) {
+    encode_header(buffer, 3, text.len());
+    buffer.extend_from_slice(text.as_bytes());
+}
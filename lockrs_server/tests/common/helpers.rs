@@ -1,33 +1,85 @@
-use std::{net::TcpListener, time::Duration};
+use std::{net::TcpListener, path::PathBuf, sync::Arc, time::Duration};
 
 use diesel::{pg::Pg, Connection, PgConnection, RunQueryDsl};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use lockrs_server::{
     api::v1::{
+        mailers::{InMemoryMailer, Mail},
         models::{SessionModel, UserAuthModel},
         responses::{SessionResponse, SessionTokenResponse},
         services::UserAuthService,
     },
+    db::memory::InMemoryStore,
+    models::{ClientAuthModel, RedirectCreateModel, RedirectMatchMode},
+    oauth2::v1::notifiers::LocalAuthenticationDeviceNotifier,
+    services::ClientAuthService,
     utils::jwt::JwtUtil,
-    AppConfig, AppState,
+    AppConfig, AppState, EmailVerificationPolicy, LoginThrottleConfig, MailTransport,
+    PasswordHashingConfig, PasswordPolicy, SessionStore, StorageBackend,
 };
+use url::Url;
 use uuid::Uuid;
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations/");
 
+/// far cheaper than anything fit for production, as tests hash a password for every user they
+/// store
+pub const TEST_PASSWORD_HASHING: PasswordHashingConfig = PasswordHashingConfig {
+    memory_kib: 1024,
+    iterations: 1,
+    parallelism: 1,
+};
+
+/// The config every test app starts from, before the spawn functions point it at their storage
+/// and tests change what they need.
+pub fn test_config(storage_backend: StorageBackend, session_store: SessionStore) -> AppConfig {
+    AppConfig {
+        storage_backend,
+        session_store,
+        postgres_url: String::new(),
+        redis_url: String::new(),
+        sqlite_url: String::new(),
+        auth_interval: chrono::Duration::minutes(10),
+        key_interval: chrono::Duration::minutes(11),
+        gc_interval: chrono::Duration::hours(1),
+        gc_batch_size: 1000,
+        gc_retention: chrono::Duration::days(1),
+        cache_capacity: 1000,
+        cache_ttl: chrono::Duration::seconds(60),
+        cache_redis: false,
+        webauthn_rp_id: String::from("localhost"),
+        webauthn_origin: String::from("http://localhost:8000"),
+        frontend_url: String::from("http://localhost:8000"),
+        mail_transport: MailTransport::InMemory,
+        mail_from: String::from("no-reply@localhost"),
+        link_signing_key: b"test-link-signing-key".to_vec(),
+        email_verification: EmailVerificationPolicy::Optional,
+        password_hashing: TEST_PASSWORD_HASHING,
+        password_policy: PasswordPolicy::default(),
+        login_throttle: LoginThrottleConfig::default(),
+        admin_api_key: None,
+        federation_providers: vec![],
+        allow_private_notification_endpoints: false,
+    }
+}
+
 pub struct TestApp {
     address: String,
     state: AppState,
     client: reqwest::Client,
+    authentication_device_notifier: Arc<LocalAuthenticationDeviceNotifier>,
+    mailer: Arc<InMemoryMailer>,
 
-    pg_base_url: String,
-    pg_db_name: String,
+    /// the base url and name of the database created for the test, if it runs against pg
+    pg_database: Option<(String, String)>,
+
+    /// the path of the database file created for the test, if it runs against sqlite
+    sqlite_database: Option<PathBuf>,
 }
 
 impl TestApp {
     pub async fn spawn() -> TestApp {
         let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind to random port");
-        let port = listener.local_addr().unwrap().port();
 
         // configure pg db for test
         let pg_base_url = String::from("postgres://postgres@localhost");
@@ -44,12 +96,82 @@ impl TestApp {
         let test_config = AppConfig {
             postgres_url,
             redis_url: String::from("redis://localhost:6379"),
-            auth_interval: chrono::Duration::minutes(10),
-            key_interval: chrono::Duration::minutes(11),
+            ..test_config(StorageBackend::Postgres, SessionStore::Redis)
         };
 
         let state = AppState::new(Some(test_config)).await;
 
+        Self::serve(listener, state, Some((pg_base_url, pg_db_name))).await
+    }
+
+    /// Spawns the app on the in-memory repositories, for tests that need neither postgres nor
+    /// redis.
+    pub async fn spawn_in_memory() -> TestApp {
+        Self::spawn_in_memory_with(|_| {}).await
+    }
+
+    /// Spawns the app on the in-memory repositories, with the config changed by `configure`
+    /// first.
+    pub async fn spawn_in_memory_with<F>(configure: F) -> TestApp
+    where
+        F: FnOnce(&mut AppConfig),
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind to random port");
+
+        let mut test_config = test_config(StorageBackend::InMemory, SessionStore::Redis);
+
+        configure(&mut test_config);
+
+        let state = AppState::in_memory(Some(test_config), Arc::new(InMemoryStore::default()));
+
+        Self::serve(listener, state, None).await
+    }
+
+    /// Spawns the app on the sqlite repositories, with a database file of its own that is removed
+    /// once the test is done.
+    #[cfg(feature = "sqlite")]
+    pub async fn spawn_sqlite() -> TestApp {
+        Self::spawn_sqlite_with(|_| {}).await
+    }
+
+    /// Spawns the app on the sqlite repositories, with the config changed by `configure` first.
+    #[cfg(feature = "sqlite")]
+    pub async fn spawn_sqlite_with<F>(configure: F) -> TestApp
+    where
+        F: FnOnce(&mut AppConfig),
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind to random port");
+
+        let sqlite_database =
+            std::env::temp_dir().join(format!("lockrs_test_{}.db", Uuid::new_v4().as_simple()));
+
+        let mut test_config = AppConfig {
+            sqlite_url: sqlite_database.to_string_lossy().into_owned(),
+            ..test_config(StorageBackend::Sqlite, SessionStore::Sqlite)
+        };
+
+        configure(&mut test_config);
+
+        let state = AppState::new(Some(test_config)).await;
+
+        let mut app = Self::serve(listener, state, None).await;
+        app.sqlite_database = Some(sqlite_database);
+        app
+    }
+
+    async fn serve(
+        listener: TcpListener,
+        mut state: AppState,
+        pg_database: Option<(String, String)>,
+    ) -> TestApp {
+        let port = listener.local_addr().unwrap().port();
+
+        let authentication_device_notifier = Arc::new(LocalAuthenticationDeviceNotifier::default());
+        state.authentication_device_notifier = authentication_device_notifier.clone();
+
+        let mailer = Arc::new(InMemoryMailer::default());
+        state.mailer = mailer.clone();
+
         let client = reqwest::ClientBuilder::new()
             .cookie_store(true)
             .build()
@@ -65,8 +187,10 @@ impl TestApp {
             address: format!("http://127.0.0.1:{}", port),
             state,
             client,
-            pg_base_url,
-            pg_db_name,
+            authentication_device_notifier,
+            mailer,
+            pg_database,
+            sqlite_database: None,
         }
     }
 
@@ -82,6 +206,39 @@ impl TestApp {
         &self.client
     }
 
+    pub fn get_authentication_device_notifier(&self) -> &LocalAuthenticationDeviceNotifier {
+        &self.authentication_device_notifier
+    }
+
+    pub fn get_mailer(&self) -> &InMemoryMailer {
+        &self.mailer
+    }
+
+    pub fn count_mails_for(&self, email: &str) -> usize {
+        self.mailer
+            .get_mails()
+            .iter()
+            .filter(|mail| mail.to == email)
+            .count()
+    }
+
+    /// Waits for a mail to `email` beyond the `already_sent` ones, for mail that goes out after the
+    /// response.
+    pub async fn wait_for_mail_to(&self, email: &str, already_sent: usize) -> Mail {
+        for _ in 0..50 {
+            if self.count_mails_for(email) > already_sent {
+                return self
+                    .mailer
+                    .get_latest_for(email)
+                    .expect("No mail was sent.");
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        panic!("No mail was sent to {}.", email);
+    }
+
     fn configure_pg(base_url: &str, db_name: &str) {
         let pg_url = format!("{}/postgres", base_url);
         let conn =
@@ -100,7 +257,20 @@ impl TestApp {
 
 impl Drop for TestApp {
     fn drop(&mut self) {
-        let pg_url = format!("{}/postgres", self.pg_base_url);
+        if let Some(sqlite_database) = &self.sqlite_database {
+            for suffix in ["", "-wal", "-shm"] {
+                let mut path = sqlite_database.clone().into_os_string();
+                path.push(suffix);
+                let _ = std::fs::remove_file(path);
+            }
+        }
+
+        let Some((pg_base_url, pg_db_name)) = &self.pg_database
+        else {
+            return;
+        };
+
+        let pg_url = format!("{}/postgres", pg_base_url);
         let conn =
             &mut PgConnection::establish(&pg_url).expect("Error connecting to postgres database.");
 
@@ -108,14 +278,14 @@ impl Drop for TestApp {
             "SELECT pg_terminate_backend(pid)
 FROM pg_stat_activity
 WHERE datname = '{}';",
-            self.pg_db_name
+            pg_db_name
         );
 
         diesel::sql_query(disconnect_users).execute(conn).unwrap();
 
-        diesel::sql_query(format!("DROP DATABASE {};", self.pg_db_name))
+        diesel::sql_query(format!("DROP DATABASE {};", pg_db_name))
             .execute(conn)
-            .unwrap_or_else(|_| panic!("Couldn't drop database {}.", self.pg_db_name));
+            .unwrap_or_else(|_| panic!("Couldn't drop database {}.", pg_db_name));
     }
 }
 
@@ -219,10 +389,19 @@ impl TestUser {
     }
 
     pub async fn store(&self, app: &TestApp) {
-        let password_hash = UserAuthService::hash_password(self.password.as_str())
-            .expect("Failed to hash password of test user.");
-
-        let user_auth = UserAuthModel::new(&self.id, self.email.as_str(), password_hash.as_str());
+        let password_hash = UserAuthService::hash_password(
+            self.password.as_str(),
+            &app.state.config.password_hashing,
+        )
+        .expect("Failed to hash password of test user.");
+
+        let user_auth = UserAuthModel::new(
+            &self.id,
+            self.email.as_str(),
+            password_hash.as_str(),
+            false,
+            0,
+        );
 
         app.state
             .repository_container
@@ -296,3 +475,71 @@ impl TestUser {
         (user, auth_info)
     }
 }
+
+pub struct TestClient {
+    id: String,
+    secret: String,
+    redirect_url: Url,
+}
+
+impl TestClient {
+    pub fn generate() -> Self {
+        Self {
+            id: ClientAuthService::generate_random_string(),
+            secret: ClientAuthService::generate_random_string(),
+            redirect_url: Url::parse("http://127.0.0.1/callback")
+                .expect("Failed to parse test client redirect url."),
+        }
+    }
+
+    pub fn get_id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn get_secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn get_redirect_url(&self) -> &Url {
+        &self.redirect_url
+    }
+
+    pub async fn store(&self, app: &TestApp, owner: &TestUser) {
+        let client_auth = ClientAuthModel::new(
+            owner.get_id(),
+            self.id.as_str(),
+            false,
+            "Test Client",
+            "A client used for integration tests.",
+            "http://127.0.0.1/",
+        );
+
+        let redirect = RedirectCreateModel::new(
+            self.id.as_str(),
+            &self.redirect_url,
+            RedirectMatchMode::Exact,
+        );
+
+        app.state
+            .repository_container
+            .client_auth_repository
+            .create(
+                &app.state.db_context,
+                &client_auth,
+                Some(&ClientAuthService::hash_secret(
+                    self.id.as_str(),
+                    self.secret.as_str(),
+                    None,
+                )),
+                &redirect,
+            )
+            .await
+            .expect("Failed to store test client in client database.");
+    }
+
+    pub async fn generate_stored(app: &TestApp, owner: &TestUser) -> Self {
+        let client = Self::generate();
+        client.store(app, owner).await;
+        client
+    }
+}
@@ -0,0 +1,317 @@
+use std::{
+    collections::HashMap,
+    net::TcpListener,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    extract::{Form, Query, State},
+    http::{header::LOCATION, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use base64::{engine::general_purpose, Engine as _};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use lockrs_server::{utils::extractors::BearerAuth, FederationProtocol, FederationProvider};
+use ring::{
+    digest,
+    rand::SystemRandom,
+    signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING},
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use url::Url;
+use uuid::Uuid;
+
+pub const MOCK_IDP_CLIENT_ID: &str = "lockrs";
+pub const MOCK_IDP_CLIENT_SECRET: &str = "mock-idp-client-secret";
+const MOCK_IDP_KEY_ID: &str = "mock-idp-key";
+
+/// Who the mock identity provider signs the user in as.
+#[derive(Clone)]
+pub struct MockIdpUser {
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+}
+
+impl MockIdpUser {
+    pub fn generate() -> Self {
+        Self {
+            // numeric, so it can be served as GitHub's `id` as well
+            subject: rand::random::<u32>().to_string(),
+            email: Some(format!("{}@example.com", Uuid::new_v4().simple())),
+            email_verified: true,
+        }
+    }
+}
+
+struct PendingCode {
+    redirect_uri: String,
+    code_challenge: String,
+    nonce: Option<String>,
+    user: MockIdpUser,
+}
+
+struct MockIdpState {
+    issuer: String,
+    pkcs8: Vec<u8>,
+    public_key: Vec<u8>,
+    user: Mutex<MockIdpUser>,
+    /// signed into id tokens in place of the nonce the sign-in was started with, when set
+    nonce_override: Mutex<Option<String>>,
+    codes: Mutex<HashMap<String, PendingCode>>,
+    access_tokens: Mutex<HashMap<String, MockIdpUser>>,
+}
+
+/// A local identity provider for lockrs to sign users in with, speaking just enough oidc, and
+/// GitHub style oauth2, for the federation endpoints to be run end to end. It signs every user
+/// in as whoever was last set with `set_user`, without asking.
+pub struct MockIdp {
+    address: String,
+    state: Arc<MockIdpState>,
+}
+
+impl MockIdp {
+    pub async fn spawn() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind to random port");
+        let address = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+            .expect("Failed to generate key.");
+        let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref())
+            .expect("Failed to read generated key.");
+
+        let state = Arc::new(MockIdpState {
+            issuer: address.to_owned(),
+            pkcs8: pkcs8.as_ref().to_vec(),
+            public_key: key_pair.public_key().as_ref().to_vec(),
+            user: Mutex::new(MockIdpUser::generate()),
+            nonce_override: Mutex::new(None),
+            codes: Mutex::new(HashMap::new()),
+            access_tokens: Mutex::new(HashMap::new()),
+        });
+
+        let router = Router::new()
+            .route("/authorize", get(authorize))
+            .route("/token", post(token))
+            .route("/jwks", get(jwks))
+            .route("/userinfo", get(userinfo))
+            .with_state(state.clone());
+
+        let server = axum::Server::from_tcp(listener)
+            .expect("Failed to bind address.")
+            .serve(router.into_make_service());
+
+        let _ = tokio::spawn(server);
+
+        Self { address, state }
+    }
+
+    /// The provider lockrs is configured with to sign in with this identity provider.
+    pub fn provider(&self, name: &str, protocol: FederationProtocol) -> FederationProvider {
+        let (issuer, jwks_uri, userinfo_endpoint, scopes, subject_claim) = match protocol {
+            FederationProtocol::Oidc => (
+                Some(self.address.to_owned()),
+                Some(format!("{}/jwks", self.address)),
+                None,
+                vec![String::from("openid"), String::from("email")],
+                String::from("sub"),
+            ),
+            FederationProtocol::OAuth2 => (
+                None,
+                None,
+                Some(format!("{}/userinfo", self.address)),
+                vec![String::from("user:email")],
+                String::from("id"),
+            ),
+        };
+
+        FederationProvider {
+            name: name.to_owned(),
+            protocol,
+            client_id: String::from(MOCK_IDP_CLIENT_ID),
+            client_secret: String::from(MOCK_IDP_CLIENT_SECRET),
+            authorization_endpoint: format!("{}/authorize", self.address),
+            token_endpoint: format!("{}/token", self.address),
+            issuer,
+            jwks_uri,
+            userinfo_endpoint,
+            scopes,
+            subject_claim,
+            link_by_email: false,
+        }
+    }
+
+    pub fn set_user(&self, user: &MockIdpUser) {
+        *self.state.user.lock().unwrap() = user.clone();
+    }
+
+    pub fn set_nonce_override(&self, nonce: Option<&str>) {
+        *self.state.nonce_override.lock().unwrap() = nonce.map(String::from);
+    }
+}
+
+#[derive(Deserialize)]
+struct AuthorizeParams {
+    client_id: String,
+    redirect_uri: String,
+    state: String,
+    code_challenge: String,
+    code_challenge_method: String,
+    nonce: Option<String>,
+}
+
+async fn authorize(
+    State(state): State<Arc<MockIdpState>>,
+    Query(params): Query<AuthorizeParams>,
+) -> impl IntoResponse {
+    if params.client_id != MOCK_IDP_CLIENT_ID || params.code_challenge_method != "S256" {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+
+    let code = Uuid::new_v4().simple().to_string();
+    let user = state.user.lock().unwrap().clone();
+
+    state.codes.lock().unwrap().insert(
+        code.to_owned(),
+        PendingCode {
+            redirect_uri: params.redirect_uri.to_owned(),
+            code_challenge: params.code_challenge,
+            nonce: params.nonce,
+            user,
+        },
+    );
+
+    let mut location = Url::parse(params.redirect_uri.as_str()).expect("Invalid redirect uri.");
+    location
+        .query_pairs_mut()
+        .append_pair("code", code.as_str())
+        .append_pair("state", params.state.as_str());
+
+    (StatusCode::FOUND, [(LOCATION, location.to_string())]).into_response()
+}
+
+#[derive(Deserialize)]
+struct TokenParams {
+    grant_type: String,
+    code: String,
+    redirect_uri: String,
+    client_id: String,
+    client_secret: String,
+    code_verifier: String,
+}
+
+async fn token(
+    State(state): State<Arc<MockIdpState>>,
+    Form(params): Form<TokenParams>,
+) -> impl IntoResponse {
+    let Some(pending) = state.codes.lock().unwrap().remove(&params.code)
+    else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+
+    let code_challenge = general_purpose::URL_SAFE_NO_PAD.encode(digest::digest(
+        &digest::SHA256,
+        params.code_verifier.as_bytes(),
+    ));
+
+    if params.grant_type != "authorization_code"
+        || params.client_id != MOCK_IDP_CLIENT_ID
+        || params.client_secret != MOCK_IDP_CLIENT_SECRET
+        || params.redirect_uri != pending.redirect_uri
+        || code_challenge != pending.code_challenge
+    {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+
+    let access_token = Uuid::new_v4().simple().to_string();
+    state
+        .access_tokens
+        .lock()
+        .unwrap()
+        .insert(access_token.to_owned(), pending.user.clone());
+
+    let mut body = json!({
+        "access_token": access_token,
+        "token_type": "Bearer",
+        "expires_in": 3600,
+    });
+
+    if let Some(nonce) = pending.nonce {
+        let nonce = state
+            .nonce_override
+            .lock()
+            .unwrap()
+            .clone()
+            .unwrap_or(nonce);
+        let now = chrono::Utc::now().timestamp();
+
+        let claims = json!({
+            "iss": state.issuer,
+            "aud": MOCK_IDP_CLIENT_ID,
+            "sub": pending.user.subject,
+            "email": pending.user.email,
+            "email_verified": pending.user.email_verified,
+            "nonce": nonce,
+            "iat": now,
+            "exp": now + 300,
+        });
+
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(String::from(MOCK_IDP_KEY_ID));
+
+        let id_token = jsonwebtoken::encode(
+            &header,
+            &claims,
+            &EncodingKey::from_ec_der(state.pkcs8.as_slice()),
+        )
+        .expect("Failed to sign id token.");
+
+        body["id_token"] = Value::String(id_token);
+    }
+
+    Json(body).into_response()
+}
+
+async fn jwks(State(state): State<Arc<MockIdpState>>) -> impl IntoResponse {
+    // an uncompressed point: 0x04 followed by the x and y coordinates
+    let (x, y) = state.public_key[1..].split_at(32);
+
+    Json(json!({
+        "keys": [{
+            "kty": "EC",
+            "crv": "P-256",
+            "use": "sig",
+            "alg": "ES256",
+            "kid": MOCK_IDP_KEY_ID,
+            "x": general_purpose::URL_SAFE_NO_PAD.encode(x),
+            "y": general_purpose::URL_SAFE_NO_PAD.encode(y),
+        }],
+    }))
+}
+
+/// Answers the way GitHub's `/user` does, with a numeric id.
+async fn userinfo(
+    State(state): State<Arc<MockIdpState>>,
+    BearerAuth(access_token): BearerAuth,
+) -> impl IntoResponse {
+    let Some(user) = state
+        .access_tokens
+        .lock()
+        .unwrap()
+        .get(&access_token)
+        .cloned()
+    else {
+        return StatusCode::UNAUTHORIZED.into_response();
+    };
+
+    Json(json!({
+        "id": user.subject.parse::<u64>().expect("Subject should be numeric."),
+        "login": "octocat",
+        "email": user.email,
+    }))
+    .into_response()
+}
@@ -0,0 +1,91 @@
+use std::{
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+use serde_json::Value;
+
+struct MockNotificationEndpointState {
+    /// how many of the next notifications are answered with a 500
+    failures_left: Mutex<u32>,
+    /// every notification body received, including those that were answered with a 500
+    notifications: Mutex<Vec<Value>>,
+}
+
+/// A client notification endpoint for ping and push mode backchannel authentication, recording
+/// every notification it is sent.
+pub struct MockNotificationEndpoint {
+    address: String,
+    state: Arc<MockNotificationEndpointState>,
+}
+
+impl MockNotificationEndpoint {
+    pub async fn spawn() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind to random port");
+        let address = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+
+        let state = Arc::new(MockNotificationEndpointState {
+            failures_left: Mutex::new(0),
+            notifications: Mutex::new(Vec::new()),
+        });
+
+        let router = Router::new()
+            .route("/notify", post(notify))
+            .with_state(state.clone());
+
+        let server = axum::Server::from_tcp(listener)
+            .expect("Failed to bind address.")
+            .serve(router.into_make_service());
+
+        let _ = tokio::spawn(server);
+
+        Self { address, state }
+    }
+
+    pub fn get_url(&self) -> String {
+        format!("{}/notify", self.address)
+    }
+
+    /// Answers the next `failures` notifications with a 500.
+    pub fn fail_next(&self, failures: u32) {
+        *self.state.failures_left.lock().unwrap() = failures;
+    }
+
+    pub fn get_notifications(&self) -> Vec<Value> {
+        self.state.notifications.lock().unwrap().clone()
+    }
+
+    /// Waits for the endpoint to have been sent `count` notifications, returning them.
+    pub async fn wait_for_notifications(&self, count: usize) -> Vec<Value> {
+        for _ in 0..100 {
+            let notifications = self.get_notifications();
+            if notifications.len() >= count {
+                return notifications;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        panic!(
+            "The client notification endpoint was not sent {} notifications.",
+            count
+        );
+    }
+}
+
+async fn notify(
+    State(state): State<Arc<MockNotificationEndpointState>>,
+    Json(body): Json<Value>,
+) -> StatusCode {
+    state.notifications.lock().unwrap().push(body);
+
+    let mut failures_left = state.failures_left.lock().unwrap();
+    if *failures_left > 0 {
+        *failures_left -= 1;
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+
+    StatusCode::NO_CONTENT
+}
@@ -1,2 +1,5 @@
+pub mod authenticator;
 mod health_check;
 pub mod helpers;
+pub mod mock_idp;
+pub mod mock_notification_endpoint;
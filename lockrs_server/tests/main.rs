@@ -1,2 +1,3 @@
 mod api;
 mod common;
+mod oauth2;
@@ -0,0 +1,682 @@
+use chrono::{Duration, Utc};
+use hyper::StatusCode;
+use lockrs_server::{
+    db::{
+        digest_token,
+        repositories::{QueryFailure, RepositoryError},
+    },
+    oauth2::v1::models::{AuthorizationCodeCreateModel, AuthorizationCodeModel},
+};
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::common::helpers::{TestApp, TestClient, TestUser, TestUserAuthInfo};
+
+const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+async fn create_code(
+    app: &TestApp,
+    client: &TestClient,
+    user: &TestUser,
+    code: &str,
+    expires_in: Duration,
+) -> AuthorizationCodeModel {
+    let state = app.get_state();
+
+    let code_create = AuthorizationCodeCreateModel::new(
+        code,
+        client.get_id(),
+        user.get_id(),
+        "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM",
+        false,
+        client.get_redirect_url(),
+        &(Utc::now() + expires_in).naive_utc(),
+        &[String::from("read")],
+        &[],
+    );
+
+    state
+        .repository_container
+        .authorization_code_repository
+        .create(&state.db_context, &code_create)
+        .await
+        .expect("Failed to create authorization code.")
+}
+
+async fn register_payment_type(app: &TestApp, client: &TestClient) {
+    let response = app
+        .get_client()
+        .post(&format!(
+            "{}/api/v1/clients/{}/authorization_details",
+            &app.get_address(),
+            client.get_id()
+        ))
+        .json(&json!({
+            "name": "payment_initiation",
+            "description": "Initiate a payment from the user's account",
+            "schema": {
+                "required": ["amount"],
+                "properties": { "amount": "string" },
+            },
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+async fn grant_consent(
+    app: &TestApp,
+    user: &TestUser,
+    client: &TestClient,
+    authorization_details: &Value,
+) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/users/{}/consents",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .json(&json!({
+            "client_id": client.get_id(),
+            "scope": "read",
+            "authorization_details": authorization_details,
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// requests a code the way the user's browser would, returning the redirect back to the client
+async fn authorize(
+    app: &TestApp,
+    auth_info: &TestUserAuthInfo,
+    client: &TestClient,
+    authorization_details: &Value,
+) -> reqwest::Response {
+    let browser = reqwest::ClientBuilder::new()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("Failed to build http client.");
+
+    let auth_cookie = auth_info
+        .get_auth_cookie()
+        .expect("Logged in user should have an auth cookie.");
+
+    browser
+        .post(&format!("{}/oauth2/v1/authorize", &app.get_address()))
+        .basic_auth(client.get_id(), Some(client.get_secret()))
+        .header(reqwest::header::COOKIE, auth_cookie.to_string())
+        .query(&[
+            ("response_type", "code"),
+            ("redirect_uri", client.get_redirect_url().as_str()),
+            ("code_challenge", digest_token(CODE_VERIFIER).as_str()),
+            ("code_challenge_method", "S256"),
+            ("scope", "read"),
+            ("state", "STATE"),
+            (
+                "authorization_details",
+                authorization_details.to_string().as_str(),
+            ),
+        ])
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+fn code_from_redirect(response: &reqwest::Response) -> String {
+    let location = response
+        .headers()
+        .get(reqwest::header::LOCATION)
+        .and_then(|location| location.to_str().ok())
+        .and_then(|location| Url::parse(location).ok())
+        .expect("Authorize should redirect back to the client.");
+
+    let query_value = |name: &str| {
+        location
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    assert_eq!(Some(String::from("STATE")), query_value("state"));
+
+    query_value("code").expect("Redirect should carry a code.")
+}
+
+async fn exchange_code(
+    app: &TestApp,
+    client: &TestClient,
+    code: &str,
+    code_verifier: &str,
+) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/oauth2/v1/token", &app.get_address()))
+        .basic_auth(client.get_id(), Some(client.get_secret()))
+        .query(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("code_verifier", code_verifier),
+            ("redirect_uri", client.get_redirect_url().as_str()),
+        ])
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// logs a user in, consents to the client and authorizes the payments, returning the issued code
+async fn authorize_payments(
+    app: &TestApp,
+    client: &TestClient,
+    authorization_details: &Value,
+) -> String {
+    let (user, auth_info) = TestUser::generate_logged_in(app).await;
+    let consent = grant_consent(app, &user, client, authorization_details).await;
+    assert_eq!(StatusCode::OK, consent.status());
+
+    let response = authorize(app, &auth_info, client, authorization_details).await;
+    assert!(response.status().is_redirection());
+
+    code_from_redirect(&response)
+}
+
+#[tokio::test]
+async fn authorization_code_grant_carries_authorization_details_into_the_token() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_payment_type(&app, &client).await;
+    let code = authorize_payments(
+        &app,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": "10.00" }]),
+    )
+    .await;
+
+    // Act
+    let response = exchange_code(&app, &client, &code, CODE_VERIFIER).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+    assert_eq!(
+        body["authorization_details"],
+        json!([{ "type": "payment_initiation", "amount": "10.00" }])
+    );
+}
+
+#[tokio::test]
+async fn authorization_code_grant_rejects_a_reused_code() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_payment_type(&app, &client).await;
+    let code = authorize_payments(
+        &app,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": "10.00" }]),
+    )
+    .await;
+    exchange_code(&app, &client, &code, CODE_VERIFIER).await;
+
+    // Act
+    let response = exchange_code(&app, &client, &code, CODE_VERIFIER).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn authorization_code_grant_rejects_the_wrong_code_verifier() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_payment_type(&app, &client).await;
+    let code = authorize_payments(
+        &app,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": "10.00" }]),
+    )
+    .await;
+
+    // Act
+    let response = exchange_code(
+        &app,
+        &client,
+        &code,
+        "WRONG-VERIFIER-WRONG-VERIFIER-WRONG-VERIFIER",
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn refresh_may_narrow_but_never_widen_the_authorization_details_of_a_code() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_payment_type(&app, &client).await;
+
+    app.get_client()
+        .put(&format!(
+            "{}/api/v1/clients/{}/policy",
+            &app.get_address(),
+            client.get_id()
+        ))
+        .json(&json!({
+            "grant_types": ["authorization_code", "refresh_token"],
+            "access_token_lifetime": 600,
+            "refresh_token_lifetime": 86400,
+            "id_token_lifetime": 3600,
+            "always_issue_refresh_token": true,
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    let code = authorize_payments(
+        &app,
+        &client,
+        &json!([
+            { "type": "payment_initiation", "amount": "10.00" },
+            { "type": "payment_initiation", "amount": "20.00" },
+        ]),
+    )
+    .await;
+
+    let token = exchange_code(&app, &client, &code, CODE_VERIFIER)
+        .await
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    let refresh = |refresh_token: String, authorization_details: Value| {
+        app.get_client()
+            .post(&format!("{}/oauth2/v1/token", &app.get_address()))
+            .basic_auth(client.get_id(), Some(client.get_secret()))
+            .query(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token.as_str()),
+                (
+                    "authorization_details",
+                    authorization_details.to_string().as_str(),
+                ),
+            ])
+            .send()
+    };
+
+    // Act
+    let narrowed = refresh(
+        token["refresh_token"]
+            .as_str()
+            .expect("Token response should carry a refresh token.")
+            .to_owned(),
+        json!([{ "type": "payment_initiation", "amount": "10.00" }]),
+    )
+    .await
+    .expect("Failed to execute request.");
+
+    assert_eq!(StatusCode::OK, narrowed.status());
+
+    let narrowed = narrowed
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    let widened = refresh(
+        narrowed["refresh_token"]
+            .as_str()
+            .expect("Token response should carry a refresh token.")
+            .to_owned(),
+        json!([{ "type": "payment_initiation", "amount": "20.00" }]),
+    )
+    .await
+    .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(
+        narrowed["authorization_details"],
+        json!([{ "type": "payment_initiation", "amount": "10.00" }])
+    );
+    assert_eq!(StatusCode::BAD_REQUEST, widened.status());
+}
+
+#[tokio::test]
+async fn authorize_prompts_for_consent_with_the_requested_authorization_details() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_payment_type(&app, &client).await;
+    let (_, auth_info) = TestUser::generate_logged_in(&app).await;
+    let authorization_details = json!([{ "type": "payment_initiation", "amount": "10.00" }]);
+
+    // Act
+    let response = authorize(&app, &auth_info, &client, &authorization_details).await;
+
+    // Assert
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert_eq!(body["error"], "consent_required");
+    assert_eq!(body["client_id"], client.get_id());
+    assert_eq!(body["scopes"], json!(["read"]));
+    assert_eq!(body["authorization_details"], authorization_details);
+}
+
+#[tokio::test]
+async fn authorize_prompts_again_for_authorization_details_the_user_has_not_approved() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_payment_type(&app, &client).await;
+    let (user, auth_info) = TestUser::generate_logged_in(&app).await;
+    grant_consent(
+        &app,
+        &user,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": "10.00" }]),
+    )
+    .await;
+
+    // Act
+    let approved = authorize(
+        &app,
+        &auth_info,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": "10.00" }]),
+    )
+    .await;
+    let unapproved = authorize(
+        &app,
+        &auth_info,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": "5000.00" }]),
+    )
+    .await;
+
+    // Assert
+    assert!(approved.status().is_redirection());
+    assert_eq!(StatusCode::FORBIDDEN, unapproved.status());
+}
+
+#[tokio::test]
+async fn grant_consent_returns_a_400_for_authorization_details_that_do_not_match_the_schema() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_payment_type(&app, &client).await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+
+    // Act
+    let response = grant_consent(
+        &app,
+        &user,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": 10 }]),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn grant_consent_keeps_the_authorization_details_approved_before() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_payment_type(&app, &client).await;
+    let (user, _) = TestUser::generate_logged_in(&app).await;
+    grant_consent(
+        &app,
+        &user,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": "10.00" }]),
+    )
+    .await;
+
+    // Act
+    let response = grant_consent(
+        &app,
+        &user,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": "20.00" }]),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert_eq!(
+        body["authorization_details"],
+        json!([
+            { "type": "payment_initiation", "amount": "10.00" },
+            { "type": "payment_initiation", "amount": "20.00" },
+        ])
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_authorize_prompts_again_for_authorization_details_the_user_has_not_approved() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_payment_type(&app, &client).await;
+    let (user, auth_info) = TestUser::generate_logged_in(&app).await;
+    grant_consent(
+        &app,
+        &user,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": "10.00" }]),
+    )
+    .await;
+
+    // Act
+    let approved = authorize(
+        &app,
+        &auth_info,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": "10.00" }]),
+    )
+    .await;
+    let unapproved = authorize(
+        &app,
+        &auth_info,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": "5000.00" }]),
+    )
+    .await;
+
+    // Assert
+    assert!(approved.status().is_redirection());
+    assert_eq!(StatusCode::FORBIDDEN, unapproved.status());
+}
+
+#[tokio::test]
+async fn in_memory_code_can_be_read_back_by_its_plaintext() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+    let created = create_code(&app, &client, &user, "CODE", Duration::minutes(1)).await;
+
+    // Act
+    let state = app.get_state();
+    let code = state
+        .repository_container
+        .authorization_code_repository
+        .get_by_code(&state.db_context, "CODE")
+        .await
+        .expect("Failed to read back authorization code.");
+
+    // Assert
+    assert_eq!(created, code);
+    assert_eq!("CODE", code.code);
+}
+
+#[tokio::test]
+async fn in_memory_code_can_only_be_used_once() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+    create_code(&app, &client, &user, "CODE", Duration::minutes(1)).await;
+
+    let state = app.get_state();
+    let authorization_code_repository = &state.repository_container.authorization_code_repository;
+
+    // Act
+    let first_use = authorization_code_repository
+        .use_by_code(&state.db_context, "CODE")
+        .await;
+    let second_use = authorization_code_repository
+        .use_by_code(&state.db_context, "CODE")
+        .await;
+    let read_after_use = authorization_code_repository
+        .get_by_code(&state.db_context, "CODE")
+        .await;
+
+    // Assert
+    assert!(first_use.is_ok());
+    assert!(matches!(
+        second_use,
+        Err(RepositoryError::QueryFailed(QueryFailure::NotUpdated))
+    ));
+    assert!(matches!(
+        read_after_use,
+        Err(RepositoryError::QueryFailed(QueryFailure::NotFound))
+    ));
+}
+
+#[tokio::test]
+async fn in_memory_expired_code_cannot_be_used() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+    create_code(&app, &client, &user, "CODE", Duration::minutes(-1)).await;
+
+    // Act
+    let state = app.get_state();
+    let response = state
+        .repository_container
+        .authorization_code_repository
+        .use_by_code(&state.db_context, "CODE")
+        .await;
+
+    // Assert
+    assert!(matches!(
+        response,
+        Err(RepositoryError::QueryFailed(QueryFailure::NotUpdated))
+    ));
+}
+
+#[tokio::test]
+async fn in_memory_code_can_be_deleted_by_id() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+    let created = create_code(&app, &client, &user, "CODE", Duration::minutes(1)).await;
+
+    let state = app.get_state();
+    let authorization_code_repository = &state.repository_container.authorization_code_repository;
+
+    // Act
+    let deleted = authorization_code_repository
+        .delete_by_id(&state.db_context, created.id)
+        .await
+        .expect("Failed to delete authorization code.");
+    let read_after_delete = authorization_code_repository
+        .get_by_id(&state.db_context, created.id)
+        .await;
+
+    // Assert
+    assert_eq!(created.id, deleted.id);
+    assert!(read_after_delete.is_err());
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_code_can_only_be_used_once() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+    let created = create_code(&app, &client, &user, "CODE", Duration::minutes(1)).await;
+
+    let state = app.get_state();
+    let authorization_code_repository = &state.repository_container.authorization_code_repository;
+
+    // Act
+    let read = authorization_code_repository
+        .get_by_code(&state.db_context, "CODE")
+        .await
+        .expect("Failed to read back authorization code.");
+    let first_use = authorization_code_repository
+        .use_by_code(&state.db_context, "CODE")
+        .await;
+    let second_use = authorization_code_repository
+        .use_by_code(&state.db_context, "CODE")
+        .await;
+
+    // Assert
+    assert_eq!(created, read);
+    assert!(first_use.is_ok());
+    assert!(matches!(
+        second_use,
+        Err(RepositoryError::QueryFailed(QueryFailure::NotUpdated))
+    ));
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_code_can_be_deleted_by_id() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+    let created = create_code(&app, &client, &user, "CODE", Duration::minutes(1)).await;
+
+    let state = app.get_state();
+    let authorization_code_repository = &state.repository_container.authorization_code_repository;
+
+    // Act
+    let deleted = authorization_code_repository
+        .delete_by_id(&state.db_context, created.id)
+        .await
+        .expect("Failed to delete authorization code.");
+    let read_after_delete = authorization_code_repository
+        .get_by_id(&state.db_context, created.id)
+        .await;
+
+    // Assert
+    assert_eq!(created.id, deleted.id);
+    assert!(read_after_delete.is_err());
+}
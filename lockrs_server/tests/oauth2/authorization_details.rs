@@ -0,0 +1,250 @@
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+async fn register_type(app: &TestApp, client: &TestClient, name: &str) -> reqwest::Response {
+    app.get_client()
+        .post(&format!(
+            "{}/api/v1/clients/{}/authorization_details",
+            &app.get_address(),
+            client.get_id()
+        ))
+        .json(&json!({
+            "name": name,
+            "description": "Initiate a payment from the user's account",
+            "schema": {
+                "required": ["amount"],
+                "properties": { "amount": "string", "currency": "string" },
+            },
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn request_token(
+    app: &TestApp,
+    client: &TestClient,
+    authorization_details: &Value,
+) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/oauth2/v1/token", &app.get_address()))
+        .basic_auth(client.get_id(), Some(client.get_secret()))
+        .query(&[
+            ("grant_type", "client_credentials"),
+            ("scope", "read"),
+            (
+                "authorization_details",
+                authorization_details.to_string().as_str(),
+            ),
+        ])
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn register_type_returns_a_200_and_lists_the_type() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+
+    // Act
+    let response = register_type(&app, &client, "payment_initiation").await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = app
+        .get_client()
+        .get(&format!(
+            "{}/api/v1/clients/{}/authorization_details",
+            &app.get_address(),
+            client.get_id()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    let detail_types = body["authorization_detail_types"]
+        .as_array()
+        .expect("Authorization detail type list should be an array.");
+
+    assert_eq!(1, detail_types.len());
+    assert_eq!(detail_types[0]["name"], "payment_initiation");
+    assert_eq!(detail_types[0]["schema"]["required"], json!(["amount"]));
+}
+
+#[tokio::test]
+async fn register_type_returns_a_400_for_a_name_the_client_already_registered() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_type(&app, &client, "payment_initiation").await;
+
+    // Act
+    let response = register_type(&app, &client, "payment_initiation").await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn register_type_allows_two_clients_the_same_name() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let first_client = TestClient::generate_stored(&app, &owner).await;
+    let second_client = TestClient::generate_stored(&app, &owner).await;
+    register_type(&app, &first_client, "payment_initiation").await;
+
+    // Act
+    let response = register_type(&app, &second_client, "payment_initiation").await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+#[tokio::test]
+async fn token_returns_the_authorization_details_that_match_the_schema() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_type(&app, &client, "payment_initiation").await;
+    let authorization_details = json!([{ "type": "payment_initiation", "amount": "10.00" }]);
+
+    // Act
+    let response = request_token(&app, &client, &authorization_details).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert_eq!(body["authorization_details"], authorization_details);
+}
+
+#[tokio::test]
+async fn token_returns_a_400_for_authorization_details_that_do_not_match_the_schema() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_type(&app, &client, "payment_initiation").await;
+
+    let invalid_details = vec![
+        // missing a required field
+        json!([{ "type": "payment_initiation", "currency": "EUR" }]),
+        // a field of the wrong type
+        json!([{ "type": "payment_initiation", "amount": 10 }]),
+        // a field the schema does not define
+        json!([{ "type": "payment_initiation", "amount": "10.00", "creditor": "ACME" }]),
+        // a type that was never registered
+        json!([{ "type": "account_information" }]),
+    ];
+
+    for authorization_details in invalid_details {
+        // Act
+        let response = request_token(&app, &client, &authorization_details).await;
+
+        // Assert
+        assert_eq!(
+            StatusCode::BAD_REQUEST,
+            response.status(),
+            "The API did not fail with 400 Bad Request when the authorization_details were {}.",
+            authorization_details
+        );
+    }
+}
+
+#[tokio::test]
+async fn token_returns_a_400_for_a_type_registered_by_another_client() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let other_client = TestClient::generate_stored(&app, &owner).await;
+    register_type(&app, &other_client, "payment_initiation").await;
+
+    // Act
+    let response = request_token(
+        &app,
+        &client,
+        &json!([{ "type": "payment_initiation", "amount": "10.00" }]),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn introspect_returns_the_authorization_details_of_the_token() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    register_type(&app, &client, "payment_initiation").await;
+    let authorization_details = json!([{ "type": "payment_initiation", "amount": "10.00" }]);
+
+    let token = request_token(&app, &client, &authorization_details)
+        .await
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    // Act
+    let response = app
+        .get_client()
+        .post(&format!("{}/oauth2/v1/introspect", &app.get_address()))
+        .basic_auth(client.get_id(), Some(client.get_secret()))
+        .query(&[(
+            "token",
+            token["access_token"]
+                .as_str()
+                .expect("Token response should carry an access token."),
+        )])
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert_eq!(body["active"], true);
+    assert_eq!(body["authorization_details"], authorization_details);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_register_type_allows_two_clients_the_same_name() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let first_client = TestClient::generate_stored(&app, &owner).await;
+    let second_client = TestClient::generate_stored(&app, &owner).await;
+    register_type(&app, &first_client, "payment_initiation").await;
+
+    // Act
+    let response = register_type(&app, &second_client, "payment_initiation").await;
+    let reused = register_type(&app, &second_client, "payment_initiation").await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(StatusCode::BAD_REQUEST, reused.status());
+}
@@ -0,0 +1,257 @@
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+async fn request_token(
+    app: &TestApp,
+    client: &TestClient,
+    scope: Option<&str>,
+) -> reqwest::Response {
+    let mut query = vec![("grant_type", "client_credentials")];
+    if let Some(scope) = scope {
+        query.push(("scope", scope));
+    }
+
+    app.get_client()
+        .post(&format!("{}/oauth2/v1/token", &app.get_address()))
+        .basic_auth(client.get_id(), Some(client.get_secret()))
+        .query(&query)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn set_allowed_scopes(app: &TestApp, client: &TestClient, body: &Value) -> reqwest::Response {
+    app.get_client()
+        .put(&format!(
+            "{}/api/v1/clients/{}/allowed_scopes",
+            &app.get_address(),
+            client.get_id()
+        ))
+        .json(body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn client_credentials_returns_a_400_for_an_unknown_scope() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+
+    // Act
+    let response = request_token(&app, &client, Some("read unknown")).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn client_credentials_returns_a_400_for_a_scope_outside_the_allowlist() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    set_allowed_scopes(&app, &client, &json!({ "allowed_scopes": ["read"] })).await;
+
+    // Act
+    let response = request_token(&app, &client, Some("read write")).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn client_credentials_applies_default_scopes_when_scope_is_omitted() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    set_allowed_scopes(
+        &app,
+        &client,
+        &json!({ "allowed_scopes": ["read", "write"], "default_scopes": ["read"] }),
+    )
+    .await;
+
+    // Act
+    let response = request_token(&app, &client, None).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert_eq!(body["scopes"], "read");
+}
+
+#[tokio::test]
+async fn allowed_scopes_returns_a_400_for_another_clients_scope() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    // Act
+    let response = set_allowed_scopes(
+        &app,
+        &client,
+        &json!({ "allowed_scopes": ["read", "OTHER_CLIENT:photos.read"] }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn client_credentials_never_issues_a_refresh_token() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    app.get_client()
+        .put(&format!(
+            "{}/api/v1/clients/{}/policy",
+            &app.get_address(),
+            client.get_id()
+        ))
+        .json(&json!({
+            "grant_types": ["client_credentials", "refresh_token"],
+            "access_token_lifetime": 600,
+            "refresh_token_lifetime": 86400,
+            "id_token_lifetime": 3600,
+            "always_issue_refresh_token": true,
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Act
+    let response = request_token(&app, &client, Some("read offline_access")).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert!(body["access_token"].is_string());
+    assert!(body["refresh_token"].is_null());
+}
+
+#[tokio::test]
+async fn client_credentials_issues_a_token_with_in_memory_repositories() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    set_allowed_scopes(
+        &app,
+        &client,
+        &json!({ "allowed_scopes": ["read", "write"], "default_scopes": ["read"] }),
+    )
+    .await;
+
+    // Act
+    let response = request_token(&app, &client, None).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert!(body["access_token"].is_string());
+    assert_eq!(body["scopes"], "read");
+}
+
+#[tokio::test]
+async fn client_credentials_returns_a_401_for_an_unknown_client_with_in_memory_repositories() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let client = TestClient::generate();
+
+    // Act
+    let response = request_token(&app, &client, Some("read")).await;
+
+    // Assert
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn client_credentials_issues_a_token_with_sqlite_repositories() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    set_allowed_scopes(
+        &app,
+        &client,
+        &json!({ "allowed_scopes": ["read", "write"], "default_scopes": ["read"] }),
+    )
+    .await;
+
+    // Act
+    let response = request_token(&app, &client, None).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert!(body["access_token"].is_string());
+    assert_eq!(body["scopes"], "read");
+}
+
+#[tokio::test]
+async fn client_credentials_sees_allowed_scopes_updated_after_a_cached_lookup() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let owner = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    owner.login(&app).await;
+
+    set_allowed_scopes(&app, &client, &json!({ "allowed_scopes": ["read"] })).await;
+
+    let response = request_token(&app, &client, Some("write")).await;
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    set_allowed_scopes(
+        &app,
+        &client,
+        &json!({ "allowed_scopes": ["read", "write"] }),
+    )
+    .await;
+
+    // Act
+    let response = request_token(&app, &client, Some("write")).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+}
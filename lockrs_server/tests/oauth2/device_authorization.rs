@@ -0,0 +1,85 @@
+use hyper::StatusCode;
+use lockrs_server::oauth2::v1::services::DeviceAuthorizationService;
+use serde_json::Value;
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+async fn request_device_authorization(app: &TestApp, client: &TestClient) -> Value {
+    let response = app
+        .get_client()
+        .post(&format!(
+            "{}/oauth2/v1/device_authorization",
+            &app.get_address()
+        ))
+        .basic_auth(client.get_id(), Some(client.get_secret()))
+        .query(&[("scope", "read")])
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(StatusCode::OK, response.status());
+
+    response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.")
+}
+
+/// starts the device flow and looks the authorization up by the code the user enters on the
+/// verification page and by the code the device polls with, which have to find the same one
+async fn assert_device_flow_finds_the_authorization_by_each_code(app: &TestApp) {
+    // Arrange
+    let owner = TestUser::generate_stored(app).await;
+    let client = TestClient::generate_stored(app, &owner).await;
+
+    let state = app.get_state();
+    let device_authorization_repository =
+        &*state.repository_container.device_authorization_repository;
+
+    // Act
+    let body = request_device_authorization(app, &client).await;
+    let user_code = body["user_code"].as_str().expect("Missing user_code.");
+    let device_code = body["device_code"].as_str().expect("Missing device_code.");
+
+    let by_user_code = DeviceAuthorizationService::get_from_user_code(
+        &state.db_context,
+        device_authorization_repository,
+        user_code,
+    )
+    .await
+    .expect("Failed to find the device authorization by its user code.");
+    let by_device_code = DeviceAuthorizationService::get_from_device_code(
+        &state.db_context,
+        device_authorization_repository,
+        device_code,
+    )
+    .await
+    .expect("Failed to find the device authorization by its device code.");
+
+    // Assert
+    assert_eq!(by_user_code.id, by_device_code.id);
+    assert_eq!(client.get_id(), by_user_code.client_id);
+    assert_eq!(user_code, by_device_code.user_code);
+    assert_eq!(device_code, by_device_code.device_code);
+}
+
+#[tokio::test]
+async fn device_flow_finds_the_authorization_by_its_user_code_and_its_device_code() {
+    let app = TestApp::spawn().await;
+
+    assert_device_flow_finds_the_authorization_by_each_code(&app).await;
+}
+
+#[tokio::test]
+async fn in_memory_device_flow_finds_the_authorization_by_its_user_code_and_its_device_code() {
+    let app = TestApp::spawn_in_memory().await;
+
+    assert_device_flow_finds_the_authorization_by_each_code(&app).await;
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_device_flow_finds_the_authorization_by_its_user_code_and_its_device_code() {
+    let app = TestApp::spawn_sqlite().await;
+
+    assert_device_flow_finds_the_authorization_by_each_code(&app).await;
+}
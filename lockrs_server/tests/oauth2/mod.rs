@@ -0,0 +1,11 @@
+mod authorization_code;
+mod authorization_details;
+mod backchannel_authentication;
+mod client_credentials;
+mod device_authorization;
+mod pushed_authorization_request;
+mod repository_cache;
+mod token_digests;
+mod token_issuance;
+mod token_reaper;
+mod userinfo;
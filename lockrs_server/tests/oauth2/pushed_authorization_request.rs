@@ -0,0 +1,396 @@
+use chrono::{Duration, Utc};
+use hyper::StatusCode;
+use lockrs_server::{db::digest_token, oauth2::v1::models::AuthorizationRequestModel};
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::common::helpers::{TestApp, TestClient, TestUser, TestUserAuthInfo};
+
+const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+async fn push(app: &TestApp, client: &TestClient) -> reqwest::Response {
+    app.get_client()
+        .post(&format!("{}/oauth2/v1/par", &app.get_address()))
+        .basic_auth(client.get_id(), Some(client.get_secret()))
+        .query(&[
+            ("response_type", "code"),
+            ("redirect_uri", client.get_redirect_url().as_str()),
+            ("code_challenge", digest_token(CODE_VERIFIER).as_str()),
+            ("code_challenge_method", "S256"),
+            ("scope", "read"),
+            ("state", "STATE"),
+        ])
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn push_request_uri(app: &TestApp, client: &TestClient) -> String {
+    let response = push(app, client).await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.")["request_uri"]
+        .as_str()
+        .expect("Pushed authorization response should carry a request_uri.")
+        .to_owned()
+}
+
+async fn grant_consent(app: &TestApp, user: &TestUser, client: &TestClient) {
+    let response = app
+        .get_client()
+        .post(&format!(
+            "{}/api/v1/users/{}/consents",
+            &app.get_address(),
+            user.get_id()
+        ))
+        .json(&json!({
+            "client_id": client.get_id(),
+            "scope": "read",
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+/// sends the user's browser to `/authorize` with nothing but the client id and the request_uri
+async fn authorize(
+    app: &TestApp,
+    auth_info: &TestUserAuthInfo,
+    client_id: &str,
+    request_uri: &str,
+) -> reqwest::Response {
+    let browser = reqwest::ClientBuilder::new()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("Failed to build http client.");
+
+    let auth_cookie = auth_info
+        .get_auth_cookie()
+        .expect("Logged in user should have an auth cookie.");
+
+    browser
+        .post(&format!("{}/oauth2/v1/authorize", &app.get_address()))
+        .header(reqwest::header::COOKIE, auth_cookie.to_string())
+        .query(&[("client_id", client_id), ("request_uri", request_uri)])
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// logs a user in who has already consented to the client
+async fn generate_consenting_user(app: &TestApp, client: &TestClient) -> TestUserAuthInfo {
+    let (user, auth_info) = TestUser::generate_logged_in(app).await;
+    grant_consent(app, &user, client).await;
+
+    auth_info
+}
+
+#[tokio::test]
+async fn push_returns_a_201_with_a_request_uri() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+
+    // Act
+    let response = push(&app, &client).await;
+
+    // Assert
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    let body = response
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert!(body["request_uri"]
+        .as_str()
+        .expect("Pushed authorization response should carry a request_uri.")
+        .starts_with("urn:ietf:params:oauth:request_uri:"));
+    assert_eq!(body["expires_in"], 60);
+}
+
+#[tokio::test]
+async fn push_returns_a_400_for_an_invalid_request() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let code_challenge = digest_token(CODE_VERIFIER);
+    let redirect_uri = client.get_redirect_url().to_string();
+
+    let invalid_requests = vec![
+        (
+            vec![
+                ("redirect_uri", redirect_uri.as_str()),
+                ("code_challenge", code_challenge.as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+            "missing the response type",
+        ),
+        (
+            vec![
+                ("response_type", "code"),
+                ("code_challenge", code_challenge.as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+            "missing the redirect uri",
+        ),
+        (
+            vec![
+                ("response_type", "code"),
+                ("redirect_uri", redirect_uri.as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+            "missing the code challenge",
+        ),
+        (
+            vec![
+                ("response_type", "code"),
+                ("redirect_uri", redirect_uri.as_str()),
+                ("code_challenge", code_challenge.as_str()),
+                ("code_challenge_method", "S256"),
+                ("request_uri", "urn:ietf:params:oauth:request_uri:abc"),
+            ],
+            "carrying a request uri",
+        ),
+    ];
+
+    for (query, error_message) in invalid_requests {
+        // Act
+        let response = app
+            .get_client()
+            .post(&format!("{}/oauth2/v1/par", &app.get_address()))
+            .basic_auth(client.get_id(), Some(client.get_secret()))
+            .query(&query)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        // Assert
+        assert_eq!(
+            StatusCode::BAD_REQUEST,
+            response.status(),
+            "The API did not fail with 400 Bad Request when the payload was {}.",
+            error_message
+        );
+    }
+}
+
+#[tokio::test]
+async fn push_returns_a_400_for_an_unauthenticated_client() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+
+    // Act
+    let response = app
+        .get_client()
+        .post(&format!("{}/oauth2/v1/par", &app.get_address()))
+        .basic_auth(client.get_id(), Some("not-the-secret"))
+        .query(&[
+            ("response_type", "code"),
+            ("redirect_uri", client.get_redirect_url().as_str()),
+            ("code_challenge", digest_token(CODE_VERIFIER).as_str()),
+            ("code_challenge_method", "S256"),
+        ])
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn authorize_with_a_pushed_request_issues_a_code_for_it() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let auth_info = generate_consenting_user(&app, &client).await;
+    let request_uri = push_request_uri(&app, &client).await;
+
+    // Act
+    let response = authorize(&app, &auth_info, client.get_id(), &request_uri).await;
+
+    // Assert
+    assert!(response.status().is_redirection());
+
+    let location = response
+        .headers()
+        .get(reqwest::header::LOCATION)
+        .and_then(|location| location.to_str().ok())
+        .and_then(|location| Url::parse(location).ok())
+        .expect("Authorize should redirect back to the client.");
+    let query_value = |name: &str| {
+        location
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    assert_eq!(Some(String::from("STATE")), query_value("state"));
+
+    let code = query_value("code").expect("Redirect should carry a code.");
+    let token = app
+        .get_client()
+        .post(&format!("{}/oauth2/v1/token", &app.get_address()))
+        .basic_auth(client.get_id(), Some(client.get_secret()))
+        .query(&[
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("code_verifier", CODE_VERIFIER),
+            ("redirect_uri", client.get_redirect_url().as_str()),
+        ])
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(StatusCode::OK, token.status());
+}
+
+#[tokio::test]
+async fn authorize_with_a_pushed_request_can_resume_after_the_consent_prompt() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let (user, auth_info) = TestUser::generate_logged_in(&app).await;
+    let request_uri = push_request_uri(&app, &client).await;
+
+    // Act
+    let prompt = authorize(&app, &auth_info, client.get_id(), &request_uri).await;
+    grant_consent(&app, &user, &client).await;
+    let resumed = authorize(&app, &auth_info, client.get_id(), &request_uri).await;
+
+    // Assert
+    assert_eq!(StatusCode::FORBIDDEN, prompt.status());
+
+    let body = prompt
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+
+    assert_eq!(body["error"], "consent_required");
+    assert_eq!(body["scopes"], json!(["read"]));
+    assert_eq!(body["request_uri"], request_uri.as_str());
+    assert!(resumed.status().is_redirection());
+}
+
+#[tokio::test]
+async fn authorize_returns_a_400_for_a_request_uri_used_twice() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let auth_info = generate_consenting_user(&app, &client).await;
+    let request_uri = push_request_uri(&app, &client).await;
+    authorize(&app, &auth_info, client.get_id(), &request_uri).await;
+
+    // Act
+    let response = authorize(&app, &auth_info, client.get_id(), &request_uri).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn authorize_returns_a_400_for_a_request_uri_pushed_by_another_client() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let other_client = TestClient::generate_stored(&app, &owner).await;
+    let auth_info = generate_consenting_user(&app, &other_client).await;
+    let request_uri = push_request_uri(&app, &client).await;
+
+    // Act
+    let response = authorize(&app, &auth_info, other_client.get_id(), &request_uri).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn authorize_returns_a_400_for_an_expired_request_uri() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let auth_info = generate_consenting_user(&app, &client).await;
+    let request_uri = "urn:ietf:params:oauth:request_uri:expired";
+
+    let state = app.get_state();
+    state
+        .repository_container
+        .pushed_authorization_request_repository
+        .create(
+            &state.db_context,
+            request_uri,
+            &AuthorizationRequestModel::new(
+                client.get_id(),
+                client.get_redirect_url(),
+                digest_token(CODE_VERIFIER).as_str(),
+                false,
+                &[String::from("read")],
+                &[],
+                None,
+            ),
+            (Utc::now() - Duration::seconds(1)).timestamp_millis(),
+        )
+        .await
+        .expect("Failed to create pushed authorization request.");
+
+    // Act
+    let response = authorize(&app, &auth_info, client.get_id(), request_uri).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn authorize_returns_a_400_for_an_unknown_request_uri() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let auth_info = generate_consenting_user(&app, &client).await;
+
+    // Act
+    let response = authorize(
+        &app,
+        &auth_info,
+        client.get_id(),
+        "urn:ietf:params:oauth:request_uri:unknown",
+    )
+    .await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_authorize_with_a_pushed_request_issues_a_single_code() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let (owner, _) = TestUser::generate_logged_in(&app).await;
+    let client = TestClient::generate_stored(&app, &owner).await;
+    let auth_info = generate_consenting_user(&app, &client).await;
+    let request_uri = push_request_uri(&app, &client).await;
+
+    // Act
+    let response = authorize(&app, &auth_info, client.get_id(), &request_uri).await;
+    let reused = authorize(&app, &auth_info, client.get_id(), &request_uri).await;
+
+    // Assert
+    assert!(response.status().is_redirection());
+    assert_eq!(StatusCode::BAD_REQUEST, reused.status());
+}
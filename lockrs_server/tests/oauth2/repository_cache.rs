@@ -0,0 +1,231 @@
+use std::{sync::Arc, time::Duration};
+
+use lockrs_server::db::{
+    cache::RepositoryCache,
+    repositories::{QueryFailure, RepositoryError},
+    DbContext,
+};
+use scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+const REDIS_URL: &str = "redis://localhost:6379";
+
+/// a group of its own, so tests sharing a redis never see each other's entries
+fn unique_group() -> String {
+    RepositoryCache::client_group(Uuid::new_v4().simple().to_string().as_str())
+}
+
+async fn get_or_load(
+    cache: &RepositoryCache,
+    db_context: &Arc<DbContext>,
+    group: &str,
+    loaded: &str,
+) -> String {
+    cache
+        .get_or_load(db_context, group, "value", || async {
+            Ok::<_, RepositoryError>(loaded.to_owned())
+        })
+        .await
+        .expect("Failed to load value.")
+}
+
+/// polls `cache` until it no longer serves what it had cached, as broadcasts arrive asynchronously
+async fn wait_for_invalidation(
+    cache: &RepositoryCache,
+    db_context: &Arc<DbContext>,
+    group: &str,
+) -> String {
+    for _ in 0..50 {
+        let value = get_or_load(cache, db_context, group, "new").await;
+        if value == "new" {
+            return value;
+        }
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    panic!("The invalidation of {} was never received.", group);
+}
+
+fn redis_db_context() -> Arc<DbContext> {
+    Arc::new(DbContext::default().with_redis_pool(REDIS_URL, 4))
+}
+
+#[cfg(feature = "sqlite")]
+fn sqlite_db_context() -> Arc<DbContext> {
+    let sqlite_database =
+        std::env::temp_dir().join(format!("lockrs_test_{}.db", Uuid::new_v4().as_simple()));
+
+    Arc::new(DbContext::default().with_sqlite_pool(&sqlite_database.to_string_lossy(), 2))
+}
+
+#[tokio::test]
+async fn invalidate_all_drops_every_group() {
+    // Arrange
+    let db_context = Arc::new(DbContext::in_memory());
+    let cache = Arc::new(RepositoryCache::new(10, Duration::from_secs(60)));
+    let (first_group, second_group) = (unique_group(), unique_group());
+    get_or_load(&cache, &db_context, &first_group, "old").await;
+    get_or_load(&cache, &db_context, &second_group, "old").await;
+
+    // Act
+    cache.invalidate_all(&db_context).await;
+
+    // Assert
+    assert_eq!(
+        "new",
+        get_or_load(&cache, &db_context, &first_group, "new").await
+    );
+    assert_eq!(
+        "new",
+        get_or_load(&cache, &db_context, &second_group, "new").await
+    );
+}
+
+#[tokio::test]
+async fn redis_tier_serves_values_cached_by_another_instance() {
+    // Arrange
+    let db_context = redis_db_context();
+    let first_instance = RepositoryCache::new(10, Duration::from_secs(60)).with_redis();
+    let second_instance = RepositoryCache::new(10, Duration::from_secs(60)).with_redis();
+    let group = unique_group();
+    get_or_load(&first_instance, &db_context, &group, "cached").await;
+
+    // Act
+    let value = get_or_load(&second_instance, &db_context, &group, "loaded").await;
+
+    // Assert
+    assert_eq!("cached", value);
+}
+
+#[tokio::test]
+async fn redis_tier_is_cleared_by_an_invalidation() {
+    // Arrange
+    let db_context = redis_db_context();
+    let first_instance = Arc::new(RepositoryCache::new(10, Duration::from_secs(60)).with_redis());
+    let second_instance = RepositoryCache::new(10, Duration::from_secs(60)).with_redis();
+    let group = unique_group();
+    get_or_load(&first_instance, &db_context, &group, "old").await;
+
+    // Act
+    first_instance.invalidate(&db_context, &group).await;
+    let value = get_or_load(&second_instance, &db_context, &group, "new").await;
+
+    // Assert
+    assert_eq!("new", value);
+}
+
+#[tokio::test]
+async fn invalidation_is_broadcast_to_every_subscribed_instance() {
+    // Arrange
+    let db_context = redis_db_context();
+    let first_instance = Arc::new(RepositoryCache::new(10, Duration::from_secs(60)));
+    let second_instance = Arc::new(RepositoryCache::new(10, Duration::from_secs(60)));
+    let subscription = second_instance.subscribe(REDIS_URL);
+    // the subscription drops everything cached locally once it takes effect
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let group = unique_group();
+    get_or_load(&first_instance, &db_context, &group, "old").await;
+    get_or_load(&second_instance, &db_context, &group, "old").await;
+
+    // Act
+    first_instance.invalidate(&db_context, &group).await;
+
+    // Assert
+    assert_eq!(
+        "new",
+        wait_for_invalidation(&second_instance, &db_context, &group).await
+    );
+
+    subscription.abort();
+}
+
+#[tokio::test]
+async fn invalidate_all_is_broadcast_to_every_subscribed_instance() {
+    // Arrange
+    let db_context = redis_db_context();
+    let first_instance = Arc::new(RepositoryCache::new(10, Duration::from_secs(60)).with_redis());
+    let second_instance = Arc::new(RepositoryCache::new(10, Duration::from_secs(60)).with_redis());
+    let subscription = second_instance.subscribe(REDIS_URL);
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let (first_group, second_group) = (unique_group(), unique_group());
+    get_or_load(&second_instance, &db_context, &first_group, "old").await;
+    get_or_load(&second_instance, &db_context, &second_group, "old").await;
+
+    // Act
+    first_instance.invalidate_all(&db_context).await;
+
+    // Assert
+    assert_eq!(
+        "new",
+        wait_for_invalidation(&second_instance, &db_context, &first_group).await
+    );
+    assert_eq!(
+        "new",
+        get_or_load(&second_instance, &db_context, &second_group, "new").await
+    );
+
+    subscription.abort();
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_invalidation_inside_a_unit_of_work_waits_for_it_to_commit() {
+    // Arrange
+    let db_context = sqlite_db_context();
+    let cache = Arc::new(RepositoryCache::new(10, Duration::from_secs(60)));
+    let group = unique_group();
+    get_or_load(&cache, &db_context, &group, "old").await;
+
+    // Act
+    let during_unit_of_work = db_context
+        .transaction::<_, RepositoryError, _>(|unit_of_work| {
+            let (cache, db_context, group) = (cache.clone(), db_context.clone(), group.clone());
+
+            async move {
+                cache.invalidate(unit_of_work, &group).await;
+
+                // another request, outside the unit of work, still reads the row as committed
+                Ok(get_or_load(&cache, &db_context, &group, "old").await)
+            }
+            .scope_boxed()
+        })
+        .await
+        .expect("Failed to run the unit of work.");
+    let after_commit = get_or_load(&cache, &db_context, &group, "new").await;
+
+    // Assert
+    assert_eq!("old", during_unit_of_work);
+    assert_eq!("new", after_commit);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_invalidation_inside_a_unit_of_work_is_dropped_if_it_rolls_back() {
+    // Arrange
+    let db_context = sqlite_db_context();
+    let cache = Arc::new(RepositoryCache::new(10, Duration::from_secs(60)));
+    let group = unique_group();
+    get_or_load(&cache, &db_context, &group, "old").await;
+
+    // Act
+    let rolled_back = db_context
+        .transaction::<(), RepositoryError, _>(|unit_of_work| {
+            let (cache, group) = (cache.clone(), group.clone());
+
+            async move {
+                cache.invalidate(unit_of_work, &group).await;
+
+                Err(RepositoryError::QueryFailed(QueryFailure::NotUpdated))
+            }
+            .scope_boxed()
+        })
+        .await;
+    let after_rollback = get_or_load(&cache, &db_context, &group, "new").await;
+
+    // Assert
+    assert!(rolled_back.is_err());
+    assert_eq!("old", after_rollback);
+}
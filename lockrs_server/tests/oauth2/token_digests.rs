@@ -0,0 +1,418 @@
+use chrono::{Duration, Utc};
+use diesel::{sql_query, sql_types::Text, QueryableByName};
+use lockrs_server::{
+    db::digest_token,
+    oauth2::v1::models::{
+        AccessTokenCreateModel, AccessTokenModel, AuthorizationCodeCreateModel,
+        DeviceAuthorizationCreateModel, RefreshTokenCreateModel,
+    },
+};
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+const TOKEN: &str = "TOKEN";
+const USER_CODE: &str = "USER-CODE";
+
+#[derive(QueryableByName)]
+struct StoredValue {
+    #[diesel(sql_type = Text)]
+    value: String,
+}
+
+/// reads the raw value a pg repository persisted, bypassing the repository
+async fn read_pg_stored_value(app: &TestApp, query: String) -> String {
+    use diesel_async::RunQueryDsl;
+
+    let mut conn = app
+        .get_state()
+        .db_context
+        .get_pg_connection()
+        .await
+        .expect("Failed to get a pg connection.");
+
+    sql_query(query)
+        .get_result::<StoredValue>(&mut *conn)
+        .await
+        .expect("Failed to read the stored value.")
+        .value
+}
+
+/// reads the raw value a sqlite repository persisted, bypassing the repository
+#[cfg(feature = "sqlite")]
+async fn read_sqlite_stored_value(app: &TestApp, query: String) -> String {
+    use diesel::RunQueryDsl;
+
+    app.get_state()
+        .db_context
+        .with_sqlite_connection(move |conn| sql_query(query).get_result::<StoredValue>(conn))
+        .await
+        .expect("Failed to get a sqlite connection.")
+        .expect("Failed to read the stored value.")
+        .value
+}
+
+async fn create_access_token(
+    app: &TestApp,
+    client: &TestClient,
+    user: &TestUser,
+) -> AccessTokenModel {
+    let state = app.get_state();
+
+    let token_create = AccessTokenCreateModel::new(
+        TOKEN,
+        client.get_id(),
+        Some(user.get_id()),
+        &(Utc::now() + Duration::minutes(5)).naive_utc(),
+        &[String::from("read")],
+        &[],
+    );
+
+    state
+        .repository_container
+        .access_token_repository
+        .create(&state.db_context, &token_create)
+        .await
+        .expect("Failed to create access token.")
+}
+
+async fn create_refresh_token(app: &TestApp, client: &TestClient, user: &TestUser) -> i32 {
+    let state = app.get_state();
+    let access_token = create_access_token(app, client, user).await;
+
+    let token_create = RefreshTokenCreateModel::new(
+        TOKEN,
+        access_token.id,
+        client.get_id(),
+        Some(user.get_id()),
+        &(Utc::now() + Duration::minutes(5)).naive_utc(),
+        &[String::from("read")],
+        &[],
+        &Utc::now().naive_utc(),
+    );
+
+    state
+        .repository_container
+        .refresh_token_repository
+        .create(&state.db_context, &token_create)
+        .await
+        .expect("Failed to create refresh token.")
+        .id
+}
+
+async fn create_authorization_code(app: &TestApp, client: &TestClient, user: &TestUser) -> i32 {
+    let state = app.get_state();
+
+    let code_create = AuthorizationCodeCreateModel::new(
+        TOKEN,
+        client.get_id(),
+        user.get_id(),
+        "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM",
+        false,
+        client.get_redirect_url(),
+        &(Utc::now() + Duration::minutes(1)).naive_utc(),
+        &[String::from("read")],
+        &[],
+    );
+
+    state
+        .repository_container
+        .authorization_code_repository
+        .create(&state.db_context, &code_create)
+        .await
+        .expect("Failed to create authorization code.")
+        .id
+}
+
+async fn create_device_authorization(app: &TestApp, client: &TestClient) -> i32 {
+    let state = app.get_state();
+
+    let device_authorization_create = DeviceAuthorizationCreateModel::new(
+        client.get_id(),
+        USER_CODE,
+        TOKEN,
+        &(Utc::now() + Duration::minutes(5)).naive_utc(),
+        &[String::from("read")],
+    );
+
+    state
+        .repository_container
+        .device_authorization_repository
+        .create(&state.db_context, &device_authorization_create)
+        .await
+        .expect("Failed to create device authorization.")
+        .id
+}
+
+async fn assert_access_token_is_looked_up_by_plaintext(app: &TestApp) {
+    let state = app.get_state();
+
+    let read = state
+        .repository_container
+        .access_token_repository
+        .get_by_token(&state.db_context, TOKEN)
+        .await
+        .expect("Failed to read back access token by its plaintext.");
+    let read_by_digest = state
+        .repository_container
+        .access_token_repository
+        .get_by_token(&state.db_context, &digest_token(TOKEN))
+        .await;
+
+    assert_eq!(TOKEN, read.token);
+    assert!(read_by_digest.is_err());
+}
+
+async fn assert_refresh_token_is_looked_up_by_plaintext(app: &TestApp) {
+    let state = app.get_state();
+
+    let read = state
+        .repository_container
+        .refresh_token_repository
+        .get_by_token(&state.db_context, TOKEN)
+        .await
+        .expect("Failed to read back refresh token by its plaintext.");
+    let read_by_digest = state
+        .repository_container
+        .refresh_token_repository
+        .get_by_token(&state.db_context, &digest_token(TOKEN))
+        .await;
+
+    assert_eq!(TOKEN, read.token);
+    assert!(read_by_digest.is_err());
+}
+
+async fn assert_authorization_code_is_looked_up_by_plaintext(app: &TestApp) {
+    let state = app.get_state();
+
+    let read = state
+        .repository_container
+        .authorization_code_repository
+        .get_by_code(&state.db_context, TOKEN)
+        .await
+        .expect("Failed to read back authorization code by its plaintext.");
+    let read_by_digest = state
+        .repository_container
+        .authorization_code_repository
+        .get_by_code(&state.db_context, &digest_token(TOKEN))
+        .await;
+
+    assert_eq!(TOKEN, read.code);
+    assert!(read_by_digest.is_err());
+}
+
+async fn assert_device_authorization_is_looked_up_by_the_matching_code(app: &TestApp) {
+    let state = app.get_state();
+    let device_authorization_repository =
+        &state.repository_container.device_authorization_repository;
+
+    let read_by_user_code = device_authorization_repository
+        .get_by_user_code(&state.db_context, USER_CODE)
+        .await
+        .expect("Failed to read back device authorization by its user code.");
+    let read_by_device_code = device_authorization_repository
+        .get_by_device_code(&state.db_context, TOKEN)
+        .await
+        .expect("Failed to read back device authorization by its device code.");
+    let user_code_as_device_code = device_authorization_repository
+        .get_by_device_code(&state.db_context, USER_CODE)
+        .await;
+    let device_code_as_user_code = device_authorization_repository
+        .get_by_user_code(&state.db_context, TOKEN)
+        .await;
+
+    assert_eq!(read_by_user_code.id, read_by_device_code.id);
+    assert_eq!(USER_CODE, read_by_user_code.user_code);
+    assert_eq!(TOKEN, read_by_device_code.device_code);
+    assert!(user_code_as_device_code.is_err());
+    assert!(device_code_as_user_code.is_err());
+}
+
+#[tokio::test]
+async fn access_token_is_stored_as_its_digest_and_looked_up_by_plaintext() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+
+    // Act
+    let created = create_access_token(&app, &client, &user).await;
+    let stored = read_pg_stored_value(
+        &app,
+        format!(
+            "SELECT token AS value FROM access_tokens WHERE id = {}",
+            created.id
+        ),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(TOKEN, created.token);
+    assert_eq!(digest_token(TOKEN), stored);
+    assert_access_token_is_looked_up_by_plaintext(&app).await;
+}
+
+#[tokio::test]
+async fn refresh_token_is_stored_as_its_digest_and_looked_up_by_plaintext() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+
+    // Act
+    let id = create_refresh_token(&app, &client, &user).await;
+    let stored = read_pg_stored_value(
+        &app,
+        format!(
+            "SELECT token AS value FROM refresh_tokens WHERE id = {}",
+            id
+        ),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(digest_token(TOKEN), stored);
+    assert_refresh_token_is_looked_up_by_plaintext(&app).await;
+}
+
+#[tokio::test]
+async fn authorization_code_is_stored_as_its_digest_and_looked_up_by_plaintext() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+
+    // Act
+    let id = create_authorization_code(&app, &client, &user).await;
+    let stored = read_pg_stored_value(
+        &app,
+        format!(
+            "SELECT code AS value FROM authorization_codes WHERE id = {}",
+            id
+        ),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(digest_token(TOKEN), stored);
+    assert_authorization_code_is_looked_up_by_plaintext(&app).await;
+}
+
+#[tokio::test]
+async fn device_code_is_stored_as_its_digest_and_each_code_finds_its_own_column() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+
+    // Act
+    let id = create_device_authorization(&app, &client).await;
+    let stored = read_pg_stored_value(
+        &app,
+        format!(
+            "SELECT device_code AS value FROM device_authorizations WHERE id = {}",
+            id
+        ),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(digest_token(TOKEN), stored);
+    assert_device_authorization_is_looked_up_by_the_matching_code(&app).await;
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_access_token_is_stored_as_its_digest_and_looked_up_by_plaintext() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+
+    // Act
+    let created = create_access_token(&app, &client, &user).await;
+    let stored = read_sqlite_stored_value(
+        &app,
+        format!(
+            "SELECT token AS value FROM access_tokens WHERE id = {}",
+            created.id
+        ),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(TOKEN, created.token);
+    assert_eq!(digest_token(TOKEN), stored);
+    assert_access_token_is_looked_up_by_plaintext(&app).await;
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_refresh_token_is_stored_as_its_digest_and_looked_up_by_plaintext() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+
+    // Act
+    let id = create_refresh_token(&app, &client, &user).await;
+    let stored = read_sqlite_stored_value(
+        &app,
+        format!(
+            "SELECT token AS value FROM refresh_tokens WHERE id = {}",
+            id
+        ),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(digest_token(TOKEN), stored);
+    assert_refresh_token_is_looked_up_by_plaintext(&app).await;
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_authorization_code_is_stored_as_its_digest_and_looked_up_by_plaintext() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+
+    // Act
+    let id = create_authorization_code(&app, &client, &user).await;
+    let stored = read_sqlite_stored_value(
+        &app,
+        format!(
+            "SELECT code AS value FROM authorization_codes WHERE id = {}",
+            id
+        ),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(digest_token(TOKEN), stored);
+    assert_authorization_code_is_looked_up_by_plaintext(&app).await;
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_device_code_is_stored_as_its_digest_and_each_code_finds_its_own_column() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+
+    // Act
+    let id = create_device_authorization(&app, &client).await;
+    let stored = read_sqlite_stored_value(
+        &app,
+        format!(
+            "SELECT device_code AS value FROM device_authorizations WHERE id = {}",
+            id
+        ),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(digest_token(TOKEN), stored);
+    assert_device_authorization_is_looked_up_by_the_matching_code(&app).await;
+}
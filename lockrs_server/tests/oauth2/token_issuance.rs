@@ -0,0 +1,289 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::{sql_query, sql_types::BigInt, QueryableByName};
+use hyper::StatusCode;
+use lockrs_server::{
+    db::{
+        repositories::{QueryFailure, RefreshTokenRepository, RepositoryError},
+        DbContext,
+    },
+    models::{ClientPolicyModel, GrantType},
+    oauth2::v1::{
+        models::{AccessTokenCreateModel, RefreshTokenCreateModel, RefreshTokenModel, ScopeModel},
+        services::TokenService,
+    },
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+/// a refresh token repository that fails every call, standing in for a database that goes away
+/// between the access and refresh token inserts
+struct FailingRefreshTokenRepository;
+
+#[async_trait]
+impl RefreshTokenRepository for FailingRefreshTokenRepository {
+    async fn create(
+        &self,
+        _db_context: &Arc<DbContext>,
+        _token_create: &RefreshTokenCreateModel,
+    ) -> Result<RefreshTokenModel, RepositoryError> {
+        Err(RepositoryError::QueryFailed(QueryFailure::NotCreated))
+    }
+
+    async fn get_by_token(
+        &self,
+        _db_context: &Arc<DbContext>,
+        _token: &str,
+    ) -> Result<RefreshTokenModel, RepositoryError> {
+        Err(RepositoryError::QueryFailed(QueryFailure::NotFound))
+    }
+
+    async fn use_by_token(
+        &self,
+        _db_context: &Arc<DbContext>,
+        _token: &str,
+    ) -> Result<RefreshTokenModel, RepositoryError> {
+        Err(RepositoryError::QueryFailed(QueryFailure::NotUpdated))
+    }
+
+    async fn revoke_all_by_user_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        _user_id: &Uuid,
+    ) -> Result<usize, RepositoryError> {
+        Err(RepositoryError::QueryFailed(QueryFailure::NotUpdated))
+    }
+
+    async fn revoke_all_by_user_id_and_client_id(
+        &self,
+        _db_context: &Arc<DbContext>,
+        _user_id: &Uuid,
+        _client_id: &str,
+    ) -> Result<usize, RepositoryError> {
+        Err(RepositoryError::QueryFailed(QueryFailure::NotUpdated))
+    }
+
+    async fn delete_by_token(
+        &self,
+        _db_context: &Arc<DbContext>,
+        _token: &str,
+    ) -> Result<(), RepositoryError> {
+        Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted))
+    }
+
+    async fn delete_expired(
+        &self,
+        _db_context: &Arc<DbContext>,
+        _expired_before: &NaiveDateTime,
+        _limit: i64,
+    ) -> Result<usize, RepositoryError> {
+        Err(RepositoryError::QueryFailed(QueryFailure::NotDeleted))
+    }
+}
+
+#[derive(QueryableByName)]
+struct RowCount {
+    #[diesel(sql_type = BigInt)]
+    count: i64,
+}
+
+fn count_access_tokens_query(client: &TestClient) -> String {
+    format!(
+        "SELECT COUNT(*) AS count FROM access_tokens WHERE client_id = '{}'",
+        client.get_id()
+    )
+}
+
+async fn count_pg_access_tokens(app: &TestApp, client: &TestClient) -> i64 {
+    use diesel_async::RunQueryDsl;
+
+    let mut conn = app
+        .get_state()
+        .db_context
+        .get_pg_connection()
+        .await
+        .expect("Failed to get a pg connection.");
+
+    sql_query(count_access_tokens_query(client))
+        .get_result::<RowCount>(&mut *conn)
+        .await
+        .expect("Failed to count access tokens.")
+        .count
+}
+
+#[cfg(feature = "sqlite")]
+async fn count_sqlite_access_tokens(app: &TestApp, client: &TestClient) -> i64 {
+    use diesel::RunQueryDsl;
+
+    let query = count_access_tokens_query(client);
+
+    app.get_state()
+        .db_context
+        .with_sqlite_connection(move |conn| sql_query(query).get_result::<RowCount>(conn))
+        .await
+        .expect("Failed to get a sqlite connection.")
+        .expect("Failed to count access tokens.")
+        .count
+}
+
+/// issues a token pair with a refresh token whose insert always fails
+async fn issue_token_without_refresh_token_storage(
+    app: &TestApp,
+    client: &TestClient,
+    user: &TestUser,
+) -> bool {
+    let state = app.get_state();
+
+    let mut client_policy = ClientPolicyModel::default_for(client.get_id());
+    client_policy.always_issue_refresh_token = true;
+
+    TokenService::create_token(
+        &state.db_context,
+        &*state.repository_container.access_token_repository,
+        &FailingRefreshTokenRepository,
+        &client_policy,
+        GrantType::AuthorizationCode,
+        Some(user.get_id()),
+        ScopeModel::new(&[String::from("read")]),
+        &[],
+        None,
+    )
+    .await
+    .is_ok()
+}
+
+/// stores a refresh token for `read`, returning its plaintext
+async fn create_refresh_token(app: &TestApp, client: &TestClient, user: &TestUser) -> String {
+    let state = app.get_state();
+    let now = Utc::now().naive_utc();
+
+    let access_token_create = AccessTokenCreateModel::new(
+        "ACCESS_TOKEN",
+        client.get_id(),
+        Some(user.get_id()),
+        &(now + Duration::minutes(5)),
+        &[String::from("read")],
+        &[],
+    );
+
+    let access_token = state
+        .repository_container
+        .access_token_repository
+        .create(&state.db_context, &access_token_create)
+        .await
+        .expect("Failed to create access token.");
+
+    let refresh_token_create = RefreshTokenCreateModel::new(
+        "REFRESH_TOKEN",
+        access_token.id,
+        client.get_id(),
+        Some(user.get_id()),
+        &(now + Duration::hours(1)),
+        &[String::from("read")],
+        &[],
+        &now,
+    );
+
+    state
+        .repository_container
+        .refresh_token_repository
+        .create(&state.db_context, &refresh_token_create)
+        .await
+        .expect("Failed to create refresh token.")
+        .token
+}
+
+async fn refresh(
+    app: &TestApp,
+    client: &TestClient,
+    refresh_token: &str,
+    scope: Option<&str>,
+) -> reqwest::Response {
+    let mut query = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token),
+    ];
+
+    if let Some(scope) = scope {
+        query.push(("scope", scope));
+    }
+
+    app.get_client()
+        .post(&format!("{}/oauth2/v1/token", &app.get_address()))
+        .basic_auth(client.get_id(), Some(client.get_secret()))
+        .query(&query)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// fails a rotation part way through by asking for more than was granted, then retries it
+async fn assert_failed_rotation_leaves_the_refresh_token_usable(app: &TestApp) {
+    // Arrange
+    let user = TestUser::generate_stored(app).await;
+    let client = TestClient::generate_stored(app, &user).await;
+    let refresh_token = create_refresh_token(app, &client, &user).await;
+
+    // Act
+    let failed = refresh(app, &client, &refresh_token, Some("read write")).await;
+    let retried = refresh(app, &client, &refresh_token, None).await;
+
+    // Assert
+    assert_eq!(StatusCode::BAD_REQUEST, failed.status());
+    assert_eq!(StatusCode::OK, retried.status());
+
+    let body = retried
+        .json::<Value>()
+        .await
+        .expect("Failed to read request body.");
+    assert!(body["refresh_token"].is_string());
+    assert_ne!(body["refresh_token"], refresh_token.as_str());
+}
+
+#[tokio::test]
+async fn token_issuance_rolls_back_the_access_token_when_the_refresh_token_fails() {
+    // Arrange
+    let app = TestApp::spawn().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+
+    // Act
+    let issued = issue_token_without_refresh_token_storage(&app, &client, &user).await;
+
+    // Assert
+    assert!(!issued);
+    assert_eq!(0, count_pg_access_tokens(&app, &client).await);
+}
+
+#[tokio::test]
+async fn failed_rotation_leaves_the_old_refresh_token_usable() {
+    let app = TestApp::spawn().await;
+    assert_failed_rotation_leaves_the_refresh_token_usable(&app).await;
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_token_issuance_rolls_back_the_access_token_when_the_refresh_token_fails() {
+    // Arrange
+    let app = TestApp::spawn_sqlite().await;
+    let user = TestUser::generate_stored(&app).await;
+    let client = TestClient::generate_stored(&app, &user).await;
+
+    // Act
+    let issued = issue_token_without_refresh_token_storage(&app, &client, &user).await;
+
+    // Assert
+    assert!(!issued);
+    assert_eq!(0, count_sqlite_access_tokens(&app, &client).await);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_failed_rotation_leaves_the_old_refresh_token_usable() {
+    let app = TestApp::spawn_sqlite().await;
+    assert_failed_rotation_leaves_the_refresh_token_usable(&app).await;
+}
@@ -0,0 +1,237 @@
+use chrono::{Duration, Utc};
+use lockrs_server::oauth2::v1::{
+    models::{AccessTokenCreateModel, DeviceAuthorizationCreateModel, PurgeReportModel},
+    services::{ReaperService, ReaperServiceError},
+};
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+async fn create_access_token(
+    app: &TestApp,
+    client: &TestClient,
+    user: &TestUser,
+    token: &str,
+    expires_in: Duration,
+) {
+    let state = app.get_state();
+
+    let token_create = AccessTokenCreateModel::new(
+        token,
+        client.get_id(),
+        Some(user.get_id()),
+        &(Utc::now() + expires_in).naive_utc(),
+        &[String::from("read")],
+        &[],
+    );
+
+    state
+        .repository_container
+        .access_token_repository
+        .create(&state.db_context, &token_create)
+        .await
+        .expect("Failed to create access token.");
+}
+
+async fn create_device_authorization(app: &TestApp, client: &TestClient, expires_in: Duration) {
+    let state = app.get_state();
+
+    let device_authorization_create = DeviceAuthorizationCreateModel::new(
+        client.get_id(),
+        "USER-CODE",
+        "DEVICE_CODE",
+        &(Utc::now() + expires_in).naive_utc(),
+        &[String::from("read")],
+    );
+
+    state
+        .repository_container
+        .device_authorization_repository
+        .create(&state.db_context, &device_authorization_create)
+        .await
+        .expect("Failed to create device authorization.");
+}
+
+async fn purge_expired(
+    app: &TestApp,
+    batch_size: i64,
+    retention: Duration,
+) -> Result<PurgeReportModel, ReaperServiceError> {
+    let state = app.get_state();
+    let repository_container = state.repository_container.as_ref();
+
+    ReaperService::purge_expired(
+        &state.db_context,
+        &*repository_container.access_token_repository,
+        &*repository_container.refresh_token_repository,
+        &*repository_container.authorization_code_repository,
+        &*repository_container.device_authorization_repository,
+        batch_size,
+        &retention,
+    )
+    .await
+}
+
+async fn assert_expired_rows_are_deleted_in_bounded_batches(app: &TestApp) {
+    // Arrange
+    let user = TestUser::generate_stored(app).await;
+    let client = TestClient::generate_stored(app, &user).await;
+    for i in 0..5 {
+        create_access_token(
+            app,
+            &client,
+            &user,
+            &format!("TOKEN_{}", i),
+            -Duration::days(2),
+        )
+        .await;
+    }
+
+    let state = app.get_state();
+
+    // Act
+    let single_batch = state
+        .repository_container
+        .access_token_repository
+        .delete_expired(
+            &state.db_context,
+            &(Utc::now() - Duration::days(1)).naive_utc(),
+            2,
+        )
+        .await
+        .expect("Failed to delete a batch of expired access tokens.");
+    let first_run = purge_expired(app, 2, Duration::days(1))
+        .await
+        .expect("Failed to purge expired rows.");
+    let second_run = purge_expired(app, 2, Duration::days(1))
+        .await
+        .expect("Failed to purge expired rows.");
+
+    // Assert
+    assert_eq!(2, single_batch);
+    assert_eq!(3, first_run.access_tokens);
+    assert_eq!(3, first_run.total());
+    assert_eq!(PurgeReportModel::default(), second_run);
+}
+
+async fn assert_rows_within_the_retention_period_are_kept(app: &TestApp) {
+    // Arrange
+    let user = TestUser::generate_stored(app).await;
+    let client = TestClient::generate_stored(app, &user).await;
+    create_access_token(app, &client, &user, "TOKEN", -Duration::hours(1)).await;
+    create_device_authorization(app, &client, -Duration::hours(1)).await;
+
+    // Act
+    let retained = purge_expired(app, 10, Duration::days(1))
+        .await
+        .expect("Failed to purge expired rows.");
+    let purged = purge_expired(app, 10, Duration::minutes(30))
+        .await
+        .expect("Failed to purge expired rows.");
+
+    // Assert
+    assert_eq!(PurgeReportModel::default(), retained);
+    assert_eq!(1, purged.access_tokens);
+    assert_eq!(1, purged.device_authorizations);
+    assert_eq!(2, purged.total());
+}
+
+async fn assert_unexpired_rows_are_kept(app: &TestApp) {
+    // Arrange
+    let user = TestUser::generate_stored(app).await;
+    let client = TestClient::generate_stored(app, &user).await;
+    create_access_token(app, &client, &user, "EXPIRED", -Duration::minutes(1)).await;
+    create_access_token(app, &client, &user, "LIVE", Duration::minutes(5)).await;
+    create_device_authorization(app, &client, Duration::minutes(5)).await;
+
+    let state = app.get_state();
+
+    // Act
+    let report = purge_expired(app, 10, Duration::zero())
+        .await
+        .expect("Failed to purge expired rows.");
+    let live_token = state
+        .repository_container
+        .access_token_repository
+        .get_by_token(&state.db_context, "LIVE")
+        .await;
+    let live_device_authorization = state
+        .repository_container
+        .device_authorization_repository
+        .get_by_device_code(&state.db_context, "DEVICE_CODE")
+        .await;
+
+    // Assert
+    assert_eq!(1, report.access_tokens);
+    assert_eq!(1, report.total());
+    assert!(live_token.is_ok());
+    assert!(live_device_authorization.is_ok());
+}
+
+#[tokio::test]
+async fn purge_expired_deletes_expired_rows_in_bounded_batches() {
+    let app = TestApp::spawn().await;
+    assert_expired_rows_are_deleted_in_bounded_batches(&app).await;
+}
+
+#[tokio::test]
+async fn purge_expired_keeps_rows_within_the_retention_period() {
+    let app = TestApp::spawn().await;
+    assert_rows_within_the_retention_period_are_kept(&app).await;
+}
+
+#[tokio::test]
+async fn purge_expired_keeps_unexpired_rows() {
+    let app = TestApp::spawn().await;
+    assert_unexpired_rows_are_kept(&app).await;
+}
+
+#[tokio::test]
+async fn purge_expired_rejects_an_empty_batch() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+
+    // Act
+    let result = purge_expired(&app, 0, Duration::days(1)).await;
+
+    // Assert
+    assert!(matches!(result, Err(ReaperServiceError::InvalidBatchSize)));
+}
+
+#[tokio::test]
+async fn in_memory_purge_expired_deletes_expired_rows_in_bounded_batches() {
+    let app = TestApp::spawn_in_memory().await;
+    assert_expired_rows_are_deleted_in_bounded_batches(&app).await;
+}
+
+#[tokio::test]
+async fn in_memory_purge_expired_keeps_rows_within_the_retention_period() {
+    let app = TestApp::spawn_in_memory().await;
+    assert_rows_within_the_retention_period_are_kept(&app).await;
+}
+
+#[tokio::test]
+async fn in_memory_purge_expired_keeps_unexpired_rows() {
+    let app = TestApp::spawn_in_memory().await;
+    assert_unexpired_rows_are_kept(&app).await;
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_purge_expired_deletes_expired_rows_in_bounded_batches() {
+    let app = TestApp::spawn_sqlite().await;
+    assert_expired_rows_are_deleted_in_bounded_batches(&app).await;
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_purge_expired_keeps_rows_within_the_retention_period() {
+    let app = TestApp::spawn_sqlite().await;
+    assert_rows_within_the_retention_period_are_kept(&app).await;
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_purge_expired_keeps_unexpired_rows() {
+    let app = TestApp::spawn_sqlite().await;
+    assert_unexpired_rows_are_kept(&app).await;
+}
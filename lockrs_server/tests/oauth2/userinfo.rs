@@ -0,0 +1,115 @@
+use chrono::{Duration, Utc};
+use hyper::StatusCode;
+use lockrs_server::oauth2::v1::{models::AccessTokenCreateModel, responses::UserinfoResponse};
+use uuid::Uuid;
+
+use crate::common::helpers::{TestApp, TestClient, TestUser};
+
+/// stores an access token issued to a stored client, for `user_id` with `scopes`
+async fn store_access_token(app: &TestApp, user_id: Option<&Uuid>, scopes: &[&str]) -> String {
+    let owner = TestUser::generate_stored(app).await;
+    let client = TestClient::generate_stored(app, &owner).await;
+    let token = Uuid::new_v4().simple().to_string();
+
+    let token_create = AccessTokenCreateModel::new(
+        token.as_str(),
+        client.get_id(),
+        user_id,
+        &(Utc::now() + Duration::minutes(10)).naive_utc(),
+        &scopes
+            .iter()
+            .map(|scope| String::from(*scope))
+            .collect::<Vec<String>>(),
+        &[],
+    );
+
+    let state = app.get_state();
+    state
+        .repository_container
+        .access_token_repository
+        .create(&state.db_context, &token_create)
+        .await
+        .expect("Failed to store access token.");
+
+    token
+}
+
+async fn request_userinfo(app: &TestApp, token: &str) -> reqwest::Response {
+    app.get_client()
+        .get(&format!("{}/oauth2/v1/userinfo", &app.get_address()))
+        .bearer_auth(token)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn userinfo_returns_the_user_with_their_email_for_the_email_scope() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let token = store_access_token(&app, Some(user.get_id()), &["read", "email"]).await;
+
+    // Act
+    let response = request_userinfo(&app, token.as_str()).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let userinfo = response
+        .json::<UserinfoResponse>()
+        .await
+        .expect("Failed to read request body.");
+    assert_eq!(user.get_id(), &userinfo.sub);
+    assert_eq!(Some(user.get_email()), userinfo.email.as_deref());
+    assert_eq!(Some(false), userinfo.email_verified);
+}
+
+#[tokio::test]
+async fn userinfo_leaves_out_the_email_without_the_email_scope() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let user = TestUser::generate_stored(&app).await;
+    let token = store_access_token(&app, Some(user.get_id()), &["read"]).await;
+
+    // Act
+    let response = request_userinfo(&app, token.as_str()).await;
+
+    // Assert
+    assert_eq!(StatusCode::OK, response.status());
+
+    let userinfo = response
+        .json::<UserinfoResponse>()
+        .await
+        .expect("Failed to read request body.");
+    assert_eq!(user.get_id(), &userinfo.sub);
+    assert!(userinfo.email.is_none());
+}
+
+#[tokio::test]
+async fn userinfo_returns_a_401_for_an_unknown_token() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+
+    // Act
+    let response = request_userinfo(&app, "unknown").await;
+
+    // Assert
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    assert!(response
+        .headers()
+        .contains_key(reqwest::header::WWW_AUTHENTICATE));
+}
+
+#[tokio::test]
+async fn userinfo_returns_a_401_for_a_client_credentials_token() {
+    // Arrange
+    let app = TestApp::spawn_in_memory().await;
+    let token = store_access_token(&app, None, &["email"]).await;
+
+    // Act
+    let response = request_userinfo(&app, token.as_str()).await;
+
+    // Assert
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}